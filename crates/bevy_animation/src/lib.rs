@@ -13,6 +13,7 @@ pub mod animatable;
 pub mod animation_curves;
 pub mod gltf_curves;
 pub mod graph;
+pub mod root_motion;
 pub mod transition;
 mod util;
 
@@ -52,7 +53,7 @@ use uuid::Uuid;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        animatable::*, animation_curves::*, graph::*, transition::*, AnimationClip,
+        animatable::*, animation_curves::*, graph::*, root_motion::*, transition::*, AnimationClip,
         AnimationPlayer, AnimationPlugin, VariableCurve,
     };
 }
@@ -60,6 +61,7 @@ pub mod prelude {
 use crate::{
     animation_curves::AnimationCurve,
     graph::{AnimationGraph, AnimationGraphAssetLoader, AnimationNodeIndex},
+    root_motion::{extract_root_motion, RootMotion},
     transition::{advance_transitions, expire_completed_transitions, AnimationTransitions},
 };
 use alloc::sync::Arc;
@@ -1237,6 +1239,7 @@ impl Plugin for AnimationPlugin {
             .register_type::<AnimationPlayer>()
             .register_type::<AnimationTarget>()
             .register_type::<AnimationTransitions>()
+            .register_type::<RootMotion>()
             .register_type::<AnimationGraphHandle>()
             .register_type::<NodeIndex>()
             .register_type::<ThreadedAnimationGraphs>()
@@ -1256,6 +1259,7 @@ impl Plugin for AnimationPlugin {
                     animate_targets
                         .before(bevy_render::mesh::inherit_weights)
                         .ambiguous_with_all(),
+                    extract_root_motion,
                     trigger_untargeted_animation_events,
                     expire_completed_transitions,
                 )
@@ -0,0 +1,165 @@
+//! Root motion extraction.
+
+use bevy_ecs::{
+    change_detection::DetectChanges, component::Component, entity::Entity,
+    reflect::ReflectComponent, system::Query,
+};
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::Reflect;
+use bevy_transform::components::Transform;
+
+use crate::{AnimationPlayer, AnimationTarget, AnimationTargetId};
+
+/// Extracts the motion of a root bone from the animations of an [`AnimationPlayer`], so that a
+/// character controller can apply it, instead of the animation moving the bone away from the
+/// character and making its feet slide.
+///
+/// Place this component on the same entity as the [`AnimationPlayer`]. Each frame, after the
+/// bone is animated, the translation and rotation the animation moved it by are stored in
+/// [`delta_translation`](Self::delta_translation) and [`delta_rotation`](Self::delta_rotation),
+/// and removed from its [`Transform`], which stays at the pose it had when the extraction started.
+///
+/// The deltas are in the space of the parent of the bone. When a repeating animation wraps around,
+/// the bone jumps back to the start of the clip, so the deltas of the previous frame are reused.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Clone, Debug)]
+pub struct RootMotion {
+    /// The bone to extract the motion of.
+    pub bone: AnimationTargetId,
+    /// Whether the translation of the bone is extracted.
+    pub extract_translation: bool,
+    /// Whether the rotation of the bone is extracted.
+    pub extract_rotation: bool,
+    /// The translation of the bone during the last frame, if extracted.
+    pub delta_translation: Vec3,
+    /// The rotation of the bone during the last frame, if extracted.
+    pub delta_rotation: Quat,
+    /// The entity of the bone, found from its [`AnimationTarget`].
+    bone_entity: Option<Entity>,
+    /// The pose the bone is kept at.
+    reference: Option<(Vec3, Quat)>,
+    /// The pose the animation gave the bone on the previous frame.
+    previous: Option<(Vec3, Quat)>,
+}
+
+impl RootMotion {
+    /// Creates a [`RootMotion`] extracting both the translation and the rotation of the bone.
+    pub fn new(bone: AnimationTargetId) -> Self {
+        Self {
+            bone,
+            extract_translation: true,
+            extract_rotation: true,
+            delta_translation: Vec3::ZERO,
+            delta_rotation: Quat::IDENTITY,
+            bone_entity: None,
+            reference: None,
+            previous: None,
+        }
+    }
+}
+
+/// Moves the animated motion of [`RootMotion`] bones into their deltas.
+pub fn extract_root_motion(
+    mut players: Query<(Entity, &AnimationPlayer, &mut RootMotion)>,
+    mut bones: Query<(Entity, &AnimationTarget, &mut Transform)>,
+) {
+    for (player_entity, player, mut root_motion) in &mut players {
+        let root_motion = &mut *root_motion;
+        root_motion.delta_translation = Vec3::ZERO;
+        root_motion.delta_rotation = Quat::IDENTITY;
+
+        let is_bone = |target: &AnimationTarget| {
+            target.player == player_entity && target.id == root_motion.bone
+        };
+        let cached = root_motion.bone_entity.filter(|&entity| {
+            bones
+                .get(entity)
+                .is_ok_and(|(_, target, _)| is_bone(target))
+        });
+        let Some(bone_entity) = cached.or_else(|| {
+            bones
+                .iter()
+                .find(|(_, target, _)| is_bone(target))
+                .map(|(entity, _, _)| entity)
+        }) else {
+            continue;
+        };
+        root_motion.bone_entity = Some(bone_entity);
+        let Ok((_, _, mut transform)) = bones.get_mut(bone_entity) else {
+            continue;
+        };
+
+        // This system's own changes aren't detected, so the transform only changed if it was
+        // animated.
+        if !transform.is_changed() {
+            continue;
+        }
+
+        let pose = (transform.translation, transform.rotation);
+        let reference = *root_motion.reference.get_or_insert(pose);
+        let wrapped = player
+            .playing_animations()
+            .any(|(_, animation)| animation.just_completed);
+        let (delta_translation, delta_rotation) = match root_motion.previous {
+            Some(_) if wrapped => (root_motion.delta_translation, root_motion.delta_rotation),
+            Some((translation, rotation)) => (pose.0 - translation, pose.1 * rotation.inverse()),
+            None => (Vec3::ZERO, Quat::IDENTITY),
+        };
+        root_motion.previous = Some(pose);
+
+        if root_motion.extract_translation {
+            root_motion.delta_translation = delta_translation;
+            transform.translation = reference.0;
+        }
+        if root_motion.extract_rotation {
+            root_motion.delta_rotation = delta_rotation;
+            transform.rotation = reference.1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{schedule::Schedule, world::World};
+    use uuid::Uuid;
+
+    #[test]
+    fn extracts_motion() {
+        let mut world = World::new();
+        let bone = AnimationTargetId(Uuid::nil());
+        let player = world
+            .spawn((AnimationPlayer::default(), RootMotion::new(bone)))
+            .id();
+        let bone_entity = world
+            .spawn((AnimationTarget { id: bone, player }, Transform::default()))
+            .id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(extract_root_motion);
+
+        schedule.run(&mut world);
+        assert_eq!(
+            world.get::<RootMotion>(player).unwrap().delta_translation,
+            Vec3::ZERO
+        );
+
+        // Animate the bone.
+        world.get_mut::<Transform>(bone_entity).unwrap().translation = Vec3::X;
+        schedule.run(&mut world);
+        assert_eq!(
+            world.get::<RootMotion>(player).unwrap().delta_translation,
+            Vec3::X
+        );
+        assert_eq!(
+            world.get::<Transform>(bone_entity).unwrap().translation,
+            Vec3::ZERO
+        );
+
+        // Not animated.
+        schedule.run(&mut world);
+        assert_eq!(
+            world.get::<RootMotion>(player).unwrap().delta_translation,
+            Vec3::ZERO
+        );
+    }
+}
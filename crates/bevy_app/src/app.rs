@@ -12,12 +12,13 @@ use bevy_ecs::{
     component::RequiredComponentsError,
     event::{event_update_system, EventCursor},
     intern::Interned,
+    maintenance::{Maintenance, MaintenanceTask},
     prelude::*,
     schedule::{InternedSystemSet, ScheduleBuildSettings, ScheduleLabel},
     system::{IntoObserverSystem, ScheduleSystem, SystemId, SystemInput},
 };
-use bevy_platform_support::collections::HashMap;
-use core::{fmt::Debug, num::NonZero, panic::AssertUnwindSafe};
+use bevy_platform_support::{collections::HashMap, time::Instant};
+use core::{fmt::Debug, num::NonZero, panic::AssertUnwindSafe, time::Duration};
 use log::debug;
 
 #[cfg(feature = "trace")]
@@ -455,6 +456,17 @@ impl App {
         self
     }
 
+    /// Adds a [`MaintenanceTask`] run a little every frame by the
+    /// [`MaintenancePlugin`](crate::MaintenancePlugin).
+    ///
+    /// The tasks don't run if the plugin isn't added.
+    pub fn add_maintenance_task(&mut self, task: impl MaintenanceTask) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<Maintenance>()
+            .add_task(task);
+        self
+    }
+
     pub(crate) fn add_boxed_plugin(
         &mut self,
         plugin: Box<dyn Plugin>,
@@ -475,6 +487,7 @@ impl App {
 
         self.main_mut().plugin_build_depth += 1;
 
+        let start = Instant::now();
         let f = AssertUnwindSafe(|| plugin.build(self));
 
         #[cfg(feature = "std")]
@@ -486,6 +499,9 @@ impl App {
         self.main_mut()
             .plugin_names
             .insert(plugin.name().to_string());
+        self.main_mut()
+            .plugin_build_times
+            .push((plugin.name().to_string(), start.elapsed()));
         self.main_mut().plugin_build_depth -= 1;
 
         #[cfg(feature = "std")]
@@ -531,6 +547,15 @@ impl App {
         self.main().get_added_plugins::<T>()
     }
 
+    /// Returns how long the [`Plugin::build`] of each plugin added to the main app took, in the
+    /// order they were added, to find out which plugins slow down the startup of the app.
+    ///
+    /// The time of a plugin includes the plugins it added while it was built, which are listed
+    /// after it.
+    pub fn plugin_build_times(&self) -> impl Iterator<Item = (&str, Duration)> {
+        self.main().plugin_build_times()
+    }
+
     /// Installs a [`Plugin`] collection.
     ///
     /// Bevy prioritizes modularity as a core principle. **All** engine features are implemented
@@ -1422,6 +1447,7 @@ impl Termination for AppExit {
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec::Vec;
     use core::marker::PhantomData;
     use std::sync::Mutex;
 
@@ -1477,6 +1503,17 @@ mod tests {
         App::new().add_plugins((PluginA, PluginB));
     }
 
+    #[test]
+    fn records_plugin_build_times() {
+        let mut app = App::new();
+        app.add_plugins((PluginA, PluginB));
+        let names: Vec<_> = app.plugin_build_times().map(|(name, _)| name).collect();
+        assert!(names.ends_with(&[
+            core::any::type_name::<PluginA>(),
+            core::any::type_name::<PluginB>()
+        ]));
+    }
+
     #[test]
     #[should_panic]
     fn cant_add_twice_the_same_plugin() {
@@ -0,0 +1,15 @@
+use crate::{App, Last, Plugin};
+use bevy_ecs::arena::{reset_frame_arena, FrameArena};
+
+/// Adds the [`FrameArena`] resource, and resets it at the end of every frame.
+///
+/// Systems in [`Last`] that use the arena should be ordered before [`reset_frame_arena`].
+#[derive(Default)]
+pub struct FrameArenaPlugin;
+
+impl Plugin for FrameArenaPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FrameArena>()
+            .add_systems(Last, reset_frame_arena);
+    }
+}
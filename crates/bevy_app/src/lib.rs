@@ -24,22 +24,32 @@ extern crate alloc;
 extern crate self as bevy_app;
 
 mod app;
+#[cfg(feature = "std")]
+mod frame_arena_plugin;
 mod main_schedule;
+mod maintenance_plugin;
 mod panic_handler;
 mod plugin;
 mod plugin_group;
 mod schedule_runner;
+#[cfg(feature = "std")]
+mod standard_paths;
 mod sub_app;
 mod task_pool_plugin;
 #[cfg(all(any(unix, windows), feature = "std"))]
 mod terminal_ctrl_c_handler;
 
 pub use app::*;
+#[cfg(feature = "std")]
+pub use frame_arena_plugin::*;
 pub use main_schedule::*;
+pub use maintenance_plugin::*;
 pub use panic_handler::*;
 pub use plugin::*;
 pub use plugin_group::*;
 pub use schedule_runner::*;
+#[cfg(feature = "std")]
+pub use standard_paths::*;
 pub use sub_app::*;
 pub use task_pool_plugin::*;
 #[cfg(all(any(unix, windows), feature = "std"))]
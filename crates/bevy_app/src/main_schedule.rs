@@ -33,15 +33,25 @@ use bevy_ecs::{
 /// * [`RunFixedMainLoop`]
 ///     * This will run [`FixedMain`] zero to many times, based on how much time has elapsed.
 /// * [`Update`]
+/// * [`SpawnScene`]
 /// * [`PostUpdate`]
 /// * [`Last`]
 ///
+/// Plugins should add their systems to these schedules, and order them with system sets, rather
+/// than relying on the order plugins are added in. New schedules can be inserted relative to
+/// them with [`MainScheduleOrder`].
+///
 /// # Rendering
 ///
 /// Note rendering is not executed in the main schedule by default.
 /// Instead, rendering is performed in a separate [`SubApp`]
 /// which exchanges data with the main app in between the main schedule runs.
 ///
+/// The data of the main world is extracted by the [`ExtractSchedule`] after [`Last`], and before
+/// the next [`First`]. Systems preparing data for rendering should run in [`PostUpdate`], which
+/// runs after the app logic, or in [`Last`]. In the render world, the [`Render`] schedule is
+/// ordered by the [`RenderSet`]s.
+///
 /// See [`RenderPlugin`] and [`PipelinedRenderingPlugin`] for more details.
 ///
 /// [^1]: [`StateTransition`] is inserted only if you have `bevy_state` feature enabled. It is enabled in `default` features.
@@ -49,6 +59,9 @@ use bevy_ecs::{
 /// [`StateTransition`]: https://docs.rs/bevy/latest/bevy/prelude/struct.StateTransition.html
 /// [`OnEnter(MyState::Foo)`]: https://docs.rs/bevy/latest/bevy/prelude/struct.OnEnter.html
 /// [`OnEnter(MyComputedState)`]: https://docs.rs/bevy/latest/bevy/prelude/struct.OnEnter.html
+/// [`ExtractSchedule`]: https://docs.rs/bevy/latest/bevy/render/struct.ExtractSchedule.html
+/// [`Render`]: https://docs.rs/bevy/latest/bevy/render/struct.Render.html
+/// [`RenderSet`]: https://docs.rs/bevy/latest/bevy/render/enum.RenderSet.html
 /// [`RenderPlugin`]: https://docs.rs/bevy/latest/bevy/render/struct.RenderPlugin.html
 /// [`PipelinedRenderingPlugin`]: https://docs.rs/bevy/latest/bevy/render/pipelined_rendering/struct.PipelinedRenderingPlugin.html
 /// [`SubApp`]: crate::SubApp
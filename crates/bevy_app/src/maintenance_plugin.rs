@@ -0,0 +1,41 @@
+use crate::{App, Last, Plugin};
+use bevy_ecs::{
+    hierarchy::Children,
+    maintenance::{run_maintenance, Maintenance, ShrinkRelationships, ShrinkStorages},
+};
+use core::time::Duration;
+
+/// Adds the [`Maintenance`] resource, and runs its tasks at the end of every frame.
+///
+/// The memory of the archetypes and tables that were emptied or shrunk a lot is freed with
+/// [`ShrinkStorages`], and the one of [`Children`] with [`ShrinkRelationships`]. Other plugins add
+/// their own tasks with [`App::add_maintenance_task`].
+///
+/// This plugin isn't part of the `DefaultPlugins`. Add it before them, so that the plugins checking
+/// whether it was added register their tasks:
+///
+/// ```
+/// # use bevy_app::{App, MaintenancePlugin, NoopPluginGroup as DefaultPlugins};
+/// App::new().add_plugins((MaintenancePlugin::default(), DefaultPlugins));
+/// ```
+pub struct MaintenancePlugin {
+    /// The time spent on maintenance per frame.
+    pub budget: Duration,
+}
+
+impl Default for MaintenancePlugin {
+    fn default() -> Self {
+        Self {
+            budget: Maintenance::default().budget,
+        }
+    }
+}
+
+impl Plugin for MaintenancePlugin {
+    fn build(&self, app: &mut App) {
+        app.world_mut().get_resource_or_init::<Maintenance>().budget = self.budget;
+        app.add_maintenance_task(ShrinkStorages::default())
+            .add_maintenance_task(ShrinkRelationships::<Children>::default())
+            .add_systems(Last, run_maintenance);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{App, Plugin};
+use alloc::string::{String, ToString};
+use bevy_ecs::resource::Resource;
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+/// The standard directories where an application stores its files, following the conventions of
+/// the current platform.
+///
+/// | Platform | Config                          | Data                            | Cache                      | Logs                         |
+/// |----------|---------------------------------|---------------------------------|----------------------------|------------------------------|
+/// | Linux    | `$XDG_CONFIG_HOME/<app>`        | `$XDG_DATA_HOME/<app>`          | `$XDG_CACHE_HOME/<app>`    | `$XDG_STATE_HOME/<app>/logs` |
+/// | macOS    | `~/Library/Application Support/<app>` | `~/Library/Application Support/<app>` | `~/Library/Caches/<app>` | `~/Library/Logs/<app>` |
+/// | Windows  | `%APPDATA%\<app>\config`        | `%APPDATA%\<app>\data`          | `%LOCALAPPDATA%\<app>\cache` | `%LOCALAPPDATA%\<app>\logs` |
+/// | Web      | `/<app>/config`                 | `/<app>/data`                   | `/<app>/cache`             | `/<app>/logs`                |
+///
+/// On Linux, the XDG directories fall back to `~/.config`, `~/.local/share`, `~/.cache` and `~/.local/state`.
+/// iOS follows the macOS conventions within the application's sandbox.
+/// On other platforms, or if the home directory can't be determined, the directories are `None`.
+///
+/// The web has no file system, so the directories are virtual: files written with
+/// [`StandardPaths::write`] are stored in the browser's `localStorage`, keyed by their path.
+/// Files under these directories should always be accessed through [`StandardPaths::read_to_string`]
+/// and [`StandardPaths::write`] to work on every platform.
+///
+/// Inserted by the [`StandardPathsPlugin`].
+#[derive(Resource, Clone, Debug)]
+pub struct StandardPaths {
+    app_name: String,
+    config_dir: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    log_dir: Option<PathBuf>,
+}
+
+impl StandardPaths {
+    /// Resolves the standard directories of the application named `app_name`.
+    pub fn new(app_name: impl Into<String>) -> Self {
+        let app_name = app_name.into();
+        let env = |name: &str| {
+            std::env::var_os(name)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        };
+        let home = env("HOME");
+
+        let (config_dir, data_dir, cache_dir, log_dir) = if cfg!(target_arch = "wasm32") {
+            let root = Path::new("/").join(&app_name);
+            (
+                Some(root.join("config")),
+                Some(root.join("data")),
+                Some(root.join("cache")),
+                Some(root.join("logs")),
+            )
+        } else if cfg!(target_os = "windows") {
+            let roaming = env("APPDATA").map(|dir| dir.join(&app_name));
+            let local = env("LOCALAPPDATA").map(|dir| dir.join(&app_name));
+            (
+                roaming.as_ref().map(|dir| dir.join("config")),
+                roaming.map(|dir| dir.join("data")),
+                local.as_ref().map(|dir| dir.join("cache")),
+                local.map(|dir| dir.join("logs")),
+            )
+        } else if cfg!(any(target_os = "macos", target_os = "ios")) {
+            let library = home.map(|home| home.join("Library"));
+            let support = library
+                .as_ref()
+                .map(|dir| dir.join("Application Support").join(&app_name));
+            (
+                support.clone(),
+                support,
+                library
+                    .as_ref()
+                    .map(|dir| dir.join("Caches").join(&app_name)),
+                library.map(|dir| dir.join("Logs").join(&app_name)),
+            )
+        } else if cfg!(unix) && !cfg!(target_os = "android") {
+            let xdg = |name: &str, fallback: &str| {
+                env(name)
+                    .or_else(|| home.as_ref().map(|home| home.join(fallback)))
+                    .map(|dir| dir.join(&app_name))
+            };
+            (
+                xdg("XDG_CONFIG_HOME", ".config"),
+                xdg("XDG_DATA_HOME", ".local/share"),
+                xdg("XDG_CACHE_HOME", ".cache"),
+                xdg("XDG_STATE_HOME", ".local/state").map(|dir| dir.join("logs")),
+            )
+        } else {
+            (None, None, None, None)
+        };
+
+        Self {
+            app_name,
+            config_dir,
+            data_dir,
+            cache_dir,
+            log_dir,
+        }
+    }
+
+    /// Returns the name of the application the directories belong to.
+    pub fn app_name(&self) -> &str {
+        &self.app_name
+    }
+
+    /// Returns the directory for user configuration, such as settings.
+    pub fn config_dir(&self) -> Option<&Path> {
+        self.config_dir.as_deref()
+    }
+
+    /// Returns the directory for user data, such as save games.
+    pub fn data_dir(&self) -> Option<&Path> {
+        self.data_dir.as_deref()
+    }
+
+    /// Returns the directory for data that can be regenerated, such as downloaded or processed assets.
+    pub fn cache_dir(&self) -> Option<&Path> {
+        self.cache_dir.as_deref()
+    }
+
+    /// Returns the directory for log files.
+    pub fn log_dir(&self) -> Option<&Path> {
+        self.log_dir.as_deref()
+    }
+
+    /// Reads the file at `path` into a string.
+    ///
+    /// On the web, the file is read from `localStorage`.
+    pub fn read_to_string(path: &Path) -> io::Result<String> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            std::fs::read_to_string(path)
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            web_storage::read_to_string(path)
+        }
+    }
+
+    /// Writes `contents` to the file at `path`, creating its parent directories if needed.
+    ///
+    /// On the web, the file is written to `localStorage`.
+    pub fn write(path: &Path, contents: &str) -> io::Result<()> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, contents)
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            web_storage::write(path, contents)
+        }
+    }
+}
+
+impl Default for StandardPaths {
+    /// Resolves the standard directories of the application named after the current executable.
+    fn default() -> Self {
+        let app_name = std::env::current_exe()
+            .ok()
+            .and_then(|exe| Some(exe.file_stem()?.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "bevy".to_string());
+        Self::new(app_name)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web_storage {
+    use alloc::string::String;
+    use std::{io, path::Path};
+
+    #[cfg(feature = "web")]
+    fn storage() -> io::Result<web_sys::Storage> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or_else(|| {
+                io::Error::new(io::ErrorKind::Unsupported, "localStorage is unavailable")
+            })
+    }
+
+    #[cfg(feature = "web")]
+    fn key(path: &Path) -> String {
+        alloc::format!("bevy:{}", path.display())
+    }
+
+    #[cfg(feature = "web")]
+    pub(super) fn read_to_string(path: &Path) -> io::Result<String> {
+        storage()?
+            .get_item(&key(path))
+            .map_err(|_| io::Error::other("failed to read from localStorage"))?
+            .ok_or_else(|| io::ErrorKind::NotFound.into())
+    }
+
+    #[cfg(feature = "web")]
+    pub(super) fn write(path: &Path, contents: &str) -> io::Result<()> {
+        storage()?
+            .set_item(&key(path), contents)
+            .map_err(|_| io::Error::other("failed to write to localStorage, it may be full"))
+    }
+
+    #[cfg(not(feature = "web"))]
+    pub(super) fn read_to_string(_path: &Path) -> io::Result<String> {
+        Err(unsupported())
+    }
+
+    #[cfg(not(feature = "web"))]
+    pub(super) fn write(_path: &Path, _contents: &str) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    #[cfg(not(feature = "web"))]
+    fn unsupported() -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the `web` feature is required to access localStorage",
+        )
+    }
+}
+
+/// Inserts the [`StandardPaths`] resource for the application named [`StandardPathsPlugin::app_name`].
+///
+/// If the resource has already been inserted, it is left unchanged.
+#[derive(Default)]
+pub struct StandardPathsPlugin {
+    /// The name of the application, used to name its directories.
+    ///
+    /// If `None`, the name of the current executable is used.
+    pub app_name: Option<String>,
+}
+
+impl StandardPathsPlugin {
+    /// Creates a [`StandardPathsPlugin`] for the application named `app_name`.
+    pub fn new(app_name: impl Into<String>) -> Self {
+        Self {
+            app_name: Some(app_name.into()),
+        }
+    }
+}
+
+impl Plugin for StandardPathsPlugin {
+    fn build(&self, app: &mut App) {
+        if app.world().contains_resource::<StandardPaths>() {
+            return;
+        }
+        let paths = match &self.app_name {
+            Some(app_name) => StandardPaths::new(app_name.clone()),
+            None => StandardPaths::default(),
+        };
+        app.insert_resource(paths);
+    }
+}
@@ -7,7 +7,7 @@ use bevy_ecs::{
     system::{ScheduleSystem, SystemId, SystemInput},
 };
 use bevy_platform_support::collections::{HashMap, HashSet};
-use core::fmt::Debug;
+use core::{fmt::Debug, time::Duration};
 
 #[cfg(feature = "trace")]
 use tracing::info_span;
@@ -68,6 +68,8 @@ pub struct SubApp {
     pub(crate) plugin_names: HashSet<String>,
     /// Panics if an update is attempted while plugins are building.
     pub(crate) plugin_build_depth: usize,
+    /// How long the [`Plugin::build`] of each plugin took, in the order they were added.
+    pub(crate) plugin_build_times: Vec<(String, Duration)>,
     pub(crate) plugins_state: PluginsState,
     /// The schedule that will be run by [`update`](Self::update).
     pub update_schedule: Option<InternedScheduleLabel>,
@@ -91,6 +93,7 @@ impl Default for SubApp {
             plugin_registry: Vec::default(),
             plugin_names: HashSet::default(),
             plugin_build_depth: 0,
+            plugin_build_times: Vec::new(),
             plugins_state: PluginsState::Adding,
             update_schedule: None,
             extract: None,
@@ -372,6 +375,13 @@ impl SubApp {
             .collect()
     }
 
+    /// See [`App::plugin_build_times`].
+    pub fn plugin_build_times(&self) -> impl Iterator<Item = (&str, Duration)> {
+        self.plugin_build_times
+            .iter()
+            .map(|(name, duration)| (name.as_str(), *duration))
+    }
+
     /// Returns `true` if there is no plugin in the middle of being built.
     pub(crate) fn is_building_plugins(&self) -> bool {
         self.plugin_build_depth > 0
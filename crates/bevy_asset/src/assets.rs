@@ -2,9 +2,12 @@ use crate::asset_changed::AssetChanges;
 use crate::{Asset, AssetEvent, AssetHandleProvider, AssetId, AssetServer, Handle, UntypedHandle};
 use alloc::{sync::Arc, vec::Vec};
 use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    maintenance::MaintenanceTask,
     prelude::EventWriter,
     resource::Resource,
     system::{Res, ResMut, SystemChangeTick},
+    world::World,
 };
 use bevy_platform_support::collections::HashMap;
 use bevy_reflect::{Reflect, TypePath};
@@ -292,6 +295,10 @@ pub struct Assets<A: Asset> {
     /// Assets managed by the `Assets` struct with live strong `Handle`s
     /// originating from `get_strong_handle`.
     duplicate_handles: HashMap<AssetId<A>, u16>,
+    /// Whether the values of the assets removed because all their handles were dropped are kept
+    /// in `unused` until they are dropped by the [`DropUnusedAssets`] maintenance task.
+    defer_drops: bool,
+    unused: Vec<A>,
 }
 
 impl<A: Asset> Default for Assets<A> {
@@ -305,6 +312,8 @@ impl<A: Asset> Default for Assets<A> {
             hash_map: Default::default(),
             queued_events: Default::default(),
             duplicate_handles: Default::default(),
+            defer_drops: false,
+            unused: Vec::new(),
         }
     }
 }
@@ -437,6 +446,19 @@ impl<A: Asset> Assets<A> {
         result
     }
 
+    /// Retrieves a mutable reference to the [`Asset`] with the given `id`, if it exists. This skips emitting [`AssetEvent::Modified`].
+    /// Note that this supports anything that implements `Into<AssetId<A>>`, which includes [`Handle`] and [`AssetId`].
+    ///
+    /// This is useful to post-process an asset in response to its own [`AssetEvent`], without triggering the
+    /// post-processing again.
+    #[inline]
+    pub fn get_mut_untracked(&mut self, id: impl Into<AssetId<A>>) -> Option<&mut A> {
+        match id.into() {
+            AssetId::Index { index, .. } => self.dense_storage.get_mut(index),
+            AssetId::Uuid { uuid } => self.hash_map.get_mut(&uuid),
+        }
+    }
+
     /// Removes (and returns) the [`Asset`] with the given `id`, if it exists.
     /// Note that this supports anything that implements `Into<AssetId<A>>`, which includes [`Handle`] and [`AssetId`].
     pub fn remove(&mut self, id: impl Into<AssetId<A>>) -> Option<A> {
@@ -468,15 +490,33 @@ impl<A: Asset> Assets<A> {
                 return;
             }
         }
-        let existed = match id {
-            AssetId::Index { index, .. } => self.dense_storage.remove_dropped(index).is_some(),
-            AssetId::Uuid { uuid } => self.hash_map.remove(&uuid).is_some(),
+        let removed = match id {
+            AssetId::Index { index, .. } => self.dense_storage.remove_dropped(index),
+            AssetId::Uuid { uuid } => self.hash_map.remove(&uuid),
         };
-        if existed {
+        if let Some(asset) = removed {
+            if self.defer_drops {
+                self.unused.push(asset);
+            }
             self.queued_events.push(AssetEvent::Removed { id });
         }
     }
 
+    /// Keeps the values of the assets removed because all their handles were dropped, until they
+    /// are dropped a few at a time by the [`DropUnusedAssets`] maintenance task.
+    ///
+    /// Dropping large assets, such as meshes or images, can otherwise cause stalls when many of
+    /// them are unloaded in the same frame.
+    pub(crate) fn defer_drops(&mut self) {
+        self.defer_drops = true;
+    }
+
+    /// Returns the number of assets removed because all their handles were dropped, whose values
+    /// haven't been dropped yet.
+    pub fn unused_len(&self) -> usize {
+        self.unused.len()
+    }
+
     /// Returns `true` if there are no assets in this collection.
     pub fn is_empty(&self) -> bool {
         self.dense_storage.is_empty() && self.hash_map.is_empty()
@@ -591,6 +631,30 @@ impl<A: Asset> Assets<A> {
     }
 }
 
+/// A [`MaintenanceTask`] dropping the values of the [`Assets`] removed because all their handles
+/// were dropped, one at a time.
+///
+/// It's added for every asset type when the [`MaintenancePlugin`](bevy_app::MaintenancePlugin) is
+/// added before the asset type is initialized.
+pub struct DropUnusedAssets<A: Asset>(PhantomData<fn() -> A>);
+
+impl<A: Asset> Default for DropUnusedAssets<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: Asset> MaintenanceTask for DropUnusedAssets<A> {
+    fn step(&mut self, world: &mut World) -> bool {
+        let Some(mut assets) = world.get_resource_mut::<Assets<A>>() else {
+            return false;
+        };
+        let assets = assets.bypass_change_detection();
+        drop(assets.unused.pop());
+        !assets.unused.is_empty()
+    }
+}
+
 /// A mutable iterator over [`Assets`].
 pub struct AssetsMutIterator<'a, A: Asset> {
     queued_events: &'a mut Vec<AssetEvent<A>>,
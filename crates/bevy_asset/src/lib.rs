@@ -216,7 +216,7 @@ use alloc::{
     sync::Arc,
     vec::Vec,
 };
-use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_app::{App, MaintenancePlugin, Plugin, PostUpdate, PreUpdate, StandardPaths};
 use bevy_ecs::prelude::Component;
 use bevy_ecs::{
     reflect::AppTypeRegistry,
@@ -241,6 +241,10 @@ compile_error!(
 /// Supports flexible "modes", such as [`AssetMode::Processed`] and
 /// [`AssetMode::Unprocessed`] that enable using the asset workflow that best suits your project.
 ///
+/// If the [`StandardPaths`] resource has been inserted, the application's data and cache directories
+/// are available as the `data://` and `cache://` asset sources, unless sources with these names
+/// have already been registered.
+///
 /// [`AssetSource`]: io::AssetSource
 pub struct AssetPlugin {
     /// The default file path to use (relative to the project root) for unprocessed assets.
@@ -354,6 +358,7 @@ impl Plugin for AssetPlugin {
     fn build(&self, app: &mut App) {
         let embedded = EmbeddedAssetRegistry::default();
         {
+            let standard_paths = app.world().get_resource::<StandardPaths>().cloned();
             let mut sources = app
                 .world_mut()
                 .get_resource_or_init::<AssetSourceBuilders>();
@@ -363,6 +368,27 @@ impl Plugin for AssetPlugin {
                     .then_some(self.processed_file_path.as_str()),
             );
             embedded.register_source(&mut sources);
+
+            // The standard directories are virtual on the web, and not accessible through the file system on Android.
+            #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+            if let Some(standard_paths) = standard_paths {
+                for (id, dir) in [
+                    ("data", standard_paths.data_dir()),
+                    ("cache", standard_paths.cache_dir()),
+                ] {
+                    let Some(dir) = dir else {
+                        continue;
+                    };
+                    if sources.get_mut(id).is_none() {
+                        sources.insert(
+                            id,
+                            AssetSourceBuilder::platform_default(&dir.to_string_lossy(), None),
+                        );
+                    }
+                }
+            }
+            #[cfg(any(target_arch = "wasm32", target_os = "android"))]
+            let _ = standard_paths;
         }
         {
             let mut watch = cfg!(feature = "watch");
@@ -421,6 +447,7 @@ impl Plugin for AssetPlugin {
             .init_asset::<LoadedUntypedAsset>()
             .init_asset::<()>()
             .add_event::<UntypedAssetLoadFailedEvent>()
+            .init_resource::<LoadingProgress>()
             .configure_sets(PreUpdate, TrackAssets.after(handle_internal_asset_events))
             // `handle_internal_asset_events` requires the use of `&mut World`,
             // and as a result has ambiguous system ordering with all other systems in `PreUpdate`.
@@ -593,7 +620,11 @@ impl AssetApp for App {
     }
 
     fn init_asset<A: Asset>(&mut self) -> &mut Self {
-        let assets = Assets::<A>::default();
+        let mut assets = Assets::<A>::default();
+        if self.is_plugin_added::<MaintenancePlugin>() {
+            assets.defer_drops();
+            self.add_maintenance_task(DropUnusedAssets::<A>::default());
+        }
         self.world()
             .resource::<AssetServer>()
             .register_asset(&assets);
@@ -670,7 +701,7 @@ mod tests {
         },
         loader::{AssetLoader, LoadContext},
         Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, LoadState, UnapprovedPathMode,
+        AssetPlugin, AssetServer, Assets, LoadState, LoadingProgress, UnapprovedPathMode,
     };
     use alloc::{
         boxed::Box,
@@ -680,7 +711,7 @@ mod tests {
         vec,
         vec::Vec,
     };
-    use bevy_app::{App, TaskPoolPlugin, Update};
+    use bevy_app::{App, MaintenancePlugin, TaskPoolPlugin, Update};
     use bevy_ecs::{
         event::EventCursor,
         prelude::*,
@@ -1665,6 +1696,65 @@ mod tests {
         });
     }
 
+    #[test]
+    fn loading_progress() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        let dir = Dir::default();
+
+        let a_path = "text/a.cool.ron";
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "b.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let b_path = "b.cool.ron";
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), b_ron);
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let _handle: Handle<LoadedFolder> = asset_server.load_folder("text");
+        app.update();
+        assert!(!app.world().resource::<LoadingProgress>().is_done());
+
+        gate_opener.open(a_path);
+        gate_opener.open(b_path);
+        run_app_until(&mut app, |world| {
+            world.resource::<LoadingProgress>().is_done().then_some(())
+        });
+
+        // The folder, the asset in it and its dependency.
+        let expected = LoadingProgress {
+            total: 3,
+            loaded: 3,
+            failed: 0,
+            bytes: (a_ron.len() + b_ron.len()) as u64,
+        };
+        assert_eq!(*app.world().resource::<LoadingProgress>(), expected);
+        assert_eq!(asset_server.loading_progress(), expected);
+        assert_eq!(expected.fraction(), 1.0);
+
+        asset_server.reset_loading_progress();
+        assert_eq!(asset_server.loading_progress(), LoadingProgress::default());
+    }
+
     /// Tests that `AssetLoadFailedEvent<A>` events are emitted and can be used to retry failed assets.
     #[test]
     fn load_error_events() {
@@ -1809,6 +1899,39 @@ mod tests {
         app.world_mut().run_schedule(Update);
     }
 
+    #[test]
+    fn unused_assets_are_dropped_by_maintenance() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            MaintenancePlugin {
+                budget: Duration::ZERO,
+            },
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>();
+
+        let handles: Vec<Handle<CoolText>> = (0..3)
+            .map(|_| {
+                app.world_mut()
+                    .resource_mut::<Assets<CoolText>>()
+                    .add(CoolText::default())
+            })
+            .collect();
+        drop(handles);
+
+        // Every frame, the assets whose handles were dropped are removed, but only one of their
+        // values is dropped since the maintenance budget is spent.
+        app.update();
+        let assets = app.world().resource::<Assets<CoolText>>();
+        assert!(assets.is_empty());
+        assert_eq!(assets.unused_len(), 2);
+
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Assets<CoolText>>().unused_len(), 0);
+    }
+
     // This test is not checking a requirement, but documenting a current limitation. We simply are
     // not capable of loading subassets when doing nested immediate loads.
     #[test]
@@ -1,8 +1,8 @@
 use crate::{
     meta::{AssetHash, MetaTransform},
     Asset, AssetHandleProvider, AssetLoadError, AssetPath, DependencyLoadState, ErasedLoadedAsset,
-    Handle, InternalAssetEvent, LoadState, RecursiveDependencyLoadState, StrongHandle,
-    UntypedAssetId, UntypedHandle,
+    Handle, InternalAssetEvent, LoadState, LoadingProgress, RecursiveDependencyLoadState,
+    StrongHandle, UntypedAssetId, UntypedHandle,
 };
 use alloc::{
     borrow::ToOwned,
@@ -86,6 +86,8 @@ pub(crate) struct AssetInfos {
     pub(crate) dependency_failed_event_sender:
         TypeIdMap<fn(&mut World, UntypedAssetId, AssetPath<'static>, AssetLoadError)>,
     pub(crate) pending_tasks: HashMap<UntypedAssetId, Task<()>>,
+    /// The counts of the [`LoadingProgress`], without its bytes.
+    pub(crate) progress: LoadingProgress,
 }
 
 impl core::fmt::Debug for AssetInfos {
@@ -103,6 +105,7 @@ impl AssetInfos {
         type_id: TypeId,
         type_name: &'static str,
     ) -> UntypedHandle {
+        self.progress.total += 1;
         unwrap_with_context(
             Self::create_handle_internal(
                 &mut self.infos,
@@ -227,6 +230,9 @@ impl AssetInfos {
                     || (loading_mode == HandleLoadingMode::Request
                         && matches!(info.load_state, LoadState::NotLoaded | LoadState::Failed(_)))
                 {
+                    if !matches!(info.load_state, LoadState::Loading) {
+                        self.progress.total += 1;
+                    }
                     info.load_state = LoadState::Loading;
                     info.dep_load_state = DependencyLoadState::Loading;
                     info.rec_dep_load_state = RecursiveDependencyLoadState::Loading;
@@ -274,6 +280,9 @@ impl AssetInfos {
                     should_load,
                 )?;
                 entry.insert(handle.id());
+                if should_load {
+                    self.progress.total += 1;
+                }
                 Ok((handle, should_load))
             }
         }
@@ -373,6 +382,7 @@ impl AssetInfos {
             &mut self.loader_dependents,
             &mut self.living_labeled_assets,
             &mut self.pending_tasks,
+            &mut self.progress,
             self.watching_for_changes,
             id,
         )
@@ -486,8 +496,12 @@ impl AssetInfos {
                 }
             }
             let info = self
-                .get_mut(loaded_asset_id)
+                .infos
+                .get_mut(&loaded_asset_id)
                 .expect("Asset info should always exist at this point");
+            if matches!(info.load_state, LoadState::Loading) {
+                self.progress.loaded += 1;
+            }
             info.loading_dependencies = loading_deps;
             info.failed_dependencies = failed_deps;
             info.loading_rec_dependencies = loading_rec_deps;
@@ -610,10 +624,13 @@ impl AssetInfos {
 
         let error = Arc::new(error);
         let (dependents_waiting_on_load, dependents_waiting_on_rec_load) = {
-            let Some(info) = self.get_mut(failed_id) else {
+            let Some(info) = self.infos.get_mut(&failed_id) else {
                 // The asset was already dropped.
                 return;
             };
+            if matches!(info.load_state, LoadState::Loading) {
+                self.progress.failed += 1;
+            }
             info.load_state = LoadState::Failed(error.clone());
             info.dep_load_state = DependencyLoadState::Failed(error.clone());
             info.rec_dep_load_state = RecursiveDependencyLoadState::Failed(error.clone());
@@ -677,6 +694,7 @@ impl AssetInfos {
         loader_dependents: &mut HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
         living_labeled_assets: &mut HashMap<AssetPath<'static>, HashSet<Box<str>>>,
         pending_tasks: &mut HashMap<UntypedAssetId, Task<()>>,
+        progress: &mut LoadingProgress,
         watching_for_changes: bool,
         id: UntypedAssetId,
     ) -> bool {
@@ -696,6 +714,10 @@ impl AssetInfos {
         let type_id = entry.key().type_id();
 
         let info = entry.remove();
+        // The load won't finish, so it is no longer counted.
+        if matches!(info.load_state, LoadState::Loading) {
+            progress.total = progress.total.saturating_sub(1);
+        }
         let Some(path) = &info.path else {
             return true;
         };
@@ -736,6 +758,7 @@ impl AssetInfos {
                         &mut self.loader_dependents,
                         &mut self.living_labeled_assets,
                         &mut self.pending_tasks,
+                        &mut self.progress,
                         self.watching_for_changes,
                         id.untyped(provider.type_id),
                     );
@@ -1,5 +1,6 @@
 mod info;
 mod loaders;
+mod progress;
 
 use crate::{
     folder::LoadedFolder,
@@ -26,7 +27,10 @@ use alloc::{
 };
 use atomicow::CowArc;
 use bevy_ecs::prelude::*;
-use bevy_platform_support::collections::HashSet;
+use bevy_platform_support::{
+    collections::HashSet,
+    sync::atomic::{AtomicU64, Ordering},
+};
 use bevy_tasks::IoTaskPool;
 use core::{any::TypeId, future::Future, panic::AssertUnwindSafe, task::Poll};
 use crossbeam_channel::{Receiver, Sender};
@@ -35,10 +39,13 @@ use futures_lite::{FutureExt, StreamExt};
 use info::*;
 use loaders::*;
 use parking_lot::{RwLock, RwLockWriteGuard};
+use progress::CountingReader;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tracing::{error, info};
 
+pub use progress::LoadingProgress;
+
 /// Loads and tracks the state of [`Asset`] values from a configured [`AssetReader`](crate::io::AssetReader).
 /// This can be used to kick off new asset loads and retrieve their current load states.
 ///
@@ -69,6 +76,8 @@ pub(crate) struct AssetServerData {
     mode: AssetServerMode,
     meta_check: AssetMetaCheck,
     unapproved_path_mode: UnapprovedPathMode,
+    /// The number of bytes read by the asset loaders, for the [`LoadingProgress`].
+    bytes_loaded: AtomicU64,
 }
 
 /// The "asset mode" the server is currently in.
@@ -139,6 +148,7 @@ impl AssetServer {
                 loaders,
                 infos: RwLock::new(infos),
                 unapproved_path_mode,
+                bytes_loaded: AtomicU64::new(0),
             }),
         }
     }
@@ -943,6 +953,9 @@ impl AssetServer {
     /// feature is enabled, [`LoadedFolder`] handles will reload when a file in the folder is
     /// removed, added or moved. This includes files in subdirectories and moving, adding,
     /// or removing complete subdirectories.
+    ///
+    /// The loads of the folder and of its assets are counted in the [`LoadingProgress`] resource,
+    /// which can drive a loading bar.
     #[must_use = "not using the returned strong handle may result in the unexpected release of the assets"]
     pub fn load_folder<'a>(&self, path: impl Into<AssetPath<'a>>) -> Handle<LoadedFolder> {
         let path = path.into().into_owned();
@@ -1165,6 +1178,27 @@ impl AssetServer {
         )
     }
 
+    /// Returns the progress of the loads started by this server, also available as the
+    /// [`LoadingProgress`] resource.
+    pub fn loading_progress(&self) -> LoadingProgress {
+        LoadingProgress {
+            bytes: self.data.bytes_loaded.load(Ordering::Relaxed),
+            ..self.data.infos.read().progress
+        }
+    }
+
+    /// Starts counting the [`LoadingProgress`] from now, for example before loading a new level.
+    ///
+    /// The loads which haven't finished yet are still counted.
+    pub fn reset_loading_progress(&self) {
+        let mut infos = self.data.infos.write();
+        infos.progress = LoadingProgress {
+            total: infos.progress.pending(),
+            ..Default::default()
+        };
+        self.data.bytes_loaded.store(0, Ordering::Relaxed);
+    }
+
     /// Returns an active handle for the given path, if the asset at the given path has already started loading,
     /// or is still "alive".
     pub fn get_handle<'a, A: Asset>(&self, path: impl Into<AssetPath<'a>>) -> Option<Handle<A>> {
@@ -1430,7 +1464,11 @@ impl AssetServer {
         let asset_path = asset_path.clone_owned();
         let load_context =
             LoadContext::new(self, asset_path.clone(), load_dependencies, populate_hashes);
-        AssertUnwindSafe(loader.load(reader, meta, load_context))
+        let mut reader = CountingReader {
+            reader,
+            bytes: &self.data.bytes_loaded,
+        };
+        AssertUnwindSafe(loader.load(&mut reader, meta, load_context))
             .catch_unwind()
             .await
             .map_err(|_| AssetLoadError::AssetLoaderPanic {
@@ -1669,6 +1707,14 @@ pub fn handle_internal_asset_events(world: &mut World) {
             world.send_event_batch(untyped_failures);
         }
 
+        let progress = LoadingProgress {
+            bytes: server.data.bytes_loaded.load(Ordering::Relaxed),
+            ..infos.progress
+        };
+        if let Some(mut resource) = world.get_resource_mut::<LoadingProgress>() {
+            resource.set_if_neq(progress);
+        }
+
         fn queue_ancestors(
             asset_path: &AssetPath,
             infos: &AssetInfos,
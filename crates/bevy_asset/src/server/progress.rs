@@ -0,0 +1,97 @@
+use crate::io::{AsyncSeekForward, Reader};
+use bevy_ecs::prelude::*;
+use bevy_platform_support::sync::atomic::{AtomicU64, Ordering};
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_io::AsyncRead;
+
+/// The progress of the loads started by the [`AssetServer`](crate::AssetServer), updated every
+/// frame, for example to draw a loading bar.
+///
+/// The counts include every asset requested from the server, along with their dependencies, since
+/// the app started or since [`AssetServer::reset_loading_progress`](crate::AssetServer::reset_loading_progress).
+/// Loads whose handles are all dropped before they finish are no longer counted.
+///
+/// ```
+/// # use bevy_asset::LoadingProgress;
+/// # use bevy_ecs::prelude::*;
+/// fn update_loading_bar(progress: Res<LoadingProgress>) {
+///     if progress.is_changed() {
+///         println!(
+///             "{:.0}% ({} / {} assets, {} failed, {} KiB)",
+///             progress.fraction() * 100.0,
+///             progress.loaded,
+///             progress.total,
+///             progress.failed,
+///             progress.bytes / 1024,
+///         );
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadingProgress {
+    /// The number of loads started.
+    pub total: usize,
+    /// The number of assets loaded, without waiting for their dependencies.
+    pub loaded: usize,
+    /// The number of assets which failed to load.
+    pub failed: usize,
+    /// The number of bytes read by the asset loaders.
+    pub bytes: u64,
+}
+
+impl LoadingProgress {
+    /// Returns the number of loads which are neither loaded nor failed yet.
+    pub fn pending(&self) -> usize {
+        self.total.saturating_sub(self.loaded + self.failed)
+    }
+
+    /// Returns `true` if all the loads finished, successfully or not.
+    pub fn is_done(&self) -> bool {
+        self.pending() == 0
+    }
+
+    /// Returns the fraction of the loads which finished, successfully or not, between 0 and 1.
+    ///
+    /// This is 1 when no loads were started.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        (self.total - self.pending()) as f32 / self.total as f32
+    }
+}
+
+/// A [`Reader`] counting the bytes read, for the [`LoadingProgress`].
+pub(super) struct CountingReader<'a> {
+    pub(super) reader: &'a mut dyn Reader,
+    pub(super) bytes: &'a AtomicU64,
+}
+
+impl AsyncRead for CountingReader<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<futures_io::Result<usize>> {
+        let result = Pin::new(&mut *self.reader).poll_read(cx, buf);
+        if let Poll::Ready(Ok(count)) = result {
+            self.bytes.fetch_add(count as u64, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
+impl AsyncSeekForward for CountingReader<'_> {
+    fn poll_seek_forward(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        offset: u64,
+    ) -> Poll<futures_io::Result<u64>> {
+        Pin::new(&mut *self.reader).poll_seek_forward(cx, offset)
+    }
+}
+
+impl Reader for CountingReader<'_> {}
@@ -1,47 +1,117 @@
 use crate::{
-    AudioPlayer, Decodable, DefaultSpatialScale, GlobalVolume, PlaybackMode, PlaybackSettings,
-    SpatialAudioSink, SpatialListener,
+    AudioBus, AudioMix, AudioMixer, AudioOutputDevice, AudioPlayer, Decodable, DefaultSpatialScale,
+    PlaybackMode, PlaybackSettings, SpatialAudioSink, SpatialListener, Volume,
 };
+use alloc::string::String;
 use bevy_asset::{Asset, Assets};
 use bevy_ecs::{prelude::*, system::SystemParam};
 use bevy_math::Vec3;
+use bevy_platform_support::time::Instant;
+use bevy_tasks::{futures::check_ready, IoTaskPool, Task};
 use bevy_transform::prelude::GlobalTransform;
-use rodio::{OutputStream, OutputStreamHandle, Sink, Source, SpatialSink};
-use tracing::warn;
+use bevy_window::AppLifecycle;
+use core::time::Duration;
+use rodio::{
+    cpal::traits::DeviceTrait, OutputStream, OutputStreamHandle, Sink, Source, SpatialSink,
+};
+use tracing::{info, warn};
 
 use crate::{AudioSink, AudioSinkPlayback};
 
+/// How often the audio devices are checked for changes.
+const DEVICE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Used internally to play audio on the current "audio device"
 ///
-/// ## Note
-///
-/// Initializing this resource will leak [`OutputStream`]
-/// using [`std::mem::forget`].
-/// This is done to avoid storing this in the struct (and making this `!Send`)
-/// while preventing it from dropping (to avoid halting of audio).
-///
-/// This is fine when initializing this once (as is default when adding this plugin),
-/// since the memory cost will be the same.
-/// However, repeatedly inserting this resource into the app will **leak more memory**.
+/// The [`OutputStream`] itself is stored in the [`AudioOutputStream`] non-send resource, as it
+/// must stay alive for audio to play.
 #[derive(Resource)]
 pub(crate) struct AudioOutput {
     stream_handle: Option<OutputStreamHandle>,
+    /// The device that was asked for.
+    device: AudioOutputDevice,
+    /// The name of the device audio is played on.
+    device_name: Option<String>,
 }
 
-impl Default for AudioOutput {
-    fn default() -> Self {
-        if let Ok((stream, stream_handle)) = OutputStream::try_default() {
-            // We leak `OutputStream` to prevent the audio from stopping.
-            core::mem::forget(stream);
-            Self {
-                stream_handle: Some(stream_handle),
-            }
-        } else {
+impl AudioOutput {
+    /// Opens an output stream on the given device.
+    pub(crate) fn open(device: AudioOutputDevice) -> (Self, Option<OutputStream>) {
+        let Some(found) = device.find() else {
             warn!("No audio device found.");
-            Self {
+            let audio_output = Self {
                 stream_handle: None,
+                device,
+                device_name: None,
+            };
+            return (audio_output, None);
+        };
+
+        let device_name = found.name().ok();
+        if let AudioOutputDevice::Named(name) = &device {
+            if device_name.as_ref() != Some(name) {
+                warn!("Audio device {name} not found, using the default device.");
             }
         }
+        let (stream, stream_handle) = match OutputStream::try_from_device(&found) {
+            Ok((stream, stream_handle)) => (Some(stream), Some(stream_handle)),
+            Err(err) => {
+                warn!("Error opening the audio device: {err}");
+                (None, None)
+            }
+        };
+        let audio_output = Self {
+            stream_handle,
+            device,
+            // Kept even if the device couldn't be opened, so that it isn't retried until it
+            // changes.
+            device_name,
+        };
+        (audio_output, stream)
+    }
+}
+
+/// Keeps the [`OutputStream`] of the [`AudioOutput`] alive.
+pub(crate) struct AudioOutputStream(pub(crate) Option<OutputStream>);
+
+/// The playback state of a sink that was stopped by an audio device change, to continue playing
+/// from where it was on the new device.
+#[derive(Component)]
+pub(crate) struct RestoredPlayback {
+    position: Duration,
+    paused: bool,
+    speed: f32,
+    /// The linear volume of the sound before mixing.
+    base_volume: f32,
+    muted: bool,
+}
+
+impl RestoredPlayback {
+    fn new(sink: &impl AudioSinkPlayback, mix: &AudioMix) -> Self {
+        Self {
+            position: sink.position(),
+            paused: sink.is_paused(),
+            speed: sink.speed(),
+            base_volume: mix.base_volume(sink),
+            muted: sink.is_muted(),
+        }
+    }
+
+    fn apply(&self, sink: &mut impl AudioSinkPlayback) {
+        if let Err(err) = sink.try_seek(self.position) {
+            warn!("Error restoring the playback position after an audio device change: {err:?}");
+        }
+        sink.set_speed(self.speed);
+        if self.muted {
+            sink.mute();
+        } else {
+            sink.unmute();
+        }
+        if self.paused {
+            sink.pause();
+        } else {
+            sink.play();
+        }
     }
 }
 
@@ -96,13 +166,15 @@ impl<'w, 's> EarPositions<'w, 's> {
 pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
     audio_output: Res<AudioOutput>,
     audio_sources: Res<Assets<Source>>,
-    global_volume: Res<GlobalVolume>,
+    mixer: AudioMixer,
     query_nonplaying: Query<
         (
             Entity,
             &AudioPlayer<Source>,
             &PlaybackSettings,
             Option<&GlobalTransform>,
+            Option<&RestoredPlayback>,
+            Option<&AudioBus>,
         ),
         (Without<AudioSink>, Without<SpatialAudioSink>),
     >,
@@ -117,10 +189,17 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
         return;
     };
 
-    for (entity, source_handle, settings, maybe_emitter_transform) in &query_nonplaying {
+    let focused = mixer.focused();
+    for (entity, source_handle, settings, maybe_emitter_transform, restored, bus) in
+        &query_nonplaying
+    {
         let Some(audio_source) = audio_sources.get(&source_handle.0) else {
             continue;
         };
+        let mix = AudioMix {
+            base: restored.map_or(settings.volume.to_linear(), |restored| restored.base_volume),
+            mix: mixer.mix(bus, focused),
+        };
         // audio data is available (has loaded), begin playback and insert sink component
         if settings.spatial {
             let (left_ear, right_ear) = ear_positions.get();
@@ -170,22 +249,29 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
             }
 
             sink.set_speed(settings.speed);
-            sink.set_volume(settings.volume * global_volume.volume);
+            sink.set_volume(Volume::Linear(mix.base * mix.mix));
 
             if settings.paused {
                 sink.pause();
             }
 
+            if let Some(restored) = restored {
+                restored.apply(&mut sink);
+                commands.entity(entity).remove::<RestoredPlayback>();
+            }
+
             match settings.mode {
-                PlaybackMode::Loop | PlaybackMode::Once => commands.entity(entity).insert(sink),
+                PlaybackMode::Loop | PlaybackMode::Once => {
+                    commands.entity(entity).insert((sink, mix))
+                }
                 PlaybackMode::Despawn => commands
                     .entity(entity)
                     // PERF: insert as bundle to reduce archetype moves
-                    .insert((sink, PlaybackDespawnMarker)),
+                    .insert((sink, mix, PlaybackDespawnMarker)),
                 PlaybackMode::Remove => commands
                     .entity(entity)
                     // PERF: insert as bundle to reduce archetype moves
-                    .insert((sink, PlaybackRemoveMarker)),
+                    .insert((sink, mix, PlaybackRemoveMarker)),
             };
         } else {
             let sink = match Sink::try_new(stream_handle) {
@@ -210,22 +296,29 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
             }
 
             sink.set_speed(settings.speed);
-            sink.set_volume(settings.volume * global_volume.volume);
+            sink.set_volume(Volume::Linear(mix.base * mix.mix));
 
             if settings.paused {
                 sink.pause();
             }
 
+            if let Some(restored) = restored {
+                restored.apply(&mut sink);
+                commands.entity(entity).remove::<RestoredPlayback>();
+            }
+
             match settings.mode {
-                PlaybackMode::Loop | PlaybackMode::Once => commands.entity(entity).insert(sink),
+                PlaybackMode::Loop | PlaybackMode::Once => {
+                    commands.entity(entity).insert((sink, mix))
+                }
                 PlaybackMode::Despawn => commands
                     .entity(entity)
                     // PERF: insert as bundle to reduce archetype moves
-                    .insert((sink, PlaybackDespawnMarker)),
+                    .insert((sink, mix, PlaybackDespawnMarker)),
                 PlaybackMode::Remove => commands
                     .entity(entity)
                     // PERF: insert as bundle to reduce archetype moves
-                    .insert((sink, PlaybackRemoveMarker)),
+                    .insert((sink, mix, PlaybackRemoveMarker)),
             };
         }
     }
@@ -332,3 +425,148 @@ pub(crate) fn update_listener_positions(
         sink.set_ears_position(left_ear * scale, right_ear * scale);
     }
 }
+
+/// Pauses the playing audio sinks when the app is suspended, and plays them again when it's
+/// resumed, so that audio doesn't keep playing in the background on mobile platforms.
+pub(crate) fn pause_audio_on_suspend(
+    mut lifecycle_events: EventReader<AppLifecycle>,
+    sinks: Query<(Entity, &AudioSink)>,
+    spatial_sinks: Query<(Entity, &SpatialAudioSink)>,
+    mut paused: Local<Vec<Entity>>,
+) {
+    for event in lifecycle_events.read() {
+        match event {
+            AppLifecycle::WillSuspend => {
+                for (entity, sink) in &sinks {
+                    if !sink.is_paused() {
+                        sink.pause();
+                        paused.push(entity);
+                    }
+                }
+                for (entity, sink) in &spatial_sinks {
+                    if !sink.is_paused() {
+                        sink.pause();
+                        paused.push(entity);
+                    }
+                }
+            }
+            AppLifecycle::WillResume => {
+                for entity in paused.drain(..) {
+                    if let Ok((_, sink)) = sinks.get(entity) {
+                        sink.play();
+                    } else if let Ok((_, sink)) = spatial_sinks.get(entity) {
+                        sink.play();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The state of the checks of the audio devices made by [`update_audio_output`].
+#[derive(Default)]
+pub(crate) struct DeviceCheck {
+    last_check: Option<Instant>,
+    /// Finds the name of the device that [`AudioOutputDevice`] refers to, as listing the devices
+    /// can block for a while on some platforms.
+    task: Option<Task<Option<String>>>,
+}
+
+/// Moves the playing sounds to another audio device when [`AudioOutputDevice`] changes, or when the
+/// device it refers to changes, for example when the default device is unplugged.
+///
+/// The devices are listed on the [`IoTaskPool`], so that checking them doesn't block the app.
+pub(crate) fn update_audio_output(
+    mut audio_output: ResMut<AudioOutput>,
+    mut stream: NonSendMut<AudioOutputStream>,
+    device: Res<AudioOutputDevice>,
+    sinks: Query<(Entity, &AudioSink, &AudioMix)>,
+    spatial_sinks: Query<(Entity, &SpatialAudioSink, &AudioMix)>,
+    mut check: Local<DeviceCheck>,
+    mut commands: Commands,
+) {
+    if *device != audio_output.device {
+        // The result of a pending check is about the previous device.
+        check.task = None;
+        check.last_check = Some(Instant::now());
+    } else if let Some(task) = &mut check.task {
+        let Some(device_name) = check_ready(task) else {
+            return;
+        };
+        check.task = None;
+        if device_name == audio_output.device_name {
+            return;
+        }
+    } else {
+        if check
+            .last_check
+            .is_some_and(|last_check| last_check.elapsed() < DEVICE_CHECK_INTERVAL)
+        {
+            return;
+        }
+        check.last_check = Some(Instant::now());
+        let device = device.clone();
+        check.task = Some(
+            IoTaskPool::get()
+                .spawn(async move { device.find().and_then(|found| found.name().ok()) }),
+        );
+        return;
+    }
+
+    // Drop the previous stream before opening a new one, some platforms only allow one.
+    stream.0 = None;
+    let (new_audio_output, new_stream) = AudioOutput::open(device.clone());
+    *audio_output = new_audio_output;
+    stream.0 = new_stream;
+    if let Some(device_name) = &audio_output.device_name {
+        info!("Playing audio on {device_name}.");
+    }
+
+    // The sinks were playing on the previous stream, so they are recreated on the new one.
+    for (entity, sink, mix) in &sinks {
+        if !sink.empty() {
+            commands
+                .entity(entity)
+                .remove::<AudioSink>()
+                .insert(RestoredPlayback::new(sink, mix));
+        }
+    }
+    for (entity, sink, mix) in &spatial_sinks {
+        if !sink.empty() {
+            commands
+                .entity(entity)
+                .remove::<SpatialAudioSink>()
+                .insert(RestoredPlayback::new(sink, mix));
+        }
+    }
+}
+
+/// Applies changes of the [`GlobalVolume`](crate::GlobalVolume), the
+/// [`AudioBuses`](crate::AudioBuses) and the focus of the app to the playing sinks.
+pub(crate) fn update_audio_mix(
+    mixer: AudioMixer,
+    mut sinks: Query<(&mut AudioSink, &mut AudioMix, Option<&AudioBus>)>,
+    mut spatial_sinks: Query<(&mut SpatialAudioSink, &mut AudioMix, Option<&AudioBus>)>,
+) {
+    let focused = mixer.focused();
+    for (mut sink, mut applied, bus) in &mut sinks {
+        let mix = mixer.mix(bus, focused);
+        if applied.mix != mix {
+            remix(&mut *sink, &mut applied, mix);
+        }
+    }
+    for (mut sink, mut applied, bus) in &mut spatial_sinks {
+        let mix = mixer.mix(bus, focused);
+        if applied.mix != mix {
+            remix(&mut *sink, &mut applied, mix);
+        }
+    }
+}
+
+fn remix(sink: &mut impl AudioSinkPlayback, applied: &mut AudioMix, mix: f32) {
+    // Keep the changes made to the volume of the sink since it was mixed.
+    let base = applied.base_volume(sink);
+    sink.set_volume(Volume::Linear(base * mix));
+    *applied = AudioMix { base, mix };
+}
@@ -0,0 +1,248 @@
+use alloc::borrow::Cow;
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::prelude::*;
+use bevy_window::Window;
+
+use crate::{AudioSinkPlayback, GlobalVolume, Volume};
+
+/// The bus an audio entity plays on, to control the volume of groups of sounds, like music or sound
+/// effects, together with [`AudioBuses`].
+///
+/// Audio entities without this component play on the default bus, [`AudioBuses::default`].
+#[derive(Component, Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component, Debug, PartialEq, Hash, Clone)]
+pub struct AudioBus(pub Cow<'static, str>);
+
+impl AudioBus {
+    /// Creates a bus with the given name.
+    pub const fn new(name: &'static str) -> Self {
+        Self(Cow::Borrowed(name))
+    }
+}
+
+/// What happens to the sounds of a bus when none of the windows have focus.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+pub enum FocusLossBehavior {
+    /// The sounds keep playing at their volume.
+    #[default]
+    Keep,
+    /// The sounds are muted.
+    Mute,
+    /// The volume of the sounds is multiplied by this volume.
+    Duck(Volume),
+}
+
+/// The settings of an audio bus.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq, Clone)]
+pub struct AudioBusSettings {
+    /// The volume of the bus, multiplied with the volume of each of its sounds.
+    pub volume: Volume,
+    /// What happens to the sounds of the bus when none of the windows have focus.
+    pub on_focus_loss: FocusLossBehavior,
+}
+
+impl Default for AudioBusSettings {
+    fn default() -> Self {
+        Self {
+            volume: Volume::Linear(1.0),
+            on_focus_loss: FocusLossBehavior::Keep,
+        }
+    }
+}
+
+/// Use this [`Resource`] to control the volume of [`AudioBus`]es, and whether they are muted or
+/// ducked when the app loses focus.
+///
+/// Changes apply to the sounds that are already playing.
+///
+/// ```
+/// # use bevy_audio::{AudioBus, AudioBusSettings, AudioBuses, FocusLossBehavior, Volume};
+/// const MUSIC: AudioBus = AudioBus::new("music");
+///
+/// let mut buses = AudioBuses::default();
+/// buses.insert(
+///     MUSIC,
+///     AudioBusSettings {
+///         volume: Volume::Linear(0.5),
+///         on_focus_loss: FocusLossBehavior::Duck(Volume::Linear(0.25)),
+///     },
+/// );
+/// // Mute the sound effects and the other sounds on the default bus.
+/// buses.default.on_focus_loss = FocusLossBehavior::Mute;
+/// ```
+#[derive(Resource, Clone, Debug, Default, Reflect)]
+#[reflect(Resource, Debug, Default, Clone)]
+pub struct AudioBuses {
+    /// The settings of the sounds without an [`AudioBus`], and of the buses that weren't
+    /// [inserted](Self::insert).
+    pub default: AudioBusSettings,
+    /// The settings of the inserted buses, by name.
+    buses: HashMap<Cow<'static, str>, AudioBusSettings>,
+}
+
+impl AudioBuses {
+    /// Sets the settings of a bus.
+    pub fn insert(&mut self, bus: AudioBus, settings: AudioBusSettings) -> &mut Self {
+        self.buses.insert(bus.0, settings);
+        self
+    }
+
+    /// Returns the settings of a bus, or the default settings if it wasn't inserted.
+    pub fn get(&self, bus: Option<&AudioBus>) -> &AudioBusSettings {
+        bus.and_then(|bus| self.buses.get(&bus.0))
+            .unwrap_or(&self.default)
+    }
+
+    /// Returns the settings of a bus, to change them, inserting the default settings if it wasn't
+    /// inserted.
+    pub fn get_mut(&mut self, bus: AudioBus) -> &mut AudioBusSettings {
+        let default = self.default;
+        self.buses.entry(bus.0).or_insert(default)
+    }
+}
+
+/// The volume a sink was mixed with, from the [`GlobalVolume`], its [`AudioBus`], and the focus
+/// of the app.
+#[derive(Component, Clone, Copy, Debug)]
+pub(crate) struct AudioMix {
+    /// The linear volume of the sound before mixing.
+    pub(crate) base: f32,
+    /// The linear volume the sound is mixed with.
+    pub(crate) mix: f32,
+}
+
+impl AudioMix {
+    /// Returns the linear volume of the sound played by `sink` before mixing, including the changes
+    /// made to the volume of the sink since it was mixed.
+    pub(crate) fn base_volume(&self, sink: &impl AudioSinkPlayback) -> f32 {
+        let volume = sink.volume().to_linear();
+        if volume == self.base * self.mix {
+            self.base
+        } else if self.mix > 0.0 {
+            volume / self.mix
+        } else {
+            // The volume was changed while the mix was muted.
+            volume
+        }
+    }
+}
+
+/// Computes the volume each sound is mixed with.
+#[derive(SystemParam)]
+pub(crate) struct AudioMixer<'w, 's> {
+    global_volume: Res<'w, GlobalVolume>,
+    buses: Res<'w, AudioBuses>,
+    windows: Query<'w, 's, &'static Window>,
+}
+
+impl AudioMixer<'_, '_> {
+    /// Whether the app has focus. Apps without windows always do.
+    pub(crate) fn focused(&self) -> bool {
+        self.windows.is_empty() || self.windows.iter().any(|window| window.focused)
+    }
+
+    /// Returns the linear volume a sound on the given bus is mixed with.
+    pub(crate) fn mix(&self, bus: Option<&AudioBus>, focused: bool) -> f32 {
+        let settings = self.buses.get(bus);
+        let focus = match settings.on_focus_loss {
+            _ if focused => 1.0,
+            FocusLossBehavior::Keep => 1.0,
+            FocusLossBehavior::Mute => 0.0,
+            FocusLossBehavior::Duck(volume) => volume.to_linear(),
+        };
+        self.global_volume.volume.to_linear() * settings.volume.to_linear() * focus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bus_settings() {
+        const MUSIC: AudioBus = AudioBus::new("music");
+
+        let mut buses = AudioBuses::default();
+        buses.default.volume = Volume::Linear(0.5);
+        assert_eq!(buses.get(Some(&MUSIC)).volume, Volume::Linear(0.5));
+
+        // Inserted from the default settings.
+        buses.get_mut(MUSIC).on_focus_loss = FocusLossBehavior::Mute;
+        assert_eq!(
+            *buses.get(Some(&MUSIC)),
+            AudioBusSettings {
+                volume: Volume::Linear(0.5),
+                on_focus_loss: FocusLossBehavior::Mute,
+            }
+        );
+        assert_eq!(buses.get(None).on_focus_loss, FocusLossBehavior::Keep);
+    }
+
+    #[test]
+    fn base_volume() {
+        let (sink, _queue_rx) = rodio::Sink::new_idle();
+        let mut sink = crate::AudioSink::new(sink);
+        let mut mix = AudioMix {
+            base: 0.8,
+            mix: 0.5,
+        };
+        sink.set_volume(Volume::Linear(0.4));
+        assert_eq!(mix.base_volume(&sink), 0.8);
+
+        // Muting the mix keeps the volume of the sound.
+        mix.mix = 0.0;
+        sink.set_volume(Volume::Linear(0.0));
+        assert_eq!(mix.base_volume(&sink), 0.8);
+
+        // The volume of the sink was changed since it was mixed.
+        mix.mix = 0.5;
+        sink.set_volume(Volume::Linear(0.2));
+        assert_eq!(mix.base_volume(&sink), 0.4);
+    }
+
+    #[cfg(feature = "bevy_settings")]
+    #[test]
+    fn persist_buses() {
+        use bevy_app::App;
+        use bevy_ecs::reflect::AppTypeRegistry;
+        use bevy_settings::{AppSettingsExt, SettingsPlugin, SettingsStore};
+
+        const MUSIC: AudioBus = AudioBus::new("music");
+
+        let path = std::env::temp_dir().join("bevy_audio_persist_buses.ron");
+        let settings = AudioBusSettings {
+            volume: Volume::Linear(0.5),
+            on_focus_loss: FocusLossBehavior::Duck(Volume::Linear(0.25)),
+        };
+        let mut app = App::new();
+        app.add_plugins(SettingsPlugin {
+            path: Some(path.clone()),
+            ..Default::default()
+        })
+        .register_settings::<AudioBuses>();
+        app.world_mut()
+            .resource_mut::<AudioBuses>()
+            .insert(MUSIC, settings);
+        let world = app.world();
+        let contents = world
+            .resource::<SettingsStore>()
+            .serialize(world, &world.resource::<AppTypeRegistry>().read())
+            .unwrap();
+        std::fs::write(&path, contents).unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(SettingsPlugin {
+            path: Some(path.clone()),
+            ..Default::default()
+        })
+        .register_settings::<AudioBuses>();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            *app.world().resource::<AudioBuses>().get(Some(&MUSIC)),
+            settings
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use alloc::{string::String, vec::Vec};
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use rodio::cpal::{
+    traits::{DeviceTrait, HostTrait},
+    Device,
+};
+
+/// Use this [`Resource`] to choose the device audio is played on.
+///
+/// The device can be changed at any time, and the playing sounds move to the new device,
+/// continuing from where they were.
+///
+/// With [`AudioOutputDevice::Default`], the sounds also follow the default device of the system
+/// when it changes, for example when headphones are unplugged. When a
+/// [named](AudioOutputDevice::Named) device is missing, the default device is used until it is
+/// available again.
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Resource, Debug, Default, PartialEq, Clone)]
+pub enum AudioOutputDevice {
+    /// The default output device of the system.
+    #[default]
+    Default,
+    /// The output device with the given name, from [`AudioOutputDevice::available`].
+    Named(String),
+}
+
+impl AudioOutputDevice {
+    /// Returns the names of the audio output devices of the system.
+    ///
+    /// This can be slow on some platforms, so avoid calling it every frame.
+    pub fn available() -> Vec<String> {
+        let Ok(devices) = rodio::cpal::default_host().output_devices() else {
+            return Vec::new();
+        };
+        devices.filter_map(|device| device.name().ok()).collect()
+    }
+
+    /// Finds the device to play audio on, falling back to the default device if a named device is
+    /// missing.
+    pub(crate) fn find(&self) -> Option<Device> {
+        let host = rodio::cpal::default_host();
+        if let AudioOutputDevice::Named(name) = self {
+            let device = host.output_devices().ok().and_then(|mut devices| {
+                devices.find(|device| device.name().is_ok_and(|device_name| device_name == *name))
+            });
+            if device.is_some() {
+                return device;
+            }
+        }
+        host.default_output_device()
+    }
+}
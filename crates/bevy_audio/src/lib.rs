@@ -32,6 +32,8 @@ extern crate alloc;
 mod audio;
 mod audio_output;
 mod audio_source;
+mod bus;
+mod device;
 mod pitch;
 mod sinks;
 mod volume;
@@ -42,17 +44,24 @@ mod volume;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        AudioPlayer, AudioSink, AudioSinkPlayback, AudioSource, Decodable, GlobalVolume, Pitch,
-        PlaybackSettings, SpatialAudioSink, SpatialListener,
+        AudioBus, AudioBuses, AudioOutputDevice, AudioPlayer, AudioSink, AudioSinkPlayback,
+        AudioSource, Decodable, GlobalVolume, Pitch, PlaybackSettings, SpatialAudioSink,
+        SpatialListener,
     };
 }
 
 pub use audio::*;
 pub use audio_source::*;
+pub use bus::*;
+pub use device::*;
 pub use pitch::*;
 pub use volume::*;
 
-pub use rodio::{cpal::Sample as CpalSample, source::Source, Sample};
+pub use rodio::{
+    cpal::Sample as CpalSample,
+    source::{SeekError, Source},
+    Sample,
+};
 pub use sinks::*;
 
 use bevy_app::prelude::*;
@@ -69,9 +78,14 @@ struct AudioPlaySet;
 /// Adds support for audio playback to a Bevy Application
 ///
 /// Insert an [`AudioPlayer`] onto your entities to play audio.
+///
+/// With the `bevy_settings` feature, the [`GlobalVolume`] and the [`AudioBuses`] are persisted as
+/// user settings when the `SettingsPlugin` of `bevy_settings` is added before this plugin.
 #[derive(Default)]
 pub struct AudioPlugin {
     /// The global volume for all audio entities.
+    ///
+    /// A global volume stored in the user settings replaces it.
     pub global_volume: GlobalVolume,
     /// The scale factor applied to the positions of audio sources and listeners for
     /// spatial audio.
@@ -86,7 +100,11 @@ impl Plugin for AudioPlugin {
             .register_type::<DefaultSpatialScale>()
             .register_type::<PlaybackMode>()
             .register_type::<PlaybackSettings>()
+            .register_type::<AudioOutputDevice>()
+            .register_type::<AudioBus>()
+            .register_type::<AudioBuses>()
             .insert_resource(self.global_volume)
+            .init_resource::<AudioBuses>()
             .insert_resource(DefaultSpatialScale(self.default_spatial_scale))
             .configure_sets(
                 PostUpdate,
@@ -96,9 +114,37 @@ impl Plugin for AudioPlugin {
             )
             .add_systems(
                 PostUpdate,
-                (update_emitter_positions, update_listener_positions).in_set(AudioPlaySet),
+                (
+                    update_emitter_positions,
+                    update_listener_positions,
+                    update_audio_mix,
+                )
+                    .in_set(AudioPlaySet),
             )
-            .init_resource::<AudioOutput>();
+            .add_systems(
+                PostUpdate,
+                pause_audio_on_suspend
+                    .run_if(resource_exists::<Events<bevy_window::AppLifecycle>>)
+                    .in_set(AudioPlaySet),
+            )
+            .add_systems(PostUpdate, update_audio_output.before(AudioPlaySet))
+            .init_resource::<AudioOutputDevice>();
+
+        #[cfg(feature = "bevy_settings")]
+        if app
+            .world()
+            .contains_resource::<bevy_settings::SettingsStore>()
+        {
+            use bevy_settings::AppSettingsExt;
+
+            app.register_settings::<GlobalVolume>()
+                .register_settings::<AudioBuses>();
+        }
+
+        let (audio_output, stream) =
+            AudioOutput::open(app.world().resource::<AudioOutputDevice>().clone());
+        app.insert_resource(audio_output)
+            .insert_non_send_resource(AudioOutputStream(stream));
 
         #[cfg(any(feature = "mp3", feature = "flac", feature = "wav", feature = "vorbis"))]
         {
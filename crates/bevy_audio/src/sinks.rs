@@ -1,7 +1,8 @@
 use bevy_ecs::component::Component;
 use bevy_math::Vec3;
 use bevy_transform::prelude::Transform;
-use rodio::{Sink, SpatialSink};
+use core::time::Duration;
+use rodio::{source::SeekError, Sink, SpatialSink};
 
 use crate::Volume;
 
@@ -36,6 +37,14 @@ pub trait AudioSinkPlayback {
     /// will change the play speed of the sound.
     fn set_speed(&self, speed: f32);
 
+    /// Gets the playback position of the sound.
+    fn position(&self) -> Duration;
+
+    /// Seeks to the given position in the sound.
+    ///
+    /// This fails if the source doesn't support seeking.
+    fn try_seek(&self, position: Duration) -> Result<(), SeekError>;
+
     /// Resumes playback of a paused sink.
     ///
     /// No effect if not paused.
@@ -156,6 +165,14 @@ impl AudioSinkPlayback for AudioSink {
         self.sink.set_speed(speed);
     }
 
+    fn position(&self) -> Duration {
+        self.sink.get_pos()
+    }
+
+    fn try_seek(&self, position: Duration) -> Result<(), SeekError> {
+        self.sink.try_seek(position)
+    }
+
     fn play(&self) {
         self.sink.play();
     }
@@ -252,6 +269,14 @@ impl AudioSinkPlayback for SpatialAudioSink {
         self.sink.set_speed(speed);
     }
 
+    fn position(&self) -> Duration {
+        self.sink.get_pos()
+    }
+
+    fn try_seek(&self, position: Duration) -> Result<(), SeekError> {
+        self.sink.try_seek(position)
+    }
+
     fn play(&self) {
         self.sink.play();
     }
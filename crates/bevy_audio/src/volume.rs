@@ -4,7 +4,7 @@ use bevy_reflect::prelude::*;
 
 /// Use this [`Resource`] to control the global volume of all audio.
 ///
-/// Note: Changing [`GlobalVolume`] does not affect already playing audio.
+/// Changing [`GlobalVolume`] also affects the audio that is already playing.
 #[derive(Resource, Debug, Default, Clone, Copy, Reflect)]
 #[reflect(Resource, Debug, Default, Clone)]
 pub struct GlobalVolume {
@@ -88,6 +88,9 @@ impl Plugin for SkyboxPlugin {
 /// Note that this component does not (currently) affect the scene's lighting.
 /// To do so, use `EnvironmentMapLight` alongside this component.
 ///
+/// An equirectangular panorama, such as an HDR environment image, can be converted to a cubemap
+/// with [`Image::equirectangular_to_cubemap`].
+///
 /// See also <https://en.wikipedia.org/wiki/Skybox_(video_games)>.
 #[derive(Component, Clone, Reflect)]
 #[reflect(Component, Default, Clone)]
@@ -0,0 +1,191 @@
+//! Module containing logic for the diagnostics overlay.
+
+use bevy_app::{Plugin, Startup, Update};
+use bevy_asset::Handle;
+use bevy_color::Color;
+use bevy_diagnostic::{
+    DiagnosticPath, DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin,
+};
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    component::Component,
+    entity::Entity,
+    prelude::Local,
+    query::With,
+    resource::Resource,
+    schedule::{common_conditions::resource_changed, IntoScheduleConfigs},
+    system::{Commands, Query, Res},
+};
+use bevy_render::view::Visibility;
+use bevy_text::{Font, TextColor, TextFont};
+use bevy_time::Time;
+use bevy_ui::{
+    widget::{Text, TextUiWriter},
+    GlobalZIndex, Node, PositionType, Val,
+};
+use core::{fmt::Write, time::Duration};
+
+/// [`GlobalZIndex`] used to render the diagnostics overlay.
+///
+/// It is right under the [`FPS_OVERLAY_ZINDEX`](crate::fps_overlay::FPS_OVERLAY_ZINDEX).
+pub const DIAGNOSTICS_OVERLAY_ZINDEX: i32 = i32::MAX - 33;
+
+/// A plugin that adds an overlay to the Bevy application, showing the values of diagnostics from
+/// the [`DiagnosticsStore`].
+///
+/// By default, it shows the FPS, the frame time and the entity count, and adds the
+/// [`FrameTimeDiagnosticsPlugin`] and [`EntityCountDiagnosticsPlugin`] if they weren't added
+/// before.
+#[derive(Default)]
+pub struct DiagnosticsOverlayPlugin {
+    /// Starting configuration of overlay, this can be later be changed through
+    /// [`DiagnosticsOverlayConfig`] resource.
+    pub config: DiagnosticsOverlayConfig,
+}
+
+impl Plugin for DiagnosticsOverlayPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        // TODO: Use plugin dependencies, see https://github.com/bevyengine/bevy/issues/69
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+        }
+        if !app.is_plugin_added::<EntityCountDiagnosticsPlugin>() {
+            app.add_plugins(EntityCountDiagnosticsPlugin);
+        }
+        app.insert_resource(self.config.clone())
+            .add_systems(Startup, setup)
+            .add_systems(
+                Update,
+                (
+                    (customize_text, toggle_display)
+                        .run_if(resource_changed::<DiagnosticsOverlayConfig>),
+                    update_text,
+                ),
+            );
+    }
+}
+
+/// Configuration options for the diagnostics overlay.
+#[derive(Resource, Clone)]
+pub struct DiagnosticsOverlayConfig {
+    /// Configuration of text in the overlay.
+    pub text_config: TextFont,
+    /// Color of text in the overlay.
+    pub text_color: Color,
+    /// Displays the diagnostics overlay if true.
+    pub enabled: bool,
+    /// The period after which the diagnostics overlay re-renders.
+    ///
+    /// Defaults to once every 100 ms.
+    pub refresh_interval: Duration,
+    /// The diagnostics to show, one per line. Each path also selects the diagnostics below it.
+    ///
+    /// Defaults to the FPS, the frame time and the entity count.
+    pub diagnostics: Vec<DiagnosticPath>,
+}
+
+impl Default for DiagnosticsOverlayConfig {
+    fn default() -> Self {
+        DiagnosticsOverlayConfig {
+            text_config: TextFont {
+                font: Handle::<Font>::default(),
+                font_size: 16.0,
+                ..Default::default()
+            },
+            text_color: Color::WHITE,
+            enabled: true,
+            refresh_interval: Duration::from_millis(100),
+            diagnostics: vec![
+                FrameTimeDiagnosticsPlugin::FPS,
+                FrameTimeDiagnosticsPlugin::FRAME_TIME,
+                EntityCountDiagnosticsPlugin::ENTITY_COUNT,
+            ],
+        }
+    }
+}
+
+#[derive(Component)]
+struct DiagnosticsText;
+
+fn setup(mut commands: Commands, overlay_config: Res<DiagnosticsOverlayConfig>) {
+    commands
+        .spawn((
+            Node {
+                // We need to make sure the overlay doesn't affect the position of other UI nodes,
+                // and doesn't cover the FPS overlay
+                position_type: PositionType::Absolute,
+                right: Val::Px(0.0),
+                ..Default::default()
+            },
+            // Render overlay on top of everything
+            GlobalZIndex(DIAGNOSTICS_OVERLAY_ZINDEX),
+        ))
+        .with_child((
+            Text::default(),
+            overlay_config.text_config.clone(),
+            TextColor(overlay_config.text_color),
+            DiagnosticsText,
+        ));
+}
+
+fn update_text(
+    diagnostics: Res<DiagnosticsStore>,
+    query: Query<Entity, With<DiagnosticsText>>,
+    mut writer: TextUiWriter,
+    time: Res<Time>,
+    config: Res<DiagnosticsOverlayConfig>,
+    mut time_since_rerender: Local<Duration>,
+) {
+    *time_since_rerender += time.delta();
+    if !config.enabled || *time_since_rerender < config.refresh_interval {
+        return;
+    }
+    *time_since_rerender = Duration::ZERO;
+
+    let mut text = String::new();
+    for diagnostic in config
+        .diagnostics
+        .iter()
+        .flat_map(|path| diagnostics.iter_prefixed(path))
+        .filter(|diagnostic| diagnostic.is_enabled)
+    {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        let _ = write!(text, "{}: ", diagnostic.path());
+        match diagnostic.smoothed() {
+            Some(value) => {
+                let _ = write!(text, "{value:.2}{}", diagnostic.suffix);
+            }
+            None => text.push('-'),
+        }
+    }
+    for entity in &query {
+        *writer.text(entity, 0) = text.clone();
+    }
+}
+
+fn customize_text(
+    overlay_config: Res<DiagnosticsOverlayConfig>,
+    query: Query<Entity, With<DiagnosticsText>>,
+    mut writer: TextUiWriter,
+) {
+    for entity in &query {
+        writer.for_each_font(entity, |mut font| {
+            *font = overlay_config.text_config.clone();
+        });
+        writer.for_each_color(entity, |mut color| color.0 = overlay_config.text_color);
+    }
+}
+
+fn toggle_display(
+    overlay_config: Res<DiagnosticsOverlayConfig>,
+    mut query: Query<&mut Visibility, With<DiagnosticsText>>,
+) {
+    for mut visibility in &mut query {
+        visibility.set_if_neq(match overlay_config.enabled {
+            true => Visibility::Visible,
+            false => Visibility::Hidden,
+        });
+    }
+}
@@ -13,10 +13,15 @@ use bevy_app::prelude::*;
 #[cfg(feature = "bevy_ci_testing")]
 pub mod ci_testing;
 
+pub mod diagnostics_overlay;
+
 pub mod fps_overlay;
 
 pub mod picking_debug;
 
+#[cfg(feature = "bevy_gizmos")]
+pub mod skinned_mesh_gizmos;
+
 pub mod states;
 
 /// Enables developer tools in an [`App`]. This plugin is added automatically with `bevy_dev_tools`
@@ -0,0 +1,286 @@
+//! Debug visualization of the skeletons and joint weights of skinned meshes.
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::Assets;
+use bevy_color::{
+    palettes::css::{BLUE, RED, WHITE, YELLOW},
+    Color, Mix,
+};
+use bevy_ecs::{hierarchy::ChildOf, name::Name, prelude::*};
+use bevy_gizmos::{
+    config::{GizmoConfigGroup, GizmoConfigStore},
+    gizmos::Gizmos,
+    AppGizmoBuilder,
+};
+use bevy_math::{Mat4, Vec2, Vec3};
+use bevy_platform_support::collections::HashSet;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::Camera,
+    mesh::{
+        skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+        Mesh, Mesh3d, VertexAttributeValues,
+    },
+};
+use bevy_text::{TextColor, TextFont};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+use bevy_ui::{widget::Text, Display, Node, PositionType, UiScale, UiSystem, UiTargetCamera, Val};
+
+/// A [`Plugin`] that draws the skeletons of [`SkinnedMesh`]es with gizmos, labels their joints
+/// with their [`Name`], and draws heatmaps of the weights of their joints, to diagnose broken
+/// imports and skinning artifacts.
+///
+/// Add [`ShowSkinnedMeshGizmo`] to an entity with a [`SkinnedMesh`] to debug it, or set
+/// [`SkinnedMeshGizmoConfigGroup::draw_all`] to draw the skeletons of all skinned meshes.
+///
+/// Requires the `GizmoPlugin`, and the `UiPlugin` for the joint names.
+pub struct SkinnedMeshGizmoPlugin;
+
+impl Plugin for SkinnedMeshGizmoPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SkinnedMeshGizmoConfigGroup>()
+            .register_type::<ShowSkinnedMeshGizmo>()
+            .init_gizmo_group::<SkinnedMeshGizmoConfigGroup>()
+            .add_systems(
+                PostUpdate,
+                (
+                    (draw_skeletons, draw_joint_weights).after(TransformSystem::TransformPropagate),
+                    update_joint_labels.before(UiSystem::Layout),
+                ),
+            );
+    }
+}
+
+/// The [`GizmoConfigGroup`] used to draw the skeletons of [`SkinnedMesh`]es.
+#[derive(Clone, Reflect, GizmoConfigGroup)]
+#[reflect(Clone, Default)]
+pub struct SkinnedMeshGizmoConfigGroup {
+    /// Draw the skeletons of all skinned meshes if true, and not only of the ones with a
+    /// [`ShowSkinnedMeshGizmo`].
+    ///
+    /// Defaults to `false`.
+    pub draw_all: bool,
+    /// The color of the bones, drawn from each joint to its parent joint.
+    ///
+    /// Defaults to yellow.
+    pub bone_color: Color,
+    /// The radius of the spheres drawn at the joints.
+    ///
+    /// Defaults to `0.02`.
+    pub joint_radius: f32,
+    /// Whether the joints of the skinned meshes with a [`ShowSkinnedMeshGizmo`] are labeled with
+    /// their [`Name`].
+    ///
+    /// Defaults to `true`.
+    pub draw_joint_names: bool,
+    /// The half size of the crosses drawn at the vertices for the weight heatmaps.
+    ///
+    /// Defaults to `0.005`.
+    pub vertex_size: f32,
+}
+
+impl Default for SkinnedMeshGizmoConfigGroup {
+    fn default() -> Self {
+        Self {
+            draw_all: false,
+            bone_color: YELLOW.into(),
+            joint_radius: 0.02,
+            draw_joint_names: true,
+            vertex_size: 0.005,
+        }
+    }
+}
+
+/// Add this [`Component`] to an entity with a [`SkinnedMesh`] to draw its skeleton.
+#[derive(Component, Reflect, Default, Debug, Clone)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct ShowSkinnedMeshGizmo {
+    /// The joint whose weights are drawn at each vertex of the mesh, from blue for vertices it
+    /// doesn't influence to red for vertices it fully moves. If [`None`], no weights are drawn.
+    ///
+    /// Defaults to [`None`].
+    pub weights_of: Option<Entity>,
+}
+
+/// A text label following a joint of a skinned mesh.
+#[derive(Component)]
+struct JointLabel(Entity);
+
+fn draw_skeletons(
+    skinned_meshes: Query<(&SkinnedMesh, Has<ShowSkinnedMeshGizmo>)>,
+    joints: Query<(&GlobalTransform, Option<&ChildOf>)>,
+    mut gizmos: Gizmos<SkinnedMeshGizmoConfigGroup>,
+) {
+    let color = gizmos.config_ext.bone_color;
+    let radius = gizmos.config_ext.joint_radius;
+    for (skinned_mesh, show) in &skinned_meshes {
+        if !show && !gizmos.config_ext.draw_all {
+            continue;
+        }
+        for &joint in &skinned_mesh.joints {
+            let Ok((transform, child_of)) = joints.get(joint) else {
+                continue;
+            };
+            let position = transform.translation();
+            gizmos.sphere(position, radius, color).resolution(8);
+
+            // Only draw the bones between joints, not to the other ancestors.
+            let Some(parent) = child_of
+                .map(|child_of| child_of.parent)
+                .filter(|parent| skinned_mesh.joints.contains(parent))
+            else {
+                continue;
+            };
+            if let Ok((parent_transform, _)) = joints.get(parent) {
+                gizmos.line(parent_transform.translation(), position, color);
+            }
+        }
+    }
+}
+
+fn draw_joint_weights(
+    skinned_meshes: Query<(&SkinnedMesh, &Mesh3d, &ShowSkinnedMeshGizmo)>,
+    joints: Query<&GlobalTransform>,
+    meshes: Res<Assets<Mesh>>,
+    inverse_bindposes: Res<Assets<SkinnedMeshInverseBindposes>>,
+    mut gizmos: Gizmos<SkinnedMeshGizmoConfigGroup>,
+) {
+    let size = gizmos.config_ext.vertex_size;
+    for (skinned_mesh, mesh, show) in &skinned_meshes {
+        let Some(selected) = show.weights_of.and_then(|selected| {
+            skinned_mesh
+                .joints
+                .iter()
+                .position(|&joint| joint == selected)
+        }) else {
+            continue;
+        };
+        let (Some(mesh), Some(inverse_bindposes)) = (
+            meshes.get(&mesh.0),
+            inverse_bindposes.get(&skinned_mesh.inverse_bindposes),
+        ) else {
+            continue;
+        };
+        let (
+            Some(VertexAttributeValues::Float32x3(positions)),
+            Some(VertexAttributeValues::Uint16x4(joint_indices)),
+            Some(VertexAttributeValues::Float32x4(joint_weights)),
+        ) = (
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION),
+            mesh.attribute(Mesh::ATTRIBUTE_JOINT_INDEX),
+            mesh.attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT),
+        )
+        else {
+            continue;
+        };
+
+        // The same matrices the mesh is skinned with on the GPU.
+        let joint_matrices: Vec<Mat4> = skinned_mesh
+            .joints
+            .iter()
+            .zip(inverse_bindposes.iter())
+            .map(|(&joint, inverse_bindpose)| {
+                joints.get(joint).map_or(Mat4::IDENTITY, |transform| {
+                    transform.compute_matrix() * *inverse_bindpose
+                })
+            })
+            .collect();
+
+        for ((position, indices), weights) in positions.iter().zip(joint_indices).zip(joint_weights)
+        {
+            let mut skinned_position = Vec3::ZERO;
+            let mut selected_weight = 0.0;
+            for (&index, &weight) in indices.iter().zip(weights) {
+                let Some(matrix) = joint_matrices.get(index as usize) else {
+                    continue;
+                };
+                skinned_position += weight * matrix.transform_point3(Vec3::from(*position));
+                if index as usize == selected {
+                    selected_weight += weight;
+                }
+            }
+            let color = BLUE.mix(&RED, selected_weight.clamp(0.0, 1.0));
+            gizmos.cross(skinned_position, size, color);
+        }
+    }
+}
+
+fn update_joint_labels(
+    mut commands: Commands,
+    config: Res<GizmoConfigStore>,
+    skinned_meshes: Query<&SkinnedMesh, With<ShowSkinnedMeshGizmo>>,
+    joints: Query<(&GlobalTransform, Option<&Name>)>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+    mut labels: Query<(Entity, &JointLabel, &mut Node)>,
+    scale: Res<UiScale>,
+) {
+    let (_, config) = config.config::<SkinnedMeshGizmoConfigGroup>();
+    // Label the joints in the view of the active camera rendered last, usually the main one.
+    let camera = cameras
+        .iter()
+        .filter(|(_, camera, _)| camera.is_active)
+        .max_by_key(|(_, camera, _)| camera.order);
+    let mut unlabeled: HashSet<Entity> = match camera {
+        Some(_) if config.draw_joint_names => skinned_meshes
+            .iter()
+            .flat_map(|skinned_mesh| skinned_mesh.joints.iter().copied())
+            .filter(|&joint| joints.contains(joint))
+            .collect(),
+        _ => HashSet::default(),
+    };
+
+    let label_position = |joint: Entity| -> Option<Vec2> {
+        let (_, camera, camera_transform) = camera?;
+        let (transform, _) = joints.get(joint).ok()?;
+        let position = camera
+            .world_to_viewport(camera_transform, transform.translation())
+            .ok()?;
+        let viewport_min = camera
+            .logical_viewport_rect()
+            .map_or(Vec2::ZERO, |viewport| viewport.min);
+        Some((position - viewport_min) / scale.0)
+    };
+    let place = |node: &mut Node, position: Option<Vec2>| match position {
+        Some(position) => {
+            node.display = Display::Flex;
+            node.left = Val::Px(position.x);
+            node.top = Val::Px(position.y);
+        }
+        None => node.display = Display::None,
+    };
+
+    for (entity, label, mut node) in &mut labels {
+        if unlabeled.remove(&label.0) {
+            place(&mut node, label_position(label.0));
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    let Some((camera_entity, _, _)) = camera else {
+        return;
+    };
+    for joint in unlabeled {
+        let name = joints
+            .get(joint)
+            .ok()
+            .and_then(|(_, name)| name)
+            .map_or_else(|| format!("{joint}"), |name| name.to_string());
+        let mut node = Node {
+            position_type: PositionType::Absolute,
+            ..Default::default()
+        };
+        place(&mut node, label_position(joint));
+        commands.spawn((
+            JointLabel(joint),
+            Text::new(name),
+            TextFont {
+                font_size: 10.0,
+                ..Default::default()
+            },
+            TextColor(WHITE.into()),
+            node,
+            UiTargetCamera(camera_entity),
+        ));
+    }
+}
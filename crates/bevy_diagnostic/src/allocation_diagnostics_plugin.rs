@@ -0,0 +1,194 @@
+use bevy_app::prelude::*;
+use bevy_ecs::system::Local;
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use log::warn;
+use std::alloc::System;
+
+use crate::{Diagnostic, DiagnosticPath, DiagnosticSmoothing, Diagnostics, RegisterDiagnostic};
+
+const BYTES_TO_MIB: f64 = 1.0 / 1024.0 / 1024.0;
+
+/// The number of bytes currently allocated.
+static HEAP_USAGE: AtomicUsize = AtomicUsize::new(0);
+/// The number of allocations since the app started.
+static ALLOCATION_COUNT: AtomicUsize = AtomicUsize::new(0);
+/// The number of bytes allocated since the app started.
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// A global allocator counting the allocations made through it, for the
+/// [`AllocationDiagnosticsPlugin`].
+///
+/// It forwards the allocations to another allocator, the [`System`] one by default, and must be
+/// set as the global allocator of the app:
+///
+/// ```
+/// use bevy_diagnostic::TrackingAllocator;
+/// use std::alloc::System;
+///
+/// #[global_allocator]
+/// static GLOBAL: TrackingAllocator = TrackingAllocator::new(System);
+/// # fn main() {}
+/// ```
+///
+/// The counters are shared by all the tracking allocators.
+pub struct TrackingAllocator<A = System> {
+    allocator: A,
+}
+
+impl<A> TrackingAllocator<A> {
+    /// Creates a tracking allocator forwarding the allocations to `allocator`.
+    pub const fn new(allocator: A) -> Self {
+        Self { allocator }
+    }
+}
+
+impl TrackingAllocator {
+    /// Returns the number of bytes currently allocated.
+    pub fn heap_usage() -> usize {
+        HEAP_USAGE.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of allocations since the app started, including reallocations.
+    pub fn allocation_count() -> usize {
+        ALLOCATION_COUNT.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of bytes allocated since the app started, including reallocations.
+    pub fn allocated_bytes() -> usize {
+        ALLOCATED_BYTES.load(Ordering::Relaxed)
+    }
+}
+
+fn record_allocation(size: usize) {
+    HEAP_USAGE.fetch_add(size, Ordering::Relaxed);
+    ALLOCATION_COUNT.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+}
+
+#[expect(unsafe_code, reason = "GlobalAlloc is an unsafe trait.")]
+// SAFETY: All the calls are forwarded to the wrapped allocator, which upholds the contract.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: The caller upholds the contract of `GlobalAlloc::alloc`.
+        let ptr = unsafe { self.allocator.alloc(layout) };
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: The caller upholds the contract of `GlobalAlloc::alloc_zeroed`.
+        let ptr = unsafe { self.allocator.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: The caller upholds the contract of `GlobalAlloc::dealloc`.
+        unsafe { self.allocator.dealloc(ptr, layout) };
+        HEAP_USAGE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: The caller upholds the contract of `GlobalAlloc::realloc`.
+        let new_ptr = unsafe { self.allocator.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            HEAP_USAGE.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_allocation(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Adds "heap usage", "allocation count" and "allocated bytes" diagnostics to an App.
+///
+/// The counts are the allocations made during the previous frame, to find the frames where
+/// spikes of allocations cause hitches. The measurements come from the [`TrackingAllocator`],
+/// which must be set as the global allocator.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct AllocationDiagnosticsPlugin;
+
+impl Plugin for AllocationDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        if TrackingAllocator::allocation_count() == 0 {
+            warn!(
+                "AllocationDiagnosticsPlugin was added, but TrackingAllocator isn't the global allocator, so no allocations will be measured"
+            );
+        }
+
+        app.register_diagnostic(
+            Diagnostic::new(Self::HEAP_USAGE)
+                .with_suffix("MiB")
+                .with_smoothing(DiagnosticSmoothing::Latest),
+        )
+        .register_diagnostic(Diagnostic::new(Self::ALLOCATION_COUNT))
+        .register_diagnostic(Diagnostic::new(Self::ALLOCATED_BYTES).with_suffix(" B"))
+        .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+impl AllocationDiagnosticsPlugin {
+    /// The memory currently allocated on the heap, in MiB.
+    pub const HEAP_USAGE: DiagnosticPath = DiagnosticPath::const_new("allocations/heap_usage");
+    /// The number of allocations during the previous frame.
+    pub const ALLOCATION_COUNT: DiagnosticPath = DiagnosticPath::const_new("allocations/count");
+    /// The number of bytes allocated during the previous frame.
+    pub const ALLOCATED_BYTES: DiagnosticPath = DiagnosticPath::const_new("allocations/bytes");
+
+    pub fn diagnostic_system(
+        mut diagnostics: Diagnostics,
+        mut previous_totals: Local<Option<(usize, usize)>>,
+    ) {
+        let totals = (
+            TrackingAllocator::allocation_count(),
+            TrackingAllocator::allocated_bytes(),
+        );
+        diagnostics.add_measurement(&Self::HEAP_USAGE, || {
+            TrackingAllocator::heap_usage() as f64 * BYTES_TO_MIB
+        });
+        if let Some((count, bytes)) = previous_totals.replace(totals) {
+            diagnostics.add_measurement(&Self::ALLOCATION_COUNT, || {
+                totals.0.wrapping_sub(count) as f64
+            });
+            diagnostics.add_measurement(&Self::ALLOCATED_BYTES, || {
+                totals.1.wrapping_sub(bytes) as f64
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[expect(unsafe_code, reason = "Allocates through the GlobalAlloc trait.")]
+    fn tracks_allocations() {
+        let allocator = TrackingAllocator::new(System);
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        let count = TrackingAllocator::allocation_count();
+        let bytes = TrackingAllocator::allocated_bytes();
+
+        // SAFETY: The layout has a non-zero size.
+        let ptr = unsafe { allocator.alloc(layout) };
+        assert!(!ptr.is_null());
+        // SAFETY: `ptr` was allocated by `allocator` with `layout`, and the new size is non-zero.
+        let ptr = unsafe { allocator.realloc(ptr, layout, 128) };
+        assert!(!ptr.is_null());
+        // SAFETY: `ptr` was reallocated by `allocator` to 128 bytes.
+        unsafe { allocator.dealloc(ptr, Layout::from_size_align(128, 8).unwrap()) };
+
+        assert_eq!(TrackingAllocator::allocation_count() - count, 2);
+        assert_eq!(TrackingAllocator::allocated_bytes() - bytes, 192);
+    }
+}
@@ -1,4 +1,4 @@
-use alloc::{borrow::Cow, collections::VecDeque, string::String};
+use alloc::{borrow::Cow, collections::VecDeque, string::String, vec::Vec};
 use core::{
     hash::{Hash, Hasher},
     time::Duration,
@@ -10,7 +10,10 @@ use bevy_ecs::system::{Deferred, Res, SystemBuffer, SystemParam};
 use bevy_platform_support::{collections::HashMap, hash::PassHash, time::Instant};
 use const_fnv1a_hash::fnv1a_hash_str_64;
 
-use crate::DEFAULT_MAX_HISTORY_LENGTH;
+use crate::{
+    DiagnosticClock, DiagnosticsClock, DiagnosticsSnapshot, InstantClock,
+    DEFAULT_MAX_HISTORY_LENGTH,
+};
 
 /// Unique diagnostic path, separated by `/`.
 ///
@@ -82,6 +85,21 @@ impl DiagnosticPath {
     pub fn components(&self) -> impl Iterator<Item = &str> + '_ {
         self.path.split('/')
     }
+
+    /// Returns `true` if `prefix` is this path, or one of its parents.
+    ///
+    /// ```
+    /// # use bevy_diagnostic::DiagnosticPath;
+    /// let path = DiagnosticPath::const_new("render/pass/opaque");
+    /// assert!(path.starts_with(&DiagnosticPath::const_new("render/pass")));
+    /// assert!(path.starts_with(&path));
+    /// assert!(!path.starts_with(&DiagnosticPath::const_new("render/pa")));
+    /// ```
+    pub fn starts_with(&self, prefix: &DiagnosticPath) -> bool {
+        self.path
+            .strip_prefix(prefix.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    }
 }
 
 impl From<DiagnosticPath> for String {
@@ -111,12 +129,77 @@ impl core::fmt::Display for DiagnosticPath {
 }
 
 /// A single measurement of a [`Diagnostic`].
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct DiagnosticMeasurement {
+    /// The wall-clock time the value was measured at.
     pub time: Instant,
     pub value: f64,
 }
 
+/// How the [`smoothed`](Diagnostic::smoothed) value of a [`Diagnostic`] is
+/// computed from its measurements.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiagnosticSmoothing {
+    /// The latest value, without any smoothing. Suited to counters.
+    Latest,
+    /// The simple moving average of the values in the history, as returned by
+    /// [`Diagnostic::average`].
+    MovingAverage,
+    /// The exponential moving average, see
+    /// [`Diagnostic::with_smoothing_factor`].
+    Exponential {
+        /// The number of seconds it takes for about 83% of a change to be
+        /// reflected in the smoothed value.
+        smoothing_factor: f64,
+    },
+}
+
+impl Default for DiagnosticSmoothing {
+    fn default() -> Self {
+        DiagnosticSmoothing::Exponential {
+            smoothing_factor: 2.0 / 21.0,
+        }
+    }
+}
+
+/// The minimum, maximum and average of the values of a [`Diagnostic`], over its history or a window
+/// of it. `NaN` values are ignored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiagnosticStats {
+    /// The smallest value.
+    pub min: f64,
+    /// The largest value.
+    pub max: f64,
+    /// The mean of the values.
+    pub average: f64,
+    /// The number of values the statistics were computed from.
+    pub count: usize,
+}
+
+impl DiagnosticStats {
+    /// Computes the statistics of `values`, or returns `None` if there are none that aren't `NaN`.
+    pub fn from_values(values: impl IntoIterator<Item = f64>) -> Option<Self> {
+        let mut stats: Option<Self> = None;
+        let mut sum = 0.0;
+        for value in values.into_iter().filter(|value| !value.is_nan()) {
+            sum += value;
+            let stats = stats.get_or_insert(DiagnosticStats {
+                min: value,
+                max: value,
+                average: 0.0,
+                count: 0,
+            });
+            stats.min = stats.min.min(value);
+            stats.max = stats.max.max(value);
+            stats.count += 1;
+        }
+        stats.map(|stats| DiagnosticStats {
+            average: sum / stats.count as f64,
+            ..stats
+        })
+    }
+}
+
 /// A timeline of [`DiagnosticMeasurement`]s of a specific type.
 /// Diagnostic examples: frames per second, CPU usage, network latency
 #[derive(Debug)]
@@ -126,7 +209,7 @@ pub struct Diagnostic {
     history: VecDeque<DiagnosticMeasurement>,
     sum: f64,
     ema: f64,
-    ema_smoothing_factor: f64,
+    smoothing: DiagnosticSmoothing,
     max_history_length: usize,
     pub is_enabled: bool,
 }
@@ -134,14 +217,16 @@ pub struct Diagnostic {
 impl Diagnostic {
     /// Add a new value as a [`DiagnosticMeasurement`].
     pub fn add_measurement(&mut self, measurement: DiagnosticMeasurement) {
-        if measurement.value.is_nan() {
-            // Skip calculating the moving average.
-        } else if let Some(previous) = self.measurement() {
-            let delta = (measurement.time - previous.time).as_secs_f64();
-            let alpha = (delta / self.ema_smoothing_factor).clamp(0.0, 1.0);
-            self.ema += alpha * (measurement.value - self.ema);
-        } else {
-            self.ema = measurement.value;
+        if let DiagnosticSmoothing::Exponential { smoothing_factor } = self.smoothing {
+            if measurement.value.is_nan() {
+                // Skip calculating the moving average.
+            } else if let Some(previous) = self.measurement() {
+                let delta = (measurement.time - previous.time).as_secs_f64();
+                let alpha = (delta / smoothing_factor).clamp(0.0, 1.0);
+                self.ema += alpha * (measurement.value - self.ema);
+            } else {
+                self.ema = measurement.value;
+            }
         }
 
         if self.max_history_length > 1 {
@@ -177,7 +262,7 @@ impl Diagnostic {
             max_history_length: DEFAULT_MAX_HISTORY_LENGTH,
             sum: 0.0,
             ema: 0.0,
-            ema_smoothing_factor: 2.0 / 21.0,
+            smoothing: DiagnosticSmoothing::default(),
             is_enabled: true,
         }
     }
@@ -213,12 +298,27 @@ impl Diagnostic {
     /// change in measurement to e reflected in the smoothed value.
     ///
     /// A smoothing factor of 0.0 will effectively disable smoothing.
+    ///
+    /// This is a shorthand for [`with_smoothing`](Self::with_smoothing) with
+    /// [`DiagnosticSmoothing::Exponential`].
     #[must_use]
     pub fn with_smoothing_factor(mut self, smoothing_factor: f64) -> Self {
-        self.ema_smoothing_factor = smoothing_factor;
+        self.smoothing = DiagnosticSmoothing::Exponential { smoothing_factor };
+        self
+    }
+
+    /// Set how the [`smoothed`](Self::smoothed) value is computed.
+    #[must_use]
+    pub fn with_smoothing(mut self, smoothing: DiagnosticSmoothing) -> Self {
+        self.smoothing = smoothing;
         self
     }
 
+    /// Returns how the [`smoothed`](Self::smoothed) value is computed.
+    pub fn smoothing(&self) -> DiagnosticSmoothing {
+        self.smoothing
+    }
+
     pub fn path(&self) -> &DiagnosticPath {
         &self.path
     }
@@ -244,17 +344,110 @@ impl Diagnostic {
         }
     }
 
-    /// Return the exponential moving average of this diagnostic.
+    /// Return the smoothed value of this diagnostic, computed as configured by
+    /// [`with_smoothing`](Self::with_smoothing).
     ///
-    /// This is by default tuned to behave reasonably well for a typical
-    /// measurement that changes every frame such as frametime. This can be
-    /// adjusted using [`with_smoothing_factor`](Self::with_smoothing_factor).
+    /// This is by default the exponential moving average, tuned to behave
+    /// reasonably well for a typical measurement that changes every frame such
+    /// as frametime. This can be adjusted using
+    /// [`with_smoothing_factor`](Self::with_smoothing_factor).
     pub fn smoothed(&self) -> Option<f64> {
-        if !self.history.is_empty() {
-            Some(self.ema)
-        } else {
-            None
+        match self.smoothing {
+            DiagnosticSmoothing::Latest => self.value(),
+            DiagnosticSmoothing::MovingAverage => self.average(),
+            DiagnosticSmoothing::Exponential { .. } => {
+                if !self.history.is_empty() {
+                    Some(self.ema)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Return the given percentile of this diagnostic's recent values, between `0.0` and `100.0`,
+    /// linearly interpolated between the nearest values.
+    ///
+    /// The values are the ones kept in the history, so the number of measurements considered is
+    /// configured with [`with_max_history_length`](Self::with_max_history_length). `NaN` values
+    /// are ignored.
+    ///
+    /// This sorts a copy of the history, so it is more expensive than [`average`](Self::average).
+    pub fn percentile(&self, percentile: f64) -> Option<f64> {
+        let mut values: Vec<f64> = self.values().copied().filter(|v| !v.is_nan()).collect();
+        if values.is_empty() {
+            return None;
         }
+        values.sort_unstable_by(f64::total_cmp);
+
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0) * (values.len() - 1) as f64;
+        let lower = rank as usize;
+        let upper = (lower + 1).min(values.len() - 1);
+        let fraction = rank - lower as f64;
+        Some(values[lower] + (values[upper] - values[lower]) * fraction)
+    }
+
+    /// Return the median of this diagnostic's recent values.
+    ///
+    /// See [`percentile`](Self::percentile).
+    pub fn p50(&self) -> Option<f64> {
+        self.percentile(50.0)
+    }
+
+    /// Return the 95th percentile of this diagnostic's recent values.
+    ///
+    /// See [`percentile`](Self::percentile).
+    pub fn p95(&self) -> Option<f64> {
+        self.percentile(95.0)
+    }
+
+    /// Return the 99th percentile of this diagnostic's recent values.
+    ///
+    /// See [`percentile`](Self::percentile).
+    pub fn p99(&self) -> Option<f64> {
+        self.percentile(99.0)
+    }
+
+    /// Return the smallest of this diagnostic's recent values, ignoring `NaN` values.
+    pub fn min(&self) -> Option<f64> {
+        self.stats().map(|stats| stats.min)
+    }
+
+    /// Return the largest of this diagnostic's recent values, ignoring `NaN` values.
+    pub fn max(&self) -> Option<f64> {
+        self.stats().map(|stats| stats.max)
+    }
+
+    /// Return the minimum, maximum and average of this diagnostic's recent values.
+    pub fn stats(&self) -> Option<DiagnosticStats> {
+        DiagnosticStats::from_values(self.values().copied())
+    }
+
+    /// Return the minimum, maximum and average of the values measured at most `window` before the
+    /// latest measurement.
+    ///
+    /// See [`measurements_within`](Self::measurements_within).
+    pub fn stats_within(&self, window: Duration) -> Option<DiagnosticStats> {
+        DiagnosticStats::from_values(
+            self.measurements_within(window)
+                .map(|measurement| measurement.value),
+        )
+    }
+
+    /// Return an iterator over the measurements taken at most `window` before the latest one,
+    /// from the oldest to the latest.
+    ///
+    /// Only the measurements kept in the history are returned, so windows longer than the history
+    /// return the whole history.
+    pub fn measurements_within(
+        &self,
+        window: Duration,
+    ) -> impl Iterator<Item = &DiagnosticMeasurement> {
+        let latest = self.measurement().map(|measurement| measurement.time);
+        self.history.iter().filter(move |measurement| {
+            latest
+                .is_some_and(|latest| latest.saturating_duration_since(measurement.time) <= window)
+        })
     }
 
     /// Return the number of elements for this diagnostic.
@@ -282,14 +475,35 @@ impl Diagnostic {
         self.max_history_length
     }
 
+    /// Return an iterator over this diagnostic's recent values, from the oldest to the latest.
+    ///
+    /// Use [`measurements`](Self::measurements) to know when they were measured.
     pub fn values(&self) -> impl Iterator<Item = &f64> {
         self.history.iter().map(|x| &x.value)
     }
 
+    /// Return an iterator over this diagnostic's recent measurements, with the [`Instant`] each
+    /// value was measured at, from the oldest to the latest.
     pub fn measurements(&self) -> impl Iterator<Item = &DiagnosticMeasurement> {
         self.history.iter()
     }
 
+    /// Return an iterator over the gaps in this diagnostic's history: the pairs of consecutive
+    /// measurements more than `max_interval` apart, such as when the app was suspended.
+    ///
+    /// Graphs can break their lines at gaps instead of interpolating over them.
+    pub fn gaps(
+        &self,
+        max_interval: Duration,
+    ) -> impl Iterator<Item = (&DiagnosticMeasurement, &DiagnosticMeasurement)> {
+        self.history
+            .iter()
+            .zip(self.history.iter().skip(1))
+            .filter(move |(previous, next)| {
+                next.time.saturating_duration_since(previous.time) > max_interval
+            })
+    }
+
     /// Clear the history of this diagnostic.
     pub fn clear_history(&mut self) {
         self.history.clear();
@@ -297,9 +511,14 @@ impl Diagnostic {
 }
 
 /// A collection of [`Diagnostic`]s.
+///
+/// Collection can be turned off at runtime, for a single diagnostic with
+/// [`set_enabled`](Self::set_enabled), or for all of them with [`set_paused`](Self::set_paused),
+/// so that measuring costs nothing, for example in release builds.
 #[derive(Debug, Default, Resource)]
 pub struct DiagnosticsStore {
     diagnostics: HashMap<DiagnosticPath, Diagnostic, PassHash>,
+    paused: bool,
 }
 
 impl DiagnosticsStore {
@@ -331,16 +550,63 @@ impl DiagnosticsStore {
         self.diagnostics.values()
     }
 
+    /// Return an iterator over the [`Diagnostic`]s whose path
+    /// [starts with](DiagnosticPath::starts_with) `prefix`, such as all the
+    /// `render/pass/*` diagnostics.
+    pub fn iter_prefixed<'a, 'p>(
+        &'a self,
+        prefix: &'p DiagnosticPath,
+    ) -> impl Iterator<Item = &'a Diagnostic> + use<'a, 'p> {
+        self.diagnostics
+            .values()
+            .filter(|diagnostic| diagnostic.path.starts_with(prefix))
+    }
+
     /// Return an iterator over all [`Diagnostic`]s, by mutable reference.
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Diagnostic> {
         self.diagnostics.values_mut()
     }
+
+    /// Enables or disables the collection of a [`Diagnostic`].
+    ///
+    /// Returns `false` if there is no diagnostic with this path.
+    pub fn set_enabled(&mut self, path: &DiagnosticPath, enabled: bool) -> bool {
+        self.diagnostics
+            .get_mut(path)
+            .map(|diagnostic| diagnostic.is_enabled = enabled)
+            .is_some()
+    }
+
+    /// Returns `true` if new measurements of a [`Diagnostic`] are collected: it exists, is enabled,
+    /// and collection isn't [paused](Self::set_paused).
+    pub fn is_enabled(&self, path: &DiagnosticPath) -> bool {
+        !self.paused
+            && self
+                .diagnostics
+                .get(path)
+                .is_some_and(|diagnostic| diagnostic.is_enabled)
+    }
+
+    /// Suspends or resumes the collection of all the [`Diagnostic`]s, keeping the measurements
+    /// that were already collected.
+    ///
+    /// Whether each diagnostic is enabled is kept while collection is paused.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Returns `true` if the collection of all the [`Diagnostic`]s is
+    /// [paused](Self::set_paused).
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
 }
 
 /// Record new [`DiagnosticMeasurement`]'s.
 #[derive(SystemParam)]
 pub struct Diagnostics<'w, 's> {
     store: Res<'w, DiagnosticsStore>,
+    clock: Option<Res<'w, DiagnosticsClock>>,
     queue: Deferred<'s, DiagnosticsBuffer>,
 }
 
@@ -348,22 +614,76 @@ impl<'w, 's> Diagnostics<'w, 's> {
     /// Add a measurement to an enabled [`Diagnostic`]. The measurement is passed as a function so that
     /// it will be evaluated only if the [`Diagnostic`] is enabled. This can be useful if the value is
     /// costly to calculate.
+    ///
+    /// Nothing is measured while collection is [paused](DiagnosticsStore::set_paused).
     pub fn add_measurement<F>(&mut self, path: &DiagnosticPath, value: F)
     where
         F: FnOnce() -> f64,
     {
-        if self
-            .store
-            .get(path)
-            .is_some_and(|diagnostic| diagnostic.is_enabled)
-        {
+        if self.store.is_enabled(path) {
             let measurement = DiagnosticMeasurement {
-                time: Instant::now(),
+                time: self.now(),
                 value: value(),
             };
             self.queue.0.insert(path.clone(), measurement);
         }
     }
+
+    /// Returns the current time of the [`DiagnosticsClock`], or of the [`InstantClock`] if there
+    /// is none.
+    pub fn now(&self) -> Instant {
+        match &self.clock {
+            Some(clock) => clock.now(),
+            None => InstantClock.now(),
+        }
+    }
+
+    /// Takes a [`DiagnosticsSnapshot`] of all the diagnostics. See [`DiagnosticsStore::snapshot`].
+    pub fn snapshot(&self) -> DiagnosticsSnapshot {
+        self.store.snapshot()
+    }
+
+    /// Get a [`Diagnostic`], to read its history.
+    pub fn get(&self, path: &DiagnosticPath) -> Option<&Diagnostic> {
+        self.store.get(path)
+    }
+
+    /// Get the latest [`DiagnosticMeasurement`] from an enabled [`Diagnostic`].
+    pub fn get_measurement(&self, path: &DiagnosticPath) -> Option<&DiagnosticMeasurement> {
+        self.store.get_measurement(path)
+    }
+
+    /// Return an iterator over all [`Diagnostic`]s.
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.store.iter()
+    }
+
+    /// Return an iterator over the [`Diagnostic`]s whose path
+    /// [starts with](DiagnosticPath::starts_with) `prefix`.
+    pub fn iter_prefixed<'a, 'p>(
+        &'a self,
+        prefix: &'p DiagnosticPath,
+    ) -> impl Iterator<Item = &'a Diagnostic> + use<'a, 'p> {
+        self.store.iter_prefixed(prefix)
+    }
+
+    /// Return the minimum, maximum and average of the values of a [`Diagnostic`] measured at most
+    /// `window` before its latest measurement. See [`Diagnostic::stats_within`].
+    pub fn stats_within(&self, path: &DiagnosticPath, window: Duration) -> Option<DiagnosticStats> {
+        self.store.get(path)?.stats_within(window)
+    }
+
+    /// Returns `true` if new measurements of a [`Diagnostic`] are collected. See
+    /// [`DiagnosticsStore::is_enabled`].
+    pub fn is_enabled(&self, path: &DiagnosticPath) -> bool {
+        self.store.is_enabled(path)
+    }
+
+    /// Returns `true` if the collection of all the [`Diagnostic`]s is
+    /// [paused](DiagnosticsStore::set_paused).
+    pub fn is_paused(&self) -> bool {
+        self.store.is_paused()
+    }
 }
 
 #[derive(Default)]
@@ -420,3 +740,140 @@ impl RegisterDiagnostic for App {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::Update;
+
+    #[test]
+    fn percentiles() {
+        let mut diagnostic = Diagnostic::new(DiagnosticPath::const_new("test"));
+        assert_eq!(diagnostic.p50(), None);
+
+        let time = Instant::now();
+        for value in [5.0, 1.0, 4.0, 2.0, 3.0, f64::NAN] {
+            diagnostic.add_measurement(DiagnosticMeasurement { time, value });
+        }
+        assert_eq!(diagnostic.percentile(0.0), Some(1.0));
+        assert_eq!(diagnostic.p50(), Some(3.0));
+        assert_eq!(diagnostic.percentile(100.0), Some(5.0));
+        assert_eq!(diagnostic.percentile(62.5), Some(3.5));
+    }
+
+    #[test]
+    fn stats_within() {
+        let mut diagnostic = Diagnostic::new(DiagnosticPath::const_new("test"));
+        assert_eq!(diagnostic.stats(), None);
+
+        let time = Instant::now();
+        for (i, value) in [8.0, 2.0, f64::NAN, 6.0, 4.0].into_iter().enumerate() {
+            diagnostic.add_measurement(DiagnosticMeasurement {
+                time: time + Duration::from_secs(i as u64),
+                value,
+            });
+        }
+        assert_eq!(diagnostic.min(), Some(2.0));
+        assert_eq!(diagnostic.max(), Some(8.0));
+        assert_eq!(
+            diagnostic
+                .measurements_within(Duration::from_secs(2))
+                .count(),
+            3
+        );
+        assert_eq!(
+            diagnostic.stats_within(Duration::from_secs(2)),
+            Some(DiagnosticStats {
+                min: 4.0,
+                max: 6.0,
+                average: 5.0,
+                count: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn gaps() {
+        let mut diagnostic = Diagnostic::new(DiagnosticPath::const_new("test"));
+        let time = Instant::now();
+        for millis in [0, 16, 33, 2000, 2016] {
+            diagnostic.add_measurement(DiagnosticMeasurement {
+                time: time + Duration::from_millis(millis),
+                value: 1.0,
+            });
+        }
+        let gaps: Vec<_> = diagnostic
+            .gaps(Duration::from_millis(100))
+            .map(|(previous, next)| (previous.time - time, next.time - time))
+            .collect();
+        assert_eq!(
+            gaps,
+            [(Duration::from_millis(33), Duration::from_millis(2000))]
+        );
+    }
+
+    #[test]
+    fn smoothing() {
+        let time = Instant::now();
+        let measure = |smoothing| {
+            let mut diagnostic =
+                Diagnostic::new(DiagnosticPath::const_new("test")).with_smoothing(smoothing);
+            for (i, value) in [1.0, 2.0, 6.0].into_iter().enumerate() {
+                diagnostic.add_measurement(DiagnosticMeasurement {
+                    time: time + Duration::from_secs(i as u64),
+                    value,
+                });
+            }
+            diagnostic.smoothed()
+        };
+
+        assert_eq!(measure(DiagnosticSmoothing::Latest), Some(6.0));
+        assert_eq!(measure(DiagnosticSmoothing::MovingAverage), Some(3.0));
+        // Measurements one second apart with a smoothing factor of two seconds move the average
+        // halfway to each new value.
+        assert_eq!(
+            measure(DiagnosticSmoothing::Exponential {
+                smoothing_factor: 2.0
+            }),
+            Some(3.75)
+        );
+    }
+
+    #[test]
+    fn pause_and_disable() {
+        const PATH: DiagnosticPath = DiagnosticPath::const_new("test");
+
+        let mut app = App::new();
+        app.register_diagnostic(Diagnostic::new(PATH)).add_systems(
+            Update,
+            |mut diagnostics: Diagnostics| {
+                diagnostics.add_measurement(&PATH, || 1.0);
+            },
+        );
+        let history_len_after_update = |app: &mut App| {
+            app.update();
+            app.world()
+                .resource::<DiagnosticsStore>()
+                .get(&PATH)
+                .unwrap()
+                .history_len()
+        };
+
+        assert_eq!(history_len_after_update(&mut app), 1);
+        app.world_mut()
+            .resource_mut::<DiagnosticsStore>()
+            .set_paused(true);
+        assert_eq!(history_len_after_update(&mut app), 1);
+
+        let mut store = app.world_mut().resource_mut::<DiagnosticsStore>();
+        store.set_paused(false);
+        assert!(store.set_enabled(&PATH, false));
+        assert!(!store.is_enabled(&PATH));
+        assert_eq!(history_len_after_update(&mut app), 1);
+
+        app.world_mut()
+            .resource_mut::<DiagnosticsStore>()
+            .set_enabled(&PATH, true);
+        assert_eq!(history_len_after_update(&mut app), 2);
+    }
+}
@@ -0,0 +1,72 @@
+use alloc::boxed::Box;
+use bevy_ecs::prelude::*;
+use bevy_platform_support::time::Instant;
+
+/// A source of time for the [`DiagnosticMeasurement`](crate::DiagnosticMeasurement)s recorded
+/// with [`Diagnostics`](crate::Diagnostics), and the frame times of the
+/// [`FrameTimeDiagnosticsPlugin`](crate::FrameTimeDiagnosticsPlugin).
+///
+/// Functions returning an [`Instant`] are clocks, which makes it possible to record diagnostics on
+/// platforms without a system clock, or with a simulated time in tests.
+pub trait DiagnosticClock: Send + Sync + 'static {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+}
+
+impl<F: Fn() -> Instant + Send + Sync + 'static> DiagnosticClock for F {
+    fn now(&self) -> Instant {
+        self()
+    }
+}
+
+/// The default [`DiagnosticClock`], reading [`Instant::now`].
+///
+/// Natively, this is the monotonic clock of the standard library. On `wasm32`, this is
+/// `performance.now()` when the `web` feature is enabled.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct InstantClock;
+
+impl DiagnosticClock for InstantClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// The [`DiagnosticClock`] used by the diagnostics, [`InstantClock`] by default.
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_diagnostic::DiagnosticsClock;
+/// # use bevy_platform_support::time::Instant;
+/// # use core::{sync::atomic::{AtomicU64, Ordering}, time::Duration};
+/// # use std::sync::Arc;
+/// // A simulated time, advanced by the test.
+/// let start = Instant::now();
+/// let elapsed_millis = Arc::new(AtomicU64::new(0));
+/// let clock_millis = elapsed_millis.clone();
+/// App::new().insert_resource(DiagnosticsClock::new(move || {
+///     start + Duration::from_millis(clock_millis.load(Ordering::Relaxed))
+/// }));
+///
+/// elapsed_millis.fetch_add(16, Ordering::Relaxed);
+/// ```
+#[derive(Resource)]
+pub struct DiagnosticsClock(Box<dyn DiagnosticClock>);
+
+impl DiagnosticsClock {
+    /// Creates a [`DiagnosticsClock`] using the given clock.
+    pub fn new(clock: impl DiagnosticClock) -> Self {
+        Self(Box::new(clock))
+    }
+
+    /// Returns the current time.
+    pub fn now(&self) -> Instant {
+        self.0.now()
+    }
+}
+
+impl Default for DiagnosticsClock {
+    fn default() -> Self {
+        Self::new(InstantClock)
+    }
+}
@@ -0,0 +1,146 @@
+use super::{Diagnostic, DiagnosticPath, DiagnosticsStore};
+use alloc::{borrow::Cow, string::String};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_platform_support::{collections::HashMap, hash::PassHash};
+
+/// An App Plugin that mirrors each diagnostic into an entity with a [`DiagnosticValue`] component,
+/// so that diagnostics can be read with queries and change detection, for example to build a debug
+/// UI.
+///
+/// The entities are spawned when their diagnostic is first seen and updated in [`Last`], and the
+/// [`DiagnosticEntities`] resource maps the diagnostic paths to them. A [`DiagnosticValue`] is only
+/// changed when the values of its diagnostic changed.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_diagnostic::DiagnosticValue;
+/// fn print_slow_diagnostics(diagnostics: Query<&DiagnosticValue, Changed<DiagnosticValue>>) {
+///     for diagnostic in &diagnostics {
+///         if diagnostic.suffix == "ms" && diagnostic.smoothed.is_some_and(|value| value > 16.0) {
+///             println!("{} is slow", diagnostic.path);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct DiagnosticEntitiesPlugin;
+
+impl Plugin for DiagnosticEntitiesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DiagnosticsStore>()
+            .init_resource::<DiagnosticEntities>()
+            .add_systems(Last, Self::sync_system);
+    }
+}
+
+/// The values of a diagnostic, mirrored by the [`DiagnosticEntitiesPlugin`].
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct DiagnosticValue {
+    /// The path of the diagnostic.
+    pub path: DiagnosticPath,
+    /// The suffix of the diagnostic, usually its unit.
+    pub suffix: Cow<'static, str>,
+    /// Whether the diagnostic is enabled.
+    pub is_enabled: bool,
+    /// The latest value.
+    pub value: Option<f64>,
+    /// The smoothed value.
+    pub smoothed: Option<f64>,
+    /// The average of the history.
+    pub average: Option<f64>,
+}
+
+impl DiagnosticValue {
+    fn new(diagnostic: &Diagnostic) -> Self {
+        Self {
+            path: diagnostic.path().clone(),
+            suffix: diagnostic.suffix.clone(),
+            is_enabled: diagnostic.is_enabled,
+            value: diagnostic.value(),
+            smoothed: diagnostic.smoothed(),
+            average: diagnostic.average(),
+        }
+    }
+}
+
+/// The entities of the diagnostics, spawned by the [`DiagnosticEntitiesPlugin`].
+#[derive(Resource, Debug, Default)]
+pub struct DiagnosticEntities {
+    entities: HashMap<DiagnosticPath, Entity, PassHash>,
+}
+
+impl DiagnosticEntities {
+    /// Returns the entity of the diagnostic with the given path.
+    pub fn get(&self, path: &DiagnosticPath) -> Option<Entity> {
+        self.entities.get(path).copied()
+    }
+
+    /// Returns an iterator over the diagnostic paths and their entities.
+    pub fn iter(&self) -> impl Iterator<Item = (&DiagnosticPath, Entity)> {
+        self.entities.iter().map(|(path, entity)| (path, *entity))
+    }
+}
+
+impl DiagnosticEntitiesPlugin {
+    fn sync_system(
+        mut commands: Commands,
+        mut entities: ResMut<DiagnosticEntities>,
+        diagnostics: Res<DiagnosticsStore>,
+        mut values: Query<&mut DiagnosticValue>,
+    ) {
+        for diagnostic in diagnostics.iter() {
+            let value = DiagnosticValue::new(diagnostic);
+            // The entity is spawned again if it was despawned.
+            if let Some(mut current) = entities
+                .get(diagnostic.path())
+                .and_then(|entity| values.get_mut(entity).ok())
+            {
+                current.set_if_neq(value);
+                continue;
+            }
+            let name = Name::new(String::from(diagnostic.path().as_str()));
+            let entity = commands.spawn((name, value)).id();
+            entities.entities.insert(diagnostic.path().clone(), entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DiagnosticMeasurement, RegisterDiagnostic};
+    use bevy_platform_support::time::Instant;
+
+    #[test]
+    fn mirrors_diagnostics() {
+        const PATH: DiagnosticPath = DiagnosticPath::const_new("test");
+
+        let mut app = App::new();
+        app.add_plugins(DiagnosticEntitiesPlugin)
+            .register_diagnostic(Diagnostic::new(PATH).with_suffix("ms"));
+        app.update();
+
+        let entity = app
+            .world()
+            .resource::<DiagnosticEntities>()
+            .get(&PATH)
+            .unwrap();
+        let value = app.world().get::<DiagnosticValue>(entity).unwrap();
+        assert_eq!(value.suffix, "ms");
+        assert_eq!(value.value, None);
+
+        app.world_mut()
+            .resource_mut::<DiagnosticsStore>()
+            .get_mut(&PATH)
+            .unwrap()
+            .add_measurement(DiagnosticMeasurement {
+                time: Instant::now(),
+                value: 4.0,
+            });
+        app.update();
+        let value = app.world().get::<DiagnosticValue>(entity).unwrap();
+        assert_eq!(value.value, Some(4.0));
+        assert_eq!(value.average, Some(4.0));
+    }
+}
@@ -0,0 +1,119 @@
+use alloc::{borrow::Cow, vec::Vec};
+use bevy_ecs::prelude::*;
+
+/// Key-value labels describing the context of the measurements, like the current scene or quality
+/// settings, which are attached to the diagnostics exported while they are set.
+///
+/// Aggregated values are hard to interpret when the app switches scenes or settings during a run,
+/// so the exporters write the labels with each record: the
+/// [`DiagnosticsFileLoggerPlugin`](crate::DiagnosticsFileLoggerPlugin) in a `labels` column or
+/// field, the [`DiagnosticsExporterPlugin`](crate::DiagnosticsExporterPlugin) as Prometheus
+/// labels, and the [`DiagnosticsStreamPlugin`](crate::DiagnosticsStreamPlugin) in its updates.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_diagnostic::DiagnosticLabels;
+/// fn enter_level_3(mut labels: ResMut<DiagnosticLabels>) {
+///     labels.insert("scene", "level_3");
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiagnosticLabels {
+    /// The labels, sorted by key.
+    labels: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+}
+
+impl DiagnosticLabels {
+    /// Creates a [`DiagnosticLabels`] without labels.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a label, returning the labels.
+    pub fn with(
+        mut self,
+        key: impl Into<Cow<'static, str>>,
+        value: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    /// Sets the label `key` to `value`, returning its previous value.
+    pub fn insert(
+        &mut self,
+        key: impl Into<Cow<'static, str>>,
+        value: impl Into<Cow<'static, str>>,
+    ) -> Option<Cow<'static, str>> {
+        let (key, value) = (key.into(), value.into());
+        match self.search(&key) {
+            Ok(index) => Some(core::mem::replace(&mut self.labels[index].1, value)),
+            Err(index) => {
+                self.labels.insert(index, (key, value));
+                None
+            }
+        }
+    }
+
+    /// Removes the label `key`, returning its value.
+    pub fn remove(&mut self, key: &str) -> Option<Cow<'static, str>> {
+        let index = self.search(key).ok()?;
+        Some(self.labels.remove(index).1)
+    }
+
+    /// Returns the value of the label `key`.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        let index = self.search(key).ok()?;
+        Some(&self.labels[index].1)
+    }
+
+    /// Returns an iterator over the keys and values of the labels, sorted by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.labels
+            .iter()
+            .map(|(key, value)| (key.as_ref(), value.as_ref()))
+    }
+
+    /// Returns the number of labels.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Returns `true` if there are no labels.
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Removes all the labels.
+    pub fn clear(&mut self) {
+        self.labels.clear();
+    }
+
+    fn search(&self, key: &str) -> Result<usize, usize> {
+        self.labels
+            .binary_search_by(|(other, _)| other.as_ref().cmp(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorted_labels() {
+        let mut labels = DiagnosticLabels::new()
+            .with("scene", "level_1")
+            .with("quality", "high");
+        assert_eq!(
+            labels.insert("scene", "level_3").as_deref(),
+            Some("level_1")
+        );
+        assert_eq!(
+            labels.iter().collect::<Vec<_>>(),
+            [("quality", "high"), ("scene", "level_3")]
+        );
+        assert_eq!(labels.remove("quality").as_deref(), Some("high"));
+        assert_eq!(labels.get("quality"), None);
+        assert_eq!(labels.len(), 1);
+    }
+}
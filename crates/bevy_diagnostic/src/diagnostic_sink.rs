@@ -0,0 +1,274 @@
+use super::{Diagnostic, DiagnosticsSnapshot};
+use alloc::{boxed::Box, collections::VecDeque, string::String, vec::Vec};
+use bevy_ecs::prelude::*;
+use bevy_platform_support::sync::{Arc, Mutex, PoisonError};
+use log::{debug, info};
+
+#[cfg(feature = "std")]
+use {
+    log::warn,
+    std::{
+        fs::File,
+        io::{self, BufWriter, Write},
+        path::Path,
+        println,
+    },
+};
+
+/// The diagnostics reported to the [`DiagnosticSink`]s by the
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin), selected by its filter and limits.
+#[derive(Debug)]
+pub struct DiagnosticReport<'a> {
+    /// The reported diagnostics.
+    pub diagnostics: &'a [&'a Diagnostic],
+    /// The reported diagnostics with a value, formatted as the lines of an aligned table, with a
+    /// header.
+    pub table: &'a [String],
+}
+
+/// A consumer of the diagnostics reported by the
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin), added to the [`DiagnosticSinks`].
+///
+/// All the sinks receive the same report, so that the diagnostics are only selected and formatted
+/// once however many consumers there are.
+pub trait DiagnosticSink: Send + Sync + 'static {
+    /// Receives the diagnostics reported this time.
+    fn report(&mut self, report: &DiagnosticReport);
+}
+
+/// The [`DiagnosticSink`]s receiving the reports of the
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin).
+///
+/// The plugin adds a [`LogSink`] if there isn't this resource yet. Sinks can be added at any time,
+/// including after the app is built:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_diagnostic::{DiagnosticSinks, MemorySink};
+/// fn add_memory_sink(mut sinks: ResMut<DiagnosticSinks>) {
+///     let sink = MemorySink::new(60);
+///     // Keep a clone of the sink to read the reports, for example from a debug UI.
+///     sinks.add(sink.clone());
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct DiagnosticSinks {
+    sinks: Vec<Box<dyn DiagnosticSink>>,
+}
+
+impl DiagnosticSinks {
+    /// Creates a [`DiagnosticSinks`] without sinks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a sink.
+    pub fn add(&mut self, sink: impl DiagnosticSink) -> &mut Self {
+        self.sinks.push(Box::new(sink));
+        self
+    }
+
+    /// Adds a sink, returning the sinks.
+    pub fn with(mut self, sink: impl DiagnosticSink) -> Self {
+        self.add(sink);
+        self
+    }
+
+    /// Removes all the sinks.
+    pub fn clear(&mut self) {
+        self.sinks.clear();
+    }
+
+    /// Returns the number of sinks.
+    pub fn len(&self) -> usize {
+        self.sinks.len()
+    }
+
+    /// Returns `true` if there are no sinks.
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// Sends the `report` to all the sinks.
+    pub fn report(&mut self, report: &DiagnosticReport) {
+        for sink in &mut self.sinks {
+            sink.report(report);
+        }
+    }
+}
+
+/// A [`DiagnosticSink`] logging the table of the diagnostics at the info level, or each diagnostic
+/// at the debug level with [`debug`](Self::debug).
+///
+/// The logs go through `tracing` when the `LogPlugin` of `bevy_log` is added.
+#[derive(Debug, Default, Clone)]
+pub struct LogSink {
+    /// Log all the details of each diagnostic, at the debug level.
+    pub debug: bool,
+}
+
+impl DiagnosticSink for LogSink {
+    fn report(&mut self, report: &DiagnosticReport) {
+        if self.debug {
+            for diagnostic in report.diagnostics {
+                debug!("{:#?}\n", diagnostic);
+            }
+        } else {
+            for line in report.table {
+                info!(target: "bevy diagnostic", "{line}");
+            }
+        }
+    }
+}
+
+/// A [`DiagnosticSink`] printing the table of the diagnostics to the standard output.
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone)]
+pub struct StdoutSink;
+
+#[cfg(feature = "std")]
+impl DiagnosticSink for StdoutSink {
+    #[expect(clippy::print_stdout, reason = "Allowed behind `std` feature gate.")]
+    fn report(&mut self, report: &DiagnosticReport) {
+        for line in report.table {
+            println!("{line}");
+        }
+    }
+}
+
+/// A [`DiagnosticSink`] appending the table of the diagnostics to a file, followed by an empty
+/// line.
+///
+/// The [`colored_thresholds`](crate::LogDiagnosticsPlugin::colored_thresholds) should be disabled,
+/// so that the file doesn't contain escape codes.
+#[cfg(feature = "std")]
+pub struct FileSink {
+    writer: BufWriter<File>,
+}
+
+#[cfg(feature = "std")]
+impl FileSink {
+    /// Creates a [`FileSink`] appending to the file at `path`, which is created if needed.
+    pub fn new(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::options().create(true).append(true).open(path)?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl DiagnosticSink for FileSink {
+    fn report(&mut self, report: &DiagnosticReport) {
+        let result = report
+            .table
+            .iter()
+            .try_for_each(|line| writeln!(self.writer, "{line}"))
+            .and_then(|()| writeln!(self.writer))
+            .and_then(|()| self.writer.flush());
+        if let Err(err) = result {
+            warn!("Failed to write diagnostics to a file: {err}");
+        }
+    }
+}
+
+/// A [`DiagnosticSink`] keeping the latest reports in memory, as [`DiagnosticsSnapshot`]s, for
+/// example to draw graphs in a debug UI.
+///
+/// Clones of a [`MemorySink`] share the same reports, so that a clone can read the reports
+/// received by the one added to the [`DiagnosticSinks`].
+#[derive(Debug, Clone)]
+pub struct MemorySink {
+    reports: Arc<Mutex<VecDeque<DiagnosticsSnapshot>>>,
+    capacity: usize,
+}
+
+impl MemorySink {
+    /// Creates a [`MemorySink`] keeping the latest `capacity` reports.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            reports: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Returns the kept reports, from the oldest to the latest.
+    pub fn reports(&self) -> Vec<DiagnosticsSnapshot> {
+        let reports = self.reports.lock().unwrap_or_else(PoisonError::into_inner);
+        reports.iter().cloned().collect()
+    }
+
+    /// Returns the latest report.
+    pub fn latest(&self) -> Option<DiagnosticsSnapshot> {
+        let reports = self.reports.lock().unwrap_or_else(PoisonError::into_inner);
+        reports.back().cloned()
+    }
+}
+
+impl DiagnosticSink for MemorySink {
+    fn report(&mut self, report: &DiagnosticReport) {
+        if self.capacity == 0 {
+            return;
+        }
+        let snapshot = DiagnosticsSnapshot::from_diagnostics(report.diagnostics.iter().copied());
+        let mut reports = self.reports.lock().unwrap_or_else(PoisonError::into_inner);
+        if reports.len() >= self.capacity {
+            reports.pop_front();
+        }
+        reports.push_back(snapshot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DiagnosticMeasurement, DiagnosticPath, DiagnosticsPlugin, DiagnosticsStore,
+        LogDiagnosticsPlugin, RegisterDiagnostic,
+    };
+    use bevy_app::App;
+    use bevy_platform_support::time::Instant;
+    use bevy_time::{Real, Time};
+    use core::time::Duration;
+
+    #[test]
+    fn sinks_receive_the_same_reports() {
+        const PATH: DiagnosticPath = DiagnosticPath::const_new("test");
+
+        let mut app = App::new();
+        app.add_plugins((
+            DiagnosticsPlugin,
+            LogDiagnosticsPlugin {
+                wait_duration: Duration::ZERO,
+                ..Default::default()
+            },
+        ))
+        .init_resource::<Time<Real>>()
+        .register_diagnostic(Diagnostic::new(PATH));
+        // The log sink is added by default.
+        assert_eq!(app.world().resource::<DiagnosticSinks>().len(), 1);
+
+        // Sinks can be added after the app is built.
+        let (first, second) = (MemorySink::new(2), MemorySink::new(2));
+        app.world_mut()
+            .resource_mut::<DiagnosticSinks>()
+            .add(first.clone())
+            .add(second.clone());
+        for value in [1.0, 2.0, 3.0] {
+            app.world_mut()
+                .resource_mut::<DiagnosticsStore>()
+                .get_mut(&PATH)
+                .unwrap()
+                .add_measurement(DiagnosticMeasurement {
+                    time: Instant::now(),
+                    value,
+                });
+            app.update();
+        }
+
+        let reports = first.reports();
+        assert_eq!(reports, second.reports());
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[1].get(&PATH).unwrap().value, Some(3.0));
+    }
+}
@@ -0,0 +1,222 @@
+use alloc::vec::Vec;
+
+use bevy_ecs::prelude::*;
+use log::warn;
+
+use crate::{DiagnosticPath, DiagnosticsStore};
+
+/// A limit a [`Diagnostic`](crate::Diagnostic) value shouldn't cross.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiagnosticLimit {
+    /// The value is exceeded when it is greater than this.
+    Above(f64),
+    /// The value is exceeded when it is less than this.
+    Below(f64),
+}
+
+impl DiagnosticLimit {
+    /// Returns `true` if `value` crosses this limit.
+    pub fn is_exceeded_by(&self, value: f64) -> bool {
+        match *self {
+            DiagnosticLimit::Above(limit) => value > limit,
+            DiagnosticLimit::Below(limit) => value < limit,
+        }
+    }
+}
+
+/// A threshold on the value of a [`Diagnostic`](crate::Diagnostic), added to
+/// [`DiagnosticThresholds`].
+///
+/// When the value crosses the [`limit`](Self::limit), a [`DiagnosticThresholdExceeded`] event is
+/// sent. It is sent again only once the value has gone back within the limit and crossed it
+/// again.
+#[derive(Debug, Clone)]
+pub struct DiagnosticThreshold {
+    /// The path of the diagnostic to watch.
+    pub path: DiagnosticPath,
+    /// The limit the value shouldn't cross.
+    pub limit: DiagnosticLimit,
+    /// Whether the smoothed value is compared to the limit, rather than the latest one, so that a
+    /// single spike doesn't exceed it.
+    ///
+    /// Defaults to `true`.
+    pub smoothed: bool,
+    /// Whether a warning is logged when the threshold is exceeded.
+    ///
+    /// Defaults to `false`.
+    pub log_warning: bool,
+    exceeded: bool,
+}
+
+impl DiagnosticThreshold {
+    /// Creates a threshold exceeded when the diagnostic value is greater than `limit`.
+    pub fn above(path: DiagnosticPath, limit: f64) -> Self {
+        Self::new(path, DiagnosticLimit::Above(limit))
+    }
+
+    /// Creates a threshold exceeded when the diagnostic value is less than `limit`.
+    pub fn below(path: DiagnosticPath, limit: f64) -> Self {
+        Self::new(path, DiagnosticLimit::Below(limit))
+    }
+
+    /// Creates a threshold exceeded when the diagnostic value crosses `limit`.
+    pub fn new(path: DiagnosticPath, limit: DiagnosticLimit) -> Self {
+        Self {
+            path,
+            limit,
+            smoothed: true,
+            log_warning: false,
+            exceeded: false,
+        }
+    }
+
+    /// Sets whether the smoothed value is compared to the limit.
+    pub fn with_smoothed(mut self, smoothed: bool) -> Self {
+        self.smoothed = smoothed;
+        self
+    }
+
+    /// Sets whether a warning is logged when the threshold is exceeded.
+    pub fn with_log_warning(mut self, log_warning: bool) -> Self {
+        self.log_warning = log_warning;
+        self
+    }
+
+    /// Returns `true` if the diagnostic value is currently crossing the limit.
+    pub fn is_exceeded(&self) -> bool {
+        self.exceeded
+    }
+}
+
+/// The [`DiagnosticThreshold`]s checked every frame by the
+/// [`DiagnosticsPlugin`](crate::DiagnosticsPlugin).
+///
+/// ```
+/// use bevy_diagnostic::{DiagnosticThreshold, DiagnosticThresholds, FrameTimeDiagnosticsPlugin};
+///
+/// let mut thresholds = DiagnosticThresholds::default();
+/// // Frame time is measured in milliseconds.
+/// thresholds.add(
+///     DiagnosticThreshold::above(FrameTimeDiagnosticsPlugin::FRAME_TIME, 33.0)
+///         .with_log_warning(true),
+/// );
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct DiagnosticThresholds {
+    thresholds: Vec<DiagnosticThreshold>,
+}
+
+impl DiagnosticThresholds {
+    /// Adds a threshold.
+    pub fn add(&mut self, threshold: DiagnosticThreshold) -> &mut Self {
+        self.thresholds.push(threshold);
+        self
+    }
+
+    /// Removes all the thresholds on the diagnostic with the given path.
+    pub fn remove(&mut self, path: &DiagnosticPath) {
+        self.thresholds.retain(|threshold| threshold.path != *path);
+    }
+
+    /// Returns an iterator over the thresholds.
+    pub fn iter(&self) -> impl Iterator<Item = &DiagnosticThreshold> {
+        self.thresholds.iter()
+    }
+}
+
+/// An event sent when the value of a diagnostic crosses a [`DiagnosticThreshold`].
+#[derive(Event, Debug, Clone)]
+pub struct DiagnosticThresholdExceeded {
+    /// The path of the diagnostic.
+    pub path: DiagnosticPath,
+    /// The value that crossed the limit.
+    pub value: f64,
+    /// The limit that was crossed.
+    pub limit: DiagnosticLimit,
+}
+
+/// Sends [`DiagnosticThresholdExceeded`] events for the [`DiagnosticThresholds`] that are newly
+/// exceeded.
+pub fn check_diagnostic_thresholds(
+    mut thresholds: ResMut<DiagnosticThresholds>,
+    diagnostics: Res<DiagnosticsStore>,
+    mut exceeded_events: EventWriter<DiagnosticThresholdExceeded>,
+) {
+    for threshold in &mut thresholds.bypass_change_detection().thresholds {
+        let Some(diagnostic) = diagnostics
+            .get(&threshold.path)
+            .filter(|diagnostic| diagnostic.is_enabled)
+        else {
+            continue;
+        };
+        let value = if threshold.smoothed {
+            diagnostic.smoothed()
+        } else {
+            diagnostic.value()
+        };
+        let Some(value) = value else {
+            continue;
+        };
+
+        let exceeded = threshold.limit.is_exceeded_by(value);
+        if exceeded && !threshold.exceeded {
+            if threshold.log_warning {
+                let (DiagnosticLimit::Above(limit) | DiagnosticLimit::Below(limit)) =
+                    threshold.limit;
+                warn!(
+                    "{} is {value:.2}{}, which exceeds its threshold of {limit:.2}{}",
+                    threshold.path, diagnostic.suffix, diagnostic.suffix
+                );
+            }
+            exceeded_events.write(DiagnosticThresholdExceeded {
+                path: threshold.path.clone(),
+                value,
+                limit: threshold.limit,
+            });
+        }
+        threshold.exceeded = exceeded;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Diagnostic, DiagnosticMeasurement, DiagnosticsPlugin, RegisterDiagnostic};
+    use bevy_app::prelude::*;
+    use bevy_platform_support::time::Instant;
+
+    const PATH: DiagnosticPath = DiagnosticPath::const_new("test");
+
+    #[test]
+    fn threshold_exceeded_once() {
+        let mut app = App::new();
+        app.add_plugins(DiagnosticsPlugin)
+            .register_diagnostic(Diagnostic::new(PATH));
+        app.world_mut()
+            .resource_mut::<DiagnosticThresholds>()
+            .add(DiagnosticThreshold::above(PATH, 10.0).with_smoothed(false));
+
+        let measure = |app: &mut App, value: f64| {
+            app.world_mut()
+                .resource_mut::<DiagnosticsStore>()
+                .get_mut(&PATH)
+                .unwrap()
+                .add_measurement(DiagnosticMeasurement {
+                    time: Instant::now(),
+                    value,
+                });
+            app.update();
+            app.world_mut()
+                .resource_mut::<Events<DiagnosticThresholdExceeded>>()
+                .drain()
+                .count()
+        };
+
+        assert_eq!(measure(&mut app, 5.0), 0);
+        assert_eq!(measure(&mut app, 20.0), 1);
+        // Still exceeded, not sent again.
+        assert_eq!(measure(&mut app, 20.0), 0);
+        assert_eq!(measure(&mut app, 5.0), 0);
+        assert_eq!(measure(&mut app, 20.0), 1);
+    }
+}
@@ -0,0 +1,443 @@
+use super::{Diagnostic, DiagnosticPath, DiagnosticsStore};
+use alloc::{string::String, vec::Vec};
+use bevy_ecs::prelude::*;
+use bevy_platform_support::{collections::HashMap, hash::PassHash};
+use core::fmt::{self, Write as _};
+
+#[cfg(feature = "std")]
+use {
+    super::FrameTimeDiagnosticsPlugin,
+    alloc::{string::ToString, vec},
+    bevy_app::prelude::*,
+    bevy_time::{Real, Time, Timer, TimerMode},
+    core::time::Duration,
+    log::{error, info},
+    std::{fs, path::PathBuf},
+};
+
+/// The averages of diagnostics recorded during a reference run, to find the regressions of later
+/// runs.
+///
+/// When this resource exists, the [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) logs the
+/// change of each diagnostic from its baseline. It is saved and loaded as CSV, with a
+/// `path,average` header, by the [`DiagnosticsBaselinePlugin`].
+#[derive(Resource, Debug, Clone, Default)]
+pub struct DiagnosticsBaseline {
+    averages: HashMap<DiagnosticPath, f64, PassHash>,
+}
+
+impl DiagnosticsBaseline {
+    /// Records the current averages of the enabled diagnostics.
+    pub fn from_diagnostics(diagnostics: &DiagnosticsStore) -> Self {
+        let mut baseline = Self::default();
+        for diagnostic in diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_enabled)
+        {
+            if let Some(average) = diagnostic.average().filter(|average| average.is_finite()) {
+                baseline.insert(diagnostic.path().clone(), average);
+            }
+        }
+        baseline
+    }
+
+    /// Parses a baseline from the CSV written by [`DiagnosticsBaseline::to_csv`].
+    pub fn from_csv(text: &str) -> Result<Self, BaselineParseError> {
+        let mut baseline = Self::default();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || (index == 0 && line == "path,average") {
+                continue;
+            }
+            // The average is after the last comma, so that paths may contain commas.
+            let (path, average) = line
+                .rsplit_once(',')
+                .filter(|(path, _)| !path.is_empty())
+                .ok_or(BaselineParseError { line: index + 1 })?;
+            let average = average
+                .trim()
+                .parse()
+                .map_err(|_| BaselineParseError { line: index + 1 })?;
+            baseline.insert(DiagnosticPath::new(String::from(path)), average);
+        }
+        Ok(baseline)
+    }
+
+    /// Writes the baseline as CSV, sorted by path.
+    pub fn to_csv(&self) -> String {
+        let mut averages: Vec<_> = self.averages.iter().collect();
+        averages.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+
+        let mut text = String::from("path,average\n");
+        for (path, average) in averages {
+            let _ = writeln!(text, "{path},{average}");
+        }
+        text
+    }
+
+    /// Sets the baseline average of a diagnostic.
+    pub fn insert(&mut self, path: DiagnosticPath, average: f64) {
+        self.averages.insert(path, average);
+    }
+
+    /// Returns the baseline average of a diagnostic.
+    pub fn get(&self, path: &DiagnosticPath) -> Option<f64> {
+        self.averages.get(path).copied()
+    }
+
+    /// Returns the number of diagnostics in the baseline.
+    pub fn len(&self) -> usize {
+        self.averages.len()
+    }
+
+    /// Returns `true` if the baseline has no diagnostics.
+    pub fn is_empty(&self) -> bool {
+        self.averages.is_empty()
+    }
+
+    /// Returns the relative change of the average of `diagnostic` from its baseline, so that `0.1`
+    /// is 10% more than the baseline.
+    ///
+    /// Returns `None` if the diagnostic isn't in the baseline, has no average, or has a baseline of
+    /// zero.
+    pub fn delta(&self, diagnostic: &Diagnostic) -> Option<f64> {
+        let baseline = self
+            .get(diagnostic.path())
+            .filter(|baseline| *baseline != 0.0)?;
+        let average = diagnostic.average()?;
+        Some((average - baseline) / baseline.abs())
+    }
+
+    /// Compares the enabled diagnostics of the baseline to their current average.
+    ///
+    /// A diagnostic has regressed when it changed by more than `tolerance`, a fraction of its
+    /// baseline, in the wrong direction: up, unless its path is in `higher_is_better`.
+    pub fn compare(
+        &self,
+        diagnostics: &DiagnosticsStore,
+        tolerance: f64,
+        higher_is_better: &[DiagnosticPath],
+    ) -> Vec<BaselineComparison> {
+        let mut comparisons: Vec<_> = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_enabled)
+            .filter_map(|diagnostic| {
+                let delta = self.delta(diagnostic)?;
+                let worse = if higher_is_better.contains(diagnostic.path()) {
+                    -delta
+                } else {
+                    delta
+                };
+                Some(BaselineComparison {
+                    path: diagnostic.path().clone(),
+                    baseline: self.get(diagnostic.path())?,
+                    average: diagnostic.average()?,
+                    delta,
+                    regressed: worse > tolerance,
+                })
+            })
+            .collect();
+        comparisons.sort_by(|a, b| a.path.as_str().cmp(b.path.as_str()));
+        comparisons
+    }
+}
+
+/// A diagnostic compared to its [`DiagnosticsBaseline`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaselineComparison {
+    /// The path of the diagnostic.
+    pub path: DiagnosticPath,
+    /// The average in the baseline.
+    pub baseline: f64,
+    /// The current average.
+    pub average: f64,
+    /// The relative change from the baseline.
+    pub delta: f64,
+    /// Whether the change is a regression beyond the tolerance.
+    pub regressed: bool,
+}
+
+impl fmt::Display for BaselineComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {:.3} (baseline {:.3}, {:+.1}%)",
+            self.path,
+            self.average,
+            self.baseline,
+            self.delta * 100.0
+        )
+    }
+}
+
+/// An error returned when a [`DiagnosticsBaseline`] can't be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BaselineParseError {
+    /// The line that couldn't be parsed, starting from 1.
+    pub line: usize,
+}
+
+impl fmt::Display for BaselineParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {} of the baseline isn't a `path,average` record",
+            self.line
+        )
+    }
+}
+
+impl core::error::Error for BaselineParseError {}
+
+/// An App Plugin that records the averages of the diagnostics to a baseline file, or checks them
+/// against it to catch performance regressions.
+///
+/// After the app ran for [`duration`](Self::duration), the current averages are recorded or
+/// compared. Each average covers the history of its diagnostic, the last
+/// [`DEFAULT_MAX_HISTORY_LENGTH`](crate::DEFAULT_MAX_HISTORY_LENGTH) measurements by default.
+/// When comparing, the [`DiagnosticsBaseline`] is also inserted as a resource from the start, so
+/// that the [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) logs the changes.
+///
+/// With [`exit`](Self::exit) set, the app exits once done, with an error code if a diagnostic
+/// regressed, to run the check in CI:
+///
+/// ```no_run
+/// # use bevy_app::prelude::*;
+/// # use bevy_diagnostic::{DiagnosticsBaselinePlugin, FrameTimeDiagnosticsPlugin};
+/// App::new()
+///     .add_plugins(FrameTimeDiagnosticsPlugin::default())
+///     .add_plugins(DiagnosticsBaselinePlugin {
+///         exit: true,
+///         ..DiagnosticsBaselinePlugin::compare("benches/baseline.csv")
+///     })
+///     .run();
+/// ```
+#[cfg(feature = "std")]
+pub struct DiagnosticsBaselinePlugin {
+    /// The baseline file.
+    pub path: PathBuf,
+    /// Whether the baseline is recorded or compared to.
+    pub mode: BaselineMode,
+    /// How long the app runs before the averages are recorded or compared.
+    ///
+    /// Defaults to 10 seconds.
+    pub duration: Duration,
+    /// Whether the app exits once the averages are recorded or compared.
+    ///
+    /// Defaults to `false`.
+    pub exit: bool,
+    /// How much a diagnostic may change from its baseline before it is a regression, as a fraction
+    /// of the baseline.
+    ///
+    /// Defaults to `0.1`, 10%.
+    pub tolerance: f64,
+    /// The diagnostics that regress when they go down rather than up.
+    ///
+    /// Defaults to [`FrameTimeDiagnosticsPlugin::FPS`].
+    pub higher_is_better: Vec<DiagnosticPath>,
+}
+
+/// Whether the [`DiagnosticsBaselinePlugin`] records or compares to its baseline.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaselineMode {
+    /// The averages are written to the baseline file, replacing it.
+    Record,
+    /// The averages are compared to the baseline file, logging the regressions as errors.
+    Compare,
+}
+
+#[cfg(feature = "std")]
+impl DiagnosticsBaselinePlugin {
+    /// Creates a plugin recording the baseline to the file at `path`.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self::new(path, BaselineMode::Record)
+    }
+
+    /// Creates a plugin comparing the diagnostics to the baseline in the file at `path`.
+    pub fn compare(path: impl Into<PathBuf>) -> Self {
+        Self::new(path, BaselineMode::Compare)
+    }
+
+    fn new(path: impl Into<PathBuf>, mode: BaselineMode) -> Self {
+        Self {
+            path: path.into(),
+            mode,
+            duration: Duration::from_secs(10),
+            exit: false,
+            tolerance: 0.1,
+            higher_is_better: vec![FrameTimeDiagnosticsPlugin::FPS],
+        }
+    }
+}
+
+/// State used by the [`DiagnosticsBaselinePlugin`]
+#[cfg(feature = "std")]
+#[derive(Resource)]
+struct DiagnosticsBaselineState {
+    timer: Timer,
+    path: PathBuf,
+    mode: BaselineMode,
+    exit: bool,
+    tolerance: f64,
+    higher_is_better: Vec<DiagnosticPath>,
+}
+
+#[cfg(feature = "std")]
+impl Plugin for DiagnosticsBaselinePlugin {
+    fn build(&self, app: &mut App) {
+        if self.mode == BaselineMode::Compare {
+            let baseline = fs::read_to_string(&self.path)
+                .map_err(|err| err.to_string())
+                .and_then(|text| {
+                    DiagnosticsBaseline::from_csv(&text).map_err(|err| err.to_string())
+                });
+            match baseline {
+                Ok(baseline) => {
+                    app.insert_resource(baseline);
+                }
+                Err(err) => {
+                    error!(
+                        "Failed to load the diagnostics baseline {}: {err}",
+                        self.path.display()
+                    );
+                    return;
+                }
+            }
+        }
+
+        app.init_resource::<DiagnosticsStore>()
+            .insert_resource(DiagnosticsBaselineState {
+                timer: Timer::new(self.duration, TimerMode::Once),
+                path: self.path.clone(),
+                mode: self.mode,
+                exit: self.exit,
+                tolerance: self.tolerance,
+                higher_is_better: self.higher_is_better.clone(),
+            })
+            .add_systems(Last, Self::baseline_system);
+    }
+}
+
+#[cfg(feature = "std")]
+impl DiagnosticsBaselinePlugin {
+    fn baseline_system(
+        mut state: ResMut<DiagnosticsBaselineState>,
+        time: Res<Time<Real>>,
+        diagnostics: Res<DiagnosticsStore>,
+        baseline: Option<Res<DiagnosticsBaseline>>,
+        mut exits: EventWriter<AppExit>,
+    ) {
+        if !state.timer.tick(time.delta()).just_finished() {
+            return;
+        }
+
+        let mut exit = AppExit::Success;
+        match state.mode {
+            BaselineMode::Record => {
+                let baseline = DiagnosticsBaseline::from_diagnostics(&diagnostics);
+                match fs::write(&state.path, baseline.to_csv()) {
+                    Ok(()) => info!(
+                        "Recorded the baseline of {} diagnostics to {}",
+                        baseline.len(),
+                        state.path.display()
+                    ),
+                    Err(err) => {
+                        error!(
+                            "Failed to write the diagnostics baseline {}: {err}",
+                            state.path.display()
+                        );
+                        exit = AppExit::error();
+                    }
+                }
+            }
+            BaselineMode::Compare => {
+                let Some(baseline) = baseline else {
+                    return;
+                };
+                let comparisons =
+                    baseline.compare(&diagnostics, state.tolerance, &state.higher_is_better);
+                let regressions = comparisons.iter().filter(|c| c.regressed).count();
+                for comparison in &comparisons {
+                    if comparison.regressed {
+                        error!("Regression: {comparison}");
+                    } else {
+                        info!("{comparison}");
+                    }
+                }
+                if regressions == 0 {
+                    info!(
+                        "Compared {} diagnostics to the baseline, without regressions",
+                        comparisons.len()
+                    );
+                } else {
+                    error!(
+                        "{regressions} of {} diagnostics regressed beyond the {}% tolerance",
+                        comparisons.len(),
+                        state.tolerance * 100.0
+                    );
+                    exit = AppExit::error();
+                }
+            }
+        }
+
+        if state.exit {
+            exits.write(exit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DiagnosticMeasurement;
+    use bevy_platform_support::time::Instant;
+
+    const FRAME_TIME: DiagnosticPath = DiagnosticPath::const_new("frame_time");
+    const FPS: DiagnosticPath = DiagnosticPath::const_new("fps");
+
+    fn store(frame_time: f64, fps: f64) -> DiagnosticsStore {
+        let mut diagnostics = DiagnosticsStore::default();
+        for (path, value) in [(FRAME_TIME, frame_time), (FPS, fps)] {
+            let mut diagnostic = Diagnostic::new(path);
+            diagnostic.add_measurement(DiagnosticMeasurement {
+                time: Instant::now(),
+                value,
+            });
+            diagnostics.add(diagnostic);
+        }
+        diagnostics
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let baseline = DiagnosticsBaseline::from_diagnostics(&store(16.5, 60.0));
+        let csv = baseline.to_csv();
+        assert_eq!(csv, "path,average\nfps,60\nframe_time,16.5\n");
+
+        let parsed = DiagnosticsBaseline::from_csv(&csv).unwrap();
+        assert_eq!(parsed.get(&FRAME_TIME), Some(16.5));
+        assert_eq!(parsed.get(&FPS), Some(60.0));
+        assert_eq!(
+            DiagnosticsBaseline::from_csv("path,average\nfps,fast\n").unwrap_err(),
+            BaselineParseError { line: 2 }
+        );
+    }
+
+    #[test]
+    fn regressions() {
+        let baseline = DiagnosticsBaseline::from_diagnostics(&store(16.0, 60.0));
+
+        // Within the tolerance.
+        let comparisons = baseline.compare(&store(17.0, 57.0), 0.1, &[FPS]);
+        assert!(comparisons.iter().all(|c| !c.regressed));
+
+        // Slower frames and fewer frames per second regress, faster frames don't.
+        let comparisons = baseline.compare(&store(20.0, 50.0), 0.1, &[FPS]);
+        assert!(comparisons.iter().all(|c| c.regressed));
+        assert_eq!(comparisons[1].path, FRAME_TIME);
+        assert_eq!(comparisons[1].delta, 0.25);
+        let comparisons = baseline.compare(&store(8.0, 120.0), 0.1, &[FPS]);
+        assert!(comparisons.iter().all(|c| !c.regressed));
+    }
+}
@@ -0,0 +1,229 @@
+use super::{Diagnostic, DiagnosticLabels, DiagnosticsStore};
+use alloc::{format, string::String, sync::Arc};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_time::{Real, Time, Timer, TimerMode};
+use core::{fmt::Write as _, time::Duration};
+use log::{error, info};
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::Mutex,
+    thread,
+};
+
+/// An App Plugin that serves all enabled diagnostics over HTTP in the
+/// [Prometheus text format](https://prometheus.io/docs/instrumenting/exposition_formats/).
+///
+/// Every request to [`address`](Self::address), whatever its path, is answered with the
+/// latest value and smoothed value of each diagnostic, as gauges. Their names are the
+/// [`DiagnosticPath`](crate::DiagnosticPath) prefixed with `bevy_`, with characters that aren't
+/// allowed by Prometheus replaced by `_`, so that `fps` is exported as `bevy_fps` and
+/// `bevy_fps_smoothed`. The [`DiagnosticLabels`] are added to each gauge as Prometheus labels.
+///
+/// The requests are handled on a separate thread, and the exported values are refreshed every
+/// [`wait_duration`](Self::wait_duration).
+pub struct DiagnosticsExporterPlugin {
+    /// The address the HTTP server listens on.
+    pub address: SocketAddr,
+    /// How often the exported values are refreshed.
+    pub wait_duration: Duration,
+}
+
+impl Default for DiagnosticsExporterPlugin {
+    fn default() -> Self {
+        DiagnosticsExporterPlugin {
+            address: SocketAddr::from(([127, 0, 0, 1], 9464)),
+            wait_duration: Duration::from_secs(1),
+        }
+    }
+}
+
+/// State used by the [`DiagnosticsExporterPlugin`]
+#[derive(Resource)]
+struct DiagnosticsExporterState {
+    timer: Timer,
+    metrics: Arc<Mutex<String>>,
+}
+
+impl Plugin for DiagnosticsExporterPlugin {
+    fn build(&self, app: &mut App) {
+        let metrics = Arc::new(Mutex::new(String::new()));
+
+        let listener = match TcpListener::bind(self.address) {
+            Ok(listener) => listener,
+            Err(err) => {
+                error!(
+                    "Failed to start the diagnostics exporter on {}: {err}",
+                    self.address
+                );
+                return;
+            }
+        };
+        info!("Exporting diagnostics on http://{}", self.address);
+        let server_metrics = metrics.clone();
+        let spawned = thread::Builder::new()
+            .name("Diagnostics exporter".into())
+            .spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    serve_metrics(stream, &server_metrics);
+                }
+            });
+        if let Err(err) = spawned {
+            error!("Failed to spawn the diagnostics exporter thread: {err}");
+            return;
+        }
+
+        app.insert_resource(DiagnosticsExporterState {
+            timer: Timer::new(self.wait_duration, TimerMode::Repeating),
+            metrics,
+        })
+        .add_systems(PostUpdate, Self::export_diagnostics_system);
+    }
+}
+
+impl DiagnosticsExporterPlugin {
+    fn export_diagnostics_system(
+        mut state: ResMut<DiagnosticsExporterState>,
+        time: Res<Time<Real>>,
+        diagnostics: Res<DiagnosticsStore>,
+        labels: Option<Res<DiagnosticLabels>>,
+    ) {
+        if !state.timer.tick(time.delta()).finished() {
+            return;
+        }
+
+        let labels = labels.as_deref().map(format_labels).unwrap_or_default();
+        let mut text = String::new();
+        for diagnostic in diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_enabled)
+        {
+            write_diagnostic(&mut text, diagnostic, &labels);
+        }
+        *state.metrics.lock().unwrap() = text;
+    }
+}
+
+/// Answers a single HTTP request with the exported metrics.
+fn serve_metrics(mut stream: TcpStream, metrics: &Mutex<String>) {
+    // The request itself is ignored, but it has to be read before answering.
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(1)));
+    let mut request = [0; 1024];
+    let mut read = 0;
+    while let Ok(count @ 1..) = stream.read(&mut request[read..]) {
+        read += count;
+        if request[..read].windows(4).any(|end| end == b"\r\n\r\n") || read == request.len() {
+            break;
+        }
+    }
+
+    let body = metrics.lock().unwrap().clone();
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+/// Replaces the characters that aren't allowed in Prometheus names by `_`.
+fn sanitize_name(name: &str) -> impl Iterator<Item = char> + '_ {
+    name.chars().map(|c| {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            c
+        } else {
+            '_'
+        }
+    })
+}
+
+/// Formats the labels in the Prometheus text format, like `{scene="level_3"}`, or an empty
+/// string without labels.
+fn format_labels(labels: &DiagnosticLabels) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let mut text = String::from("{");
+    for (i, (key, value)) in labels.iter().enumerate() {
+        if i > 0 {
+            text.push(',');
+        }
+        if key.starts_with(|c: char| c.is_ascii_digit()) {
+            text.push('_');
+        }
+        text.extend(sanitize_name(key));
+        text.push_str("=\"");
+        for c in value.chars() {
+            match c {
+                '\\' => text.push_str("\\\\"),
+                '"' => text.push_str("\\\""),
+                '\n' => text.push_str("\\n"),
+                c => text.push(c),
+            }
+        }
+        text.push('"');
+    }
+    text.push('}');
+    text
+}
+
+/// Appends the gauges of a diagnostic in the Prometheus text format, with the formatted `labels`.
+fn write_diagnostic(text: &mut String, diagnostic: &Diagnostic, labels: &str) {
+    let mut name = String::from("bevy_");
+    name.extend(sanitize_name(diagnostic.path().as_str()));
+    let path = diagnostic.path().as_str();
+    let suffix = &diagnostic.suffix;
+
+    if let Some(value) = diagnostic.value() {
+        let _ = writeln!(text, "# HELP {name} Latest value of `{path}` {suffix}");
+        let _ = writeln!(text, "# TYPE {name} gauge");
+        let _ = writeln!(text, "{name}{labels} {value}");
+    }
+    if let Some(smoothed) = diagnostic.smoothed() {
+        let _ = writeln!(
+            text,
+            "# HELP {name}_smoothed Smoothed value of `{path}` {suffix}"
+        );
+        let _ = writeln!(text, "# TYPE {name}_smoothed gauge");
+        let _ = writeln!(text, "{name}_smoothed{labels} {smoothed}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DiagnosticMeasurement, DiagnosticPath};
+    use bevy_platform_support::time::Instant;
+
+    #[test]
+    fn prometheus_text_format() {
+        let mut diagnostic = Diagnostic::new(DiagnosticPath::const_new("render/frame_time"))
+            .with_suffix("ms")
+            .with_smoothing_factor(0.0);
+        diagnostic.add_measurement(DiagnosticMeasurement {
+            time: Instant::now(),
+            value: 16.5,
+        });
+
+        let mut text = String::new();
+        write_diagnostic(&mut text, &diagnostic, "");
+        assert_eq!(
+            text,
+            "# HELP bevy_render_frame_time Latest value of `render/frame_time` ms\n\
+             # TYPE bevy_render_frame_time gauge\n\
+             bevy_render_frame_time 16.5\n\
+             # HELP bevy_render_frame_time_smoothed Smoothed value of `render/frame_time` ms\n\
+             # TYPE bevy_render_frame_time_smoothed gauge\n\
+             bevy_render_frame_time_smoothed 16.5\n"
+        );
+
+        let labels = DiagnosticLabels::new()
+            .with("scene", "level \"3\"")
+            .with("2d", "true");
+        let mut text = String::new();
+        write_diagnostic(&mut text, &diagnostic, &format_labels(&labels));
+        assert!(
+            text.contains("bevy_render_frame_time{_2d=\"true\",scene=\"level \\\"3\\\"\"} 16.5\n")
+        );
+    }
+}
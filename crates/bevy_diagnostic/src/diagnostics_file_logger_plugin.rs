@@ -0,0 +1,284 @@
+use super::{Diagnostic, DiagnosticLabels, DiagnosticPath, DiagnosticsStore};
+use alloc::{format, string::String, vec::Vec};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_time::{Real, Time, Timer, TimerMode};
+use core::{fmt::Write as _, time::Duration};
+use log::error;
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+/// An App Plugin that appends diagnostics to a file, in a machine-readable format.
+///
+/// Every [`wait_duration`](Self::wait_duration), one record is written per diagnostic, holding the
+/// number of seconds since the app started, the path of the diagnostic, and its latest and
+/// smoothed values, and the [`DiagnosticLabels`] set when it was written. Missing values are left
+/// empty in CSV and written as `null` in JSON.
+///
+/// This is useful to collect benchmark results, for example from headless runs in CI.
+pub struct DiagnosticsFileLoggerPlugin {
+    /// The file the diagnostics are appended to. It is created if it doesn't exist.
+    pub path: PathBuf,
+    /// The format of the records.
+    pub format: DiagnosticsFileFormat,
+    /// How often the diagnostics are written.
+    pub wait_duration: Duration,
+    /// The diagnostics to write, or `None` to write all the enabled diagnostics.
+    ///
+    /// Each path also selects the diagnostics below it.
+    pub filter: Option<Vec<DiagnosticPath>>,
+}
+
+/// The format of the records written by the [`DiagnosticsFileLoggerPlugin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiagnosticsFileFormat {
+    /// Comma-separated values, with a `time,path,value,smoothed,labels` header when the file is
+    /// empty. The labels are written as `key=value` pairs separated by `;`.
+    #[default]
+    Csv,
+    /// One JSON object per line, with `time`, `path`, `value`, `smoothed` and `labels` fields, the
+    /// labels being an object.
+    JsonLines,
+}
+
+impl DiagnosticsFileLoggerPlugin {
+    /// Creates a plugin writing all the enabled diagnostics to the file at `path`, every second.
+    pub fn new(path: impl Into<PathBuf>, format: DiagnosticsFileFormat) -> Self {
+        DiagnosticsFileLoggerPlugin {
+            path: path.into(),
+            format,
+            wait_duration: Duration::from_secs(1),
+            filter: None,
+        }
+    }
+}
+
+/// State used by the [`DiagnosticsFileLoggerPlugin`]
+#[derive(Resource)]
+struct DiagnosticsFileLoggerState {
+    timer: Timer,
+    format: DiagnosticsFileFormat,
+    filter: Option<Vec<DiagnosticPath>>,
+    file: BufWriter<File>,
+}
+
+impl Plugin for DiagnosticsFileLoggerPlugin {
+    fn build(&self, app: &mut App) {
+        let file = match OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+        {
+            Ok(file) => file,
+            Err(err) => {
+                error!(
+                    "Failed to open the diagnostics file {}: {err}",
+                    self.path.display()
+                );
+                return;
+            }
+        };
+        let is_empty = file.metadata().is_ok_and(|metadata| metadata.len() == 0);
+        let mut file = BufWriter::new(file);
+        if self.format == DiagnosticsFileFormat::Csv && is_empty {
+            if let Err(err) = writeln!(file, "time,path,value,smoothed,labels") {
+                error!("Failed to write to the diagnostics file: {err}");
+            }
+        }
+
+        app.insert_resource(DiagnosticsFileLoggerState {
+            timer: Timer::new(self.wait_duration, TimerMode::Repeating),
+            format: self.format,
+            filter: self.filter.clone(),
+            file,
+        })
+        .add_systems(PostUpdate, Self::write_diagnostics_system);
+    }
+}
+
+impl DiagnosticsFileLoggerPlugin {
+    fn write_diagnostics_system(
+        mut state: ResMut<DiagnosticsFileLoggerState>,
+        time: Res<Time<Real>>,
+        diagnostics: Res<DiagnosticsStore>,
+        labels: Option<Res<DiagnosticLabels>>,
+    ) {
+        if !state.timer.tick(time.delta()).finished() {
+            return;
+        }
+
+        let state = &mut *state;
+        let elapsed = time.elapsed_secs_f64();
+        let no_labels = DiagnosticLabels::default();
+        let labels = labels.as_deref().unwrap_or(&no_labels);
+        let mut text = String::new();
+        let mut write = |diagnostic: &Diagnostic| {
+            if diagnostic.is_enabled {
+                write_record(&mut text, state.format, elapsed, diagnostic, labels);
+            }
+        };
+        if let Some(filter) = &state.filter {
+            filter
+                .iter()
+                .flat_map(|path| diagnostics.iter_prefixed(path))
+                .for_each(&mut write);
+        } else {
+            diagnostics.iter().for_each(&mut write);
+        }
+
+        if let Err(err) = state
+            .file
+            .write_all(text.as_bytes())
+            .and_then(|()| state.file.flush())
+        {
+            error!("Failed to write to the diagnostics file: {err}");
+        }
+    }
+}
+
+/// Appends the record of a diagnostic, in the given format.
+fn write_record(
+    text: &mut String,
+    format: DiagnosticsFileFormat,
+    elapsed: f64,
+    diagnostic: &Diagnostic,
+    labels: &DiagnosticLabels,
+) {
+    let path = diagnostic.path().as_str();
+    let value = diagnostic.value().filter(|value| value.is_finite());
+    let smoothed = diagnostic.smoothed().filter(|value| value.is_finite());
+    match format {
+        DiagnosticsFileFormat::Csv => {
+            let _ = write!(text, "{elapsed},");
+            write_csv_field(text, path);
+            text.push(',');
+            if let Some(value) = value {
+                let _ = write!(text, "{value}");
+            }
+            text.push(',');
+            if let Some(smoothed) = smoothed {
+                let _ = write!(text, "{smoothed}");
+            }
+            text.push(',');
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect();
+            write_csv_field(text, &labels.join(";"));
+            text.push('\n');
+        }
+        DiagnosticsFileFormat::JsonLines => {
+            let _ = write!(text, "{{\"time\":{elapsed},\"path\":");
+            write_json_string(text, path);
+            text.push_str(",\"value\":");
+            match value {
+                Some(value) => {
+                    let _ = write!(text, "{value}");
+                }
+                None => text.push_str("null"),
+            }
+            text.push_str(",\"smoothed\":");
+            match smoothed {
+                Some(smoothed) => {
+                    let _ = write!(text, "{smoothed}");
+                }
+                None => text.push_str("null"),
+            }
+            text.push_str(",\"labels\":{");
+            for (i, (key, value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    text.push(',');
+                }
+                write_json_string(text, key);
+                text.push(':');
+                write_json_string(text, value);
+            }
+            text.push_str("}}\n");
+        }
+    }
+}
+
+/// Appends `value` as a CSV field, quoted if needed.
+fn write_csv_field(text: &mut String, value: &str) {
+    if value.contains([',', '"', '\n']) {
+        let _ = write!(text, "\"{}\"", value.replace('"', "\"\""));
+    } else {
+        text.push_str(value);
+    }
+}
+
+/// Appends `value` as a JSON string, with quotes.
+pub(crate) fn write_json_string(text: &mut String, value: &str) {
+    text.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => text.push_str("\\\""),
+            '\\' => text.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(text, "\\u{:04x}", c as u32);
+            }
+            c => text.push(c),
+        }
+    }
+    text.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DiagnosticMeasurement;
+    use bevy_platform_support::time::Instant;
+
+    #[test]
+    fn record_formats() {
+        let mut diagnostic =
+            Diagnostic::new(DiagnosticPath::const_new("frame_time")).with_smoothing_factor(0.0);
+        diagnostic.add_measurement(DiagnosticMeasurement {
+            time: Instant::now(),
+            value: 16.5,
+        });
+
+        let labels = DiagnosticLabels::new()
+            .with("scene", "level_3")
+            .with("quality", "high");
+        let mut text = String::new();
+        write_record(
+            &mut text,
+            DiagnosticsFileFormat::Csv,
+            2.0,
+            &diagnostic,
+            &labels,
+        );
+        assert_eq!(text, "2,frame_time,16.5,16.5,quality=high;scene=level_3\n");
+
+        let mut text = String::new();
+        write_record(
+            &mut text,
+            DiagnosticsFileFormat::JsonLines,
+            2.0,
+            &diagnostic,
+            &labels,
+        );
+        assert_eq!(
+            text,
+            "{\"time\":2,\"path\":\"frame_time\",\"value\":16.5,\"smoothed\":16.5,\"labels\":{\"quality\":\"high\",\"scene\":\"level_3\"}}\n"
+        );
+
+        let diagnostic = Diagnostic::new(DiagnosticPath::const_new("empty"));
+        let mut text = String::new();
+        write_record(
+            &mut text,
+            DiagnosticsFileFormat::JsonLines,
+            2.0,
+            &diagnostic,
+            &DiagnosticLabels::new(),
+        );
+        assert_eq!(
+            text,
+            "{\"time\":2,\"path\":\"empty\",\"value\":null,\"smoothed\":null,\"labels\":{}}\n"
+        );
+    }
+}
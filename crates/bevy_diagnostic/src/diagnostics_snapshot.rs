@@ -0,0 +1,206 @@
+use super::{Diagnostic, DiagnosticPath, DiagnosticStats, DiagnosticsStore};
+use alloc::{borrow::Cow, vec::Vec};
+
+/// An owned copy of the values of all the [`Diagnostic`]s at some point, which can be compared
+/// and kept after the app is dropped, for example to check performance characteristics in tests.
+///
+/// Snapshots are taken with [`DiagnosticsStore::snapshot`] or
+/// [`Diagnostics::snapshot`](crate::Diagnostics::snapshot). Diagnostics measuring time, like frame
+/// times, can be made deterministic with a [`DiagnosticsClock`](crate::DiagnosticsClock).
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_diagnostic::{DiagnosticsPlugin, DiagnosticsStore, EntityCountDiagnosticsPlugin};
+/// let mut app = App::new();
+/// app.add_plugins((DiagnosticsPlugin, EntityCountDiagnosticsPlugin));
+/// for _ in 0..10 {
+///     app.update();
+/// }
+///
+/// let snapshot = app.world().resource::<DiagnosticsStore>().snapshot();
+/// snapshot.assert_average_below(&EntityCountDiagnosticsPlugin::ENTITY_COUNT, 100.0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DiagnosticsSnapshot {
+    /// The diagnostics, sorted by path.
+    diagnostics: Vec<(DiagnosticPath, DiagnosticSnapshot)>,
+}
+
+/// The values of a [`Diagnostic`] in a [`DiagnosticsSnapshot`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagnosticSnapshot {
+    /// The suffix of the diagnostic, usually its unit.
+    pub suffix: Cow<'static, str>,
+    /// The latest value.
+    pub value: Option<f64>,
+    /// The smoothed value.
+    pub smoothed: Option<f64>,
+    /// The minimum, maximum and average of the history.
+    pub stats: Option<DiagnosticStats>,
+}
+
+impl DiagnosticSnapshot {
+    fn new(diagnostic: &Diagnostic) -> Self {
+        Self {
+            suffix: diagnostic.suffix.clone(),
+            value: diagnostic.value(),
+            smoothed: diagnostic.smoothed(),
+            stats: diagnostic.stats(),
+        }
+    }
+}
+
+impl DiagnosticsSnapshot {
+    /// Takes a snapshot of all the diagnostics of the `store`.
+    pub fn from_store(store: &DiagnosticsStore) -> Self {
+        Self::from_diagnostics(store.iter())
+    }
+
+    /// Takes a snapshot of the given diagnostics.
+    pub fn from_diagnostics<'a>(diagnostics: impl IntoIterator<Item = &'a Diagnostic>) -> Self {
+        let mut diagnostics: Vec<_> = diagnostics
+            .into_iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.path().clone(),
+                    DiagnosticSnapshot::new(diagnostic),
+                )
+            })
+            .collect();
+        diagnostics.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        Self { diagnostics }
+    }
+
+    /// Returns the values of the diagnostic with the given path.
+    pub fn get(&self, path: &DiagnosticPath) -> Option<&DiagnosticSnapshot> {
+        self.diagnostics
+            .binary_search_by(|(other, _)| other.as_str().cmp(path.as_str()))
+            .ok()
+            .map(|index| &self.diagnostics[index].1)
+    }
+
+    /// Returns an iterator over the diagnostic paths and their values, sorted by path.
+    pub fn iter(&self) -> impl Iterator<Item = (&DiagnosticPath, &DiagnosticSnapshot)> {
+        self.diagnostics
+            .iter()
+            .map(|(path, snapshot)| (path, snapshot))
+    }
+
+    /// Returns the number of diagnostics.
+    pub fn len(&self) -> usize {
+        self.diagnostics.len()
+    }
+
+    /// Returns `true` if there are no diagnostics.
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Returns the average of the history of the diagnostic with the given path.
+    pub fn average(&self, path: &DiagnosticPath) -> Option<f64> {
+        Some(self.get(path)?.stats?.average)
+    }
+
+    /// Panics if the average of the diagnostic with the given path isn't below `max`, or if it
+    /// has no measurements.
+    #[track_caller]
+    pub fn assert_average_below(&self, path: &DiagnosticPath, max: f64) {
+        let stats = self.expect_stats(path);
+        assert!(
+            stats.average < max,
+            "the average of {path} is {}, expected below {max}",
+            stats.average
+        );
+    }
+
+    /// Panics if the average of the diagnostic with the given path isn't above `min`, or if it
+    /// has no measurements.
+    #[track_caller]
+    pub fn assert_average_above(&self, path: &DiagnosticPath, min: f64) {
+        let stats = self.expect_stats(path);
+        assert!(
+            stats.average > min,
+            "the average of {path} is {}, expected above {min}",
+            stats.average
+        );
+    }
+
+    /// Panics if any value of the diagnostic with the given path isn't below `max`, or if it has
+    /// no measurements.
+    #[track_caller]
+    pub fn assert_max_below(&self, path: &DiagnosticPath, max: f64) {
+        let stats = self.expect_stats(path);
+        assert!(
+            stats.max < max,
+            "the maximum of {path} is {}, expected below {max}",
+            stats.max
+        );
+    }
+
+    #[track_caller]
+    fn expect_stats(&self, path: &DiagnosticPath) -> DiagnosticStats {
+        let Some(diagnostic) = self.get(path) else {
+            panic!("no diagnostic {path} in the snapshot");
+        };
+        let Some(stats) = diagnostic.stats else {
+            panic!("the diagnostic {path} has no measurements");
+        };
+        stats
+    }
+}
+
+impl DiagnosticsStore {
+    /// Takes a [`DiagnosticsSnapshot`] of all the diagnostics.
+    pub fn snapshot(&self) -> DiagnosticsSnapshot {
+        DiagnosticsSnapshot::from_store(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DiagnosticMeasurement, RegisterDiagnostic};
+    use bevy_app::App;
+    use bevy_platform_support::time::Instant;
+
+    const PATH: DiagnosticPath = DiagnosticPath::const_new("test/value");
+
+    fn app_with_values(values: &[f64]) -> App {
+        let mut app = App::new();
+        app.register_diagnostic(Diagnostic::new(PATH).with_suffix("ms"));
+        let mut store = app.world_mut().resource_mut::<DiagnosticsStore>();
+        let diagnostic = store.get_mut(&PATH).unwrap();
+        let time = Instant::now();
+        for &value in values {
+            diagnostic.add_measurement(DiagnosticMeasurement { time, value });
+        }
+        app
+    }
+
+    #[test]
+    fn snapshot_assertions() {
+        let app = app_with_values(&[2.0, 4.0, 6.0]);
+        let snapshot = app.world().resource::<DiagnosticsStore>().snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot.average(&PATH), Some(4.0));
+        assert_eq!(snapshot.get(&PATH).unwrap().value, Some(6.0));
+        snapshot.assert_average_below(&PATH, 5.0);
+        snapshot.assert_average_above(&PATH, 3.0);
+        snapshot.assert_max_below(&PATH, 7.0);
+
+        // Snapshots of the same values are equal.
+        let same = app_with_values(&[2.0, 4.0, 6.0]);
+        assert_eq!(
+            same.world().resource::<DiagnosticsStore>().snapshot(),
+            snapshot
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the average of test/value is 4, expected below 3")]
+    fn failed_assertion() {
+        let app = app_with_values(&[2.0, 4.0, 6.0]);
+        let snapshot = app.world().resource::<DiagnosticsStore>().snapshot();
+        snapshot.assert_average_below(&PATH, 3.0);
+    }
+}
@@ -0,0 +1,450 @@
+use super::{
+    diagnostics_file_logger_plugin::write_json_string, Diagnostic, DiagnosticLabels,
+    DiagnosticPath, DiagnosticsStore,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_platform_support::{collections::HashMap, time::Instant};
+use bevy_time::{Real, Time, Timer, TimerMode};
+use core::{fmt::Write as _, time::Duration};
+
+/// The version of the protocol used by the [`DiagnosticsStreamPlugin`].
+pub const DIAGNOSTICS_STREAM_VERSION: u32 = 1;
+
+/// The delay before reconnecting after the first failed connection. It doubles after each failure,
+/// up to [`MAX_RECONNECT_DELAY`].
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// An App Plugin that streams diagnostic measurements to an external dashboard, so that it can
+/// graph a running app.
+///
+/// The app connects to the dashboard at [`address`](Self::address), over TCP, or over a
+/// WebSocket on Wasm. When the connection can't be made or is lost, it is retried after a delay
+/// doubling from one second up to thirty seconds, and the measurements are dropped meanwhile.
+///
+/// # Protocol
+///
+/// Each message is a UTF-8 JSON object. Over TCP, each message is preceded by its length in bytes,
+/// as a big-endian `u32`. Over a WebSocket, each message is a text message. After each
+/// connection, the app sends:
+///
+/// - `{"type":"hello","version":1}`, with the [`DIAGNOSTICS_STREAM_VERSION`].
+/// - `{"type":"describe","diagnostics":[{"path":"fps","suffix":""}]}`, describing the
+///   diagnostics. It is sent again when diagnostics are added.
+/// - Every [`wait_duration`](Self::wait_duration),
+///   `{"type":"update","time":1.5,"values":{"fps":60.1}}`, with the time in seconds since the app
+///   started, and the latest value of each enabled diagnostic measured since the previous update.
+///   When there are [`DiagnosticLabels`], they are added as a `"labels":{"scene":"level_3"}`
+///   object.
+pub struct DiagnosticsStreamPlugin {
+    /// The address of the dashboard, like `127.0.0.1:9465`.
+    ///
+    /// On Wasm, it is the URL of the WebSocket, like `ws://127.0.0.1:9465`. Addresses without a
+    /// scheme are prefixed with `ws://`.
+    pub address: String,
+    /// How often the measurements are sent.
+    pub wait_duration: Duration,
+}
+
+impl Default for DiagnosticsStreamPlugin {
+    fn default() -> Self {
+        DiagnosticsStreamPlugin {
+            address: "127.0.0.1:9465".to_string(),
+            wait_duration: Duration::from_millis(100),
+        }
+    }
+}
+
+/// State used by the [`DiagnosticsStreamPlugin`]
+///
+/// It isn't `Send` on Wasm, where it holds a WebSocket.
+struct DiagnosticsStreamState {
+    timer: Timer,
+    transport: transport::Transport,
+    /// The connection the messages are sent to, if connected.
+    connection: Option<u32>,
+    /// The number of diagnostics described to the current connection.
+    described: usize,
+    /// The time of the last measurement sent for each diagnostic.
+    last_sent: HashMap<DiagnosticPath, Instant>,
+}
+
+impl Plugin for DiagnosticsStreamPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(transport) = transport::Transport::new(&self.address) else {
+            return;
+        };
+        app.init_resource::<DiagnosticsStore>()
+            .insert_non_send_resource(DiagnosticsStreamState {
+                timer: Timer::new(self.wait_duration, TimerMode::Repeating),
+                transport,
+                connection: None,
+                described: 0,
+                last_sent: HashMap::default(),
+            })
+            .add_systems(PostUpdate, Self::stream_diagnostics_system);
+    }
+}
+
+impl DiagnosticsStreamPlugin {
+    fn stream_diagnostics_system(
+        mut state: NonSendMut<DiagnosticsStreamState>,
+        time: Res<Time<Real>>,
+        diagnostics: Res<DiagnosticsStore>,
+        labels: Option<Res<DiagnosticLabels>>,
+    ) {
+        if !state.timer.tick(time.delta()).finished() {
+            return;
+        }
+        let state = &mut *state;
+
+        let connection = state.transport.poll(time.elapsed());
+        if connection != state.connection {
+            state.connection = connection;
+            state.described = 0;
+            state.last_sent.clear();
+            if let Some(connection) = connection {
+                state.transport.send(connection, hello_message());
+            }
+        }
+        let Some(connection) = state.connection else {
+            return;
+        };
+
+        let mut diagnostics: Vec<&Diagnostic> = diagnostics.iter().collect();
+        diagnostics.sort_by(|a, b| a.path().as_str().cmp(b.path().as_str()));
+        if diagnostics.len() != state.described {
+            state.described = diagnostics.len();
+            state
+                .transport
+                .send(connection, describe_message(&diagnostics));
+        }
+
+        let mut values = Vec::new();
+        for diagnostic in diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_enabled)
+        {
+            let Some(measurement) = diagnostic.measurement() else {
+                continue;
+            };
+            let last_sent = state.last_sent.get(diagnostic.path());
+            if !measurement.value.is_finite() || last_sent.is_some_and(|&t| t >= measurement.time) {
+                continue;
+            }
+            state
+                .last_sent
+                .insert(diagnostic.path().clone(), measurement.time);
+            values.push((diagnostic.path(), measurement.value));
+        }
+        if !values.is_empty() {
+            state.transport.send(
+                connection,
+                update_message(time.elapsed().as_secs_f64(), &values, labels.as_deref()),
+            );
+        }
+    }
+}
+
+fn hello_message() -> String {
+    format!("{{\"type\":\"hello\",\"version\":{DIAGNOSTICS_STREAM_VERSION}}}")
+}
+
+fn describe_message(diagnostics: &[&Diagnostic]) -> String {
+    let mut text = String::from("{\"type\":\"describe\",\"diagnostics\":[");
+    for (i, diagnostic) in diagnostics.iter().enumerate() {
+        if i > 0 {
+            text.push(',');
+        }
+        text.push_str("{\"path\":");
+        write_json_string(&mut text, diagnostic.path().as_str());
+        text.push_str(",\"suffix\":");
+        write_json_string(&mut text, &diagnostic.suffix);
+        text.push('}');
+    }
+    text.push_str("]}");
+    text
+}
+
+fn update_message(
+    time: f64,
+    values: &[(&DiagnosticPath, f64)],
+    labels: Option<&DiagnosticLabels>,
+) -> String {
+    let mut text = format!("{{\"type\":\"update\",\"time\":{time},\"values\":{{");
+    for (i, (path, value)) in values.iter().enumerate() {
+        if i > 0 {
+            text.push(',');
+        }
+        write_json_string(&mut text, path.as_str());
+        let _ = write!(text, ":{value}");
+    }
+    text.push('}');
+    if let Some(labels) = labels.filter(|labels| !labels.is_empty()) {
+        text.push_str(",\"labels\":{");
+        for (i, (key, value)) in labels.iter().enumerate() {
+            if i > 0 {
+                text.push(',');
+            }
+            write_json_string(&mut text, key);
+            text.push(':');
+            write_json_string(&mut text, value);
+        }
+        text.push('}');
+    }
+    text.push('}');
+    text
+}
+
+/// Prefixes a message with its length, to send it over TCP.
+#[cfg_attr(
+    target_arch = "wasm32",
+    expect(dead_code, reason = "Wasm uses WebSockets.")
+)]
+fn encode_frame(message: &str) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(4 + message.len());
+    frame.extend_from_slice(&(message.len() as u32).to_be_bytes());
+    frame.extend_from_slice(message.as_bytes());
+    frame
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod transport {
+    use super::{encode_frame, MAX_RECONNECT_DELAY, MIN_RECONNECT_DELAY};
+    use alloc::{string::String, sync::Arc};
+    use core::{
+        sync::atomic::{AtomicU32, Ordering},
+        time::Duration,
+    };
+    use log::{error, info, warn};
+    use std::{
+        io::Write,
+        net::TcpStream,
+        sync::mpsc::{sync_channel, Receiver, RecvTimeoutError, SyncSender},
+        thread,
+        time::Instant,
+    };
+
+    /// The number of messages waiting to be sent after which new ones are dropped.
+    const QUEUE_LENGTH: usize = 64;
+
+    /// Sends the messages over TCP from a separate thread, which connects and reconnects to the
+    /// dashboard.
+    pub(super) struct Transport {
+        sender: SyncSender<(u32, String)>,
+        /// The id of the current connection, or `0` when disconnected.
+        connection: Arc<AtomicU32>,
+    }
+
+    impl Transport {
+        pub(super) fn new(address: &str) -> Option<Self> {
+            let (sender, receiver) = sync_channel(QUEUE_LENGTH);
+            let connection = Arc::new(AtomicU32::new(0));
+            let thread_connection = connection.clone();
+            let address = String::from(address);
+            let spawned = thread::Builder::new()
+                .name("Diagnostics stream".into())
+                .spawn(move || run(&address, &receiver, &thread_connection));
+            if let Err(err) = spawned {
+                error!("Failed to spawn the diagnostics stream thread: {err}");
+                return None;
+            }
+            Some(Self { sender, connection })
+        }
+
+        /// Returns the id of the current connection, if connected.
+        pub(super) fn poll(&mut self, _now: Duration) -> Option<u32> {
+            match self.connection.load(Ordering::Relaxed) {
+                0 => None,
+                connection => Some(connection),
+            }
+        }
+
+        /// Sends a message, if `connection` is still the current connection.
+        pub(super) fn send(&mut self, connection: u32, message: String) {
+            // The message is dropped if the dashboard is too slow to read them.
+            let _ = self.sender.try_send((connection, message));
+        }
+    }
+
+    fn run(address: &str, receiver: &Receiver<(u32, String)>, connection: &AtomicU32) {
+        let mut id = 0u32;
+        let mut delay = MIN_RECONNECT_DELAY;
+        let mut warned = false;
+        loop {
+            match TcpStream::connect(address) {
+                Ok(mut stream) => {
+                    info!("Streaming diagnostics to {address}");
+                    let _ = stream.set_nodelay(true);
+                    let _ = stream.set_write_timeout(Some(Duration::from_secs(5)));
+                    id = id.wrapping_add(1).max(1);
+                    connection.store(id, Ordering::Relaxed);
+                    delay = MIN_RECONNECT_DELAY;
+                    warned = false;
+
+                    loop {
+                        let Ok((message_connection, message)) = receiver.recv() else {
+                            // The app was dropped.
+                            return;
+                        };
+                        // Messages sent before a reconnection are stale.
+                        if message_connection != id {
+                            continue;
+                        }
+                        if let Err(err) = stream.write_all(&encode_frame(&message)) {
+                            warn!("Lost the connection to the diagnostics dashboard at {address}: {err}");
+                            break;
+                        }
+                    }
+                    connection.store(0, Ordering::Relaxed);
+                }
+                Err(err) => {
+                    if !warned {
+                        warn!("Failed to connect to the diagnostics dashboard at {address}, retrying: {err}");
+                        warned = true;
+                    }
+                }
+            }
+
+            // Wait before reconnecting, dropping the messages sent meanwhile.
+            let deadline = Instant::now() + delay;
+            loop {
+                match receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(_) => {}
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+            }
+            delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod transport {
+    use super::{MAX_RECONNECT_DELAY, MIN_RECONNECT_DELAY};
+    use alloc::{format, string::String};
+    use core::time::Duration;
+    use log::{info, warn};
+    use web_sys::WebSocket;
+
+    /// Sends the messages over a WebSocket, reconnecting when it is closed.
+    pub(super) struct Transport {
+        url: String,
+        socket: Option<WebSocket>,
+        /// The id of the current connection, or `0` when disconnected.
+        connection: u32,
+        id: u32,
+        retry_at: Duration,
+        delay: Duration,
+    }
+
+    impl Transport {
+        pub(super) fn new(address: &str) -> Option<Self> {
+            let url = match address.contains("://") {
+                true => String::from(address),
+                false => format!("ws://{address}"),
+            };
+            Some(Self {
+                url,
+                socket: None,
+                connection: 0,
+                id: 0,
+                retry_at: Duration::ZERO,
+                delay: MIN_RECONNECT_DELAY,
+            })
+        }
+
+        /// Returns the id of the current connection, if connected, and reconnects if needed.
+        pub(super) fn poll(&mut self, now: Duration) -> Option<u32> {
+            match self.socket.as_ref().map(WebSocket::ready_state) {
+                Some(WebSocket::OPEN) => {
+                    if self.connection == 0 {
+                        info!("Streaming diagnostics to {}", self.url);
+                        self.id = self.id.wrapping_add(1).max(1);
+                        self.connection = self.id;
+                        self.delay = MIN_RECONNECT_DELAY;
+                    }
+                    return Some(self.connection);
+                }
+                Some(WebSocket::CONNECTING) => return None,
+                Some(_) => {
+                    if self.connection != 0 {
+                        warn!(
+                            "Lost the connection to the diagnostics dashboard at {}",
+                            self.url
+                        );
+                        self.connection = 0;
+                    }
+                    self.socket = None;
+                    self.retry_at = now + self.delay;
+                    self.delay = (self.delay * 2).min(MAX_RECONNECT_DELAY);
+                    return None;
+                }
+                None => {}
+            }
+
+            if now >= self.retry_at {
+                match WebSocket::new(&self.url) {
+                    Ok(socket) => self.socket = Some(socket),
+                    Err(err) => {
+                        warn!(
+                            "Failed to connect to the diagnostics dashboard at {}: {err:?}",
+                            self.url
+                        );
+                        self.retry_at = now + self.delay;
+                        self.delay = (self.delay * 2).min(MAX_RECONNECT_DELAY);
+                    }
+                }
+            }
+            None
+        }
+
+        /// Sends a message, if `connection` is still the current connection.
+        pub(super) fn send(&mut self, connection: u32, message: String) {
+            if connection != self.connection {
+                return;
+            }
+            if let Some(socket) = &self.socket {
+                let _ = socket.send_with_str(&message);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages() {
+        let fps = Diagnostic::new(DiagnosticPath::const_new("fps"));
+        let frame_time = Diagnostic::new(DiagnosticPath::const_new("frame_time")).with_suffix("ms");
+        assert_eq!(
+            describe_message(&[&fps, &frame_time]),
+            "{\"type\":\"describe\",\"diagnostics\":[{\"path\":\"fps\",\"suffix\":\"\"},{\"path\":\"frame_time\",\"suffix\":\"ms\"}]}"
+        );
+        assert_eq!(
+            update_message(1.5, &[(fps.path(), 60.0), (frame_time.path(), 16.5)], None),
+            "{\"type\":\"update\",\"time\":1.5,\"values\":{\"fps\":60,\"frame_time\":16.5}}"
+        );
+        assert_eq!(
+            update_message(
+                1.5,
+                &[(fps.path(), 60.0)],
+                Some(&DiagnosticLabels::new().with("scene", "level_3"))
+            ),
+            "{\"type\":\"update\",\"time\":1.5,\"values\":{\"fps\":60},\"labels\":{\"scene\":\"level_3\"}}"
+        );
+
+        let frame = encode_frame(&hello_message());
+        assert_eq!(frame[..4], [0, 0, 0, 28]);
+        assert_eq!(&frame[4..], b"{\"type\":\"hello\",\"version\":1}");
+    }
+}
@@ -1,9 +1,13 @@
 use bevy_app::prelude::*;
-use bevy_ecs::entity::Entities;
+use bevy_ecs::{archetype::Archetypes, entity::Entities};
+use bevy_platform_support::collections::HashSet;
 
-use crate::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use crate::{Diagnostic, DiagnosticPath, DiagnosticSmoothing, Diagnostics, RegisterDiagnostic};
 
-/// Adds "entity count" diagnostic to an App.
+/// Adds "entity count", "archetype count" and "table count" diagnostics to an App.
+///
+/// Steadily growing counts usually mean that entities are leaked, or that components are added
+/// and removed in ways that keep creating new archetypes.
 ///
 /// # See also
 ///
@@ -13,15 +17,39 @@ pub struct EntityCountDiagnosticsPlugin;
 
 impl Plugin for EntityCountDiagnosticsPlugin {
     fn build(&self, app: &mut App) {
-        app.register_diagnostic(Diagnostic::new(Self::ENTITY_COUNT))
-            .add_systems(Update, Self::diagnostic_system);
+        // Counts aren't averaged, so that they are exact.
+        app.register_diagnostic(
+            Diagnostic::new(Self::ENTITY_COUNT).with_smoothing(DiagnosticSmoothing::Latest),
+        )
+        .register_diagnostic(
+            Diagnostic::new(Self::ARCHETYPE_COUNT).with_smoothing(DiagnosticSmoothing::Latest),
+        )
+        .register_diagnostic(
+            Diagnostic::new(Self::TABLE_COUNT).with_smoothing(DiagnosticSmoothing::Latest),
+        )
+        .add_systems(Update, Self::diagnostic_system);
     }
 }
 
 impl EntityCountDiagnosticsPlugin {
     pub const ENTITY_COUNT: DiagnosticPath = DiagnosticPath::const_new("entity_count");
+    pub const ARCHETYPE_COUNT: DiagnosticPath = DiagnosticPath::const_new("archetype_count");
+    /// The number of tables used by archetypes.
+    pub const TABLE_COUNT: DiagnosticPath = DiagnosticPath::const_new("table_count");
 
-    pub fn diagnostic_system(mut diagnostics: Diagnostics, entities: &Entities) {
+    pub fn diagnostic_system(
+        mut diagnostics: Diagnostics,
+        entities: &Entities,
+        archetypes: &Archetypes,
+    ) {
         diagnostics.add_measurement(&Self::ENTITY_COUNT, || entities.len() as f64);
+        diagnostics.add_measurement(&Self::ARCHETYPE_COUNT, || archetypes.len() as f64);
+        diagnostics.add_measurement(&Self::TABLE_COUNT, || {
+            archetypes
+                .iter()
+                .map(|archetype| archetype.table_id().as_u32())
+                .collect::<HashSet<_>>()
+                .len() as f64
+        });
     }
 }
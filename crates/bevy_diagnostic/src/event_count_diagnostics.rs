@@ -0,0 +1,119 @@
+use alloc::format;
+use bevy_ecs::{
+    event::{Event, Events},
+    system::{Res, ResMut},
+};
+use bevy_platform_support::time::Instant;
+use disqualified::ShortName;
+
+use crate::{
+    Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticSmoothing, DiagnosticsStore,
+};
+
+/// Returns the path of the diagnostic recorded by [`event_count_diagnostic_system`] for the number
+/// of events of type `T` sent since its previous run: `events/<T>/sent`.
+pub fn event_sent_diagnostic_path<T: Event>() -> DiagnosticPath {
+    DiagnosticPath::new(format!("events/{}/sent", ShortName::of::<T>()))
+}
+
+/// Returns the path of the diagnostic recorded by [`event_count_diagnostic_system`] for the number
+/// of events of type `T` still stored in [`Events<T>`]: `events/<T>/unread`.
+pub fn event_unread_diagnostic_path<T: Event>() -> DiagnosticPath {
+    DiagnosticPath::new(format!("events/{}/unread", ShortName::of::<T>()))
+}
+
+/// Returns a system recording how many events of type `T` were sent since its previous run, and
+/// how many are still stored in [`Events<T>`] waiting to be read, to spot event backlogs.
+///
+/// Events are normally dropped after being stored for two updates of [`Events<T>`], so a growing
+/// number of unread events means that they aren't updated, for example because
+/// [`Events::update`] is called manually, and that readers won't keep up.
+///
+/// The diagnostics are registered when the system first runs, at
+/// [`event_sent_diagnostic_path`] and [`event_unread_diagnostic_path`]. Nothing is recorded while
+/// [`Events<T>`] doesn't exist.
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_diagnostic::event_count_diagnostic_system;
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Event)]
+/// struct Jump;
+///
+/// App::new()
+///     .add_event::<Jump>()
+///     .add_systems(Last, event_count_diagnostic_system::<Jump>());
+/// ```
+pub fn event_count_diagnostic_system<T: Event>(
+) -> impl FnMut(ResMut<DiagnosticsStore>, Option<Res<Events<T>>>) {
+    let sent_path = event_sent_diagnostic_path::<T>();
+    let unread_path = event_unread_diagnostic_path::<T>();
+    let mut last_event_count = 0;
+    move |mut store: ResMut<DiagnosticsStore>, events: Option<Res<Events<T>>>| {
+        for path in [&sent_path, &unread_path] {
+            if store.get(path).is_none() {
+                // Counts aren't averaged, so that they are exact.
+                store
+                    .add(Diagnostic::new(path.clone()).with_smoothing(DiagnosticSmoothing::Latest));
+            }
+        }
+        let Some(events) = events else {
+            return;
+        };
+
+        // The events that were ever sent, as cleared events are still counted in their ids.
+        let event_count = events.oldest_event_count() + events.len();
+        let sent = event_count.saturating_sub(last_event_count);
+        last_event_count = event_count;
+
+        let time = Instant::now();
+        for (path, value) in [(&sent_path, sent), (&unread_path, events.len())] {
+            if store.is_enabled(path) {
+                if let Some(diagnostic) = store.get_mut(path) {
+                    diagnostic.add_measurement(DiagnosticMeasurement {
+                        time,
+                        value: value as f64,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        system::{IntoSystem, System},
+        world::World,
+    };
+
+    #[derive(Event)]
+    struct Jump;
+
+    #[test]
+    fn counts_events() {
+        let mut world = World::new();
+        world.init_resource::<DiagnosticsStore>();
+        world.init_resource::<Events<Jump>>();
+        let mut system = IntoSystem::into_system(event_count_diagnostic_system::<Jump>());
+        system.initialize(&mut world);
+
+        let mut run = |world: &mut World, jumps: usize| {
+            let mut events = world.resource_mut::<Events<Jump>>();
+            events.update();
+            events.send_batch((0..jumps).map(|_| Jump));
+            system.run((), world);
+            let store = world.resource::<DiagnosticsStore>();
+            [
+                event_sent_diagnostic_path::<Jump>(),
+                event_unread_diagnostic_path::<Jump>(),
+            ]
+            .map(|path| store.get(&path).unwrap().value().unwrap())
+        };
+        assert_eq!(run(&mut world, 3), [3.0, 3.0]);
+        assert_eq!(run(&mut world, 2), [2.0, 5.0]);
+        assert_eq!(run(&mut world, 0), [0.0, 2.0]);
+        assert_eq!(run(&mut world, 1), [1.0, 1.0]);
+    }
+}
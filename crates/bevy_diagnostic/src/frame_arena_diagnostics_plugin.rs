@@ -0,0 +1,36 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{arena::FrameArena, system::Res};
+
+use crate::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+
+/// Adds "frame arena used bytes" diagnostic to an App.
+///
+/// The measurement is the number of bytes in use in the [`FrameArena`] at the end of the previous frame,
+/// see [`FrameArena::used_bytes`].
+/// Nothing is recorded if the [`FrameArena`] resource does not exist.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct FrameArenaDiagnosticsPlugin;
+
+impl Plugin for FrameArenaDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::USED_BYTES).with_suffix(" B"))
+            .add_systems(Update, Self::diagnostic_system);
+    }
+}
+
+impl FrameArenaDiagnosticsPlugin {
+    /// The number of bytes in use in the [`FrameArena`] at the end of the previous frame.
+    pub const USED_BYTES: DiagnosticPath = DiagnosticPath::const_new("frame_arena/used_bytes");
+
+    /// Records the bytes used in the [`FrameArena`], if it exists.
+    pub fn diagnostic_system(mut diagnostics: Diagnostics, arena: Option<Res<FrameArena>>) {
+        let Some(arena) = arena else {
+            return;
+        };
+        diagnostics.add_measurement(&Self::USED_BYTES, || arena.last_frame_used_bytes() as f64);
+    }
+}
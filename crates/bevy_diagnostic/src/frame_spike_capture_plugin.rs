@@ -0,0 +1,268 @@
+use super::{DiagnosticsStore, FrameCount, SystemRunTimesReader};
+use alloc::{format, string::String, vec::Vec};
+use bevy_app::prelude::*;
+use bevy_ecs::{prelude::*, schedule::Schedules};
+use bevy_time::{Real, Time, TimeSystem};
+use core::{cmp::Reverse, fmt::Write as _, time::Duration};
+use log::{error, info, warn};
+use std::{fs, path::PathBuf};
+
+/// An App Plugin that describes the frames taking longer than a budget, with the slowest systems,
+/// the number of entities and the latest diagnostics, to find the cause of frame spikes that
+/// averages hide.
+///
+/// Systems are measured with [`Schedule::set_record_system_run_times`], like the
+/// [`SystemProfilingPlugin`](crate::SystemProfilingPlugin), and only the schedules that ran since
+/// the previous check are listed. The frame time is the [`Time<Real>`] delta, so each frame is
+/// checked at the start of the next one, in [`First`]. Nothing is captured while collection is
+/// [paused](DiagnosticsStore::set_paused).
+pub struct FrameSpikeCapturePlugin {
+    /// Frames taking longer than this are captured.
+    ///
+    /// Defaults to 1/30th of a second.
+    pub budget: Duration,
+    /// Where the reports are written.
+    ///
+    /// Defaults to [`SpikeReportOutput::Log`].
+    pub output: SpikeReportOutput,
+    /// The number of systems listed in each report, starting from the slowest.
+    ///
+    /// Defaults to 10.
+    pub system_count: usize,
+    /// The maximum number of reports written, so that an app that is always over budget doesn't
+    /// flood the log or the disk.
+    ///
+    /// Defaults to 20.
+    pub max_reports: usize,
+}
+
+impl Default for FrameSpikeCapturePlugin {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1) / 30,
+            output: SpikeReportOutput::Log,
+            system_count: 10,
+            max_reports: 20,
+        }
+    }
+}
+
+/// Where the [`FrameSpikeCapturePlugin`] writes its reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpikeReportOutput {
+    /// Each report is logged as a warning.
+    Log,
+    /// Each report is written to a `frame_spike_<frame>.txt` file in this directory, which is
+    /// created if needed.
+    Directory(PathBuf),
+}
+
+/// State used by the [`FrameSpikeCapturePlugin`]
+#[derive(Resource)]
+struct FrameSpikeCaptureState {
+    budget: Duration,
+    output: SpikeReportOutput,
+    system_count: usize,
+    remaining_reports: usize,
+    reader: SystemRunTimesReader,
+}
+
+impl Plugin for FrameSpikeCapturePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DiagnosticsStore>()
+            .insert_resource(FrameSpikeCaptureState {
+                budget: self.budget,
+                output: self.output.clone(),
+                system_count: self.system_count,
+                remaining_reports: self.max_reports,
+                reader: SystemRunTimesReader::default(),
+            })
+            .add_systems(First, Self::capture_system.after(TimeSystem));
+    }
+}
+
+/// A system that ran during a captured frame.
+struct SystemRunTime {
+    schedule: String,
+    name: String,
+    run_time: Duration,
+}
+
+impl FrameSpikeCapturePlugin {
+    fn capture_system(world: &mut World) {
+        world.resource_scope(|world, mut state: Mut<FrameSpikeCaptureState>| {
+            let mut systems = Vec::new();
+            world.resource_scope(|_, mut schedules: Mut<Schedules>| {
+                state.reader.read(&mut schedules, |label, schedule| {
+                    systems.extend(schedule.system_run_times().map(|(_, system, run_time)| {
+                        SystemRunTime {
+                            schedule: format!("{label:?}"),
+                            name: system.name().into(),
+                            run_time,
+                        }
+                    }));
+                });
+            });
+
+            let frame_time = world.resource::<Time<Real>>().delta();
+            let diagnostics = world.resource::<DiagnosticsStore>();
+            if frame_time <= state.budget || diagnostics.is_paused() || state.remaining_reports == 0
+            {
+                return;
+            }
+            state.remaining_reports -= 1;
+
+            // The frame count was already increased at the end of the captured frame.
+            let frame = world
+                .get_resource::<FrameCount>()
+                .map(|count| count.0.wrapping_sub(1));
+            systems.sort_by_key(|system| Reverse(system.run_time));
+            systems.truncate(state.system_count);
+            let report = write_report(
+                frame,
+                frame_time,
+                state.budget,
+                &systems,
+                world.entities().len(),
+                world.archetypes().len(),
+                diagnostics,
+            );
+
+            match &state.output {
+                SpikeReportOutput::Log => warn!("{report}"),
+                SpikeReportOutput::Directory(directory) => {
+                    let name = match frame {
+                        Some(frame) => format!("frame_spike_{frame}.txt"),
+                        None => format!("frame_spike_{}.txt", state.remaining_reports),
+                    };
+                    let path = directory.join(name);
+                    match fs::create_dir_all(directory).and_then(|()| fs::write(&path, report)) {
+                        Ok(()) => info!("Wrote a frame spike report to {}", path.display()),
+                        Err(err) => error!(
+                            "Failed to write the frame spike report {}: {err}",
+                            path.display()
+                        ),
+                    }
+                }
+            }
+            if state.remaining_reports == 0 {
+                info!("Reached the maximum number of frame spike reports, no more are captured");
+            }
+        });
+    }
+}
+
+/// Describes a frame that went over budget.
+fn write_report(
+    frame: Option<u32>,
+    frame_time: Duration,
+    budget: Duration,
+    systems: &[SystemRunTime],
+    entity_count: u32,
+    archetype_count: usize,
+    diagnostics: &DiagnosticsStore,
+) -> String {
+    let mut report = String::new();
+    if let Some(frame) = frame {
+        let _ = write!(report, "Frame {frame}");
+    } else {
+        report.push_str("A frame");
+    }
+    let _ = writeln!(
+        report,
+        " took {:.3}ms, over the {:.3}ms budget",
+        millis(frame_time),
+        millis(budget)
+    );
+    let _ = writeln!(
+        report,
+        "Entities: {entity_count}, archetypes: {archetype_count}"
+    );
+
+    if !systems.is_empty() {
+        report.push_str("\nSlowest systems:\n");
+        for system in systems {
+            let _ = writeln!(
+                report,
+                "{:>10.3}ms  {:<16} {}",
+                millis(system.run_time),
+                system.schedule,
+                system.name
+            );
+        }
+    }
+
+    let mut diagnostics: Vec<_> = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.is_enabled)
+        .filter_map(|diagnostic| Some((diagnostic, diagnostic.value()?)))
+        .collect();
+    if !diagnostics.is_empty() {
+        diagnostics.sort_by(|(a, _), (b, _)| a.path().as_str().cmp(b.path().as_str()));
+        report.push_str("\nDiagnostics:\n");
+        for (diagnostic, value) in diagnostics {
+            let _ = write!(
+                report,
+                "  {:<40} {value:.3}{}",
+                diagnostic.path().as_str(),
+                diagnostic.suffix
+            );
+            if let Some(average) = diagnostic.average() {
+                let _ = write!(report, " (average {average:.3}{})", diagnostic.suffix);
+            }
+            report.push('\n');
+        }
+    }
+    report
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Diagnostic, DiagnosticMeasurement, DiagnosticPath};
+    use bevy_platform_support::time::Instant;
+
+    #[test]
+    fn report() {
+        let mut diagnostics = DiagnosticsStore::default();
+        let path = DiagnosticPath::const_new("fps");
+        diagnostics.add(Diagnostic::new(path.clone()).with_suffix("fps"));
+        diagnostics
+            .get_mut(&path)
+            .unwrap()
+            .add_measurement(DiagnosticMeasurement {
+                time: Instant::now(),
+                value: 20.0,
+            });
+        let systems = [SystemRunTime {
+            schedule: "Update".into(),
+            name: "my_game::physics".into(),
+            run_time: Duration::from_millis(40),
+        }];
+
+        let report = write_report(
+            Some(7),
+            Duration::from_millis(50),
+            Duration::from_millis(20),
+            &systems,
+            3,
+            2,
+            &diagnostics,
+        );
+        assert_eq!(
+            report,
+            "Frame 7 took 50.000ms, over the 20.000ms budget\n\
+             Entities: 3, archetypes: 2\n\
+             \n\
+             Slowest systems:\n    \
+             40.000ms  Update           my_game::physics\n\
+             \n\
+             Diagnostics:\n  \
+             fps                                      20.000fps (average 20.000fps)\n"
+        );
+    }
+}
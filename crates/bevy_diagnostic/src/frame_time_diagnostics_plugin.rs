@@ -1,13 +1,16 @@
 use crate::{
-    Diagnostic, DiagnosticPath, Diagnostics, FrameCount, RegisterDiagnostic,
+    Diagnostic, DiagnosticPath, DiagnosticSmoothing, Diagnostics, FrameCount, RegisterDiagnostic,
     DEFAULT_MAX_HISTORY_LENGTH,
 };
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
-use bevy_time::{Real, Time};
+use bevy_platform_support::time::Instant;
 
 /// Adds "frame time" diagnostic to an App, specifically "frame time", "fps" and "frame count"
 ///
+/// Frame times are measured between updates with the [`DiagnosticsClock`](crate::DiagnosticsClock),
+/// so that they can be recorded in browsers, with the `web` feature, or with a custom clock.
+///
 /// # See also
 ///
 /// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
@@ -50,7 +53,7 @@ impl Plugin for FrameTimeDiagnosticsPlugin {
         // to zero and disable smoothing.
         .register_diagnostic(
             Diagnostic::new(Self::FRAME_COUNT)
-                .with_smoothing_factor(0.0)
+                .with_smoothing(DiagnosticSmoothing::Latest)
                 .with_max_history_length(0),
         )
         .add_systems(Update, Self::diagnostic_system);
@@ -64,12 +67,20 @@ impl FrameTimeDiagnosticsPlugin {
 
     pub fn diagnostic_system(
         mut diagnostics: Diagnostics,
-        time: Res<Time<Real>>,
         frame_count: Res<FrameCount>,
+        mut last_update: Local<Option<Instant>>,
     ) {
+        let now = diagnostics.now();
+        let Some(last_update) = last_update.replace(now) else {
+            return;
+        };
+        if diagnostics.is_paused() {
+            return;
+        }
+
         diagnostics.add_measurement(&Self::FRAME_COUNT, || frame_count.0 as f64);
 
-        let delta_seconds = time.delta_secs_f64();
+        let delta_seconds = now.saturating_duration_since(last_update).as_secs_f64();
         if delta_seconds == 0.0 {
             return;
         }
@@ -79,3 +90,41 @@ impl FrameTimeDiagnosticsPlugin {
         diagnostics.add_measurement(&Self::FPS, || 1.0 / delta_seconds);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DiagnosticsClock, DiagnosticsPlugin, DiagnosticsStore, FrameCountPlugin};
+    use alloc::sync::Arc;
+    use core::{
+        sync::atomic::{AtomicU64, Ordering},
+        time::Duration,
+    };
+
+    #[test]
+    fn measures_with_the_clock() {
+        let start = Instant::now();
+        let elapsed_millis = Arc::new(AtomicU64::new(0));
+        let clock_millis = elapsed_millis.clone();
+        let mut app = App::new();
+        app.add_plugins((
+            DiagnosticsPlugin,
+            FrameCountPlugin,
+            FrameTimeDiagnosticsPlugin::default(),
+        ))
+        .insert_resource(DiagnosticsClock::new(move || {
+            start + Duration::from_millis(clock_millis.load(Ordering::Relaxed))
+        }));
+        for _ in 0..3 {
+            app.update();
+            elapsed_millis.fetch_add(20, Ordering::Relaxed);
+        }
+
+        let store = app.world().resource::<DiagnosticsStore>();
+        let frame_time = store.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME).unwrap();
+        assert_eq!(frame_time.measurements().count(), 2);
+        assert!((frame_time.value().unwrap() - 20.0).abs() < 1e-6);
+        let fps = store.get(&FrameTimeDiagnosticsPlugin::FPS).unwrap();
+        assert!((fps.value().unwrap() - 50.0).abs() < 1e-6);
+    }
+}
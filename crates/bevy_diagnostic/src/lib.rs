@@ -1,6 +1,6 @@
 #![expect(missing_docs, reason = "Not all docs are written yet, see #3492.")]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "allocation_diagnostics"), forbid(unsafe_code))]
 #![doc(
     html_logo_url = "https://bevyengine.org/assets/icon.png",
     html_favicon_url = "https://bevyengine.org/assets/icon.png"
@@ -16,32 +16,102 @@ extern crate std;
 
 extern crate alloc;
 
+#[cfg(feature = "allocation_diagnostics")]
+mod allocation_diagnostics_plugin;
 mod diagnostic;
+mod diagnostic_clock;
+mod diagnostic_entities_plugin;
+mod diagnostic_labels;
+mod diagnostic_sink;
+mod diagnostic_threshold;
+mod diagnostics_baseline_plugin;
+#[cfg(feature = "diagnostics_exporter")]
+mod diagnostics_exporter_plugin;
+#[cfg(feature = "std")]
+mod diagnostics_file_logger_plugin;
+mod diagnostics_snapshot;
+#[cfg(feature = "diagnostics_stream")]
+mod diagnostics_stream_plugin;
 mod entity_count_diagnostics_plugin;
+mod event_count_diagnostics;
+#[cfg(feature = "std")]
+mod frame_arena_diagnostics_plugin;
 mod frame_count_diagnostics_plugin;
+#[cfg(feature = "std")]
+mod frame_spike_capture_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
+mod startup_time_diagnostics_plugin;
 #[cfg(feature = "sysinfo_plugin")]
 mod system_information_diagnostics_plugin;
+mod system_profiling_plugin;
+mod system_run_times_reader;
+#[cfg(feature = "std")]
+mod trace_export_plugin;
+mod world_memory_diagnostics_plugin;
 
+#[cfg(feature = "allocation_diagnostics")]
+pub use allocation_diagnostics_plugin::{AllocationDiagnosticsPlugin, TrackingAllocator};
 pub use diagnostic::*;
+pub use diagnostic_clock::{DiagnosticClock, DiagnosticsClock, InstantClock};
+pub use diagnostic_entities_plugin::{
+    DiagnosticEntities, DiagnosticEntitiesPlugin, DiagnosticValue,
+};
+pub use diagnostic_labels::DiagnosticLabels;
+pub use diagnostic_sink::{DiagnosticReport, DiagnosticSink, DiagnosticSinks, LogSink, MemorySink};
+#[cfg(feature = "std")]
+pub use diagnostic_sink::{FileSink, StdoutSink};
+pub use diagnostic_threshold::*;
+pub use diagnostics_baseline_plugin::{
+    BaselineComparison, BaselineParseError, DiagnosticsBaseline,
+};
+#[cfg(feature = "std")]
+pub use diagnostics_baseline_plugin::{BaselineMode, DiagnosticsBaselinePlugin};
 
+#[cfg(feature = "diagnostics_exporter")]
+pub use diagnostics_exporter_plugin::DiagnosticsExporterPlugin;
+#[cfg(feature = "std")]
+pub use diagnostics_file_logger_plugin::{DiagnosticsFileFormat, DiagnosticsFileLoggerPlugin};
+pub use diagnostics_snapshot::{DiagnosticSnapshot, DiagnosticsSnapshot};
+#[cfg(feature = "diagnostics_stream")]
+pub use diagnostics_stream_plugin::{DiagnosticsStreamPlugin, DIAGNOSTICS_STREAM_VERSION};
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
+pub use event_count_diagnostics::{
+    event_count_diagnostic_system, event_sent_diagnostic_path, event_unread_diagnostic_path,
+};
+#[cfg(feature = "std")]
+pub use frame_arena_diagnostics_plugin::FrameArenaDiagnosticsPlugin;
 pub use frame_count_diagnostics_plugin::{update_frame_count, FrameCount, FrameCountPlugin};
+#[cfg(feature = "std")]
+pub use frame_spike_capture_plugin::{FrameSpikeCapturePlugin, SpikeReportOutput};
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
+pub use startup_time_diagnostics_plugin::StartupTimeDiagnosticsPlugin;
 #[cfg(feature = "sysinfo_plugin")]
 pub use system_information_diagnostics_plugin::{SystemInfo, SystemInformationDiagnosticsPlugin};
+pub use system_profiling_plugin::SystemProfilingPlugin;
+pub use system_run_times_reader::SystemRunTimesReader;
+#[cfg(feature = "std")]
+pub use trace_export_plugin::TraceExportPlugin;
+pub use world_memory_diagnostics_plugin::WorldMemoryDiagnosticsPlugin;
 
 use bevy_app::prelude::*;
 
 /// Adds core diagnostics resources to an App.
+///
+/// This also checks the [`DiagnosticThresholds`] at the end of each frame, sending
+/// [`DiagnosticThresholdExceeded`] events.
 #[derive(Default)]
 pub struct DiagnosticsPlugin;
 
 impl Plugin for DiagnosticsPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<DiagnosticsStore>();
+        app.init_resource::<DiagnosticsStore>()
+            .init_resource::<DiagnosticsClock>()
+            .init_resource::<DiagnosticLabels>()
+            .init_resource::<DiagnosticThresholds>()
+            .add_event::<DiagnosticThresholdExceeded>()
+            .add_systems(Last, check_diagnostic_thresholds);
 
         #[cfg(feature = "sysinfo_plugin")]
         app.init_resource::<SystemInfo>();
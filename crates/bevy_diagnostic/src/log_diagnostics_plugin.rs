@@ -1,10 +1,13 @@
-use super::{Diagnostic, DiagnosticPath, DiagnosticsStore};
-use alloc::vec::Vec;
+use super::{
+    Diagnostic, DiagnosticPath, DiagnosticReport, DiagnosticSinks, DiagnosticThresholds,
+    DiagnosticsBaseline, DiagnosticsStore, LogSink,
+};
+use alloc::{format, string::String, vec, vec::Vec};
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
+use bevy_platform_support::{collections::HashMap, hash::PassHash};
 use bevy_time::{Real, Time, Timer, TimerMode};
 use core::time::Duration;
-use log::{debug, info};
 
 /// An App Plugin that logs diagnostics to the console.
 ///
@@ -12,11 +15,59 @@ use log::{debug, info};
 /// [`FrameTimeDiagnosticsPlugin`](crate::FrameTimeDiagnosticsPlugin)
 /// or can be provided by the user.
 ///
+/// The diagnostics are reported to the [`DiagnosticSinks`], a [`LogSink`] by default. Other sinks,
+/// like a [`MemorySink`](crate::MemorySink) for a debug UI, can be added to the resource at any
+/// time, and all receive the same measurements.
+///
+/// The diagnostics are logged as a table, with a column for their smoothed value, their average,
+/// their [percentiles](Self::percentiles), their change from the
+/// [`DiagnosticsBaseline`] if there is one, and their unit:
+///
+/// ```text
+/// diagnostic   value     avg  unit
+/// fps         59.871  59.902
+/// frame_time  16.702  16.694  ms
+/// ```
+///
+/// To avoid flooding the logs with values that don't change, diagnostics can be logged less often
+/// with [`rate_limits`](Self::rate_limits), or only when they change with
+/// [`change_epsilon`](Self::change_epsilon):
+///
+/// ```
+/// # use bevy_diagnostic::{DiagnosticPath, LogDiagnosticsPlugin};
+/// # use core::time::Duration;
+/// LogDiagnosticsPlugin {
+///     rate_limits: vec![(DiagnosticPath::new("entity_count"), Duration::from_secs(10))],
+///     change_epsilon: Some(0.5),
+///     ..Default::default()
+/// };
+/// ```
+///
 /// When no diagnostics are provided, this plugin does nothing.
 pub struct LogDiagnosticsPlugin {
+    /// Log all the details of each diagnostic, with the default [`LogSink`].
     pub debug: bool,
     pub wait_duration: Duration,
+    /// The diagnostics to log, or `None` to log all of them.
+    ///
+    /// Each path also selects the diagnostics below it, so that `render` logs all the
+    /// `render/*` diagnostics.
     pub filter: Option<Vec<DiagnosticPath>>,
+    /// Also log the 50th, 95th and 99th percentiles of the diagnostics with a history.
+    pub percentiles: bool,
+    /// Color the values crossing one of the [`DiagnosticThresholds`] in red.
+    ///
+    /// The colors are ANSI escape codes, which are only understood by terminals.
+    pub colored_thresholds: bool,
+    /// The minimum time between two logs of the given diagnostics, longer than the
+    /// [`wait_duration`](Self::wait_duration).
+    ///
+    /// Each path also selects the diagnostics below it. The rate limit of the most specific path
+    /// applies.
+    pub rate_limits: Vec<(DiagnosticPath, Duration)>,
+    /// Only log a diagnostic when its smoothed value changed by more than this amount since it was
+    /// last logged, or `None` to log it every time.
+    pub change_epsilon: Option<f64>,
 }
 
 /// State used by the [`LogDiagnosticsPlugin`]
@@ -24,6 +75,52 @@ pub struct LogDiagnosticsPlugin {
 struct LogDiagnosticsState {
     timer: Timer,
     filter: Option<Vec<DiagnosticPath>>,
+    percentiles: bool,
+    colored_thresholds: bool,
+    limiter: LogLimiter,
+}
+
+/// Decides which diagnostics are logged, according to their rate limits and changes.
+#[derive(Default)]
+struct LogLimiter {
+    /// The time since the plugin started.
+    elapsed: Duration,
+    rate_limits: Vec<(DiagnosticPath, Duration)>,
+    change_epsilon: Option<f64>,
+    /// When each diagnostic was last logged, and its smoothed value then.
+    logged: HashMap<DiagnosticPath, (Duration, Option<f64>), PassHash>,
+}
+
+impl LogLimiter {
+    /// Returns whether the diagnostic should be logged now, and records it if so.
+    fn should_log(&mut self, diagnostic: &Diagnostic) -> bool {
+        let value = diagnostic.smoothed();
+        if let Some(&(logged_at, logged_value)) = self.logged.get(diagnostic.path()) {
+            let rate_limit = self
+                .rate_limits
+                .iter()
+                .filter(|(path, _)| diagnostic.path().starts_with(path))
+                .max_by_key(|(path, _)| path.as_str().len())
+                .map(|(_, rate_limit)| *rate_limit)
+                .unwrap_or_default();
+            if self.elapsed.saturating_sub(logged_at) < rate_limit {
+                return false;
+            }
+            let changed = match (self.change_epsilon, logged_value, value) {
+                (Some(epsilon), Some(logged_value), Some(value)) => {
+                    (value - logged_value).abs() > epsilon
+                }
+                (Some(_), logged_value, value) => logged_value.is_some() != value.is_some(),
+                (None, ..) => true,
+            };
+            if !changed {
+                return false;
+            }
+        }
+        self.logged
+            .insert(diagnostic.path().clone(), (self.elapsed, value));
+        true
+    }
 }
 
 impl Default for LogDiagnosticsPlugin {
@@ -32,6 +129,10 @@ impl Default for LogDiagnosticsPlugin {
             debug: false,
             wait_duration: Duration::from_secs(1),
             filter: None,
+            percentiles: false,
+            colored_thresholds: false,
+            rate_limits: Vec::new(),
+            change_epsilon: None,
         }
     }
 }
@@ -41,13 +142,20 @@ impl Plugin for LogDiagnosticsPlugin {
         app.insert_resource(LogDiagnosticsState {
             timer: Timer::new(self.wait_duration, TimerMode::Repeating),
             filter: self.filter.clone(),
+            percentiles: self.percentiles,
+            colored_thresholds: self.colored_thresholds,
+            limiter: LogLimiter {
+                rate_limits: self.rate_limits.clone(),
+                change_epsilon: self.change_epsilon,
+                ..Default::default()
+            },
         });
 
-        if self.debug {
-            app.add_systems(PostUpdate, Self::log_diagnostics_debug_system);
-        } else {
-            app.add_systems(PostUpdate, Self::log_diagnostics_system);
+        if !app.world().contains_resource::<DiagnosticSinks>() {
+            app.insert_resource(DiagnosticSinks::new().with(LogSink { debug: self.debug }));
         }
+
+        app.add_systems(PostUpdate, Self::report_diagnostics_system);
     }
 }
 
@@ -59,89 +167,291 @@ impl LogDiagnosticsPlugin {
         }
     }
 
-    fn for_each_diagnostic(
-        state: &LogDiagnosticsState,
-        diagnostics: &DiagnosticsStore,
-        mut callback: impl FnMut(&Diagnostic),
+    /// Calls `callback` with the diagnostics to report now.
+    fn for_each_diagnostic<'a>(
+        state: &mut LogDiagnosticsState,
+        diagnostics: &'a DiagnosticsStore,
+        mut callback: impl FnMut(&'a Diagnostic),
     ) {
-        if let Some(filter) = &state.filter {
-            for path in filter {
-                if let Some(diagnostic) = diagnostics.get(path) {
-                    if diagnostic.is_enabled {
-                        callback(diagnostic);
-                    }
-                }
+        let LogDiagnosticsState {
+            filter, limiter, ..
+        } = state;
+        let mut log = |diagnostic: &'a Diagnostic| {
+            if diagnostic.is_enabled && limiter.should_log(diagnostic) {
+                callback(diagnostic);
             }
-        } else {
-            for diagnostic in diagnostics.iter() {
-                if diagnostic.is_enabled {
-                    callback(diagnostic);
-                }
+        };
+        if let Some(filter) = filter {
+            for path in filter.iter() {
+                diagnostics.iter_prefixed(path).for_each(&mut log);
             }
+        } else {
+            diagnostics.iter().for_each(log);
         }
     }
 
-    fn log_diagnostic(path_width: usize, diagnostic: &Diagnostic) {
-        let Some(value) = diagnostic.smoothed() else {
+    fn report_diagnostics(
+        state: &mut LogDiagnosticsState,
+        diagnostics: &DiagnosticsStore,
+        thresholds: Option<&DiagnosticThresholds>,
+        baseline: Option<&DiagnosticsBaseline>,
+        sinks: &mut DiagnosticSinks,
+    ) {
+        let (percentiles, colored_thresholds) = (state.percentiles, state.colored_thresholds);
+        let mut selected = Vec::new();
+        Self::for_each_diagnostic(state, diagnostics, |diagnostic| selected.push(diagnostic));
+        if selected.is_empty() {
             return;
-        };
-
-        if diagnostic.get_max_history_length() > 1 {
-            let Some(average) = diagnostic.average() else {
-                return;
-            };
-
-            info!(
-                target: "bevy diagnostic",
-                // Suffix is only used for 's' or 'ms' currently,
-                // so we reserve two columns for it; however,
-                // Do not reserve columns for the suffix in the average
-                // The ) hugging the value is more aesthetically pleasing
-                "{path:<path_width$}: {value:>11.6}{suffix:2} (avg {average:>.6}{suffix:})",
-                path = diagnostic.path(),
-                suffix = diagnostic.suffix,
-            );
-        } else {
-            info!(
-                target: "bevy diagnostic",
-                "{path:<path_width$}: {value:>.6}{suffix:}",
-                path = diagnostic.path(),
-                suffix = diagnostic.suffix,
-            );
         }
-    }
 
-    fn log_diagnostics(state: &LogDiagnosticsState, diagnostics: &DiagnosticsStore) {
-        let mut path_width = 0;
-        Self::for_each_diagnostic(state, diagnostics, |diagnostic| {
-            let width = diagnostic.path().as_str().len();
-            path_width = path_width.max(width);
-        });
+        let rows: Vec<_> = selected
+            .iter()
+            .filter_map(|diagnostic| {
+                let value = diagnostic.smoothed()?;
+                let has_history = diagnostic.get_max_history_length() > 1;
+                let exceeded = colored_thresholds
+                    && thresholds.is_some_and(|thresholds| {
+                        thresholds.iter().any(|threshold| {
+                            threshold.path == *diagnostic.path()
+                                && threshold.limit.is_exceeded_by(value)
+                        })
+                    });
+                Some(DiagnosticRow {
+                    path: diagnostic.path().as_str(),
+                    unit: &diagnostic.suffix,
+                    value,
+                    average: diagnostic.average().filter(|_| has_history),
+                    percentiles: [diagnostic.p50(), diagnostic.p95(), diagnostic.p99()]
+                        .map(|percentile| percentile.filter(|_| has_history)),
+                    delta: baseline.and_then(|baseline| baseline.delta(diagnostic)),
+                    exceeded,
+                })
+            })
+            .collect();
+        let table = format_table(&rows, percentiles);
 
-        Self::for_each_diagnostic(state, diagnostics, |diagnostic| {
-            Self::log_diagnostic(path_width, diagnostic);
+        sinks.report(&DiagnosticReport {
+            diagnostics: &selected,
+            table: &table,
         });
     }
 
-    fn log_diagnostics_system(
+    fn report_diagnostics_system(
         mut state: ResMut<LogDiagnosticsState>,
         time: Res<Time<Real>>,
         diagnostics: Res<DiagnosticsStore>,
+        thresholds: Option<Res<DiagnosticThresholds>>,
+        baseline: Option<Res<DiagnosticsBaseline>>,
+        sinks: Option<ResMut<DiagnosticSinks>>,
     ) {
-        if state.timer.tick(time.delta()).finished() {
-            Self::log_diagnostics(&state, &diagnostics);
+        state.limiter.elapsed += time.delta();
+        if !state.timer.tick(time.delta()).finished() {
+            return;
+        }
+        let Some(mut sinks) = sinks else {
+            return;
+        };
+        Self::report_diagnostics(
+            &mut state,
+            &diagnostics,
+            thresholds.as_deref(),
+            baseline.as_deref(),
+            &mut sinks,
+        );
+    }
+}
+
+/// A row of the table logged by the [`LogDiagnosticsPlugin`].
+struct DiagnosticRow<'a> {
+    path: &'a str,
+    unit: &'a str,
+    value: f64,
+    average: Option<f64>,
+    percentiles: [Option<f64>; 3],
+    /// The relative change of the average from the [`DiagnosticsBaseline`].
+    delta: Option<f64>,
+    /// Whether the value crosses a threshold, and is colored.
+    exceeded: bool,
+}
+
+/// Formats the lines of the table logged by the [`LogDiagnosticsPlugin`], aligning the columns.
+fn format_table(rows: &[DiagnosticRow], percentiles: bool) -> Vec<String> {
+    if rows.is_empty() {
+        return Vec::new();
+    }
+
+    let mut headers = vec!["value", "avg"];
+    if percentiles {
+        headers.extend(["p50", "p95", "p99"]);
+    }
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            let mut values = vec![Some(row.value), row.average];
+            if percentiles {
+                values.extend(row.percentiles);
+            }
+            values
+                .into_iter()
+                .map(|value| value.map_or_else(|| "-".into(), |value| format!("{value:.3}")))
+                .collect()
+        })
+        .collect();
+
+    let path_width = rows
+        .iter()
+        .map(|row| row.path.len())
+        .chain(["diagnostic".len()])
+        .max()
+        .unwrap_or_default();
+    let widths: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            cells
+                .iter()
+                .map(|row| row[column].len())
+                .chain([header.len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    let has_units = rows.iter().any(|row| !row.unit.is_empty());
+    let deltas: Option<Vec<String>> = rows.iter().any(|row| row.delta.is_some()).then(|| {
+        rows.iter()
+            .map(|row| {
+                row.delta
+                    .map_or_else(|| "-".into(), |delta| format!("{:+.1}%", delta * 100.0))
+            })
+            .collect()
+    });
+    let delta_width = deltas
+        .iter()
+        .flatten()
+        .map(String::len)
+        .chain(["delta".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut header = format!("{:<path_width$}", "diagnostic");
+    for (header_cell, width) in headers.iter().zip(&widths) {
+        header.push_str(&format!("  {header_cell:>width$}"));
+    }
+    if deltas.is_some() {
+        header.push_str(&format!("  {:>delta_width$}", "delta"));
+    }
+    if has_units {
+        header.push_str("  unit");
+    }
+
+    let mut lines = vec![header];
+    for (index, (row, row_cells)) in rows.iter().zip(&cells).enumerate() {
+        let mut line = format!("{:<path_width$}", row.path);
+        for (column, (cell, width)) in row_cells.iter().zip(&widths).enumerate() {
+            // The padding is added before the escape codes, which take no room on screen.
+            let cell = format!("{cell:>width$}");
+            if column == 0 && row.exceeded {
+                line.push_str(&format!("  \x1b[31m{cell}\x1b[0m"));
+            } else {
+                line.push_str(&format!("  {cell}"));
+            }
+        }
+        if let Some(deltas) = &deltas {
+            line.push_str(&format!("  {:>delta_width$}", deltas[index]));
+        }
+        if !row.unit.is_empty() {
+            line.push_str(&format!("  {}", row.unit));
         }
+        lines.push(line);
     }
+    lines
+}
 
-    fn log_diagnostics_debug_system(
-        mut state: ResMut<LogDiagnosticsState>,
-        time: Res<Time<Real>>,
-        diagnostics: Res<DiagnosticsStore>,
-    ) {
-        if state.timer.tick(time.delta()).finished() {
-            Self::for_each_diagnostic(&state, &diagnostics, |diagnostic| {
-                debug!("{:#?}\n", diagnostic);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DiagnosticMeasurement, DiagnosticSmoothing};
+    use bevy_platform_support::time::Instant;
+
+    #[test]
+    fn aligned_table() {
+        let row = |path, unit, value, average, exceeded| DiagnosticRow {
+            path,
+            unit,
+            value,
+            average,
+            percentiles: [None; 3],
+            delta: None,
+            exceeded,
+        };
+        let rows = [
+            row("fps", "", 59.8712, Some(59.9), false),
+            row("frame_time", "ms", 116.7, Some(16.694), true),
+            row("entity_count", "", 12.0, None, false),
+        ];
+
+        assert_eq!(
+            format_table(&rows, false),
+            [
+                "diagnostic      value     avg  unit",
+                "fps            59.871  59.900",
+                "frame_time    \x1b[31m116.700\x1b[0m  16.694  ms",
+                "entity_count   12.000       -",
+            ]
+        );
+        assert_eq!(
+            format_table(&rows[..1], true),
+            [
+                "diagnostic   value     avg  p50  p95  p99",
+                "fps         59.871  59.900    -    -    -",
+            ]
+        );
+
+        let mut rows = rows;
+        rows[1].delta = Some(0.125);
+        assert_eq!(
+            format_table(&rows[1..], false),
+            [
+                "diagnostic      value     avg   delta  unit",
+                "frame_time    \x1b[31m116.700\x1b[0m  16.694  +12.5%  ms",
+                "entity_count   12.000       -       -",
+            ]
+        );
+    }
+
+    #[test]
+    fn rate_limits_and_changes() {
+        let mut limiter = LogLimiter {
+            rate_limits: vec![(DiagnosticPath::new("slow"), Duration::from_secs(5))],
+            change_epsilon: Some(0.5),
+            ..Default::default()
+        };
+        let diagnostic = |path| {
+            Diagnostic::new(DiagnosticPath::new(path)).with_smoothing(DiagnosticSmoothing::Latest)
+        };
+        let (mut fast, mut slow) = (diagnostic("fast"), diagnostic("slow/child"));
+        let measure = |diagnostic: &mut Diagnostic, value| {
+            diagnostic.add_measurement(DiagnosticMeasurement {
+                time: Instant::now(),
+                value,
             });
-        }
+        };
+        measure(&mut fast, 1.0);
+        measure(&mut slow, 1.0);
+        assert!(limiter.should_log(&fast));
+        assert!(limiter.should_log(&slow));
+
+        // Values that didn't change enough aren't logged again.
+        limiter.elapsed = Duration::from_secs(1);
+        measure(&mut fast, 1.4);
+        assert!(!limiter.should_log(&fast));
+        measure(&mut fast, 1.6);
+        assert!(limiter.should_log(&fast));
+
+        // The rate limit applies to the diagnostics below its path.
+        measure(&mut slow, 10.0);
+        assert!(!limiter.should_log(&slow));
+        limiter.elapsed = Duration::from_secs(5);
+        assert!(limiter.should_log(&slow));
     }
 }
@@ -0,0 +1,185 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    intern::Interned,
+    prelude::*,
+    schedule::{ScheduleLabel, Schedules},
+};
+use bevy_platform_support::{collections::HashMap, time::Instant};
+use core::time::Duration;
+use log::info;
+
+use crate::{
+    Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticSmoothing, DiagnosticsStore,
+};
+
+/// Adds diagnostics of how long the app took to start, in milliseconds, and logs the slowest
+/// steps once after the first frame.
+///
+/// - `startup/plugin/<plugin>` is how long the [`Plugin::build`] of each plugin took, including the
+///   plugins it added, as returned by [`App::plugin_build_times`].
+/// - `startup/<schedule>/<system>` is how long each system of the [`PreStartup`], [`Startup`] and
+///   [`PostStartup`] schedules took.
+///
+/// The build time of every plugin is recorded, even those added before this one.
+///
+/// # See also
+///
+/// [`SystemProfilingPlugin`](crate::SystemProfilingPlugin) to measure systems every frame.
+pub struct StartupTimeDiagnosticsPlugin {
+    /// The number of plugins and systems logged, starting from the slowest.
+    ///
+    /// Defaults to 10.
+    pub log_count: usize,
+}
+
+impl Default for StartupTimeDiagnosticsPlugin {
+    fn default() -> Self {
+        Self { log_count: 10 }
+    }
+}
+
+/// The plugin build times collected by the [`StartupTimeDiagnosticsPlugin`], removed once they
+/// are published.
+#[derive(Resource)]
+struct StartupTimes {
+    plugins: Vec<(String, Duration)>,
+    log_count: usize,
+}
+
+impl Plugin for StartupTimeDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        for label in Self::schedules() {
+            app.edit_schedule(label, |schedule| {
+                schedule.set_record_system_run_times(true);
+            });
+        }
+        app.init_resource::<DiagnosticsStore>().add_systems(
+            Last,
+            Self::diagnostic_system.run_if(resource_exists::<StartupTimes>),
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        // All the plugins are built at this point.
+        let plugins = app
+            .plugin_build_times()
+            .map(|(name, duration)| (name.to_string(), duration))
+            .collect();
+        app.insert_resource(StartupTimes {
+            plugins,
+            log_count: self.log_count,
+        });
+    }
+}
+
+impl StartupTimeDiagnosticsPlugin {
+    /// The prefix of the path of every startup time diagnostic.
+    pub const STARTUP: &'static str = "startup";
+
+    fn schedules() -> [Interned<dyn ScheduleLabel>; 3] {
+        [PreStartup.intern(), Startup.intern(), PostStartup.intern()]
+    }
+
+    fn diagnostic_system(world: &mut World) {
+        let Some(startup) = world.remove_resource::<StartupTimes>() else {
+            return;
+        };
+
+        let mut durations = HashMap::<String, Duration>::default();
+        for (name, duration) in startup.plugins {
+            let path = format!("{}/plugin/{}", Self::STARTUP, name.replace('/', "_"));
+            *durations.entry(path).or_default() += duration;
+        }
+        world.resource_scope(|_, mut schedules: Mut<Schedules>| {
+            for label in Self::schedules() {
+                let Some(schedule) = schedules.get_mut(label) else {
+                    continue;
+                };
+                for (_, system, run_time) in schedule.system_run_times() {
+                    let path = format!(
+                        "{}/{label:?}/{}",
+                        Self::STARTUP,
+                        system.name().replace('/', "_")
+                    );
+                    *durations.entry(path).or_default() += run_time;
+                }
+                // The startup schedules don't run again.
+                schedule.set_record_system_run_times(false);
+            }
+        });
+
+        let mut durations: Vec<_> = durations.into_iter().collect();
+        durations.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        let mut diagnostics = world.resource_mut::<DiagnosticsStore>();
+        if !diagnostics.is_paused() {
+            let time = Instant::now();
+            for (path, duration) in &durations {
+                let path = DiagnosticPath::new(path.clone());
+                let diagnostic = match diagnostics.get_mut(&path) {
+                    Some(diagnostic) => diagnostic,
+                    None => {
+                        diagnostics.add(
+                            Diagnostic::new(path.clone())
+                                .with_suffix("ms")
+                                .with_smoothing(DiagnosticSmoothing::Latest),
+                        );
+                        diagnostics.get_mut(&path).unwrap()
+                    }
+                };
+                if diagnostic.is_enabled {
+                    diagnostic.add_measurement(DiagnosticMeasurement {
+                        time,
+                        value: duration.as_secs_f64() * 1000.0,
+                    });
+                }
+            }
+        }
+
+        if startup.log_count > 0 && !durations.is_empty() {
+            info!("Slowest startup steps:");
+            for (path, duration) in durations.iter().take(startup.log_count) {
+                info!("{path:<70} {:>10.3}ms", duration.as_secs_f64() * 1000.0);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn my_startup_system() {}
+
+    #[test]
+    fn records_startup_times() {
+        let mut app = App::new();
+        app.add_plugins(StartupTimeDiagnosticsPlugin::default())
+            .add_systems(Startup, my_startup_system);
+        app.finish();
+        app.update();
+        app.update();
+
+        let diagnostics = app.world().resource::<DiagnosticsStore>();
+        let system = diagnostics
+            .get(&DiagnosticPath::new(format!(
+                "startup/Startup/{}",
+                core::any::type_name_of_val(&my_startup_system)
+            )))
+            .unwrap();
+        assert_eq!(system.suffix, "ms");
+        // The startup times are only published once.
+        assert_eq!(system.history_len(), 1);
+        assert!(diagnostics
+            .get(&DiagnosticPath::new(format!(
+                "startup/plugin/{}",
+                core::any::type_name::<StartupTimeDiagnosticsPlugin>()
+            )))
+            .is_some());
+    }
+}
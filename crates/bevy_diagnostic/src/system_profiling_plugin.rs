@@ -0,0 +1,133 @@
+use alloc::{format, string::String};
+use bevy_app::prelude::*;
+use bevy_ecs::{prelude::*, schedule::Schedules};
+use bevy_platform_support::{collections::HashMap, time::Instant};
+
+use crate::{
+    Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore, SystemRunTimesReader,
+};
+
+/// Adds a diagnostic for the run time of every system, in milliseconds, to an App.
+///
+/// Each diagnostic is named `system/<schedule>/<system>`, for example
+/// `system/Update/my_game::movement::move_player`. Systems with the same name in the same schedule
+/// share a diagnostic, which records their total run time.
+///
+/// Measuring is enabled on every schedule in [`Schedules`] using
+/// [`Schedule::set_record_system_run_times`], and the times are collected in [`Last`]. Schedules
+/// that are still running at that point, like [`Main`] and [`Last`] itself, aren't measured. Each
+/// run is recorded once, so schedules that didn't run during a frame don't add measurements.
+///
+/// While collection is [paused](DiagnosticsStore::set_paused), measuring is disabled again.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct SystemProfilingPlugin;
+
+impl Plugin for SystemProfilingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DiagnosticsStore>()
+            .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+impl SystemProfilingPlugin {
+    /// The prefix of the path of every system run time diagnostic.
+    pub const SYSTEM: &'static str = "system";
+
+    /// Records the run time of every system measured since the last call, and enables measuring
+    /// on the schedules that aren't measured yet.
+    pub fn diagnostic_system(world: &mut World, mut reader: Local<SystemRunTimesReader>) {
+        world.resource_scope(|world, mut schedules: Mut<Schedules>| {
+            let mut diagnostics = world.resource_mut::<DiagnosticsStore>();
+            if diagnostics.is_paused() {
+                for (_, schedule) in schedules.iter_mut() {
+                    schedule.set_record_system_run_times(false);
+                }
+                return;
+            }
+            let time = Instant::now();
+
+            let mut run_times = HashMap::<String, f64>::default();
+            reader.read(&mut schedules, |label, schedule| {
+                for (_, system, run_time) in schedule.system_run_times() {
+                    let path = format!(
+                        "{}/{label:?}/{}",
+                        Self::SYSTEM,
+                        system.name().replace('/', "_")
+                    );
+                    *run_times.entry(path).or_default() += run_time.as_secs_f64() * 1000.0;
+                }
+            });
+
+            for (path, value) in run_times {
+                let path = DiagnosticPath::new(path);
+                let diagnostic = match diagnostics.get_mut(&path) {
+                    Some(diagnostic) => diagnostic,
+                    None => {
+                        diagnostics.add(Diagnostic::new(path.clone()).with_suffix("ms"));
+                        diagnostics.get_mut(&path).unwrap()
+                    }
+                };
+                if diagnostic.is_enabled {
+                    diagnostic.add_measurement(DiagnosticMeasurement { time, value });
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use bevy_ecs::schedule::ScheduleLabel;
+
+    fn my_system() {}
+
+    #[test]
+    fn records_system_run_times() {
+        let mut app = App::new();
+        app.add_plugins(SystemProfilingPlugin)
+            .add_systems(Update, my_system);
+
+        // The first update only enables measuring.
+        app.update();
+        app.update();
+
+        let path = DiagnosticPath::new(format!(
+            "system/Update/{}",
+            core::any::type_name_of_val(&my_system)
+        ));
+        let diagnostics = app.world().resource::<DiagnosticsStore>();
+        let diagnostic = diagnostics.get(&path).unwrap();
+        assert_eq!(diagnostic.suffix, "ms");
+        assert_eq!(diagnostic.history_len(), 1);
+    }
+
+    #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Rare;
+
+    #[test]
+    fn records_each_run_once() {
+        let mut app = App::new();
+        app.add_plugins(SystemProfilingPlugin)
+            .add_systems(Rare, my_system);
+
+        // The first update only enables measuring.
+        app.update();
+        app.world_mut().run_schedule(Rare);
+        app.update();
+        // `Rare` didn't run again.
+        app.update();
+
+        let path = DiagnosticPath::new(format!(
+            "system/Rare/{}",
+            core::any::type_name_of_val(&my_system)
+        ));
+        let diagnostics = app.world().resource::<DiagnosticsStore>();
+        assert_eq!(diagnostics.get(&path).unwrap().history_len(), 1);
+    }
+}
@@ -0,0 +1,73 @@
+use bevy_ecs::schedule::{InternedScheduleLabel, Schedule, Schedules};
+use bevy_platform_support::collections::HashMap;
+
+/// Reads the system run times recorded by the schedules in [`Schedules`], reporting each run of a
+/// schedule once.
+///
+/// A schedule keeps the run times of its last run until it runs again, so schedules that didn't
+/// run since the last read, like `OnEnter` schedules or a `FixedUpdate` without ticks, are skipped
+/// by comparing [`Schedule::run_count`].
+#[derive(Default)]
+pub struct SystemRunTimesReader {
+    /// The run count of each schedule at the last read.
+    last_runs: HashMap<InternedScheduleLabel, u64>,
+}
+
+impl SystemRunTimesReader {
+    /// Calls `f` with each schedule that ran since the last read, and enables
+    /// [recording](Schedule::set_record_system_run_times) on the schedules that don't record their
+    /// system run times yet, which are read from their next run on.
+    pub fn read(
+        &mut self,
+        schedules: &mut Schedules,
+        mut f: impl FnMut(InternedScheduleLabel, &Schedule),
+    ) {
+        for (_, schedule) in schedules.iter_mut() {
+            let label = schedule.label();
+            let run_count = schedule.run_count();
+            let last_run = self.last_runs.insert(label, run_count);
+            if !schedule.records_system_run_times() {
+                schedule.set_record_system_run_times(true);
+                continue;
+            }
+            // Schedules seen for the first time may hold times from before recording was enabled
+            // for this reader, and are read from their next run on too.
+            if last_run.is_some_and(|last_run| last_run != run_count) {
+                f(label, schedule);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{schedule::ScheduleLabel, world::World};
+
+    #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct TestSchedule;
+
+    #[test]
+    fn reads_each_run_once() {
+        let mut world = World::new();
+        let mut schedules = Schedules::new();
+        schedules.add_systems(TestSchedule, || {});
+        let mut reader = SystemRunTimesReader::default();
+        let mut read = |schedules: &mut Schedules| {
+            let mut count = 0;
+            reader.read(schedules, |_, schedule| {
+                count += schedule.system_run_times().count();
+            });
+            count
+        };
+
+        // The first read only enables recording.
+        assert_eq!(read(&mut schedules), 0);
+        schedules.get_mut(TestSchedule).unwrap().run(&mut world);
+        assert_eq!(read(&mut schedules), 1);
+        // The schedule didn't run again.
+        assert_eq!(read(&mut schedules), 0);
+        schedules.get_mut(TestSchedule).unwrap().run(&mut world);
+        assert_eq!(read(&mut schedules), 1);
+    }
+}
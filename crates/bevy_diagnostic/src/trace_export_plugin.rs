@@ -0,0 +1,323 @@
+use super::{
+    diagnostics_file_logger_plugin::write_json_string, DiagnosticPath, DiagnosticsStore,
+    SystemRunTimesReader,
+};
+use alloc::{format, string::String, vec::Vec};
+use bevy_app::prelude::*;
+use bevy_ecs::{prelude::*, schedule::Schedules};
+use bevy_platform_support::{collections::HashMap, time::Instant};
+use core::{fmt::Write as _, time::Duration};
+use log::{error, info};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+/// An App Plugin that records diagnostic measurements and the run time of every system, and writes
+/// them to a JSON file in the [Trace Event Format], which is completed when the app exits.
+///
+/// The file can be opened with `chrome://tracing` or [Perfetto](https://ui.perfetto.dev), to find
+/// what caused frame spikes. Each diagnostic is a counter track. Systems are grouped by schedule,
+/// in as many tracks as systems ran in parallel.
+///
+/// Systems are measured with [`Schedule::set_record_system_run_times`], like the
+/// [`SystemProfilingPlugin`](crate::SystemProfilingPlugin). Nothing is recorded while collection is
+/// [paused](DiagnosticsStore::set_paused). The events of each frame are written to the file at the
+/// end of the frame, so that long sessions don't keep the trace in memory.
+///
+/// [Trace Event Format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+pub struct TraceExportPlugin {
+    /// The file the trace is written to. It is overwritten if it exists.
+    pub path: PathBuf,
+    /// Whether the run time of systems is recorded, and not only diagnostics.
+    ///
+    /// Defaults to `true`.
+    pub record_systems: bool,
+}
+
+impl TraceExportPlugin {
+    /// Creates a plugin writing the trace to the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        TraceExportPlugin {
+            path: path.into(),
+            record_systems: true,
+        }
+    }
+}
+
+/// State used by the [`TraceExportPlugin`]
+#[derive(Resource)]
+struct TraceExportState {
+    path: PathBuf,
+    /// The file the trace is written to, until the trace is completed.
+    file: Option<BufWriter<File>>,
+    record_systems: bool,
+    start: Instant,
+    /// The trace events recorded since they were last written to the file.
+    events: String,
+    /// Whether an event was recorded, so that the next one is separated by a comma.
+    has_events: bool,
+    /// The time of the last exported measurement of each diagnostic.
+    last_measurements: HashMap<DiagnosticPath, Instant>,
+    /// The thread id of each track of systems, by name.
+    tracks: HashMap<String, usize>,
+    reader: SystemRunTimesReader,
+}
+
+impl Plugin for TraceExportPlugin {
+    fn build(&self, app: &mut App) {
+        let mut file = match File::create(&self.path) {
+            Ok(file) => BufWriter::new(file),
+            Err(err) => {
+                error!("Failed to create the trace {}: {err}", self.path.display());
+                return;
+            }
+        };
+        if let Err(err) = file.write_all(b"{\"displayTimeUnit\":\"ms\",\"traceEvents\":[\n") {
+            error!("Failed to write the trace {}: {err}", self.path.display());
+            return;
+        }
+
+        app.init_resource::<DiagnosticsStore>()
+            .insert_resource(TraceExportState {
+                path: self.path.clone(),
+                file: Some(file),
+                record_systems: self.record_systems,
+                start: Instant::now(),
+                events: String::new(),
+                has_events: false,
+                last_measurements: HashMap::default(),
+                tracks: HashMap::default(),
+                reader: SystemRunTimesReader::default(),
+            })
+            .add_systems(Last, Self::record_system);
+    }
+}
+
+impl TraceExportPlugin {
+    fn record_system(world: &mut World) {
+        world.resource_scope(|world, mut state: Mut<TraceExportState>| {
+            let state = &mut *state;
+            if !world.resource::<DiagnosticsStore>().is_paused() {
+                if state.record_systems {
+                    world.resource_scope(|_, mut schedules: Mut<Schedules>| {
+                        record_system_spans(state, &mut schedules);
+                    });
+                }
+                record_measurements(state, world.resource::<DiagnosticsStore>());
+            }
+
+            if world
+                .get_resource::<Events<AppExit>>()
+                .is_some_and(|events| !events.is_empty())
+            {
+                state.finish();
+            } else {
+                state.write_events();
+            }
+        });
+    }
+}
+
+fn record_system_spans(state: &mut TraceExportState, schedules: &mut Schedules) {
+    let mut reader = core::mem::take(&mut state.reader);
+    reader.read(schedules, |label, schedule| {
+        let mut spans: Vec<_> = schedule.system_run_spans().collect();
+        spans.sort_by_key(|(_, _, start, _)| *start);
+        // Spread the systems that ran in parallel over several tracks, as the viewers expect the
+        // events of a track to be nested.
+        let mut track_ends = Vec::<Instant>::new();
+        let category = format!("{label:?}");
+        for (_, system, start, duration) in spans {
+            let end = start + duration;
+            let track = match track_ends.iter().position(|&track_end| track_end <= start) {
+                Some(track) => {
+                    track_ends[track] = end;
+                    track
+                }
+                None => {
+                    track_ends.push(end);
+                    track_ends.len() - 1
+                }
+            };
+            let tid = state.track(format!("{category} {track}"));
+            state.push_event(|text, start_micros| {
+                text.push_str("{\"ph\":\"X\",\"name\":");
+                write_json_string(text, &system.name());
+                text.push_str(",\"cat\":");
+                write_json_string(text, &category);
+                let _ = write!(
+                    text,
+                    ",\"ts\":{},\"dur\":{},\"pid\":1,\"tid\":{tid}}}",
+                    start_micros(start),
+                    micros(duration)
+                );
+            });
+        }
+    });
+    state.reader = reader;
+}
+
+fn record_measurements(state: &mut TraceExportState, diagnostics: &DiagnosticsStore) {
+    for diagnostic in diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.is_enabled)
+    {
+        let Some(measurement) = diagnostic.measurement() else {
+            continue;
+        };
+        if !measurement.value.is_finite()
+            || state
+                .last_measurements
+                .get(diagnostic.path())
+                .is_some_and(|&time| time >= measurement.time)
+        {
+            continue;
+        }
+        state
+            .last_measurements
+            .insert(diagnostic.path().clone(), measurement.time);
+
+        state.push_event(|text, start_micros| {
+            text.push_str("{\"ph\":\"C\",\"name\":");
+            write_json_string(text, diagnostic.path().as_str());
+            let _ = write!(
+                text,
+                ",\"ts\":{},\"pid\":1,\"args\":{{\"value\":{}}}}}",
+                start_micros(measurement.time),
+                measurement.value
+            );
+        });
+    }
+}
+
+/// Converts a duration to the microseconds used by the trace.
+fn micros(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1000.0
+}
+
+impl TraceExportState {
+    /// Records an event, written by `write` with a function converting instants to trace
+    /// timestamps.
+    fn push_event(&mut self, write: impl FnOnce(&mut String, &dyn Fn(Instant) -> f64)) {
+        if self.has_events {
+            self.events.push_str(",\n");
+        }
+        self.has_events = true;
+        let start = self.start;
+        write(&mut self.events, &|instant| {
+            micros(instant.saturating_duration_since(start))
+        });
+    }
+
+    /// Returns the thread id of the track with the given name, naming a new thread for it if
+    /// needed.
+    fn track(&mut self, name: String) -> usize {
+        if let Some(&tid) = self.tracks.get(&name) {
+            return tid;
+        }
+        let tid = self.tracks.len() + 1;
+        self.push_event(|text, _| {
+            let _ = write!(
+                text,
+                "{{\"ph\":\"M\",\"name\":\"thread_name\",\"pid\":1,\"tid\":{tid},\"args\":{{\"name\":"
+            );
+            write_json_string(text, &name);
+            text.push_str("}}");
+        });
+        self.tracks.insert(name, tid);
+        tid
+    }
+
+    /// Writes the events recorded since the last call to the file.
+    fn write_events(&mut self) {
+        if let Some(file) = &mut self.file {
+            if let Err(err) = file.write_all(self.events.as_bytes()) {
+                error!("Failed to write the trace {}: {err}", self.path.display());
+                self.file = None;
+            }
+        }
+        self.events.clear();
+    }
+
+    /// Writes the remaining events and completes the trace, once.
+    fn finish(&mut self) {
+        self.write_events();
+        let Some(mut file) = self.file.take() else {
+            return;
+        };
+        match file.write_all(b"\n]}\n").and_then(|()| file.flush()) {
+            Ok(()) => info!("Wrote the trace to {}", self.path.display()),
+            Err(err) => error!("Failed to write the trace {}: {err}", self.path.display()),
+        }
+    }
+}
+
+impl Drop for TraceExportState {
+    fn drop(&mut self) {
+        // Apps that don't exit with an `AppExit` event still complete the trace when the world is
+        // dropped.
+        self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Diagnostic, DiagnosticMeasurement};
+
+    #[test]
+    fn records_events() {
+        let start = Instant::now();
+        let mut state = TraceExportState {
+            path: PathBuf::new(),
+            file: None,
+            record_systems: true,
+            start,
+            events: String::new(),
+            has_events: false,
+            last_measurements: HashMap::default(),
+            tracks: HashMap::default(),
+            reader: SystemRunTimesReader::default(),
+        };
+        let mut diagnostics = DiagnosticsStore::default();
+        diagnostics.add(Diagnostic::new(DiagnosticPath::const_new("fps")));
+        diagnostics
+            .get_mut(&DiagnosticPath::const_new("fps"))
+            .unwrap()
+            .add_measurement(DiagnosticMeasurement {
+                time: start + Duration::from_millis(2),
+                value: 60.0,
+            });
+
+        record_measurements(&mut state, &diagnostics);
+        // The measurement was already recorded.
+        record_measurements(&mut state, &diagnostics);
+        assert_eq!(
+            state.events,
+            "{\"ph\":\"C\",\"name\":\"fps\",\"ts\":2000,\"pid\":1,\"args\":{\"value\":60}}"
+        );
+    }
+
+    #[test]
+    fn streams_events_to_the_file() {
+        let path = std::env::temp_dir().join(format!("trace_export_{}.json", std::process::id()));
+        let mut app = App::new();
+        app.add_plugins(TraceExportPlugin::new(&path))
+            .add_systems(Update, || {});
+
+        app.update();
+        app.update();
+        // The events were written to the file, and aren't kept in memory.
+        assert!(app.world().resource::<TraceExportState>().events.is_empty());
+
+        app.world_mut().send_event(AppExit::Success);
+        app.update();
+        let trace = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(trace.starts_with("{\"displayTimeUnit\":\"ms\",\"traceEvents\":[\n"));
+        assert!(trace.contains("\"ph\":\"X\""));
+        assert!(trace.ends_with("\n]}\n"));
+    }
+}
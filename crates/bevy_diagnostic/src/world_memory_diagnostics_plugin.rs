@@ -0,0 +1,105 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    system::{Local, Res},
+    world::World,
+};
+use bevy_time::{Real, Time};
+use core::time::Duration;
+
+use crate::{Diagnostic, DiagnosticPath, DiagnosticSmoothing, Diagnostics, RegisterDiagnostic};
+
+const BYTES_TO_KIB: f64 = 1.0 / 1024.0;
+
+/// Adds diagnostics of the memory used by the storages of the main [`World`], measured with
+/// [`World::memory_usage`], to an App.
+///
+/// Measuring iterates over all the archetypes and storages, so it is done once per
+/// [`interval`](Self::interval). The memory used by each component and archetype can be found
+/// with [`World::memory_usage`].
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct WorldMemoryDiagnosticsPlugin {
+    /// The time between two measurements.
+    ///
+    /// Defaults to one second.
+    pub interval: Duration,
+}
+
+impl Default for WorldMemoryDiagnosticsPlugin {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Plugin for WorldMemoryDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        let interval = self.interval;
+        for path in Self::PATHS {
+            app.register_diagnostic(
+                Diagnostic::new(path)
+                    .with_suffix("KiB")
+                    .with_smoothing(DiagnosticSmoothing::Latest),
+            );
+        }
+        app.add_systems(
+            Last,
+            move |diagnostics: Diagnostics,
+                  world: &World,
+                  time: Res<Time<Real>>,
+                  last_measurement: Local<Option<Duration>>| {
+                Self::diagnostic_system(diagnostics, world, time, last_measurement, interval);
+            },
+        );
+    }
+}
+
+impl WorldMemoryDiagnosticsPlugin {
+    /// The memory used by the archetypes, tables and sparse sets, in KiB.
+    pub const TOTAL: DiagnosticPath = DiagnosticPath::const_new("world_memory/total");
+    /// The memory used by components stored in tables, in KiB.
+    pub const TABLES: DiagnosticPath = DiagnosticPath::const_new("world_memory/tables");
+    /// The memory used by components stored in sparse sets, including their overhead, in KiB.
+    pub const SPARSE_SETS: DiagnosticPath = DiagnosticPath::const_new("world_memory/sparse_sets");
+    /// The size of the largest command queue applied so far, in KiB.
+    pub const COMMAND_QUEUE_PEAK: DiagnosticPath =
+        DiagnosticPath::const_new("world_memory/command_queue_peak");
+
+    const PATHS: [DiagnosticPath; 4] = [
+        Self::TOTAL,
+        Self::TABLES,
+        Self::SPARSE_SETS,
+        Self::COMMAND_QUEUE_PEAK,
+    ];
+
+    fn diagnostic_system(
+        mut diagnostics: Diagnostics,
+        world: &World,
+        time: Res<Time<Real>>,
+        mut last_measurement: Local<Option<Duration>>,
+        interval: Duration,
+    ) {
+        let now = time.elapsed();
+        if last_measurement.is_some_and(|last| now.saturating_sub(last) < interval)
+            || !Self::PATHS.iter().any(|path| diagnostics.is_enabled(path))
+        {
+            return;
+        }
+        *last_measurement = Some(now);
+
+        let usage = world.memory_usage();
+        let table_bytes: usize = usage.components.iter().map(|c| c.table_bytes).sum();
+        let sparse_set_bytes: usize = usage.components.iter().map(|c| c.sparse_set_bytes).sum();
+        diagnostics.add_measurement(&Self::TOTAL, || usage.total() as f64 * BYTES_TO_KIB);
+        diagnostics.add_measurement(&Self::TABLES, || table_bytes as f64 * BYTES_TO_KIB);
+        diagnostics.add_measurement(&Self::SPARSE_SETS, || {
+            (sparse_set_bytes + usage.sparse_set_overhead) as f64 * BYTES_TO_KIB
+        });
+        diagnostics.add_measurement(&Self::COMMAND_QUEUE_PEAK, || {
+            usage.command_queue_peak as f64 * BYTES_TO_KIB
+        });
+    }
+}
@@ -467,6 +467,11 @@ impl Archetype {
         &self.entities
     }
 
+    /// Returns the number of bytes allocated for the list of entities of this archetype.
+    pub(crate) fn entities_allocated_bytes(&self) -> usize {
+        self.entities.capacity() * size_of::<ArchetypeEntity>()
+    }
+
     /// Gets an iterator of all of the components stored in [`Table`]s.
     ///
     /// All of the IDs are unique.
@@ -645,6 +650,16 @@ impl Archetype {
             .map(|info| info.archetype_component_id)
     }
 
+    /// Returns the number of entities the archetype can store without reallocating its list of entities.
+    pub(crate) fn entity_capacity(&self) -> usize {
+        self.entities.capacity()
+    }
+
+    /// Frees the capacity of the list of entities of this archetype that isn't used.
+    pub(crate) fn shrink_entities(&mut self) {
+        self.entities.shrink_to_fit();
+    }
+
     /// Clears all entities from the archetype.
     pub(crate) fn clear_entities(&mut self) {
         self.entities.clear();
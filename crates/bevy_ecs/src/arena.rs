@@ -0,0 +1,134 @@
+//! A frame-scoped bump allocator for transient data.
+//!
+//! See [`FrameArena`] for more details.
+
+use bevy_utils::Parallel;
+use bumpalo::Bump;
+
+use crate::{resource::Resource, system::ResMut};
+
+pub use bumpalo::collections::{String as ArenaString, Vec as ArenaVec};
+
+/// A bump allocator [`Resource`] for data that only needs to live until the end of the current frame.
+///
+/// Allocating from an arena is a pointer bump, and all of the memory is released at once by
+/// [`FrameArena::reset`], keeping the underlying chunks around for the next frame.
+/// This makes it well suited for scratch collections built every frame in hot paths,
+/// such as sorting keys or extraction buffers, which would otherwise churn the global allocator.
+///
+/// Each thread allocates from its own arena, so [`FrameArena::scope`] can be used concurrently
+/// from multiple systems, as well as from within [`Query::par_iter`](crate::system::Query::par_iter).
+/// Allocations cannot escape the scope, and the arena must be [reset](FrameArena::reset) once per frame,
+/// usually by running [`reset_frame_arena`] at the end of the frame.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::arena::{ArenaVec, FrameArena};
+///
+/// #[derive(Component)]
+/// struct Depth(f32);
+///
+/// fn sort_by_depth(arena: Res<FrameArena>, query: Query<(Entity, &Depth)>) {
+///     arena.scope(|bump| {
+///         let mut sorted = ArenaVec::with_capacity_in(query.iter().len(), bump);
+///         sorted.extend(query.iter().map(|(entity, depth)| (entity, depth.0)));
+///         sorted.sort_by(|a, b| a.1.total_cmp(&b.1));
+///         // ...
+///     });
+/// }
+/// # bevy_ecs::system::assert_is_system(sort_by_depth);
+/// ```
+#[derive(Resource, Default)]
+pub struct FrameArena {
+    bumps: Parallel<Bump>,
+    last_frame_used_bytes: usize,
+}
+
+impl FrameArena {
+    /// Runs `f` with the arena for the current thread.
+    pub fn scope<R>(&self, f: impl FnOnce(&Bump) -> R) -> R {
+        self.bumps.scope(|bump| f(bump))
+    }
+
+    /// Returns the number of bytes in use in the arenas of all threads since the last reset.
+    ///
+    /// This is the space the live allocations take up, including alignment padding. Allocations
+    /// that were freed aren't counted: a [`Bump`] reclaims the memory of its latest allocation when
+    /// it's freed or shrunk, for example when an [`ArenaVec`] grows in place.
+    pub fn used_bytes(&mut self) -> usize {
+        self.bumps
+            .iter_mut()
+            .map(|bump| bump.iter_allocated_chunks().map(<[_]>::len).sum::<usize>())
+            .sum()
+    }
+
+    /// Returns the number of bytes reserved by the arenas of all threads, including unused capacity.
+    pub fn capacity_bytes(&mut self) -> usize {
+        self.bumps
+            .iter_mut()
+            .map(|bump| bump.allocated_bytes())
+            .sum()
+    }
+
+    /// Returns the number of bytes that were in use at the end of the last frame,
+    /// as measured by [`FrameArena::used_bytes`] in the last call to [`FrameArena::reset`].
+    pub fn last_frame_used_bytes(&self) -> usize {
+        self.last_frame_used_bytes
+    }
+
+    /// Releases all allocations made since the last reset, keeping the largest chunk of each thread's
+    /// arena for reuse.
+    pub fn reset(&mut self) {
+        self.last_frame_used_bytes = self.used_bytes();
+        for bump in self.bumps.iter_mut() {
+            bump.reset();
+        }
+    }
+}
+
+/// A system that [resets](FrameArena::reset) the [`FrameArena`].
+///
+/// This should run once per frame, after all systems that use the arena.
+pub fn reset_frame_arena(mut arena: ResMut<FrameArena>) {
+    arena.reset();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_tracks_last_frame_used_bytes() {
+        let mut arena = FrameArena::default();
+        arena.scope(|bump| {
+            // Dropping the last allocation of an arena frees it, so keep this one alive until
+            // the reset.
+            let values = bump.alloc_slice_fill_copy(1024, 0_u32);
+            assert_eq!(values.len(), 1024);
+        });
+        assert_eq!(arena.used_bytes(), 1024 * size_of::<u32>());
+
+        arena.reset();
+        assert_eq!(arena.last_frame_used_bytes(), 1024 * size_of::<u32>());
+
+        assert_eq!(arena.used_bytes(), 0);
+        assert!(arena.capacity_bytes() > 0);
+
+        arena.reset();
+        assert_eq!(arena.last_frame_used_bytes(), 0);
+    }
+
+    #[test]
+    fn freed_allocations_are_not_used() {
+        let mut arena = FrameArena::default();
+        arena.scope(|bump| {
+            let kept = bump.alloc_slice_fill_copy(16, 0_u64);
+            assert_eq!(kept.len(), 16);
+            let mut freed = ArenaVec::with_capacity_in(64, bump);
+            freed.extend(0_u64..64);
+        });
+        assert_eq!(arena.used_bytes(), 16 * size_of::<u64>());
+    }
+}
@@ -1,12 +1,18 @@
 pub use bevy_ecs_macros::MapEntities;
 
 use crate::{
-    entity::{hash_map::EntityHashMap, Entity},
+    entity::{
+        hash_map::EntityHashMap, hash_set::EntityHashSet, index_set::EntityIndexSet,
+        paged_set::PagedEntitySet, Entity,
+    },
     identifier::masks::{IdentifierMask, HIGH_MASK},
     world::World,
 };
 
-use alloc::{collections::VecDeque, vec::Vec};
+use alloc::{
+    collections::{BTreeSet, VecDeque},
+    vec::Vec,
+};
 use bevy_platform_support::collections::HashSet;
 use core::hash::BuildHasher;
 use smallvec::SmallVec;
@@ -72,6 +78,48 @@ impl<S: BuildHasher + Default> MapEntities for HashSet<Entity, S> {
         *self = self.drain().map(|e| entity_mapper.get_mapped(e)).collect();
     }
 }
+
+impl MapEntities for EntityHashSet {
+    fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
+        *self = self.drain().map(|e| entity_mapper.get_mapped(e)).collect();
+    }
+}
+
+impl MapEntities for EntityIndexSet {
+    fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
+        *self = self
+            .drain(..)
+            .map(|e| entity_mapper.get_mapped(e))
+            .collect();
+    }
+}
+
+impl MapEntities for PagedEntitySet {
+    fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
+        *self = core::mem::take(self)
+            .iter()
+            .map(|e| entity_mapper.get_mapped(e))
+            .collect();
+    }
+}
+
+impl MapEntities for BTreeSet<Entity> {
+    fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
+        *self = core::mem::take(self)
+            .into_iter()
+            .map(|e| entity_mapper.get_mapped(e))
+            .collect();
+    }
+}
+
+impl<const N: usize> MapEntities for [Entity; N] {
+    fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
+        for entity in self.iter_mut() {
+            *entity = entity_mapper.get_mapped(*entity);
+        }
+    }
+}
+
 impl MapEntities for Vec<Entity> {
     fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
         for entity in self.iter_mut() {
@@ -281,7 +329,10 @@ impl<'m> SceneEntityMapper<'m> {
 #[cfg(test)]
 mod tests {
     use crate::{
-        entity::{hash_map::EntityHashMap, Entity, EntityMapper, SceneEntityMapper},
+        entity::{
+            hash_map::EntityHashMap, hash_set::EntityHashSet, index_set::EntityIndexSet, Entity,
+            EntityMapper, MapEntities, SceneEntityMapper,
+        },
         world::World,
     };
 
@@ -346,4 +397,32 @@ mod tests {
         // The SceneEntityMapper should leave `Entities` in a flushed state.
         assert!(!world.entities.needs_flush());
     }
+
+    #[test]
+    fn map_entity_collections() {
+        let a = Entity::from_raw(1);
+        let b = Entity::from_raw(2);
+        let mapped_a = Entity::from_raw(3);
+        let mapped_b = Entity::from_raw(4);
+
+        let mut map = EntityHashMap::default();
+        map.insert(a, mapped_a);
+        map.insert(b, mapped_b);
+
+        let mut hash_set = EntityHashSet::from([a, b]);
+        hash_set.map_entities(&mut map);
+        assert_eq!(hash_set, EntityHashSet::from([mapped_a, mapped_b]));
+
+        let mut index_set = EntityIndexSet::from_iter([b, a]);
+        index_set.map_entities(&mut map);
+        assert!(index_set.iter().eq([mapped_b, mapped_a].iter()));
+
+        let mut array = [a, b];
+        array.map_entities(&mut map);
+        assert_eq!(array, [mapped_a, mapped_b]);
+
+        let mut option = Some(a);
+        option.map_entities(&mut map);
+        assert_eq!(option, Some(mapped_a));
+    }
 }
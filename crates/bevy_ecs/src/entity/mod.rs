@@ -39,6 +39,8 @@
 mod clone_entities;
 mod entity_set;
 mod map_entities;
+#[cfg(feature = "uuid")]
+mod runtime_uuid;
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::Reflect;
 #[cfg(all(feature = "bevy_reflect", feature = "serialize"))]
@@ -47,6 +49,8 @@ use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
 pub use clone_entities::*;
 pub use entity_set::*;
 pub use map_entities::*;
+#[cfg(feature = "uuid")]
+pub use runtime_uuid::*;
 
 mod hash;
 pub use hash::*;
@@ -57,6 +61,8 @@ pub mod hash_set;
 pub mod index_map;
 pub mod index_set;
 
+pub mod paged_set;
+
 pub mod unique_array;
 pub mod unique_slice;
 pub mod unique_vec;
@@ -305,12 +311,19 @@ impl Entity {
         Self::from_raw_and_generation(index, NonZero::<u32>::MIN)
     }
 
-    /// Convert to a form convenient for passing outside of rust.
+    /// Convert to a form convenient for passing outside of rust, such as sending entities over the
+    /// network.
     ///
-    /// Only useful for identifying entities within the same instance of an application. Do not use
-    /// for serialization between runs.
+    /// The [`index`](Self::index) is stored in the low 32 bits, and the
+    /// [`generation`](Self::generation) in the high 32 bits, whose most significant bit is always
+    /// zero. This layout is guaranteed, so the bits can be packed or inspected by other programs,
+    /// and [`Entity::from_bits`] accepts the bits of any entity. An entity is never zero, so
+    /// `Option<Entity>` has the same size as `u64`.
     ///
-    /// No particular structure is guaranteed for the returned bits.
+    /// Only useful for identifying entities within the same instance of an application, or
+    /// instances sharing their index space. Do not use for serialization between runs: indices and
+    /// generations depend on the order entities are spawned in. See `RuntimeUuid`, with the `uuid`
+    /// feature, for stable identifiers.
     #[inline(always)]
     pub const fn to_bits(self) -> u64 {
         IdentifierMask::pack_into_u64(self.index, self.generation.get())
@@ -338,7 +351,10 @@ impl Entity {
     ///
     /// Only useful when applied to results from `to_bits` in the same instance of an application.
     ///
-    /// This method is the fallible counterpart to [`Entity::from_bits`].
+    /// This method is the fallible counterpart to [`Entity::from_bits`], returning an error for
+    /// bits with a zero generation or with the most significant bit set, which no entity has. Use
+    /// it to validate bits received from untrusted sources, then [`Entities::contains`] to check
+    /// that the entity is still alive.
     #[inline(always)]
     pub const fn try_from_bits(bits: u64) -> Result<Self, IdentifierError> {
         if let Ok(id) = Identifier::try_from_bits(bits) {
@@ -809,6 +825,10 @@ impl Entities {
     }
 
     /// Returns true if the [`Entities`] contains [`entity`](Entity).
+    ///
+    /// Both the index and the generation must match, so this returns `false` for despawned
+    /// entities, even if their index was reused by a newer entity. This makes it safe to check
+    /// entities kept for a long time or received from other programs before using them.
     // This will return false for entities which have been freed, even if
     // not reallocated since the generation is incremented in `free`
     pub fn contains(&self, entity: Entity) -> bool {
@@ -1148,6 +1168,14 @@ mod tests {
         assert_eq!(Entity::from_bits(e.to_bits()), e);
     }
 
+    #[test]
+    fn entity_bits_layout() {
+        let e = Entity::from_raw_and_generation(7, NonZero::<u32>::new(3).unwrap());
+        assert_eq!(e.to_bits(), (3 << 32) | 7);
+        assert!(Entity::try_from_bits(7).is_err());
+        assert!(Entity::try_from_bits((1 << 63) | (3 << 32) | 7).is_err());
+    }
+
     #[test]
     fn reserve_entity_len() {
         let mut e = Entities::new();
@@ -0,0 +1,317 @@
+//! Contains the [`PagedEntitySet`] type, a compact set of entities for very large collections.
+//!
+//! This is designed for [`RelationshipTarget`](crate::relationship::RelationshipTarget)s with
+//! hundreds of thousands of sources, where removing from a `Vec<Entity>` is too slow and the
+//! memory overhead of an [`EntityHashSet`](super::hash_set::EntityHashSet) too high.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    fmt::{self, Debug, Formatter},
+    iter::FusedIterator,
+    num::NonZero,
+};
+
+use bevy_platform_support::collections::hash_map::{self, HashMap};
+
+use super::Entity;
+
+/// The number of bits of an entity index selecting its offset in a page.
+const PAGE_BITS: u32 = 12;
+
+/// The number of entity indices covered by a page.
+const PAGE_LEN: usize = 1 << PAGE_BITS;
+
+/// The number of words of the bitmap of a page.
+const PAGE_WORDS: usize = PAGE_LEN / 64;
+
+/// A set of entities stored as bitmaps of their indices, split in pages of 4096 consecutive
+/// indices.
+///
+/// Each page with entities takes 512 bytes, and each entity 4 more bytes for its generation, so
+/// that sets of entities whose indices are close to each other, like entities spawned together,
+/// take about half the memory of a `Vec<Entity>`. Sparse sets are better stored in an
+/// [`EntityHashSet`](super::hash_set::EntityHashSet). Checking whether an entity is in the set,
+/// inserting and removing it take a constant time, bounded by the size of a page.
+///
+/// A set holds at most one entity per index: inserting an entity replaces the entity with the
+/// same index and another generation, which is no longer alive.
+///
+/// Entities are iterated in arbitrary order across pages, and in index order within a page.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::entity::paged_set::PagedEntitySet;
+/// #[derive(Component)]
+/// #[relationship(relationship_target = ChunkBlocks)]
+/// struct InChunk(Entity);
+///
+/// #[derive(Component)]
+/// #[relationship_target(relationship = InChunk, linked_spawn)]
+/// struct ChunkBlocks(PagedEntitySet);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct PagedEntitySet {
+    pages: HashMap<u32, Page>,
+    len: usize,
+}
+
+/// The entities of a [`PagedEntitySet`] within a range of `PAGE_LEN` indices.
+#[derive(Clone, PartialEq, Eq)]
+struct Page {
+    bits: Box<[u64; PAGE_WORDS]>,
+    /// The generations of the entities, in the order of their indices.
+    generations: Vec<NonZero<u32>>,
+}
+
+impl Page {
+    fn new() -> Self {
+        Self {
+            bits: Box::new([0; PAGE_WORDS]),
+            generations: Vec::new(),
+        }
+    }
+
+    fn contains(&self, offset: usize) -> bool {
+        self.bits[offset / 64] & (1 << (offset % 64)) != 0
+    }
+
+    /// Returns the number of entities before `offset`, the position of its generation.
+    fn rank(&self, offset: usize) -> usize {
+        let (word, bit) = (offset / 64, offset % 64);
+        let before: u32 = self.bits[..word].iter().map(|word| word.count_ones()).sum();
+        (before + (self.bits[word] & ((1 << bit) - 1)).count_ones()) as usize
+    }
+}
+
+/// Splits an entity index into its page and its offset in the page.
+fn split(entity: Entity) -> (u32, usize) {
+    let index = entity.index();
+    (index >> PAGE_BITS, index as usize % PAGE_LEN)
+}
+
+impl PagedEntitySet {
+    /// Creates an empty `PagedEntitySet`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of entities in the set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the set contains no entities.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if the set contains `entity`.
+    pub fn contains(&self, entity: Entity) -> bool {
+        let (page, offset) = split(entity);
+        self.pages.get(&page).is_some_and(|page| {
+            page.contains(offset) && page.generations[page.rank(offset)] == entity.generation
+        })
+    }
+
+    /// Adds `entity` to the set.
+    ///
+    /// Returns whether the entity was added, `false` if it was already in the set.
+    pub fn insert(&mut self, entity: Entity) -> bool {
+        let (page, offset) = split(entity);
+        let page = self.pages.entry(page).or_insert_with(Page::new);
+        let rank = page.rank(offset);
+        if page.contains(offset) {
+            let generation = &mut page.generations[rank];
+            let added = *generation != entity.generation;
+            *generation = entity.generation;
+            return added;
+        }
+        page.bits[offset / 64] |= 1 << (offset % 64);
+        page.generations.insert(rank, entity.generation);
+        self.len += 1;
+        true
+    }
+
+    /// Removes `entity` from the set.
+    ///
+    /// Returns whether the entity was in the set.
+    pub fn remove(&mut self, entity: Entity) -> bool {
+        let (page_index, offset) = split(entity);
+        let Some(page) = self.pages.get_mut(&page_index) else {
+            return false;
+        };
+        let rank = page.rank(offset);
+        if !page.contains(offset) || page.generations[rank] != entity.generation {
+            return false;
+        }
+        page.bits[offset / 64] &= !(1 << (offset % 64));
+        page.generations.remove(rank);
+        if page.generations.is_empty() {
+            self.pages.remove(&page_index);
+        }
+        self.len -= 1;
+        true
+    }
+
+    /// Removes all the entities.
+    pub fn clear(&mut self) {
+        self.pages.clear();
+        self.len = 0;
+    }
+
+    /// Shrinks the capacity of the pages as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.pages.shrink_to_fit();
+        for page in self.pages.values_mut() {
+            page.generations.shrink_to_fit();
+        }
+    }
+
+    /// Returns an iterator over the entities of the set.
+    pub fn iter(&self) -> Iter<'_> {
+        let mut pages = self.pages.iter();
+        let page = pages.next().map(|(&index, page)| (index, page));
+        Iter {
+            pages,
+            page,
+            word: 0,
+            bits: page.map_or(0, |(_, page)| page.bits[0]),
+            rank: 0,
+            remaining: self.len,
+        }
+    }
+}
+
+impl Debug for PagedEntitySet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl Extend<Entity> for PagedEntitySet {
+    fn extend<T: IntoIterator<Item = Entity>>(&mut self, iter: T) {
+        for entity in iter {
+            self.insert(entity);
+        }
+    }
+}
+
+impl FromIterator<Entity> for PagedEntitySet {
+    fn from_iter<T: IntoIterator<Item = Entity>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a> IntoIterator for &'a PagedEntitySet {
+    type Item = Entity;
+
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entities of a [`PagedEntitySet`].
+///
+/// This struct is created by the [`iter`] method on [`PagedEntitySet`]. See its documentation for more.
+///
+/// [`iter`]: PagedEntitySet::iter
+#[derive(Clone)]
+pub struct Iter<'a> {
+    pages: hash_map::Iter<'a, u32, Page>,
+    page: Option<(u32, &'a Page)>,
+    word: usize,
+    /// The bits of the current word not iterated yet.
+    bits: u64,
+    rank: usize,
+    remaining: usize,
+}
+
+impl Iterator for Iter<'_> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (page_index, page) = self.page?;
+            if self.bits != 0 {
+                let offset = self.word * 64 + self.bits.trailing_zeros() as usize;
+                self.bits &= self.bits - 1;
+                let generation = page.generations[self.rank];
+                self.rank += 1;
+                self.remaining -= 1;
+                let index = (page_index << PAGE_BITS) | offset as u32;
+                return Some(Entity::from_raw_and_generation(index, generation));
+            }
+
+            self.word += 1;
+            if self.word < PAGE_WORDS {
+                self.bits = page.bits[self.word];
+                continue;
+            }
+            self.page = self.pages.next().map(|(&index, page)| (index, page));
+            self.word = 0;
+            self.bits = self.page.map_or(0, |(_, page)| page.bits[0]);
+            self.rank = 0;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_> {}
+
+impl FusedIterator for Iter<'_> {}
+
+impl Debug for Iter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter").field(&self.remaining).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_remove_iter() {
+        let entities: Vec<Entity> = [0, 1, 63, 64, 4095, 4096, 100_000]
+            .into_iter()
+            .map(Entity::from_raw)
+            .collect();
+        let mut set: PagedEntitySet = entities.iter().copied().collect();
+        assert_eq!(set.len(), entities.len());
+        assert!(!set.insert(entities[2]));
+        assert!(entities.iter().all(|&entity| set.contains(entity)));
+        assert!(!set.contains(Entity::from_raw(2)));
+
+        let mut iterated: Vec<Entity> = set.iter().collect();
+        iterated.sort();
+        assert_eq!(iterated, entities);
+
+        // Entities with another generation are different.
+        let reused = Entity::from_raw_and_generation(64, NonZero::new(2).unwrap());
+        assert!(!set.contains(reused));
+        assert!(!set.remove(reused));
+        assert!(set.insert(reused));
+        assert!(!set.contains(entities[3]));
+        assert!(set.contains(reused));
+        assert_eq!(set.len(), entities.len());
+
+        assert!(set.remove(entities[5]));
+        assert!(!set.remove(entities[5]));
+        assert!(set.remove(entities[6]));
+        assert_eq!(set.len(), entities.len() - 2);
+        assert_eq!(set.iter().len(), set.len());
+        // Empty pages are dropped.
+        assert_eq!(set.pages.len(), 1);
+
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(set.iter().next(), None);
+    }
+}
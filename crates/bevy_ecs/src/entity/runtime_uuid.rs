@@ -0,0 +1,250 @@
+use crate::{
+    component::{
+        Component, ComponentCloneBehavior, ComponentHook, HookContext, Immutable, StorageType,
+    },
+    entity::Entity,
+    resource::Resource,
+    world::{FromWorld, World},
+};
+use alloc::vec::Vec;
+use bevy_platform_support::collections::HashMap;
+use core::fmt;
+use log::warn;
+use uuid::Uuid;
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    crate::reflect::ReflectComponent,
+    bevy_reflect::{std_traits::ReflectDefault, Reflect},
+};
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// A random identifier of an entity that stays the same across runs, to persist references to
+/// entities, unlike [`Entity`] whose index and generation depend on the order entities were
+/// spawned in.
+///
+/// Only entities that need one should get a `RuntimeUuid`. The entity of a UUID can be found with
+/// the [`RuntimeUuids`] resource, which the engine keeps up to date once it was initialized.
+/// `RuntimeUuid` is immutable, and isn't copied when entities are
+/// [cloned](crate::entity::EntityCloner), so that no two entities share the same UUID.
+///
+/// ```
+/// # use bevy_ecs::{entity::{RuntimeUuid, RuntimeUuids}, prelude::*};
+/// let mut world = World::new();
+/// world.init_resource::<RuntimeUuids>();
+///
+/// let uuid = RuntimeUuid::new();
+/// let entity = world.spawn(uuid).id();
+/// assert_eq!(world.resource::<RuntimeUuids>().get(uuid), Some(entity));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "serialize", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Component, Default, Debug, Clone, Hash, PartialEq)
+)]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Deserialize, Serialize)
+)]
+pub struct RuntimeUuid(Uuid);
+
+impl RuntimeUuid {
+    /// Creates a new random (version 4) identifier.
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// Creates an identifier from an existing `uuid`, for example one loaded from a save.
+    pub const fn from_uuid(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+
+    /// Returns the UUID of the entity.
+    pub const fn uuid(self) -> Uuid {
+        self.0
+    }
+}
+
+impl Default for RuntimeUuid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Uuid> for RuntimeUuid {
+    fn from(uuid: Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+impl fmt::Display for RuntimeUuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Component for RuntimeUuid {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+    type Mutability = Immutable;
+
+    fn on_insert() -> Option<ComponentHook> {
+        Some(|mut world, HookContext { entity, .. }| {
+            let uuid = *world.get::<RuntimeUuid>(entity).unwrap();
+            if let Some(mut uuids) = world.get_resource_mut::<RuntimeUuids>() {
+                uuids.add(uuid, entity);
+            }
+        })
+    }
+
+    fn on_replace() -> Option<ComponentHook> {
+        Some(|mut world, HookContext { entity, .. }| {
+            let uuid = *world.get::<RuntimeUuid>(entity).unwrap();
+            if let Some(mut uuids) = world.get_resource_mut::<RuntimeUuids>() {
+                uuids.remove(uuid, entity);
+            }
+        })
+    }
+
+    fn clone_behavior() -> ComponentCloneBehavior {
+        ComponentCloneBehavior::Ignore
+    }
+}
+
+/// Finds entities from their [`RuntimeUuid`].
+///
+/// The entities that already have a [`RuntimeUuid`] are found when the resource is initialized,
+/// then it is updated each time a [`RuntimeUuid`] is inserted, replaced or removed.
+///
+/// If several entities have the same [`RuntimeUuid`], for example when a save is loaded while the
+/// entities it was saved from still exist, the UUID refers to the latest of them, then to the
+/// previous one again once the latest loses it.
+#[derive(Resource, Debug)]
+pub struct RuntimeUuids {
+    entities: HashMap<RuntimeUuid, Entity>,
+    /// The previous entities with a [`RuntimeUuid`] that was reused, from the oldest to the
+    /// latest.
+    shadowed: HashMap<RuntimeUuid, Vec<Entity>>,
+}
+
+impl RuntimeUuids {
+    /// Returns the entity with the given [`RuntimeUuid`], if it exists.
+    pub fn get(&self, uuid: impl Into<RuntimeUuid>) -> Option<Entity> {
+        self.entities.get(&uuid.into()).copied()
+    }
+
+    /// Returns the number of distinct [`RuntimeUuid`]s.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entity has a [`RuntimeUuid`].
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Returns an iterator over the [`RuntimeUuid`]s and the entities they refer to, in no
+    /// particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (RuntimeUuid, Entity)> + '_ {
+        self.entities.iter().map(|(&uuid, &entity)| (uuid, entity))
+    }
+
+    fn add(&mut self, uuid: RuntimeUuid, entity: Entity) {
+        let Some(previous) = self.entities.insert(uuid, entity) else {
+            return;
+        };
+        if previous != entity {
+            warn!("{entity} reused the RuntimeUuid {uuid} of {previous}");
+            self.shadowed.entry(uuid).or_default().push(previous);
+        }
+    }
+
+    fn remove(&mut self, uuid: RuntimeUuid, entity: Entity) {
+        let Some(shadowed) = self.shadowed.get_mut(&uuid) else {
+            if self.entities.get(&uuid) == Some(&entity) {
+                self.entities.remove(&uuid);
+            }
+            return;
+        };
+        if self.entities.get(&uuid) == Some(&entity) {
+            // Refer to the previous entity with the UUID again.
+            if let Some(previous) = shadowed.pop() {
+                self.entities.insert(uuid, previous);
+            }
+        } else if let Some(index) = shadowed.iter().rposition(|&shadowed| shadowed == entity) {
+            shadowed.remove(index);
+        }
+        if shadowed.is_empty() {
+            self.shadowed.remove(&uuid);
+        }
+    }
+}
+
+impl FromWorld for RuntimeUuids {
+    fn from_world(world: &mut World) -> Self {
+        let mut uuids = Self {
+            entities: HashMap::default(),
+            shadowed: HashMap::default(),
+        };
+        let mut query = world.query::<(Entity, &RuntimeUuid)>();
+        for (entity, &uuid) in query.iter(world) {
+            uuids.add(uuid, entity);
+        }
+        uuids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_uuids() {
+        let mut world = World::new();
+        let a = RuntimeUuid::new();
+        let entity_a = world.spawn(a).id();
+        world.init_resource::<RuntimeUuids>();
+        assert_eq!(world.resource::<RuntimeUuids>().get(a), Some(entity_a));
+
+        let b = RuntimeUuid::new();
+        let entity_b = world.spawn(b).id();
+        assert_eq!(world.resource::<RuntimeUuids>().get(b), Some(entity_b));
+        assert_eq!(world.resource::<RuntimeUuids>().len(), 2);
+
+        let c = RuntimeUuid::new();
+        world.entity_mut(entity_b).insert(c);
+        assert_eq!(world.resource::<RuntimeUuids>().get(b), None);
+        assert_eq!(world.resource::<RuntimeUuids>().get(c), Some(entity_b));
+
+        world.despawn(entity_a);
+        assert_eq!(world.resource::<RuntimeUuids>().get(a), None);
+
+        let clone = world.entity_mut(entity_b).clone_and_spawn();
+        assert!(!world.entity(clone).contains::<RuntimeUuid>());
+        assert_eq!(world.resource::<RuntimeUuids>().len(), 1);
+    }
+
+    #[test]
+    fn reused_runtime_uuids() {
+        let mut world = World::new();
+        world.init_resource::<RuntimeUuids>();
+        let uuid = RuntimeUuid::new();
+        let old = world.spawn(uuid).id();
+        let new = world.spawn(uuid).id();
+        assert_eq!(world.resource::<RuntimeUuids>().get(uuid), Some(new));
+
+        // The old entity still has the UUID.
+        world.despawn(new);
+        assert_eq!(world.resource::<RuntimeUuids>().get(uuid), Some(old));
+
+        let new = world.spawn(uuid).id();
+        world.despawn(old);
+        assert_eq!(world.resource::<RuntimeUuids>().get(uuid), Some(new));
+        world.despawn(new);
+        assert_eq!(world.resource::<RuntimeUuids>().get(uuid), None);
+        assert!(world.resource::<RuntimeUuids>().shadowed.is_empty());
+    }
+}
@@ -36,6 +36,7 @@ where
                 err.into(),
                 ErrorContext::Command {
                     name: type_name::<C>().into(),
+                    queued_by: world.command_source.clone(),
                 },
             ),
         }
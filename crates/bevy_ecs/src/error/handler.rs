@@ -26,6 +26,8 @@ pub enum ErrorContext {
     Command {
         /// The name of the command that failed.
         name: Cow<'static, str>,
+        /// The name of the system that queued the command, if known.
+        queued_by: Option<Cow<'static, str>>,
     },
     /// The error occurred in an observer.
     Observer {
@@ -42,7 +44,11 @@ impl Display for ErrorContext {
             Self::System { name, .. } => {
                 write!(f, "System `{}` failed", name)
             }
-            Self::Command { name } => write!(f, "Command `{}` failed", name),
+            Self::Command {
+                name,
+                queued_by: Some(system),
+            } => write!(f, "Command `{}` queued by system `{}` failed", name, system),
+            Self::Command { name, .. } => write!(f, "Command `{}` failed", name),
             Self::Observer { name, .. } => {
                 write!(f, "Observer `{}` failed", name)
             }
@@ -64,6 +70,14 @@ impl ErrorContext {
         }
     }
 
+    /// The name of the system that queued the failed command, if known.
+    pub fn queued_by(&self) -> Option<&str> {
+        match self {
+            Self::Command { queued_by, .. } => queued_by.as_deref(),
+            _ => None,
+        }
+    }
+
     /// A string representation of the kind of ECS construct that failed.
     ///
     /// This is a simpler helper used for logging.
@@ -126,12 +140,25 @@ pub fn default_error_handler() -> fn(BevyError, ErrorContext) {
 
 macro_rules! inner {
     ($call:path, $e:ident, $c:ident) => {
-        $call!(
-            "Encountered an error in {} `{}`: {:?}",
-            $c.kind(),
-            $c.name(),
-            $e
-        );
+        match $c.queued_by() {
+            Some(system) => {
+                $call!(
+                    "Encountered an error in {} `{}` queued by system `{}`: {:?}",
+                    $c.kind(),
+                    $c.name(),
+                    system,
+                    $e
+                );
+            }
+            None => {
+                $call!(
+                    "Encountered an error in {} `{}`: {:?}",
+                    $c.kind(),
+                    $c.name(),
+                    $e
+                );
+            }
+        }
     };
 }
 
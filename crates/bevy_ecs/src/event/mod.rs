@@ -5,6 +5,8 @@ mod event_cursor;
 mod iterators;
 mod mut_iterators;
 mod mutator;
+#[cfg(feature = "std")]
+mod parallel_writer;
 mod reader;
 mod registry;
 mod update;
@@ -22,6 +24,8 @@ pub use iterators::{EventIterator, EventIteratorWithId};
 pub use mut_iterators::EventMutParIter;
 pub use mut_iterators::{EventMutIterator, EventMutIteratorWithId};
 pub use mutator::EventMutator;
+#[cfg(feature = "std")]
+pub use parallel_writer::ParallelEventWriter;
 pub use reader::EventReader;
 pub use registry::{EventRegistry, ShouldUpdateEvents};
 pub use update::{
@@ -618,4 +622,61 @@ mod tests {
         });
         schedule.run(&mut world);
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parallel_event_writer() {
+        use bevy_ecs::prelude::*;
+
+        #[derive(Component)]
+        struct Value(usize);
+
+        let mut world = World::new();
+        world.init_resource::<Events<TestEvent>>();
+        world.spawn_batch((0..100).map(Value));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            |query: Query<&Value>, writer: ParallelEventWriter<TestEvent>| {
+                query.par_iter().for_each(|value| {
+                    writer.write(TestEvent { i: value.0 });
+                });
+            },
+        );
+        schedule.run(&mut world);
+
+        let events = world.resource::<Events<TestEvent>>();
+        let mut received = events
+            .get_cursor()
+            .read(events)
+            .map(|event| event.i)
+            .collect::<Vec<_>>();
+        received.sort_unstable();
+        assert_eq!(received, (0..100).collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parallel_event_writers_dont_conflict() {
+        use bevy_ecs::prelude::*;
+
+        let mut world = World::new();
+        world.init_resource::<Events<TestEvent>>();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((
+            |writer: ParallelEventWriter<TestEvent>| writer.write(TestEvent { i: 0 }),
+            |writer: ParallelEventWriter<TestEvent>| {
+                writer.write_batch([1, 2].map(|i| TestEvent { i }));
+            },
+            |mut reader: EventReader<TestEvent>| reader.clear(),
+        ));
+        let _ = schedule.initialize(&mut world);
+        assert_eq!(schedule.graph().conflicting_systems().len(), 0);
+
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        let events = world.resource::<Events<TestEvent>>();
+        assert_eq!(events.len(), 6);
+    }
 }
@@ -0,0 +1,104 @@
+use alloc::vec::Vec;
+use bevy_ecs::{
+    event::Event,
+    system::{Deferred, SystemBuffer, SystemMeta, SystemParam},
+    world::World,
+};
+use bevy_utils::Parallel;
+
+/// Thread-local event buffers backing a [`ParallelEventWriter`].
+struct ParallelEventBuffer<E: Event> {
+    thread_buffers: Parallel<Vec<E>>,
+}
+
+impl<E: Event> Default for ParallelEventBuffer<E> {
+    fn default() -> Self {
+        Self {
+            thread_buffers: Parallel::default(),
+        }
+    }
+}
+
+impl<E: Event> SystemBuffer for ParallelEventBuffer<E> {
+    fn apply(&mut self, _system_meta: &SystemMeta, world: &mut World) {
+        // Each thread's segment is appended in turn, keeping its allocation for the next writes,
+        // so that systems writing many events every frame don't reallocate them.
+        let mut sent = true;
+        for buffer in self.thread_buffers.iter_mut() {
+            if !sent {
+                buffer.clear();
+            } else if !buffer.is_empty() {
+                // Logs an error once if the event wasn't added to the app.
+                sent = world.send_event_batch(buffer.drain(..)).is_some();
+            }
+        }
+    }
+}
+
+/// An alternative to [`EventWriter`](super::EventWriter) that can be used in parallel contexts,
+/// such as those in [`Query::par_iter`](crate::system::Query::par_iter).
+///
+/// Events are collected into thread-local buffers and merged into [`Events<E>`](super::Events)
+/// the next time deferred system buffers are applied (for example, at an
+/// [`ApplyDeferred`](crate::schedule::ApplyDeferred) sync point).
+/// As a result, written events are not visible to readers until then, and no [`EventId`](super::EventId)
+/// is returned when writing.
+///
+/// Since this param does not access [`Events<E>`](super::Events) directly, multiple systems using
+/// `ParallelEventWriter<E>` can run concurrently with each other and with [`EventReader<E>`](super::EventReader)s.
+///
+/// The events of each thread are sent in the order they were written, after the events of the
+/// threads merged before it, and receive their [`EventId`](super::EventId)s then. Readers see them
+/// like any other events. The relative order of events written from different threads is not
+/// guaranteed.
+///
+/// # Example
+/// ```
+/// # use bevy_ecs::{prelude::*, event::ParallelEventWriter};
+/// #
+/// # #[derive(Component)]
+/// # struct Health(f32);
+/// #[derive(Event)]
+/// struct Died(Entity);
+///
+/// fn parallel_event_system(query: Query<(Entity, &Health)>, died: ParallelEventWriter<Died>) {
+///     query.par_iter().for_each(|(entity, health)| {
+///         if health.0 <= 0.0 {
+///             died.write(Died(entity));
+///         }
+///     });
+/// }
+/// # bevy_ecs::system::assert_is_system(parallel_event_system);
+/// ```
+#[derive(SystemParam)]
+pub struct ParallelEventWriter<'s, E: Event> {
+    buffer: Deferred<'s, ParallelEventBuffer<E>>,
+}
+
+impl<'s, E: Event> ParallelEventWriter<'s, E> {
+    /// Queues an `event` in the buffer for the current thread.
+    ///
+    /// The event is sent to [`Events<E>`](super::Events) when this system's buffers are applied.
+    pub fn write(&self, event: E) {
+        self.buffer
+            .thread_buffers
+            .scope(|buffer| buffer.push(event));
+    }
+
+    /// Queues a list of `events` in the buffer for the current thread.
+    ///
+    /// The events are sent to [`Events<E>`](super::Events) when this system's buffers are applied.
+    pub fn write_batch(&self, events: impl IntoIterator<Item = E>) {
+        self.buffer
+            .thread_buffers
+            .scope(|buffer| buffer.extend(events));
+    }
+
+    /// Queues the default value of the event. Useful when the event is an empty struct.
+    pub fn write_default(&self)
+    where
+        E: Default,
+    {
+        self.write(E::default());
+    }
+}
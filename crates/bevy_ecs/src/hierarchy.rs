@@ -48,6 +48,10 @@ use log::warn;
 ///
 /// When a parent is despawned, all children (and their descendants) will _also_ be despawned.
 ///
+/// When several systems change the hierarchy with commands applied at the same sync point, the result doesn't
+/// depend on the order in which their commands are applied: parents always exist before their children are added
+/// to them, and despawns are applied last. See [`World::defer_despawns`](crate::world::World::defer_despawns).
+///
 /// You can create parent-child relationships in a variety of ways. The most direct way is to insert a [`ChildOf`] component:
 ///
 /// ```
@@ -478,7 +482,10 @@ mod tests {
         entity::Entity,
         hierarchy::{ChildOf, Children},
         relationship::{RelationshipHookMode, RelationshipTarget},
+        resource::Resource,
+        schedule::{IntoScheduleConfigs, Schedule},
         spawn::{Spawn, SpawnRelated},
+        system::{Commands, Res, ResMut, ScheduleSystem},
         world::World,
     };
     use alloc::{vec, vec::Vec};
@@ -607,6 +614,119 @@ mod tests {
         );
     }
 
+    #[derive(Resource)]
+    struct Family {
+        parent: Entity,
+        other_parent: Entity,
+        child: Entity,
+    }
+
+    fn despawn_parent(mut commands: Commands, family: Res<Family>) {
+        commands.entity(family.parent).despawn();
+    }
+
+    fn add_child(mut commands: Commands, family: Res<Family>) {
+        commands.entity(family.child).insert(ChildOf {
+            parent: family.parent,
+        });
+    }
+
+    fn move_child(mut commands: Commands, family: Res<Family>) {
+        commands.entity(family.child).insert(ChildOf {
+            parent: family.other_parent,
+        });
+    }
+
+    /// Runs `first` and `second` in this order, applying their commands at the same sync point.
+    fn run_at_same_sync_point<M1, M2>(
+        world: &mut World,
+        first: impl IntoScheduleConfigs<ScheduleSystem, M1>,
+        second: impl IntoScheduleConfigs<ScheduleSystem, M2>,
+    ) {
+        let mut schedule = Schedule::default();
+        schedule.add_systems((first, second).chain_ignore_deferred());
+        schedule.run(world);
+    }
+
+    fn family(world: &mut World) -> Family {
+        let family = Family {
+            parent: world.spawn_empty().id(),
+            other_parent: world.spawn_empty().id(),
+            child: world.spawn_empty().id(),
+        };
+        world.spawn(ChildOf {
+            parent: family.child,
+        });
+        family
+    }
+
+    #[test]
+    fn despawn_parent_and_add_child_in_any_order() {
+        for despawn_first in [true, false] {
+            let mut world = World::new();
+            let family = family(&mut world);
+            let (parent, child) = (family.parent, family.child);
+            world.insert_resource(family);
+
+            if despawn_first {
+                run_at_same_sync_point(&mut world, despawn_parent, add_child);
+            } else {
+                run_at_same_sync_point(&mut world, add_child, despawn_parent);
+            }
+
+            assert!(world.get_entity(parent).is_err());
+            assert!(world.get_entity(child).is_err());
+            assert_eq!(world.entities().len(), 1, "only `other_parent` is left");
+        }
+    }
+
+    #[test]
+    fn despawn_parent_and_move_child_in_any_order() {
+        for despawn_first in [true, false] {
+            let mut world = World::new();
+            let family = family(&mut world);
+            world.entity_mut(family.child).insert(ChildOf {
+                parent: family.parent,
+            });
+            let (parent, other_parent, child) = (family.parent, family.other_parent, family.child);
+            world.insert_resource(family);
+
+            if despawn_first {
+                run_at_same_sync_point(&mut world, despawn_parent, move_child);
+            } else {
+                run_at_same_sync_point(&mut world, move_child, despawn_parent);
+            }
+
+            assert!(world.get_entity(parent).is_err());
+            assert_eq!(world.get::<ChildOf>(child).unwrap().parent, other_parent);
+            assert_eq!(world.get::<Children>(other_parent).unwrap().len(), 1);
+        }
+    }
+
+    #[test]
+    fn add_child_to_parent_spawned_at_same_sync_point() {
+        #[derive(Resource)]
+        struct Parent(Entity);
+
+        let mut world = World::new();
+        let parent = world.spawn_empty().id();
+        world.insert_resource(Parent(parent));
+        world.despawn(parent);
+
+        run_at_same_sync_point(
+            &mut world,
+            |mut commands: Commands, mut parent: ResMut<Parent>| {
+                parent.0 = commands.spawn_empty().id();
+            },
+            |mut commands: Commands, parent: Res<Parent>| {
+                commands.spawn(ChildOf { parent: parent.0 });
+            },
+        );
+
+        let parent = world.resource::<Parent>().0;
+        assert_eq!(world.get::<Children>(parent).unwrap().len(), 1);
+    }
+
     #[test]
     fn missing_parent_invalid() {
         let mut world = World::new();
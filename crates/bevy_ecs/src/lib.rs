@@ -30,6 +30,8 @@ extern crate alloc;
 extern crate self as bevy_ecs;
 
 pub mod archetype;
+#[cfg(feature = "std")]
+pub mod arena;
 pub mod batching;
 pub mod bundle;
 pub mod change_detection;
@@ -42,6 +44,7 @@ pub mod hierarchy;
 pub mod identifier;
 pub mod intern;
 pub mod label;
+pub mod maintenance;
 pub mod name;
 pub mod observer;
 pub mod query;
@@ -0,0 +1,347 @@
+//! Cleanup work spread over many frames, within a time budget.
+//!
+//! See [`Maintenance`] for more details.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{marker::PhantomData, time::Duration};
+
+use bevy_platform_support::time::Instant;
+
+use crate::{
+    archetype::{ArchetypeEntity, ArchetypeId},
+    change_detection::{DetectChangesMut, Mut},
+    entity::Entity,
+    relationship::{RelationshipSourceCollection, RelationshipTarget},
+    resource::Resource,
+    world::World,
+};
+
+/// The number of archetypes visited by each step of [`ShrinkStorages`].
+const ARCHETYPES_PER_STEP: usize = 16;
+
+/// The number of entities visited by each step of [`ShrinkRelationships`].
+const ENTITIES_PER_STEP: usize = 64;
+
+/// Some cleanup work run a little at a time by [`Maintenance`].
+pub trait MaintenanceTask: Send + Sync + 'static {
+    /// Does a small, bounded amount of work on the `world`.
+    ///
+    /// Returns `true` if there is more work to do this frame, in which case the task is stepped
+    /// again as long as the frame's budget isn't spent.
+    fn step(&mut self, world: &mut World) -> bool;
+}
+
+/// A [`Resource`] running [`MaintenanceTask`]s a little every frame.
+///
+/// Cleanup work such as freeing memory that isn't used anymore causes large stalls when it's done
+/// all at once, and unbounded growth when it's never done. Instead, [`run_maintenance`] steps the
+/// tasks in turn until [`Maintenance::budget`] is spent. Every task is stepped at least once per
+/// frame, so that they keep making progress even if the budget is too small.
+///
+/// [`ShrinkStorages`] and [`ShrinkRelationships`] free the memory of the storages and relationships
+/// that shrunk, and other crates add their own tasks.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::maintenance::{run_maintenance, Maintenance, ShrinkStorages};
+///
+/// let mut world = World::new();
+/// let mut maintenance = Maintenance::default();
+/// maintenance.add_task(ShrinkStorages::default());
+/// world.insert_resource(maintenance);
+///
+/// // Usually run at the end of every frame.
+/// world.run_system_cached(run_maintenance).unwrap();
+/// ```
+#[derive(Resource)]
+pub struct Maintenance {
+    /// The time spent on maintenance per frame.
+    pub budget: Duration,
+    tasks: Vec<Box<dyn MaintenanceTask>>,
+    first: usize,
+    last_frame_duration: Duration,
+}
+
+impl Default for Maintenance {
+    fn default() -> Self {
+        Self::new(Duration::from_micros(500))
+    }
+}
+
+impl Maintenance {
+    /// Creates a new [`Maintenance`] without any task, spending at most `budget` per frame.
+    pub fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            tasks: Vec::new(),
+            first: 0,
+            last_frame_duration: Duration::ZERO,
+        }
+    }
+
+    /// Adds a task to run every frame.
+    pub fn add_task(&mut self, task: impl MaintenanceTask) -> &mut Self {
+        self.tasks.push(Box::new(task));
+        self
+    }
+
+    /// Returns the number of tasks.
+    pub fn task_count(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns the time spent on maintenance during the last call to [`Maintenance::run`].
+    ///
+    /// It may exceed the budget by the duration of the last step, or of the first step of every task.
+    pub fn last_frame_duration(&self) -> Duration {
+        self.last_frame_duration
+    }
+
+    /// Steps every task once, then keeps stepping the tasks with more work to do until the budget
+    /// is spent.
+    pub fn run(&mut self, world: &mut World) {
+        let start = Instant::now();
+        let len = self.tasks.len();
+        let mut pending: Vec<usize> = (0..len).map(|i| (self.first + i) % len).collect();
+        while !pending.is_empty() {
+            pending.retain(|&index| self.tasks[index].step(world));
+            if start.elapsed() >= self.budget {
+                break;
+            }
+        }
+        // Start from the next task next frame, so that the same tasks don't always get the
+        // remaining budget.
+        self.first = (self.first + 1) % len.max(1);
+        self.last_frame_duration = start.elapsed();
+    }
+}
+
+/// A system that [runs](Maintenance::run) the [`Maintenance`] tasks.
+pub fn run_maintenance(world: &mut World) {
+    world.resource_scope(|world, mut maintenance: Mut<Maintenance>| maintenance.run(world));
+}
+
+/// A [`MaintenanceTask`] going through all the archetypes to free the memory of the ones that were
+/// emptied or shrunk a lot, with [`World::shrink_archetype`].
+#[derive(Default)]
+pub struct ShrinkStorages {
+    next: usize,
+}
+
+impl MaintenanceTask for ShrinkStorages {
+    fn step(&mut self, world: &mut World) -> bool {
+        let len = world.archetypes().len();
+        let end = (self.next + ARCHETYPES_PER_STEP).min(len);
+        for index in self.next..end {
+            world.shrink_archetype(ArchetypeId::new(index));
+        }
+        if end < len {
+            self.next = end;
+            true
+        } else {
+            self.next = 0;
+            false
+        }
+    }
+}
+
+/// A [`MaintenanceTask`] going through all the entities with the [`RelationshipTarget`] `R` to free
+/// the capacity of their collections that isn't used anymore.
+///
+/// The collections aren't marked as changed.
+pub struct ShrinkRelationships<R: RelationshipTarget> {
+    archetype: usize,
+    row: usize,
+    batch: Vec<Entity>,
+    marker: PhantomData<fn() -> R>,
+}
+
+impl<R: RelationshipTarget> Default for ShrinkRelationships<R> {
+    fn default() -> Self {
+        Self {
+            archetype: 0,
+            row: 0,
+            batch: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R: RelationshipTarget> MaintenanceTask for ShrinkRelationships<R> {
+    fn step(&mut self, world: &mut World) -> bool {
+        let Some(component_id) = world.component_id::<R>() else {
+            return false;
+        };
+        let archetypes = world.archetypes();
+        while let Some(archetype) = archetypes.get(ArchetypeId::new(self.archetype)) {
+            if archetype.contains(component_id) && self.row < archetype.len() {
+                let end = (self.row + ENTITIES_PER_STEP).min(archetype.len());
+                self.batch.extend(
+                    archetype.entities()[self.row..end]
+                        .iter()
+                        .map(ArchetypeEntity::id),
+                );
+                self.row = end;
+                break;
+            }
+            self.archetype += 1;
+            self.row = 0;
+        }
+
+        if self.batch.is_empty() {
+            self.archetype = 0;
+            return false;
+        }
+        for entity in self.batch.drain(..) {
+            if let Some(mut target) = world.get_mut::<R>(entity) {
+                target
+                    .bypass_change_detection()
+                    .collection_mut_risky()
+                    .shrink_to_fit();
+            }
+        }
+        true
+    }
+}
+
+impl World {
+    /// Frees the memory allocated for the archetype with the given `id` and for its table that
+    /// isn't used by their entities anymore.
+    ///
+    /// Only the storages using less than a quarter of their capacity are shrunk, so that the ones
+    /// whose number of entities oscillates aren't reallocated over and over. Archetypes and tables
+    /// are never removed, since their ids are cached by queries, but the empty ones release all
+    /// of their memory.
+    ///
+    /// Returns `true` if any memory was freed.
+    pub fn shrink_archetype(&mut self, id: ArchetypeId) -> bool {
+        let Some(archetype) = self.archetypes.get(id) else {
+            return false;
+        };
+        let shrink_entities = archetype.len() <= archetype.entity_capacity() / 4;
+        let table = &mut self.storages.tables[archetype.table_id()];
+        let shrunk_table = table.entity_count() <= table.capacity() / 4 && table.shrink_to_fit();
+
+        if shrink_entities && archetype.entity_capacity() > archetype.len() {
+            self.archetypes[id].shrink_entities();
+            true
+        } else {
+            shrunk_table
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        change_detection::DetectChanges,
+        component::Component,
+        hierarchy::{ChildOf, Children},
+    };
+    use alloc::{sync::Arc, vec};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Component)]
+    struct A(#[expect(dead_code, reason = "Only the size of the component matters.")] [u8; 16]);
+
+    #[test]
+    fn shrink_archetype() {
+        let mut world = World::new();
+        let entities: Vec<Entity> = world.spawn_batch((0..100).map(|_| A([0; 16]))).collect();
+        let archetype = world.entity(entities[0]).archetype().id();
+        let table = world.entity(entities[0]).archetype().table_id();
+        assert!(!world.shrink_archetype(archetype));
+
+        for entity in &entities[1..] {
+            world.despawn(*entity);
+        }
+        assert!(world.shrink_archetype(archetype));
+        assert_eq!(world.storages().tables[table].capacity(), 1);
+        assert_eq!(world.archetypes()[archetype].entity_capacity(), 1);
+
+        world.despawn(entities[0]);
+        assert!(world.shrink_archetype(archetype));
+        assert_eq!(world.storages().tables[table].capacity(), 0);
+        assert!(!world.shrink_archetype(archetype));
+
+        // The emptied table can be used again.
+        let entity = world.spawn(A([1; 16])).id();
+        assert_eq!(world.entity(entity).archetype().table_id(), table);
+    }
+
+    #[test]
+    fn shrink_relationships() {
+        let mut world = World::new();
+        let parent = world.spawn_empty().id();
+        let children: Vec<Entity> = (0..100)
+            .map(|_| world.spawn(ChildOf { parent }).id())
+            .collect();
+        for child in &children[1..] {
+            world.despawn(*child);
+        }
+        world.clear_trackers();
+
+        let mut maintenance = Maintenance::new(Duration::MAX);
+        maintenance.add_task(ShrinkRelationships::<Children>::default());
+        maintenance.run(&mut world);
+
+        let parent = world.entity(parent);
+        assert_eq!(**parent.get::<Children>().unwrap(), vec![children[0]]);
+        assert!(!parent.get_ref::<Children>().unwrap().is_changed());
+    }
+
+    struct Countdown {
+        remaining: usize,
+        steps: Arc<AtomicUsize>,
+    }
+
+    impl Countdown {
+        fn new(remaining: usize) -> (Self, Arc<AtomicUsize>) {
+            let steps = Arc::new(AtomicUsize::new(0));
+            let task = Self {
+                remaining,
+                steps: steps.clone(),
+            };
+            (task, steps)
+        }
+    }
+
+    impl MaintenanceTask for Countdown {
+        fn step(&mut self, _world: &mut World) -> bool {
+            self.steps.fetch_add(1, Ordering::Relaxed);
+            self.remaining = self.remaining.saturating_sub(1);
+            self.remaining > 0
+        }
+    }
+
+    #[test]
+    fn every_task_steps_once_per_frame() {
+        let mut world = World::new();
+        let (a, a_steps) = Countdown::new(10);
+        let (b, b_steps) = Countdown::new(10);
+        let mut maintenance = Maintenance::new(Duration::ZERO);
+        maintenance.add_task(a).add_task(b);
+        world.insert_resource(maintenance);
+
+        world.run_system_cached(run_maintenance).unwrap();
+        world.run_system_cached(run_maintenance).unwrap();
+        assert_eq!(a_steps.load(Ordering::Relaxed), 2);
+        assert_eq!(b_steps.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn tasks_step_until_done_within_budget() {
+        let mut world = World::new();
+        let (a, a_steps) = Countdown::new(10);
+        let (b, b_steps) = Countdown::new(3);
+        let mut maintenance = Maintenance::new(Duration::MAX);
+        maintenance.add_task(a).add_task(b);
+
+        maintenance.run(&mut world);
+        assert_eq!(a_steps.load(Ordering::Relaxed), 10);
+        assert_eq!(b_steps.load(Ordering::Relaxed), 3);
+    }
+}
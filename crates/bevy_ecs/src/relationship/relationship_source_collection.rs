@@ -1,4 +1,4 @@
-use crate::entity::{hash_set::EntityHashSet, Entity};
+use crate::entity::{hash_set::EntityHashSet, paged_set::PagedEntitySet, Entity};
 use alloc::vec::Vec;
 use smallvec::SmallVec;
 
@@ -172,6 +172,48 @@ impl RelationshipSourceCollection for EntityHashSet {
     }
 }
 
+impl RelationshipSourceCollection for PagedEntitySet {
+    type SourceIter<'a> = crate::entity::paged_set::Iter<'a>;
+
+    fn new() -> Self {
+        PagedEntitySet::new()
+    }
+
+    fn reserve(&mut self, _: usize) {}
+
+    fn with_capacity(_capacity: usize) -> Self {
+        PagedEntitySet::new()
+    }
+
+    fn add(&mut self, entity: Entity) -> bool {
+        self.insert(entity)
+    }
+
+    fn remove(&mut self, entity: Entity) -> bool {
+        PagedEntitySet::remove(self, entity)
+    }
+
+    fn iter(&self) -> Self::SourceIter<'_> {
+        PagedEntitySet::iter(self)
+    }
+
+    fn len(&self) -> usize {
+        PagedEntitySet::len(self)
+    }
+
+    fn clear(&mut self) {
+        PagedEntitySet::clear(self);
+    }
+
+    fn shrink_to_fit(&mut self) {
+        PagedEntitySet::shrink_to_fit(self);
+    }
+
+    fn extend_from_iter(&mut self, entities: impl IntoIterator<Item = Entity>) {
+        self.extend(entities);
+    }
+}
+
 impl<const N: usize> RelationshipSourceCollection for SmallVec<[Entity; N]> {
     type SourceIter<'a> = core::iter::Copied<core::slice::Iter<'a, Entity>>;
 
@@ -325,6 +367,33 @@ mod tests {
         assert_eq!(collection, &SmallVec::from_buf([a]));
     }
 
+    #[test]
+    fn paged_relationship_source_collection() {
+        #[derive(Component)]
+        #[relationship(relationship_target = RelTarget)]
+        struct Rel(Entity);
+
+        #[derive(Component)]
+        #[relationship_target(relationship = Rel, linked_spawn)]
+        struct RelTarget(PagedEntitySet);
+
+        let mut world = World::new();
+        let target = world.spawn_empty().id();
+        let sources: Vec<Entity> = (0..5000).map(|_| world.spawn(Rel(target)).id()).collect();
+
+        let collection = world.get::<RelTarget>(target).unwrap().collection();
+        assert_eq!(collection.len(), sources.len());
+        assert!(sources.iter().all(|source| collection.contains(source)));
+
+        world.despawn(sources[10]);
+        let collection = world.get::<RelTarget>(target).unwrap().collection();
+        assert_eq!(collection.len(), sources.len() - 1);
+        assert!(!collection.contains(sources[10]));
+
+        world.despawn(target);
+        assert!(world.get_entity(sources[0]).is_err());
+    }
+
     #[test]
     fn entity_relationship_source_collection() {
         #[derive(Component)]
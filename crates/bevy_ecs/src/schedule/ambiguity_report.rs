@@ -0,0 +1,95 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+
+/// A pair of systems with conflicting data access and no ordering between them.
+///
+/// See [`AmbiguityReport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Deserialize, serde::Serialize))]
+pub struct SystemAmbiguity {
+    /// The name of the first system.
+    pub system_a: String,
+    /// The name of the second system.
+    pub system_b: String,
+    /// The names of the components and resources both systems conflict on.
+    ///
+    /// If this is empty, one or both of the systems are exclusive and the systems conflict on
+    /// the entire [`World`](crate::world::World).
+    pub conflicts: Vec<String>,
+}
+
+/// A structured report of the system order ambiguities detected in a [`Schedule`](super::Schedule).
+///
+/// Components and resources registered via [`Schedules::allow_ambiguous_component`](super::Schedules::allow_ambiguous_component)
+/// or [`Schedules::allow_ambiguous_resource`](super::Schedules::allow_ambiguous_resource), as well as systems
+/// configured with `ambiguous_with`, are excluded from the report.
+///
+/// The [`Display`] implementation prints the ambiguities grouped by system.
+/// With the `serialize` feature enabled, the report can also be exported to formats such as JSON.
+///
+/// Obtained from [`Schedule::ambiguity_report`](super::Schedule::ambiguity_report).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Deserialize, serde::Serialize))]
+pub struct AmbiguityReport {
+    /// The name of the schedule the ambiguities were detected in.
+    pub schedule: String,
+    /// The detected ambiguities.
+    pub ambiguities: Vec<SystemAmbiguity>,
+}
+
+impl AmbiguityReport {
+    /// Returns the number of ambiguous system pairs in the report.
+    pub fn len(&self) -> usize {
+        self.ambiguities.len()
+    }
+
+    /// Returns `true` if no ambiguities were detected.
+    pub fn is_empty(&self) -> bool {
+        self.ambiguities.is_empty()
+    }
+
+    /// Groups the ambiguities by the name of their first system.
+    ///
+    /// Systems are sorted by name, and each ambiguous pair is listed exactly once.
+    pub fn grouped_by_system(&self) -> BTreeMap<&str, Vec<&SystemAmbiguity>> {
+        let mut grouped = BTreeMap::<&str, Vec<&SystemAmbiguity>>::new();
+        for ambiguity in &self.ambiguities {
+            grouped
+                .entry(ambiguity.system_a.as_str())
+                .or_default()
+                .push(ambiguity);
+        }
+        grouped
+    }
+}
+
+impl Display for AmbiguityReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Schedule {}: {} pairs of systems with conflicting data access have indeterminate execution order.",
+            self.schedule,
+            self.len()
+        )?;
+        for (system, ambiguities) in self.grouped_by_system() {
+            writeln!(f, " -- {system}")?;
+            for ambiguity in ambiguities {
+                if ambiguity.conflicts.is_empty() {
+                    writeln!(
+                        f,
+                        "    and {}: conflict on: {}",
+                        ambiguity.system_b,
+                        core::any::type_name::<crate::world::World>()
+                    )?;
+                } else {
+                    writeln!(
+                        f,
+                        "    and {}: conflict on: {:?}",
+                        ambiguity.system_b, ambiguity.conflicts
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -4,7 +4,8 @@ mod simple;
 mod single_threaded;
 
 use alloc::{borrow::Cow, vec, vec::Vec};
-use core::any::TypeId;
+use bevy_platform_support::time::Instant;
+use core::{any::TypeId, time::Duration};
 
 pub use self::{simple::SimpleExecutor, single_threaded::SingleThreadedExecutor};
 
@@ -99,6 +100,13 @@ pub struct SystemSchedule {
     ///
     /// If a set doesn't run because of its conditions, this is used to skip all systems in it.
     pub(super) systems_in_sets_with_conditions: Vec<FixedBitSet>,
+    /// Indexed by system node id.
+    /// When each system started and how long it took the last time the schedule ran, or `None` if
+    /// it didn't run.
+    ///
+    /// This is empty unless [`Schedule::set_record_system_run_times`](super::Schedule::set_record_system_run_times)
+    /// is enabled, in which case the executor fills it in.
+    pub(super) system_run_times: Vec<Option<(Instant, Duration)>>,
 }
 
 impl SystemSchedule {
@@ -114,8 +122,14 @@ impl SystemSchedule {
             system_dependents: Vec::new(),
             sets_with_conditions_of_systems: Vec::new(),
             systems_in_sets_with_conditions: Vec::new(),
+            system_run_times: Vec::new(),
         }
     }
+
+    /// Returns `true` if the executor should record [`system_run_times`](Self::system_run_times).
+    pub(super) fn records_system_run_times(&self) -> bool {
+        !self.system_run_times.is_empty()
+    }
 }
 
 /// See [`ApplyDeferred`].
@@ -1,9 +1,9 @@
 use alloc::{boxed::Box, vec::Vec};
-use bevy_platform_support::sync::Arc;
+use bevy_platform_support::{sync::Arc, time::Instant};
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
 use bevy_utils::{default, syncunsafecell::SyncUnsafeCell};
 use concurrent_queue::ConcurrentQueue;
-use core::{any::Any, panic::AssertUnwindSafe};
+use core::{any::Any, panic::AssertUnwindSafe, time::Duration};
 use fixedbitset::FixedBitSet;
 #[cfg(feature = "std")]
 use std::eprintln;
@@ -30,6 +30,7 @@ struct Environment<'env, 'sys> {
     systems: &'sys [SyncUnsafeCell<ScheduleSystem>],
     conditions: SyncUnsafeCell<Conditions<'sys>>,
     world_cell: UnsafeWorldCell<'env>,
+    record_system_run_times: bool,
 }
 
 struct Conditions<'a> {
@@ -45,6 +46,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
         schedule: &'sys mut SystemSchedule,
         world: &'env mut World,
     ) -> Self {
+        let record_system_run_times = schedule.records_system_run_times();
         Environment {
             executor,
             systems: SyncUnsafeCell::from_mut(schedule.systems.as_mut_slice()).as_slice_of_cells(),
@@ -55,6 +57,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
                 systems_in_sets_with_conditions: &schedule.systems_in_sets_with_conditions,
             }),
             world_cell: world.as_unsafe_world_cell(),
+            record_system_run_times,
         }
     }
 }
@@ -75,6 +78,8 @@ struct SystemTaskMetadata {
 /// The result of running a system that is sent across a channel.
 struct SystemResult {
     system_index: usize,
+    /// When the system started and how long it took to run, if it was measured.
+    run_time: Option<(Instant, Duration)>,
 }
 
 /// Runs the schedule using a thread pool. Non-conflicting systems can run in parallel.
@@ -121,6 +126,8 @@ pub struct ExecutorState {
     completed_systems: FixedBitSet,
     /// Systems that have run but have not had their buffers applied.
     unapplied_systems: FixedBitSet,
+    /// How long each system took to run, if measured.
+    system_run_times: Vec<Option<(Instant, Duration)>>,
 }
 
 /// References to data required by the executor.
@@ -194,6 +201,10 @@ impl SystemExecutor for MultiThreadedExecutor {
             .num_dependencies_remaining
             .clone_from(&schedule.system_dependencies);
         state.ready_systems.clone_from(&self.starting_systems);
+        state.system_run_times.clear();
+        state
+            .system_run_times
+            .resize(schedule.system_run_times.len(), None);
 
         // If stepping is enabled, make sure we skip those systems that should
         // not be run.
@@ -261,6 +272,9 @@ impl SystemExecutor for MultiThreadedExecutor {
         state.evaluated_sets.clear();
         state.skipped_systems.clear();
         state.completed_systems.clear();
+        schedule
+            .system_run_times
+            .clone_from(&state.system_run_times);
     }
 
     fn set_apply_final_deferred(&mut self, value: bool) {
@@ -274,12 +288,16 @@ impl<'scope, 'env: 'scope, 'sys> Context<'scope, 'env, 'sys> {
         system_index: usize,
         res: Result<(), Box<dyn Any + Send>>,
         system: &ScheduleSystem,
+        run_time: Option<(Instant, Duration)>,
     ) {
         // tell the executor that the system finished
         self.environment
             .executor
             .system_completion
-            .push(SystemResult { system_index })
+            .push(SystemResult {
+                system_index,
+                run_time,
+            })
             .unwrap_or_else(|error| unreachable!("{}", error));
         if let Err(payload) = res {
             #[cfg(feature = "std")]
@@ -357,6 +375,7 @@ impl ExecutorState {
             skipped_systems: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
+            system_run_times: Vec::new(),
         }
     }
 
@@ -624,6 +643,10 @@ impl ExecutorState {
         let system_meta = &self.system_task_metadata[system_index];
 
         let task = async move {
+            let start = context
+                .environment
+                .record_system_run_times
+                .then(Instant::now);
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 // SAFETY:
                 // - The caller ensures that we have permission to
@@ -645,7 +668,12 @@ impl ExecutorState {
                     }
                 };
             }));
-            context.system_completed(system_index, res, system);
+            context.system_completed(
+                system_index,
+                res,
+                system,
+                start.map(|start| (start, start.elapsed())),
+            );
         };
 
         self.active_access
@@ -676,7 +704,7 @@ impl ExecutorState {
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
                 let res = apply_deferred(&unapplied_systems, context.environment.systems, world);
-                context.system_completed(system_index, res, system);
+                context.system_completed(system_index, res, system, None);
             };
 
             context.scope.spawn_on_scope(task);
@@ -685,6 +713,10 @@ impl ExecutorState {
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
+                let start = context
+                    .environment
+                    .record_system_run_times
+                    .then(Instant::now);
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     if let Err(err) = __rust_begin_short_backtrace::run(system, world) {
                         (context.error_handler)(
@@ -696,7 +728,12 @@ impl ExecutorState {
                         );
                     }
                 }));
-                context.system_completed(system_index, res, system);
+                context.system_completed(
+                    system_index,
+                    res,
+                    system,
+                    start.map(|start| (start, start.elapsed())),
+                );
             };
 
             context.scope.spawn_on_scope(task);
@@ -707,7 +744,14 @@ impl ExecutorState {
     }
 
     fn finish_system_and_handle_dependents(&mut self, result: SystemResult) {
-        let SystemResult { system_index, .. } = result;
+        let SystemResult {
+            system_index,
+            run_time,
+        } = result;
+
+        if run_time.is_some() {
+            self.system_run_times[system_index] = run_time;
+        }
 
         if self.system_task_metadata[system_index].is_exclusive {
             self.exclusive_running = false;
@@ -757,25 +801,27 @@ fn apply_deferred(
     systems: &[SyncUnsafeCell<ScheduleSystem>],
     world: &mut World,
 ) -> Result<(), Box<dyn Any + Send>> {
-    for system_index in unapplied_systems.ones() {
-        // SAFETY: none of these systems are running, no other references exist
-        let system = unsafe { &mut *systems[system_index].get() };
-        let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
-            system.apply_deferred(world);
-        }));
-        if let Err(payload) = res {
-            #[cfg(feature = "std")]
-            #[expect(clippy::print_stderr, reason = "Allowed behind `std` feature gate.")]
-            {
-                eprintln!(
-                    "Encountered a panic when applying buffers for system `{}`!",
-                    &*system.name()
-                );
+    world.defer_despawns(|world| {
+        for system_index in unapplied_systems.ones() {
+            // SAFETY: none of these systems are running, no other references exist
+            let system = unsafe { &mut *systems[system_index].get() };
+            let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                system.apply_deferred(world);
+            }));
+            if let Err(payload) = res {
+                #[cfg(feature = "std")]
+                #[expect(clippy::print_stderr, reason = "Allowed behind `std` feature gate.")]
+                {
+                    eprintln!(
+                        "Encountered a panic when applying buffers for system `{}`!",
+                        &*system.name()
+                    );
+                }
+                return Err(payload);
             }
-            return Err(payload);
         }
-    }
-    Ok(())
+        Ok(())
+    })
 }
 
 /// # Safety
@@ -1,3 +1,4 @@
+use bevy_platform_support::time::Instant;
 use core::panic::AssertUnwindSafe;
 use fixedbitset::FixedBitSet;
 
@@ -54,6 +55,7 @@ impl SystemExecutor for SimpleExecutor {
             self.completed_systems |= skipped_systems;
         }
 
+        let record_system_run_times = schedule.records_system_run_times();
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -119,6 +121,7 @@ impl SystemExecutor for SimpleExecutor {
                 continue;
             }
 
+            let start = record_system_run_times.then(Instant::now);
             let f = AssertUnwindSafe(|| {
                 if let Err(err) = __rust_begin_short_backtrace::run(system, world) {
                     error_handler(
@@ -144,6 +147,10 @@ impl SystemExecutor for SimpleExecutor {
             {
                 (f)();
             }
+
+            if let Some(start) = start {
+                schedule.system_run_times[system_index] = Some((start, start.elapsed()));
+            }
         }
 
         self.evaluated_sets.clear();
@@ -1,3 +1,4 @@
+use bevy_platform_support::time::Instant;
 use core::panic::AssertUnwindSafe;
 use fixedbitset::FixedBitSet;
 
@@ -60,6 +61,7 @@ impl SystemExecutor for SingleThreadedExecutor {
             self.completed_systems |= skipped_systems;
         }
 
+        let record_system_run_times = schedule.records_system_run_times();
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -127,6 +129,7 @@ impl SystemExecutor for SingleThreadedExecutor {
                 continue;
             }
 
+            let start = record_system_run_times.then(Instant::now);
             let f = AssertUnwindSafe(|| {
                 if system.is_exclusive() {
                     if let Err(err) = __rust_begin_short_backtrace::run(system, world) {
@@ -172,6 +175,9 @@ impl SystemExecutor for SingleThreadedExecutor {
                 (f)();
             }
 
+            if let Some(start) = start {
+                schedule.system_run_times[system_index] = Some((start, start.elapsed()));
+            }
             self.unapplied_systems.insert(system_index);
         }
 
@@ -201,10 +207,12 @@ impl SingleThreadedExecutor {
     }
 
     fn apply_deferred(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
-        for system_index in self.unapplied_systems.ones() {
-            let system = &mut schedule.systems[system_index];
-            system.apply_deferred(world);
-        }
+        world.defer_despawns(|world| {
+            for system_index in self.unapplied_systems.ones() {
+                let system = &mut schedule.systems[system_index];
+                system.apply_deferred(world);
+            }
+        });
 
         self.unapplied_systems.clear();
     }
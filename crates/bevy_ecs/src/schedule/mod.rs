@@ -1,5 +1,6 @@
 //! Contains APIs for ordering systems and executing them on a [`World`](crate::world::World)
 
+mod ambiguity_report;
 mod auto_insert_apply_deferred;
 mod condition;
 mod config;
@@ -8,9 +9,10 @@ mod pass;
 mod schedule;
 mod set;
 mod stepping;
+mod visualization;
 
 use self::graph::*;
-pub use self::{condition::*, config::*, executor::*, schedule::*, set::*};
+pub use self::{ambiguity_report::*, condition::*, config::*, executor::*, schedule::*, set::*};
 pub use pass::ScheduleBuildPass;
 
 pub use self::graph::NodeId;
@@ -846,6 +848,34 @@ mod tests {
             assert_eq!(schedule.graph().conflicting_systems().len(), 1);
         }
 
+        #[test]
+        fn ambiguity_report() {
+            let mut world = World::new();
+            world.insert_resource(R);
+            world.spawn(A);
+
+            let mut schedule = Schedule::default();
+            schedule.add_systems((
+                resmut_system,
+                res_system,
+                read_component_system,
+                write_component_system,
+            ));
+
+            let _ = schedule.initialize(&mut world);
+
+            let report = schedule.ambiguity_report(world.components());
+            assert_eq!(report.len(), 2);
+            assert_eq!(report.grouped_by_system().len(), 2);
+            for ambiguity in &report.ambiguities {
+                assert_eq!(ambiguity.conflicts.len(), 1);
+            }
+            assert!(report
+                .ambiguities
+                .iter()
+                .any(|ambiguity| ambiguity.conflicts[0].ends_with("::R")));
+        }
+
         #[test]
         fn nonsend() {
             let mut world = World::new();
@@ -1178,6 +1208,49 @@ mod tests {
         }
     }
 
+    mod visualization {
+        use super::*;
+
+        #[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+        struct Gameplay;
+
+        fn first() {}
+        fn second() {}
+
+        fn exported_schedule() -> Schedule {
+            let mut schedule = Schedule::default();
+            schedule.configure_sets(Gameplay.run_if(|| true));
+            schedule.add_systems((first, second).chain().in_set(Gameplay));
+            schedule
+        }
+
+        #[test]
+        fn export_dot() {
+            let mut schedule = exported_schedule();
+            let mut world = World::new();
+
+            for _ in 0..2 {
+                let dot = schedule.export_dot();
+                assert!(dot.contains("label=\"first\""));
+                assert!(dot.contains("label=\"second\""));
+                assert!(dot.contains("Gameplay\\nrun_if:"));
+                assert!(dot.contains("\"system_0\" -> \"system_1\";"));
+
+                // Exporting must also work once systems have been moved into the executable schedule.
+                schedule.initialize(&mut world).unwrap();
+            }
+        }
+
+        #[test]
+        fn export_mermaid() {
+            let schedule = exported_schedule();
+            let mermaid = schedule.export_mermaid();
+            assert!(mermaid.contains("system_0[\"first\"]"));
+            assert!(mermaid.contains("system_0 --> system_1"));
+            assert!(mermaid.contains(" -.-> system_0"));
+        }
+    }
+
     #[cfg(feature = "bevy_debug_stepping")]
     mod stepping {
         use super::*;
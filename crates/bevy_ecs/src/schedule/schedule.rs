@@ -3,6 +3,7 @@
     reason = "This instance of module inception is being discussed; see #17344."
 )]
 use alloc::{
+    borrow::Cow,
     boxed::Box,
     collections::{BTreeMap, BTreeSet},
     format,
@@ -10,11 +11,15 @@ use alloc::{
     vec,
     vec::Vec,
 };
-use bevy_platform_support::collections::{HashMap, HashSet};
+use bevy_platform_support::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
 use bevy_utils::{default, TypeIdMap};
 use core::{
     any::{Any, TypeId},
     fmt::{Debug, Write},
+    time::Duration,
 };
 use disqualified::ShortName;
 use fixedbitset::FixedBitSet;
@@ -296,6 +301,8 @@ pub struct Schedule {
     executable: SystemSchedule,
     executor: Box<dyn SystemExecutor>,
     executor_initialized: bool,
+    record_system_run_times: bool,
+    run_count: u64,
 }
 
 #[derive(ScheduleLabel, Hash, PartialEq, Eq, Debug, Clone)]
@@ -320,6 +327,8 @@ impl Schedule {
             executable: SystemSchedule::new(),
             executor: make_executor(ExecutorKind::default()),
             executor_initialized: false,
+            record_system_run_times: false,
+            run_count: 0,
         };
         // Call `set_build_settings` to add any default build passes
         this.set_build_settings(Default::default());
@@ -431,6 +440,67 @@ impl Schedule {
         self
     }
 
+    /// Set whether the schedule measures how long each of its systems takes to run.
+    ///
+    /// The times of the last run are returned by [`Schedule::system_run_times`]. This is disabled
+    /// by default, as it reads the clock twice for each system.
+    pub fn set_record_system_run_times(&mut self, record_system_run_times: bool) -> &mut Self {
+        self.record_system_run_times = record_system_run_times;
+        if !record_system_run_times {
+            self.executable.system_run_times.clear();
+        }
+        self
+    }
+
+    /// Returns whether the schedule measures how long each of its systems takes to run.
+    ///
+    /// See [`Schedule::set_record_system_run_times`].
+    pub fn records_system_run_times(&self) -> bool {
+        self.record_system_run_times
+    }
+
+    /// Returns how many times the schedule ran.
+    ///
+    /// The run times of a schedule are only replaced when it runs again, so readers compare
+    /// this count with the one of their last read to report each run once.
+    pub fn run_count(&self) -> u64 {
+        self.run_count
+    }
+
+    /// Returns how long each system that ran took, the last time the schedule ran.
+    ///
+    /// This is empty unless [`Schedule::set_record_system_run_times`] is enabled. Systems that
+    /// were skipped, for example by their run conditions, aren't included.
+    pub fn system_run_times(&self) -> impl Iterator<Item = (NodeId, &ScheduleSystem, Duration)> {
+        self.executable
+            .system_ids
+            .iter()
+            .zip(&self.executable.systems)
+            .zip(&self.executable.system_run_times)
+            .filter_map(|((node_id, system), run_time)| {
+                Some((*node_id, system, run_time.as_ref()?.1))
+            })
+    }
+
+    /// Returns when each system that ran started and how long it took, the last time the
+    /// schedule ran.
+    ///
+    /// This is empty unless [`Schedule::set_record_system_run_times`] is enabled. Systems that
+    /// were skipped, for example by their run conditions, aren't included.
+    pub fn system_run_spans(
+        &self,
+    ) -> impl Iterator<Item = (NodeId, &ScheduleSystem, Instant, Duration)> {
+        self.executable
+            .system_ids
+            .iter()
+            .zip(&self.executable.systems)
+            .zip(&self.executable.system_run_times)
+            .filter_map(|((node_id, system), run_time)| {
+                let (start, duration) = (*run_time)?;
+                Some((*node_id, system, start, duration))
+            })
+    }
+
     /// Runs all systems in this schedule on the `world`, using its current execution strategy.
     pub fn run(&mut self, world: &mut World) {
         #[cfg(feature = "trace")]
@@ -440,6 +510,14 @@ impl Schedule {
         self.initialize(world)
             .unwrap_or_else(|e| panic!("Error when initializing schedule {:?}: {e}", self.label));
 
+        self.run_count += 1;
+        self.executable.system_run_times.clear();
+        if self.record_system_run_times {
+            self.executable
+                .system_run_times
+                .resize(self.executable.systems.len(), None);
+        }
+
         let error_handler = default_error_handler();
 
         #[cfg(not(feature = "bevy_debug_stepping"))]
@@ -491,6 +569,34 @@ impl Schedule {
         Ok(())
     }
 
+    /// Returns a structured report of the system order ambiguities in this schedule.
+    ///
+    /// Ambiguities are detected when the schedule is built, regardless of
+    /// [`ScheduleBuildSettings::ambiguity_detection`], so the report is only populated after
+    /// [`Schedule::initialize`] or [`Schedule::run`] has been called.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Resource)]
+    /// struct Counter(u32);
+    ///
+    /// fn increment(mut counter: ResMut<Counter>) {}
+    /// fn reset(mut counter: ResMut<Counter>) {}
+    ///
+    /// let mut world = World::new();
+    /// world.insert_resource(Counter(0));
+    ///
+    /// let mut schedule = Schedule::default();
+    /// schedule.add_systems((increment, reset));
+    /// schedule.initialize(&mut world).unwrap();
+    ///
+    /// let report = schedule.ambiguity_report(world.components());
+    /// assert_eq!(report.len(), 1);
+    /// ```
+    pub fn ambiguity_report(&self, components: &Components) -> AmbiguityReport {
+        self.graph.ambiguity_report(self.label, components)
+    }
+
     /// Returns the [`ScheduleGraph`].
     pub fn graph(&self) -> &ScheduleGraph {
         &self.graph
@@ -631,16 +737,26 @@ impl SystemSetNode {
 /// A [`ScheduleSystem`] stored in a [`ScheduleGraph`].
 pub struct SystemNode {
     inner: Option<ScheduleSystem>,
+    name: Cow<'static, str>,
 }
 
 impl SystemNode {
     /// Create a new [`SystemNode`]
     pub fn new(system: ScheduleSystem) -> Self {
         Self {
+            name: system.name(),
             inner: Some(system),
         }
     }
 
+    /// Returns the name of the system represented by this node.
+    ///
+    /// Unlike [`SystemNode::get`], this is available even while the system has been moved into
+    /// the executable schedule.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Obtain a reference to the [`ScheduleSystem`] represented by this node.
     pub fn get(&self) -> Option<&ScheduleSystem> {
         self.inner.as_ref()
@@ -1493,6 +1609,7 @@ impl ScheduleGraph {
             system_dependents,
             sets_with_conditions_of_systems,
             systems_in_sets_with_conditions,
+            system_run_times: Vec::new(),
         }
     }
 
@@ -1593,7 +1710,7 @@ impl ScheduleGraph {
     fn get_node_name_inner(&self, id: &NodeId, report_sets: bool) -> String {
         let name = match id {
             NodeId::System(_) => {
-                let name = self.systems[id.index()].get().unwrap().name().to_string();
+                let name = self.systems[id.index()].name().to_string();
                 if report_sets {
                     let sets = self.names_of_sets_containing_node(id);
                     if sets.is_empty() {
@@ -1897,6 +2014,27 @@ impl ScheduleGraph {
         message
     }
 
+    /// Returns a structured report of the [conflicting systems](Self::conflicting_systems) in this graph.
+    pub fn ambiguity_report(
+        &self,
+        schedule_label: InternedScheduleLabel,
+        components: &Components,
+    ) -> AmbiguityReport {
+        let ambiguities = self
+            .conflicts_to_string(&self.conflicting_systems, components)
+            .map(|(system_a, system_b, conflicts)| SystemAmbiguity {
+                system_a,
+                system_b,
+                conflicts: conflicts.into_iter().map(ToString::to_string).collect(),
+            })
+            .collect();
+
+        AmbiguityReport {
+            schedule: format!("{schedule_label:?}"),
+            ambiguities,
+        }
+    }
+
     /// convert conflicts to human readable format
     pub fn conflicts_to_string<'a>(
         &'a self,
@@ -2110,6 +2248,36 @@ mod tests {
         assert_eq!(schedule.executable.systems.len(), 3);
     }
 
+    #[test]
+    fn records_system_run_times() {
+        use crate::schedule::ExecutorKind;
+
+        for executor in [
+            ExecutorKind::Simple,
+            ExecutorKind::SingleThreaded,
+            #[cfg(feature = "std")]
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut schedule = Schedule::default();
+            let mut world = World::default();
+            schedule.set_executor_kind(executor);
+            schedule.add_systems((|| {}, (|| {}).run_if(|| false)));
+
+            schedule.run(&mut world);
+            assert_eq!(schedule.system_run_times().count(), 0);
+
+            schedule.set_record_system_run_times(true);
+            schedule.run(&mut world);
+            assert_eq!(schedule.run_count(), 2);
+            // The system skipped by its run condition isn't measured.
+            assert_eq!(schedule.system_run_times().count(), 1);
+            assert_eq!(schedule.system_run_spans().count(), 1);
+
+            schedule.set_record_system_run_times(false);
+            assert_eq!(schedule.system_run_times().count(), 0);
+        }
+    }
+
     #[test]
     fn explicit_sync_point_used_as_auto_sync_point() {
         let mut schedule = Schedule::default();
@@ -0,0 +1,291 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use bevy_platform_support::collections::HashMap;
+use core::fmt::Write;
+use disqualified::ShortName;
+
+use crate::schedule::{graph::Direction, BoxedCondition, NodeId, Schedule};
+
+/// A node of a [`Schedule`] as it appears in an exported graph.
+struct ExportNode {
+    id: NodeId,
+    label: String,
+    conditions: Vec<String>,
+}
+
+/// A flattened view of a [`Schedule`], shared by the exporters.
+struct ExportGraph {
+    systems: Vec<ExportNode>,
+    sets: Vec<ExportNode>,
+    /// `(set, member)` pairs.
+    hierarchy: Vec<(NodeId, NodeId)>,
+    /// `(before, after)` pairs.
+    dependencies: Vec<(NodeId, NodeId)>,
+}
+
+impl Schedule {
+    /// Exports the systems, system sets, ordering edges and run conditions of this schedule as a
+    /// [Graphviz](https://graphviz.org/) `dot` graph.
+    ///
+    /// Systems are drawn as boxes and system sets as dashed ellipses. Dashed edges point from a set
+    /// to its members, and solid edges point from a system or set to the systems and sets that run after it.
+    /// Run conditions are listed in the label of the node they apply to.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// fn a() {}
+    /// fn b() {}
+    ///
+    /// let mut schedule = Schedule::default();
+    /// schedule.add_systems((a, b.run_if(|| true)).chain());
+    ///
+    /// let dot = schedule.export_dot();
+    /// assert!(dot.starts_with("digraph"));
+    /// ```
+    pub fn export_dot(&self) -> String {
+        let graph = self.export_graph();
+        let mut dot = String::new();
+
+        writeln!(
+            dot,
+            "digraph \"{}\" {{",
+            escape_dot(&format!("{:?}", self.label()))
+        )
+        .unwrap();
+        writeln!(dot, "    rankdir=LR;").unwrap();
+        for node in &graph.systems {
+            writeln!(
+                dot,
+                "    \"{}\" [shape=box, label=\"{}\"];",
+                node_key(node.id),
+                escape_dot(&node_label(node, "\\n"))
+            )
+            .unwrap();
+        }
+        for node in &graph.sets {
+            writeln!(
+                dot,
+                "    \"{}\" [shape=ellipse, style=dashed, label=\"{}\"];",
+                node_key(node.id),
+                escape_dot(&node_label(node, "\\n"))
+            )
+            .unwrap();
+        }
+        for (set, member) in &graph.hierarchy {
+            writeln!(
+                dot,
+                "    \"{}\" -> \"{}\" [style=dashed];",
+                node_key(*set),
+                node_key(*member)
+            )
+            .unwrap();
+        }
+        for (before, after) in &graph.dependencies {
+            writeln!(
+                dot,
+                "    \"{}\" -> \"{}\";",
+                node_key(*before),
+                node_key(*after)
+            )
+            .unwrap();
+        }
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    /// Exports the systems, system sets, ordering edges and run conditions of this schedule as a
+    /// [Mermaid](https://mermaid.js.org/) flowchart.
+    ///
+    /// Systems are drawn as rectangles and system sets as stadiums. Dotted edges point from a set
+    /// to its members, and solid edges point from a system or set to the systems and sets that run after it.
+    /// Run conditions are listed in the label of the node they apply to.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// fn a() {}
+    /// fn b() {}
+    ///
+    /// let mut schedule = Schedule::default();
+    /// schedule.add_systems((a, b).chain());
+    ///
+    /// let mermaid = schedule.export_mermaid();
+    /// assert!(mermaid.starts_with("flowchart"));
+    /// ```
+    pub fn export_mermaid(&self) -> String {
+        let graph = self.export_graph();
+        let mut mermaid = String::new();
+
+        writeln!(mermaid, "flowchart LR").unwrap();
+        for node in &graph.systems {
+            writeln!(
+                mermaid,
+                "    {}[\"{}\"]",
+                node_key(node.id),
+                escape_mermaid(&node_label(node, "<br>"))
+            )
+            .unwrap();
+        }
+        for node in &graph.sets {
+            writeln!(
+                mermaid,
+                "    {}([\"{}\"])",
+                node_key(node.id),
+                escape_mermaid(&node_label(node, "<br>"))
+            )
+            .unwrap();
+        }
+        for (set, member) in &graph.hierarchy {
+            writeln!(mermaid, "    {} -.-> {}", node_key(*set), node_key(*member)).unwrap();
+        }
+        for (before, after) in &graph.dependencies {
+            writeln!(
+                mermaid,
+                "    {} --> {}",
+                node_key(*before),
+                node_key(*after)
+            )
+            .unwrap();
+        }
+
+        mermaid
+    }
+
+    fn export_graph(&self) -> ExportGraph {
+        let graph = self.graph();
+        let executable = self.executable();
+
+        // Once the schedule is built, systems and conditions are moved into the executable schedule.
+        let executable_systems: HashMap<NodeId, usize> = executable
+            .system_ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (*id, index))
+            .collect();
+        let executable_sets: HashMap<NodeId, usize> = executable
+            .set_ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (*id, index))
+            .collect();
+
+        let systems = graph
+            .systems
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let id = NodeId::System(index);
+                let conditions = match executable_systems.get(&id) {
+                    Some(&index) => &executable.system_conditions[index],
+                    None => &graph.system_conditions[index],
+                };
+                ExportNode {
+                    id,
+                    label: ShortName(node.name()).to_string(),
+                    conditions: condition_names(conditions),
+                }
+            })
+            .collect();
+
+        let mut sets = Vec::new();
+        for (id, set, conditions) in graph.system_sets() {
+            // Each system has an implicit set for its type, which is resolved to the system itself below.
+            if set.system_type().is_some() {
+                continue;
+            }
+            let conditions = match executable_sets.get(&id) {
+                Some(&index) => executable.set_conditions[index].as_slice(),
+                None => conditions,
+            };
+            let label = if set.is_anonymous() {
+                "(anonymous set)".to_string()
+            } else {
+                ShortName(&format!("{set:?}")).to_string()
+            };
+            sets.push(ExportNode {
+                id,
+                label,
+                conditions: condition_names(conditions),
+            });
+        }
+        sets.sort_by_key(|node| node.id);
+
+        let resolve = |id: NodeId| -> Vec<NodeId> {
+            match graph.get_set_at(id) {
+                Some(set) if set.system_type().is_some() => graph
+                    .hierarchy()
+                    .graph()
+                    .neighbors_directed(id, Direction::Outgoing)
+                    .collect(),
+                _ => vec![id],
+            }
+        };
+
+        let mut hierarchy = Vec::new();
+        for (set, member) in graph.hierarchy().graph().all_edges() {
+            if graph
+                .get_set_at(set)
+                .is_some_and(|set| set.system_type().is_some())
+            {
+                continue;
+            }
+            hierarchy.push((set, member));
+        }
+
+        let mut dependencies = Vec::new();
+        for (before, after) in graph.dependency().graph().all_edges() {
+            for before in resolve(before) {
+                for after in resolve(after) {
+                    dependencies.push((before, after));
+                }
+            }
+        }
+        dependencies.sort();
+        dependencies.dedup();
+
+        ExportGraph {
+            systems,
+            sets,
+            hierarchy,
+            dependencies,
+        }
+    }
+}
+
+fn condition_names(conditions: &[BoxedCondition]) -> Vec<String> {
+    conditions
+        .iter()
+        .map(|condition| ShortName(&condition.name()).to_string())
+        .collect()
+}
+
+fn node_key(id: NodeId) -> String {
+    match id {
+        NodeId::System(index) => format!("system_{index}"),
+        NodeId::Set(index) => format!("set_{index}"),
+    }
+}
+
+fn node_label(node: &ExportNode, line_break: &str) -> String {
+    if node.conditions.is_empty() {
+        node.label.clone()
+    } else {
+        format!(
+            "{}{line_break}run_if: {}",
+            node.label,
+            node.conditions.join(", ")
+        )
+    }
+}
+
+fn escape_dot(label: &str) -> String {
+    label.replace('"', "\\\"")
+}
+
+fn escape_mermaid(label: &str) -> String {
+    label.replace('"', "#quot;")
+}
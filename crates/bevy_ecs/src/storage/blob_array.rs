@@ -246,9 +246,12 @@ impl BlobArray {
     /// Reallocate memory for this array.
     /// For example, if the length (number of stored elements) reached the capacity (number of elements the current allocation can store),
     /// you might want to use this method to increase the allocation, so more data can be stored in the array.
+    /// It can also shrink the allocation, to free the memory that isn't used by the stored elements.
     ///
     /// # Safety
     /// - `current_capacity` is indeed the current capacity of this array.
+    /// - If `new_capacity` is smaller than `current_capacity`, the elements at indices `new_capacity..current_capacity`
+    ///   must be uninitialized (dropped or moved out): they are discarded without being dropped.
     /// - After calling this method, the caller must update their saved capacity to reflect the change.
     pub(super) unsafe fn realloc(
         &mut self,
@@ -265,6 +268,8 @@ impl BlobArray {
             // - ptr was be allocated via this allocator
             // - the layout used to previously allocate this array is equivalent to `array_layout(&self.item_layout, current_capacity.get())`
             // - `item_layout.size() > 0` (`self.is_zst`==false) and `new_capacity > 0`, so the layout size is non-zero
+            // - when shrinking, the caller guarantees that the discarded elements were already dropped or moved out,
+            //   and `realloc` preserves the contents of the first `new_capacity` elements
             // - "new_size, when rounded up to the nearest multiple of layout.align(), must not overflow (i.e., the rounded value must be less than usize::MAX)",
             // since the item size is always a multiple of its align, the rounding cannot happen
             // here and the overflow is handled in `array_layout`
@@ -39,6 +39,11 @@ impl<I, V> SparseArray<I, V> {
             marker: PhantomData,
         }
     }
+
+    /// Returns the number of bytes allocated by the array.
+    pub(crate) fn allocated_bytes(&self) -> usize {
+        self.values.capacity() * size_of::<Option<V>>()
+    }
 }
 
 macro_rules! impl_sparse_array {
@@ -157,6 +162,19 @@ impl ComponentSparseSet {
         self.dense.len() == 0
     }
 
+    /// Returns the number of bytes allocated for the component values and their change ticks.
+    pub(crate) fn values_allocated_bytes(&self) -> usize {
+        self.dense.allocated_bytes()
+    }
+
+    /// Returns the number of bytes allocated to find the values of entities, on top of the values.
+    pub(crate) fn index_allocated_bytes(&self) -> usize {
+        fn vec_allocated_bytes<T>(vec: &Vec<T>) -> usize {
+            vec.capacity() * size_of::<T>()
+        }
+        vec_allocated_bytes(&self.entities) + self.sparse.allocated_bytes()
+    }
+
     /// Inserts the `entity` key and component `value` pair into this sparse
     /// set.
     ///
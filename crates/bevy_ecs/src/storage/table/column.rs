@@ -8,6 +8,12 @@ use alloc::vec::Vec;
 use bevy_ptr::PtrMut;
 use core::panic::Location;
 
+/// The number of bytes a column allocates for each component: the value, its change ticks, and where
+/// it was changed from if `track_location` is enabled.
+fn row_size(item_layout: Layout) -> usize {
+    item_layout.size() + 2 * size_of::<UnsafeCell<Tick>>() + size_of::<MaybeLocation>()
+}
+
 /// Very similar to a normal [`Column`], but with the capacities and lengths cut out for performance reasons.
 ///
 /// This type is used by [`Table`], because all of the capacities and lengths of the [`Table`]'s columns must match.
@@ -36,6 +42,11 @@ impl ThinColumn {
         }
     }
 
+    /// Returns the number of bytes allocated by the column when it has the given `capacity`.
+    pub(crate) fn allocated_bytes(&self, capacity: usize) -> usize {
+        capacity * row_size(self.data.layout())
+    }
+
     /// Swap-remove and drop the removed element, but the component at `row` must not be the last element.
     ///
     /// # Safety
@@ -108,6 +119,8 @@ impl ThinColumn {
     ///
     /// # Safety
     /// - `current_capacity` must be the current capacity of this column (the capacity of `self.data`, `self.added_ticks`, `self.changed_tick`)
+    /// - If `new_capacity` is smaller than `current_capacity`, the rows at indices `new_capacity..current_capacity` must
+    ///   be uninitialized: they are discarded without being dropped.
     /// -   The caller should make sure their saved `capacity` value is updated to `new_capacity` after this operation.
     pub(crate) unsafe fn realloc(
         &mut self,
@@ -133,6 +146,43 @@ impl ThinColumn {
             .map(|changed_by| changed_by.alloc(new_capacity));
     }
 
+    /// Shrinks the memory allocation for this [`ThinColumn`] to `new_capacity`, freeing it entirely
+    /// if `new_capacity` is 0.
+    ///
+    /// # Safety
+    /// - `current_capacity` must be the current capacity of this column, and be greater than 0
+    /// - `len` must be the length of the column, and `new_capacity` must be at least `len`
+    /// -   The caller should make sure their saved `capacity` value is updated to `new_capacity` after this operation.
+    pub(crate) unsafe fn shrink(
+        &mut self,
+        current_capacity: NonZeroUsize,
+        len: usize,
+        new_capacity: usize,
+    ) {
+        match NonZeroUsize::new(new_capacity) {
+            // SAFETY: `current_capacity` is the capacity of the column, and the rows past
+            // `new_capacity` are uninitialized since it's at least `len`
+            Some(new_capacity) => self.realloc(current_capacity, new_capacity),
+            None => {
+                // SAFETY: The components stored in the empty column match the ones stored in `self`
+                let data =
+                    unsafe { BlobArray::with_capacity(self.data.layout(), self.data.drop, 0) };
+                let mut old = core::mem::replace(
+                    self,
+                    Self {
+                        data,
+                        added_ticks: ThinArrayPtr::with_capacity(0),
+                        changed_ticks: ThinArrayPtr::with_capacity(0),
+                        changed_by: MaybeLocation::new_with(|| ThinArrayPtr::with_capacity(0)),
+                    },
+                );
+                // SAFETY: `current_capacity` and `len` are the capacity and length of `old`,
+                // which is never used again
+                old.drop(current_capacity.get(), len);
+            }
+        }
+    }
+
     /// Writes component data to the column at the given row.
     /// Assumes the slot is uninitialized, drop is not called.
     /// To overwrite existing initialized value, use [`Self::replace`] instead.
@@ -371,6 +421,11 @@ impl Column {
         self.data.layout()
     }
 
+    /// Returns the number of bytes allocated by the column.
+    pub(crate) fn allocated_bytes(&self) -> usize {
+        self.added_ticks.capacity() * row_size(self.item_layout())
+    }
+
     /// Writes component data to the column at given row.
     /// Assumes the slot is initialized, calls drop.
     ///
@@ -625,6 +625,48 @@ impl Table {
         }
     }
 
+    /// Frees the capacity of the [`Table`] that isn't used by its entities, releasing all of its memory
+    /// if it's empty.
+    ///
+    /// Returns `true` if any memory was freed.
+    pub(crate) fn shrink_to_fit(&mut self) -> bool {
+        let len = self.entity_count();
+        let Some(column_cap) = NonZeroUsize::new(self.capacity()) else {
+            return false;
+        };
+        if len == column_cap.get() {
+            return false;
+        }
+        if len == 0 {
+            self.entities = Vec::new();
+        } else {
+            self.entities.shrink_to_fit();
+        }
+        // `Vec::shrink_to_fit` may keep some extra capacity,
+        // use entities vector capacity as driving capacity for all related allocations
+        let new_capacity = self.entities.capacity();
+        if new_capacity == column_cap.get() {
+            return false;
+        }
+        for col in self.columns.values_mut() {
+            // SAFETY:
+            // - `column_cap` is indeed the columns' capacity, and is non-zero
+            // - `len` is the length of the columns, and `new_capacity` >= `len`
+            unsafe { col.shrink(column_cap, len, new_capacity) };
+        }
+        true
+    }
+
+    /// Iterates over the number of bytes allocated by the column of each component of the [`Table`].
+    pub(crate) fn columns_allocated_bytes(
+        &self,
+    ) -> impl Iterator<Item = (ComponentId, usize)> + '_ {
+        let capacity = self.entity_capacity();
+        self.columns
+            .iter()
+            .map(move |(id, column)| (*id, column.allocated_bytes(capacity)))
+    }
+
     /// Iterates over the [`ThinColumn`]s of the [`Table`].
     pub fn iter_columns(&self) -> impl Iterator<Item = &ThinColumn> {
         self.columns.values()
@@ -827,7 +869,10 @@ mod tests {
         ptr::OwningPtr,
         storage::{TableBuilder, TableId, TableRow, Tables},
     };
-    use alloc::vec::Vec;
+    use alloc::{
+        string::{String, ToString},
+        vec::Vec,
+    };
 
     #[derive(Component)]
     struct W<T>(T);
@@ -876,4 +921,46 @@ mod tests {
         assert_eq!(table.entity_capacity(), 256);
         assert_eq!(table.entity_count(), 200);
     }
+
+    #[test]
+    fn shrink_to_fit_keeps_live_rows() {
+        let mut components = Components::default();
+        let mut componentids = ComponentIds::default();
+        // SAFETY: They are both new.
+        let mut registrator =
+            unsafe { ComponentsRegistrator::new(&mut components, &mut componentids) };
+        let component_id = registrator.register_component::<W<String>>();
+        let mut table = TableBuilder::with_capacity(0, 1)
+            .add_column(components.get_info(component_id).unwrap())
+            .build();
+        for index in 0..200 {
+            // SAFETY: we allocate and immediately set data afterwards
+            unsafe {
+                let row = table.allocate(Entity::from_raw(index));
+                OwningPtr::make(W(index.to_string()), |value_ptr| {
+                    table.get_column_mut(component_id).unwrap().initialize(
+                        row,
+                        value_ptr,
+                        Tick::new(0),
+                        MaybeLocation::caller(),
+                    );
+                });
+            };
+        }
+        for row in (40..200).rev() {
+            // SAFETY: `row` is the last row of the table
+            unsafe { table.swap_remove_unchecked(TableRow::from_u32(row)) };
+        }
+
+        assert!(table.shrink_to_fit());
+        assert_eq!(table.entity_capacity(), 40);
+        assert!(!table.shrink_to_fit());
+        // SAFETY: `W<String>` is the type of the component
+        let values = unsafe { table.get_data_slice_for::<W<String>>(component_id) }.unwrap();
+        for (index, value) in values.iter().enumerate() {
+            assert_eq!(table.entities()[index], Entity::from_raw(index as u32));
+            // SAFETY: the table isn't mutated while the value is read
+            assert_eq!(unsafe { &*value.get() }.0, index.to_string());
+        }
+    }
 }
@@ -78,12 +78,15 @@ impl<T> ThinArrayPtr<T> {
     }
 
     /// Reallocate memory for the array, this should only be used if a previous allocation for this array has been made (capacity > 0).
+    /// The allocation can grow or shrink.
     ///
     /// # Panics
     /// - Panics if the new capacity overflows `usize`
     ///
     /// # Safety
     /// - The current capacity is indeed greater than 0
+    /// - If `new_capacity` is smaller than `current_capacity`, the elements at indices `new_capacity..current_capacity`
+    ///   must be uninitialized (dropped or moved out): they are discarded without being dropped
     /// - The caller should update their saved `capacity` value to reflect the fact that it was changed
     pub unsafe fn realloc(&mut self, current_capacity: NonZeroUsize, new_capacity: NonZeroUsize) {
         #[cfg(debug_assertions)]
@@ -96,6 +99,8 @@ impl<T> ThinArrayPtr<T> {
             // - ptr was be allocated via this allocator
             // - the layout of the array is the same as `Layout::array::<T>(current_capacity)`
             // - the size of `T` is non 0, and `new_capacity` > 0
+            // - when shrinking, the caller guarantees that the discarded elements were already dropped or moved out,
+            //   and `realloc` preserves the contents of the first `new_capacity` elements
             // - "new_size, when rounded up to the nearest multiple of layout.align(), must not overflow (i.e., the rounded value must be less than usize::MAX)",
             // since the item size is always a multiple of its align, the rounding cannot happen
             // here and the overflow is handled in `Layout::array`
@@ -206,11 +206,17 @@ pub fn retain<T: Bundle>() -> impl EntityCommand {
 /// This will also despawn any [`Children`](crate::hierarchy::Children) entities,
 /// and any other [`RelationshipTarget`](crate::relationship::RelationshipTarget) that is configured to despawn descendants.
 /// This results in "recursive despawn" behavior.
+///
+/// Within [`World::defer_despawns`], the entity is only despawned once all the other commands
+/// were applied.
 #[track_caller]
 pub fn despawn() -> impl EntityCommand {
     let caller = MaybeLocation::caller();
-    move |entity: EntityWorldMut| {
-        entity.despawn_with_caller(caller);
+    move |mut entity: EntityWorldMut| {
+        let id = entity.id();
+        if !entity.world_scope(|world| world.defer_despawn(id, caller)) {
+            entity.despawn_with_caller(caller);
+        }
     }
 }
 
@@ -1713,7 +1713,9 @@ impl<'a> EntityCommands<'a> {
     ///
     /// This will emit a warning if the entity does not exist.
     ///
-    /// See [`World::despawn`] for more details.
+    /// See [`World::despawn`] for more details. When the commands of several systems are applied
+    /// at the same sync point, despawns are applied after all the other commands, see
+    /// [`World::defer_despawns`].
     ///
     /// # Note
     ///
@@ -153,7 +153,10 @@ pub use system_name::*;
 pub use system_param::*;
 pub use system_registry::*;
 
-use crate::world::World;
+use crate::{
+    error::{BevyError, ErrorContext},
+    world::World,
+};
 
 /// Conversion trait to turn something into a [`System`].
 ///
@@ -228,6 +231,38 @@ pub trait IntoSystem<In: SystemInput, Out, Marker>: Sized {
         IntoAdapterSystem::new(f, self)
     }
 
+    /// Handles errors returned by this system with `error_handler`, instead of the
+    /// [default error handler](crate::error::default_error_handler).
+    ///
+    /// This allows expected failures of individual systems to be logged or ignored, while
+    /// unexpected errors elsewhere in the app still use the app-wide policy.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// use bevy_ecs::error::warn;
+    ///
+    /// # #[derive(Resource)] struct Level;
+    /// fn spawn_enemies(level: Option<Res<Level>>) -> Result {
+    ///     let level = level.ok_or("the level has not been loaded yet")?;
+    ///     // ...
+    ///     Ok(())
+    /// }
+    ///
+    /// # let mut schedule = Schedule::default();
+    /// schedule.add_systems(spawn_enemies.handle_error_with(warn));
+    /// # let mut world = World::new();
+    /// # schedule.run(&mut world);
+    /// ```
+    fn handle_error_with(
+        self,
+        error_handler: fn(BevyError, ErrorContext),
+    ) -> ErrorHandlerSystem<Self::System>
+    where
+        Self::System: System<In = (), Out = crate::error::Result>,
+    {
+        ErrorHandlerSystem::new(IntoSystem::into_system(self), error_handler)
+    }
+
     /// Get the [`TypeId`] of the [`System`] produced after calling [`into_system`](`IntoSystem::into_system`).
     #[inline]
     fn system_type_id(&self) -> TypeId {
@@ -1823,4 +1858,72 @@ mod tests {
         let mut world = World::new();
         run_system(&mut world, sys);
     }
+
+    #[test]
+    fn fallible_system_with_error_handler() {
+        use crate::error::{BevyError, ErrorContext};
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static HANDLED: AtomicUsize = AtomicUsize::new(0);
+
+        fn count(_: BevyError, ctx: ErrorContext) {
+            assert_eq!(ctx.kind(), "system");
+            HANDLED.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn sys() -> Result {
+            Err("error")?;
+            Ok(())
+        }
+
+        fn missing_resource(_: Res<B>) -> Result {
+            Ok(())
+        }
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((
+            sys.handle_error_with(count),
+            missing_resource.handle_error_with(count),
+        ));
+        schedule.run(&mut world);
+
+        assert_eq!(HANDLED.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn command_error_reports_system() {
+        use crate::error::{BevyError, ErrorContext};
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        static HANDLED: AtomicUsize = AtomicUsize::new(0);
+
+        fn check(_: BevyError, ctx: ErrorContext) {
+            assert_eq!(ctx.kind(), "command");
+            assert!(ctx.queued_by().unwrap().ends_with("insert_into_despawned"));
+            HANDLED.fetch_add(1, Ordering::Relaxed);
+        }
+
+        #[derive(Resource)]
+        struct Despawned(Entity);
+
+        fn insert_into_despawned(mut commands: Commands, despawned: Res<Despawned>) {
+            let entity = despawned.0;
+            commands.queue_handled(
+                move |world: &mut World| -> Result {
+                    world.get_entity_mut(entity)?;
+                    Ok(())
+                },
+                check,
+            );
+        }
+
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+        world.despawn(entity);
+        world.insert_resource(Despawned(entity));
+        run_system(&mut world, insert_into_despawned);
+
+        assert_eq!(HANDLED.load(Ordering::Relaxed), 1);
+    }
 }
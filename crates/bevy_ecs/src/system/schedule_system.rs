@@ -3,7 +3,7 @@ use alloc::{borrow::Cow, vec::Vec};
 use crate::{
     archetype::ArchetypeComponentId,
     component::{ComponentId, Tick},
-    error::Result,
+    error::{BevyError, ErrorContext, Result},
     query::Access,
     system::{input::SystemIn, BoxedSystem, System},
     world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
@@ -113,5 +113,139 @@ impl<S: System<In = (), Out = ()>> System for InfallibleSystemWrapper<S> {
     }
 }
 
+/// A wrapper system that handles errors returned by a fallible system with a specific error handler,
+/// instead of the [default error handler](crate::error::default_error_handler) used by the executor.
+///
+/// Errors raised while validating the system's parameters are routed to the same handler, and cause
+/// the system to be skipped.
+///
+/// Created by [`IntoSystem::handle_error_with`].
+pub struct ErrorHandlerSystem<S: System<In = (), Out = Result>> {
+    system: S,
+    error_handler: fn(BevyError, ErrorContext),
+}
+
+impl<S: System<In = (), Out = Result>> ErrorHandlerSystem<S> {
+    /// Creates a new system that handles errors returned by `system` with `error_handler`.
+    pub fn new(system: S, error_handler: fn(BevyError, ErrorContext)) -> Self {
+        Self {
+            system,
+            error_handler,
+        }
+    }
+
+    fn handle_error(&self, error: BevyError) {
+        (self.error_handler)(
+            error,
+            ErrorContext::System {
+                name: self.system.name(),
+                last_run: self.system.get_last_run(),
+            },
+        );
+    }
+}
+
+impl<S: System<In = (), Out = Result>> System for ErrorHandlerSystem<S> {
+    type In = ();
+    type Out = Result;
+
+    #[inline]
+    fn name(&self) -> Cow<'static, str> {
+        self.system.name()
+    }
+
+    #[inline]
+    fn component_access(&self) -> &Access<ComponentId> {
+        self.system.component_access()
+    }
+
+    #[inline]
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        self.system.archetype_component_access()
+    }
+
+    #[inline]
+    fn is_send(&self) -> bool {
+        self.system.is_send()
+    }
+
+    #[inline]
+    fn is_exclusive(&self) -> bool {
+        self.system.is_exclusive()
+    }
+
+    #[inline]
+    fn has_deferred(&self) -> bool {
+        self.system.has_deferred()
+    }
+
+    #[inline]
+    unsafe fn run_unsafe(
+        &mut self,
+        input: SystemIn<'_, Self>,
+        world: UnsafeWorldCell,
+    ) -> Self::Out {
+        // SAFETY: `system.run_unsafe` has the same invariants as `self.run_unsafe`.
+        if let Err(error) = unsafe { self.system.run_unsafe(input, world) } {
+            self.handle_error(error);
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn apply_deferred(&mut self, world: &mut World) {
+        self.system.apply_deferred(world);
+    }
+
+    #[inline]
+    fn queue_deferred(&mut self, world: DeferredWorld) {
+        self.system.queue_deferred(world);
+    }
+
+    #[inline]
+    unsafe fn validate_param_unsafe(
+        &mut self,
+        world: UnsafeWorldCell,
+    ) -> Result<(), SystemParamValidationError> {
+        // SAFETY: Delegate to other `System` implementations.
+        match unsafe { self.system.validate_param_unsafe(world) } {
+            Err(error) if !error.skipped => {
+                self.handle_error(error.into());
+                Err(SystemParamValidationError::skipped())
+            }
+            result => result,
+        }
+    }
+
+    #[inline]
+    fn initialize(&mut self, world: &mut World) {
+        self.system.initialize(world);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
+        self.system.update_archetype_component_access(world);
+    }
+
+    #[inline]
+    fn check_change_tick(&mut self, change_tick: Tick) {
+        self.system.check_change_tick(change_tick);
+    }
+
+    #[inline]
+    fn get_last_run(&self) -> Tick {
+        self.system.get_last_run()
+    }
+
+    #[inline]
+    fn set_last_run(&mut self, last_run: Tick) {
+        self.system.set_last_run(last_run);
+    }
+
+    fn default_system_sets(&self) -> Vec<crate::schedule::InternedSystemSet> {
+        self.system.default_system_sets()
+    }
+}
+
 /// Type alias for a `BoxedSystem` that a `Schedule` can store.
 pub type ScheduleSystem = BoxedSystem<(), Result>;
@@ -2,7 +2,7 @@ use crate::{
     system::{Command, SystemBuffer, SystemMeta},
     world::{DeferredWorld, World},
 };
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
 use bevy_ptr::{OwningPtr, Unaligned};
 use core::{
     fmt::Debug,
@@ -89,6 +89,10 @@ impl CommandQueue {
         // flush the world's internal queue
         world.flush_commands();
 
+        world.command_queue_peak = world
+            .command_queue_peak
+            .max(self.bytes.len().saturating_sub(self.cursor));
+
         // SAFETY: A reference is always a valid pointer
         unsafe {
             self.get_raw().apply_or_drop_queued(Some(world.into()));
@@ -142,6 +146,18 @@ impl RawCommandQueue {
         (unsafe { *self.cursor.as_ref() }) >= (unsafe { self.bytes.as_ref() }).len()
     }
 
+    /// Returns the number of bytes of the commands that haven't been applied yet.
+    ///
+    /// # Safety
+    ///
+    /// * Caller ensures that `bytes` and `cursor` point to valid memory
+    pub unsafe fn len(&self) -> usize {
+        // SAFETY: Pointers are guaranteed to be valid by requirements on `.clone_unsafe`
+        (unsafe { self.bytes.as_ref() })
+            .len()
+            .saturating_sub(unsafe { *self.cursor.as_ref() })
+    }
+
     /// Push a [`Command`] onto the queue.
     ///
     /// # Safety
@@ -320,10 +336,29 @@ impl Drop for CommandQueue {
 
 impl SystemBuffer for CommandQueue {
     #[inline]
-    fn apply(&mut self, _system_meta: &SystemMeta, world: &mut World) {
+    fn apply(&mut self, system_meta: &SystemMeta, world: &mut World) {
         #[cfg(feature = "trace")]
-        let _span_guard = _system_meta.commands_span.enter();
-        self.apply(world);
+        let _span_guard = system_meta.commands_span.enter();
+        struct CommandSourceGuard<'a> {
+            world: &'a mut World,
+            previous_source: Option<Cow<'static, str>>,
+        }
+
+        // By restoring the source in the drop impl, we ensure that it gets reset
+        // even if a command panics.
+        impl Drop for CommandSourceGuard<'_> {
+            fn drop(&mut self) {
+                self.world.command_source = self.previous_source.take();
+            }
+        }
+
+        // Remember which system queued the commands, so that their errors can report it.
+        let previous_source = world.command_source.replace(system_meta.name.clone());
+        let guard = CommandSourceGuard {
+            world,
+            previous_source,
+        };
+        self.apply(guard.world);
     }
 
     #[inline]
@@ -442,6 +477,22 @@ mod test {
         }
     }
 
+    #[test]
+    fn command_source_is_restored_after_panic() {
+        std::panic::set_hook(Box::new(|_| {}));
+
+        let mut queue = CommandQueue::default();
+        queue.push(PanicCommand("I panic!".to_owned()));
+
+        let mut world = World::new();
+        let meta = SystemMeta::new::<PanicCommand>();
+        let _ = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            SystemBuffer::apply(&mut queue, &meta, &mut world);
+        }));
+
+        assert_eq!(world.command_source, None);
+    }
+
     #[test]
     fn test_command_queue_inner_panic_safe() {
         std::panic::set_hook(Box::new(|_| {}));
@@ -0,0 +1,222 @@
+//! Introspection of the memory used by the storages of a [`World`].
+
+use crate::{
+    archetype::ArchetypeId,
+    component::{ComponentId, ComponentInfo, Components, StorageType},
+    entity::Entity,
+    storage::TableId,
+    world::World,
+};
+use alloc::{string::String, vec::Vec};
+
+/// The memory used by the storages of a [`World`], returned by [`World::memory_usage`].
+///
+/// All the sizes are in bytes, and include the capacity that was allocated but isn't used yet.
+/// Memory allocated by the components themselves, like the contents of a `Vec` in a component,
+/// isn't counted.
+#[derive(Debug, Clone, Default)]
+pub struct WorldMemoryUsage {
+    /// The memory used by each archetype, in the order of their [`ArchetypeId`]s.
+    pub archetypes: Vec<ArchetypeMemoryUsage>,
+    /// The memory used by each component stored in a table or sparse set, in the order of their
+    /// [`ComponentId`]s.
+    pub components: Vec<ComponentMemoryUsage>,
+    /// The memory used by sparse sets to find the components of entities, on top of the
+    /// components themselves.
+    pub sparse_set_overhead: usize,
+    /// The memory used by tables to list their entities, on top of their components.
+    pub table_overhead: usize,
+    /// The size of the largest [`CommandQueue`](crate::world::CommandQueue) applied to the world
+    /// so far, including the commands queued directly on the world.
+    pub command_queue_peak: usize,
+}
+
+/// The memory used by the entities of an archetype, in [`WorldMemoryUsage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchetypeMemoryUsage {
+    /// The archetype.
+    pub id: ArchetypeId,
+    /// The table storing the table components of the archetype. It may be shared with other
+    /// archetypes.
+    pub table_id: TableId,
+    /// The number of entities in the archetype.
+    pub entity_count: usize,
+    /// The size of the components of all the entities of the archetype, without their change
+    /// ticks. This memory is also counted in [`WorldMemoryUsage::components`].
+    pub component_bytes: usize,
+    /// The memory used by the archetype to list its entities.
+    pub entity_bytes: usize,
+}
+
+/// The memory used by a component, in [`WorldMemoryUsage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentMemoryUsage {
+    /// The component.
+    pub id: ComponentId,
+    /// The name of the component.
+    pub name: String,
+    /// The storage type of the component.
+    pub storage_type: StorageType,
+    /// The memory used by the component and its change ticks in all tables.
+    pub table_bytes: usize,
+    /// The memory used by the component and its change ticks in its sparse set.
+    pub sparse_set_bytes: usize,
+}
+
+impl ComponentMemoryUsage {
+    /// Returns the memory used by the component in tables and sparse sets.
+    pub fn total(&self) -> usize {
+        self.table_bytes + self.sparse_set_bytes
+    }
+}
+
+impl WorldMemoryUsage {
+    /// Returns the total memory used by the storages of the world, except command queues.
+    pub fn total(&self) -> usize {
+        self.components
+            .iter()
+            .map(ComponentMemoryUsage::total)
+            .sum::<usize>()
+            + self
+                .archetypes
+                .iter()
+                .map(|archetype| archetype.entity_bytes)
+                .sum::<usize>()
+            + self.sparse_set_overhead
+            + self.table_overhead
+    }
+}
+
+/// Returns the entry of the component `id` in `components`, adding it if it's missing.
+fn component_entry<'a>(
+    components: &'a mut Vec<Option<ComponentMemoryUsage>>,
+    infos: &Components,
+    id: ComponentId,
+) -> &'a mut ComponentMemoryUsage {
+    if components.len() <= id.index() {
+        components.resize(id.index() + 1, None);
+    }
+    components[id.index()].get_or_insert_with(|| {
+        let info = infos.get_info(id);
+        ComponentMemoryUsage {
+            id,
+            name: info.map(|info| info.name().into()).unwrap_or_default(),
+            storage_type: info.map_or(StorageType::Table, ComponentInfo::storage_type),
+            table_bytes: 0,
+            sparse_set_bytes: 0,
+        }
+    })
+}
+
+impl World {
+    /// Measures the memory used by the archetypes, tables and sparse sets of this world, to find
+    /// out which components and archetypes cause memory regressions.
+    ///
+    /// This iterates over all archetypes and storages, so it shouldn't be called every frame for
+    /// worlds with many of them. Resources aren't included.
+    pub fn memory_usage(&self) -> WorldMemoryUsage {
+        let mut components: Vec<Option<ComponentMemoryUsage>> = Vec::new();
+
+        let mut table_overhead = 0;
+        for table in self.storages.tables.iter() {
+            table_overhead += table.entity_capacity() * size_of::<Entity>();
+            for (id, bytes) in table.columns_allocated_bytes() {
+                component_entry(&mut components, &self.components, id).table_bytes += bytes;
+            }
+        }
+
+        let mut sparse_set_overhead = 0;
+        for (id, sparse_set) in self.storages.sparse_sets.iter() {
+            sparse_set_overhead += sparse_set.index_allocated_bytes();
+            component_entry(&mut components, &self.components, id).sparse_set_bytes +=
+                sparse_set.values_allocated_bytes();
+        }
+
+        let archetypes = self
+            .archetypes
+            .iter()
+            .map(|archetype| {
+                let row_size: usize = archetype
+                    .components()
+                    .filter_map(|id| self.components.get_info(id))
+                    .map(|info| info.layout().size())
+                    .sum();
+                ArchetypeMemoryUsage {
+                    id: archetype.id(),
+                    table_id: archetype.table_id(),
+                    entity_count: archetype.len(),
+                    component_bytes: archetype.len() * row_size,
+                    entity_bytes: archetype.entities_allocated_bytes(),
+                }
+            })
+            .collect();
+
+        // SAFETY: `self.command_queue` is only de-allocated in `World`'s `Drop`
+        let queued = unsafe { self.command_queue.len() };
+
+        WorldMemoryUsage {
+            archetypes,
+            components: components.into_iter().flatten().collect(),
+            sparse_set_overhead,
+            table_overhead,
+            command_queue_peak: self.command_queue_peak.max(queued),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{component::Component, world::World};
+
+    #[derive(Component)]
+    struct A(#[expect(dead_code, reason = "Only the size of the component matters.")] [u8; 16]);
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct B(#[expect(dead_code, reason = "Only the size of the component matters.")] u64);
+
+    #[test]
+    fn memory_usage() {
+        let mut world = World::new();
+        world.spawn_batch((0..10).map(|_| (A([0; 16]), B(0))));
+        let a = world.register_component::<A>();
+        let b = world.register_component::<B>();
+
+        let usage = world.memory_usage();
+        let archetype = usage
+            .archetypes
+            .iter()
+            .find(|archetype| archetype.entity_count == 10)
+            .unwrap();
+        assert_eq!(archetype.component_bytes, 10 * (16 + 8));
+
+        let a = usage.components.iter().find(|c| c.id == a).unwrap();
+        assert!(a.table_bytes >= 10 * 16);
+        assert_eq!(a.sparse_set_bytes, 0);
+        let b = usage.components.iter().find(|c| c.id == b).unwrap();
+        assert!(b.sparse_set_bytes >= 10 * 8);
+        assert_eq!(b.table_bytes, 0);
+        assert!(usage.sparse_set_overhead > 0);
+        assert!(usage.total() >= a.total() + b.total());
+    }
+
+    #[test]
+    fn command_queue_peak() {
+        let mut world = World::new();
+        let mut commands = world.commands();
+        for _ in 0..10 {
+            commands.queue(|world: &mut World| {
+                world.spawn_empty();
+            });
+        }
+        world.flush();
+        let peak = world.memory_usage().command_queue_peak;
+        assert!(peak > 0);
+
+        world.commands().queue(|world: &mut World| {
+            world.spawn_empty();
+        });
+        world.flush();
+        assert_eq!(world.memory_usage().command_queue_peak, peak);
+    }
+}
@@ -8,6 +8,7 @@ mod entity_ref;
 pub mod error;
 mod filtered_resource;
 mod identifier;
+mod memory_usage;
 mod spawn_batch;
 pub mod unsafe_world_cell;
 
@@ -28,6 +29,7 @@ pub use entity_ref::{
 };
 pub use filtered_resource::*;
 pub use identifier::WorldId;
+pub use memory_usage::*;
 pub use spawn_batch::*;
 
 #[expect(
@@ -66,7 +68,7 @@ use crate::{
         },
     },
 };
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
 use bevy_platform_support::sync::atomic::{AtomicU32, Ordering};
 use bevy_ptr::{OwningPtr, Ptr, UnsafeCellDeref};
 use core::{any::TypeId, fmt};
@@ -106,6 +108,12 @@ pub struct World {
     pub(crate) last_check_tick: Tick,
     pub(crate) last_trigger_id: u32,
     pub(crate) command_queue: RawCommandQueue,
+    /// The name of the system whose commands are being applied, if any.
+    pub(crate) command_source: Option<Cow<'static, str>>,
+    /// The despawns delayed until the end of [`World::defer_despawns`], if it's running.
+    pub(crate) deferred_despawns: Option<Vec<(Entity, MaybeLocation)>>,
+    /// The size of the largest [`CommandQueue`] applied to the world, in bytes.
+    pub(crate) command_queue_peak: usize,
 }
 
 impl Default for World {
@@ -126,6 +134,9 @@ impl Default for World {
             last_check_tick: Tick::new(0),
             last_trigger_id: 0,
             command_queue: RawCommandQueue::new(),
+            command_source: None,
+            deferred_despawns: None,
+            command_queue_peak: 0,
             component_ids: ComponentIds::default(),
         };
         world.bootstrap();
@@ -1425,6 +1436,90 @@ impl World {
         Ok(())
     }
 
+    /// Runs `f`, delaying the despawns applied by [commands](crate::system::EntityCommands::despawn)
+    /// until it returns.
+    ///
+    /// The schedule executors apply the commands of all the systems reaching a sync point within
+    /// this, so that the resulting hierarchy doesn't depend on the order in which the commands of
+    /// the different systems are applied:
+    /// - Parents are created before their children: entities spawned with commands are reserved
+    ///   as soon as the commands are queued, so they exist before any command inserting a
+    ///   [`ChildOf`](crate::hierarchy::ChildOf) relating to them is applied.
+    /// - Despawns are applied last, in the order they were queued: despawning a parent also
+    ///   despawns the children added to it at the same sync point, and not the ones moved to
+    ///   another parent. Entities that were already despawned by then, for example along with
+    ///   their parent, are skipped.
+    ///
+    /// If despawns are already deferred, `f` is simply run, and the despawns are applied at the
+    /// end of the outermost call. If `f` panics, the despawns it deferred are discarded.
+    ///
+    /// Like [`World::despawn`], a warning is logged for each deferred despawn whose entity doesn't
+    /// exist anymore.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::world::CommandQueue;
+    /// let mut world = World::new();
+    /// let parent = world.spawn_empty().id();
+    /// let child = world.spawn_empty().id();
+    ///
+    /// let mut first = CommandQueue::default();
+    /// Commands::new(&mut first, &world).entity(parent).despawn();
+    /// let mut second = CommandQueue::default();
+    /// Commands::new(&mut second, &world).entity(child).insert(ChildOf { parent });
+    ///
+    /// world.defer_despawns(|world| {
+    ///     first.apply(world);
+    ///     second.apply(world);
+    /// });
+    /// // The child was added to its parent before the parent was despawned.
+    /// assert!(world.get_entity(child).is_err());
+    /// ```
+    pub fn defer_despawns<R>(&mut self, f: impl FnOnce(&mut World) -> R) -> R {
+        if self.deferred_despawns.is_some() {
+            return f(self);
+        }
+
+        struct DeferredDespawnsGuard<'a> {
+            world: &'a mut World,
+        }
+
+        // By clearing the deferred despawns in the drop impl, we ensure that despawns
+        // stop being deferred even if a panic occurs during the scope.
+        impl Drop for DeferredDespawnsGuard<'_> {
+            fn drop(&mut self) {
+                self.world.deferred_despawns = None;
+            }
+        }
+
+        let guard = DeferredDespawnsGuard { world: self };
+        guard.world.deferred_despawns = Some(Vec::new());
+        let result = f(guard.world);
+        let despawns = guard.world.deferred_despawns.take().unwrap_or_default();
+        drop(guard);
+
+        for (entity, caller) in despawns {
+            // The entity may already have been despawned along with a previous one.
+            if let Err(error) = self.despawn_with_caller(entity, caller) {
+                warn!("{error}");
+            }
+        }
+        result
+    }
+
+    /// Delays the despawn of `entity` until the end of [`World::defer_despawns`].
+    ///
+    /// Returns `false` if despawns aren't deferred, in which case the caller should despawn it.
+    pub(crate) fn defer_despawn(&mut self, entity: Entity, caller: MaybeLocation) -> bool {
+        match &mut self.deferred_despawns {
+            Some(despawns) => {
+                despawns.push((entity, caller));
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Clears the internal component tracker state.
     ///
     /// The world maintains some internal state about changed and removed components. This state
@@ -2925,6 +3020,9 @@ impl World {
     pub(crate) fn flush_commands(&mut self) {
         // SAFETY: `self.command_queue` is only de-allocated in `World`'s `Drop`
         if !unsafe { self.command_queue.is_empty() } {
+            // SAFETY: `self.command_queue` is only de-allocated in `World`'s `Drop`
+            let len = unsafe { self.command_queue.len() };
+            self.command_queue_peak = self.command_queue_peak.max(len);
             // SAFETY: `self.command_queue` is only de-allocated in `World`'s `Drop`
             unsafe {
                 self.command_queue
@@ -3899,6 +3997,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn panic_while_deferring_despawns() {
+        let mut world = World::new();
+        let entity = world.spawn_empty().id();
+
+        let res = std::panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            world.defer_despawns(|world| {
+                world.commands().entity(entity).despawn();
+                world.flush();
+                panic!("testing what happens on panic while deferring despawns");
+            });
+        }));
+        assert!(res.is_err());
+
+        // The despawn deferred before the panic is discarded, and despawns aren't deferred anymore.
+        assert!(world.get_entity(entity).is_ok());
+        world.commands().entity(entity).despawn();
+        world.flush();
+        assert!(world.get_entity(entity).is_err());
+    }
+
     #[derive(Resource)]
     struct TestResource(u32);
 
@@ -9,7 +9,8 @@ use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 
 use bevy_asset::{Asset, RenderAssetUsages};
 use bevy_color::{Color, ColorToComponents, Gray, LinearRgba, Srgba, Xyza};
-use bevy_math::{AspectRatio, UVec2, UVec3, Vec2};
+use bevy_math::{ops, AspectRatio, UVec2, UVec3, Vec2, Vec3, Vec4};
+use core::f32::consts::{PI, TAU};
 use core::hash::Hash;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -17,7 +18,7 @@ use tracing::warn;
 use wgpu_types::{
     AddressMode, CompareFunction, Extent3d, Features, FilterMode, SamplerBorderColor,
     SamplerDescriptor, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
-    TextureViewDescriptor,
+    TextureViewDescriptor, TextureViewDimension,
 };
 
 pub trait BevyDefault {
@@ -897,6 +898,81 @@ impl Image {
         });
     }
 
+    /// Converts an equirectangular (latitude-longitude) panorama, such as an HDR environment
+    /// image, into a cubemap with faces of `face_size` by `face_size` pixels, suitable for a
+    /// skybox or an environment map.
+    ///
+    /// The center of the panorama faces the -Z direction, and its top faces +Y.
+    /// The cubemap uses the [`TextureFormat::Rgba32Float`] format, so that HDR values are kept.
+    ///
+    /// The pixels are read with [`get_color_at`](Self::get_color_at), so the same texture formats
+    /// are supported.
+    pub fn equirectangular_to_cubemap(&self, face_size: u32) -> Result<Image, TextureAccessError> {
+        if self.texture_descriptor.dimension != TextureDimension::D2
+            || self.texture_descriptor.size.depth_or_array_layers != 1
+        {
+            return Err(TextureAccessError::WrongDimension);
+        }
+
+        let (width, height) = (self.width(), self.height());
+        // Bilinearly samples the panorama, wrapping around horizontally.
+        let sample = |u: f32, v: f32| -> Result<Vec4, TextureAccessError> {
+            let x = u * width as f32 - 0.5;
+            let y = (v * height as f32 - 0.5).clamp(0.0, (height - 1) as f32);
+            let (x0, y0) = (ops::floor(x), ops::floor(y));
+            let (fx, fy) = (x - x0, y - y0);
+            let column = |x: f32| ops::rem_euclid(x, width as f32) as u32;
+            let row = |y: f32| (y as u32).min(height - 1);
+            let texel = |x: f32, y: f32| -> Result<Vec4, TextureAccessError> {
+                Ok(LinearRgba::from(self.get_color_at(column(x), row(y))?).to_vec4())
+            };
+            let top = texel(x0, y0)?.lerp(texel(x0 + 1.0, y0)?, fx);
+            let bottom = texel(x0, y0 + 1.0)?.lerp(texel(x0 + 1.0, y0 + 1.0)?, fx);
+            Ok(top.lerp(bottom, fy))
+        };
+
+        let mut data = Vec::with_capacity((face_size * face_size * 6 * 16) as usize);
+        // The faces are in the +X, -X, +Y, -Y, +Z, -Z order expected by cubemaps.
+        for face in 0..6 {
+            for y in 0..face_size {
+                for x in 0..face_size {
+                    let u = 2.0 * (x as f32 + 0.5) / face_size as f32 - 1.0;
+                    let v = 2.0 * (y as f32 + 0.5) / face_size as f32 - 1.0;
+                    let direction = match face {
+                        0 => Vec3::new(1.0, -v, -u),
+                        1 => Vec3::new(-1.0, -v, u),
+                        2 => Vec3::new(u, 1.0, v),
+                        3 => Vec3::new(u, -1.0, -v),
+                        4 => Vec3::new(u, -v, 1.0),
+                        _ => Vec3::new(-u, -v, -1.0),
+                    }
+                    .normalize();
+                    let longitude = ops::atan2(direction.x, -direction.z);
+                    let latitude = ops::acos(direction.y.clamp(-1.0, 1.0));
+                    let color = sample(0.5 + longitude / TAU, latitude / PI)?;
+                    data.extend(color.to_array().iter().flat_map(|c| c.to_le_bytes()));
+                }
+            }
+        }
+
+        let mut cubemap = Image::new(
+            Extent3d {
+                width: face_size,
+                height: face_size,
+                depth_or_array_layers: 6,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba32Float,
+            self.asset_usage,
+        );
+        cubemap.texture_view_descriptor = Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..Default::default()
+        });
+        Ok(cubemap)
+    }
+
     /// Convert a texture from a format to another. Only a few formats are
     /// supported as input and output:
     /// - `TextureFormat::R8Unorm`
@@ -1723,4 +1799,32 @@ mod test {
         image.set_color_at_3d(4, 9, 2, Color::WHITE).unwrap();
         assert!(matches!(image.get_color_at_3d(4, 9, 2), Ok(Color::WHITE)));
     }
+
+    #[test]
+    fn equirectangular_to_cubemap() {
+        // A white sky above a black ground.
+        let mut panorama = Image::new_fill(
+            Extent3d {
+                width: 8,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::MAIN_WORLD,
+        );
+        for x in 0..8 {
+            for y in 0..2 {
+                panorama.set_color_at(x, y, Color::WHITE).unwrap();
+            }
+        }
+
+        let cubemap = panorama.equirectangular_to_cubemap(4).unwrap();
+        assert_eq!(cubemap.texture_descriptor.size.depth_or_array_layers, 6);
+        let luminance = |face| LinearRgba::from(cubemap.get_color_at_3d(2, 2, face).unwrap()).red;
+        // +Y and -Y faces.
+        assert_eq!(luminance(2), 1.0);
+        assert_eq!(luminance(3), 0.0);
+    }
 }
@@ -124,11 +124,11 @@ use {
 #[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Default, Resource))]
 pub struct ButtonInput<T: Copy + Eq + Hash + Send + Sync + 'static> {
     /// A collection of every button that is currently being pressed.
-    pressed: HashSet<T>,
+    pub(crate) pressed: HashSet<T>,
     /// A collection of every button that has just been pressed.
-    just_pressed: HashSet<T>,
+    pub(crate) just_pressed: HashSet<T>,
     /// A collection of every button that has just been released.
-    just_released: HashSet<T>,
+    pub(crate) just_released: HashSet<T>,
 }
 
 impl<T: Copy + Eq + Hash + Send + Sync + 'static> Default for ButtonInput<T> {
@@ -0,0 +1,160 @@
+//! The input state seen by fixed timestep systems.
+
+use crate::ButtonInput;
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    resource::Resource,
+    system::{Res, ResMut},
+};
+use bevy_platform_support::collections::HashSet;
+use core::{hash::Hash, ops::Deref};
+
+/// The state of a [`ButtonInput`] for the current fixed timestep tick, to be read by systems in
+/// [`FixedUpdate`](bevy_app::FixedUpdate) and the other fixed schedules.
+///
+/// [`ButtonInput`] is updated once per frame, so fixed timestep systems would miss the presses
+/// made during frames running no fixed tick, and see the same press again in each tick of a frame
+/// running several. This resource accumulates the presses and releases of all the frames since the
+/// previous tick instead:
+///
+/// * [`just_pressed`](ButtonInput::just_pressed) and
+///   [`just_released`](ButtonInput::just_released) return `true` during the first tick after the
+///   input was pressed or released. A tap shorter than a tick is both just pressed and just
+///   released.
+/// * [`pressed`](ButtonInput::pressed) is the state at the end of the last frame.
+///
+/// The [`InputPlugin`](crate::InputPlugin) adds it for [`KeyCode`](crate::keyboard::KeyCode) and
+/// [`MouseButton`](crate::mouse::MouseButton). Other inputs can be added by initializing the
+/// resource and adding [`accumulate_fixed_button_input`] and [`update_fixed_button_input`]:
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_input::{
+/// #     accumulate_fixed_button_input, update_fixed_button_input, ButtonInput,
+/// #     FixedButtonInput, InputSystem,
+/// # };
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+/// enum PedalButton {
+///     Left,
+///     Right,
+/// }
+///
+/// let mut app = App::new();
+/// app.init_resource::<ButtonInput<PedalButton>>()
+///     .init_resource::<FixedButtonInput<PedalButton>>()
+///     .add_systems(
+///         PreUpdate,
+///         accumulate_fixed_button_input::<PedalButton>.after(InputSystem),
+///     )
+///     .add_systems(
+///         FixedPreUpdate,
+///         update_fixed_button_input::<PedalButton>.in_set(InputSystem),
+///     );
+/// ```
+#[derive(Debug, Clone, Resource)]
+pub struct FixedButtonInput<T: Copy + Eq + Hash + Send + Sync + 'static> {
+    /// The state seen by the current tick.
+    tick: ButtonInput<T>,
+    /// The inputs pressed since the last tick.
+    pending_just_pressed: HashSet<T>,
+    /// The inputs released since the last tick.
+    pending_just_released: HashSet<T>,
+    /// The inputs pressed at the end of the last frame.
+    pending_pressed: HashSet<T>,
+}
+
+impl<T: Copy + Eq + Hash + Send + Sync + 'static> Default for FixedButtonInput<T> {
+    fn default() -> Self {
+        Self {
+            tick: Default::default(),
+            pending_just_pressed: Default::default(),
+            pending_just_released: Default::default(),
+            pending_pressed: Default::default(),
+        }
+    }
+}
+
+impl<T: Copy + Eq + Hash + Send + Sync + 'static> Deref for FixedButtonInput<T> {
+    type Target = ButtonInput<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.tick
+    }
+}
+
+impl<T: Copy + Eq + Hash + Send + Sync + 'static> FixedButtonInput<T> {
+    /// Records the presses and releases of a frame, to be seen by the next tick.
+    pub fn accumulate(&mut self, input: &ButtonInput<T>) {
+        self.pending_just_pressed
+            .extend(input.get_just_pressed().copied());
+        self.pending_just_released
+            .extend(input.get_just_released().copied());
+        self.pending_pressed.clear();
+        self.pending_pressed.extend(input.get_pressed().copied());
+    }
+
+    /// Starts a new tick, seeing the presses and releases accumulated since the previous one.
+    pub fn start_tick(&mut self) {
+        self.tick.pressed.clone_from(&self.pending_pressed);
+        self.tick.just_pressed = core::mem::take(&mut self.pending_just_pressed);
+        self.tick.just_released = core::mem::take(&mut self.pending_just_released);
+    }
+}
+
+/// Records the presses and releases of the frame in [`FixedButtonInput<T>`].
+///
+/// This must run after [`ButtonInput<T>`] is updated, after the [`InputSystem`](crate::InputSystem)
+/// set in [`PreUpdate`](bevy_app::PreUpdate).
+pub fn accumulate_fixed_button_input<T: Copy + Eq + Hash + Send + Sync + 'static>(
+    input: Res<ButtonInput<T>>,
+    mut fixed_input: ResMut<FixedButtonInput<T>>,
+) {
+    if input.is_changed() {
+        fixed_input.accumulate(&input);
+    }
+}
+
+/// Starts a new tick of [`FixedButtonInput<T>`], at the start of each fixed timestep tick.
+pub fn update_fixed_button_input<T: Copy + Eq + Hash + Send + Sync + 'static>(
+    mut fixed_input: ResMut<FixedButtonInput<T>>,
+) {
+    fixed_input.start_tick();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, Eq, PartialEq, Hash)]
+    enum DummyInput {
+        Input1,
+        Input2,
+    }
+
+    #[test]
+    fn accumulates_between_ticks() {
+        let mut input = ButtonInput::default();
+        let mut fixed = FixedButtonInput::default();
+
+        // A tap during a frame without tick is seen by the next tick.
+        input.press(DummyInput::Input1);
+        input.release(DummyInput::Input1);
+        fixed.accumulate(&input);
+        input.clear();
+        input.press(DummyInput::Input2);
+        fixed.accumulate(&input);
+        fixed.start_tick();
+        assert!(fixed.just_pressed(DummyInput::Input1));
+        assert!(fixed.just_released(DummyInput::Input1));
+        assert!(!fixed.pressed(DummyInput::Input1));
+        assert!(fixed.just_pressed(DummyInput::Input2));
+        assert!(fixed.pressed(DummyInput::Input2));
+
+        // A second tick in the same frame doesn't see the presses again.
+        fixed.start_tick();
+        assert!(!fixed.just_pressed(DummyInput::Input1));
+        assert!(!fixed.just_pressed(DummyInput::Input2));
+        assert!(fixed.pressed(DummyInput::Input2));
+    }
+}
@@ -21,6 +21,7 @@ mod axis;
 mod button_input;
 /// Common run conditions
 pub mod common_conditions;
+mod fixed_button_input;
 pub mod gamepad;
 pub mod gestures;
 pub mod keyboard;
@@ -29,6 +30,7 @@ pub mod touch;
 
 pub use axis::*;
 pub use button_input::*;
+pub use fixed_button_input::*;
 
 /// The input prelude.
 ///
@@ -40,7 +42,7 @@ pub mod prelude {
         keyboard::KeyCode,
         mouse::MouseButton,
         touch::{TouchInput, Touches},
-        Axis, ButtonInput,
+        Axis, ButtonInput, FixedButtonInput,
     };
 }
 
@@ -86,6 +88,15 @@ impl Plugin for InputPlugin {
             .add_event::<KeyboardFocusLost>()
             .init_resource::<ButtonInput<KeyCode>>()
             .add_systems(PreUpdate, keyboard_input_system.in_set(InputSystem))
+            .init_resource::<FixedButtonInput<KeyCode>>()
+            .add_systems(
+                PreUpdate,
+                accumulate_fixed_button_input::<KeyCode>.after(keyboard_input_system),
+            )
+            .add_systems(
+                FixedPreUpdate,
+                update_fixed_button_input::<KeyCode>.in_set(InputSystem),
+            )
             // mouse
             .add_event::<MouseButtonInput>()
             .add_event::<MouseMotion>()
@@ -100,6 +111,15 @@ impl Plugin for InputPlugin {
                 )
                     .in_set(InputSystem),
             )
+            .init_resource::<FixedButtonInput<MouseButton>>()
+            .add_systems(
+                PreUpdate,
+                accumulate_fixed_button_input::<MouseButton>.after(mouse_button_input_system),
+            )
+            .add_systems(
+                FixedPreUpdate,
+                update_fixed_button_input::<MouseButton>.in_set(InputSystem),
+            )
             .add_event::<PinchGesture>()
             .add_event::<RotationGesture>()
             .add_event::<DoubleTapGesture>()
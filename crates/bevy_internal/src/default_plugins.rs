@@ -7,6 +7,8 @@ plugin_group! {
         #[cfg(feature = "bevy_log")]
         bevy_log:::LogPlugin,
         bevy_app:::TaskPoolPlugin,
+        #[cfg(feature = "std")]
+        bevy_app:::StandardPathsPlugin,
         bevy_diagnostic:::FrameCountPlugin,
         bevy_time:::TimePlugin,
         #[custom(cfg(any(feature = "libm", feature = "std")))]
@@ -45,16 +45,24 @@ pub use bevy_image as image;
 pub use bevy_input as input;
 #[cfg(feature = "bevy_input_focus")]
 pub use bevy_input_focus as input_focus;
+#[cfg(feature = "bevy_level")]
+pub use bevy_level as level;
 #[cfg(feature = "bevy_log")]
 pub use bevy_log as log;
 #[cfg(any(feature = "libm", feature = "std"))]
 pub use bevy_math as math;
+#[cfg(feature = "bevy_navmesh")]
+pub use bevy_navmesh as navmesh;
+#[cfg(feature = "bevy_net")]
+pub use bevy_net as net;
 #[cfg(feature = "bevy_pbr")]
 pub use bevy_pbr as pbr;
 #[cfg(feature = "bevy_picking")]
 pub use bevy_picking as picking;
 pub use bevy_platform_support as platform_support;
 pub use bevy_ptr as ptr;
+#[cfg(feature = "bevy_rand")]
+pub use bevy_rand as rand;
 pub use bevy_reflect as reflect;
 #[cfg(feature = "bevy_remote")]
 pub use bevy_remote as remote;
@@ -62,6 +70,8 @@ pub use bevy_remote as remote;
 pub use bevy_render as render;
 #[cfg(feature = "bevy_scene")]
 pub use bevy_scene as scene;
+#[cfg(feature = "bevy_settings")]
+pub use bevy_settings as settings;
 #[cfg(feature = "bevy_sprite")]
 pub use bevy_sprite as sprite;
 #[cfg(feature = "bevy_state")]
@@ -0,0 +1,330 @@
+use crate::{ColliderShape, LevelCollider, LevelObject, LevelProperties, LevelProperty};
+use alloc::string::String;
+use bevy_color::{Alpha, Color, Srgba};
+use bevy_ecs::{
+    bundle::Bundle, entity::Entity, hierarchy::ChildOf, name::Name, reflect::ReflectComponent,
+    world::World,
+};
+use bevy_reflect::{
+    prelude::ReflectDefault, DynamicEnum, DynamicVariant, PartialReflect, ReflectMut,
+    TypeRegistration, TypeRegistry,
+};
+use bevy_render::view::Visibility;
+use bevy_scene::Scene;
+use bevy_sprite::{TileStorage, Tilemap};
+use bevy_transform::components::Transform;
+
+/// Spawns the entities of a level into the world of a [`Scene`].
+pub(crate) struct LevelBuilder<'a> {
+    world: World,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> LevelBuilder<'a> {
+    pub(crate) fn new(registry: &'a TypeRegistry) -> Self {
+        Self {
+            world: World::new(),
+            registry,
+        }
+    }
+
+    /// Spawns a layer, or a group of layers, under `parent`.
+    pub(crate) fn spawn_layer(
+        &mut self,
+        parent: Option<Entity>,
+        name: &str,
+        transform: Transform,
+        visible: bool,
+        properties: LevelProperties,
+    ) -> Entity {
+        let visibility = if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        let mut layer = self
+            .world
+            .spawn((Name::new(String::from(name)), transform, visibility));
+        if !properties.0.is_empty() {
+            layer.insert(properties);
+        }
+        if let Some(parent) = parent {
+            layer.insert(ChildOf { parent });
+        }
+        layer.id()
+    }
+
+    /// Spawns a tilemap under `layer`, unless it has no tiles.
+    pub(crate) fn spawn_tilemap(
+        &mut self,
+        layer: Entity,
+        name: &str,
+        tilemap: Tilemap,
+        storage: TileStorage,
+    ) -> Option<Entity> {
+        if storage.is_empty() {
+            return None;
+        }
+        let entity = self
+            .world
+            .spawn((
+                Name::new(String::from(name)),
+                tilemap,
+                storage,
+                ChildOf { parent: layer },
+            ))
+            .id();
+        Some(entity)
+    }
+
+    /// Spawns a level object under `parent`.
+    ///
+    /// If `class` names a registered component, the component is inserted with its fields set
+    /// from `properties`.
+    pub(crate) fn spawn_object(
+        &mut self,
+        parent: Entity,
+        name: &str,
+        class: &str,
+        transform: Transform,
+        properties: LevelProperties,
+        collider: Option<ColliderShape>,
+    ) -> Entity {
+        let mut object = self.world.spawn((
+            Name::new(String::from(name)),
+            LevelObject {
+                class: String::from(class),
+            },
+            transform,
+            Visibility::default(),
+            ChildOf { parent },
+        ));
+        if let Some(collider) = collider {
+            object.insert(LevelCollider(collider));
+        }
+
+        if let Some((registration, reflect_component, reflect_default)) =
+            class_component(self.registry, class)
+        {
+            let mut component = reflect_default.default();
+            if let ReflectMut::Struct(component) = component.reflect_mut() {
+                for (field_name, property) in &properties.0 {
+                    let Some(field) = component.field_mut(field_name) else {
+                        continue;
+                    };
+                    if !apply_property(field, property) {
+                        log::warn!(
+                            "Property `{field_name}` of object `{name}` can't be converted to the type of field `{field_name}` of `{}`",
+                            registration.type_info().type_path()
+                        );
+                    }
+                }
+            }
+            reflect_component.insert(&mut object, component.as_partial_reflect(), self.registry);
+        }
+
+        if !properties.0.is_empty() {
+            object.insert(properties);
+        }
+        object.id()
+    }
+
+    /// Spawns a standalone collider under `parent`.
+    pub(crate) fn spawn_collider(
+        &mut self,
+        parent: Entity,
+        name: &str,
+        transform: Transform,
+        collider: ColliderShape,
+    ) -> Entity {
+        self.world
+            .spawn((
+                Name::new(String::from(name)),
+                LevelCollider(collider),
+                transform,
+                Visibility::default(),
+                ChildOf { parent },
+            ))
+            .id()
+    }
+
+    /// Inserts additional components on `entity`.
+    pub(crate) fn insert(&mut self, entity: Entity, bundle: impl Bundle) {
+        self.world.entity_mut(entity).insert(bundle);
+    }
+
+    pub(crate) fn finish(self) -> Scene {
+        Scene::new(self.world)
+    }
+}
+
+/// Finds the component registered with the type path or short type path `class`.
+fn class_component<'r>(
+    registry: &'r TypeRegistry,
+    class: &str,
+) -> Option<(
+    &'r TypeRegistration,
+    &'r ReflectComponent,
+    &'r ReflectDefault,
+)> {
+    if class.is_empty() {
+        return None;
+    }
+    let registration = registry
+        .get_with_type_path(class)
+        .or_else(|| registry.get_with_short_type_path(class))?;
+    Some((
+        registration,
+        registration.data::<ReflectComponent>()?,
+        registration.data::<ReflectDefault>()?,
+    ))
+}
+
+/// Sets `field` to the value of `property`, converting it to the type of the field.
+///
+/// Returns `false` if the property can't be converted.
+fn apply_property(field: &mut dyn PartialReflect, property: &LevelProperty) -> bool {
+    macro_rules! set {
+        ($value:expr => $($ty:ty),*) => {
+            $(
+                if let Some(field) = field.try_downcast_mut::<$ty>() {
+                    *field = $value as $ty;
+                    return true;
+                }
+            )*
+        };
+    }
+
+    match property {
+        LevelProperty::Bool(value) => {
+            set!(*value => bool);
+        }
+        LevelProperty::Int(value) => {
+            set!(*value => i64, i32, i16, i8, isize, u64, u32, u16, u8, usize, f32, f64);
+        }
+        LevelProperty::Float(value) => {
+            set!(*value => f32, f64);
+        }
+        LevelProperty::String(value) => {
+            if let Some(field) = field.try_downcast_mut::<String>() {
+                field.clone_from(value);
+                return true;
+            }
+            // Unit enum variants, such as those of LDtk enums.
+            let variant = DynamicEnum::new(value.as_str(), DynamicVariant::Unit);
+            return field.try_apply(&variant).is_ok();
+        }
+        LevelProperty::Color(value) => {
+            if let Some(field) = field.try_downcast_mut::<Color>() {
+                *field = *value;
+                return true;
+            }
+            if let Some(field) = field.try_downcast_mut::<Srgba>() {
+                *field = value.to_srgba();
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Parses a `#RRGGBB` or `#AARRGGBB` color, as written by Tiled and LDtk.
+pub(crate) fn parse_color(hex: &str) -> Option<Color> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    let hex = match hex.len() {
+        6 => hex,
+        8 => {
+            let (alpha, rgb) = hex.split_at(2);
+            let srgba = Srgba::hex(rgb).ok()?;
+            let alpha = u8::from_str_radix(alpha, 16).ok()?;
+            return Some(srgba.with_alpha(alpha as f32 / 255.0).into());
+        }
+        _ => return None,
+    };
+    Srgba::hex(hex).ok().map(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::component::Component;
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component, Default)]
+    struct Spawner {
+        count: u32,
+        interval: f32,
+        label: String,
+        mode: Mode,
+    }
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    enum Mode {
+        #[default]
+        Once,
+        Loop,
+    }
+
+    #[test]
+    fn class_component_fields_are_set_from_properties() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<Spawner>();
+        let mut builder = LevelBuilder::new(&registry);
+        let layer = builder.spawn_layer(
+            None,
+            "objects",
+            Transform::default(),
+            true,
+            LevelProperties::default(),
+        );
+
+        let properties = LevelProperties(
+            [
+                ("count", LevelProperty::Int(3)),
+                ("interval", LevelProperty::Float(0.5)),
+                ("label", LevelProperty::String("wave".into())),
+                ("mode", LevelProperty::String("Loop".into())),
+                ("unknown", LevelProperty::Bool(true)),
+            ]
+            .into_iter()
+            .map(|(name, value)| (String::from(name), value))
+            .collect(),
+        );
+        let object = builder.spawn_object(
+            layer,
+            "spawner",
+            "Spawner",
+            Transform::default(),
+            properties,
+            None,
+        );
+
+        let scene = builder.finish();
+        assert_eq!(
+            scene.world.get::<Spawner>(object),
+            Some(&Spawner {
+                count: 3,
+                interval: 0.5,
+                label: "wave".into(),
+                mode: Mode::Loop,
+            })
+        );
+        assert_eq!(scene.world.get::<ChildOf>(object).unwrap().parent, layer);
+    }
+
+    #[test]
+    fn colors() {
+        assert_eq!(
+            parse_color("#ff0000"),
+            Some(Color::from(Srgba::rgb_u8(255, 0, 0)))
+        );
+        assert_eq!(
+            parse_color("#8000ff00"),
+            Some(Color::from(
+                Srgba::rgb_u8(0, 255, 0).with_alpha(128.0 / 255.0)
+            ))
+        );
+        assert_eq!(parse_color("red"), None);
+    }
+}
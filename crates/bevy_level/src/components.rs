@@ -0,0 +1,88 @@
+use bevy_color::Color;
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_math::Vec2;
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::prelude::*;
+
+/// An object placed in a level editor, such as a Tiled object or an LDtk entity.
+///
+/// The object's name is stored in its [`Name`](bevy_ecs::name::Name) component.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Debug, Default, Clone)]
+pub struct LevelObject {
+    /// The class of the object in the editor: the object class in Tiled, or the entity
+    /// identifier in LDtk.
+    ///
+    /// If a component with this type path, or short type path, is registered with
+    /// [`ReflectComponent`] and [`ReflectDefault`], it is inserted on the object with its fields
+    /// set from the matching [`LevelProperties`].
+    pub class: String,
+}
+
+/// The custom properties of a level object or layer, as defined in the level editor.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Debug, Default, Clone)]
+pub struct LevelProperties(pub HashMap<String, LevelProperty>);
+
+impl LevelProperties {
+    /// Returns the property named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&LevelProperty> {
+        self.0.get(name)
+    }
+}
+
+/// The value of a custom property of a level object or layer.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub enum LevelProperty {
+    /// A boolean.
+    Bool(bool),
+    /// An integer.
+    Int(i64),
+    /// A floating point number.
+    Float(f64),
+    /// A string, or any value without a dedicated variant, such as file paths or enums.
+    String(String),
+    /// A color.
+    Color(Color),
+}
+
+/// A collision shape defined in the level editor.
+///
+/// Colliders are not used by Bevy itself, and are meant to be turned into colliders of the
+/// physics engine used by the application.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Debug, PartialEq, Clone)]
+pub struct LevelCollider(pub ColliderShape);
+
+/// The shape of a [`LevelCollider`], relative to the [`Transform`](bevy_transform::components::Transform)
+/// of its entity.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub enum ColliderShape {
+    /// An axis-aligned rectangle.
+    Rectangle {
+        /// The center of the rectangle.
+        center: Vec2,
+        /// Half of the size of the rectangle.
+        half_size: Vec2,
+    },
+    /// An axis-aligned ellipse.
+    Ellipse {
+        /// The center of the ellipse.
+        center: Vec2,
+        /// The radii of the ellipse.
+        half_size: Vec2,
+    },
+    /// A closed polygon.
+    Polygon(Vec<Vec2>),
+    /// An open line strip.
+    Polyline(Vec<Vec2>),
+    /// A single point.
+    Point(Vec2),
+}
+
+/// The value of the LDtk `IntGrid` cells covered by a [`LevelCollider`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component, Debug, PartialEq, Clone)]
+pub struct IntGridValue(pub i32);
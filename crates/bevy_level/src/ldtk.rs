@@ -0,0 +1,555 @@
+use crate::{
+    builder::{parse_color, LevelBuilder},
+    ColliderShape, IntGridValue, LevelProperties, LevelProperty,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_asset::{
+    io::Reader, AssetLoader, Handle, LoadContext, ParseAssetPathError, ReadAssetBytesError,
+};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::AppTypeRegistry,
+    world::{FromWorld, World},
+};
+use bevy_image::Image;
+use bevy_math::{IVec2, UVec2, Vec2, Vec3};
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::{TypeRegistry, TypeRegistryArc};
+use bevy_scene::Scene;
+use bevy_sprite::{Tile, TileStorage, Tilemap};
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Asset loader for [LDtk](https://ldtk.io) projects (`.ldtk`).
+///
+/// The project is loaded as a [`Scene`] with an entity per level, placed at its position in the
+/// world, and an entity per layer of each level:
+/// - Tile and auto-layers spawn a [`Tilemap`]. Stacked tiles spawn additional tilemaps.
+/// - `IntGrid` layers spawn a child with a [`LevelCollider`](crate::LevelCollider) and an
+///   [`IntGridValue`] for each horizontal run of cells with the same value, as well as a
+///   [`Tilemap`] for their auto-layer tiles.
+/// - Entity layers spawn a child for each entity, with a [`LevelObject`](crate::LevelObject), its
+///   fields as [`LevelProperties`] and a [`LevelCollider`](crate::LevelCollider) covering its bounds.
+///
+/// LDtk uses pixels as units, and `y` pointing down: levels are flipped so that the bottom-left
+/// corner of the world is at the origin of the scene. Each layer is placed one unit in front of
+/// the layer below it.
+///
+/// Levels can be embedded in the project or saved in separate files. Tilesets must not have
+/// spacing or padding.
+#[derive(Debug)]
+pub struct LdtkLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for LdtkLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        LdtkLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+/// Possible errors that can be produced by [`LdtkLoader`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum LdtkLoaderError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to read the project file: {0}")]
+    Io(#[from] std::io::Error),
+    /// An external level couldn't be read.
+    #[error("Could not read level: {0}")]
+    ReadAssetBytes(#[from] ReadAssetBytesError),
+    /// The path of a level or tileset is invalid.
+    #[error("Invalid asset path: {0}")]
+    AssetPath(#[from] ParseAssetPathError),
+    /// The project or a level is not a valid LDtk file.
+    #[error("Could not parse JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The project uses a feature that isn't supported.
+    #[error("Unsupported project: {0}")]
+    Unsupported(String),
+}
+
+impl AssetLoader for LdtkLoader {
+    type Asset = Scene;
+    type Settings = ();
+    type Error = LdtkLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut project: Project = serde_json::from_slice(&bytes)?;
+
+        for level in &mut project.levels {
+            if level.layer_instances.is_some() {
+                continue;
+            }
+            let Some(external) = &level.external_rel_path else {
+                continue;
+            };
+            let path = load_context.asset_path().resolve_embed(external)?;
+            let bytes = load_context.read_asset_bytes(path).await?;
+            *level = serde_json::from_slice(&bytes)?;
+        }
+
+        build_project(
+            &project,
+            &mut |path| {
+                let path = load_context.asset_path().resolve_embed(path)?;
+                Ok(load_context.load(path))
+            },
+            &self.type_registry.read(),
+        )
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ldtk"]
+    }
+}
+
+#[derive(Deserialize)]
+struct Project {
+    defs: Definitions,
+    levels: Vec<Level>,
+}
+
+#[derive(Deserialize)]
+struct Definitions {
+    tilesets: Vec<TilesetDefinition>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TilesetDefinition {
+    uid: i32,
+    identifier: String,
+    rel_path: Option<String>,
+    px_wid: u32,
+    px_hei: u32,
+    tile_grid_size: u32,
+    spacing: u32,
+    padding: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Level {
+    identifier: String,
+    world_x: f32,
+    world_y: f32,
+    px_hei: f32,
+    #[serde(default)]
+    field_instances: Vec<FieldInstance>,
+    layer_instances: Option<Vec<LayerInstance>>,
+    external_rel_path: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LayerInstance {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__type")]
+    layer_type: String,
+    #[serde(rename = "__cWid")]
+    c_wid: i32,
+    #[serde(rename = "__cHei")]
+    c_hei: i32,
+    #[serde(rename = "__gridSize")]
+    grid_size: f32,
+    #[serde(rename = "__tilesetDefUid")]
+    tileset_def_uid: Option<i32>,
+    #[serde(rename = "__pxTotalOffsetX")]
+    px_total_offset_x: f32,
+    #[serde(rename = "__pxTotalOffsetY")]
+    px_total_offset_y: f32,
+    visible: bool,
+    #[serde(default)]
+    int_grid_csv: Vec<i32>,
+    #[serde(default)]
+    grid_tiles: Vec<TileInstance>,
+    #[serde(default)]
+    auto_layer_tiles: Vec<TileInstance>,
+    #[serde(default)]
+    entity_instances: Vec<EntityInstance>,
+}
+
+#[derive(Deserialize)]
+struct TileInstance {
+    px: [f32; 2],
+    t: u32,
+    f: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EntityInstance {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__pivot")]
+    pivot: [f32; 2],
+    px: [f32; 2],
+    width: f32,
+    height: f32,
+    #[serde(default)]
+    field_instances: Vec<FieldInstance>,
+}
+
+#[derive(Deserialize)]
+struct FieldInstance {
+    #[serde(rename = "__identifier")]
+    identifier: String,
+    #[serde(rename = "__type")]
+    field_type: String,
+    #[serde(rename = "__value")]
+    value: serde_json::Value,
+}
+
+type LoadImage<'a> = dyn FnMut(&str) -> Result<Handle<Image>, LdtkLoaderError> + 'a;
+
+struct Tileset<'a> {
+    name: &'a str,
+    image: Handle<Image>,
+    size: UVec2,
+    tile_size: u32,
+}
+
+fn build_project(
+    project: &Project,
+    load_image: &mut LoadImage,
+    registry: &TypeRegistry,
+) -> Result<Scene, LdtkLoaderError> {
+    let mut tilesets: HashMap<_, _> = HashMap::default();
+    for tileset in &project.defs.tilesets {
+        // Tilesets without an image, such as the internal icons, can't be used by layers.
+        let Some(path) = &tileset.rel_path else {
+            continue;
+        };
+        if tileset.spacing != 0 || tileset.padding != 0 {
+            return Err(LdtkLoaderError::Unsupported(format!(
+                "tileset `{}` has spacing or padding",
+                tileset.identifier
+            )));
+        }
+        let tile_size = tileset.tile_grid_size.max(1);
+        tilesets.insert(
+            tileset.uid,
+            Tileset {
+                name: &tileset.identifier,
+                image: load_image(path)?,
+                size: UVec2::new(tileset.px_wid, tileset.px_hei) / tile_size,
+                tile_size,
+            },
+        );
+    }
+
+    let mut builder = LevelBuilder::new(registry);
+    for level in &project.levels {
+        let Some(layers) = &level.layer_instances else {
+            log::warn!("Level `{}` has no layers", level.identifier);
+            continue;
+        };
+        // The origin of the level is its bottom-left corner.
+        let transform = Transform::from_xyz(level.world_x, -(level.world_y + level.px_hei), 0.0);
+        let level_entity = builder.spawn_layer(
+            None,
+            &level.identifier,
+            transform,
+            true,
+            parse_fields(&level.field_instances),
+        );
+
+        // Layers are listed from top to bottom.
+        for (index, layer) in layers.iter().rev().enumerate() {
+            let transform = Transform::from_xyz(
+                layer.px_total_offset_x,
+                -layer.px_total_offset_y,
+                index as f32,
+            );
+            let layer_entity = builder.spawn_layer(
+                Some(level_entity),
+                &layer.identifier,
+                transform,
+                layer.visible,
+                LevelProperties::default(),
+            );
+
+            match layer.layer_type.as_str() {
+                "Entities" => spawn_entities(&mut builder, layer, layer_entity),
+                "IntGrid" => spawn_int_grid(&mut builder, layer, layer_entity),
+                _ => {}
+            }
+            let tiles = layer.grid_tiles.iter().chain(&layer.auto_layer_tiles);
+            if let Some(tileset) = layer.tileset_def_uid.and_then(|uid| tilesets.get(&uid)) {
+                spawn_tiles(&mut builder, layer, layer_entity, tileset, tiles);
+            }
+        }
+    }
+    Ok(builder.finish())
+}
+
+fn spawn_tiles<'a>(
+    builder: &mut LevelBuilder,
+    layer: &LayerInstance,
+    layer_entity: Entity,
+    tileset: &Tileset,
+    tiles: impl Iterator<Item = &'a TileInstance>,
+) {
+    // Tiles can be stacked in the same cell, each stacked tile goes in the next tilemap.
+    let mut storages: Vec<TileStorage> = Vec::new();
+    for tile in tiles {
+        let cell = Vec2::from(tile.px) / layer.grid_size;
+        let position = IVec2::new(cell.x as i32, layer.c_hei - 1 - cell.y as i32);
+        let storage = match storages
+            .iter_mut()
+            .position(|storage| storage.get(position).is_none())
+        {
+            Some(index) => &mut storages[index],
+            None => {
+                storages.push(TileStorage::default());
+                storages.last_mut().unwrap()
+            }
+        };
+        let mut tile_data = Tile::new(tile.t);
+        tile_data.flip_x = tile.f & 1 != 0;
+        tile_data.flip_y = tile.f & 2 != 0;
+        storage.set(position, tile_data);
+    }
+
+    if tileset.tile_size as f32 != layer.grid_size {
+        log::warn!(
+            "Tiles of tileset `{}` will be drawn with the grid size of layer `{}`",
+            tileset.name,
+            layer.identifier
+        );
+    }
+    for storage in storages {
+        let tilemap = Tilemap {
+            tileset: tileset.image.clone(),
+            tileset_size: tileset.size,
+            tile_size: Vec2::splat(layer.grid_size),
+        };
+        builder.spawn_tilemap(layer_entity, tileset.name, tilemap, storage);
+    }
+}
+
+fn spawn_int_grid(builder: &mut LevelBuilder, layer: &LayerInstance, layer_entity: Entity) {
+    let width = layer.c_wid.max(0) as usize;
+    if width == 0 {
+        return;
+    }
+    for (row, cells) in layer.int_grid_csv.chunks(width).enumerate() {
+        for (start, len, value) in runs(cells) {
+            let size = Vec2::new(len as f32, 1.0) * layer.grid_size;
+            // Rows go down in LDtk and up in the scene.
+            let min =
+                Vec2::new(start as f32, (layer.c_hei - 1 - row as i32) as f32) * layer.grid_size;
+            let transform = Transform::from_translation((min + size / 2.0).extend(0.0));
+            let collider = builder.spawn_collider(
+                layer_entity,
+                &layer.identifier,
+                transform,
+                ColliderShape::Rectangle {
+                    center: Vec2::ZERO,
+                    half_size: size / 2.0,
+                },
+            );
+            builder.insert(collider, IntGridValue(value));
+        }
+    }
+}
+
+/// Returns the start, length and value of each run of consecutive non-empty cells with the same value.
+fn runs(cells: &[i32]) -> impl Iterator<Item = (usize, usize, i32)> + '_ {
+    let mut start = 0;
+    core::iter::from_fn(move || {
+        while start < cells.len() && cells[start] == 0 {
+            start += 1;
+        }
+        let value = *cells.get(start)?;
+        let len = cells[start..]
+            .iter()
+            .take_while(|&&cell| cell == value)
+            .count();
+        let run = (start, len, value);
+        start += len;
+        Some(run)
+    })
+}
+
+fn spawn_entities(builder: &mut LevelBuilder, layer: &LayerInstance, layer_entity: Entity) {
+    let layer_height = layer.c_hei as f32 * layer.grid_size;
+    for entity in &layer.entity_instances {
+        let position = Vec2::new(entity.px[0], layer_height - entity.px[1]);
+        let size = Vec2::new(entity.width, entity.height);
+        // The pivot is relative to the top-left corner of the entity, with `y` pointing down.
+        let pivot = Vec2::from(entity.pivot);
+        let center = Vec2::new(0.5 - pivot.x, pivot.y - 0.5) * size;
+        builder.spawn_object(
+            layer_entity,
+            &entity.identifier,
+            &entity.identifier,
+            Transform::from_translation(Vec3::from((position, 0.0))),
+            parse_fields(&entity.field_instances),
+            Some(ColliderShape::Rectangle {
+                center,
+                half_size: size / 2.0,
+            }),
+        );
+    }
+}
+
+fn parse_fields(fields: &[FieldInstance]) -> LevelProperties {
+    use serde_json::Value;
+
+    let mut properties = LevelProperties::default();
+    for field in fields {
+        let value = match (field.field_type.as_str(), &field.value) {
+            (_, Value::Null) => continue,
+            ("Int", Value::Number(value)) => match value.as_i64() {
+                Some(value) => LevelProperty::Int(value),
+                None => continue,
+            },
+            ("Float", Value::Number(value)) => match value.as_f64() {
+                Some(value) => LevelProperty::Float(value),
+                None => continue,
+            },
+            ("Bool", &Value::Bool(value)) => LevelProperty::Bool(value),
+            ("Color", Value::String(value)) => match parse_color(value) {
+                Some(color) => LevelProperty::Color(color),
+                None => continue,
+            },
+            // Strings, multiline strings, file paths and enum values.
+            (_, Value::String(value)) => LevelProperty::String(value.clone()),
+            (field_type, _) => {
+                log::debug!(
+                    "Field `{}` of type `{field_type}` is not supported",
+                    field.identifier
+                );
+                continue;
+            }
+        };
+        properties.0.insert(field.identifier.to_string(), value);
+    }
+    properties
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LevelCollider, LevelObject};
+
+    const PROJECT: &str = r#"{
+        "defs": {
+            "tilesets": [{
+                "uid": 1, "identifier": "Terrain", "relPath": "terrain.png",
+                "pxWid": 32, "pxHei": 16, "tileGridSize": 16, "spacing": 0, "padding": 0
+            }]
+        },
+        "levels": [{
+            "identifier": "Level_0", "worldX": 0, "worldY": 0, "pxWid": 48, "pxHei": 32,
+            "fieldInstances": [],
+            "externalRelPath": null,
+            "layerInstances": [
+                {
+                    "__identifier": "Entities", "__type": "Entities", "__cWid": 3, "__cHei": 2,
+                    "__gridSize": 16, "__tilesetDefUid": null,
+                    "__pxTotalOffsetX": 0, "__pxTotalOffsetY": 0, "visible": true,
+                    "intGridCsv": [], "gridTiles": [], "autoLayerTiles": [],
+                    "entityInstances": [{
+                        "__identifier": "Player", "__pivot": [0.5, 1], "px": [24, 32],
+                        "width": 16, "height": 16,
+                        "fieldInstances": [
+                            { "__identifier": "health", "__type": "Int", "__value": 3 },
+                            { "__identifier": "target", "__type": "EntityRef", "__value": null }
+                        ]
+                    }]
+                },
+                {
+                    "__identifier": "Ground", "__type": "IntGrid", "__cWid": 3, "__cHei": 2,
+                    "__gridSize": 16, "__tilesetDefUid": 1,
+                    "__pxTotalOffsetX": 0, "__pxTotalOffsetY": 0, "visible": true,
+                    "intGridCsv": [0, 0, 0, 1, 1, 2],
+                    "gridTiles": [],
+                    "autoLayerTiles": [
+                        { "px": [0, 16], "src": [0, 0], "f": 1, "t": 0 },
+                        { "px": [0, 16], "src": [16, 0], "f": 0, "t": 1 }
+                    ],
+                    "entityInstances": []
+                }
+            ]
+        }]
+    }"#;
+
+    #[test]
+    fn load_project() {
+        let registry = TypeRegistry::new();
+        let project: Project = serde_json::from_str(PROJECT).unwrap();
+        let mut scene = build_project(&project, &mut |_| Ok(Handle::default()), &registry).unwrap();
+        let world = &mut scene.world;
+
+        let mut tilemaps = world.query::<&TileStorage>();
+        let mut storages = tilemaps.iter(world).collect::<Vec<_>>();
+        storages.sort_by_key(|storage| storage.get(IVec2::ZERO).unwrap().index);
+        assert_eq!(storages.len(), 2);
+        assert!(storages[0].get(IVec2::ZERO).unwrap().flip_x);
+        assert_eq!(storages[1].get(IVec2::ZERO), Some(&Tile::new(1)));
+
+        let mut grid = world.query::<(&IntGridValue, &LevelCollider, &Transform)>();
+        let mut runs = grid
+            .iter(world)
+            .map(|(value, collider, transform)| {
+                (value.0, collider.0.clone(), transform.translation)
+            })
+            .collect::<Vec<_>>();
+        runs.sort_by_key(|(value, ..)| *value);
+        assert_eq!(
+            runs,
+            [
+                (
+                    1,
+                    ColliderShape::Rectangle {
+                        center: Vec2::ZERO,
+                        half_size: Vec2::new(16.0, 8.0),
+                    },
+                    Vec3::new(16.0, 8.0, 0.0),
+                ),
+                (
+                    2,
+                    ColliderShape::Rectangle {
+                        center: Vec2::ZERO,
+                        half_size: Vec2::new(8.0, 8.0),
+                    },
+                    Vec3::new(40.0, 8.0, 0.0),
+                ),
+            ]
+        );
+
+        let mut objects =
+            world.query::<(&LevelObject, &LevelProperties, &Transform, &LevelCollider)>();
+        let (object, properties, transform, collider) = objects.single(world).unwrap();
+        assert_eq!(object.class, "Player");
+        assert_eq!(properties.0.len(), 1);
+        assert_eq!(properties.get("health"), Some(&LevelProperty::Int(3)));
+        assert_eq!(transform.translation, Vec3::new(24.0, 0.0, 0.0));
+        assert_eq!(
+            collider.0,
+            ColliderShape::Rectangle {
+                center: Vec2::new(0.0, 8.0),
+                half_size: Vec2::new(8.0, 8.0),
+            }
+        );
+    }
+}
@@ -0,0 +1,90 @@
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![doc(
+    html_logo_url = "https://bevyengine.org/assets/icon.png",
+    html_favicon_url = "https://bevyengine.org/assets/icon.png"
+)]
+
+//! Provides asset loaders for levels authored in external level editors.
+//!
+//! Levels are loaded as [`Scene`](bevy_scene::Scene)s, which can be spawned with a
+//! [`SceneRoot`](bevy_scene::SceneRoot):
+//! - With the `tiled` feature, [Tiled](https://www.mapeditor.org) maps (`.tmx`) are loaded by the [`TiledLoader`].
+//! - With the `ldtk` feature, [LDtk](https://ldtk.io) projects (`.ldtk`) are loaded by the [`LdtkLoader`].
+//!
+//! Tile layers are rendered with [`Tilemap`](bevy_sprite::Tilemap)s. Objects placed in the editor
+//! are spawned as entities with a [`LevelObject`] component, their custom [`LevelProperties`], and
+//! a [`LevelCollider`] describing their shape, to be turned into colliders of a physics engine.
+//!
+//! The class of an object can name a reflected component, registered with `#[reflect(Component, Default)]`:
+//! the component is then inserted on the object, with its fields set from the properties of the
+//! object that have the same name.
+//!
+//! ```no_run
+//! # use bevy_app::prelude::*;
+//! # use bevy_asset::AssetServer;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_reflect::prelude::*;
+//! # use bevy_scene::SceneRoot;
+//! #[derive(Component, Reflect, Default)]
+//! #[reflect(Component, Default)]
+//! struct Door {
+//!     locked: bool,
+//! }
+//!
+//! fn spawn_level(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     commands.spawn(SceneRoot(asset_server.load("levels/dungeon.tmx")));
+//! }
+//!
+//! # let mut app = App::new();
+//! app.register_type::<Door>().add_systems(Startup, spawn_level);
+//! ```
+
+extern crate alloc;
+
+mod builder;
+mod components;
+#[cfg(feature = "ldtk")]
+mod ldtk;
+#[cfg(feature = "tiled")]
+mod tiled;
+
+pub use components::*;
+#[cfg(feature = "ldtk")]
+pub use ldtk::*;
+#[cfg(feature = "tiled")]
+pub use tiled::*;
+
+/// The level prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{ColliderShape, LevelCollider, LevelObject, LevelPlugin, LevelProperties};
+}
+
+use bevy_app::prelude::*;
+#[cfg(any(feature = "tiled", feature = "ldtk"))]
+use bevy_asset::AssetApp;
+
+/// Adds support for loading levels authored in external level editors.
+///
+/// This plugin must be added after the [`ScenePlugin`](bevy_scene::ScenePlugin) and the
+/// [`SpritePlugin`](bevy_sprite::SpritePlugin).
+#[derive(Default)]
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<LevelObject>()
+            .register_type::<LevelProperties>()
+            .register_type::<LevelProperty>()
+            .register_type::<LevelCollider>()
+            .register_type::<ColliderShape>()
+            .register_type::<IntGridValue>();
+
+        #[cfg(feature = "tiled")]
+        app.init_asset_loader::<TiledLoader>();
+        #[cfg(feature = "ldtk")]
+        app.init_asset_loader::<LdtkLoader>();
+    }
+}
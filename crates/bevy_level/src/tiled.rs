@@ -0,0 +1,737 @@
+use crate::{
+    builder::{parse_color, LevelBuilder},
+    ColliderShape, LevelProperties, LevelProperty,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use bevy_asset::{
+    io::Reader, AssetLoader, Handle, LoadContext, ParseAssetPathError, ReadAssetBytesError,
+};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::AppTypeRegistry,
+    world::{FromWorld, World},
+};
+use bevy_image::Image;
+use bevy_math::{IVec2, Quat, UVec2, Vec2, Vec3};
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::{TypeRegistry, TypeRegistryArc};
+use bevy_scene::Scene;
+use bevy_sprite::{Tile, TileAnimation, TileStorage, Tilemap};
+use bevy_transform::components::Transform;
+use roxmltree::{Document, Node};
+use thiserror::Error;
+
+const FLIPPED_HORIZONTALLY: u32 = 0x8000_0000;
+const FLIPPED_VERTICALLY: u32 = 0x4000_0000;
+const FLIPPED_DIAGONALLY: u32 = 0x2000_0000;
+const ROTATED_HEXAGONAL: u32 = 0x1000_0000;
+const GID_MASK: u32 =
+    !(FLIPPED_HORIZONTALLY | FLIPPED_VERTICALLY | FLIPPED_DIAGONALLY | ROTATED_HEXAGONAL);
+
+/// Asset loader for [Tiled](https://www.mapeditor.org) maps (`.tmx`).
+///
+/// The map is loaded as a [`Scene`] with an entity per layer, in the order of the layers in the
+/// map:
+/// - Tile layers spawn a [`Tilemap`] child for each tileset used by the layer.
+///   Collision shapes drawn on tiles in the tileset editor are spawned as children of the layer
+///   with a [`LevelCollider`](crate::LevelCollider).
+/// - Object layers spawn a child for each object, with a [`LevelObject`](crate::LevelObject),
+///   its custom [`LevelProperties`] and a [`LevelCollider`](crate::LevelCollider) matching its shape.
+/// - Group layers spawn their layers as children.
+///
+/// Tiled uses pixels as units, and `y` pointing down: the map is flipped so that its bottom-left
+/// corner is at the origin of the scene. Each layer is placed one unit in front of the previous one.
+///
+/// Only orthogonal maps are supported. Tilesets can be embedded in the map or stored in external
+/// `.tsx` files, but must be made of a single image without spacing or margin. Tile layer data
+/// must not be compressed.
+#[derive(Debug)]
+pub struct TiledLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for TiledLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        TiledLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+/// Possible errors that can be produced by [`TiledLoader`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum TiledLoaderError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to read the map file: {0}")]
+    Io(#[from] std::io::Error),
+    /// An external tileset couldn't be read.
+    #[error("Could not read tileset: {0}")]
+    ReadAssetBytes(#[from] ReadAssetBytesError),
+    /// The path of a tileset or image is invalid.
+    #[error("Invalid asset path: {0}")]
+    AssetPath(#[from] ParseAssetPathError),
+    /// The map or a tileset is not valid XML.
+    #[error("Could not parse XML: {0}")]
+    Xml(#[from] roxmltree::Error),
+    /// The map or a tileset is not a valid Tiled document.
+    #[error("Invalid map: {0}")]
+    Invalid(String),
+    /// The map uses a feature that isn't supported.
+    #[error("Unsupported map: {0}")]
+    Unsupported(String),
+}
+
+impl AssetLoader for TiledLoader {
+    type Asset = Scene;
+    type Settings = ();
+    type Error = TiledLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = String::from_utf8(bytes).map_err(|_| invalid("the map is not valid UTF-8"))?;
+        let document = Document::parse(&text)?;
+
+        let mut external_tilesets = HashMap::default();
+        for tileset in document
+            .root_element()
+            .children()
+            .filter(|node| node.has_tag_name("tileset"))
+        {
+            let Some(source) = tileset.attribute("source") else {
+                continue;
+            };
+            let path = load_context.asset_path().resolve_embed(source)?;
+            let bytes = load_context.read_asset_bytes(path).await?;
+            let text = String::from_utf8(bytes)
+                .map_err(|_| invalid(format!("tileset `{source}` is not valid UTF-8")))?;
+            external_tilesets.insert(source.to_string(), text);
+        }
+
+        build_map(
+            &document,
+            &external_tilesets,
+            &mut |path| {
+                let path = load_context.asset_path().resolve_embed(path)?;
+                Ok(load_context.load(path))
+            },
+            &self.type_registry.read(),
+        )
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["tmx"]
+    }
+}
+
+type LoadImage<'a> = dyn FnMut(&str) -> Result<Handle<Image>, TiledLoaderError> + 'a;
+
+struct Tileset {
+    first_gid: u32,
+    name: String,
+    image: Handle<Image>,
+    size: UVec2,
+    tile_size: Vec2,
+    animations: HashMap<u32, TileAnimation>,
+    colliders: HashMap<u32, Vec<(Transform, ColliderShape)>>,
+}
+
+struct MapContext {
+    tilesets: Vec<Tileset>,
+    tile_size: Vec2,
+    /// The height of the map, in pixels.
+    height: f32,
+}
+
+/// Builds the scene of a Tiled map, given the contents of its external tilesets keyed by their
+/// `source`.
+fn build_map(
+    document: &Document,
+    external_tilesets: &HashMap<String, String>,
+    load_image: &mut LoadImage,
+    registry: &TypeRegistry,
+) -> Result<Scene, TiledLoaderError> {
+    let map = document.root_element();
+    if !map.has_tag_name("map") {
+        return Err(invalid("the root element is not a map"));
+    }
+    let orientation = map.attribute("orientation").unwrap_or("orthogonal");
+    if orientation != "orthogonal" {
+        return Err(TiledLoaderError::Unsupported(format!(
+            "{orientation} maps are not supported"
+        )));
+    }
+
+    let mut tilesets = Vec::new();
+    for node in map.children().filter(|node| node.has_tag_name("tileset")) {
+        let first_gid = attribute(node, "firstgid")?;
+        let tileset = match node.attribute("source") {
+            Some(source) => {
+                let text = external_tilesets
+                    .get(source)
+                    .ok_or_else(|| invalid(format!("missing tileset `{source}`")))?;
+                let document = Document::parse(text)?;
+                parse_tileset(document.root_element(), first_gid, source, load_image)?
+            }
+            None => parse_tileset(node, first_gid, "", load_image)?,
+        };
+        tilesets.push(tileset);
+    }
+    tilesets.sort_by_key(|tileset| tileset.first_gid);
+
+    let tile_size = Vec2::new(attribute(map, "tilewidth")?, attribute(map, "tileheight")?);
+    for tileset in &tilesets {
+        if tileset.tile_size != tile_size {
+            log::warn!(
+                "Tiles of tileset `{}` will be drawn with the tile size of the map",
+                tileset.name
+            );
+        }
+    }
+    let context = MapContext {
+        tilesets,
+        tile_size,
+        height: attribute::<f32>(map, "height")? * tile_size.y,
+    };
+
+    let mut builder = LevelBuilder::new(registry);
+    spawn_layers(&mut builder, &context, map, None)?;
+    Ok(builder.finish())
+}
+
+fn parse_tileset(
+    node: Node,
+    first_gid: u32,
+    source: &str,
+    load_image: &mut LoadImage,
+) -> Result<Tileset, TiledLoaderError> {
+    let name = node.attribute("name").unwrap_or_default().to_string();
+    if node
+        .attribute("spacing")
+        .is_some_and(|spacing| spacing != "0")
+        || node.attribute("margin").is_some_and(|margin| margin != "0")
+    {
+        return Err(TiledLoaderError::Unsupported(format!(
+            "tileset `{name}` has spacing or a margin"
+        )));
+    }
+    let Some(image) = node.children().find(|node| node.has_tag_name("image")) else {
+        return Err(TiledLoaderError::Unsupported(format!(
+            "tileset `{name}` is not made of a single image"
+        )));
+    };
+
+    let tile_size = Vec2::new(
+        attribute(node, "tilewidth")?,
+        attribute(node, "tileheight")?,
+    );
+    let columns: u32 = attribute(node, "columns")?;
+    let tile_count: u32 = attribute(node, "tilecount")?;
+    let image_source = image
+        .attribute("source")
+        .ok_or_else(|| invalid(format!("the image of tileset `{name}` has no source")))?;
+    // Images of external tilesets are relative to the tileset file.
+    let image_path = match source.rfind('/') {
+        Some(index) if !image_source.starts_with('/') => {
+            format!("{}{image_source}", &source[..=index])
+        }
+        _ => image_source.to_string(),
+    };
+
+    let mut tileset = Tileset {
+        first_gid,
+        image: load_image(&image_path)?,
+        size: UVec2::new(columns, tile_count.div_ceil(columns.max(1))),
+        tile_size,
+        animations: HashMap::default(),
+        colliders: HashMap::default(),
+        name,
+    };
+
+    for tile in node.children().filter(|node| node.has_tag_name("tile")) {
+        let id: u32 = attribute(tile, "id")?;
+        if let Some(animation) = tile.children().find(|node| node.has_tag_name("animation")) {
+            let frames = animation
+                .children()
+                .filter(|node| node.has_tag_name("frame"))
+                .map(|frame| Ok((attribute(frame, "tileid")?, attribute(frame, "duration")?)))
+                .collect::<Result<Vec<(u32, u32)>, TiledLoaderError>>()?;
+            match tile_animation(id, &frames) {
+                Some(animation) => {
+                    tileset.animations.insert(id, animation);
+                }
+                None => log::warn!(
+                    "Animation of tile {id} of tileset `{}` is not supported: \
+                    frames must be consecutive tiles starting with the animated tile, with the same duration",
+                    tileset.name
+                ),
+            }
+        }
+
+        if let Some(group) = tile
+            .children()
+            .find(|node| node.has_tag_name("objectgroup"))
+        {
+            let colliders = group
+                .children()
+                .filter(|node| node.has_tag_name("object"))
+                .map(|object| parse_object(object, tile_size.y))
+                .filter_map(|object| {
+                    object
+                        .map(|object| Some((object.transform, object.shape?)))
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if !colliders.is_empty() {
+                tileset.colliders.insert(id, colliders);
+            }
+        }
+    }
+
+    Ok(tileset)
+}
+
+/// Converts the frames of a Tiled animation to a [`TileAnimation`], if they can be represented as one.
+fn tile_animation(id: u32, frames: &[(u32, u32)]) -> Option<TileAnimation> {
+    let &(_, duration) = frames.first()?;
+    let consecutive = frames
+        .iter()
+        .enumerate()
+        .all(|(index, &(tile, frame_duration))| {
+            tile == id + index as u32 && frame_duration == duration && duration > 0
+        });
+    consecutive.then(|| TileAnimation {
+        frames: frames.len() as u32,
+        fps: 1000.0 / duration as f32,
+    })
+}
+
+fn spawn_layers(
+    builder: &mut LevelBuilder,
+    context: &MapContext,
+    parent_node: Node,
+    parent: Option<Entity>,
+) -> Result<(), TiledLoaderError> {
+    let layers = parent_node.children().filter(|node| {
+        node.has_tag_name("layer")
+            || node.has_tag_name("objectgroup")
+            || node.has_tag_name("imagelayer")
+            || node.has_tag_name("group")
+    });
+    for (index, node) in layers.enumerate() {
+        if node.has_tag_name("imagelayer") {
+            log::warn!(
+                "Image layer `{}` is not supported",
+                node.attribute("name").unwrap_or_default()
+            );
+            continue;
+        }
+
+        let name = node.attribute("name").unwrap_or_default();
+        let offset = Vec2::new(
+            optional_attribute(node, "offsetx")?.unwrap_or(0.0),
+            optional_attribute(node, "offsety")?.unwrap_or(0.0),
+        );
+        let transform = Transform::from_xyz(offset.x, -offset.y, index as f32);
+        let visible = node.attribute("visible") != Some("0");
+        let layer = builder.spawn_layer(parent, name, transform, visible, parse_properties(node)?);
+
+        match node.tag_name().name() {
+            "layer" => spawn_tile_layer(builder, context, node, layer)?,
+            "objectgroup" => {
+                for object in node.children().filter(|node| node.has_tag_name("object")) {
+                    let object = parse_object(object, context.height)?;
+                    builder.spawn_object(
+                        layer,
+                        &object.name,
+                        &object.class,
+                        object.transform,
+                        object.properties,
+                        object.shape,
+                    );
+                }
+            }
+            _ => spawn_layers(builder, context, node, Some(layer))?,
+        }
+    }
+    Ok(())
+}
+
+fn spawn_tile_layer(
+    builder: &mut LevelBuilder,
+    context: &MapContext,
+    node: Node,
+    layer: Entity,
+) -> Result<(), TiledLoaderError> {
+    let name = node.attribute("name").unwrap_or_default();
+    let Some(data) = node.children().find(|node| node.has_tag_name("data")) else {
+        return Ok(());
+    };
+    let height: i32 = attribute(node, "height")?;
+
+    let mut storages: Vec<Option<TileStorage>> = context.tilesets.iter().map(|_| None).collect();
+    let mut colliders = Vec::new();
+    let mut place = |position: IVec2, gid: u32| {
+        let id = gid & GID_MASK;
+        let Some(tileset_index) = context
+            .tilesets
+            .iter()
+            .rposition(|tileset| tileset.first_gid <= id)
+        else {
+            log::warn!("Tile {id} of layer `{name}` doesn't belong to any tileset");
+            return;
+        };
+        let tileset = &context.tilesets[tileset_index];
+        let index = id - tileset.first_gid;
+        // Tiled rows go down, tilemap rows go up.
+        let position = IVec2::new(position.x, height - 1 - position.y);
+
+        let mut tile = Tile::new(index);
+        tile.flip_x = gid & FLIPPED_HORIZONTALLY != 0;
+        tile.flip_y = gid & FLIPPED_VERTICALLY != 0;
+        tile.flip_diagonal = gid & FLIPPED_DIAGONALLY != 0;
+        tile.animation = tileset.animations.get(&index).copied();
+        storages[tileset_index]
+            .get_or_insert_with(TileStorage::default)
+            .set(position, tile);
+
+        if let Some(shapes) = tileset.colliders.get(&index) {
+            let cell = position.as_vec2() * context.tile_size;
+            for (transform, shape) in shapes {
+                let mut transform = *transform;
+                transform.translation += cell.extend(0.0);
+                colliders.push((transform, shape.clone()));
+            }
+        }
+    };
+
+    let chunks = data
+        .children()
+        .filter(|node| node.has_tag_name("chunk"))
+        .collect::<Vec<_>>();
+    if chunks.is_empty() {
+        let width: i32 = attribute(node, "width")?;
+        for (index, gid) in decode_data(data, (width * height) as usize)?
+            .into_iter()
+            .enumerate()
+        {
+            if gid != 0 {
+                place(IVec2::new(index as i32 % width, index as i32 / width), gid);
+            }
+        }
+    } else {
+        // Infinite maps store their tiles in chunks.
+        for chunk in chunks {
+            let origin = IVec2::new(attribute(chunk, "x")?, attribute(chunk, "y")?);
+            let width: i32 = attribute(chunk, "width")?;
+            let len = (width * attribute::<i32>(chunk, "height")?) as usize;
+            for (index, gid) in decode_data_with(data, chunk, len)?.into_iter().enumerate() {
+                if gid != 0 {
+                    place(
+                        origin + IVec2::new(index as i32 % width, index as i32 / width),
+                        gid,
+                    );
+                }
+            }
+        }
+    }
+
+    for (tileset, storage) in context.tilesets.iter().zip(storages) {
+        let Some(storage) = storage else {
+            continue;
+        };
+        let tilemap = Tilemap {
+            tileset: tileset.image.clone(),
+            tileset_size: tileset.size,
+            tile_size: context.tile_size,
+        };
+        builder.spawn_tilemap(layer, &tileset.name, tilemap, storage);
+    }
+    for (transform, shape) in colliders {
+        builder.spawn_collider(layer, "Tile Collider", transform, shape);
+    }
+    Ok(())
+}
+
+/// Decodes the global tile ids of a `<data>` element.
+fn decode_data(data: Node, len: usize) -> Result<Vec<u32>, TiledLoaderError> {
+    decode_data_with(data, data, len)
+}
+
+/// Decodes the global tile ids of `node`, which is either a `<data>` element or one of its chunks.
+fn decode_data_with(data: Node, node: Node, len: usize) -> Result<Vec<u32>, TiledLoaderError> {
+    if let Some(compression) = data.attribute("compression") {
+        return Err(TiledLoaderError::Unsupported(format!(
+            "{compression} compressed tile layers are not supported, use CSV or uncompressed Base64"
+        )));
+    }
+    let text = node.text().unwrap_or_default();
+    let gids = match data.attribute("encoding") {
+        Some("csv") => text
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| invalid(format!("invalid tile `{value}`")))
+            })
+            .collect::<Result<Vec<u32>, _>>()?,
+        Some("base64") => BASE64_STANDARD
+            .decode(text.trim())
+            .map_err(|error| invalid(format!("invalid Base64 tile data: {error}")))?
+            .chunks_exact(4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect(),
+        Some(encoding) => {
+            return Err(TiledLoaderError::Unsupported(format!(
+                "{encoding} encoded tile layers are not supported"
+            )))
+        }
+        None => node
+            .children()
+            .filter(|node| node.has_tag_name("tile"))
+            .map(|tile| Ok(optional_attribute(tile, "gid")?.unwrap_or(0)))
+            .collect::<Result<Vec<u32>, TiledLoaderError>>()?,
+    };
+    if gids.len() != len {
+        return Err(invalid(format!(
+            "expected {len} tiles in layer data, found {}",
+            gids.len()
+        )));
+    }
+    Ok(gids)
+}
+
+struct Object {
+    name: String,
+    class: String,
+    transform: Transform,
+    properties: LevelProperties,
+    shape: Option<ColliderShape>,
+}
+
+/// Parses an object, positioned in a space of height `height` in pixels.
+fn parse_object(node: Node, height: f32) -> Result<Object, TiledLoaderError> {
+    let position = Vec2::new(attribute(node, "x")?, height - attribute::<f32>(node, "y")?);
+    let rotation: f32 = optional_attribute(node, "rotation")?.unwrap_or(0.0);
+    let size = Vec2::new(
+        optional_attribute(node, "width")?.unwrap_or(0.0),
+        optional_attribute(node, "height")?.unwrap_or(0.0),
+    );
+    // Shapes are anchored at their top-left corner.
+    let center = Vec2::new(size.x, -size.y) / 2.0;
+
+    let child = |name: &str| node.children().find(|node| node.has_tag_name(name));
+    let shape = if node.attribute("gid").is_some() || child("text").is_some() {
+        None
+    } else if child("ellipse").is_some() {
+        Some(ColliderShape::Ellipse {
+            center,
+            half_size: size / 2.0,
+        })
+    } else if child("point").is_some() {
+        Some(ColliderShape::Point(Vec2::ZERO))
+    } else if let Some(polygon) = child("polygon") {
+        Some(ColliderShape::Polygon(parse_points(polygon)?))
+    } else if let Some(polyline) = child("polyline") {
+        Some(ColliderShape::Polyline(parse_points(polyline)?))
+    } else if size.x > 0.0 && size.y > 0.0 {
+        Some(ColliderShape::Rectangle {
+            center,
+            half_size: size / 2.0,
+        })
+    } else {
+        Some(ColliderShape::Point(Vec2::ZERO))
+    };
+
+    Ok(Object {
+        name: node.attribute("name").unwrap_or_default().to_string(),
+        // `type` was renamed to `class` in Tiled 1.9.
+        class: node
+            .attribute("class")
+            .or_else(|| node.attribute("type"))
+            .unwrap_or_default()
+            .to_string(),
+        transform: Transform {
+            translation: Vec3::from((position, 0.0)),
+            // Tiled rotates clockwise.
+            rotation: Quat::from_rotation_z(-rotation.to_radians()),
+            ..Transform::default()
+        },
+        properties: parse_properties(node)?,
+        shape,
+    })
+}
+
+fn parse_points(node: Node) -> Result<Vec<Vec2>, TiledLoaderError> {
+    let points = node.attribute("points").unwrap_or_default();
+    points
+        .split_whitespace()
+        .map(|point| {
+            let (x, y) = point
+                .split_once(',')
+                .ok_or_else(|| invalid(format!("invalid point `{point}`")))?;
+            let x: f32 = x
+                .parse()
+                .map_err(|_| invalid(format!("invalid point `{point}`")))?;
+            let y: f32 = y
+                .parse()
+                .map_err(|_| invalid(format!("invalid point `{point}`")))?;
+            Ok(Vec2::new(x, -y))
+        })
+        .collect()
+}
+
+fn parse_properties(node: Node) -> Result<LevelProperties, TiledLoaderError> {
+    let mut properties = LevelProperties::default();
+    let Some(list) = node.children().find(|node| node.has_tag_name("properties")) else {
+        return Ok(properties);
+    };
+    for property in list.children().filter(|node| node.has_tag_name("property")) {
+        let name = property
+            .attribute("name")
+            .ok_or_else(|| invalid("property without a name"))?;
+        // Multiline strings are stored as text.
+        let value = property
+            .attribute("value")
+            .or_else(|| property.text())
+            .unwrap_or_default();
+        let invalid_value = || invalid(format!("invalid value `{value}` for property `{name}`"));
+        let value = match property.attribute("type").unwrap_or("string") {
+            "bool" => LevelProperty::Bool(value.parse().map_err(|_| invalid_value())?),
+            "int" | "object" => LevelProperty::Int(value.parse().map_err(|_| invalid_value())?),
+            "float" => LevelProperty::Float(value.parse().map_err(|_| invalid_value())?),
+            "color" if value.is_empty() => continue,
+            "color" => LevelProperty::Color(parse_color(value).ok_or_else(invalid_value)?),
+            "class" => {
+                log::warn!("Class property `{name}` is not supported");
+                continue;
+            }
+            _ => LevelProperty::String(value.to_string()),
+        };
+        properties.0.insert(name.to_string(), value);
+    }
+    Ok(properties)
+}
+
+fn attribute<T: core::str::FromStr>(node: Node, name: &str) -> Result<T, TiledLoaderError> {
+    optional_attribute(node, name)?.ok_or_else(|| {
+        invalid(format!(
+            "missing attribute `{name}` on `{}`",
+            node.tag_name().name()
+        ))
+    })
+}
+
+fn optional_attribute<T: core::str::FromStr>(
+    node: Node,
+    name: &str,
+) -> Result<Option<T>, TiledLoaderError> {
+    node.attribute(name)
+        .map(|value| {
+            value.parse().map_err(|_| {
+                invalid(format!(
+                    "invalid attribute `{name}` on `{}`: `{value}`",
+                    node.tag_name().name()
+                ))
+            })
+        })
+        .transpose()
+}
+
+fn invalid(message: impl Into<String>) -> TiledLoaderError {
+    TiledLoaderError::Invalid(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LevelCollider, LevelObject};
+    use bevy_ecs::{hierarchy::ChildOf, name::Name};
+
+    const MAP: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="3" height="2" tilewidth="16" tileheight="16">
+ <tileset firstgid="1" name="terrain" tilewidth="16" tileheight="16" tilecount="4" columns="2">
+  <image source="terrain.png" width="32" height="32"/>
+  <tile id="2">
+   <objectgroup>
+    <object id="1" x="0" y="8" width="16" height="8"/>
+   </objectgroup>
+  </tile>
+ </tileset>
+ <layer id="1" name="ground" width="3" height="2">
+  <data encoding="csv">
+1,0,2147483650,
+3,0,0
+</data>
+ </layer>
+ <objectgroup id="2" name="entities">
+  <object id="2" name="door" type="Door" x="16" y="8" width="16" height="8" rotation="90">
+   <properties>
+    <property name="locked" type="bool" value="true"/>
+   </properties>
+  </object>
+ </objectgroup>
+</map>"#;
+
+    #[test]
+    fn load_map() {
+        let registry = TypeRegistry::new();
+        let document = Document::parse(MAP).unwrap();
+        let mut scene = build_map(
+            &document,
+            &HashMap::default(),
+            &mut |_| Ok(Handle::default()),
+            &registry,
+        )
+        .unwrap();
+        let world = &mut scene.world;
+
+        let mut tilemaps = world.query::<(&Tilemap, &TileStorage)>();
+        let (tilemap, storage) = tilemaps.single(world).unwrap();
+        assert_eq!(tilemap.tileset_size, UVec2::new(2, 2));
+        assert_eq!(storage.len(), 3);
+        assert_eq!(storage.get(IVec2::new(0, 1)), Some(&Tile::new(0)));
+        let flipped = storage.get(IVec2::new(2, 1)).unwrap();
+        assert_eq!((flipped.index, flipped.flip_x), (1, true));
+        assert_eq!(
+            storage.get(IVec2::new(0, 0)).map(|tile| tile.index),
+            Some(2)
+        );
+
+        let mut colliders = world.query::<(&Name, &LevelCollider, &Transform)>();
+        let (_, collider, transform) = colliders
+            .iter(world)
+            .find(|(name, ..)| name.as_str() == "Tile Collider")
+            .unwrap();
+        assert_eq!(transform.translation, Vec3::new(0.0, 8.0, 0.0));
+        assert_eq!(
+            collider.0,
+            ColliderShape::Rectangle {
+                center: Vec2::new(8.0, -4.0),
+                half_size: Vec2::new(8.0, 4.0),
+            }
+        );
+
+        let mut objects = world.query::<(&LevelObject, &LevelProperties, &Transform, &ChildOf)>();
+        let (object, properties, transform, child_of) = objects.single(world).unwrap();
+        assert_eq!(object.class, "Door");
+        assert_eq!(properties.get("locked"), Some(&LevelProperty::Bool(true)));
+        assert_eq!(transform.translation, Vec3::new(16.0, 24.0, 0.0));
+        let layer = world.get::<Transform>(child_of.parent).unwrap();
+        assert_eq!(layer.translation.z, 1.0);
+    }
+}
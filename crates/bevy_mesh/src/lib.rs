@@ -8,6 +8,7 @@ mod index;
 mod mesh;
 mod mikktspace;
 pub mod morph;
+mod path;
 pub mod primitives;
 pub mod skinning;
 mod vertex;
@@ -15,6 +16,7 @@ use bitflags::bitflags;
 pub use index::*;
 pub use mesh::*;
 pub use mikktspace::*;
+pub use path::*;
 pub use primitives::*;
 pub use vertex::*;
 pub use wgpu_types::VertexFormat;
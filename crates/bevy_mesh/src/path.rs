@@ -0,0 +1,563 @@
+use core::f32::consts::FRAC_PI_2;
+
+use crate::{
+    primitives::{MeshBuilder, Meshable},
+    Indices, Mesh,
+};
+use bevy_asset::RenderAssetUsages;
+use bevy_math::{ops, Rect, Rot2, Vec2};
+use bevy_reflect::prelude::*;
+use lyon_tessellation::{
+    math::point, path::Path, BuffersBuilder, FillOptions, FillTessellator, FillVertex, LineCap,
+    LineJoin, StrokeOptions, StrokeTessellator, StrokeVertex, VertexBuffers,
+};
+use tracing::warn;
+use wgpu_types::PrimitiveTopology;
+
+/// A 2D vector path made of lines, Bézier curves and arcs, like the paths of SVG, that can be
+/// filled or stroked into a [`Mesh`] that stays crisp at any resolution.
+///
+/// Paths are built from commands, starting at the origin. Each [`move_to`](Self::move_to) starts
+/// a new subpath, and [`close`](Self::close) connects the current subpath back to its start.
+///
+/// ```
+/// # use bevy_asset::Assets;
+/// # use bevy_ecs::prelude::ResMut;
+/// # use bevy_math::Vec2;
+/// # use bevy_mesh::*;
+/// # use core::f32::consts::PI;
+/// #
+/// # fn setup(mut meshes: ResMut<Assets<Mesh>>) {
+/// let heart = VectorPath::new()
+///     .move_to(Vec2::new(0.0, -40.0))
+///     .line_to(Vec2::new(-40.0, 0.0))
+///     .arc(Vec2::new(-20.0, 0.0), Vec2::splat(20.0), -PI, 0.0)
+///     .arc(Vec2::new(20.0, 0.0), Vec2::splat(20.0), -PI, 0.0)
+///     .close();
+///
+/// let fill = meshes.add(heart.fill());
+/// let outline = meshes.add(heart.stroke(PathStroke::new(4.0)));
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub struct VectorPath {
+    commands: Vec<PathCommand>,
+    /// The end of the last command.
+    current: Vec2,
+    /// The start of the current subpath.
+    start: Vec2,
+    /// Whether a subpath is started and not closed.
+    open: bool,
+}
+
+/// A command of a [`VectorPath`].
+///
+/// Arcs are converted to cubic Bézier curves.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub enum PathCommand {
+    /// Starts a new subpath at the given point.
+    MoveTo(Vec2),
+    /// A straight line to the given point.
+    LineTo(Vec2),
+    /// A quadratic Bézier curve.
+    QuadraticTo {
+        /// The control point.
+        control: Vec2,
+        /// The end of the curve.
+        to: Vec2,
+    },
+    /// A cubic Bézier curve.
+    CubicTo {
+        /// The first control point.
+        control1: Vec2,
+        /// The second control point.
+        control2: Vec2,
+        /// The end of the curve.
+        to: Vec2,
+    },
+    /// Closes the current subpath with a straight line to its start.
+    Close,
+}
+
+impl VectorPath {
+    /// Creates an empty path.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the commands of the path.
+    pub fn commands(&self) -> &[PathCommand] {
+        &self.commands
+    }
+
+    /// Returns the end of the path, where the next command starts.
+    pub fn current_point(&self) -> Vec2 {
+        self.current
+    }
+
+    /// Starts a new subpath at `to`.
+    pub fn move_to(mut self, to: Vec2) -> Self {
+        self.commands.push(PathCommand::MoveTo(to));
+        self.current = to;
+        self.start = to;
+        self.open = true;
+        self
+    }
+
+    /// Adds a straight line to `to`.
+    pub fn line_to(mut self, to: Vec2) -> Self {
+        self.begin();
+        self.commands.push(PathCommand::LineTo(to));
+        self.current = to;
+        self
+    }
+
+    /// Adds a quadratic Bézier curve to `to`.
+    pub fn quadratic_bezier_to(mut self, control: Vec2, to: Vec2) -> Self {
+        self.begin();
+        self.commands.push(PathCommand::QuadraticTo { control, to });
+        self.current = to;
+        self
+    }
+
+    /// Adds a cubic Bézier curve to `to`.
+    pub fn cubic_bezier_to(mut self, control1: Vec2, control2: Vec2, to: Vec2) -> Self {
+        self.begin();
+        self.commands.push(PathCommand::CubicTo {
+            control1,
+            control2,
+            to,
+        });
+        self.current = to;
+        self
+    }
+
+    /// Adds an elliptical arc around `center`, starting at the angle of the current point and
+    /// turning by `sweep_angle` radians, counterclockwise if positive.
+    ///
+    /// The ellipse has the given `radii` along its axes, rotated by `x_rotation` radians. If the
+    /// current point isn't on the ellipse, the arc starts where the ellipse crosses the line from
+    /// the `center` to the current point, and a line is added to it.
+    pub fn arc(mut self, center: Vec2, radii: Vec2, sweep_angle: f32, x_rotation: f32) -> Self {
+        if radii.x == 0.0 || radii.y == 0.0 || sweep_angle == 0.0 {
+            return self;
+        }
+        let rotation = Rot2::radians(x_rotation);
+        let point_at = |angle: f32| {
+            let (sin, cos) = ops::sin_cos(angle);
+            center + rotation * (Vec2::new(cos, sin) * radii)
+        };
+        let tangent_at = |angle: f32| {
+            let (sin, cos) = ops::sin_cos(angle);
+            rotation * (Vec2::new(-sin, cos) * radii)
+        };
+
+        let local = rotation.inverse() * (self.current - center) / radii;
+        let start_angle = ops::atan2(local.y, local.x);
+        let start = point_at(start_angle);
+        if !self.open {
+            self = self.move_to(start);
+        } else if start.distance_squared(self.current) > f32::EPSILON {
+            self = self.line_to(start);
+        }
+
+        // Each quarter of an ellipse is close to a cubic Bézier curve.
+        let segments = ops::ceil(sweep_angle.abs() / FRAC_PI_2).max(1.0) as u32;
+        let step = sweep_angle / segments as f32;
+        let handle = 4.0 / 3.0 * ops::tan(step / 4.0);
+        for segment in 0..segments {
+            let from = start_angle + step * segment as f32;
+            let to = from + step;
+            self = self.cubic_bezier_to(
+                point_at(from) + tangent_at(from) * handle,
+                point_at(to) - tangent_at(to) * handle,
+                point_at(to),
+            );
+        }
+        self
+    }
+
+    /// Closes the current subpath with a straight line to its start.
+    pub fn close(mut self) -> Self {
+        if self.open {
+            self.commands.push(PathCommand::Close);
+            self.current = self.start;
+            self.open = false;
+        }
+        self
+    }
+
+    /// Creates a [`VectorPathMeshBuilder`] filling the path, with the [`PathFillRule::NonZero`].
+    pub fn fill(&self) -> VectorPathMeshBuilder {
+        VectorPathMeshBuilder {
+            path: self.clone(),
+            mode: PathMeshMode::Fill(PathFillRule::NonZero),
+            tolerance: VectorPathMeshBuilder::DEFAULT_TOLERANCE,
+        }
+    }
+
+    /// Creates a [`VectorPathMeshBuilder`] stroking the path.
+    pub fn stroke(&self, stroke: PathStroke) -> VectorPathMeshBuilder {
+        VectorPathMeshBuilder {
+            path: self.clone(),
+            mode: PathMeshMode::Stroke(stroke),
+            tolerance: VectorPathMeshBuilder::DEFAULT_TOLERANCE,
+        }
+    }
+
+    /// Starts a subpath at the current point if there is none, as SVG does after a close.
+    fn begin(&mut self) {
+        if !self.open {
+            self.commands.push(PathCommand::MoveTo(self.current));
+            self.start = self.current;
+            self.open = true;
+        }
+    }
+
+    fn to_lyon(&self) -> Path {
+        let to_point = |p: Vec2| point(p.x, p.y);
+        let mut builder = Path::builder();
+        let mut open = false;
+        for command in &self.commands {
+            match *command {
+                PathCommand::MoveTo(to) => {
+                    if open {
+                        builder.end(false);
+                    }
+                    builder.begin(to_point(to));
+                    open = true;
+                }
+                PathCommand::LineTo(to) => {
+                    builder.line_to(to_point(to));
+                }
+                PathCommand::QuadraticTo { control, to } => {
+                    builder.quadratic_bezier_to(to_point(control), to_point(to));
+                }
+                PathCommand::CubicTo {
+                    control1,
+                    control2,
+                    to,
+                } => {
+                    builder.cubic_bezier_to(to_point(control1), to_point(control2), to_point(to));
+                }
+                PathCommand::Close => {
+                    builder.end(true);
+                    open = false;
+                }
+            }
+        }
+        if open {
+            builder.end(false);
+        }
+        builder.build()
+    }
+}
+
+/// How the inside of a [`VectorPath`] is decided where its subpaths overlap or intersect.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub enum PathFillRule {
+    /// Points are inside if the path winds around them at least once, in either direction.
+    #[default]
+    NonZero,
+    /// Points are inside if the path crosses an odd number of times between them and infinity,
+    /// so that nested subpaths make holes.
+    EvenOdd,
+}
+
+/// How the ends of the open subpaths of a stroked [`VectorPath`] are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub enum PathLineCap {
+    /// The stroke stops at the end of the path.
+    #[default]
+    Butt,
+    /// The stroke is extended by half its width past the end of the path.
+    Square,
+    /// The stroke ends with a half circle.
+    Round,
+}
+
+/// How the corners of a stroked [`VectorPath`] are drawn.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub enum PathLineJoin {
+    /// Sharp corners, beveled when longer than the [`miter_limit`](PathStroke::miter_limit).
+    #[default]
+    Miter,
+    /// Rounded corners.
+    Round,
+    /// Cut off corners.
+    Bevel,
+}
+
+/// The style of the stroke of a [`VectorPath`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub struct PathStroke {
+    /// The width of the stroke, centered on the path.
+    /// The default is `1.0`.
+    pub width: f32,
+    /// The ends of the open subpaths.
+    pub line_cap: PathLineCap,
+    /// The corners.
+    pub line_join: PathLineJoin,
+    /// The maximum ratio between the length of a miter and the width of the stroke.
+    /// The default is `4.0`, as in SVG.
+    pub miter_limit: f32,
+}
+
+impl Default for PathStroke {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            line_cap: PathLineCap::Butt,
+            line_join: PathLineJoin::Miter,
+            miter_limit: 4.0,
+        }
+    }
+}
+
+impl PathStroke {
+    /// Creates a [`PathStroke`] with the given width.
+    #[inline]
+    pub fn new(width: f32) -> Self {
+        Self {
+            width,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the ends of the open subpaths.
+    #[inline]
+    pub const fn line_cap(mut self, line_cap: PathLineCap) -> Self {
+        self.line_cap = line_cap;
+        self
+    }
+
+    /// Sets the corners.
+    #[inline]
+    pub const fn line_join(mut self, line_join: PathLineJoin) -> Self {
+        self.line_join = line_join;
+        self
+    }
+}
+
+/// Whether a [`VectorPathMeshBuilder`] fills or strokes its path.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub enum PathMeshMode {
+    /// Fills the inside of the path.
+    Fill(PathFillRule),
+    /// Draws a stroke along the path.
+    Stroke(PathStroke),
+}
+
+/// A builder used for creating a [`Mesh`] by filling or stroking a [`VectorPath`].
+///
+/// The mesh is in the XY plane, facing +Z, with UVs mapped to the bounding box of its vertices
+/// like a [`Rectangle`](bevy_math::primitives::Rectangle): (0, 0) at the top left and (1, 1) at
+/// the bottom right.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub struct VectorPathMeshBuilder {
+    /// The path.
+    pub path: VectorPath,
+    /// Whether the path is filled or stroked.
+    pub mode: PathMeshMode,
+    /// The maximum distance between the curves of the path and the edges of the mesh.
+    /// The default is `0.1`, suited to paths in pixels.
+    pub tolerance: f32,
+}
+
+impl VectorPathMeshBuilder {
+    /// The default [`tolerance`](Self::tolerance).
+    pub const DEFAULT_TOLERANCE: f32 = 0.1;
+
+    /// Sets the fill rule, filling the path.
+    #[inline]
+    pub const fn fill_rule(mut self, fill_rule: PathFillRule) -> Self {
+        self.mode = PathMeshMode::Fill(fill_rule);
+        self
+    }
+
+    /// Sets the maximum distance between the curves of the path and the edges of the mesh.
+    #[inline]
+    pub const fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl MeshBuilder for VectorPathMeshBuilder {
+    fn build(&self) -> Mesh {
+        let path = self.path.to_lyon();
+        let mut buffers: VertexBuffers<Vec2, u32> = VertexBuffers::new();
+        let result = match self.mode {
+            PathMeshMode::Fill(fill_rule) => {
+                let options =
+                    FillOptions::tolerance(self.tolerance).with_fill_rule(match fill_rule {
+                        PathFillRule::NonZero => lyon_tessellation::FillRule::NonZero,
+                        PathFillRule::EvenOdd => lyon_tessellation::FillRule::EvenOdd,
+                    });
+                FillTessellator::new().tessellate_path(
+                    &path,
+                    &options,
+                    &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| {
+                        Vec2::new(vertex.position().x, vertex.position().y)
+                    }),
+                )
+            }
+            PathMeshMode::Stroke(stroke) => {
+                let options = StrokeOptions::tolerance(self.tolerance)
+                    .with_line_width(stroke.width)
+                    .with_line_cap(match stroke.line_cap {
+                        PathLineCap::Butt => LineCap::Butt,
+                        PathLineCap::Square => LineCap::Square,
+                        PathLineCap::Round => LineCap::Round,
+                    })
+                    .with_line_join(match stroke.line_join {
+                        PathLineJoin::Miter => LineJoin::Miter,
+                        PathLineJoin::Round => LineJoin::Round,
+                        PathLineJoin::Bevel => LineJoin::Bevel,
+                    })
+                    .with_miter_limit(stroke.miter_limit.max(StrokeOptions::MINIMUM_MITER_LIMIT));
+                StrokeTessellator::new().tessellate_path(
+                    &path,
+                    &options,
+                    &mut BuffersBuilder::new(&mut buffers, |vertex: StrokeVertex| {
+                        Vec2::new(vertex.position().x, vertex.position().y)
+                    }),
+                )
+            }
+        };
+        if let Err(err) = result {
+            warn!("Failed to tessellate a vector path: {err:?}");
+            buffers = VertexBuffers::new();
+        }
+
+        let bounds = buffers
+            .vertices
+            .iter()
+            .fold(Rect::EMPTY, |bounds, &vertex| bounds.union_point(vertex));
+        let size = bounds.size().max(Vec2::splat(f32::EPSILON));
+        let positions: Vec<[f32; 3]> = buffers.vertices.iter().map(|v| [v.x, v.y, 0.0]).collect();
+        let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+        let uvs: Vec<[f32; 2]> = buffers
+            .vertices
+            .iter()
+            .map(|v| [(v.x - bounds.min.x) / size.x, (bounds.max.y - v.y) / size.y])
+            .collect();
+
+        // lyon outputs clockwise triangles, in a Y-up frame.
+        let mut indices = buffers.indices;
+        for triangle in indices.chunks_exact_mut(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| buffers.vertices[triangle[i] as usize]);
+            if (b - a).perp_dot(c - a) < 0.0 {
+                triangle.swap(1, 2);
+            }
+        }
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices))
+    }
+}
+
+impl Meshable for VectorPath {
+    type Output = VectorPathMeshBuilder;
+
+    fn mesh(&self) -> Self::Output {
+        self.fill()
+    }
+}
+
+impl From<VectorPath> for Mesh {
+    fn from(path: VectorPath) -> Self {
+        path.fill().build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VertexAttributeValues;
+    use core::f32::consts::{PI, TAU};
+
+    /// Returns the total area of the triangles of a mesh, positive if they are counterclockwise.
+    fn area(mesh: &Mesh) -> f32 {
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("missing positions");
+        };
+        let Some(Indices::U32(indices)) = mesh.indices() else {
+            panic!("missing indices");
+        };
+        indices
+            .chunks_exact(3)
+            .map(|triangle| {
+                let [a, b, c] =
+                    [0, 1, 2].map(|i| Vec2::from_slice(&positions[triangle[i] as usize]));
+                (b - a).perp_dot(c - a) / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn fill_and_stroke() {
+        let square = VectorPath::new()
+            .move_to(Vec2::ZERO)
+            .line_to(Vec2::new(2.0, 0.0))
+            .line_to(Vec2::new(2.0, 2.0))
+            .line_to(Vec2::new(0.0, 2.0))
+            .close();
+        assert!((area(&square.fill().build()) - 4.0).abs() < 1e-4);
+
+        // The stroke is centered on the edges.
+        let outline = square.stroke(PathStroke::new(0.2).line_join(PathLineJoin::Miter));
+        assert!((area(&outline.build()) - (2.2 * 2.2 - 1.8 * 1.8)).abs() < 1e-3);
+
+        // A nested square makes a hole with the even-odd rule.
+        let nested = square
+            .move_to(Vec2::splat(0.5))
+            .line_to(Vec2::new(1.5, 0.5))
+            .line_to(Vec2::splat(1.5))
+            .line_to(Vec2::new(0.5, 1.5))
+            .close();
+        assert!((area(&nested.fill().build()) - 4.0).abs() < 1e-4);
+        let with_hole = nested.fill().fill_rule(PathFillRule::EvenOdd).build();
+        assert!((area(&with_hole) - 3.0).abs() < 1e-4);
+
+        assert_eq!(area(&VectorPath::new().fill().build()), 0.0);
+    }
+
+    #[test]
+    fn arcs() {
+        let circle = VectorPath::new()
+            .move_to(Vec2::new(3.0, 1.0))
+            .arc(Vec2::new(1.0, 1.0), Vec2::splat(2.0), TAU, 0.0)
+            .close();
+        assert!(circle
+            .current_point()
+            .abs_diff_eq(Vec2::new(3.0, 1.0), 1e-5));
+        let mesh = circle.fill().tolerance(0.001).build();
+        assert!((area(&mesh) - PI * 4.0).abs() < 0.05);
+
+        // Arcs start at the angle of the current point.
+        let half = VectorPath::new().move_to(Vec2::new(0.0, 1.0)).arc(
+            Vec2::ZERO,
+            Vec2::new(2.0, 1.0),
+            PI,
+            0.0,
+        );
+        assert!(half.current_point().abs_diff_eq(Vec2::new(0.0, -1.0), 1e-5));
+    }
+}
@@ -0,0 +1,268 @@
+use crate::{NavMesh, NavMeshSettings};
+use alloc::{sync::Arc, vec::Vec};
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec3;
+use bevy_platform_support::collections::HashSet;
+use bevy_reflect::prelude::*;
+use bevy_render::mesh::{Mesh, Mesh3d, PrimitiveTopology, VertexAttributeValues};
+use bevy_tasks::{futures::check_ready, AsyncComputeTaskPool, Task};
+use bevy_time::Time;
+use bevy_transform::components::{GlobalTransform, Transform};
+
+/// An entity finding its way along a [`NavMesh`].
+///
+/// Whenever the agent changes, a path from its current position to its
+/// [`target`](NavAgent::target) is requested. Paths are solved asynchronously on the
+/// [`AsyncComputeTaskPool`], and written to the [`NavPath`] of the agent once found.
+/// Paths are also requested again when the navigation mesh is modified, for example after
+/// being baked again.
+///
+/// Agents move along their path by themselves if their [`speed`](NavAgent::speed) is positive.
+/// Otherwise, the [`NavPath`] can be used to drive a character controller.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Debug, Default, Clone)]
+#[require(Transform, NavPath)]
+pub struct NavAgent {
+    /// The navigation mesh the agent walks on.
+    pub navmesh: Handle<NavMesh>,
+    /// The position the agent is going to, if any.
+    pub target: Option<Vec3>,
+    /// The speed at which the agent moves along its path, in units per second.
+    ///
+    /// The agent moves its [`Transform`], so it shouldn't have a parent.
+    /// If zero, the agent doesn't move by itself.
+    pub speed: f32,
+}
+
+impl NavAgent {
+    /// Creates an agent walking on `navmesh`, without a target.
+    pub fn new(navmesh: Handle<NavMesh>) -> Self {
+        Self {
+            navmesh,
+            target: None,
+            speed: 0.0,
+        }
+    }
+}
+
+/// The path of a [`NavAgent`] to its target.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Debug, Default, Clone)]
+pub struct NavPath {
+    /// The remaining points of the path, from the next one to the target.
+    pub waypoints: Vec<Vec3>,
+    /// The status of the last path request.
+    pub status: NavPathStatus,
+}
+
+/// The status of the path request of a [`NavAgent`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Clone, Debug, Default, PartialEq)]
+pub enum NavPathStatus {
+    /// The agent has no target.
+    #[default]
+    Idle,
+    /// The path is being solved, or the navigation mesh isn't loaded yet.
+    Pending,
+    /// A path was found.
+    Found,
+    /// The target can't be reached.
+    NotFound,
+}
+
+/// A path being solved for an agent.
+#[derive(Component)]
+pub(crate) struct NavPathTask(Task<Option<Vec<Vec3>>>);
+
+/// Marks an entity with a [`Mesh3d`] as level geometry to bake [`NavMesh`]es from.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Debug, Default, Clone)]
+pub struct NavMeshSource;
+
+/// Bakes a [`NavMesh`] from the meshes of all entities with a [`NavMeshSource`].
+///
+/// The navigation mesh is baked on the [`AsyncComputeTaskPool`], and inserted into
+/// [`Assets<NavMesh>`] once baked, replacing the previous navigation mesh if any.
+/// A handle can be reserved beforehand with [`Assets::reserve_handle`].
+///
+/// Meshes that aren't loaded yet when the event is read are ignored.
+#[derive(Event, Clone, Debug)]
+pub struct BakeNavMesh {
+    /// The handle the baked navigation mesh is inserted with.
+    pub navmesh: Handle<NavMesh>,
+    /// The settings used to bake the navigation mesh.
+    pub settings: NavMeshSettings,
+}
+
+/// The navigation meshes being baked.
+#[derive(Resource, Default)]
+pub(crate) struct NavMeshBakeTasks(Vec<(Handle<NavMesh>, Task<NavMesh>)>);
+
+/// Starts baking the navigation meshes requested with [`BakeNavMesh`].
+pub(crate) fn start_navmesh_bakes(
+    mut events: EventReader<BakeNavMesh>,
+    sources: Query<(&Mesh3d, &GlobalTransform), With<NavMeshSource>>,
+    meshes: Res<Assets<Mesh>>,
+    mut tasks: ResMut<NavMeshBakeTasks>,
+) {
+    if events.is_empty() {
+        return;
+    }
+
+    let mut triangles = Vec::new();
+    for (Mesh3d(handle), transform) in &sources {
+        let Some(mesh) = meshes.get(handle) else {
+            log::warn!("A navigation mesh source isn't loaded, and will be ignored");
+            continue;
+        };
+        append_triangles(mesh, transform, &mut triangles);
+    }
+    let triangles: Arc<[[Vec3; 3]]> = triangles.into();
+
+    for event in events.read() {
+        let triangles = triangles.clone();
+        let settings = event.settings.clone();
+        let task =
+            AsyncComputeTaskPool::get().spawn(async move { NavMesh::bake(&triangles, &settings) });
+        tasks.0.push((event.navmesh.clone(), task));
+    }
+}
+
+/// Inserts the navigation meshes that have been baked.
+pub(crate) fn finish_navmesh_bakes(
+    mut tasks: ResMut<NavMeshBakeTasks>,
+    mut navmeshes: ResMut<Assets<NavMesh>>,
+) {
+    tasks.0.retain_mut(|(handle, task)| {
+        let Some(navmesh) = check_ready(task) else {
+            return true;
+        };
+        navmeshes.insert(handle.id(), navmesh);
+        false
+    });
+}
+
+/// Appends the triangles of `mesh`, transformed to world space.
+fn append_triangles(mesh: &Mesh, transform: &GlobalTransform, triangles: &mut Vec<[Vec3; 3]>) {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        log::warn!("Only triangle lists can be used as navigation mesh sources");
+        return;
+    }
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        return;
+    };
+    let vertex = |index: usize| transform.transform_point(Vec3::from(positions[index]));
+    match mesh.indices() {
+        Some(indices) => {
+            let indices = indices.iter().collect::<Vec<_>>();
+            triangles.extend(indices.chunks_exact(3).map(|triangle| {
+                [
+                    vertex(triangle[0]),
+                    vertex(triangle[1]),
+                    vertex(triangle[2]),
+                ]
+            }));
+        }
+        None => triangles.extend((0..positions.len() / 3).map(|triangle| {
+            [
+                vertex(triangle * 3),
+                vertex(triangle * 3 + 1),
+                vertex(triangle * 3 + 2),
+            ]
+        })),
+    }
+}
+
+/// Requests a new path for the [`NavAgent`]s that changed, or whose [`NavMesh`] changed.
+pub(crate) fn request_nav_paths(
+    mut commands: Commands,
+    mut agents: Query<(Entity, Ref<NavAgent>, &GlobalTransform, &mut NavPath)>,
+    mut events: EventReader<AssetEvent<NavMesh>>,
+    navmeshes: Res<Assets<NavMesh>>,
+) {
+    let changed = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    for (entity, agent, transform, mut path) in &mut agents {
+        if !agent.is_changed() && !changed.contains(&agent.navmesh.id()) {
+            continue;
+        }
+
+        let Some(target) = agent.target else {
+            commands.entity(entity).remove::<NavPathTask>();
+            path.waypoints.clear();
+            path.status = NavPathStatus::Idle;
+            continue;
+        };
+        path.status = NavPathStatus::Pending;
+        // The path is requested again once the navigation mesh is added.
+        let Some(navmesh) = navmeshes.get(&agent.navmesh) else {
+            continue;
+        };
+
+        let navmesh = navmesh.clone();
+        let start = transform.translation();
+        let task =
+            AsyncComputeTaskPool::get().spawn(async move { navmesh.find_path(start, target) });
+        commands.entity(entity).insert(NavPathTask(task));
+    }
+}
+
+/// Writes the paths that have been solved to the [`NavPath`] of their agent.
+pub(crate) fn poll_nav_paths(
+    mut commands: Commands,
+    mut agents: Query<(Entity, &mut NavPathTask, &mut NavPath)>,
+) {
+    for (entity, mut task, mut path) in &mut agents {
+        let Some(waypoints) = check_ready(&mut task.0) else {
+            continue;
+        };
+        commands.entity(entity).remove::<NavPathTask>();
+        match waypoints {
+            Some(waypoints) => {
+                path.waypoints = waypoints;
+                path.status = NavPathStatus::Found;
+            }
+            None => {
+                path.waypoints.clear();
+                path.status = NavPathStatus::NotFound;
+            }
+        }
+    }
+}
+
+/// Moves the [`NavAgent`]s with a positive speed along their path.
+pub(crate) fn follow_nav_paths(
+    time: Res<Time>,
+    mut agents: Query<(&NavAgent, &mut NavPath, &mut Transform)>,
+) {
+    for (agent, mut path, mut transform) in &mut agents {
+        if agent.speed <= 0.0 || path.waypoints.is_empty() {
+            continue;
+        }
+        let mut step = agent.speed * time.delta_secs();
+        while step > 0.0 {
+            let Some(&next) = path.waypoints.first() else {
+                break;
+            };
+            let offset = next - transform.translation;
+            let distance = offset.length();
+            if distance <= step {
+                transform.translation = next;
+                path.waypoints.remove(0);
+                step -= distance;
+            } else {
+                transform.translation += offset / distance * step;
+                step = 0.0;
+            }
+        }
+    }
+}
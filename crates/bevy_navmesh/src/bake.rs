@@ -0,0 +1,527 @@
+use crate::{NavMesh, NavPolygon, NavPortal};
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use bevy_math::{ops, Vec2, Vec3, Vec3Swizzles};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+/// Settings used to bake a [`NavMesh`].
+///
+/// The level geometry is sampled in columns of [`cell_size`](Self::cell_size) on the XZ plane:
+/// smaller cells make the navigation mesh follow the geometry more closely, at the cost of longer
+/// baking times.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, PartialEq, Default)]
+pub struct NavMeshSettings {
+    /// The width and depth of the columns the geometry is sampled in.
+    ///
+    /// Defaults to `0.25`.
+    pub cell_size: f32,
+    /// The vertical tolerance under which overlapping surfaces are merged.
+    ///
+    /// Defaults to `0.1`.
+    pub cell_height: f32,
+    /// The radius of the agents: the navigation mesh is kept this far from walls and ledges.
+    ///
+    /// Defaults to `0.4`.
+    pub agent_radius: f32,
+    /// The height of the agents: surfaces with less free space above them are not walkable.
+    ///
+    /// Defaults to `1.8`.
+    pub agent_height: f32,
+    /// The largest step up or down that agents can climb.
+    ///
+    /// Defaults to `0.4`.
+    pub max_climb: f32,
+    /// The steepest slope that agents can walk on, in radians.
+    ///
+    /// Defaults to 45 degrees.
+    pub max_slope: f32,
+}
+
+impl Default for NavMeshSettings {
+    fn default() -> Self {
+        Self {
+            cell_size: 0.25,
+            cell_height: 0.1,
+            agent_radius: 0.4,
+            agent_height: 1.8,
+            max_climb: 0.4,
+            max_slope: core::f32::consts::FRAC_PI_4,
+        }
+    }
+}
+
+const WEST: usize = 0;
+const SOUTH: usize = 1;
+const EAST: usize = 2;
+const NORTH: usize = 3;
+const OFFSETS: [(isize, isize); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
+
+/// A vertical range of solid geometry in a column.
+#[derive(Clone, Copy)]
+struct Span {
+    min: f32,
+    max: f32,
+    walkable: bool,
+}
+
+/// A walkable surface in a column, with enough free space above it for an agent.
+struct Node {
+    cell: (usize, usize),
+    floor: f32,
+    ceiling: f32,
+    links: [Option<usize>; 4],
+}
+
+/// A rectangle of nodes merged into a polygon.
+struct Rectangle {
+    origin: (usize, usize),
+    /// The nodes of the rectangle, row by row.
+    rows: Vec<Vec<usize>>,
+}
+
+impl NavMesh {
+    /// Bakes a navigation mesh from the triangles of the level geometry, in world space.
+    ///
+    /// The geometry is voxelized into columns of solid spans. The top of each span that isn't too
+    /// steep and has enough free space above it is walkable, and connected to the walkable
+    /// surfaces of the neighboring columns that are within [`NavMeshSettings::max_climb`].
+    /// Walkable surfaces too close to a wall or ledge are then removed, and the remaining ones are
+    /// merged into rectangular polygons.
+    ///
+    /// Walkable surfaces are found on every floor of the geometry, so multi-story levels are supported.
+    pub fn bake(triangles: &[[Vec3; 3]], settings: &NavMeshSettings) -> NavMesh {
+        let Some((min, max)) =
+            triangles
+                .iter()
+                .flatten()
+                .fold(None, |bounds: Option<(Vec3, Vec3)>, &vertex| {
+                    Some(bounds.map_or((vertex, vertex), |(min, max)| {
+                        (min.min(vertex), max.max(vertex))
+                    }))
+                })
+        else {
+            return NavMesh::default();
+        };
+
+        let cell_size = settings.cell_size.max(f32::EPSILON);
+        let origin = min.xz();
+        let size = ((max.xz() - origin) / cell_size).ceil().max(Vec2::ONE);
+        let (width, depth) = (size.x as usize, size.y as usize);
+
+        let columns = rasterize(triangles, settings, origin, width, depth);
+        let mut nodes = find_nodes(&columns, settings, width);
+        link_nodes(&mut nodes, settings, width, depth);
+        erode(
+            &mut nodes,
+            (settings.agent_radius / cell_size).ceil() as usize,
+        );
+        let rectangles = merge_rectangles(&nodes, width);
+
+        let to_world = |x: usize, z: usize, y: f32| {
+            Vec3::new(
+                origin.x + x as f32 * cell_size,
+                y,
+                origin.y + z as f32 * cell_size,
+            )
+        };
+        let mut polygon_of = vec![None; nodes.len()];
+        for (index, rectangle) in rectangles.iter().enumerate() {
+            for &node in rectangle.rows.iter().flatten() {
+                polygon_of[node] = Some(index);
+            }
+        }
+
+        let polygons = rectangles
+            .iter()
+            .map(|rectangle| {
+                let (x0, z0) = rectangle.origin;
+                let rows = &rectangle.rows;
+                let (w, h) = (rows[0].len(), rows.len());
+                let floor = |row: usize, column: usize| nodes[rows[row][column]].floor;
+                let vertices = vec![
+                    to_world(x0, z0, floor(0, 0)),
+                    to_world(x0 + w, z0, floor(0, w - 1)),
+                    to_world(x0 + w, z0 + h, floor(h - 1, w - 1)),
+                    to_world(x0, z0 + h, floor(h - 1, 0)),
+                ];
+
+                // Each side of the rectangle, as its boundary nodes, the direction they face,
+                // and the cell coordinates of the start of each boundary segment.
+                let south = (0..w)
+                    .map(|i| (rows[0][i], (x0 + i, z0)))
+                    .collect::<Vec<_>>();
+                let north = (0..w)
+                    .map(|i| (rows[h - 1][i], (x0 + i, z0 + h)))
+                    .collect::<Vec<_>>();
+                let west = (0..h)
+                    .map(|j| (rows[j][0], (x0, z0 + j)))
+                    .collect::<Vec<_>>();
+                let east = (0..h)
+                    .map(|j| (rows[j][w - 1], (x0 + w, z0 + j)))
+                    .collect::<Vec<_>>();
+
+                let mut portals = Vec::new();
+                for (side, direction, step) in [
+                    (south, SOUTH, (1, 0)),
+                    (north, NORTH, (1, 0)),
+                    (west, WEST, (0, 1)),
+                    (east, EAST, (0, 1)),
+                ] {
+                    let neighbor = |node: usize| {
+                        let other = nodes[node].links[direction]?;
+                        Some((polygon_of[other]?, other))
+                    };
+                    let mut start = 0;
+                    while start < side.len() {
+                        let Some((polygon, _)) = neighbor(side[start].0) else {
+                            start += 1;
+                            continue;
+                        };
+                        let mut end = start + 1;
+                        while end < side.len()
+                            && neighbor(side[end].0).map(|(other, _)| other) == Some(polygon)
+                        {
+                            end += 1;
+                        }
+                        let height = |node: usize| {
+                            let (_, other) = neighbor(node).unwrap();
+                            (nodes[node].floor + nodes[other].floor) / 2.0
+                        };
+                        let (first, (x, z)) = side[start];
+                        let last = side[end - 1].0;
+                        portals.push(NavPortal {
+                            polygon,
+                            start: to_world(x, z, height(first)),
+                            end: to_world(
+                                x + (end - start) * step.0,
+                                z + (end - start) * step.1,
+                                height(last),
+                            ),
+                        });
+                        start = end;
+                    }
+                }
+
+                NavPolygon { vertices, portals }
+            })
+            .collect();
+
+        NavMesh::new(polygons)
+    }
+}
+
+/// Voxelizes the triangles into columns of merged solid spans, sorted from bottom to top.
+fn rasterize(
+    triangles: &[[Vec3; 3]],
+    settings: &NavMeshSettings,
+    origin: Vec2,
+    width: usize,
+    depth: usize,
+) -> Vec<Vec<Span>> {
+    let cell_size = settings.cell_size.max(f32::EPSILON);
+    let min_normal_y = ops::cos(settings.max_slope);
+    let mut columns = vec![Vec::new(); width * depth];
+
+    for &[a, b, c] in triangles {
+        let normal = (b - a).cross(c - a).normalize_or_zero();
+        let walkable = normal.y.abs() >= min_normal_y;
+        let cell = |point: Vec2| {
+            let cell = ((point - origin) / cell_size).floor().max(Vec2::ZERO);
+            (
+                (cell.x as usize).min(width - 1),
+                (cell.y as usize).min(depth - 1),
+            )
+        };
+        let (x0, z0) = cell(a.xz().min(b.xz()).min(c.xz()));
+        let (x1, z1) = cell(a.xz().max(b.xz()).max(c.xz()));
+
+        for z in z0..=z1 {
+            for x in x0..=x1 {
+                let min = origin + Vec2::new(x as f32, z as f32) * cell_size;
+                let max = min + cell_size;
+                let mut polygon = vec![a, b, c];
+                polygon = clip(&polygon, |point| point.x - min.x);
+                polygon = clip(&polygon, |point| max.x - point.x);
+                polygon = clip(&polygon, |point| point.z - min.y);
+                polygon = clip(&polygon, |point| max.y - point.z);
+                if polygon.is_empty() {
+                    continue;
+                }
+                let (min, max) = polygon
+                    .iter()
+                    .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), point| {
+                        (min.min(point.y), max.max(point.y))
+                    });
+                columns[z * width + x].push(Span { min, max, walkable });
+            }
+        }
+    }
+
+    for column in &mut columns {
+        column.sort_by(|a, b| a.min.total_cmp(&b.min));
+        let mut merged: Vec<Span> = Vec::with_capacity(column.len());
+        for span in column.drain(..) {
+            let Some(last) = merged
+                .last_mut()
+                .filter(|last| span.min <= last.max + settings.cell_height)
+            else {
+                merged.push(span);
+                continue;
+            };
+            // The top of the merged span decides whether it is walkable.
+            if span.max > last.max + settings.cell_height {
+                last.max = span.max;
+                last.walkable = span.walkable;
+            } else {
+                last.max = last.max.max(span.max);
+                last.walkable |= span.walkable;
+            }
+        }
+        *column = merged;
+    }
+    columns
+}
+
+/// Clips a convex polygon to the half-space where `distance` is positive.
+fn clip(polygon: &[Vec3], distance: impl Fn(Vec3) -> f32) -> Vec<Vec3> {
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (index, &current) in polygon.iter().enumerate() {
+        let next = polygon[(index + 1) % polygon.len()];
+        let (d_current, d_next) = (distance(current), distance(next));
+        if d_current >= 0.0 {
+            clipped.push(current);
+        }
+        if (d_current >= 0.0) != (d_next >= 0.0) {
+            clipped.push(current.lerp(next, d_current / (d_current - d_next)));
+        }
+    }
+    clipped
+}
+
+/// Finds the walkable surfaces on top of the spans of each column.
+fn find_nodes(columns: &[Vec<Span>], settings: &NavMeshSettings, width: usize) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    for (index, column) in columns.iter().enumerate() {
+        for (span_index, span) in column.iter().enumerate() {
+            let ceiling = column
+                .get(span_index + 1)
+                .map_or(f32::INFINITY, |above| above.min);
+            if span.walkable && ceiling - span.max >= settings.agent_height {
+                nodes.push(Node {
+                    cell: (index % width, index / width),
+                    floor: span.max,
+                    ceiling,
+                    links: [None; 4],
+                });
+            }
+        }
+    }
+    nodes
+}
+
+/// Connects each node to the closest reachable node of each neighboring column.
+fn link_nodes(nodes: &mut [Node], settings: &NavMeshSettings, width: usize, depth: usize) {
+    let mut column_nodes = vec![Vec::new(); width * depth];
+    for (index, node) in nodes.iter().enumerate() {
+        column_nodes[node.cell.1 * width + node.cell.0].push(index);
+    }
+
+    for index in 0..nodes.len() {
+        let (x, z) = nodes[index].cell;
+        for (direction, (dx, dz)) in OFFSETS.into_iter().enumerate() {
+            let (Some(nx), Some(nz)) = (x.checked_add_signed(dx), z.checked_add_signed(dz)) else {
+                continue;
+            };
+            if nx >= width || nz >= depth {
+                continue;
+            }
+            let node = &nodes[index];
+            let link = column_nodes[nz * width + nx]
+                .iter()
+                .copied()
+                .filter(|&other| {
+                    let other = &nodes[other];
+                    (other.floor - node.floor).abs() <= settings.max_climb
+                        && other.ceiling.min(node.ceiling) - other.floor.max(node.floor)
+                            >= settings.agent_height
+                })
+                .min_by(|&a, &b| {
+                    (nodes[a].floor - node.floor)
+                        .abs()
+                        .total_cmp(&(nodes[b].floor - node.floor).abs())
+                });
+            nodes[index].links[direction] = link;
+        }
+    }
+}
+
+/// Removes the nodes closer than `radius` cells to a wall or ledge.
+fn erode(nodes: &mut [Node], radius: usize) {
+    if radius == 0 {
+        return;
+    }
+    let mut distances = vec![usize::MAX; nodes.len()];
+    let mut queue = VecDeque::new();
+    for (index, node) in nodes.iter().enumerate() {
+        if node.links.iter().any(Option::is_none) {
+            distances[index] = 0;
+            queue.push_back(index);
+        }
+    }
+    while let Some(index) = queue.pop_front() {
+        for other in nodes[index].links.into_iter().flatten() {
+            if distances[other] == usize::MAX {
+                distances[other] = distances[index] + 1;
+                queue.push_back(other);
+            }
+        }
+    }
+
+    for node in nodes.iter_mut() {
+        for link in &mut node.links {
+            if link.is_some_and(|other| distances[other] < radius) {
+                *link = None;
+            }
+        }
+    }
+    for (node, &distance) in nodes.iter_mut().zip(&distances) {
+        if distance < radius {
+            node.links = [None; 4];
+            // Removed nodes can't be walked on.
+            node.ceiling = node.floor;
+        }
+    }
+}
+
+/// Greedily merges the nodes into rectangles, first along X, then along Z.
+fn merge_rectangles(nodes: &[Node], width: usize) -> Vec<Rectangle> {
+    let mut order = (0..nodes.len())
+        .filter(|&index| nodes[index].ceiling > nodes[index].floor)
+        .collect::<Vec<_>>();
+    order.sort_by_key(|&index| {
+        let (x, z) = nodes[index].cell;
+        z * width + x
+    });
+
+    let mut used = vec![false; nodes.len()];
+    let mut rectangles = Vec::new();
+    for start in order {
+        if used[start] {
+            continue;
+        }
+        let mut row = vec![start];
+        used[start] = true;
+        while let Some(next) = nodes[*row.last().unwrap()].links[EAST].filter(|&next| !used[next]) {
+            used[next] = true;
+            row.push(next);
+        }
+
+        let mut rows = vec![row];
+        loop {
+            let previous = rows.last().unwrap();
+            let mut next_row: Vec<usize> = Vec::with_capacity(previous.len());
+            for &node in previous {
+                let next = nodes[node].links[NORTH].filter(|&next| {
+                    !used[next]
+                        && next_row
+                            .last()
+                            .is_none_or(|&left| nodes[left].links[EAST] == Some(next))
+                });
+                match next {
+                    Some(next) => next_row.push(next),
+                    None => break,
+                }
+            }
+            if next_row.len() < previous.len() {
+                break;
+            }
+            for &node in &next_row {
+                used[node] = true;
+            }
+            rows.push(next_row);
+        }
+
+        rectangles.push(Rectangle {
+            origin: nodes[start].cell,
+            rows,
+        });
+    }
+    rectangles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quad(triangles: &mut Vec<[Vec3; 3]>, [a, b, c, d]: [Vec3; 4]) {
+        triangles.push([a, b, c]);
+        triangles.push([a, c, d]);
+    }
+
+    #[test]
+    fn bake_around_obstacle() {
+        let mut triangles = Vec::new();
+        quad(
+            &mut triangles,
+            [
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, 10.0),
+                Vec3::new(10.0, 0.0, 10.0),
+                Vec3::new(10.0, 0.0, 0.0),
+            ],
+        );
+        let settings = NavMeshSettings {
+            agent_radius: 0.25,
+            ..NavMeshSettings::default()
+        };
+
+        let navmesh = NavMesh::bake(&triangles, &settings);
+        let path = navmesh
+            .find_path(Vec3::new(1.0, 0.0, 1.0), Vec3::new(9.0, 0.0, 1.0))
+            .unwrap();
+        assert_eq!(
+            path,
+            vec![Vec3::new(1.0, 0.0, 1.0), Vec3::new(9.0, 0.0, 1.0)]
+        );
+
+        // A low wall, that agents can't climb nor walk under, from z = 0 to z = 7.
+        let corner = |x: f32, y: f32, z: f32| Vec3::new(x, y, z);
+        for [a, b, c, d] in [
+            [
+                corner(4.0, 1.0, 0.0),
+                corner(4.0, 1.0, 7.0),
+                corner(6.0, 1.0, 7.0),
+                corner(6.0, 1.0, 0.0),
+            ],
+            [
+                corner(4.0, 0.0, 0.0),
+                corner(4.0, 0.0, 7.0),
+                corner(4.0, 1.0, 7.0),
+                corner(4.0, 1.0, 0.0),
+            ],
+            [
+                corner(6.0, 0.0, 0.0),
+                corner(6.0, 0.0, 7.0),
+                corner(6.0, 1.0, 7.0),
+                corner(6.0, 1.0, 0.0),
+            ],
+            [
+                corner(4.0, 0.0, 7.0),
+                corner(6.0, 0.0, 7.0),
+                corner(6.0, 1.0, 7.0),
+                corner(4.0, 1.0, 7.0),
+            ],
+        ] {
+            quad(&mut triangles, [a, b, c, d]);
+        }
+
+        let navmesh = NavMesh::bake(&triangles, &settings);
+        let path = navmesh
+            .find_path(Vec3::new(1.0, 0.0, 1.0), Vec3::new(9.0, 0.0, 1.0))
+            .unwrap();
+        assert_eq!(path.first(), Some(&Vec3::new(1.0, 0.0, 1.0)));
+        assert_eq!(path.last(), Some(&Vec3::new(9.0, 0.0, 1.0)));
+        assert!(path.iter().any(|point| point.z > 7.0));
+        assert!(path.iter().all(|point| point.y == 0.0));
+    }
+}
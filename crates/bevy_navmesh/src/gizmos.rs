@@ -0,0 +1,93 @@
+//! A module adding debug visualization of [`NavMesh`]es and [`NavPath`]s.
+
+use crate::{NavAgent, NavMesh, NavPath};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::Assets;
+use bevy_color::{
+    palettes::css::{AQUA, YELLOW},
+    Color,
+};
+use bevy_ecs::prelude::*;
+use bevy_gizmos::{config::GizmoConfigGroup, gizmos::Gizmos, AppGizmoBuilder};
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+
+/// A [`Plugin`] that draws the polygons of all [`NavMesh`]es and the paths of all [`NavAgent`]s
+/// with gizmos, for debugging.
+pub struct NavMeshGizmoPlugin;
+
+impl Plugin for NavMeshGizmoPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<NavMeshGizmoConfigGroup>()
+            .init_gizmo_group::<NavMeshGizmoConfigGroup>()
+            .add_systems(
+                PostUpdate,
+                (draw_navmeshes, draw_nav_paths).after(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// The [`GizmoConfigGroup`] used to draw [`NavMesh`]es and [`NavPath`]s.
+#[derive(Clone, Reflect, GizmoConfigGroup)]
+#[reflect(Clone, Default)]
+pub struct NavMeshGizmoConfigGroup {
+    /// The color of the outlines of the polygons of navigation meshes, or `None` to hide them.
+    ///
+    /// Defaults to aqua.
+    pub navmesh_color: Option<Color>,
+    /// The color of the paths of agents, or `None` to hide them.
+    ///
+    /// Defaults to yellow.
+    pub path_color: Option<Color>,
+}
+
+impl Default for NavMeshGizmoConfigGroup {
+    fn default() -> Self {
+        Self {
+            navmesh_color: Some(AQUA.into()),
+            path_color: Some(YELLOW.into()),
+        }
+    }
+}
+
+fn draw_navmeshes(navmeshes: Res<Assets<NavMesh>>, mut gizmos: Gizmos<NavMeshGizmoConfigGroup>) {
+    let Some(color) = gizmos.config_ext.navmesh_color else {
+        return;
+    };
+    // Lift the outlines slightly to avoid z-fighting with the ground.
+    let lift = Vec3::Y * 0.01;
+    for (_, navmesh) in navmeshes.iter() {
+        for polygon in navmesh.polygons() {
+            let Some(&first) = polygon.vertices.first() else {
+                continue;
+            };
+            gizmos.linestrip(
+                polygon
+                    .vertices
+                    .iter()
+                    .chain([&first])
+                    .map(|&vertex| vertex + lift),
+                color,
+            );
+        }
+    }
+}
+
+fn draw_nav_paths(
+    agents: Query<(&NavPath, &GlobalTransform), With<NavAgent>>,
+    mut gizmos: Gizmos<NavMeshGizmoConfigGroup>,
+) {
+    let Some(color) = gizmos.config_ext.path_color else {
+        return;
+    };
+    for (path, transform) in &agents {
+        if path.waypoints.is_empty() {
+            continue;
+        }
+        gizmos.linestrip(
+            core::iter::once(transform.translation()).chain(path.waypoints.iter().copied()),
+            color,
+        );
+    }
+}
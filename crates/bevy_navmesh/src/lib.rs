@@ -0,0 +1,104 @@
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![doc(
+    html_logo_url = "https://bevyengine.org/assets/icon.png",
+    html_favicon_url = "https://bevyengine.org/assets/icon.png"
+)]
+
+//! Provides navigation mesh baking, pathfinding and steering behaviors.
+//!
+//! A [`NavMesh`] describes the surfaces agents can walk on. It is baked from the meshes of the
+//! entities marked with [`NavMeshSource`] when a [`BakeNavMesh`] event is sent, or from arbitrary
+//! triangles with [`NavMesh::bake`].
+//!
+//! Entities with a [`NavAgent`] find their path to their target along a navigation mesh, and
+//! optionally follow it. The [`NavMeshGizmoPlugin`] draws navigation meshes and paths for debugging.
+//!
+//! The [`steering`] module provides steering and flocking behaviors, added by the
+//! [`SteeringPlugin`].
+//!
+//! ```no_run
+//! # use bevy_app::prelude::*;
+//! # use bevy_asset::Assets;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_math::Vec3;
+//! use bevy_navmesh::prelude::*;
+//!
+//! fn setup(
+//!     mut commands: Commands,
+//!     navmeshes: Res<Assets<NavMesh>>,
+//!     mut bake: EventWriter<BakeNavMesh>,
+//! ) {
+//!     let navmesh = navmeshes.reserve_handle();
+//!     bake.write(BakeNavMesh {
+//!         navmesh: navmesh.clone(),
+//!         settings: NavMeshSettings::default(),
+//!     });
+//!
+//!     commands.spawn(NavAgent {
+//!         target: Some(Vec3::new(10.0, 0.0, 5.0)),
+//!         speed: 3.0,
+//!         ..NavAgent::new(navmesh)
+//!     });
+//! }
+//! # App::new().add_plugins(NavMeshPlugin).add_systems(Startup, setup);
+//! ```
+
+extern crate alloc;
+
+mod agent;
+mod bake;
+#[cfg(feature = "bevy_gizmos")]
+pub mod gizmos;
+mod navmesh;
+pub mod steering;
+
+pub use agent::*;
+pub use bake::*;
+pub use navmesh::*;
+pub use steering::SteeringPlugin;
+
+#[cfg(feature = "bevy_gizmos")]
+pub use gizmos::NavMeshGizmoPlugin;
+
+/// The navigation mesh prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        steering::{Alignment, Arrive, Cohesion, Flee, Seek, Separation, SteeringAgent, Wander},
+        BakeNavMesh, NavAgent, NavMesh, NavMeshPlugin, NavMeshSettings, NavMeshSource, NavPath,
+        NavPathStatus, SteeringPlugin,
+    };
+
+    #[cfg(feature = "bevy_gizmos")]
+    #[doc(hidden)]
+    pub use crate::NavMeshGizmoPlugin;
+}
+
+use bevy_app::prelude::*;
+use bevy_asset::AssetApp;
+use bevy_ecs::schedule::IntoScheduleConfigs;
+use bevy_transform::TransformSystem;
+
+/// Adds support for baking [`NavMesh`]es and finding paths for [`NavAgent`]s.
+#[derive(Default)]
+pub struct NavMeshPlugin;
+
+impl Plugin for NavMeshPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<NavMesh>()
+            .register_type::<NavAgent>()
+            .register_type::<NavPath>()
+            .register_type::<NavMeshSource>()
+            .register_type::<NavMeshSettings>()
+            .add_event::<BakeNavMesh>()
+            .init_resource::<NavMeshBakeTasks>()
+            .add_systems(PreUpdate, (finish_navmesh_bakes, poll_nav_paths))
+            .add_systems(Update, follow_nav_paths)
+            .add_systems(
+                PostUpdate,
+                (start_navmesh_bakes, request_nav_paths).after(TransformSystem::TransformPropagate),
+            );
+    }
+}
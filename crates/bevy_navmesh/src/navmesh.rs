@@ -0,0 +1,380 @@
+use alloc::{collections::BinaryHeap, sync::Arc, vec, vec::Vec};
+use bevy_asset::Asset;
+use bevy_math::{Vec2, Vec3, Vec3Swizzles};
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::TypePath;
+use core::cmp::Ordering;
+
+/// A navigation mesh: the surfaces agents can walk on, as a graph of convex polygons connected
+/// by portals.
+///
+/// Navigation meshes are usually baked from level geometry with [`NavMesh::bake`] or a
+/// [`BakeNavMesh`](crate::BakeNavMesh) event, and used by [`NavAgent`](crate::NavAgent)s to find
+/// their paths.
+///
+/// Cloning a navigation mesh is cheap, as its polygons are shared.
+#[derive(Asset, TypePath, Clone, Debug, Default)]
+pub struct NavMesh {
+    polygons: Arc<[NavPolygon]>,
+}
+
+/// A convex polygon of a [`NavMesh`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct NavPolygon {
+    /// The vertices of the polygon, in order around its edges.
+    ///
+    /// The polygon is convex when projected on the XZ plane.
+    pub vertices: Vec<Vec3>,
+    /// The portals through which agents can walk to the neighbors of the polygon.
+    pub portals: Vec<NavPortal>,
+}
+
+/// A segment shared by two neighboring [`NavPolygon`]s.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NavPortal {
+    /// The index of the neighboring polygon in [`NavMesh::polygons`].
+    pub polygon: usize,
+    /// The start of the segment.
+    pub start: Vec3,
+    /// The end of the segment.
+    pub end: Vec3,
+}
+
+impl NavMesh {
+    /// Creates a navigation mesh from its polygons.
+    pub fn new(polygons: Vec<NavPolygon>) -> Self {
+        Self {
+            polygons: polygons.into(),
+        }
+    }
+
+    /// Returns the polygons of the navigation mesh.
+    pub fn polygons(&self) -> &[NavPolygon] {
+        &self.polygons
+    }
+
+    /// Returns `true` if the navigation mesh has no polygons.
+    pub fn is_empty(&self) -> bool {
+        self.polygons.is_empty()
+    }
+
+    /// Returns the index of the polygon closest to `point`, and the closest point on that polygon.
+    ///
+    /// Returns `None` if the navigation mesh is empty.
+    pub fn project(&self, point: Vec3) -> Option<(usize, Vec3)> {
+        self.polygons
+            .iter()
+            .enumerate()
+            .map(|(index, polygon)| (index, polygon.closest_point(point)))
+            .min_by(|(_, a), (_, b)| {
+                a.distance_squared(point)
+                    .total_cmp(&b.distance_squared(point))
+            })
+    }
+
+    /// Finds the shortest path from `start` to `end` along the navigation mesh.
+    ///
+    /// Both points are first moved to the closest point of the navigation mesh. The returned
+    /// path starts at the projected `start`, ends at the projected `end`, and only turns at the
+    /// corners of the navigation mesh.
+    ///
+    /// Returns `None` if there is no path between the points.
+    pub fn find_path(&self, start: Vec3, end: Vec3) -> Option<Vec<Vec3>> {
+        let (start_polygon, start) = self.project(start)?;
+        let (end_polygon, end) = self.project(end)?;
+        let corridor = self.find_corridor(start_polygon, end_polygon, end)?;
+
+        let mut portals = Vec::with_capacity(corridor.len() + 1);
+        portals.push((start, start));
+        for pair in corridor.windows(2) {
+            let (from, to) = (&self.polygons[pair[0]], &self.polygons[pair[1]]);
+            let portal = from
+                .portals
+                .iter()
+                .find(|portal| portal.polygon == pair[1])?;
+            // Orient the portal relative to the direction of travel.
+            let direction = (to.centroid() - from.centroid()).xz();
+            let center = from.centroid().xz();
+            if cross(direction, portal.start.xz() - center)
+                > cross(direction, portal.end.xz() - center)
+            {
+                portals.push((portal.start, portal.end));
+            } else {
+                portals.push((portal.end, portal.start));
+            }
+        }
+        portals.push((end, end));
+
+        Some(string_pull(&portals))
+    }
+
+    /// Finds the sequence of polygons leading from `start` to `end` with A*.
+    fn find_corridor(&self, start: usize, end: usize, goal: Vec3) -> Option<Vec<usize>> {
+        let centroids = self
+            .polygons
+            .iter()
+            .map(NavPolygon::centroid)
+            .collect::<Vec<_>>();
+        let mut costs = vec![f32::INFINITY; self.polygons.len()];
+        let mut previous = HashMap::<usize, usize>::default();
+        let mut open = BinaryHeap::new();
+        costs[start] = 0.0;
+        open.push(OpenPolygon {
+            estimate: centroids[start].distance(goal),
+            polygon: start,
+        });
+
+        while let Some(OpenPolygon { polygon, .. }) = open.pop() {
+            if polygon == end {
+                let mut corridor = vec![end];
+                let mut current = end;
+                while let Some(&next) = previous.get(&current) {
+                    corridor.push(next);
+                    current = next;
+                }
+                corridor.reverse();
+                return Some(corridor);
+            }
+            for portal in &self.polygons[polygon].portals {
+                let cost = costs[polygon] + centroids[polygon].distance(centroids[portal.polygon]);
+                if cost < costs[portal.polygon] {
+                    costs[portal.polygon] = cost;
+                    previous.insert(portal.polygon, polygon);
+                    open.push(OpenPolygon {
+                        estimate: cost + centroids[portal.polygon].distance(goal),
+                        polygon: portal.polygon,
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+impl NavPolygon {
+    /// Returns the average of the vertices of the polygon.
+    pub fn centroid(&self) -> Vec3 {
+        self.vertices.iter().sum::<Vec3>() / self.vertices.len().max(1) as f32
+    }
+
+    /// Returns `true` if `point` is inside the polygon, when projected on the XZ plane.
+    pub fn contains(&self, point: Vec2) -> bool {
+        let mut sign = 0.0;
+        for (a, b) in self.edges() {
+            let side = cross(b.xz() - a.xz(), point - a.xz());
+            if side * sign < 0.0 {
+                return false;
+            }
+            if side != 0.0 {
+                sign = side;
+            }
+        }
+        true
+    }
+
+    /// Returns the point of the polygon closest to `point`.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        let xz = point.xz();
+        if self.contains(xz) {
+            return Vec3::new(point.x, self.height_at(xz), point.z);
+        }
+        self.edges()
+            .map(|(a, b)| {
+                let edge = b.xz() - a.xz();
+                let t = ((xz - a.xz()).dot(edge) / edge.length_squared().max(f32::EPSILON))
+                    .clamp(0.0, 1.0);
+                a.lerp(b, t)
+            })
+            .min_by(|a, b| {
+                a.distance_squared(point)
+                    .total_cmp(&b.distance_squared(point))
+            })
+            .unwrap_or(point)
+    }
+
+    /// Returns the height of the polygon at `point`, interpolated between its vertices.
+    fn height_at(&self, point: Vec2) -> f32 {
+        let Some(&first) = self.vertices.first() else {
+            return 0.0;
+        };
+        for pair in self.vertices[1..].windows(2) {
+            let (a, b, c) = (first.xz(), pair[0].xz(), pair[1].xz());
+            let area = cross(b - a, c - a);
+            if area == 0.0 {
+                continue;
+            }
+            let u = cross(c - b, point - b) / area;
+            let v = cross(a - c, point - c) / area;
+            let w = 1.0 - u - v;
+            if u >= -1e-4 && v >= -1e-4 && w >= -1e-4 {
+                return u * first.y + v * pair[0].y + w * pair[1].y;
+            }
+        }
+        self.centroid().y
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (Vec3, Vec3)> + '_ {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+    }
+}
+
+/// A polygon to visit during the A* search, ordered by lowest estimated cost first.
+struct OpenPolygon {
+    estimate: f32,
+    polygon: usize,
+}
+
+impl PartialEq for OpenPolygon {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenPolygon {}
+
+impl PartialOrd for OpenPolygon {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenPolygon {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+/// Returns the z component of the cross product of `a` and `b`, positive if `b` is to the left
+/// of `a`.
+fn cross(a: Vec2, b: Vec2) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+/// Finds the shortest path through a sequence of `(left, right)` portals with the funnel
+/// algorithm, starting at the first portal and ending at the last.
+fn string_pull(portals: &[(Vec3, Vec3)]) -> Vec<Vec3> {
+    let Some(&(start, _)) = portals.first() else {
+        return Vec::new();
+    };
+    let mut path = vec![start];
+    let (mut apex, mut left, mut right) = (start, start, start);
+    let (mut left_index, mut right_index) = (0, 0);
+
+    let mut index = 1;
+    while index < portals.len() {
+        let (portal_left, portal_right) = portals[index];
+        let offset = |point: Vec3, apex: Vec3| point.xz() - apex.xz();
+
+        // Narrow the funnel from the right.
+        if cross(offset(right, apex), offset(portal_right, apex)) >= 0.0 {
+            if apex.xz() == right.xz()
+                || cross(offset(left, apex), offset(portal_right, apex)) < 0.0
+            {
+                right = portal_right;
+                right_index = index;
+            } else {
+                // The right side crossed the left side: the left side is a corner of the path.
+                path.push(left);
+                apex = left;
+                let apex_index = left_index;
+                (left, left_index) = (apex, apex_index);
+                (right, right_index) = (apex, apex_index);
+                index = apex_index + 1;
+                continue;
+            }
+        }
+
+        // Narrow the funnel from the left.
+        if cross(offset(left, apex), offset(portal_left, apex)) <= 0.0 {
+            if apex.xz() == left.xz() || cross(offset(right, apex), offset(portal_left, apex)) > 0.0
+            {
+                left = portal_left;
+                left_index = index;
+            } else {
+                // The left side crossed the right side: the right side is a corner of the path.
+                path.push(right);
+                apex = right;
+                let apex_index = right_index;
+                (left, left_index) = (apex, apex_index);
+                (right, right_index) = (apex, apex_index);
+                index = apex_index + 1;
+                continue;
+            }
+        }
+
+        index += 1;
+    }
+
+    let Some(&(end, _)) = portals.last() else {
+        return path;
+    };
+    if path.last() != Some(&end) {
+        path.push(end);
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(min: Vec2, max: Vec2) -> Vec<Vec3> {
+        vec![
+            Vec3::new(min.x, 0.0, min.y),
+            Vec3::new(max.x, 0.0, min.y),
+            Vec3::new(max.x, 0.0, max.y),
+            Vec3::new(min.x, 0.0, max.y),
+        ]
+    }
+
+    #[test]
+    fn path_around_corner() {
+        // An L-shaped corridor: 0 -> 1 along X, then 1 -> 2 along Z.
+        let portal = |polygon, start: Vec2, end: Vec2| NavPortal {
+            polygon,
+            start: Vec3::new(start.x, 0.0, start.y),
+            end: Vec3::new(end.x, 0.0, end.y),
+        };
+        let navmesh = NavMesh::new(vec![
+            NavPolygon {
+                vertices: square(Vec2::new(0.0, 0.0), Vec2::new(4.0, 1.0)),
+                portals: vec![portal(1, Vec2::new(4.0, 0.0), Vec2::new(4.0, 1.0))],
+            },
+            NavPolygon {
+                vertices: square(Vec2::new(4.0, 0.0), Vec2::new(5.0, 1.0)),
+                portals: vec![
+                    portal(0, Vec2::new(4.0, 0.0), Vec2::new(4.0, 1.0)),
+                    portal(2, Vec2::new(4.0, 1.0), Vec2::new(5.0, 1.0)),
+                ],
+            },
+            NavPolygon {
+                vertices: square(Vec2::new(4.0, 1.0), Vec2::new(5.0, 5.0)),
+                portals: vec![portal(1, Vec2::new(4.0, 1.0), Vec2::new(5.0, 1.0))],
+            },
+        ]);
+
+        let path = navmesh
+            .find_path(Vec3::new(0.5, 0.0, 0.5), Vec3::new(4.5, 0.0, 4.5))
+            .unwrap();
+        assert_eq!(
+            path,
+            vec![
+                Vec3::new(0.5, 0.0, 0.5),
+                Vec3::new(4.0, 0.0, 1.0),
+                Vec3::new(4.5, 0.0, 4.5),
+            ]
+        );
+
+        // Points off the navigation mesh are projected on it.
+        let path = navmesh
+            .find_path(Vec3::new(-1.0, 2.0, 0.5), Vec3::new(2.0, 0.0, 0.5))
+            .unwrap();
+        assert_eq!(
+            path,
+            vec![Vec3::new(0.0, 0.0, 0.5), Vec3::new(2.0, 0.0, 0.5)]
+        );
+    }
+}
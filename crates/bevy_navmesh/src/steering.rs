@@ -0,0 +1,448 @@
+//! Steering behaviors for moving agents in a natural way, such as seeking a target, wandering
+//! around or flocking with neighbors.
+//!
+//! An entity with a [`SteeringAgent`] is moved by the sum of the forces of its behavior
+//! components ([`Seek`], [`Flee`], [`Arrive`], [`Wander`], [`Separation`], [`Alignment`] and
+//! [`Cohesion`]), each scaled by its weight. Agents are evaluated in parallel in [`FixedUpdate`],
+//! and find their neighbors with the [`SteeringSpatialHash`].
+//!
+//! Steering works in 3D: 2D agents should move on the XY plane, which is the default plane of
+//! [`Wander`].
+
+use alloc::vec::Vec;
+use bevy_app::{App, FixedUpdate, Plugin};
+use bevy_ecs::prelude::*;
+use bevy_math::{Dir3, IVec3, Quat, Vec3};
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::prelude::*;
+use bevy_time::Time;
+use bevy_transform::components::Transform;
+
+/// Adds support for steering behaviors.
+pub struct SteeringPlugin {
+    /// The size of the cells of the [`SteeringSpatialHash`].
+    ///
+    /// Neighbor lookups are fastest when the cells are about as large as the radius of the
+    /// flocking behaviors.
+    ///
+    /// Defaults to `4.0`.
+    pub cell_size: f32,
+}
+
+impl Default for SteeringPlugin {
+    fn default() -> Self {
+        Self { cell_size: 4.0 }
+    }
+}
+
+impl Plugin for SteeringPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<SteeringAgent>()
+            .register_type::<Seek>()
+            .register_type::<Flee>()
+            .register_type::<Arrive>()
+            .register_type::<Wander>()
+            .register_type::<Separation>()
+            .register_type::<Alignment>()
+            .register_type::<Cohesion>()
+            .insert_resource(SteeringSpatialHash::new(self.cell_size))
+            .add_systems(
+                FixedUpdate,
+                (update_steering_spatial_hash, steer_agents)
+                    .chain()
+                    .in_set(SteeringSystems),
+            );
+    }
+}
+
+/// The [`SystemSet`] moving [`SteeringAgent`]s, in [`FixedUpdate`].
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SteeringSystems;
+
+/// An entity moved by steering behaviors.
+///
+/// The agent moves its [`Transform`] according to its [`velocity`](SteeringAgent::velocity), so it
+/// shouldn't have a parent.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Debug, Default, Clone)]
+#[require(Transform)]
+pub struct SteeringAgent {
+    /// The current velocity of the agent, in units per second.
+    pub velocity: Vec3,
+    /// The maximum speed of the agent, in units per second.
+    pub max_speed: f32,
+    /// The maximum steering force applied to the agent each second, limiting how fast it can turn
+    /// and accelerate.
+    pub max_force: f32,
+}
+
+impl Default for SteeringAgent {
+    fn default() -> Self {
+        Self {
+            velocity: Vec3::ZERO,
+            max_speed: 5.0,
+            max_force: 10.0,
+        }
+    }
+}
+
+/// Steers the agent towards a target at full speed.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Debug, Clone)]
+pub struct Seek {
+    /// The position to go to.
+    pub target: Vec3,
+    /// The weight of the behavior.
+    pub weight: f32,
+}
+
+/// Steers the agent away from a position, when it is closer than a distance.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Debug, Clone)]
+pub struct Flee {
+    /// The position to flee from.
+    pub target: Vec3,
+    /// The distance under which the agent flees.
+    pub panic_distance: f32,
+    /// The weight of the behavior.
+    pub weight: f32,
+}
+
+/// Steers the agent towards a target, slowing down to stop on it.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Debug, Clone)]
+pub struct Arrive {
+    /// The position to go to.
+    pub target: Vec3,
+    /// The distance to the target under which the agent starts slowing down.
+    pub slowing_radius: f32,
+    /// The weight of the behavior.
+    pub weight: f32,
+}
+
+/// Steers the agent in random, smoothly changing directions.
+///
+/// The agent seeks a point moving randomly on a circle in front of it.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Debug, Default, Clone)]
+pub struct Wander {
+    /// The distance of the circle in front of the agent.
+    pub distance: f32,
+    /// The radius of the circle.
+    pub radius: f32,
+    /// The largest change of the angle of the point on the circle, in radians, each step.
+    pub jitter: f32,
+    /// The normal of the plane the agent wanders on.
+    ///
+    /// Defaults to [`Dir3::Z`], for 2D agents.
+    pub plane_normal: Dir3,
+    /// The weight of the behavior.
+    pub weight: f32,
+    /// The angle of the point on the circle.
+    pub angle: f32,
+    /// The state of the random number generator of the behavior.
+    pub seed: u64,
+}
+
+impl Default for Wander {
+    fn default() -> Self {
+        Self {
+            distance: 2.0,
+            radius: 1.0,
+            jitter: 0.5,
+            plane_normal: Dir3::Z,
+            weight: 1.0,
+            angle: 0.0,
+            seed: 0x853c_49e6_748f_ea9b,
+        }
+    }
+}
+
+/// Steers the agent away from its neighbors, to avoid crowding.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Debug, Clone)]
+pub struct Separation {
+    /// The distance under which other agents are neighbors.
+    pub radius: f32,
+    /// The weight of the behavior.
+    pub weight: f32,
+}
+
+/// Steers the agent in the average direction of its neighbors.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Debug, Clone)]
+pub struct Alignment {
+    /// The distance under which other agents are neighbors.
+    pub radius: f32,
+    /// The weight of the behavior.
+    pub weight: f32,
+}
+
+/// Steers the agent towards the average position of its neighbors.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Debug, Clone)]
+pub struct Cohesion {
+    /// The distance under which other agents are neighbors.
+    pub radius: f32,
+    /// The weight of the behavior.
+    pub weight: f32,
+}
+
+/// A [`SteeringAgent`] stored in the [`SteeringSpatialHash`].
+#[derive(Clone, Copy, Debug)]
+pub struct SteeringNeighbor {
+    /// The entity of the agent.
+    pub entity: Entity,
+    /// The position of the agent.
+    pub position: Vec3,
+    /// The velocity of the agent.
+    pub velocity: Vec3,
+}
+
+/// The positions and velocities of all [`SteeringAgent`]s at the start of the steering step,
+/// grouped in cubic cells for fast neighbor lookups.
+#[derive(Resource, Debug)]
+pub struct SteeringSpatialHash {
+    cell_size: f32,
+    cells: HashMap<IVec3, Vec<SteeringNeighbor>>,
+}
+
+impl SteeringSpatialHash {
+    /// Creates an empty spatial hash with cells of `cell_size`.
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: HashMap::default(),
+        }
+    }
+
+    /// Returns the size of the cells.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    fn cell(&self, position: Vec3) -> IVec3 {
+        (position / self.cell_size).floor().as_ivec3()
+    }
+
+    /// Removes all agents.
+    pub fn clear(&mut self) {
+        self.cells.values_mut().for_each(Vec::clear);
+    }
+
+    /// Adds an agent.
+    pub fn insert(&mut self, neighbor: SteeringNeighbor) {
+        let cell = self.cell(neighbor.position);
+        self.cells.entry(cell).or_default().push(neighbor);
+    }
+
+    /// Iterates over the agents closer than `radius` to `position`.
+    pub fn neighbors(
+        &self,
+        position: Vec3,
+        radius: f32,
+    ) -> impl Iterator<Item = &SteeringNeighbor> + '_ {
+        let min = self.cell(position - radius);
+        let max = self.cell(position + radius);
+        (min.x..=max.x)
+            .flat_map(move |x| (min.y..=max.y).map(move |y| (x, y)))
+            .flat_map(move |(x, y)| (min.z..=max.z).map(move |z| IVec3::new(x, y, z)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(move |neighbor| neighbor.position.distance_squared(position) <= radius * radius)
+    }
+}
+
+/// Fills the [`SteeringSpatialHash`] with the current state of all agents.
+pub fn update_steering_spatial_hash(
+    mut hash: ResMut<SteeringSpatialHash>,
+    agents: Query<(Entity, &SteeringAgent, &Transform)>,
+) {
+    hash.clear();
+    for (entity, agent, transform) in &agents {
+        hash.insert(SteeringNeighbor {
+            entity,
+            position: transform.translation,
+            velocity: agent.velocity,
+        });
+    }
+}
+
+/// Applies the steering behaviors of all agents, and moves them.
+pub fn steer_agents(
+    time: Res<Time>,
+    hash: Res<SteeringSpatialHash>,
+    mut agents: Query<(
+        Entity,
+        &mut SteeringAgent,
+        &mut Transform,
+        Option<&Seek>,
+        Option<&Flee>,
+        Option<&Arrive>,
+        Option<&mut Wander>,
+        Option<&Separation>,
+        Option<&Alignment>,
+        Option<&Cohesion>,
+    )>,
+) {
+    let delta = time.delta_secs();
+    if delta == 0.0 {
+        return;
+    }
+
+    agents.par_iter_mut().for_each(
+        |(
+            entity,
+            mut agent,
+            mut transform,
+            seek,
+            flee,
+            arrive,
+            wander,
+            separation,
+            alignment,
+            cohesion,
+        )| {
+            let position = transform.translation;
+            let velocity = agent.velocity;
+            let max_speed = agent.max_speed;
+            // Steers towards a desired velocity.
+            let towards = |desired: Vec3| desired.normalize_or_zero() * max_speed - velocity;
+            let neighbors = |radius: f32| {
+                hash.neighbors(position, radius)
+                    .filter(move |neighbor| neighbor.entity != entity)
+            };
+
+            let mut force = Vec3::ZERO;
+            if let Some(seek) = seek {
+                force += towards(seek.target - position) * seek.weight;
+            }
+            if let Some(flee) = flee {
+                if position.distance_squared(flee.target)
+                    < flee.panic_distance * flee.panic_distance
+                {
+                    force += towards(position - flee.target) * flee.weight;
+                }
+            }
+            if let Some(arrive) = arrive {
+                force += arrive_force(position, velocity, max_speed, arrive) * arrive.weight;
+            }
+            if let Some(mut wander) = wander {
+                force += wander_force(position, velocity, &mut wander, towards) * wander.weight;
+            }
+            if let Some(separation) = separation {
+                let away = neighbors(separation.radius)
+                    .map(|neighbor| {
+                        let offset = position - neighbor.position;
+                        // Closer neighbors push harder.
+                        offset / offset.length_squared().max(f32::EPSILON)
+                    })
+                    .sum::<Vec3>();
+                if away != Vec3::ZERO {
+                    force += towards(away) * separation.weight;
+                }
+            }
+            if let Some(alignment) = alignment {
+                let heading = neighbors(alignment.radius)
+                    .map(|neighbor| neighbor.velocity)
+                    .sum::<Vec3>();
+                if heading != Vec3::ZERO {
+                    force += towards(heading) * alignment.weight;
+                }
+            }
+            if let Some(cohesion) = cohesion {
+                let (sum, count) = neighbors(cohesion.radius)
+                    .fold((Vec3::ZERO, 0), |(sum, count), neighbor| {
+                        (sum + neighbor.position, count + 1)
+                    });
+                if count > 0 {
+                    force += towards(sum / count as f32 - position) * cohesion.weight;
+                }
+            }
+
+            let force = force.clamp_length_max(agent.max_force);
+            agent.velocity = (velocity + force * delta).clamp_length_max(max_speed);
+            transform.translation += agent.velocity * delta;
+        },
+    );
+}
+
+fn arrive_force(position: Vec3, velocity: Vec3, max_speed: f32, arrive: &Arrive) -> Vec3 {
+    let offset = arrive.target - position;
+    let distance = offset.length();
+    if distance < 1e-4 {
+        return -velocity;
+    }
+    let speed = max_speed * (distance / arrive.slowing_radius.max(f32::EPSILON)).min(1.0);
+    offset / distance * speed - velocity
+}
+
+fn wander_force(
+    position: Vec3,
+    velocity: Vec3,
+    wander: &mut Wander,
+    towards: impl Fn(Vec3) -> Vec3,
+) -> Vec3 {
+    let normal = *wander.plane_normal;
+    let heading = velocity
+        .reject_from_normalized(normal)
+        .try_normalize()
+        .unwrap_or_else(|| normal.any_orthonormal_vector());
+
+    wander.seed = next_random(wander.seed);
+    // Maps the random bits to [-1, 1].
+    let random = (wander.seed >> 40) as f32 / (1u64 << 23) as f32 - 1.0;
+    wander.angle += random * wander.jitter;
+
+    let displacement = Quat::from_axis_angle(normal, wander.angle) * heading * wander.radius;
+    let target = position + heading * wander.distance + displacement;
+    towards(target - position)
+}
+
+/// Advances a xorshift random number generator.
+fn next_random(mut state: u64) -> u64 {
+    state ^= state << 13;
+    state ^= state >> 7;
+    state ^= state << 17;
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spatial_hash_neighbors() {
+        let mut hash = SteeringSpatialHash::new(1.0);
+        let mut entities = World::new();
+        for x in [0.0, 0.5, 1.5, 3.0, -1.2] {
+            hash.insert(SteeringNeighbor {
+                entity: entities.spawn_empty().id(),
+                position: Vec3::new(x, 0.0, 0.0),
+                velocity: Vec3::ZERO,
+            });
+        }
+
+        let mut found = hash
+            .neighbors(Vec3::ZERO, 1.5)
+            .map(|neighbor| neighbor.position.x)
+            .collect::<Vec<_>>();
+        found.sort_by(f32::total_cmp);
+        assert_eq!(found, [-1.2, 0.0, 0.5, 1.5]);
+    }
+
+    #[test]
+    fn arrive_slows_down() {
+        let arrive = Arrive {
+            target: Vec3::new(10.0, 0.0, 0.0),
+            slowing_radius: 4.0,
+            weight: 1.0,
+        };
+        let far = arrive_force(Vec3::ZERO, Vec3::ZERO, 5.0, &arrive);
+        assert_eq!(far, Vec3::new(5.0, 0.0, 0.0));
+        let near = arrive_force(Vec3::new(8.0, 0.0, 0.0), Vec3::ZERO, 5.0, &arrive);
+        assert_eq!(near, Vec3::new(2.5, 0.0, 0.0));
+        let moving = Vec3::new(1.0, 0.0, 0.0);
+        assert_eq!(arrive_force(arrive.target, moving, 5.0, &arrive), -moving);
+    }
+}
@@ -0,0 +1,46 @@
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![doc(
+    html_logo_url = "https://bevyengine.org/assets/icon.png",
+    html_favicon_url = "https://bevyengine.org/assets/icon.png"
+)]
+
+//! Provides the building blocks for networked multiplayer: replicating ECS state from a server
+//! [`World`](bevy_ecs::world::World) to client worlds over a pluggable transport.
+//!
+//! The server marks entities with [`Replicated`] and registers the components to send with
+//! [`AppReplicationExt::replicate`]. Every frame, the [`ReplicationPlugin`] running as a server
+//! sends the components that changed since the last update to each connected client, and the
+//! client applies them to its own world, mapping server entities to local ones.
+//!
+//! Messages are sent through a [`NetworkTransport`], stored in the [`Transport`] resource.
+//! The transport is responsible for connections and delivery, which keeps the replication logic
+//! independent of the underlying protocol. The [`TransportPlugin`] polls it every frame,
+//! turning connections and received messages into [`PeerConnected`], [`PeerDisconnected`]
+//! and [`MessageReceived`] events, and applications can send their own messages on any
+//! [`Channel`]. This crate provides an in-memory [`LoopbackTransport`] and a [`UdpTransport`].
+//!
+//! On top of replication, the [`PredictionPlugin`] lets clients simulate the entities they
+//! control ahead of the server, and reconcile them with the server state once it arrives.
+
+extern crate alloc;
+
+mod prediction;
+mod replication;
+mod transport;
+
+pub use prediction::*;
+pub use replication::*;
+pub use transport::*;
+
+/// The net prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        AppPredictionExt, AppReplicationExt, Channel, InputBuffer, MessageReceived,
+        NetworkTransport, PeerConnected, PeerDisconnected, PeerId, Predict, Predicted,
+        PredictedUpdate, PredictionPlugin, PredictionSet, Replicated, ReplicationPlugin,
+        ReplicationSet, ServerInputs, Transport, TransportPlugin,
+    };
+}
@@ -0,0 +1,796 @@
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{marker::PhantomData, time::Duration};
+
+use bevy_app::{App, FixedUpdate, Plugin, PostUpdate, PreUpdate};
+use bevy_ecs::{
+    component::{Component, ComponentId, Mutable},
+    entity::{Entity, EntityCloner},
+    event::EventReader,
+    query::{QueryState, With, Without},
+    reflect::{ReflectComponent, ReflectResource},
+    resource::Resource,
+    schedule::{common_conditions::resource_exists, IntoScheduleConfigs, ScheduleLabel, SystemSet},
+    system::{Commands, Query, Res, ResMut},
+    world::World,
+};
+use bevy_math::StableInterpolate;
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_time::{Fixed, Time};
+use log::warn;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    AppReplicationExt, Channel, MessageReceived, PeerDisconnected, PeerId, Replicated,
+    ReplicationClient, ReplicationRole, ReplicationServer, ReplicationSet, Transport, TransportSet,
+};
+
+/// The maximum number of unacknowledged inputs a client sends in a single message.
+const MAX_INPUTS_PER_MESSAGE: usize = 32;
+/// How many ticks ahead of the last input it processed the server accepts inputs from a client.
+///
+/// This is also the maximum number of unacknowledged inputs a client keeps for rollbacks.
+const MAX_INPUT_AHEAD: u32 = 128;
+/// The maximum number of inputs the server queues for a single client.
+const MAX_QUEUED_INPUTS: usize = 64;
+
+/// Marks a [`Replicated`] entity to be predicted by clients.
+///
+/// Insert it on the server, typically on the entities controlled by players. For each replicated
+/// entity with this component, clients spawn a [`Predicted`] copy that is simulated ahead of the
+/// server using the local inputs, while the replicated entity is marked [`Confirmed`] and keeps
+/// receiving the authoritative state.
+#[derive(Component, Reflect, Default, Clone, Copy, Debug)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct Predict;
+
+/// A client-side copy of a [`Confirmed`] entity, simulated ahead of the server.
+///
+/// Systems in [`PredictedUpdate`] should only move entities with this component on clients.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Predicted {
+    /// The replicated entity holding the authoritative state.
+    pub confirmed: Entity,
+}
+
+/// A replicated entity with a [`Predicted`] copy on this client.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Confirmed {
+    /// The copy of this entity simulated ahead of the server.
+    pub predicted: Entity,
+}
+
+/// The schedule containing the simulation of predicted entities, such as player movement.
+///
+/// It runs once per fixed timestep in [`PredictionSet::Simulate`], on both the server and the
+/// clients. On clients, it also runs again for every unacknowledged input when the server state
+/// of the [`Predicted`] entities is restored, so systems in this schedule must only depend on the
+/// state of the world, the current input, and the fixed [`Time`].
+#[derive(ScheduleLabel, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PredictedUpdate;
+
+/// System sets used by the [`PredictionPlugin`].
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PredictionSet {
+    /// Clients write the input for the current tick to the [`InputBuffer`], and the server
+    /// selects the next input of each client in [`ServerInputs`], in [`FixedUpdate`].
+    Input,
+    /// [`PredictedUpdate`] runs, in [`FixedUpdate`].
+    Simulate,
+    /// Clients restore the server state of [`Predicted`] entities and replay the unacknowledged
+    /// inputs, in [`PreUpdate`] after [`ReplicationSet::Receive`].
+    Rollback,
+    /// Clients apply the smoothing of corrections, in [`PostUpdate`].
+    Smooth,
+}
+
+/// Adds client-side prediction and server reconciliation on top of the
+/// [`ReplicationPlugin`](crate::ReplicationPlugin).
+///
+/// `I` is the input of a client for a single fixed timestep, such as the pressed movement keys.
+///
+/// # Client
+///
+/// Every fixed timestep, systems in [`PredictionSet::Input`] write the input to the [`InputBuffer`].
+/// The input is then sent to the server, and [`PredictedUpdate`] runs to move the [`Predicted`]
+/// entities right away. Whenever the server acknowledges new inputs, the registered components of
+/// the predicted entities are restored from their [`Confirmed`] entity, and [`PredictedUpdate`]
+/// runs again for each input the server hasn't processed yet.
+///
+/// Components are registered for prediction with [`AppPredictionExt::predict`]. Corrections of
+/// components registered with [`AppPredictionExt::predict_smoothed`] are blended in over time, see
+/// [`PredictionSettings`].
+///
+/// # Server
+///
+/// The inputs received from each client are queued and applied one per fixed timestep,
+/// in the order they were produced. Inputs too far ahead of the last processed one, or arriving
+/// while too many inputs of the client are already queued, are dropped. Systems in [`PredictedUpdate`] can read the input of each
+/// client from [`ServerInputs`]. The last input processed for each client is sent along with
+/// the replicated state.
+pub struct PredictionPlugin<I> {
+    /// Which side of the connection this app runs.
+    pub role: ReplicationRole,
+    marker: PhantomData<fn() -> I>,
+}
+
+impl<I> PredictionPlugin<I> {
+    /// The unreliable [`Channel`] inputs are sent on.
+    pub const INPUT_CHANNEL: Channel = Channel::unreliable(254);
+
+    /// Creates a [`PredictionPlugin`] for the server side.
+    pub fn server() -> Self {
+        Self {
+            role: ReplicationRole::Server,
+            marker: PhantomData,
+        }
+    }
+
+    /// Creates a [`PredictionPlugin`] for the client side.
+    pub fn client() -> Self {
+        Self {
+            role: ReplicationRole::Client,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<I> Plugin for PredictionPlugin<I>
+where
+    I: Clone + Default + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    fn build(&self, app: &mut App) {
+        app.replicate::<Predict>()
+            .init_schedule(PredictedUpdate)
+            .init_resource::<PredictionRegistry>()
+            .configure_sets(
+                FixedUpdate,
+                (PredictionSet::Input, PredictionSet::Simulate).chain(),
+            )
+            .configure_sets(
+                PreUpdate,
+                PredictionSet::Rollback.after(ReplicationSet::Receive),
+            )
+            .configure_sets(PostUpdate, PredictionSet::Smooth)
+            .add_systems(
+                FixedUpdate,
+                run_predicted_update.in_set(PredictionSet::Simulate),
+            );
+
+        match self.role {
+            ReplicationRole::Server => {
+                app.init_resource::<ServerInputs<I>>()
+                    .add_systems(PreUpdate, receive_inputs::<I>.after(TransportSet::Poll))
+                    .add_systems(FixedUpdate, next_inputs::<I>.in_set(PredictionSet::Input))
+                    .add_systems(
+                        PostUpdate,
+                        acknowledge_inputs::<I>.before(ReplicationSet::Send),
+                    );
+            }
+            ReplicationRole::Client => {
+                app.init_resource::<InputBuffer<I>>()
+                    .init_resource::<PredictionSettings>()
+                    .init_resource::<PredictionClient>()
+                    .add_systems(
+                        FixedUpdate,
+                        send_input::<I>
+                            .after(PredictionSet::Input)
+                            .before(PredictionSet::Simulate)
+                            .run_if(resource_exists::<Transport>),
+                    )
+                    .add_systems(PreUpdate, rollback::<I>.in_set(PredictionSet::Rollback));
+            }
+        }
+    }
+}
+
+/// The inputs of a client, for the current and the unacknowledged fixed timesteps.
+#[derive(Resource)]
+pub struct InputBuffer<I> {
+    tick: u32,
+    current: I,
+    history: VecDeque<(u32, I)>,
+}
+
+impl<I: Default> Default for InputBuffer<I> {
+    fn default() -> Self {
+        Self {
+            tick: 0,
+            current: I::default(),
+            history: VecDeque::new(),
+        }
+    }
+}
+
+impl<I> InputBuffer<I> {
+    /// Returns the tick of the current input.
+    ///
+    /// The tick is incremented every fixed timestep, after [`PredictionSet::Input`].
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Returns the current input.
+    pub fn current(&self) -> &I {
+        &self.current
+    }
+
+    /// Sets the input for the current fixed timestep.
+    ///
+    /// The input is kept for the following timesteps until it is set again.
+    pub fn set(&mut self, input: I) {
+        self.current = input;
+    }
+
+    /// Returns an iterator over the inputs that were not acknowledged by the server yet,
+    /// with their tick.
+    ///
+    /// Inputs are only recorded while connected to a server, and only the most recent ones are
+    /// kept if the server stops acknowledging them.
+    pub fn unacknowledged(&self) -> impl Iterator<Item = (u32, &I)> {
+        self.history.iter().map(|(tick, input)| (*tick, input))
+    }
+}
+
+/// The inputs of the connected clients, on the server.
+#[derive(Resource)]
+pub struct ServerInputs<I> {
+    peers: HashMap<PeerId, PeerInputs<I>>,
+}
+
+impl<I> Default for ServerInputs<I> {
+    fn default() -> Self {
+        Self {
+            peers: HashMap::default(),
+        }
+    }
+}
+
+struct PeerInputs<I> {
+    queue: VecDeque<(u32, I)>,
+    current: Option<I>,
+    last_processed: Option<u32>,
+}
+
+impl<I> ServerInputs<I> {
+    /// Returns the input of `peer` for the current fixed timestep.
+    ///
+    /// If no new input arrived in time, the last input of the peer is repeated.
+    pub fn get(&self, peer: PeerId) -> Option<&I> {
+        self.peers.get(&peer)?.current.as_ref()
+    }
+
+    /// Returns an iterator over the current input of every peer.
+    pub fn iter(&self) -> impl Iterator<Item = (PeerId, &I)> {
+        self.peers
+            .iter()
+            .filter_map(|(peer, inputs)| Some((*peer, inputs.current.as_ref()?)))
+    }
+
+    /// Returns the tick of the last input of `peer` that was processed.
+    pub fn last_processed(&self, peer: PeerId) -> Option<u32> {
+        self.peers.get(&peer)?.last_processed
+    }
+}
+
+/// Settings for the smoothing of prediction corrections on clients.
+#[derive(Resource, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Resource, Default, Debug, Clone)]
+pub struct PredictionSettings {
+    /// How long a correction is smoothed for, after which the simulated value is shown as-is.
+    pub correction_duration: Duration,
+    /// How fast the shown value catches up with the simulated value,
+    /// as used by [`StableInterpolate::smooth_nudge`].
+    pub correction_decay_rate: f32,
+}
+
+impl Default for PredictionSettings {
+    fn default() -> Self {
+        Self {
+            correction_duration: Duration::from_millis(250),
+            correction_decay_rate: 20.0,
+        }
+    }
+}
+
+/// The shown value of a smoothed component of a [`Predicted`] entity after a correction.
+///
+/// In [`PredictionSet::Smooth`], the component is replaced by the shown value, which moves
+/// towards the simulated value every frame. The simulated value is put back before the next
+/// simulation step, so that rendering sees the smoothed value while simulation is unaffected.
+#[derive(Component, Clone, Debug)]
+pub struct Correction<C> {
+    shown: C,
+    simulated: Option<C>,
+    remaining: Duration,
+}
+
+impl<C> Correction<C> {
+    /// Returns the value shown this frame.
+    pub fn shown(&self) -> &C {
+        &self.shown
+    }
+}
+
+/// The components restored when rolling back [`Predicted`] entities.
+#[derive(Resource, Default)]
+pub struct PredictionRegistry {
+    components: Vec<ComponentId>,
+    corrections: Vec<fn(&mut World, Entity)>,
+}
+
+impl PredictionRegistry {
+    /// Returns an iterator over the ids of the predicted components.
+    pub fn component_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.components.iter().copied()
+    }
+}
+
+/// Extension trait to register predicted components on an [`App`].
+pub trait AppPredictionExt {
+    /// Copies the component `C` from [`Confirmed`] entities to their [`Predicted`] copy
+    /// when rolling back.
+    ///
+    /// `C` should also be replicated with [`AppReplicationExt::replicate`].
+    fn predict<C: Component + Clone>(&mut self) -> &mut Self;
+
+    /// Like [`AppPredictionExt::predict`], but corrections of `C` caused by rollbacks are
+    /// smoothed over time, see [`Correction`].
+    fn predict_smoothed<C: Component<Mutability = Mutable> + Clone + StableInterpolate>(
+        &mut self,
+    ) -> &mut Self;
+}
+
+impl AppPredictionExt for App {
+    fn predict<C: Component + Clone>(&mut self) -> &mut Self {
+        let world = self.world_mut();
+        let component_id = world.register_component::<C>();
+        let mut registry = world.get_resource_or_init::<PredictionRegistry>();
+        if !registry.components.contains(&component_id) {
+            registry.components.push(component_id);
+        }
+        self
+    }
+
+    fn predict_smoothed<C: Component<Mutability = Mutable> + Clone + StableInterpolate>(
+        &mut self,
+    ) -> &mut Self {
+        self.predict::<C>()
+            .add_systems(
+                PreUpdate,
+                restore_simulated::<C>
+                    .after(ReplicationSet::Receive)
+                    .before(PredictionSet::Rollback),
+            )
+            .add_systems(
+                PostUpdate,
+                smooth_corrections::<C>
+                    .in_set(PredictionSet::Smooth)
+                    .run_if(resource_exists::<PredictionSettings>),
+            );
+        self.world_mut()
+            .resource_mut::<PredictionRegistry>()
+            .corrections
+            .push(begin_correction::<C>);
+        self
+    }
+}
+
+/// The client-side prediction state.
+#[derive(Resource, Default)]
+pub struct PredictionClient {
+    last_ack: Option<u32>,
+}
+
+impl PredictionClient {
+    /// Returns the input tick of the last rollback.
+    pub fn last_ack(&self) -> Option<u32> {
+        self.last_ack
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct InputMessage<I> {
+    inputs: Vec<(u32, I)>,
+}
+
+fn run_predicted_update(world: &mut World) {
+    world.run_schedule(PredictedUpdate);
+}
+
+fn send_input<I: Clone + Serialize + Send + Sync + 'static>(
+    mut buffer: ResMut<InputBuffer<I>>,
+    mut transport: ResMut<Transport>,
+    client: Res<ReplicationClient>,
+) {
+    let buffer = &mut *buffer;
+    buffer.tick = buffer.tick.wrapping_add(1);
+
+    let Some(server) = client.server() else {
+        return;
+    };
+    if buffer.history.len() >= MAX_INPUT_AHEAD as usize {
+        buffer.history.pop_front();
+    }
+    buffer
+        .history
+        .push_back((buffer.tick, buffer.current.clone()));
+    let start = buffer.history.len().saturating_sub(MAX_INPUTS_PER_MESSAGE);
+    let message = InputMessage {
+        inputs: buffer.history.range(start..).cloned().collect(),
+    };
+    match postcard::to_allocvec(&message) {
+        Ok(payload) => transport.send(server, PredictionPlugin::<I>::INPUT_CHANNEL, &payload),
+        Err(err) => warn!("Failed to serialize inputs: {err}"),
+    }
+}
+
+fn receive_inputs<I: DeserializeOwned + Send + Sync + 'static>(
+    mut inputs: ResMut<ServerInputs<I>>,
+    mut received: EventReader<MessageReceived>,
+    mut disconnected: EventReader<PeerDisconnected>,
+) {
+    for message in received.read() {
+        if message.channel != PredictionPlugin::<I>::INPUT_CHANNEL {
+            continue;
+        }
+        let message_inputs = match postcard::from_bytes::<InputMessage<I>>(&message.payload) {
+            Ok(message) => message.inputs,
+            Err(err) => {
+                warn!(
+                    "Failed to deserialize inputs from {:?}: {err}",
+                    message.peer
+                );
+                continue;
+            }
+        };
+
+        let peer = inputs
+            .peers
+            .entry(message.peer)
+            .or_insert_with(|| PeerInputs {
+                queue: VecDeque::new(),
+                current: None,
+                last_processed: None,
+            });
+        for (tick, input) in message_inputs {
+            let in_window = peer
+                .last_processed
+                .is_none_or(|last| (1..=MAX_INPUT_AHEAD).contains(&tick.wrapping_sub(last)));
+            let is_new = peer.queue.back().is_none_or(|(last, _)| tick > *last);
+            if in_window && is_new && peer.queue.len() < MAX_QUEUED_INPUTS {
+                peer.queue.push_back((tick, input));
+            }
+        }
+    }
+
+    for event in disconnected.read() {
+        inputs.peers.remove(&event.peer);
+    }
+}
+
+fn next_inputs<I: Send + Sync + 'static>(mut inputs: ResMut<ServerInputs<I>>) {
+    for peer in inputs.peers.values_mut() {
+        if let Some((tick, input)) = peer.queue.pop_front() {
+            peer.current = Some(input);
+            peer.last_processed = Some(tick);
+        }
+    }
+}
+
+fn acknowledge_inputs<I: Send + Sync + 'static>(
+    inputs: Res<ServerInputs<I>>,
+    mut server: ResMut<ReplicationServer>,
+) {
+    for (&peer, state) in &inputs.peers {
+        if let Some(tick) = state.last_processed {
+            server.set_input_ack(peer, tick);
+        }
+    }
+}
+
+fn rollback<I: Clone + Send + Sync + 'static>(
+    world: &mut World,
+    unpredicted: &mut QueryState<
+        Entity,
+        (
+            With<Predict>,
+            With<Replicated>,
+            Without<Confirmed>,
+            Without<Predicted>,
+        ),
+    >,
+    copies: &mut QueryState<(Entity, &Predicted)>,
+) {
+    let components: Vec<ComponentId> = world.resource::<PredictionRegistry>().components.clone();
+    let mut builder = EntityCloner::build(world);
+    builder.deny_all().allow_by_ids(components);
+    let mut cloner = builder.finish();
+
+    // Spawn a predicted copy of newly replicated entities.
+    let new_entities: Vec<Entity> = unpredicted.iter(world).collect();
+    for confirmed in new_entities {
+        let copy = world.spawn(Predicted { confirmed }).id();
+        cloner.clone_entity(world, confirmed, copy);
+        world
+            .entity_mut(confirmed)
+            .insert(Confirmed { predicted: copy });
+    }
+
+    // Despawn the copies of entities that are no longer replicated.
+    let mut pairs = Vec::new();
+    let mut orphans = Vec::new();
+    for (entity, predicted) in copies.iter(world) {
+        if world.get_entity(predicted.confirmed).is_ok() {
+            pairs.push((predicted.confirmed, entity));
+        } else {
+            orphans.push(entity);
+        }
+    }
+    for entity in orphans {
+        world.despawn(entity);
+    }
+
+    let ack = world.resource::<ReplicationClient>().input_ack();
+    let mut client = world.resource_mut::<PredictionClient>();
+    if ack.is_none() || ack == client.last_ack {
+        return;
+    }
+    client.last_ack = ack;
+    let ack = ack.unwrap();
+
+    let history: Vec<(u32, I)> = {
+        let mut buffer = world.resource_mut::<InputBuffer<I>>();
+        // Ticks wrap around, so compare them relative to the acknowledged one.
+        while buffer
+            .history
+            .front()
+            .is_some_and(|(tick, _)| tick.wrapping_sub(ack) as i32 <= 0)
+        {
+            buffer.history.pop_front();
+        }
+        buffer.history.iter().cloned().collect()
+    };
+
+    // Restore the server state.
+    let corrections = world.resource::<PredictionRegistry>().corrections.clone();
+    for &(confirmed, copy) in &pairs {
+        for begin_correction in &corrections {
+            begin_correction(world, copy);
+        }
+        cloner.clone_entity(world, confirmed, copy);
+    }
+
+    // Replay the inputs the server hasn't processed yet.
+    if history.is_empty() {
+        return;
+    }
+    let (tick, current) = {
+        let buffer = world.resource::<InputBuffer<I>>();
+        (buffer.tick, buffer.current.clone())
+    };
+    let time = world.get_resource::<Time>().copied();
+    if let Some(fixed) = world.get_resource::<Time<Fixed>>().map(Time::as_generic) {
+        world.insert_resource(fixed);
+    }
+    for (replayed_tick, input) in history {
+        {
+            let mut buffer = world.resource_mut::<InputBuffer<I>>();
+            buffer.tick = replayed_tick;
+            buffer.current = input;
+        }
+        world.run_schedule(PredictedUpdate);
+    }
+    if let Some(time) = time {
+        world.insert_resource(time);
+    }
+    let mut buffer = world.resource_mut::<InputBuffer<I>>();
+    buffer.tick = tick;
+    buffer.current = current;
+}
+
+fn begin_correction<C: Component + Clone>(world: &mut World, entity: Entity) {
+    let duration = world.resource::<PredictionSettings>().correction_duration;
+    let mut entity = world.entity_mut(entity);
+    let Some(value) = entity.get::<C>().cloned() else {
+        return;
+    };
+    if let Some(mut correction) = entity.get_mut::<Correction<C>>() {
+        correction.remaining = duration;
+    } else {
+        entity.insert(Correction {
+            shown: value,
+            simulated: None,
+            remaining: duration,
+        });
+    }
+}
+
+fn restore_simulated<C: Component<Mutability = Mutable> + Clone>(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut C, &mut Correction<C>)>,
+) {
+    for (entity, mut value, mut correction) in &mut query {
+        if let Some(simulated) = correction.simulated.take() {
+            *value = simulated;
+        }
+        if correction.remaining.is_zero() {
+            commands.entity(entity).remove::<Correction<C>>();
+        }
+    }
+}
+
+fn smooth_corrections<C: Component<Mutability = Mutable> + Clone + StableInterpolate>(
+    time: Res<Time>,
+    settings: Res<PredictionSettings>,
+    mut query: Query<(&mut C, &mut Correction<C>)>,
+) {
+    for (mut value, mut correction) in &mut query {
+        let correction = &mut *correction;
+        correction
+            .shown
+            .smooth_nudge(&*value, settings.correction_decay_rate, time.delta_secs());
+        correction.remaining = correction.remaining.saturating_sub(time.delta());
+        correction.simulated = Some(core::mem::replace(&mut *value, correction.shown.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use bevy_app::FixedMain;
+    use bevy_ecs::world::EntityRef;
+
+    use super::*;
+    use crate::{LoopbackNetwork, LoopbackTransport, NetworkTransport, ReplicationPlugin};
+
+    #[derive(Component, Reflect, Clone, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Position(i32);
+
+    /// The server moves twice as far as the clients predict, so every prediction is corrected.
+    fn server_movement(inputs: Res<ServerInputs<i32>>, mut positions: Query<&mut Position>) {
+        for (_, input) in inputs.iter() {
+            for mut position in &mut positions {
+                position.0 += 2 * input;
+            }
+        }
+    }
+
+    fn client_movement(
+        input: Res<InputBuffer<i32>>,
+        mut positions: Query<&mut Position, With<Predicted>>,
+    ) {
+        for mut position in &mut positions {
+            position.0 += input.current();
+        }
+    }
+
+    fn setup() -> (App, App) {
+        let network = LoopbackNetwork::default();
+        let server_transport = LoopbackTransport::listen(&network, "server");
+        let mut client_transport = LoopbackTransport::new(&network);
+        client_transport.connect("server").unwrap();
+
+        let mut server = App::new();
+        server
+            .add_plugins((
+                ReplicationPlugin::server(),
+                PredictionPlugin::<i32>::server(),
+            ))
+            .replicate::<Position>()
+            .predict::<Position>()
+            .add_systems(PredictedUpdate, server_movement)
+            .insert_resource(Transport::new(server_transport));
+
+        let mut client = App::new();
+        client
+            .add_plugins((
+                ReplicationPlugin::client(),
+                PredictionPlugin::<i32>::client(),
+            ))
+            .replicate::<Position>()
+            .predict::<Position>()
+            .add_systems(PredictedUpdate, client_movement)
+            .insert_resource(Transport::new(client_transport));
+
+        (server, client)
+    }
+
+    fn position(app: &mut App, filter: impl Fn(EntityRef) -> bool) -> i32 {
+        let world = app.world_mut();
+        let mut query = world.query::<(EntityRef, &Position)>();
+        let (_, position) = query
+            .iter(world)
+            .find(|(entity, _)| filter(*entity))
+            .unwrap();
+        position.0
+    }
+
+    #[test]
+    fn replays_unacknowledged_inputs() {
+        let (mut server, mut client) = setup();
+        server.world_mut().spawn((Replicated, Predict, Position(0)));
+        server.update();
+        client.update();
+
+        let predicted = |entity: EntityRef| entity.contains::<Predicted>();
+        let confirmed = |entity: EntityRef| entity.contains::<Confirmed>();
+        assert_eq!(position(&mut client, predicted), 0);
+
+        for input in [1, 2] {
+            client
+                .world_mut()
+                .resource_mut::<InputBuffer<i32>>()
+                .set(input);
+            client.world_mut().run_schedule(FixedMain);
+        }
+        assert_eq!(position(&mut client, predicted), 3);
+
+        // The server receives both inputs, but only processes the first one.
+        server.update();
+        server.world_mut().run_schedule(FixedMain);
+        server.update();
+
+        client.update();
+        assert_eq!(
+            client.world().resource::<PredictionClient>().last_ack(),
+            Some(1)
+        );
+        assert_eq!(position(&mut client, confirmed), 2);
+        // The second input is replayed on top of the server state.
+        assert_eq!(position(&mut client, predicted), 4);
+        assert_eq!(
+            client
+                .world()
+                .resource::<InputBuffer<i32>>()
+                .unacknowledged()
+                .collect::<Vec<_>>(),
+            vec![(2, &2)]
+        );
+    }
+
+    #[test]
+    fn drops_inputs_outside_the_window() {
+        let peer = PeerId(0);
+        let mut app = App::new();
+        app.add_event::<MessageReceived>()
+            .add_event::<PeerDisconnected>()
+            .init_resource::<ServerInputs<i32>>()
+            .add_systems(PreUpdate, receive_inputs::<i32>);
+        let send = |app: &mut App, ticks: core::ops::RangeInclusive<u32>| {
+            let message = InputMessage {
+                inputs: ticks.map(|tick| (tick, 0)).collect(),
+            };
+            app.world_mut().send_event(MessageReceived {
+                peer,
+                channel: PredictionPlugin::<i32>::INPUT_CHANNEL,
+                payload: postcard::to_allocvec(&message).unwrap(),
+            });
+            app.update();
+        };
+
+        // The queue of a client is capped.
+        send(&mut app, 1..=100);
+        let inputs = app.world().resource::<ServerInputs<i32>>();
+        assert_eq!(inputs.peers[&peer].queue.len(), MAX_QUEUED_INPUTS);
+
+        // Inputs already processed or too far ahead are dropped.
+        {
+            let mut inputs = app.world_mut().resource_mut::<ServerInputs<i32>>();
+            let state = inputs.peers.get_mut(&peer).unwrap();
+            state.queue.clear();
+            state.last_processed = Some(10);
+        }
+        send(&mut app, 5..=12);
+        send(&mut app, 10 + MAX_INPUT_AHEAD..=11 + MAX_INPUT_AHEAD);
+        let inputs = app.world().resource::<ServerInputs<i32>>();
+        let ticks: Vec<u32> = inputs.peers[&peer]
+            .queue
+            .iter()
+            .map(|(tick, _)| *tick)
+            .collect();
+        assert_eq!(ticks, vec![11, 12, 10 + MAX_INPUT_AHEAD]);
+    }
+}
@@ -0,0 +1,583 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::any::TypeId;
+
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_ecs::{
+    component::{Component, ComponentId, Tick},
+    entity::{hash_map::EntityHashMap, hash_set::EntityHashSet, Entity},
+    event::{EventCursor, Events},
+    query::{QueryState, With},
+    reflect::{AppTypeRegistry, ReflectComponent},
+    resource::Resource,
+    schedule::{common_conditions::resource_exists, IntoScheduleConfigs, SystemSet},
+    system::Local,
+    world::{EntityRef, Mut, World},
+};
+use bevy_platform_support::collections::HashMap;
+use bevy_reflect::{
+    prelude::ReflectDefault, GetTypeRegistration, PartialReflect, Reflect, TypeRegistry,
+};
+use bevy_scene::{
+    serde::{SceneDeserializer, SceneSerializer},
+    DynamicEntity, DynamicScene,
+};
+use log::{error, warn};
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+
+use crate::{
+    Channel, MessageReceived, PeerConnected, PeerDisconnected, PeerId, Transport, TransportPlugin,
+    TransportSet,
+};
+
+/// Marks an entity to be replicated from the server to its clients.
+///
+/// Only the components registered with [`AppReplicationExt::replicate`] are sent.
+/// Despawning the entity, or removing this component, despawns it on the clients.
+///
+/// On clients, this component is added to every entity spawned by replication.
+#[derive(Component, Reflect, Default, Clone, Copy, Debug)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct Replicated;
+
+/// System sets in which replication runs.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReplicationSet {
+    /// Clients apply received updates to their world, in [`PreUpdate`].
+    Receive,
+    /// The server sends the changes made during the frame to its clients, in [`PostUpdate`].
+    Send,
+}
+
+/// Which side of the connection a [`ReplicationPlugin`] runs on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReplicationRole {
+    /// The authoritative side, which sends its state to clients.
+    Server,
+    /// The side receiving and applying the state of a server.
+    Client,
+}
+
+/// Replicates [`Replicated`] entities from a server world to client worlds.
+///
+/// Both sides must register the same replicated components, in any order, with
+/// [`AppReplicationExt::replicate`], and insert a [`Transport`] resource connecting them.
+/// Updates are sent on [`ReplicationPlugin::CHANNEL`].
+///
+/// # Server
+///
+/// For each connected peer, the server keeps track of the entities the peer knows about and
+/// of when it was last updated. Every frame, it sends the registered components that were added
+/// or changed since then, entities that became visible with all of their registered components,
+/// and the entities that were despawned or became hidden. Which entities each peer can see can be
+/// customized with [`ReplicationServer::set_interest`].
+///
+/// Removing a registered component from a replicated entity is not replicated.
+///
+/// # Client
+///
+/// The client applies the updates to its world as they arrive, spawning a local entity for each
+/// server entity. [`MapEntities`](bevy_ecs::entity::MapEntities) components are mapped to local
+/// entities, and the mapping is available through [`ReplicationClient`].
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_net::{AppReplicationExt, ReplicationPlugin};
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component)]
+/// struct Health(u32);
+///
+/// App::new()
+///     .add_plugins(ReplicationPlugin::server())
+///     .replicate::<Health>()
+///     .run();
+/// ```
+pub struct ReplicationPlugin {
+    /// Which side of the connection this app runs.
+    pub role: ReplicationRole,
+}
+
+impl ReplicationPlugin {
+    /// The reliable [`Channel`] replication messages are sent on.
+    pub const CHANNEL: Channel = Channel::reliable(255);
+
+    /// Creates a [`ReplicationPlugin`] for the server side.
+    pub fn server() -> Self {
+        Self {
+            role: ReplicationRole::Server,
+        }
+    }
+
+    /// Creates a [`ReplicationPlugin`] for the client side.
+    pub fn client() -> Self {
+        Self {
+            role: ReplicationRole::Client,
+        }
+    }
+}
+
+impl Plugin for ReplicationPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<TransportPlugin>() {
+            app.add_plugins(TransportPlugin);
+        }
+
+        app.register_type::<Replicated>()
+            .init_resource::<AppTypeRegistry>()
+            .init_resource::<ReplicationRegistry>();
+
+        match self.role {
+            ReplicationRole::Server => {
+                app.init_resource::<ReplicationServer>().add_systems(
+                    PostUpdate,
+                    send_replication
+                        .in_set(ReplicationSet::Send)
+                        .run_if(resource_exists::<Transport>),
+                );
+            }
+            ReplicationRole::Client => {
+                app.init_resource::<ReplicationClient>().add_systems(
+                    PreUpdate,
+                    receive_replication
+                        .in_set(ReplicationSet::Receive)
+                        .after(TransportSet::Poll),
+                );
+            }
+        }
+    }
+}
+
+/// The components replicated by the [`ReplicationPlugin`].
+///
+/// Use [`AppReplicationExt::replicate`] to register a component.
+#[derive(Resource, Default, Debug)]
+pub struct ReplicationRegistry {
+    components: Vec<(ComponentId, TypeId)>,
+}
+
+impl ReplicationRegistry {
+    /// Returns `true` if the component with the given id is replicated.
+    pub fn contains(&self, component_id: ComponentId) -> bool {
+        self.components.iter().any(|(id, _)| *id == component_id)
+    }
+
+    /// Returns an iterator over the ids of the replicated components.
+    pub fn component_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.components.iter().map(|(id, _)| *id)
+    }
+}
+
+/// Extension trait to register replicated components on an [`App`].
+pub trait AppReplicationExt {
+    /// Replicates the component `C` on [`Replicated`] entities.
+    ///
+    /// `C` is registered in the [`AppTypeRegistry`], and must reflect [`Component`]
+    /// with `#[reflect(Component)]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` does not reflect [`Component`].
+    fn replicate<C: Component + GetTypeRegistration>(&mut self) -> &mut Self;
+}
+
+impl AppReplicationExt for App {
+    fn replicate<C: Component + GetTypeRegistration>(&mut self) -> &mut Self {
+        self.register_type::<C>();
+
+        let world = self.world_mut();
+        let reflects_component = world
+            .resource::<AppTypeRegistry>()
+            .read()
+            .get_type_data::<ReflectComponent>(TypeId::of::<C>())
+            .is_some();
+        assert!(
+            reflects_component,
+            "replicated component `{}` must reflect `Component`. consider adding `#[reflect(Component)]` to your type",
+            core::any::type_name::<C>()
+        );
+
+        let component_id = world.register_component::<C>();
+        let mut registry = world.get_resource_or_init::<ReplicationRegistry>();
+        if !registry.contains(component_id) {
+            registry.components.push((component_id, TypeId::of::<C>()));
+        }
+        self
+    }
+}
+
+/// Decides whether a [`Replicated`] entity is replicated to a peer.
+pub type InterestFn = dyn Fn(PeerId, EntityRef) -> bool + Send + Sync;
+
+/// The server-side replication state.
+#[derive(Resource, Default)]
+pub struct ReplicationServer {
+    peers: HashMap<PeerId, PeerState>,
+    interest: Option<Box<InterestFn>>,
+}
+
+#[derive(Default)]
+struct PeerState {
+    /// The tick up to which changes have been sent, or `None` if nothing was sent yet.
+    last_sent: Option<Tick>,
+    /// The entities that were replicated to the peer.
+    visible: EntityHashSet,
+    /// The input acknowledgement to send to the peer.
+    input_ack: Option<u32>,
+    /// The last input acknowledgement sent to the peer.
+    sent_input_ack: Option<u32>,
+}
+
+impl ReplicationServer {
+    /// Returns an iterator over the connected peers.
+    pub fn peers(&self) -> impl Iterator<Item = PeerId> + '_ {
+        self.peers.keys().copied()
+    }
+
+    /// Returns `true` if `entity` is currently replicated to `peer`.
+    pub fn is_visible(&self, peer: PeerId, entity: Entity) -> bool {
+        self.peers
+            .get(&peer)
+            .is_some_and(|state| state.visible.contains(&entity))
+    }
+
+    /// Sets the last input tick of `peer` processed by the server, sent along with the next update.
+    ///
+    /// This lets the client know which of its inputs are reflected in the replicated state.
+    /// See [`PredictionPlugin`](crate::PredictionPlugin).
+    pub fn set_input_ack(&mut self, peer: PeerId, tick: u32) {
+        if let Some(state) = self.peers.get_mut(&peer) {
+            state.input_ack = Some(tick);
+        }
+    }
+
+    /// Sets the function deciding which [`Replicated`] entities are sent to each peer.
+    ///
+    /// The function is evaluated every frame, for every peer and entity. When it stops returning
+    /// `true` for an entity, the entity is despawned on that peer, and it is sent again in full
+    /// once it becomes visible again.
+    ///
+    /// By default, every [`Replicated`] entity is sent to every peer.
+    pub fn set_interest(
+        &mut self,
+        interest: impl Fn(PeerId, EntityRef) -> bool + Send + Sync + 'static,
+    ) {
+        self.interest = Some(Box::new(interest));
+    }
+
+    /// Removes the function set by [`ReplicationServer::set_interest`],
+    /// making every [`Replicated`] entity visible to every peer.
+    pub fn clear_interest(&mut self) {
+        self.interest = None;
+    }
+}
+
+/// The client-side replication state.
+#[derive(Resource, Default)]
+pub struct ReplicationClient {
+    entity_map: EntityHashMap<Entity>,
+    server: Option<PeerId>,
+    last_tick: Option<u32>,
+    input_ack: Option<u32>,
+}
+
+impl ReplicationClient {
+    /// Returns the map from server entities to the local entities they were replicated to.
+    pub fn entity_map(&self) -> &EntityHashMap<Entity> {
+        &self.entity_map
+    }
+
+    /// Returns the local entity the given server entity was replicated to.
+    pub fn local_entity(&self, server_entity: Entity) -> Option<Entity> {
+        self.entity_map.get(&server_entity).copied()
+    }
+
+    /// Returns the peer the last update was received from.
+    pub fn server(&self) -> Option<PeerId> {
+        self.server
+    }
+
+    /// Returns the server change tick of the last applied update.
+    pub fn last_tick(&self) -> Option<u32> {
+        self.last_tick
+    }
+
+    /// Returns the last input tick of this client the server has processed, as set with
+    /// [`ReplicationServer::set_input_ack`].
+    pub fn input_ack(&self) -> Option<u32> {
+        self.input_ack
+    }
+}
+
+/// The part of a replication message preceding the serialized [`DynamicScene`].
+#[derive(Serialize, Deserialize)]
+struct ReplicationHeader {
+    tick: u32,
+    input_ack: Option<u32>,
+    despawns: Vec<Entity>,
+}
+
+fn send_replication(
+    world: &mut World,
+    replicated: &mut QueryState<EntityRef<'static>, With<Replicated>>,
+    mut connected: Local<EventCursor<PeerConnected>>,
+    mut disconnected: Local<EventCursor<PeerDisconnected>>,
+) {
+    world.resource_scope(|world, mut transport: Mut<Transport>| {
+        world.resource_scope(|world, mut server: Mut<ReplicationServer>| {
+            let ReplicationServer { peers, interest } = &mut *server;
+
+            for event in connected.read(world.resource::<Events<PeerConnected>>()) {
+                peers.insert(event.peer, PeerState::default());
+            }
+            for event in disconnected.read(world.resource::<Events<PeerDisconnected>>()) {
+                peers.remove(&event.peer);
+            }
+
+            let this_run = world.change_tick();
+            let world = &*world;
+            replicated.update_archetypes(world);
+            let registry = world.resource::<ReplicationRegistry>();
+            let type_registry = world.resource::<AppTypeRegistry>().read();
+
+            for (&peer, state) in peers.iter_mut() {
+                let mut scene = DynamicScene::default();
+                let mut visible = EntityHashSet::default();
+
+                for entity in replicated.iter_manual(world) {
+                    if interest
+                        .as_ref()
+                        .is_some_and(|interest| !interest(peer, entity))
+                    {
+                        continue;
+                    }
+                    visible.insert(entity.id());
+
+                    let is_new = !state.visible.contains(&entity.id());
+                    let mut components = Vec::new();
+                    for &(component_id, type_id) in &registry.components {
+                        let Some(ticks) = entity.get_change_ticks_by_id(component_id) else {
+                            continue;
+                        };
+                        let is_changed = match state.last_sent {
+                            Some(last_sent) => ticks.is_changed(last_sent, this_run),
+                            None => true,
+                        };
+                        if !is_new && !is_changed {
+                            continue;
+                        }
+                        if let Some(component) = reflect_component(&type_registry, type_id, entity)
+                        {
+                            components.push(component);
+                        }
+                    }
+
+                    if is_new || !components.is_empty() {
+                        scene.entities.push(DynamicEntity {
+                            entity: entity.id(),
+                            components,
+                        });
+                    }
+                }
+
+                let despawns: Vec<Entity> = state.visible.difference(&visible).copied().collect();
+                state.visible = visible;
+                state.last_sent = Some(this_run);
+
+                if scene.entities.is_empty()
+                    && despawns.is_empty()
+                    && state.input_ack == state.sent_input_ack
+                {
+                    continue;
+                }
+                state.sent_input_ack = state.input_ack;
+
+                let header = ReplicationHeader {
+                    tick: this_run.get(),
+                    input_ack: state.input_ack,
+                    despawns,
+                };
+                match serialize_message(&header, &scene, &type_registry) {
+                    Ok(payload) => transport.send(peer, ReplicationPlugin::CHANNEL, &payload),
+                    Err(err) => {
+                        error!("Failed to serialize replication message for {peer:?}: {err}");
+                    }
+                }
+            }
+        });
+    });
+}
+
+fn receive_replication(world: &mut World, mut received: Local<EventCursor<MessageReceived>>) {
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    world.resource_scope(|world, messages: Mut<Events<MessageReceived>>| {
+        world.resource_scope(|world, mut client: Mut<ReplicationClient>| {
+            for message in received.read(&messages) {
+                if message.channel != ReplicationPlugin::CHANNEL {
+                    continue;
+                }
+                let peer = message.peer;
+
+                let (header, scene) =
+                    match deserialize_message(&message.payload, &type_registry.read()) {
+                        Ok(message) => message,
+                        Err(err) => {
+                            warn!("Failed to deserialize replication message from {peer:?}: {err}");
+                            continue;
+                        }
+                    };
+
+                for server_entity in header.despawns {
+                    if let Some(entity) = client.entity_map.remove(&server_entity) {
+                        world.despawn(entity);
+                    }
+                }
+
+                if let Err(err) =
+                    scene.write_to_world_with(world, &mut client.entity_map, &type_registry)
+                {
+                    error!("Failed to apply replication message from {peer:?}: {err}");
+                }
+                for scene_entity in &scene.entities {
+                    if let Some(&entity) = client.entity_map.get(&scene_entity.entity) {
+                        world.entity_mut(entity).insert(Replicated);
+                    }
+                }
+
+                client.server = Some(peer);
+                client.last_tick = Some(header.tick);
+                if header.input_ack.is_some() {
+                    client.input_ack = header.input_ack;
+                }
+            }
+        });
+    });
+}
+
+fn reflect_component(
+    type_registry: &TypeRegistry,
+    type_id: TypeId,
+    entity: EntityRef,
+) -> Option<Box<dyn PartialReflect>> {
+    let component = type_registry
+        .get_type_data::<ReflectComponent>(type_id)?
+        .reflect(entity)?;
+    Some(
+        component
+            .reflect_clone()
+            .map(PartialReflect::into_partial_reflect)
+            .unwrap_or_else(|_| component.to_dynamic()),
+    )
+}
+
+fn serialize_message(
+    header: &ReplicationHeader,
+    scene: &DynamicScene,
+    type_registry: &TypeRegistry,
+) -> Result<Vec<u8>, postcard::Error> {
+    let mut payload = postcard::to_allocvec(header)?;
+    payload.extend(postcard::to_allocvec(&SceneSerializer::new(
+        scene,
+        type_registry,
+    ))?);
+    Ok(payload)
+}
+
+fn deserialize_message(
+    payload: &[u8],
+    type_registry: &TypeRegistry,
+) -> Result<(ReplicationHeader, DynamicScene), postcard::Error> {
+    let (header, rest) = postcard::take_from_bytes::<ReplicationHeader>(payload)?;
+    let scene = SceneDeserializer { type_registry }
+        .deserialize(&mut postcard::Deserializer::from_bytes(rest))?;
+    Ok((header, scene))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LoopbackNetwork, LoopbackTransport, NetworkTransport};
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    fn setup() -> (App, App) {
+        let network = LoopbackNetwork::default();
+        let server_transport = LoopbackTransport::listen(&network, "server");
+        let mut client_transport = LoopbackTransport::new(&network);
+        client_transport.connect("server").unwrap();
+
+        let mut server = App::new();
+        server
+            .add_plugins(ReplicationPlugin::server())
+            .replicate::<Health>()
+            .insert_resource(Transport::new(server_transport));
+
+        let mut client = App::new();
+        client
+            .add_plugins(ReplicationPlugin::client())
+            .replicate::<Health>()
+            .insert_resource(Transport::new(client_transport));
+
+        (server, client)
+    }
+
+    fn local_entity(client: &App, server_entity: Entity) -> Option<Entity> {
+        client
+            .world()
+            .resource::<ReplicationClient>()
+            .local_entity(server_entity)
+    }
+
+    #[test]
+    fn replicates_changes_and_despawns() {
+        let (mut server, mut client) = setup();
+
+        let entity = server.world_mut().spawn((Replicated, Health(10))).id();
+        let not_replicated = server.world_mut().spawn(Health(5)).id();
+        server.update();
+        client.update();
+
+        let local = local_entity(&client, entity).unwrap();
+        assert_eq!(client.world().get::<Health>(local), Some(&Health(10)));
+        assert!(client.world().get::<Replicated>(local).is_some());
+        assert!(local_entity(&client, not_replicated).is_none());
+
+        server.world_mut().get_mut::<Health>(entity).unwrap().0 = 7;
+        server.update();
+        client.update();
+        assert_eq!(client.world().get::<Health>(local), Some(&Health(7)));
+
+        server.world_mut().despawn(entity);
+        server.update();
+        client.update();
+        assert!(local_entity(&client, entity).is_none());
+        assert!(client.world().get_entity(local).is_err());
+    }
+
+    #[test]
+    fn interest_controls_visibility() {
+        let (mut server, mut client) = setup();
+        server
+            .world_mut()
+            .resource_mut::<ReplicationServer>()
+            .set_interest(|_, entity| entity.get::<Health>().is_some_and(|health| health.0 > 0));
+
+        let entity = server.world_mut().spawn((Replicated, Health(0))).id();
+        server.update();
+        client.update();
+        assert!(local_entity(&client, entity).is_none());
+
+        server.world_mut().get_mut::<Health>(entity).unwrap().0 = 3;
+        server.update();
+        client.update();
+        let local = local_entity(&client, entity).unwrap();
+        assert_eq!(client.world().get::<Health>(local), Some(&Health(3)));
+
+        server.world_mut().get_mut::<Health>(entity).unwrap().0 = 0;
+        server.update();
+        client.update();
+        assert!(client.world().get_entity(local).is_err());
+    }
+}
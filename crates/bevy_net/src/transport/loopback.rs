@@ -0,0 +1,254 @@
+use alloc::{
+    collections::VecDeque,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use std::sync::{Mutex, PoisonError};
+
+use bevy_platform_support::collections::{HashMap, HashSet};
+
+use super::{Channel, NetworkTransport, PeerId, TransportError, TransportEvent};
+
+/// An in-memory network connecting [`LoopbackTransport`]s.
+///
+/// Cloning a [`LoopbackNetwork`] returns a handle to the same network.
+#[derive(Clone, Default)]
+pub struct LoopbackNetwork {
+    inner: Arc<Mutex<LoopbackNetworkInner>>,
+}
+
+#[derive(Default)]
+struct LoopbackNetworkInner {
+    /// The inbox of every endpoint, indexed by endpoint.
+    inboxes: Vec<VecDeque<(usize, LoopbackPacket)>>,
+    /// The endpoints listening for connections, by address.
+    listeners: HashMap<String, usize>,
+}
+
+enum LoopbackPacket {
+    Connect,
+    Accept,
+    Disconnect,
+    Message(Channel, Vec<u8>),
+}
+
+impl LoopbackNetwork {
+    fn register(&self, address: Option<&str>) -> usize {
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        let endpoint = inner.inboxes.len();
+        inner.inboxes.push(VecDeque::new());
+        if let Some(address) = address {
+            inner.listeners.insert(address.to_string(), endpoint);
+        }
+        endpoint
+    }
+
+    fn push(&self, from: usize, to: usize, packet: LoopbackPacket) {
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        inner.inboxes[to].push_back((from, packet));
+    }
+}
+
+/// A [`NetworkTransport`] exchanging messages in memory with other transports on the same
+/// [`LoopbackNetwork`].
+///
+/// Messages are never lost or reordered, whatever their [`Delivery`](super::Delivery).
+/// The [`PeerId`] of a peer is the same for every transport of the network.
+///
+/// ```
+/// # use bevy_net::{LoopbackNetwork, LoopbackTransport, NetworkTransport, TransportEvent};
+/// let network = LoopbackNetwork::default();
+/// let mut server = LoopbackTransport::listen(&network, "server");
+/// let mut client = LoopbackTransport::new(&network);
+///
+/// let server_id = client.connect("server").unwrap();
+///
+/// let mut events = Vec::new();
+/// server.poll(&mut events);
+/// client.poll(&mut events);
+/// assert!(events.contains(&TransportEvent::Connected(server_id)));
+/// ```
+pub struct LoopbackTransport {
+    network: LoopbackNetwork,
+    endpoint: usize,
+    listening: bool,
+    connecting: HashSet<PeerId>,
+    connected: HashSet<PeerId>,
+    pending: Vec<TransportEvent>,
+}
+
+impl LoopbackTransport {
+    /// Creates a transport on `network` that can connect to listening transports,
+    /// but does not accept connections.
+    pub fn new(network: &LoopbackNetwork) -> Self {
+        Self::with_endpoint(network, network.register(None), false)
+    }
+
+    /// Creates a transport on `network` accepting connections made to `address`.
+    ///
+    /// If another transport was listening at `address`, it stops receiving new connections.
+    pub fn listen(network: &LoopbackNetwork, address: &str) -> Self {
+        Self::with_endpoint(network, network.register(Some(address)), true)
+    }
+
+    fn with_endpoint(network: &LoopbackNetwork, endpoint: usize, listening: bool) -> Self {
+        Self {
+            network: network.clone(),
+            endpoint,
+            listening,
+            connecting: HashSet::default(),
+            connected: HashSet::default(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Returns the id other transports of the network know this transport as.
+    pub fn id(&self) -> PeerId {
+        PeerId(self.endpoint as u64)
+    }
+
+    fn push(&self, peer: PeerId, packet: LoopbackPacket) {
+        self.network.push(self.endpoint, peer.0 as usize, packet);
+    }
+}
+
+impl NetworkTransport for LoopbackTransport {
+    fn connect(&mut self, address: &str) -> Result<PeerId, TransportError> {
+        let endpoint = self
+            .network
+            .inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .listeners
+            .get(address)
+            .copied()
+            .ok_or_else(|| TransportError::NotListening(address.to_string()))?;
+        let peer = PeerId(endpoint as u64);
+
+        if !self.connected.contains(&peer) && self.connecting.insert(peer) {
+            self.push(peer, LoopbackPacket::Connect);
+        }
+        Ok(peer)
+    }
+
+    fn disconnect(&mut self, peer: PeerId) {
+        if self.connected.remove(&peer) | self.connecting.remove(&peer) {
+            self.push(peer, LoopbackPacket::Disconnect);
+            self.pending.push(TransportEvent::Disconnected(peer));
+        }
+    }
+
+    fn send(&mut self, peer: PeerId, channel: Channel, payload: &[u8]) {
+        if self.connected.contains(&peer) {
+            self.push(peer, LoopbackPacket::Message(channel, payload.to_vec()));
+        }
+    }
+
+    fn poll(&mut self, events: &mut Vec<TransportEvent>) {
+        events.append(&mut self.pending);
+
+        let inbox = core::mem::take(
+            &mut self
+                .network
+                .inner
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .inboxes[self.endpoint],
+        );
+        for (from, packet) in inbox {
+            let peer = PeerId(from as u64);
+            match packet {
+                LoopbackPacket::Connect if self.listening => {
+                    if self.connected.insert(peer) {
+                        events.push(TransportEvent::Connected(peer));
+                    }
+                    self.push(peer, LoopbackPacket::Accept);
+                }
+                LoopbackPacket::Connect => self.push(peer, LoopbackPacket::Disconnect),
+                LoopbackPacket::Accept => {
+                    if self.connecting.remove(&peer) {
+                        self.connected.insert(peer);
+                        events.push(TransportEvent::Connected(peer));
+                    }
+                }
+                LoopbackPacket::Disconnect => {
+                    if self.connected.remove(&peer) | self.connecting.remove(&peer) {
+                        events.push(TransportEvent::Disconnected(peer));
+                    }
+                }
+                LoopbackPacket::Message(channel, payload) => {
+                    if self.connected.contains(&peer) {
+                        events.push(TransportEvent::Received {
+                            peer,
+                            channel,
+                            payload,
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Drop for LoopbackTransport {
+    fn drop(&mut self) {
+        for &peer in self.connected.iter().chain(&self.connecting) {
+            self.push(peer, LoopbackPacket::Disconnect);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn connect_send_and_disconnect() {
+        let network = LoopbackNetwork::default();
+        let mut server = LoopbackTransport::listen(&network, "server");
+        let mut client = LoopbackTransport::new(&network);
+
+        assert!(matches!(
+            client.connect("elsewhere"),
+            Err(TransportError::NotListening(_))
+        ));
+        let server_id = client.connect("server").unwrap();
+        let client_id = client.id();
+
+        let mut events = Vec::new();
+        server.poll(&mut events);
+        assert_eq!(events, vec![TransportEvent::Connected(client_id)]);
+
+        events.clear();
+        client.poll(&mut events);
+        assert_eq!(events, vec![TransportEvent::Connected(server_id)]);
+
+        client.send(server_id, Channel::reliable(0), b"hello");
+        client.send(server_id, Channel::unreliable(1), b"world");
+        events.clear();
+        server.poll(&mut events);
+        assert_eq!(
+            events,
+            vec![
+                TransportEvent::Received {
+                    peer: client_id,
+                    channel: Channel::reliable(0),
+                    payload: b"hello".to_vec(),
+                },
+                TransportEvent::Received {
+                    peer: client_id,
+                    channel: Channel::unreliable(1),
+                    payload: b"world".to_vec(),
+                },
+            ]
+        );
+
+        drop(client);
+        events.clear();
+        server.poll(&mut events);
+        assert_eq!(events, vec![TransportEvent::Disconnected(client_id)]);
+    }
+}
@@ -0,0 +1,252 @@
+//! Connections and message delivery between peers.
+//!
+//! See [`NetworkTransport`] for more details.
+
+mod loopback;
+#[cfg(not(target_family = "wasm"))]
+mod udp;
+
+pub use loopback::*;
+#[cfg(not(target_family = "wasm"))]
+pub use udp::*;
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    event::{Event, EventWriter},
+    resource::Resource,
+    schedule::{common_conditions::resource_exists, IntoScheduleConfigs, SystemSet},
+    system::{Local, ResMut},
+};
+use bevy_reflect::Reflect;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Identifies a remote peer of a [`NetworkTransport`].
+///
+/// The meaning of the id is up to the transport: a listening transport usually assigns a new id
+/// to every accepted connection, while a client usually only knows about the server it connected to.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Reflect, Serialize, Deserialize,
+)]
+#[reflect(Clone, Debug, PartialEq, Hash)]
+pub struct PeerId(pub u64);
+
+/// How the messages sent on a [`Channel`] are delivered.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Reflect, Serialize, Deserialize,
+)]
+#[reflect(Clone, Debug, PartialEq, Hash)]
+pub enum Delivery {
+    /// Messages are delivered exactly once, in the order they were sent.
+    ///
+    /// Transports may limit the size of reliable messages they accept from their peers.
+    Reliable,
+    /// Messages may be lost or arrive out of order, but are never delayed by earlier messages.
+    ///
+    /// Transports may drop unreliable messages that do not fit in a single packet.
+    Unreliable,
+}
+
+/// A stream of messages with its own [`Delivery`] guarantees.
+///
+/// Channels are identified by their id and delivery: a reliable and an unreliable channel
+/// with the same id are distinct. Ids above [`Channel::MAX_USER_ID`] are reserved for the
+/// systems of this crate.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Reflect, Serialize, Deserialize,
+)]
+#[reflect(Clone, Debug, PartialEq, Hash)]
+pub struct Channel {
+    /// The id of the channel.
+    pub id: u8,
+    /// How the messages sent on this channel are delivered.
+    pub delivery: Delivery,
+}
+
+impl Channel {
+    /// The largest channel id available to applications.
+    pub const MAX_USER_ID: u8 = 239;
+
+    /// Creates a reliable [`Channel`] with the given id.
+    pub const fn reliable(id: u8) -> Self {
+        Self {
+            id,
+            delivery: Delivery::Reliable,
+        }
+    }
+
+    /// Creates an unreliable [`Channel`] with the given id.
+    pub const fn unreliable(id: u8) -> Self {
+        Self {
+            id,
+            delivery: Delivery::Unreliable,
+        }
+    }
+}
+
+/// Something that happened on a [`NetworkTransport`] since it was last polled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransportEvent {
+    /// A connection to the peer was established, either by accepting an incoming connection
+    /// or by completing a connection started with [`NetworkTransport::connect`].
+    Connected(PeerId),
+    /// The connection to the peer was closed, lost, or could not be established.
+    Disconnected(PeerId),
+    /// A message was received from the peer.
+    Received {
+        /// The peer that sent the message.
+        peer: PeerId,
+        /// The channel the message was sent on.
+        channel: Channel,
+        /// The content of the message.
+        payload: Vec<u8>,
+    },
+}
+
+/// An error returned by [`NetworkTransport::connect`].
+#[derive(Error, Debug)]
+pub enum TransportError {
+    /// The address could not be parsed or resolved.
+    #[error("invalid address `{0}`")]
+    InvalidAddress(String),
+    /// Nothing is listening at the address.
+    #[error("no transport is listening at `{0}`")]
+    NotListening(String),
+    /// An I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// A connection-oriented transport used to exchange messages with remote peers.
+///
+/// A transport can start connections to other peers with [`NetworkTransport::connect`], and,
+/// if it was created as a listener, accepts incoming connections. Either way, established
+/// connections are reported by [`TransportEvent::Connected`] the next time it is polled.
+///
+/// Implementations own the underlying sockets or queues. They are driven from the main thread
+/// by [`TransportPlugin`], and must not block.
+///
+/// Built-in implementations:
+/// - [`LoopbackTransport`], an in-memory transport, useful for tests and local multiplayer.
+/// - [`UdpTransport`], a UDP transport with reliable channels, on all platforms but the web.
+pub trait NetworkTransport: Send + Sync + 'static {
+    /// Starts connecting to the peer at `address`, returning the id it will be known as.
+    ///
+    /// The format of the address depends on the transport.
+    fn connect(&mut self, address: &str) -> Result<PeerId, TransportError>;
+
+    /// Closes the connection to `peer`.
+    ///
+    /// A [`TransportEvent::Disconnected`] event is reported the next time the transport is polled.
+    fn disconnect(&mut self, peer: PeerId);
+
+    /// Queues `payload` to be sent to `peer` on the given `channel`.
+    ///
+    /// Sending to a peer that isn't connected does nothing.
+    fn send(&mut self, peer: PeerId, channel: Channel, payload: &[u8]);
+
+    /// Pushes everything that happened since the last poll to `events`, in order.
+    fn poll(&mut self, events: &mut Vec<TransportEvent>);
+}
+
+/// The [`NetworkTransport`] used by the networking systems of this crate.
+///
+/// Insert this resource once the transport has been created. Systems that send or receive
+/// messages do nothing while it is missing.
+#[derive(Resource, Deref, DerefMut)]
+pub struct Transport(pub Box<dyn NetworkTransport>);
+
+impl Transport {
+    /// Creates a new [`Transport`] resource from the given transport.
+    pub fn new(transport: impl NetworkTransport) -> Self {
+        Self(Box::new(transport))
+    }
+}
+
+/// A connection to a peer was established.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PeerConnected {
+    /// The connected peer.
+    pub peer: PeerId,
+}
+
+/// The connection to a peer was closed, lost, or could not be established.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PeerDisconnected {
+    /// The disconnected peer.
+    pub peer: PeerId,
+}
+
+/// A message was received from a peer.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct MessageReceived {
+    /// The peer that sent the message.
+    pub peer: PeerId,
+    /// The channel the message was sent on.
+    pub channel: Channel,
+    /// The content of the message.
+    pub payload: Vec<u8>,
+}
+
+/// System sets in which the [`Transport`] is driven.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TransportSet {
+    /// The [`Transport`] is polled and its events are sent as [`PeerConnected`],
+    /// [`PeerDisconnected`] and [`MessageReceived`] events, in [`PreUpdate`].
+    Poll,
+}
+
+/// Polls the [`Transport`] resource every frame, turning what happened into ECS events.
+///
+/// This plugin is added automatically by the other plugins of this crate.
+#[derive(Default)]
+pub struct TransportPlugin;
+
+impl Plugin for TransportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PeerConnected>()
+            .add_event::<PeerDisconnected>()
+            .add_event::<MessageReceived>()
+            .add_systems(
+                PreUpdate,
+                poll_transport
+                    .in_set(TransportSet::Poll)
+                    .run_if(resource_exists::<Transport>),
+            );
+    }
+}
+
+/// Polls the [`Transport`] and sends the corresponding events.
+pub fn poll_transport(
+    mut transport: ResMut<Transport>,
+    mut events: Local<Vec<TransportEvent>>,
+    mut connected: EventWriter<PeerConnected>,
+    mut disconnected: EventWriter<PeerDisconnected>,
+    mut received: EventWriter<MessageReceived>,
+) {
+    transport.poll(&mut events);
+    for event in events.drain(..) {
+        match event {
+            TransportEvent::Connected(peer) => {
+                connected.write(PeerConnected { peer });
+            }
+            TransportEvent::Disconnected(peer) => {
+                disconnected.write(PeerDisconnected { peer });
+            }
+            TransportEvent::Received {
+                peer,
+                channel,
+                payload,
+            } => {
+                received.write(MessageReceived {
+                    peer,
+                    channel,
+                    payload,
+                });
+            }
+        }
+    }
+}
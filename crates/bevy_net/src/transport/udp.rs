@@ -0,0 +1,586 @@
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+use core::{net::SocketAddr, time::Duration};
+use std::{
+    io::{self, ErrorKind},
+    net::{ToSocketAddrs, UdpSocket},
+};
+
+use bevy_platform_support::{collections::HashMap, time::Instant};
+use log::{debug, warn};
+
+use super::{Channel, Delivery, NetworkTransport, PeerId, TransportError, TransportEvent};
+
+/// Prefix of every packet, used to ignore datagrams that weren't sent by a [`UdpTransport`].
+const PROTOCOL_ID: [u8; 4] = *b"bvn0";
+/// The size of the packet header: protocol id and packet kind.
+const HEADER_SIZE: usize = PROTOCOL_ID.len() + 1;
+/// The largest amount of message data sent in a single datagram.
+///
+/// Larger reliable messages are split into fragments, while larger unreliable messages are dropped.
+const MAX_FRAGMENT_SIZE: usize = 1150;
+/// The size of the buffer datagrams are received into.
+const RECEIVE_BUFFER_SIZE: usize = 1500;
+/// How far ahead of the next expected fragment a reliable fragment is buffered.
+const RECEIVE_WINDOW: u32 = 4096;
+
+const CONNECT: u8 = 0;
+const ACCEPT: u8 = 1;
+const DISCONNECT: u8 = 2;
+const KEEP_ALIVE: u8 = 3;
+const UNRELIABLE: u8 = 4;
+const RELIABLE: u8 = 5;
+const ACK: u8 = 6;
+
+/// Timings and limits used by a [`UdpTransport`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UdpSettings {
+    /// How long to wait for an acknowledgement before resending a reliable fragment.
+    pub resend_interval: Duration,
+    /// How often a connection request is resent until it is accepted.
+    pub connect_interval: Duration,
+    /// How long a connection stays idle before a keep-alive packet is sent.
+    pub keep_alive_interval: Duration,
+    /// How long without receiving anything from a peer before it is considered disconnected.
+    pub timeout: Duration,
+    /// The largest reliable message, in bytes, that is sent or accepted.
+    ///
+    /// Larger messages are dropped when sent, and a peer sending one is disconnected.
+    pub max_message_size: usize,
+    /// The largest number of peers a listening transport accepts connections from.
+    ///
+    /// Connection requests are refused while this many peers are connected.
+    pub max_peers: usize,
+}
+
+impl Default for UdpSettings {
+    fn default() -> Self {
+        Self {
+            resend_interval: Duration::from_millis(200),
+            connect_interval: Duration::from_millis(250),
+            keep_alive_interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(10),
+            max_message_size: 8 * 1024 * 1024,
+            max_peers: 256,
+        }
+    }
+}
+
+/// A [`NetworkTransport`] over UDP.
+///
+/// Reliable channels are implemented on top of UDP with acknowledgements and resends, and
+/// reliable messages up to [`UdpSettings::max_message_size`] are split into fragments that fit in
+/// a single datagram. Unreliable messages are sent as-is, and dropped if they are larger than about
+/// a kilobyte.
+///
+/// Addresses passed to [`NetworkTransport::connect`] are resolved with [`ToSocketAddrs`],
+/// for example `"127.0.0.1:5000"` or `"example.com:5000"`.
+///
+/// This transport does not encrypt or authenticate packets.
+pub struct UdpTransport {
+    socket: UdpSocket,
+    listening: bool,
+    settings: UdpSettings,
+    peers: HashMap<PeerId, UdpPeer>,
+    addresses: HashMap<SocketAddr, PeerId>,
+    next_peer_id: u64,
+    pending: Vec<TransportEvent>,
+    buffer: Vec<u8>,
+}
+
+struct UdpPeer {
+    address: SocketAddr,
+    connected: bool,
+    last_received: Instant,
+    last_sent: Instant,
+    channels: HashMap<u8, ReliableChannel>,
+}
+
+#[derive(Default)]
+struct ReliableChannel {
+    next_sequence: u32,
+    unacked: BTreeMap<u32, UnackedFragment>,
+    next_expected: u32,
+    received: BTreeMap<u32, (bool, Vec<u8>)>,
+    message: Vec<u8>,
+    message_fragments: usize,
+}
+
+struct UnackedFragment {
+    packet: Vec<u8>,
+    sent_at: Instant,
+}
+
+impl UdpTransport {
+    /// Creates a transport bound to `address` that can connect to listening transports,
+    /// but does not accept connections.
+    ///
+    /// Use `"0.0.0.0:0"` to let the operating system pick the port.
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        Self::new(address, false)
+    }
+
+    /// Creates a transport bound to `address`, accepting incoming connections.
+    pub fn listen(address: impl ToSocketAddrs) -> io::Result<Self> {
+        Self::new(address, true)
+    }
+
+    fn new(address: impl ToSocketAddrs, listening: bool) -> io::Result<Self> {
+        let socket = UdpSocket::bind(address)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            listening,
+            settings: UdpSettings::default(),
+            peers: HashMap::default(),
+            addresses: HashMap::default(),
+            next_peer_id: 0,
+            pending: Vec::new(),
+            buffer: alloc::vec![0; RECEIVE_BUFFER_SIZE],
+        })
+    }
+
+    /// Replaces the [`UdpSettings`] of this transport.
+    #[must_use]
+    pub fn with_settings(mut self, settings: UdpSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Returns the address this transport is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Returns the address of the given peer.
+    pub fn peer_addr(&self, peer: PeerId) -> Option<SocketAddr> {
+        self.peers.get(&peer).map(|state| state.address)
+    }
+
+    fn add_peer(&mut self, address: SocketAddr, connected: bool) -> PeerId {
+        let peer = PeerId(self.next_peer_id);
+        self.next_peer_id += 1;
+        let now = Instant::now();
+        self.peers.insert(
+            peer,
+            UdpPeer {
+                address,
+                connected,
+                last_received: now,
+                last_sent: now,
+                channels: HashMap::default(),
+            },
+        );
+        self.addresses.insert(address, peer);
+        peer
+    }
+
+    fn remove_peer(&mut self, peer: PeerId) -> Option<UdpPeer> {
+        let state = self.peers.remove(&peer)?;
+        self.addresses.remove(&state.address);
+        Some(state)
+    }
+
+    fn receive(&mut self, events: &mut Vec<TransportEvent>) {
+        loop {
+            let (len, address) = match self.socket.recv_from(&mut self.buffer) {
+                Ok(received) => received,
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                // Reported on some platforms when a previously sent packet could not be delivered.
+                Err(err) if err.kind() == ErrorKind::ConnectionReset => continue,
+                Err(err) => {
+                    warn!("Failed to receive from UDP socket: {err}");
+                    break;
+                }
+            };
+            if len < HEADER_SIZE || self.buffer[..PROTOCOL_ID.len()] != PROTOCOL_ID {
+                continue;
+            }
+            let kind = self.buffer[PROTOCOL_ID.len()];
+            let body = self.buffer[HEADER_SIZE..len].to_vec();
+            self.handle_packet(address, kind, &body, events);
+        }
+    }
+
+    fn handle_packet(
+        &mut self,
+        address: SocketAddr,
+        kind: u8,
+        body: &[u8],
+        events: &mut Vec<TransportEvent>,
+    ) {
+        let Some(&peer) = self.addresses.get(&address) else {
+            if kind == CONNECT {
+                if self.listening && self.peers.len() < self.settings.max_peers {
+                    let peer = self.add_peer(address, true);
+                    send_packet(&self.socket, address, ACCEPT, &[]);
+                    events.push(TransportEvent::Connected(peer));
+                } else {
+                    send_packet(&self.socket, address, DISCONNECT, &[]);
+                }
+            }
+            return;
+        };
+
+        let state = self.peers.get_mut(&peer).unwrap();
+        state.last_received = Instant::now();
+
+        match kind {
+            // The accept packet was lost, so the peer is still trying to connect.
+            CONNECT if state.connected => send_packet(&self.socket, address, ACCEPT, &[]),
+            ACCEPT if !state.connected => {
+                state.connected = true;
+                events.push(TransportEvent::Connected(peer));
+            }
+            DISCONNECT => {
+                self.remove_peer(peer);
+                events.push(TransportEvent::Disconnected(peer));
+            }
+            UNRELIABLE if state.connected => {
+                let Some((&id, payload)) = body.split_first() else {
+                    return;
+                };
+                events.push(TransportEvent::Received {
+                    peer,
+                    channel: Channel::unreliable(id),
+                    payload: payload.to_vec(),
+                });
+            }
+            RELIABLE if state.connected => {
+                if body.len() < 6 {
+                    return;
+                }
+                let id = body[0];
+                let sequence = u32::from_le_bytes(body[1..5].try_into().unwrap());
+                let is_last = body[5] != 0;
+
+                let mut ack = [0; 5];
+                ack[0] = id;
+                ack[1..].copy_from_slice(&sequence.to_le_bytes());
+
+                let channel = state.channels.entry(id).or_default();
+                let ahead = sequence.wrapping_sub(channel.next_expected);
+                if ahead < RECEIVE_WINDOW {
+                    channel
+                        .received
+                        .entry(sequence)
+                        .or_insert_with(|| (is_last, body[6..].to_vec()));
+                }
+                let max_fragments = self.settings.max_message_size.div_ceil(MAX_FRAGMENT_SIZE);
+                while let Some((is_last, fragment)) =
+                    channel.received.remove(&channel.next_expected)
+                {
+                    channel.next_expected = channel.next_expected.wrapping_add(1);
+                    channel.message_fragments += 1;
+                    if channel.message.len() + fragment.len() > self.settings.max_message_size
+                        || channel.message_fragments > max_fragments
+                    {
+                        warn!(
+                            "Disconnecting {peer:?}, it sent a reliable message larger than {} bytes",
+                            self.settings.max_message_size
+                        );
+                        self.remove_peer(peer);
+                        send_packet(&self.socket, address, DISCONNECT, &[]);
+                        events.push(TransportEvent::Disconnected(peer));
+                        return;
+                    }
+                    channel.message.extend_from_slice(&fragment);
+                    if is_last {
+                        channel.message_fragments = 0;
+                        events.push(TransportEvent::Received {
+                            peer,
+                            channel: Channel::reliable(id),
+                            payload: core::mem::take(&mut channel.message),
+                        });
+                    }
+                }
+
+                state.last_sent = Instant::now();
+                send_packet(&self.socket, address, ACK, &ack);
+            }
+            ACK if state.connected => {
+                if body.len() < 5 {
+                    return;
+                }
+                let sequence = u32::from_le_bytes(body[1..5].try_into().unwrap());
+                if let Some(channel) = state.channels.get_mut(&body[0]) {
+                    channel.unacked.remove(&sequence);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn update_peers(&mut self, events: &mut Vec<TransportEvent>) {
+        let now = Instant::now();
+        let mut timed_out = Vec::new();
+
+        for (&peer, state) in self.peers.iter_mut() {
+            if now.duration_since(state.last_received) > self.settings.timeout {
+                timed_out.push(peer);
+                continue;
+            }
+
+            if !state.connected {
+                if now.duration_since(state.last_sent) >= self.settings.connect_interval {
+                    state.last_sent = now;
+                    send_packet(&self.socket, state.address, CONNECT, &[]);
+                }
+                continue;
+            }
+
+            for channel in state.channels.values_mut() {
+                for fragment in channel.unacked.values_mut() {
+                    if now.duration_since(fragment.sent_at) >= self.settings.resend_interval {
+                        fragment.sent_at = now;
+                        state.last_sent = now;
+                        send_raw(&self.socket, state.address, &fragment.packet);
+                    }
+                }
+            }
+
+            if now.duration_since(state.last_sent) >= self.settings.keep_alive_interval {
+                state.last_sent = now;
+                send_packet(&self.socket, state.address, KEEP_ALIVE, &[]);
+            }
+        }
+
+        for peer in timed_out {
+            self.remove_peer(peer);
+            events.push(TransportEvent::Disconnected(peer));
+        }
+    }
+}
+
+impl NetworkTransport for UdpTransport {
+    fn connect(&mut self, address: &str) -> Result<PeerId, TransportError> {
+        let address = address
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| TransportError::InvalidAddress(address.to_string()))?;
+        if let Some(&peer) = self.addresses.get(&address) {
+            return Ok(peer);
+        }
+
+        let peer = self.add_peer(address, false);
+        send_packet(&self.socket, address, CONNECT, &[]);
+        Ok(peer)
+    }
+
+    fn disconnect(&mut self, peer: PeerId) {
+        if let Some(state) = self.remove_peer(peer) {
+            send_packet(&self.socket, state.address, DISCONNECT, &[]);
+            self.pending.push(TransportEvent::Disconnected(peer));
+        }
+    }
+
+    fn send(&mut self, peer: PeerId, channel: Channel, payload: &[u8]) {
+        let Some(state) = self.peers.get_mut(&peer) else {
+            return;
+        };
+        if !state.connected {
+            return;
+        }
+
+        match channel.delivery {
+            Delivery::Unreliable => {
+                if payload.len() > MAX_FRAGMENT_SIZE {
+                    warn!(
+                        "Dropped unreliable message of {} bytes sent to {peer:?}, unreliable messages are limited to {MAX_FRAGMENT_SIZE} bytes",
+                        payload.len()
+                    );
+                    return;
+                }
+                let mut packet = Vec::with_capacity(HEADER_SIZE + 1 + payload.len());
+                packet.extend_from_slice(&PROTOCOL_ID);
+                packet.extend_from_slice(&[UNRELIABLE, channel.id]);
+                packet.extend_from_slice(payload);
+                state.last_sent = Instant::now();
+                send_raw(&self.socket, state.address, &packet);
+            }
+            Delivery::Reliable => {
+                if payload.len() > self.settings.max_message_size {
+                    warn!(
+                        "Dropped reliable message of {} bytes sent to {peer:?}, reliable messages are limited to {} bytes",
+                        payload.len(),
+                        self.settings.max_message_size
+                    );
+                    return;
+                }
+                let now = Instant::now();
+                let reliable = state.channels.entry(channel.id).or_default();
+                let fragment_count = payload.len().div_ceil(MAX_FRAGMENT_SIZE).max(1);
+                for index in 0..fragment_count {
+                    let start = index * MAX_FRAGMENT_SIZE;
+                    let end = payload.len().min(start + MAX_FRAGMENT_SIZE);
+                    let sequence = reliable.next_sequence;
+                    reliable.next_sequence = sequence.wrapping_add(1);
+
+                    let mut packet = Vec::with_capacity(HEADER_SIZE + 6 + end - start);
+                    packet.extend_from_slice(&PROTOCOL_ID);
+                    packet.extend_from_slice(&[RELIABLE, channel.id]);
+                    packet.extend_from_slice(&sequence.to_le_bytes());
+                    packet.push(u8::from(index + 1 == fragment_count));
+                    packet.extend_from_slice(&payload[start..end]);
+
+                    send_raw(&self.socket, state.address, &packet);
+                    reliable.unacked.insert(
+                        sequence,
+                        UnackedFragment {
+                            packet,
+                            sent_at: now,
+                        },
+                    );
+                }
+                state.last_sent = now;
+            }
+        }
+    }
+
+    fn poll(&mut self, events: &mut Vec<TransportEvent>) {
+        events.append(&mut self.pending);
+        self.receive(events);
+        self.update_peers(events);
+    }
+}
+
+impl Drop for UdpTransport {
+    fn drop(&mut self) {
+        for state in self.peers.values() {
+            send_packet(&self.socket, state.address, DISCONNECT, &[]);
+        }
+    }
+}
+
+fn send_packet(socket: &UdpSocket, address: SocketAddr, kind: u8, body: &[u8]) {
+    let mut packet = Vec::with_capacity(HEADER_SIZE + body.len());
+    packet.extend_from_slice(&PROTOCOL_ID);
+    packet.push(kind);
+    packet.extend_from_slice(body);
+    send_raw(socket, address, &packet);
+}
+
+fn send_raw(socket: &UdpSocket, address: SocketAddr, packet: &[u8]) {
+    match socket.send_to(packet, address) {
+        Ok(_) => {}
+        // Reliable fragments are resent, and unreliable messages may be lost anyway.
+        Err(err) if err.kind() == ErrorKind::WouldBlock => {}
+        Err(err) => debug!("Failed to send packet to {address}: {err}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use std::thread;
+
+    use super::*;
+
+    /// Polls both transports until `predicate` returns `true` for the events of `b`.
+    fn poll_until(
+        a: &mut UdpTransport,
+        b: &mut UdpTransport,
+        mut predicate: impl FnMut(&[TransportEvent]) -> bool,
+    ) -> Vec<TransportEvent> {
+        let mut events = Vec::new();
+        for _ in 0..500 {
+            a.poll(&mut Vec::new());
+            b.poll(&mut events);
+            if predicate(&events) {
+                return events;
+            }
+            thread::sleep(Duration::from_millis(2));
+        }
+        panic!("timed out waiting for events, got {events:?}");
+    }
+
+    #[test]
+    fn connect_and_send_fragmented_message() {
+        let mut server = UdpTransport::listen("127.0.0.1:0").unwrap();
+        let mut client = UdpTransport::bind("127.0.0.1:0").unwrap();
+
+        let server_addr = server.local_addr().unwrap().to_string();
+        let server_id = client.connect(&server_addr).unwrap();
+        poll_until(&mut server, &mut client, |events| {
+            events.contains(&TransportEvent::Connected(server_id))
+        });
+
+        let message: Vec<u8> = (0..5000_u32).map(|i| i as u8).collect();
+        client.send(server_id, Channel::reliable(3), &message);
+        let events = poll_until(&mut client, &mut server, |events| {
+            events
+                .iter()
+                .any(|event| matches!(event, TransportEvent::Received { .. }))
+        });
+        let received: Vec<_> = events
+            .into_iter()
+            .filter_map(|event| match event {
+                TransportEvent::Received {
+                    channel, payload, ..
+                } => Some((channel, payload)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(received, vec![(Channel::reliable(3), message)]);
+
+        client.disconnect(server_id);
+        let mut events = Vec::new();
+        client.poll(&mut events);
+        assert_eq!(events, vec![TransportEvent::Disconnected(server_id)]);
+        poll_until(&mut client, &mut server, |events| {
+            events
+                .iter()
+                .any(|event| matches!(event, TransportEvent::Disconnected(_)))
+        });
+    }
+
+    #[test]
+    fn refuse_connections_over_max_peers() {
+        let mut server = UdpTransport::listen("127.0.0.1:0")
+            .unwrap()
+            .with_settings(UdpSettings {
+                max_peers: 1,
+                ..Default::default()
+            });
+        let server_addr = server.local_addr().unwrap().to_string();
+
+        let mut first = UdpTransport::bind("127.0.0.1:0").unwrap();
+        let first_id = first.connect(&server_addr).unwrap();
+        poll_until(&mut server, &mut first, |events| {
+            events.contains(&TransportEvent::Connected(first_id))
+        });
+
+        let mut second = UdpTransport::bind("127.0.0.1:0").unwrap();
+        let second_id = second.connect(&server_addr).unwrap();
+        poll_until(&mut server, &mut second, |events| {
+            events.contains(&TransportEvent::Disconnected(second_id))
+        });
+        assert_eq!(server.peers.len(), 1);
+    }
+
+    #[test]
+    fn disconnect_peer_sending_oversized_message() {
+        let mut server = UdpTransport::listen("127.0.0.1:0")
+            .unwrap()
+            .with_settings(UdpSettings {
+                max_message_size: 2000,
+                ..Default::default()
+            });
+        let mut client = UdpTransport::bind("127.0.0.1:0").unwrap();
+
+        let server_addr = server.local_addr().unwrap().to_string();
+        let server_id = client.connect(&server_addr).unwrap();
+        poll_until(&mut server, &mut client, |events| {
+            events.contains(&TransportEvent::Connected(server_id))
+        });
+
+        client.send(server_id, Channel::reliable(0), &[0; 5000]);
+        let events = poll_until(&mut client, &mut server, |events| {
+            events
+                .iter()
+                .any(|event| matches!(event, TransportEvent::Disconnected(_)))
+        });
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, TransportEvent::Received { .. })));
+    }
+}
@@ -0,0 +1,192 @@
+//! Detects textures used in the wrong color space by [`StandardMaterial`]s.
+//!
+//! Color textures, such as [`StandardMaterial::base_color_texture`], store sRGB-encoded colors and
+//! must use an sRGB texture format so that they are decoded to linear colors when sampled.
+//! Data textures, such as [`StandardMaterial::normal_map_texture`], store linear values and must
+//! not: sampling them from an sRGB texture format applies the sRGB transfer function a second
+//! time, which is a common cause of washed out or too dark colors and wrong lighting.
+//!
+//! Whether an [`Image`] uses an sRGB format can be configured with
+//! [`ImageLoaderSettings::is_srgb`](bevy_image::ImageLoaderSettings::is_srgb) when loading it.
+
+use crate::StandardMaterial;
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{AssetEvent, AssetId, AssetServer, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_image::Image;
+use bevy_platform_support::collections::HashSet;
+use bevy_render::render_resource::TextureFormat;
+use tracing::warn;
+
+/// A [`Plugin`] that warns about textures used in the wrong color space by [`StandardMaterial`]s.
+///
+/// Materials are checked once all their textures are loaded, and again whenever they are modified.
+/// The detected mismatches are logged, and stored in the [`ColorSpaceMismatches`] resource.
+///
+/// This is meant for debugging, and isn't included in the default plugins.
+#[derive(Default)]
+pub struct ColorSpaceAuditPlugin;
+
+impl Plugin for ColorSpaceAuditPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ColorSpaceMismatches>()
+            .init_resource::<PendingColorSpaceAudits>()
+            .add_systems(PostUpdate, audit_material_color_spaces);
+    }
+}
+
+/// The color space a texture slot of a material expects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExpectedColorSpace {
+    /// The texture stores sRGB-encoded colors, and should use an sRGB texture format.
+    Srgb,
+    /// The texture stores linear data, and shouldn't use an sRGB texture format.
+    Linear,
+}
+
+/// A texture used in the wrong color space by a material.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ColorSpaceMismatch {
+    /// The material using the texture.
+    pub material: AssetId<StandardMaterial>,
+    /// The name of the field of the material the texture is used in.
+    pub slot: &'static str,
+    /// The texture.
+    pub image: AssetId<Image>,
+    /// The format of the texture.
+    pub format: TextureFormat,
+    /// The color space the slot expects.
+    pub expected: ExpectedColorSpace,
+}
+
+/// The textures used in the wrong color space by [`StandardMaterial`]s, as detected by the
+/// [`ColorSpaceAuditPlugin`].
+#[derive(Resource, Default, Debug)]
+pub struct ColorSpaceMismatches(pub Vec<ColorSpaceMismatch>);
+
+/// The materials to audit once all their textures are loaded.
+#[derive(Resource, Default)]
+struct PendingColorSpaceAudits(HashSet<AssetId<StandardMaterial>>);
+
+/// Returns whether `format` matches `expected`.
+///
+/// Formats without an sRGB variant, such as single channel or floating point formats, always match.
+fn is_expected_format(format: TextureFormat, expected: ExpectedColorSpace) -> bool {
+    match expected {
+        ExpectedColorSpace::Srgb => format.is_srgb() || format.add_srgb_suffix() == format,
+        ExpectedColorSpace::Linear => !format.is_srgb(),
+    }
+}
+
+fn texture_slots(
+    material: &StandardMaterial,
+) -> [(&'static str, &Option<Handle<Image>>, ExpectedColorSpace); 6] {
+    use ExpectedColorSpace::*;
+    [
+        ("base_color_texture", &material.base_color_texture, Srgb),
+        ("emissive_texture", &material.emissive_texture, Srgb),
+        (
+            "metallic_roughness_texture",
+            &material.metallic_roughness_texture,
+            Linear,
+        ),
+        ("normal_map_texture", &material.normal_map_texture, Linear),
+        ("occlusion_texture", &material.occlusion_texture, Linear),
+        ("depth_map", &material.depth_map, Linear),
+    ]
+}
+
+fn audit_material_color_spaces(
+    mut material_events: EventReader<AssetEvent<StandardMaterial>>,
+    materials: Res<Assets<StandardMaterial>>,
+    images: Res<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+    mut pending: ResMut<PendingColorSpaceAudits>,
+    mut mismatches: ResMut<ColorSpaceMismatches>,
+) {
+    for event in material_events.read() {
+        match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => {
+                pending.0.insert(*id);
+                mismatches.0.retain(|mismatch| mismatch.material != *id);
+            }
+            AssetEvent::Removed { id } => {
+                pending.0.remove(id);
+                mismatches.0.retain(|mismatch| mismatch.material != *id);
+            }
+            _ => {}
+        }
+    }
+
+    pending.0.retain(|&material_id| {
+        let Some(material) = materials.get(material_id) else {
+            return false;
+        };
+        let slots = texture_slots(material);
+        // Wait until all textures are loaded, unless they failed to load.
+        let waiting = slots.iter().any(|(_, handle, _)| {
+            handle.as_ref().is_some_and(|handle| {
+                !images.contains(handle)
+                    && !asset_server
+                        .get_load_state(handle)
+                        .is_some_and(|state| state.is_failed())
+            })
+        });
+        if waiting {
+            return true;
+        }
+
+        for (slot, handle, expected) in slots {
+            let Some(handle) = handle else {
+                continue;
+            };
+            let Some(image) = images.get(handle) else {
+                continue;
+            };
+            let format = image.texture_descriptor.format;
+            if is_expected_format(format, expected) {
+                continue;
+            }
+            let image_name = handle
+                .path()
+                .map(ToString::to_string)
+                .unwrap_or_else(|| handle.id().to_string());
+            match expected {
+                ExpectedColorSpace::Srgb => warn!(
+                    "The `{slot}` of material {material_id} uses the linear texture `{image_name}` \
+                    ({format:?}): its colors will look washed out. Load it with `is_srgb: true`."
+                ),
+                ExpectedColorSpace::Linear => warn!(
+                    "The `{slot}` of material {material_id} uses the sRGB texture `{image_name}` \
+                    ({format:?}): its values will be too dark. Load it with `is_srgb: false`."
+                ),
+            }
+            mismatches.0.push(ColorSpaceMismatch {
+                material: material_id,
+                slot,
+                image: handle.id(),
+                format,
+                expected,
+            });
+        }
+        false
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_formats() {
+        use ExpectedColorSpace::*;
+        assert!(is_expected_format(TextureFormat::Rgba8UnormSrgb, Srgb));
+        assert!(!is_expected_format(TextureFormat::Rgba8Unorm, Srgb));
+        // Formats without an sRGB variant can't be mistaken.
+        assert!(is_expected_format(TextureFormat::Rgba16Float, Srgb));
+        assert!(is_expected_format(TextureFormat::R8Unorm, Srgb));
+
+        assert!(is_expected_format(TextureFormat::Rgba8Unorm, Linear));
+        assert!(!is_expected_format(TextureFormat::Bc7RgbaUnormSrgb, Linear));
+    }
+}
@@ -18,6 +18,11 @@ use bevy_render::{extract_component::ExtractComponent, prelude::Camera};
 /// - [`FogFalloff::ExponentialSquared`]
 /// - [`FogFalloff::Atmospheric`]
 ///
+/// ## Height Falloff
+///
+/// By default, the fog is uniformly dense. With a [`FogHeightFalloff`], its density decreases
+/// exponentially with height instead, for ground fog and hazy valleys.
+///
 /// ## Example
 ///
 /// ```
@@ -68,6 +73,46 @@ pub struct DistanceFog {
 
     /// Determines which falloff mode to use, and its parameters.
     pub falloff: FogFalloff,
+
+    /// Makes the fog thinner with height, or `None` for a uniformly dense fog.
+    pub height_falloff: Option<FogHeightFalloff>,
+}
+
+/// Makes a [`DistanceFog`] exponentially thinner with height.
+///
+/// The density of the fog at a world space height `y` is multiplied by
+/// `exp(-falloff * (y - base_height))`, and the fog is integrated along the view ray.
+///
+/// ```
+/// # use bevy_pbr::{DistanceFog, FogFalloff, FogHeightFalloff};
+/// // Ground fog, quickly thinning out above the ground.
+/// let fog = DistanceFog {
+///     falloff: FogFalloff::Exponential { density: 0.05 },
+///     height_falloff: Some(FogHeightFalloff {
+///         base_height: 0.0,
+///         falloff: 0.5,
+///     }),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Default, Debug, Clone, PartialEq)]
+pub struct FogHeightFalloff {
+    /// The world space height at which the fog has the density configured by its [`FogFalloff`].
+    pub base_height: f32,
+    /// How quickly the fog thins out above `base_height`, and thickens below it.
+    ///
+    /// Higher values give a thinner layer of fog.
+    pub falloff: f32,
+}
+
+impl Default for FogHeightFalloff {
+    fn default() -> Self {
+        Self {
+            base_height: 0.0,
+            falloff: 0.1,
+        }
+    }
 }
 
 /// Allows switching between different fog falloff modes, and configuring their parameters.
@@ -471,6 +516,7 @@ impl Default for DistanceFog {
             },
             directional_light_color: Color::NONE,
             directional_light_exponent: 8.0,
+            height_falloff: None,
         }
     }
 }
@@ -26,6 +26,7 @@ pub mod experimental {
 
 mod atmosphere;
 mod cluster;
+pub mod color_space_audit;
 mod components;
 pub mod decal;
 pub mod deferred;
@@ -333,6 +334,7 @@ impl Plugin for PbrPlugin {
             .register_type::<CubemapVisibleEntities>()
             .register_type::<DirectionalLight>()
             .register_type::<DirectionalLightShadowMap>()
+            .register_type::<MaterialOverride>()
             .register_type::<NotShadowCaster>()
             .register_type::<NotShadowReceiver>()
             .register_type::<PointLight>()
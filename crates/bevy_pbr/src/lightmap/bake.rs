@@ -0,0 +1,685 @@
+//! A CPU path tracer baking [`Lightmap`](super::Lightmap) textures for static meshes.
+
+use alloc::vec::Vec;
+use bevy_asset::RenderAssetUsages;
+use bevy_color::{ColorToComponents, LinearRgba};
+use bevy_image::Image;
+use bevy_math::{Affine3A, Mat3A, UVec2, Vec2, Vec3, Vec3A};
+use bevy_render::{
+    mesh::{Indices, Mesh, VertexAttributeValues},
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use bevy_tasks::{ComputeTaskPool, ParallelSlice, TaskPool};
+use core::f32::consts::{PI, TAU};
+use half::f16;
+
+/// A mesh of the static scene, taken into account when baking lightmaps.
+#[derive(Clone, Copy)]
+pub struct LightmapBakeMesh<'a> {
+    /// The mesh, with triangle list topology.
+    ///
+    /// To receive a lightmap, the mesh must have lightmap UVs
+    /// ([`Mesh::ATTRIBUTE_UV_1`]).
+    pub mesh: &'a Mesh,
+    /// The transform of the mesh, from local space to world space.
+    pub transform: Affine3A,
+    /// The diffuse color of the surface of the mesh.
+    pub albedo: LinearRgba,
+    /// The light emitted by the surface of the mesh.
+    pub emissive: LinearRgba,
+    /// The size of the lightmap of the mesh, in texels, or `None` if the mesh doesn't receive a
+    /// lightmap, and only occludes and bounces light.
+    pub lightmap_size: Option<UVec2>,
+}
+
+/// A light taken into account when baking lightmaps.
+#[derive(Clone, Copy, Debug)]
+pub struct LightmapBakeLight {
+    /// The type of the light.
+    pub kind: LightmapBakeLightKind,
+    /// The color of the light, multiplied by its intensity: the illuminance in lux of directional
+    /// lights, or the luminous power in lumens of point lights.
+    pub color: LinearRgba,
+    /// Whether the direct light is baked into lightmaps.
+    ///
+    /// The indirect light of all lights is always baked. The direct light should only be baked if the
+    /// light doesn't light lightmapped meshes at runtime: if its `affects_lightmapped_mesh_diffuse`
+    /// field is `false`.
+    pub bake_direct: bool,
+}
+
+/// The type of a [`LightmapBakeLight`].
+#[derive(Clone, Copy, Debug)]
+pub enum LightmapBakeLightKind {
+    /// A light infinitely far away, such as the sun, shining in the given direction.
+    Directional(Vec3),
+    /// A light emitting in all directions from a position.
+    Point(Vec3),
+}
+
+/// Settings used to bake lightmaps.
+#[derive(Clone, Debug)]
+pub struct LightmapBakeSettings {
+    /// The number of paths traced for each texel.
+    ///
+    /// More samples reduce noise, at the cost of a longer bake. Defaults to `256`.
+    pub samples: u32,
+    /// The maximum number of times light bounces along a path. Defaults to `3`.
+    pub bounces: u32,
+    /// The light coming from the sky, where paths escape the scene.
+    pub sky_color: LinearRgba,
+    /// The number of texels the lightmaps are extended by around their charts, to avoid seams
+    /// when sampling them with filtering. Defaults to `2`.
+    pub dilation: u32,
+}
+
+impl Default for LightmapBakeSettings {
+    fn default() -> Self {
+        Self {
+            samples: 256,
+            bounces: 3,
+            sky_color: LinearRgba::BLACK,
+            dilation: 2,
+        }
+    }
+}
+
+/// Bakes the indirect diffuse light received by `meshes` into lightmaps, and the direct light of
+/// the lights whose [`bake_direct`](LightmapBakeLight::bake_direct) is set.
+///
+/// Returns a lightmap for each mesh with a
+/// [`lightmap_size`](LightmapBakeMesh::lightmap_size), or `None` for the other meshes.
+/// The lightmaps use the [`TextureFormat::Rgba16Float`] format, and can be assigned with a
+/// [`Lightmap`](super::Lightmap) component.
+///
+/// Paths are traced on the [`ComputeTaskPool`]. Baking is slow, and is meant to be done offline,
+/// with the resulting images saved alongside the scene.
+pub fn bake_lightmaps(
+    meshes: &[LightmapBakeMesh],
+    lights: &[LightmapBakeLight],
+    settings: &LightmapBakeSettings,
+) -> Vec<Option<Image>> {
+    let scene = BakeScene::new(meshes);
+    meshes
+        .iter()
+        .enumerate()
+        .map(|(index, mesh)| {
+            let size = mesh.lightmap_size?;
+            let Some(texels) = rasterize_lightmap_texels(mesh, size) else {
+                tracing::warn!("Mesh {index} has no lightmap UVs, and won't be lightmapped");
+                return None;
+            };
+            Some(bake_lightmap(&scene, lights, settings, &texels, size))
+        })
+        .collect()
+}
+
+/// A point on a surface to compute the light received by.
+#[derive(Clone, Copy)]
+struct LightmapTexel {
+    index: usize,
+    position: Vec3A,
+    normal: Vec3A,
+}
+
+fn bake_lightmap(
+    scene: &BakeScene,
+    lights: &[LightmapBakeLight],
+    settings: &LightmapBakeSettings,
+    texels: &[LightmapTexel],
+    size: UVec2,
+) -> Image {
+    let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+    let radiances = texels
+        .par_chunk_map(pool, 64, |chunk_index, texels| {
+            texels
+                .iter()
+                .enumerate()
+                .map(|(index, texel)| {
+                    let mut rng = Rng::new((chunk_index * 64 + index) as u64);
+                    let mut sum = Vec3A::ZERO;
+                    for _ in 0..settings.samples {
+                        let direction = cosine_hemisphere(texel.normal, &mut rng);
+                        sum += scene.trace(
+                            texel.position,
+                            texel.normal,
+                            direction,
+                            lights,
+                            settings,
+                            &mut rng,
+                        );
+                    }
+                    let mut radiance = sum / settings.samples.max(1) as f32;
+                    radiance += scene.direct_light(texel.position, texel.normal, lights, true) / PI;
+                    (texel.index, radiance)
+                })
+                .collect::<Vec<_>>()
+        })
+        .into_iter()
+        .flatten();
+
+    let texel_count = (size.x * size.y) as usize;
+    let mut pixels = vec![None; texel_count];
+    for (index, radiance) in radiances {
+        pixels[index] = Some(radiance);
+    }
+    dilate(&mut pixels, size, settings.dilation);
+
+    let data = pixels
+        .iter()
+        .flat_map(|radiance| {
+            let radiance = radiance.unwrap_or_default();
+            [radiance.x, radiance.y, radiance.z, 1.0]
+        })
+        .flat_map(|channel| f16::from_f32(channel).to_le_bytes())
+        .collect();
+    Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba16Float,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// Fills the empty pixels around the filled ones with the average of their filled neighbors.
+fn dilate(pixels: &mut [Option<Vec3A>], size: UVec2, iterations: u32) {
+    let (width, height) = (size.x as i32, size.y as i32);
+    for _ in 0..iterations {
+        let previous = pixels.to_vec();
+        for y in 0..height {
+            for x in 0..width {
+                let index = (y * width + x) as usize;
+                if previous[index].is_some() {
+                    continue;
+                }
+                let (mut sum, mut count) = (Vec3A::ZERO, 0);
+                for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                    let (nx, ny) = (x + dx, y + dy);
+                    if (0..width).contains(&nx) && (0..height).contains(&ny) {
+                        if let Some(radiance) = previous[(ny * width + nx) as usize] {
+                            sum += radiance;
+                            count += 1;
+                        }
+                    }
+                }
+                if count > 0 {
+                    pixels[index] = Some(sum / count as f32);
+                }
+            }
+        }
+    }
+}
+
+/// Finds the world space position and normal of the texels covered by the lightmap UVs of `mesh`.
+fn rasterize_lightmap_texels(mesh: &LightmapBakeMesh, size: UVec2) -> Option<Vec<LightmapTexel>> {
+    let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.mesh.attribute(Mesh::ATTRIBUTE_UV_1)
+    else {
+        return None;
+    };
+    let normal_matrix = mesh.transform.matrix3.inverse().transpose();
+    let mut covered = vec![false; (size.x * size.y) as usize];
+    let mut texels = Vec::new();
+
+    for [a, b, c] in mesh_triangles(mesh.mesh) {
+        let positions =
+            [a, b, c].map(|index| mesh.transform.transform_point3a(vertex(mesh, index)));
+        let normals = match mesh.mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+            Some(VertexAttributeValues::Float32x3(normals)) => [a, b, c]
+                .map(|index| (normal_matrix * Vec3A::from(normals[index])).normalize_or_zero()),
+            _ => {
+                [(positions[1] - positions[0])
+                    .cross(positions[2] - positions[0])
+                    .normalize_or_zero(); 3]
+            }
+        };
+        let uvs = [a, b, c].map(|index| Vec2::from(uvs[index]) * size.as_vec2());
+
+        let min = uvs[0].min(uvs[1]).min(uvs[2]).floor().max(Vec2::ZERO);
+        let max = uvs[0].max(uvs[1]).max(uvs[2]).ceil().min(size.as_vec2());
+        let area = edge(uvs[0], uvs[1], uvs[2]);
+        if area.abs() < f32::EPSILON {
+            continue;
+        }
+        for y in min.y as u32..max.y as u32 {
+            for x in min.x as u32..max.x as u32 {
+                let index = (y * size.x + x) as usize;
+                if covered[index] {
+                    continue;
+                }
+                let center = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let weights = Vec3A::new(
+                    edge(uvs[1], uvs[2], center),
+                    edge(uvs[2], uvs[0], center),
+                    edge(uvs[0], uvs[1], center),
+                ) / area;
+                if weights.min_element() < 0.0 {
+                    continue;
+                }
+                covered[index] = true;
+                texels.push(LightmapTexel {
+                    index,
+                    position: positions[0] * weights.x
+                        + positions[1] * weights.y
+                        + positions[2] * weights.z,
+                    normal: (normals[0] * weights.x
+                        + normals[1] * weights.y
+                        + normals[2] * weights.z)
+                        .normalize_or_zero(),
+                });
+            }
+        }
+    }
+    Some(texels)
+}
+
+/// Returns twice the signed area of the triangle `abc`.
+fn edge(a: Vec2, b: Vec2, c: Vec2) -> f32 {
+    (b - a).perp_dot(c - a)
+}
+
+fn vertex(mesh: &LightmapBakeMesh, index: usize) -> Vec3A {
+    match mesh.mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(positions)) => Vec3A::from(positions[index]),
+        _ => Vec3A::ZERO,
+    }
+}
+
+/// Returns the vertex indices of the triangles of `mesh`.
+fn mesh_triangles(mesh: &Mesh) -> Vec<[usize; 3]> {
+    let indices: Vec<usize> = match mesh.indices() {
+        Some(Indices::U16(indices)) => indices.iter().map(|&index| index as usize).collect(),
+        Some(Indices::U32(indices)) => indices.iter().map(|&index| index as usize).collect(),
+        None => (0..mesh.count_vertices()).collect(),
+    };
+    indices
+        .chunks_exact(3)
+        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+        .collect()
+}
+
+/// A triangle of the scene, in world space.
+struct BakeTriangle {
+    vertices: [Vec3A; 3],
+    normal: Vec3A,
+    mesh: usize,
+}
+
+/// A node of the bounding volume hierarchy of the scene.
+struct BvhNode {
+    min: Vec3A,
+    max: Vec3A,
+    /// The index of the first child if `count` is zero, or of the first triangle otherwise.
+    start: usize,
+    count: usize,
+}
+
+/// The static scene, with a bounding volume hierarchy to trace rays quickly.
+struct BakeScene {
+    triangles: Vec<BakeTriangle>,
+    nodes: Vec<BvhNode>,
+    materials: Vec<(Vec3A, Vec3A)>,
+}
+
+struct Hit {
+    distance: f32,
+    triangle: usize,
+}
+
+impl BakeScene {
+    fn new(meshes: &[LightmapBakeMesh]) -> Self {
+        let mut triangles = Vec::new();
+        for (mesh_index, mesh) in meshes.iter().enumerate() {
+            for [a, b, c] in mesh_triangles(mesh.mesh) {
+                let vertices =
+                    [a, b, c].map(|index| mesh.transform.transform_point3a(vertex(mesh, index)));
+                let normal = (vertices[1] - vertices[0])
+                    .cross(vertices[2] - vertices[0])
+                    .normalize_or_zero();
+                if normal != Vec3A::ZERO {
+                    triangles.push(BakeTriangle {
+                        vertices,
+                        normal,
+                        mesh: mesh_index,
+                    });
+                }
+            }
+        }
+        let materials = meshes
+            .iter()
+            .map(|mesh| {
+                (
+                    Vec3A::from_slice(&mesh.albedo.to_f32_array_no_alpha()),
+                    Vec3A::from_slice(&mesh.emissive.to_f32_array_no_alpha()),
+                )
+            })
+            .collect();
+
+        let mut scene = Self {
+            triangles,
+            nodes: Vec::new(),
+            materials,
+        };
+        let count = scene.triangles.len();
+        scene.nodes.push(BvhNode {
+            min: Vec3A::ZERO,
+            max: Vec3A::ZERO,
+            start: 0,
+            count,
+        });
+        scene.build_node(0);
+        scene
+    }
+
+    /// Computes the bounds of a node, and splits it in two if it has enough triangles.
+    fn build_node(&mut self, node_index: usize) {
+        let BvhNode { start, count, .. } = self.nodes[node_index];
+        let triangles = &mut self.triangles[start..start + count];
+        let (min, max) = triangles
+            .iter()
+            .flat_map(|triangle| triangle.vertices)
+            .fold(
+                (Vec3A::INFINITY, Vec3A::NEG_INFINITY),
+                |(min, max), vertex| (min.min(vertex), max.max(vertex)),
+            );
+        self.nodes[node_index].min = min;
+        self.nodes[node_index].max = max;
+        if count <= 4 {
+            return;
+        }
+
+        // Split at the median of the centroids along the longest axis.
+        let extent = max - min;
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+        let centroid = |triangle: &BakeTriangle| {
+            (triangle.vertices[0] + triangle.vertices[1] + triangle.vertices[2])[axis]
+        };
+        let middle = count / 2;
+        triangles.select_nth_unstable_by(middle, |a, b| centroid(a).total_cmp(&centroid(b)));
+
+        let first_child = self.nodes.len();
+        for (child_start, child_count) in [(start, middle), (start + middle, count - middle)] {
+            self.nodes.push(BvhNode {
+                min: Vec3A::ZERO,
+                max: Vec3A::ZERO,
+                start: child_start,
+                count: child_count,
+            });
+        }
+        self.nodes[node_index].start = first_child;
+        self.nodes[node_index].count = 0;
+        self.build_node(first_child);
+        self.build_node(first_child + 1);
+    }
+
+    /// Finds the closest triangle hit by a ray closer than `max_distance`.
+    fn intersect(&self, origin: Vec3A, direction: Vec3A, max_distance: f32) -> Option<Hit> {
+        if self.triangles.is_empty() {
+            return None;
+        }
+        let inverse_direction = direction.recip();
+        let mut closest: Option<Hit> = None;
+        let mut stack = vec![0];
+        while let Some(node_index) = stack.pop() {
+            let node = &self.nodes[node_index];
+            let limit = closest.as_ref().map_or(max_distance, |hit| hit.distance);
+            if !intersect_aabb(origin, inverse_direction, node.min, node.max, limit) {
+                continue;
+            }
+            if node.count == 0 {
+                stack.extend([node.start, node.start + 1]);
+                continue;
+            }
+            for triangle_index in node.start..node.start + node.count {
+                let limit = closest.as_ref().map_or(max_distance, |hit| hit.distance);
+                if let Some(distance) =
+                    intersect_triangle(origin, direction, &self.triangles[triangle_index])
+                {
+                    if distance < limit {
+                        closest = Some(Hit {
+                            distance,
+                            triangle: triangle_index,
+                        });
+                    }
+                }
+            }
+        }
+        closest
+    }
+
+    /// Returns the irradiance received from the direct light of `lights` at a point.
+    ///
+    /// If `baked_only` is set, only the lights whose direct light is baked are taken into account.
+    fn direct_light(
+        &self,
+        position: Vec3A,
+        normal: Vec3A,
+        lights: &[LightmapBakeLight],
+        baked_only: bool,
+    ) -> Vec3A {
+        let origin = position + normal * RAY_OFFSET;
+        let mut irradiance = Vec3A::ZERO;
+        for light in lights {
+            if baked_only && !light.bake_direct {
+                continue;
+            }
+            let color = Vec3A::from_slice(&light.color.to_f32_array_no_alpha());
+            let (direction, distance, light) = match light.kind {
+                LightmapBakeLightKind::Directional(direction) => {
+                    (-Vec3A::from(direction).normalize(), f32::INFINITY, color)
+                }
+                LightmapBakeLightKind::Point(light_position) => {
+                    let offset = Vec3A::from(light_position) - position;
+                    let distance = offset.length();
+                    // The luminous intensity in candela, divided by the squared distance.
+                    let light = color / (4.0 * PI * distance * distance).max(f32::EPSILON);
+                    (offset / distance, distance, light)
+                }
+            };
+            let cosine = normal.dot(direction);
+            if cosine <= 0.0 || self.intersect(origin, direction, distance).is_some() {
+                continue;
+            }
+            irradiance += light * cosine;
+        }
+        irradiance
+    }
+
+    /// Returns the light coming from `direction` to a point, estimated by tracing a path.
+    fn trace(
+        &self,
+        mut position: Vec3A,
+        mut normal: Vec3A,
+        mut direction: Vec3A,
+        lights: &[LightmapBakeLight],
+        settings: &LightmapBakeSettings,
+        rng: &mut Rng,
+    ) -> Vec3A {
+        let sky = Vec3A::from_slice(&settings.sky_color.to_f32_array_no_alpha());
+        let mut radiance = Vec3A::ZERO;
+        let mut throughput = Vec3A::ONE;
+        for bounce in 0..=settings.bounces {
+            let Some(hit) =
+                self.intersect(position + normal * RAY_OFFSET, direction, f32::INFINITY)
+            else {
+                radiance += throughput * sky;
+                break;
+            };
+            let triangle = &self.triangles[hit.triangle];
+            let (albedo, emissive) = self.materials[triangle.mesh];
+            position += direction * hit.distance;
+            normal = if triangle.normal.dot(direction) < 0.0 {
+                triangle.normal
+            } else {
+                -triangle.normal
+            };
+
+            radiance += throughput * emissive;
+            // A Lambertian surface reflects `albedo / π` of its irradiance in every direction.
+            radiance +=
+                throughput * albedo * self.direct_light(position, normal, lights, false) / PI;
+            if bounce == settings.bounces {
+                break;
+            }
+            // With cosine-weighted sampling, the cosine and the `1 / π` cancel out with the
+            // probability of the direction.
+            throughput *= albedo;
+            if throughput.max_element() <= 0.0 {
+                break;
+            }
+            direction = cosine_hemisphere(normal, rng);
+        }
+        radiance
+    }
+}
+
+/// The distance rays start from surfaces, to avoid hitting them again.
+const RAY_OFFSET: f32 = 1e-3;
+
+fn intersect_aabb(
+    origin: Vec3A,
+    inverse_direction: Vec3A,
+    min: Vec3A,
+    max: Vec3A,
+    limit: f32,
+) -> bool {
+    let t0 = (min - origin) * inverse_direction;
+    let t1 = (max - origin) * inverse_direction;
+    let near = t0.min(t1).max_element().max(0.0);
+    let far = t0.max(t1).min_element().min(limit);
+    near <= far
+}
+
+/// Möller–Trumbore ray-triangle intersection.
+fn intersect_triangle(origin: Vec3A, direction: Vec3A, triangle: &BakeTriangle) -> Option<f32> {
+    let [a, b, c] = triangle.vertices;
+    let (ab, ac) = (b - a, c - a);
+    let p = direction.cross(ac);
+    let determinant = ab.dot(p);
+    if determinant.abs() < 1e-8 {
+        return None;
+    }
+    let inverse = 1.0 / determinant;
+    let t = origin - a;
+    let u = t.dot(p) * inverse;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = t.cross(ab);
+    let v = direction.dot(q) * inverse;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let distance = ac.dot(q) * inverse;
+    (distance > 0.0).then_some(distance)
+}
+
+/// Returns a random direction around `normal`, with a probability proportional to its cosine.
+fn cosine_hemisphere(normal: Vec3A, rng: &mut Rng) -> Vec3A {
+    let (r1, r2) = (rng.next_f32(), rng.next_f32());
+    let radius = r1.sqrt();
+    let angle = TAU * r2;
+    let (tangent, bitangent) = Vec3::from(normal).any_orthonormal_pair();
+    let basis = Mat3A::from_cols(tangent.into(), bitangent.into(), normal);
+    basis
+        * Vec3A::new(
+            radius * angle.cos(),
+            radius * angle.sin(),
+            (1.0 - r1).sqrt(),
+        )
+}
+
+/// A small PCG random number generator, so that bakes are deterministic.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        let mut rng = Self(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ 0x853c_49e6_748f_ea9b);
+        rng.next_u32();
+        rng
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        let state = self.0;
+        self.0 = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        let xorshifted = (((state >> 18) ^ state) >> 27) as u32;
+        xorshifted.rotate_right((state >> 59) as u32)
+    }
+
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::primitives::Plane3d;
+    use bevy_render::mesh::{MeshBuilder, Meshable};
+
+    fn lightmapped_plane() -> Mesh {
+        let mut mesh = Plane3d::default().mesh().size(2.0, 2.0).build();
+        let uvs = mesh.attribute(Mesh::ATTRIBUTE_UV_0).unwrap().clone();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_1, uvs);
+        mesh
+    }
+
+    #[test]
+    fn bake_sky_light() {
+        let plane = lightmapped_plane();
+        let meshes = [LightmapBakeMesh {
+            mesh: &plane,
+            transform: Affine3A::IDENTITY,
+            albedo: LinearRgba::WHITE,
+            emissive: LinearRgba::BLACK,
+            lightmap_size: Some(UVec2::splat(4)),
+        }];
+        let settings = LightmapBakeSettings {
+            samples: 16,
+            sky_color: LinearRgba::rgb(0.5, 0.5, 0.5),
+            ..Default::default()
+        };
+        let lightmaps = bake_lightmaps(&meshes, &[], &settings);
+        let image = lightmaps[0].as_ref().unwrap();
+        assert_eq!(image.texture_descriptor.format, TextureFormat::Rgba16Float);
+
+        // An unoccluded plane receives the sky light from its whole hemisphere.
+        let data = image.data.as_ref().unwrap();
+        for texel in data.chunks_exact(8) {
+            let red = f16::from_le_bytes([texel[0], texel[1]]).to_f32();
+            assert!((red - 0.5).abs() < 1e-3, "{red}");
+        }
+    }
+
+    #[test]
+    fn occluded_ray() {
+        let plane = lightmapped_plane();
+        let scene = BakeScene::new(&[LightmapBakeMesh {
+            mesh: &plane,
+            transform: Affine3A::from_translation(Vec3::Y),
+            albedo: LinearRgba::WHITE,
+            emissive: LinearRgba::BLACK,
+            lightmap_size: None,
+        }]);
+        let hit = scene
+            .intersect(Vec3A::ZERO, Vec3A::Y, f32::INFINITY)
+            .unwrap();
+        assert!((hit.distance - 1.0).abs() < 1e-5);
+        assert!(scene.intersect(Vec3A::ZERO, Vec3A::Y, 0.5).is_none());
+        assert!(scene
+            .intersect(Vec3A::ZERO, Vec3A::X, f32::INFINITY)
+            .is_none());
+    }
+}
@@ -1,10 +1,11 @@
 //! Lightmaps, baked lighting textures that can be applied at runtime to provide
 //! diffuse global illumination.
 //!
-//! Bevy doesn't currently have any way to actually bake lightmaps, but they can
-//! be baked in an external tool like [Blender](http://blender.org), for example
-//! with an addon like [The Lightmapper]. The tools in the [`bevy-baked-gi`]
-//! project support other lightmap baking methods.
+//! With the `lightmap_baker` feature, lightmaps can be baked on the CPU with
+//! `bake_lightmaps`. They can also be baked in an external tool like
+//! [Blender](http://blender.org), for example with an addon like
+//! [The Lightmapper]. The tools in the [`bevy-baked-gi`] project support other
+//! lightmap baking methods.
 //!
 //! When a [`Lightmap`] component is added to an entity with a [`Mesh3d`] and a
 //! [`MeshMaterial3d<StandardMaterial>`], Bevy applies the lightmap when rendering. The brightness
@@ -66,6 +67,11 @@ use tracing::error;
 
 use crate::{binding_arrays_are_usable, ExtractMeshesSet};
 
+#[cfg(feature = "lightmap_baker")]
+mod bake;
+#[cfg(feature = "lightmap_baker")]
+pub use bake::*;
+
 /// The ID of the lightmap shader.
 pub const LIGHTMAP_SHADER_HANDLE: Handle<Shader> =
     weak_handle!("fc28203f-f258-47f3-973c-ce7d1dd70e59");
@@ -1,5 +1,6 @@
 use crate::Material;
 use bevy_asset::{AsAssetId, AssetId, Handle};
+use bevy_color::{Color, LinearRgba};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{component::Component, reflect::ReflectComponent};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
@@ -73,3 +74,53 @@ impl<M: Material> AsAssetId for MeshMaterial3d<M> {
         self.id()
     }
 }
+
+/// Overrides properties of the [`StandardMaterial`](crate::StandardMaterial) of a single mesh
+/// instance, without creating a new material asset.
+///
+/// The overrides are stored in the per-instance mesh data, so meshes sharing a material are still
+/// batched together regardless of their overrides: a thousand enemies with different tints can
+/// share a single material.
+///
+/// Overrides are applied by `pbr_input_from_standard_material`, so they also affect
+/// [`ExtendedMaterial`](crate::ExtendedMaterial)s based on it. They aren't supported by meshlets.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_color::Color;
+/// # use bevy_pbr::MaterialOverride;
+/// # fn system(mut commands: Commands, enemy: Entity) {
+/// commands.entity(enemy).insert(MaterialOverride {
+///     tint: Color::srgb(1.0, 0.5, 0.5),
+///     ..Default::default()
+/// });
+/// # }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, Clone, PartialEq)]
+pub struct MaterialOverride {
+    /// A color multiplied with the base color of the material.
+    ///
+    /// The channels of the linear color are clamped to `[0, 1]`, and stored with 8 bits of
+    /// precision each.
+    pub tint: Color,
+    /// A factor multiplied with the emissive light of the material.
+    pub emissive_intensity: f32,
+}
+
+impl Default for MaterialOverride {
+    fn default() -> Self {
+        Self {
+            tint: Color::WHITE,
+            emissive_intensity: 1.0,
+        }
+    }
+}
+
+impl MaterialOverride {
+    /// Returns the tint as a linear RGBA color packed into 8 bits per channel, as stored on the
+    /// GPU.
+    pub fn packed_tint(&self) -> u32 {
+        LinearRgba::from(self.tint).as_u32()
+    }
+}
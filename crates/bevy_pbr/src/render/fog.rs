@@ -30,6 +30,10 @@ pub struct GpuFog {
     bi: Vec3,
     /// Unsigned int representation of the active fog falloff mode
     mode: u32,
+    /// The height at which the fog has its base density
+    height_base: f32,
+    /// The rate at which the fog density decreases with height, or 0 if it doesn't
+    height_falloff: f32,
 }
 
 // Important: These must be kept in sync with `mesh_view_types.wgsl`
@@ -62,7 +66,7 @@ pub fn prepare_fog(
         return;
     };
     for (entity, fog) in views_iter {
-        let gpu_fog = if let Some(fog) = fog {
+        let mut gpu_fog = if let Some(fog) = fog {
             match &fog.falloff {
                 FogFalloff::Linear { start, end } => GpuFog {
                     mode: GPU_FOG_MODE_LINEAR,
@@ -102,6 +106,7 @@ pub fn prepare_fog(
                     directional_light_exponent: fog.directional_light_exponent,
                     be: *extinction,
                     bi: *inscattering,
+                    ..Default::default()
                 },
             }
         } else {
@@ -112,6 +117,11 @@ pub fn prepare_fog(
             }
         };
 
+        if let Some(height_falloff) = fog.and_then(|fog| fog.height_falloff) {
+            gpu_fog.height_base = height_falloff.base_height;
+            gpu_fog.height_falloff = height_falloff.falloff;
+        }
+
         // This is later read by `SetMeshViewBindGroup<I>`
         commands.entity(entity).insert(ViewFogUniformOffset {
             offset: writer.write(&gpu_fog),
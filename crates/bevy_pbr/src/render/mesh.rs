@@ -498,8 +498,10 @@ pub struct MeshUniform {
     pub material_and_lightmap_bind_group_slot: u32,
     /// User supplied tag to identify this mesh instance.
     pub tag: u32,
-    /// Padding.
-    pub pad: u32,
+    /// The [`MaterialOverride::tint`], as a linear RGBA color packed into 8 bits per channel.
+    pub tint: u32,
+    /// The [`MaterialOverride::emissive_intensity`].
+    pub emissive_intensity: f32,
 }
 
 /// Information that has to be transferred from CPU to GPU in order to produce
@@ -564,8 +566,16 @@ pub struct MeshInputUniform {
     pub timestamp: u32,
     /// User supplied tag to identify this mesh instance.
     pub tag: u32,
+    /// The [`MaterialOverride::tint`], as a linear RGBA color packed into 8 bits per channel.
+    pub tint: u32,
+    /// The [`MaterialOverride::emissive_intensity`].
+    pub emissive_intensity: f32,
+    /// Padding.
+    pub pad_a: u32,
     /// Padding.
-    pub pad: u32,
+    pub pad_b: u32,
+    /// Padding.
+    pub pad_c: u32,
 }
 
 /// Information about each mesh instance needed to cull it on GPU.
@@ -619,9 +629,17 @@ impl MeshUniform {
             material_and_lightmap_bind_group_slot: u32::from(material_bind_group_slot)
                 | ((lightmap_bind_group_slot as u32) << 16),
             tag: tag.unwrap_or(0),
-            pad: 0,
+            tint: u32::MAX,
+            emissive_intensity: 1.0,
         }
     }
+
+    /// Applies the per-instance [`MaterialOverride`] of the mesh.
+    pub fn with_material_override(mut self, material_override: &MaterialOverride) -> Self {
+        self.tint = material_override.packed_tint();
+        self.emissive_intensity = material_override.emissive_intensity;
+        self
+    }
 }
 
 // NOTE: These must match the bit flags in bevy_pbr/src/render/mesh_types.wgsl!
@@ -753,6 +771,8 @@ pub struct RenderMeshInstanceShared {
     pub lightmap_slab_index: Option<LightmapSlabIndex>,
     /// User supplied tag to identify this mesh instance.
     pub tag: u32,
+    /// The [`MaterialOverride`] of this mesh instance.
+    pub material_override: MaterialOverride,
 }
 
 /// Information that is gathered during the parallel portion of mesh extraction
@@ -840,6 +860,7 @@ impl RenderMeshInstanceShared {
         previous_transform: Option<&PreviousGlobalTransform>,
         mesh: &Mesh3d,
         tag: Option<&MeshTag>,
+        material_override: Option<&MaterialOverride>,
         not_shadow_caster: bool,
         no_automatic_batching: bool,
     ) -> Self {
@@ -861,6 +882,7 @@ impl RenderMeshInstanceShared {
             material_bindings_index: default(),
             lightmap_slab_index: None,
             tag: tag.map_or(0, |i| **i),
+            material_override: material_override.copied().unwrap_or_default(),
         }
     }
 
@@ -1201,7 +1223,11 @@ impl RenderMeshInstanceGpuBuilder {
                 self.shared.material_bindings_index.slot,
             ) | ((lightmap_slot as u32) << 16),
             tag: self.shared.tag,
-            pad: 0,
+            tint: self.shared.material_override.packed_tint(),
+            emissive_intensity: self.shared.material_override.emissive_intensity,
+            pad_a: 0,
+            pad_b: 0,
+            pad_c: 0,
         };
 
         // Did the last frame contain this entity as well?
@@ -1340,6 +1366,7 @@ pub fn extract_meshes_for_cpu_building(
             Option<&PreviousGlobalTransform>,
             &Mesh3d,
             Option<&MeshTag>,
+            Option<&MaterialOverride>,
             Has<NoFrustumCulling>,
             Has<NotShadowReceiver>,
             Has<TransmittedShadowReceiver>,
@@ -1359,6 +1386,7 @@ pub fn extract_meshes_for_cpu_building(
             previous_transform,
             mesh,
             tag,
+            material_override,
             no_frustum_culling,
             not_shadow_receiver,
             transmitted_receiver,
@@ -1387,6 +1415,7 @@ pub fn extract_meshes_for_cpu_building(
                 previous_transform,
                 mesh,
                 tag,
+                material_override,
                 not_shadow_caster,
                 no_automatic_batching,
             );
@@ -1436,6 +1465,7 @@ type GpuMeshExtractionQuery = (
     Option<Read<Aabb>>,
     Read<Mesh3d>,
     Option<Read<MeshTag>>,
+    Option<Read<MaterialOverride>>,
     Has<NoFrustumCulling>,
     Has<NotShadowReceiver>,
     Has<TransmittedShadowReceiver>,
@@ -1466,6 +1496,7 @@ pub fn extract_meshes_for_gpu_building(
                 Changed<Lightmap>,
                 Changed<Aabb>,
                 Changed<Mesh3d>,
+                Changed<MaterialOverride>,
                 Changed<NoFrustumCulling>,
                 Changed<NotShadowReceiver>,
                 Changed<TransmittedShadowReceiver>,
@@ -1561,6 +1592,7 @@ fn extract_mesh_for_gpu_building(
         aabb,
         mesh,
         tag,
+        material_override,
         no_frustum_culling,
         not_shadow_receiver,
         transmitted_receiver,
@@ -1595,6 +1627,7 @@ fn extract_mesh_for_gpu_building(
         previous_transform,
         mesh,
         tag,
+        material_override,
         not_shadow_caster,
         no_automatic_batching,
     );
@@ -1942,7 +1975,8 @@ impl GetBatchData for MeshPipeline {
                 maybe_lightmap.map(|lightmap| (lightmap.slot_index, lightmap.uv_rect)),
                 current_skin_index,
                 Some(mesh_instance.tag),
-            ),
+            )
+            .with_material_override(&mesh_instance.material_override),
             mesh_instance.should_batch().then_some((
                 material_bind_group_index.group,
                 mesh_instance.mesh_asset_id,
@@ -2003,14 +2037,17 @@ impl GetFullBatchData for MeshPipeline {
 
         let current_skin_index = skin_uniforms.skin_index(main_entity);
 
-        Some(MeshUniform::new(
-            &mesh_instance.transforms,
-            first_vertex_index,
-            mesh_instance.material_bindings_index.slot,
-            maybe_lightmap.map(|lightmap| (lightmap.slot_index, lightmap.uv_rect)),
-            current_skin_index,
-            Some(mesh_instance.tag),
-        ))
+        Some(
+            MeshUniform::new(
+                &mesh_instance.transforms,
+                first_vertex_index,
+                mesh_instance.material_bindings_index.slot,
+                maybe_lightmap.map(|lightmap| (lightmap.slot_index, lightmap.uv_rect)),
+                current_skin_index,
+                Some(mesh_instance.tag),
+            )
+            .with_material_override(&mesh_instance.material_override),
+        )
     }
 
     fn get_binned_index(
@@ -370,4 +370,6 @@ fn main(@builtin(global_invocation_id) global_invocation_id: vec3<u32>) {
     output[mesh_output_index].material_and_lightmap_bind_group_slot =
         current_input[input_index].material_and_lightmap_bind_group_slot;
     output[mesh_output_index].tag = current_input[input_index].tag;
+    output[mesh_output_index].tint = current_input[input_index].tint;
+    output[mesh_output_index].emissive_intensity = current_input[input_index].emissive_intensity;
 }
@@ -23,7 +23,10 @@ struct Mesh {
     material_and_lightmap_bind_group_slot: u32,
     // User supplied index to identify the mesh instance
     tag: u32,
-    pad: u32,
+    // The `MaterialOverride` tint, as a linear RGBA color packed with `pack4x8unorm`.
+    tint: u32,
+    // The `MaterialOverride` emissive intensity.
+    emissive_intensity: f32,
 };
 
 #ifdef SKINNED
@@ -86,6 +86,9 @@ struct Fog {
     directional_light_exponent: f32,
     bi: vec3<f32>,
     mode: u32,
+    // The density is multiplied by `exp(-height_falloff * (y - height_base))`
+    height_base: f32,
+    height_falloff: f32,
 }
 
 // Important: These must be kept in sync with `fog.rs`
@@ -97,6 +97,10 @@ fn pbr_input_from_standard_material(
     var pbr_input: pbr_types::PbrInput = pbr_input_from_vertex_output(in, is_front, double_sided);
     pbr_input.material.flags = flags;
     pbr_input.material.base_color *= base_color;
+#ifndef MESHLET_MESH_MATERIAL_PASS
+    // Apply the per-instance `MaterialOverride` tint.
+    pbr_input.material.base_color *= unpack4x8unorm(mesh[in.instance_index].tint);
+#endif  // MESHLET_MESH_MATERIAL_PASS
     pbr_input.material.deferred_lighting_pass_id = deferred_lighting_pass_id;
 
     // Neubelt and Pettineo 2013, "Crafting a Next-gen Material Pipeline for The Order: 1886"
@@ -366,6 +370,9 @@ fn pbr_input_from_standard_material(
             emissive.a);
         }
 #endif
+#ifndef MESHLET_MESH_MATERIAL_PASS
+        emissive = vec4<f32>(emissive.rgb * mesh[in.instance_index].emissive_intensity, emissive.a);
+#endif  // MESHLET_MESH_MATERIAL_PASS
         pbr_input.material.emissive = emissive;
 
         // metallic and perceptual roughness
@@ -769,6 +769,20 @@ fn apply_fog(fog_params: mesh_view_types::Fog, input_color: vec4<f32>, fragment_
     // fog shape that looks a bit fake
     let distance = length(view_to_world);
 
+    // With height falloff, the density varies along the ray. Scale the distance by the average
+    // of `exp(-height_falloff * (y - height_base))` along the ray, so that the fog falloff
+    // integrates the same amount of fog.
+    var fog_distance = distance;
+    if fog_params.height_falloff != 0.0 {
+        let view_density = exp(-fog_params.height_falloff * (view_world_position.y - fog_params.height_base));
+        let height_difference = fog_params.height_falloff * view_to_world.y;
+        if abs(height_difference) > 0.0001 {
+            fog_distance *= view_density * (1.0 - exp(-height_difference)) / height_difference;
+        } else {
+            fog_distance *= view_density;
+        }
+    }
+
     var scattering = vec3<f32>(0.0);
     if fog_params.directional_light_color.a > 0.0 {
         let view_to_world_normalized = view_to_world / distance;
@@ -786,13 +800,13 @@ fn apply_fog(fog_params: mesh_view_types::Fog, input_color: vec4<f32>, fragment_
     }
 
     if fog_params.mode == mesh_view_types::FOG_MODE_LINEAR {
-        return bevy_pbr::fog::linear_fog(fog_params, input_color, distance, scattering);
+        return bevy_pbr::fog::linear_fog(fog_params, input_color, fog_distance, scattering);
     } else if fog_params.mode == mesh_view_types::FOG_MODE_EXPONENTIAL {
-        return bevy_pbr::fog::exponential_fog(fog_params, input_color, distance, scattering);
+        return bevy_pbr::fog::exponential_fog(fog_params, input_color, fog_distance, scattering);
     } else if fog_params.mode == mesh_view_types::FOG_MODE_EXPONENTIAL_SQUARED {
-        return bevy_pbr::fog::exponential_squared_fog(fog_params, input_color, distance, scattering);
+        return bevy_pbr::fog::exponential_squared_fog(fog_params, input_color, fog_distance, scattering);
     } else if fog_params.mode == mesh_view_types::FOG_MODE_ATMOSPHERIC {
-        return bevy_pbr::fog::atmospheric_fog(fog_params, input_color, distance, scattering);
+        return bevy_pbr::fog::atmospheric_fog(fog_params, input_color, fog_distance, scattering);
     } else {
         return input_color;
     }
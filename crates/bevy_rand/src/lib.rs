@@ -0,0 +1,78 @@
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![doc(
+    html_logo_url = "https://bevyengine.org/assets/icon.png",
+    html_favicon_url = "https://bevyengine.org/assets/icon.png"
+)]
+
+//! Provides deterministic, seeded random number generation, for replays, tests and lockstep
+//! networking.
+//!
+//! The [`GlobalRng`] resource is created from a single seed by the [`RngPlugin`]. Instead of
+//! sharing it, each system or entity gets its own independent stream with [`GlobalRng::fork`], so
+//! that the numbers drawn don't depend on the order systems run in. The [`SystemRng`] system param
+//! forks a stream for its system automatically.
+//!
+//! All the generators implement [`RngCore`], so they can be used with the `rand` crate.
+//!
+//! ```
+//! # use bevy_app::prelude::*;
+//! # use bevy_ecs::prelude::*;
+//! use bevy_rand::prelude::*;
+//!
+//! fn roll_dice(mut rng: SystemRng) {
+//!     let roll = rng.next_u32() % 6 + 1;
+//!     # let _ = roll;
+//! }
+//!
+//! App::new()
+//!     .add_plugins(RngPlugin::with_seed(42))
+//!     .add_systems(Update, roll_dice)
+//!     .update();
+//! ```
+
+extern crate alloc;
+
+mod rng;
+mod stable_hash;
+
+pub use rand_core::{RngCore, SeedableRng};
+pub use rng::*;
+
+/// The random number generation prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{GlobalRng, RngCore, RngPlugin, RngStream, SystemRng};
+}
+
+use bevy_app::prelude::*;
+use log::info;
+use rand_core::OsRng;
+
+/// Inserts the [`GlobalRng`] resource.
+///
+/// The seed is logged when it isn't set, so that a run can be replayed by setting it.
+#[derive(Default)]
+pub struct RngPlugin {
+    /// The seed of the [`GlobalRng`]. A random seed is used if `None`.
+    pub seed: Option<u64>,
+}
+
+impl RngPlugin {
+    /// Creates a plugin seeding the [`GlobalRng`] with `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { seed: Some(seed) }
+    }
+}
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        let seed = self.seed.unwrap_or_else(|| {
+            let seed = OsRng.next_u64();
+            info!("Seeding the GlobalRng with {seed}");
+            seed
+        });
+        app.insert_resource(GlobalRng::new(seed));
+    }
+}
@@ -0,0 +1,208 @@
+use bevy_ecs::{
+    component::Component,
+    resource::Resource,
+    system::{Local, Res, SystemName, SystemParam},
+};
+use core::hash::Hash;
+use rand_chacha::ChaCha8Rng;
+use rand_core::{RngCore, SeedableRng};
+
+use crate::stable_hash::stable_hash;
+
+/// The seeded random number generator of the app, inserted by the [`RngPlugin`](crate::RngPlugin).
+///
+/// It can be used directly through [`RngCore`], but the numbers drawn then depend on the order the
+/// systems using it run in. Systems and entities should rather draw from their own stream, created
+/// with [`GlobalRng::fork`] or the [`SystemRng`] system param.
+///
+/// The generator is a `ChaCha8` stream cipher, giving the same numbers on every platform.
+#[derive(Resource, Clone, Debug)]
+pub struct GlobalRng {
+    seed: u64,
+    generation: u64,
+    rng: ChaCha8Rng,
+}
+
+impl GlobalRng {
+    /// Creates a generator from a `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            generation: 0,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the seed the generator was created or [reseeded](Self::reseed) with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Starts the generator over from a new `seed`, for example to replay a recorded session.
+    ///
+    /// The streams of the [`SystemRng`]s are forked again from the new seed. Streams returned by
+    /// [`GlobalRng::fork`] before reseeding are unaffected.
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.generation += 1;
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+    }
+
+    /// Returns an independent stream for `label`, such as a system name or an
+    /// [`Entity`](bevy_ecs::entity::Entity).
+    ///
+    /// The stream only depends on the seed and the label: forking the same label twice returns the
+    /// same stream, and drawing numbers from the [`GlobalRng`] or other streams doesn't affect it.
+    /// Labels are hashed the same way on every platform, but entities are only the same across runs
+    /// if they are spawned in the same order.
+    pub fn fork(&self, label: impl Hash) -> RngStream {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        // Stream 0 is used by the `GlobalRng` itself.
+        rng.set_stream(stable_hash(label) | 1);
+        RngStream(rng)
+    }
+}
+
+impl RngCore for GlobalRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+/// An independent stream of random numbers, forked from the [`GlobalRng`] or another stream.
+///
+/// Streams can be stored as components to give each entity its own.
+#[derive(Component, Clone, Debug)]
+pub struct RngStream(ChaCha8Rng);
+
+impl RngStream {
+    /// Returns an independent stream for `label`, depending only on this stream and `label`, like
+    /// [`GlobalRng::fork`].
+    pub fn fork(&self, label: impl Hash) -> RngStream {
+        let mut rng = ChaCha8Rng::from_seed(self.0.get_seed());
+        rng.set_stream(stable_hash((self.0.get_stream(), label)) | 1);
+        RngStream(rng)
+    }
+}
+
+impl RngCore for RngStream {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+/// A [`SystemParam`] giving each system its own [`RngStream`], forked from the [`GlobalRng`] with
+/// the name of the system as the label.
+///
+/// The stream continues from one run of the system to the next, and is forked again when the
+/// [`GlobalRng`] is [reseeded](GlobalRng::reseed). Since the name of a system is its type name,
+/// the stream changes when the system is renamed or moved to another module.
+#[derive(SystemParam)]
+pub struct SystemRng<'w, 's> {
+    global: Res<'w, GlobalRng>,
+    name: SystemName<'s>,
+    /// The stream of the system, with the generation of the [`GlobalRng`] it was forked from.
+    stream: Local<'s, Option<(u64, RngStream)>>,
+}
+
+impl SystemRng<'_, '_> {
+    /// Returns the stream of the system.
+    pub fn stream(&mut self) -> &mut RngStream {
+        let generation = self.global.generation;
+        if self
+            .stream
+            .as_ref()
+            .is_none_or(|(forked_from, _)| *forked_from != generation)
+        {
+            *self.stream = Some((generation, self.global.fork(self.name.name())));
+        }
+        &mut self.stream.as_mut().unwrap().1
+    }
+}
+
+impl RngCore for SystemRng<'_, '_> {
+    fn next_u32(&mut self) -> u32 {
+        self.stream().next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.stream().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.stream().fill_bytes(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.stream().try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use bevy_ecs::{
+        system::{IntoSystem, ResMut, RunSystemOnce, System},
+        world::World,
+    };
+
+    #[test]
+    fn forks_are_independent() {
+        let mut global = GlobalRng::new(7);
+        let first = global.fork("enemy").next_u64();
+        global.next_u64();
+        assert_eq!(global.fork("enemy").next_u64(), first);
+        assert_ne!(global.fork("player").next_u64(), first);
+        assert_ne!(GlobalRng::new(8).fork("enemy").next_u64(), first);
+
+        let stream = global.fork("enemy");
+        assert_eq!(stream.fork(1).next_u64(), stream.fork(1).next_u64());
+        assert_ne!(stream.fork(1).next_u64(), stream.fork(2).next_u64());
+    }
+
+    #[test]
+    fn system_rng() {
+        fn draw(mut rng: SystemRng) -> u64 {
+            rng.next_u64()
+        }
+
+        let mut world = World::new();
+        world.insert_resource(GlobalRng::new(3));
+        let mut system = IntoSystem::into_system(draw);
+        system.initialize(&mut world);
+        let mut run = |world: &mut World| system.run((), world);
+
+        let drawn: Vec<u64> = (0..2).map(|_| run(&mut world)).collect();
+        assert_ne!(drawn[0], drawn[1]);
+
+        world
+            .run_system_once(|mut global: ResMut<GlobalRng>| global.reseed(3))
+            .unwrap();
+        assert_eq!(run(&mut world), drawn[0]);
+    }
+}
@@ -0,0 +1,85 @@
+use core::hash::{Hash, Hasher};
+
+/// Hashes `value` the same way on every platform and in every run, unlike the hashers used by
+/// hash maps, whose results can depend on the endianness, pointer width or random keys.
+pub(crate) fn stable_hash(value: impl Hash) -> u64 {
+    let mut hasher = StableHasher(FNV_OFFSET_BASIS);
+    value.hash(&mut hasher);
+    // FNV-1a mixes the last bytes poorly, so the result is finalized with SplitMix64.
+    let mut hash = hasher.0;
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d049bb133111eb);
+    hash ^ (hash >> 31)
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// A FNV-1a hasher writing integers in little endian, and `usize`s as `u64`s.
+struct StableHasher(u64);
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as i64 as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stable_values() {
+        // These values must never change, as they would change the streams of existing seeds.
+        assert_eq!(stable_hash("player"), 0x5f89515cb3bb1a29);
+        assert_eq!(stable_hash(1_u64), 0x5ca6bbcbb1e85355);
+        assert_eq!(stable_hash(1_usize), stable_hash(1_u64));
+        assert_ne!(stable_hash("player"), stable_hash("enemy"));
+    }
+}
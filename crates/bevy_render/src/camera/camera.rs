@@ -514,10 +514,7 @@ impl Camera {
         camera_transform: &GlobalTransform,
         world_position: Vec3,
     ) -> Result<Vec2, ViewportConversionError> {
-        let target_rect = self
-            .logical_viewport_rect()
-            .ok_or(ViewportConversionError::NoViewportSize)?;
-        let mut ndc_space_coords = self
+        let ndc_space_coords = self
             .world_to_ndc(camera_transform, world_position)
             .ok_or(ViewportConversionError::InvalidData)?;
         // NDC z-values outside of 0 < z < 1 are outside the (implicit) camera frustum and are thus not in viewport-space
@@ -528,13 +525,8 @@ impl Camera {
             return Err(ViewportConversionError::PastFarPlane);
         }
 
-        // Flip the Y co-ordinate origin from the bottom to the top.
-        ndc_space_coords.y = -ndc_space_coords.y;
-
         // Once in NDC space, we can discard the z element and map x/y to the viewport rect
-        let viewport_position =
-            (ndc_space_coords.truncate() + Vec2::ONE) / 2.0 * target_rect.size() + target_rect.min;
-        Ok(viewport_position)
+        self.ndc_to_viewport(ndc_space_coords.truncate())
     }
 
     /// Given a position in world space, use the camera to compute the viewport-space coordinates and depth.
@@ -552,10 +544,7 @@ impl Camera {
         camera_transform: &GlobalTransform,
         world_position: Vec3,
     ) -> Result<Vec3, ViewportConversionError> {
-        let target_rect = self
-            .logical_viewport_rect()
-            .ok_or(ViewportConversionError::NoViewportSize)?;
-        let mut ndc_space_coords = self
+        let ndc_space_coords = self
             .world_to_ndc(camera_transform, world_position)
             .ok_or(ViewportConversionError::InvalidData)?;
         // NDC z-values outside of 0 < z < 1 are outside the (implicit) camera frustum and are thus not in viewport-space
@@ -569,12 +558,8 @@ impl Camera {
         // Stretching ndc depth to value via near plane and negating result to be in positive room again.
         let depth = -self.depth_ndc_to_view_z(ndc_space_coords.z);
 
-        // Flip the Y co-ordinate origin from the bottom to the top.
-        ndc_space_coords.y = -ndc_space_coords.y;
-
         // Once in NDC space, we can discard the z element and map x/y to the viewport rect
-        let viewport_position =
-            (ndc_space_coords.truncate() + Vec2::ONE) / 2.0 * target_rect.size() + target_rect.min;
+        let viewport_position = self.ndc_to_viewport(ndc_space_coords.truncate())?;
         Ok(viewport_position.extend(depth))
     }
 
@@ -596,14 +581,7 @@ impl Camera {
         camera_transform: &GlobalTransform,
         viewport_position: Vec2,
     ) -> Result<Ray3d, ViewportConversionError> {
-        let target_rect = self
-            .logical_viewport_rect()
-            .ok_or(ViewportConversionError::NoViewportSize)?;
-        let mut rect_relative = (viewport_position - target_rect.min) / target_rect.size();
-        // Flip the Y co-ordinate origin from the top to the bottom.
-        rect_relative.y = 1.0 - rect_relative.y;
-
-        let ndc = rect_relative * 2. - Vec2::ONE;
+        let ndc = self.viewport_to_ndc(viewport_position)?;
         let ndc_to_world =
             camera_transform.compute_matrix() * self.computed.clip_from_view.inverse();
         let world_near_plane = ndc_to_world.project_point3(ndc.extend(1.));
@@ -635,15 +613,7 @@ impl Camera {
         camera_transform: &GlobalTransform,
         viewport_position: Vec2,
     ) -> Result<Vec2, ViewportConversionError> {
-        let target_rect = self
-            .logical_viewport_rect()
-            .ok_or(ViewportConversionError::NoViewportSize)?;
-        let mut rect_relative = (viewport_position - target_rect.min) / target_rect.size();
-
-        // Flip the Y co-ordinate origin from the top to the bottom.
-        rect_relative.y = 1.0 - rect_relative.y;
-
-        let ndc = rect_relative * 2. - Vec2::ONE;
+        let ndc = self.viewport_to_ndc(viewport_position)?;
 
         let world_near_plane = self
             .ndc_to_world(camera_transform, ndc.extend(1.))
@@ -700,6 +670,42 @@ impl Camera {
         (!world_space_coords.is_nan()).then_some(world_space_coords)
     }
 
+    /// Given a position on this [`Camera`]'s viewport, in logical pixels from the top-left corner of
+    /// the render target, compute its X and Y Normalized Device Coordinates.
+    ///
+    /// Positions within the viewport have coordinates between -1.0 and 1.0, with Y pointing up. This
+    /// accounts for the [`Viewport`] and the scale factor of the render target, so cursor positions
+    /// can be passed directly.
+    ///
+    /// This is the inverse of [`ndc_to_viewport`](Self::ndc_to_viewport).
+    pub fn viewport_to_ndc(
+        &self,
+        viewport_position: Vec2,
+    ) -> Result<Vec2, ViewportConversionError> {
+        let target_rect = self
+            .logical_viewport_rect()
+            .ok_or(ViewportConversionError::NoViewportSize)?;
+        let mut rect_relative = (viewport_position - target_rect.min) / target_rect.size();
+        // Flip the Y co-ordinate origin from the top to the bottom.
+        rect_relative.y = 1.0 - rect_relative.y;
+
+        Ok(rect_relative * 2. - Vec2::ONE)
+    }
+
+    /// Given X and Y Normalized Device Coordinates, compute the position on this [`Camera`]'s
+    /// viewport, in logical pixels from the top-left corner of the render target.
+    ///
+    /// This is the inverse of [`viewport_to_ndc`](Self::viewport_to_ndc).
+    pub fn ndc_to_viewport(&self, ndc: Vec2) -> Result<Vec2, ViewportConversionError> {
+        let target_rect = self
+            .logical_viewport_rect()
+            .ok_or(ViewportConversionError::NoViewportSize)?;
+        // Flip the Y co-ordinate origin from the bottom to the top.
+        let rect_relative = (Vec2::new(ndc.x, -ndc.y) + Vec2::ONE) / 2.0;
+
+        Ok(rect_relative * target_rect.size() + target_rect.min)
+    }
+
     /// Converts the depth in Normalized Device Coordinates
     /// to linear view z for perspective projections.
     ///
@@ -17,7 +17,7 @@ use wgpu::{
 
 use crate::renderer::{RenderAdapterInfo, RenderDevice, RenderQueue, WgpuWrapper};
 
-use super::RecordDiagnostics;
+use super::{RecordDiagnostics, RenderDiagnosticsPlugin};
 
 // buffer offset must be divisible by 256, so this constant must be divisible by 32 (=256/8)
 const MAX_TIMESTAMP_QUERIES: u32 = 256;
@@ -514,6 +514,7 @@ impl FrameData {
             .collect::<Vec<u64>>();
 
         let mut diagnostics = Vec::new();
+        let mut elapsed_gpu = None;
 
         for span in &self.closed_spans {
             if let (Some(begin), Some(end)) = (span.begin_instant, span.end_instant) {
@@ -530,6 +531,11 @@ impl FrameData {
                 let end = timestamps[end as usize] as f64;
                 let value = (end - begin) * (timestamp_period_ns as f64) / 1e6;
 
+                // Nested spans are already counted by their parent.
+                if span.path_range.len() == 1 {
+                    *elapsed_gpu.get_or_insert(0.0) += value;
+                }
+
                 #[cfg(feature = "tracing-tracy")]
                 {
                     // Calling span_alloc() and end_zone() here instead of in open_span() and close_span() means that tracy does not know where each GPU command was recorded on the CPU timeline.
@@ -589,6 +595,14 @@ impl FrameData {
             }
         }
 
+        if let Some(value) = elapsed_gpu {
+            diagnostics.push(RenderDiagnostic {
+                path: RenderDiagnosticsPlugin::ELAPSED_GPU,
+                suffix: "ms",
+                value,
+            });
+        }
+
         callback(RenderDiagnostics(diagnostics));
 
         drop(data);
@@ -10,6 +10,7 @@ use alloc::{borrow::Cow, sync::Arc};
 use core::marker::PhantomData;
 
 use bevy_app::{App, Plugin, PreUpdate};
+use bevy_diagnostic::DiagnosticPath;
 
 use crate::{renderer::RenderAdapterInfo, RenderApp};
 
@@ -42,12 +43,24 @@ use super::{RenderDevice, RenderQueue};
 ///     time_span.end(render_context.command_encoder());
 ///     ```
 ///
+/// The GPU time of the whole frame is recorded as [`RenderDiagnosticsPlugin::ELAPSED_GPU`]. When it
+/// is close to the [frame time](bevy_diagnostic::FrameTimeDiagnosticsPlugin::FRAME_TIME), the app
+/// is GPU bound.
+///
 /// # Supported platforms
 /// Timestamp queries and pipeline statistics are currently supported only on Vulkan and DX12.
 /// On other platforms (Metal, WebGPU, WebGL2) only CPU time will be recorded.
 #[derive(Default)]
 pub struct RenderDiagnosticsPlugin;
 
+impl RenderDiagnosticsPlugin {
+    /// The GPU time of the frame in milliseconds, the sum of the GPU time of the spans that aren't
+    /// nested in another span.
+    ///
+    /// It is recorded a few frames late, once the timestamps are downloaded from the GPU.
+    pub const ELAPSED_GPU: DiagnosticPath = DiagnosticPath::const_new("render/elapsed_gpu");
+}
+
 impl Plugin for RenderDiagnosticsPlugin {
     fn build(&self, app: &mut App) {
         let render_diagnostics_mutex = RenderDiagnosticsMutex::default();
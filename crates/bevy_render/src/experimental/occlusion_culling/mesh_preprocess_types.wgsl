@@ -21,7 +21,13 @@ struct MeshInput {
     timestamp: u32,
     // User supplied index to identify the mesh instance
     tag: u32,
-    pad: u32,
+    // The `MaterialOverride` tint, as a linear RGBA color packed with `pack4x8unorm`.
+    tint: u32,
+    // The `MaterialOverride` emissive intensity.
+    emissive_intensity: f32,
+    pad_a: u32,
+    pad_b: u32,
+    pad_c: u32,
 }
 
 // The `wgpu` indirect parameters structure. This is a union of two structures.
@@ -67,7 +67,9 @@ pub mod prelude {
         },
         render_resource::Shader,
         texture::ImagePlugin,
-        view::{InheritedVisibility, Msaa, ViewVisibility, Visibility},
+        view::{
+            Billboard, InheritedVisibility, Msaa, ViewVisibility, Visibility, VisibilityCommandsExt,
+        },
         ExtractSchedule,
     };
 }
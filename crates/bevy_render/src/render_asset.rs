@@ -2,11 +2,12 @@ use crate::{
     render_resource::AsBindGroupError, Extract, ExtractSchedule, MainWorld, Render, RenderApp,
     RenderSet, Res,
 };
-use bevy_app::{App, Plugin, SubApp};
+use bevy_app::{App, Plugin, PostUpdate, SubApp};
 pub use bevy_asset::RenderAssetUsages;
-use bevy_asset::{Asset, AssetEvent, AssetId, Assets};
+use bevy_asset::{Asset, AssetEvent, AssetEvents, AssetId, Assets};
 use bevy_ecs::{
-    prelude::{Commands, EventReader, IntoScheduleConfigs, ResMut, Resource},
+    event::{Event, Events},
+    prelude::{Commands, EventReader, EventWriter, IntoScheduleConfigs, ResMut, Resource},
     schedule::{ScheduleConfigs, SystemSet},
     system::{ScheduleSystem, StaticSystemParam, SystemParam, SystemParamItem, SystemState},
     world::{FromWorld, Mut},
@@ -29,6 +30,49 @@ pub enum PrepareAssetError<E: Send + Sync + 'static> {
 #[derive(SystemSet, Clone, PartialEq, Eq, Debug, Hash)]
 pub struct ExtractAssetsSet;
 
+/// The system set, in [`PostUpdate`], during which assets can be post-processed in response to
+/// [`PostprocessRenderAsset`] events, before being extracted to the render world.
+///
+/// Systems in this set run after [`AssetEvents`], and always before the [`ExtractSchedule`] of the
+/// same frame.
+#[derive(SystemSet, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct PostprocessRenderAssets;
+
+/// Sent once each time an asset of type `A` is added or modified, before it is extracted to the
+/// render world.
+///
+/// This allows assets to be mutated or augmented before they are uploaded to the GPU, for example to
+/// generate mipmaps or swap the palette of textures. Systems reading this event should run in the
+/// [`PostprocessRenderAssets`] set, and mutate assets with [`Assets::get_mut_untracked`]: the asset
+/// is extracted at the end of the frame anyway, and using [`Assets::get_mut`] would send this
+/// event again next frame.
+///
+/// ```
+/// # use bevy_app::{App, PostUpdate};
+/// # use bevy_asset::Assets;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_image::Image;
+/// # use bevy_render::render_asset::{PostprocessRenderAsset, PostprocessRenderAssets};
+/// fn fix_srgb(
+///     mut events: EventReader<PostprocessRenderAsset<Image>>,
+///     mut images: ResMut<Assets<Image>>,
+/// ) {
+///     for event in events.read() {
+///         if let Some(image) = images.get_mut_untracked(event.id) {
+///             image.texture_descriptor.format = image.texture_descriptor.format.add_srgb_suffix();
+///         }
+///     }
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_systems(PostUpdate, fix_srgb.in_set(PostprocessRenderAssets));
+/// ```
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PostprocessRenderAsset<A: Asset> {
+    /// The id of the asset to post-process.
+    pub id: AssetId<A>,
+}
+
 /// Describes how an asset gets extracted and prepared for rendering.
 ///
 /// In the [`ExtractSchedule`] step the [`RenderAsset::SourceAsset`] is transferred
@@ -113,6 +157,20 @@ impl<A: RenderAsset, AFTER: RenderAssetDependency + 'static> Plugin
 {
     fn build(&self, app: &mut App) {
         app.init_resource::<CachedExtractRenderAssetSystemState<A>>();
+        // Several render assets can share the same source asset.
+        if !app
+            .world()
+            .contains_resource::<Events<PostprocessRenderAsset<A::SourceAsset>>>()
+        {
+            app.add_event::<PostprocessRenderAsset<A::SourceAsset>>()
+                .configure_sets(PostUpdate, PostprocessRenderAssets.after(AssetEvents))
+                .add_systems(
+                    PostUpdate,
+                    send_postprocess_render_asset_events::<A::SourceAsset>
+                        .after(AssetEvents)
+                        .before(PostprocessRenderAssets),
+                );
+        }
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<ExtractedAssets<A>>()
@@ -231,6 +289,21 @@ impl<A: RenderAsset> FromWorld for CachedExtractRenderAssetSystemState<A> {
     }
 }
 
+/// Sends a [`PostprocessRenderAsset`] event for each asset that was added or modified.
+fn send_postprocess_render_asset_events<A: Asset>(
+    mut asset_events: EventReader<AssetEvent<A>>,
+    mut postprocess_events: EventWriter<PostprocessRenderAsset<A>>,
+) {
+    let mut ids = <HashSet<_>>::default();
+    for event in asset_events.read() {
+        if let AssetEvent::Added { id } | AssetEvent::Modified { id } = event {
+            if ids.insert(*id) {
+                postprocess_events.write(PostprocessRenderAsset { id: *id });
+            }
+        }
+    }
+}
+
 /// This system extracts all created or modified assets of the corresponding [`RenderAsset::SourceAsset`] type
 /// into the "render world".
 pub(crate) fn extract_render_asset<A: RenderAsset>(
@@ -77,6 +77,20 @@ impl CachedComputePipelineId {
 pub struct CachedPipeline {
     pub descriptor: PipelineDescriptor,
     pub state: CachedPipelineState,
+    /// The pipeline GPU object created before one of its shaders was modified, used until the
+    /// pipeline is successfully created again.
+    previous: Option<Pipeline>,
+}
+
+impl CachedPipeline {
+    /// Returns the pipeline GPU object if it was created, or the one created before one of its
+    /// shaders was modified otherwise.
+    fn pipeline(&self) -> Option<&Pipeline> {
+        match &self.state {
+            CachedPipelineState::Ok(pipeline) => Some(pipeline),
+            _ => self.previous.as_ref(),
+        }
+    }
 }
 
 /// State of a cached pipeline inserted into a [`PipelineCache`].
@@ -654,11 +668,12 @@ impl PipelineCache {
     /// This method returns a successfully created render pipeline if any, or `None` if the pipeline
     /// was not created yet or if there was an error during creation. You can check the actual creation
     /// state with [`PipelineCache::get_render_pipeline_state()`].
+    ///
+    /// When one of the shaders of the pipeline is modified, the previously created pipeline is
+    /// returned until the pipeline is successfully created again.
     #[inline]
     pub fn get_render_pipeline(&self, id: CachedRenderPipelineId) -> Option<&RenderPipeline> {
-        if let CachedPipelineState::Ok(Pipeline::RenderPipeline(pipeline)) =
-            &self.pipelines[id.0].state
-        {
+        if let Some(Pipeline::RenderPipeline(pipeline)) = self.pipelines[id.0].pipeline() {
             Some(pipeline)
         } else {
             None
@@ -688,11 +703,12 @@ impl PipelineCache {
     /// This method returns a successfully created compute pipeline if any, or `None` if the pipeline
     /// was not created yet or if there was an error during creation. You can check the actual creation
     /// state with [`PipelineCache::get_compute_pipeline_state()`].
+    ///
+    /// When one of the shaders of the pipeline is modified, the previously created pipeline is
+    /// returned until the pipeline is successfully created again.
     #[inline]
     pub fn get_compute_pipeline(&self, id: CachedComputePipelineId) -> Option<&ComputePipeline> {
-        if let CachedPipelineState::Ok(Pipeline::ComputePipeline(pipeline)) =
-            &self.pipelines[id.0].state
-        {
+        if let Some(Pipeline::ComputePipeline(pipeline)) = self.pipelines[id.0].pipeline() {
             Some(pipeline)
         } else {
             None
@@ -724,6 +740,7 @@ impl PipelineCache {
         new_pipelines.push(CachedPipeline {
             descriptor: PipelineDescriptor::RenderPipelineDescriptor(Box::new(descriptor)),
             state: CachedPipelineState::Queued,
+            previous: None,
         });
         id
     }
@@ -753,6 +770,7 @@ impl PipelineCache {
         new_pipelines.push(CachedPipeline {
             descriptor: PipelineDescriptor::ComputePipelineDescriptor(Box::new(descriptor)),
             state: CachedPipelineState::Queued,
+            previous: None,
         });
         id
     }
@@ -761,7 +779,14 @@ impl PipelineCache {
         let mut shader_cache = self.shader_cache.lock().unwrap();
         let pipelines_to_queue = shader_cache.set_shader(id, shader.clone());
         for cached_pipeline in pipelines_to_queue {
-            self.pipelines[cached_pipeline].state = CachedPipelineState::Queued;
+            let cached_pipeline_data = &mut self.pipelines[cached_pipeline];
+            // Keep using the current pipeline until the new one is created, so that a shader
+            // being hot-reloaded doesn't stop rendering, or breaks it with an error.
+            if let CachedPipelineState::Ok(pipeline) =
+                mem::replace(&mut cached_pipeline_data.state, CachedPipelineState::Queued)
+            {
+                cached_pipeline_data.previous = Some(pipeline);
+            }
             self.waiting_pipelines.insert(cached_pipeline);
         }
     }
@@ -771,6 +796,7 @@ impl PipelineCache {
         let pipelines_to_queue = shader_cache.remove(shader);
         for cached_pipeline in pipelines_to_queue {
             self.pipelines[cached_pipeline].state = CachedPipelineState::Queued;
+            self.pipelines[cached_pipeline].previous = None;
             self.waiting_pipelines.insert(cached_pipeline);
         }
     }
@@ -990,6 +1016,7 @@ impl PipelineCache {
             CachedPipelineState::Creating(task) => match bevy_tasks::futures::check_ready(task) {
                 Some(Ok(pipeline)) => {
                     cached_pipeline.state = CachedPipelineState::Ok(pipeline);
+                    cached_pipeline.previous = None;
                     return;
                 }
                 Some(Err(err)) => cached_pipeline.state = CachedPipelineState::Err(err),
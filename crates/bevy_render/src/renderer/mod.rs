@@ -184,6 +184,38 @@ const GPU_NOT_FOUND_ERROR_MESSAGE: &str = if cfg!(target_os = "linux") {
     "Unable to find a GPU! Make sure you have installed required drivers!"
 };
 
+/// Returns the first adapter whose name contains [`WgpuSettings::adapter_name`], if any.
+#[cfg(not(target_arch = "wasm32"))]
+fn find_adapter_by_name(
+    instance: &Instance,
+    options: &WgpuSettings,
+    request_adapter_options: &RequestAdapterOptions<'_, '_>,
+) -> Option<Adapter> {
+    let name = options.adapter_name.as_ref()?.to_lowercase();
+    let adapter = instance
+        .enumerate_adapters(options.backends.unwrap_or(wgpu::Backends::all()))
+        .into_iter()
+        .find(|adapter| {
+            adapter.get_info().name.to_lowercase().contains(&name)
+                && request_adapter_options
+                    .compatible_surface
+                    .is_none_or(|surface| adapter.is_surface_supported(surface))
+        });
+    if adapter.is_none() {
+        warn!("No adapter named \"{name}\" was found, selecting an adapter by power preference");
+    }
+    adapter
+}
+
+#[cfg(target_arch = "wasm32")]
+fn find_adapter_by_name(
+    _instance: &Instance,
+    _options: &WgpuSettings,
+    _request_adapter_options: &RequestAdapterOptions<'_, '_>,
+) -> Option<Adapter> {
+    None
+}
+
 /// Initializes the renderer by retrieving and preparing the GPU instance, device and queue
 /// for the specified backend.
 pub async fn initialize_renderer(
@@ -191,10 +223,13 @@ pub async fn initialize_renderer(
     options: &WgpuSettings,
     request_adapter_options: &RequestAdapterOptions<'_, '_>,
 ) -> (RenderDevice, RenderQueue, RenderAdapterInfo, RenderAdapter) {
-    let adapter = instance
-        .request_adapter(request_adapter_options)
-        .await
-        .expect(GPU_NOT_FOUND_ERROR_MESSAGE);
+    let adapter = match find_adapter_by_name(instance, options, request_adapter_options) {
+        Some(adapter) => adapter,
+        None => instance
+            .request_adapter(request_adapter_options)
+            .await
+            .expect(GPU_NOT_FOUND_ERROR_MESSAGE),
+    };
 
     let adapter_info = adapter.get_info();
     info!("{:?}", adapter_info);
@@ -228,6 +263,16 @@ pub async fn initialize_renderer(
         limits = adapter.limits();
     }
 
+    // Enable the optional features the adapter supports
+    let supported_optional_features = options.optional_features & adapter.features();
+    if supported_optional_features != options.optional_features {
+        info!(
+            "Optional features not supported by the adapter: {:?}",
+            options.optional_features - supported_optional_features
+        );
+    }
+    features |= supported_optional_features;
+
     // Enforce the disabled features
     if let Some(disabled_features) = options.disabled_features {
         features -= disabled_features;
@@ -32,12 +32,34 @@ pub enum WgpuSettingsPriority {
 #[derive(Clone)]
 pub struct WgpuSettings {
     pub device_label: Option<Cow<'static, str>>,
+    /// The backends the renderer may use, such as Vulkan, Metal, DX12 or GL.
+    ///
+    /// Defaults to the `WGPU_BACKEND` environment variable if set, or all the backends supported by
+    /// the platform otherwise.
     pub backends: Option<Backends>,
+    /// Whether to prefer a high-performance or a low-power (usually integrated) adapter.
+    ///
+    /// Defaults to the `WGPU_POWER_PREF` environment variable if set, or
+    /// [`PowerPreference::HighPerformance`] otherwise.
     pub power_preference: PowerPreference,
+    /// Selects the first adapter whose name contains this string, ignoring case, instead of
+    /// selecting an adapter according to the [`power_preference`](Self::power_preference).
+    ///
+    /// If no adapter matches, the adapter is selected according to the power preference.
+    /// This has no effect on the web.
+    pub adapter_name: Option<String>,
     pub priority: WgpuSettingsPriority,
     /// The features to ensure are enabled regardless of what the adapter/backend supports.
     /// Setting these explicitly may cause renderer initialization to fail.
     pub features: WgpuFeatures,
+    /// The features to enable if the adapter/backend supports them.
+    ///
+    /// Unlike [`features`](Self::features), unsupported optional features are skipped instead of
+    /// failing renderer initialization. This is mostly useful with
+    /// [`WgpuSettingsPriority::Compatibility`], as all supported features are enabled with
+    /// [`WgpuSettingsPriority::Functionality`]. Rendering features that depend on a missing feature,
+    /// such as GPU culling, automatically fall back to a slower path or are disabled.
+    pub optional_features: WgpuFeatures,
     /// The features to ensure are disabled regardless of what the adapter/backend supports
     pub disabled_features: Option<WgpuFeatures>,
     /// The imposed limits.
@@ -128,8 +150,10 @@ impl Default for WgpuSettings {
             device_label: Default::default(),
             backends,
             power_preference,
+            adapter_name: None,
             priority,
             features: wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
+            optional_features: WgpuFeatures::empty(),
             disabled_features: None,
             limits,
             constrained_limits: None,
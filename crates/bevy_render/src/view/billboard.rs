@@ -0,0 +1,136 @@
+//! Entities turning to face the camera.
+
+use alloc::vec::Vec;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::prelude::*;
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_transform::{
+    components::{GlobalTransform, Transform},
+    TransformSystem,
+};
+
+use super::VisibilitySystems;
+use crate::camera::Camera;
+
+/// A plugin that turns the [`Billboard`]s to face the camera.
+pub struct BillboardPlugin;
+
+impl Plugin for BillboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Billboard>().add_systems(
+            PostUpdate,
+            update_billboards
+                .after(TransformSystem::TransformPropagate)
+                .before(VisibilitySystems::CheckVisibility),
+        );
+    }
+}
+
+/// Makes an entity turn to face the camera, for health bars, impostors or foliage cards.
+///
+/// The rotation of its [`GlobalTransform`] is replaced after transform propagation in
+/// [`PostUpdate`], before the views are extracted, so that its local +Z axis, the side sprites
+/// and [`Rectangle`](bevy_math::primitives::Rectangle) meshes are facing, points toward the
+/// camera. Its translation and scale are kept, and its descendants turn with it.
+///
+/// Billboards face the active [`Camera`] with the highest [`order`](Camera::order). With several
+/// cameras, for example in split screen, they only face that one.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq, Clone)]
+pub enum Billboard {
+    /// Turns to face the position of the camera on all axes.
+    #[default]
+    Full,
+    /// Only turns around the world Y axis, staying upright, for trees and foliage cards.
+    AxisY,
+    /// Takes the rotation of the camera, staying parallel to the screen, for health bars and
+    /// labels.
+    Screen,
+}
+
+impl Billboard {
+    /// Returns the world rotation of a billboard at `translation` facing the `camera`.
+    pub fn rotation(self, translation: Vec3, camera: &GlobalTransform) -> Quat {
+        let to_camera = camera.translation() - translation;
+        let looking_to = match self {
+            Billboard::Full => Transform::IDENTITY.looking_to(-to_camera, camera.up()),
+            Billboard::AxisY => Transform::IDENTITY.looking_to(-to_camera.with_y(0.0), Vec3::Y),
+            Billboard::Screen => return camera.rotation(),
+        };
+        looking_to.rotation
+    }
+}
+
+/// Turns the [`Billboard`]s and their descendants to face the camera.
+pub fn update_billboards(
+    cameras: Query<(&Camera, &GlobalTransform), Without<Billboard>>,
+    mut billboards: Query<(&Billboard, &mut GlobalTransform, Option<&Children>)>,
+    mut descendants: Query<
+        (&Transform, &mut GlobalTransform, Option<&Children>),
+        (Without<Billboard>, Without<Camera>),
+    >,
+    mut stack: Local<Vec<(GlobalTransform, Entity)>>,
+) {
+    let Some((_, camera)) = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .max_by_key(|(camera, _)| camera.order)
+    else {
+        return;
+    };
+
+    for (billboard, mut global_transform, children) in &mut billboards {
+        let (scale, _, translation) = global_transform.to_scale_rotation_translation();
+        let rotation = billboard.rotation(translation, camera);
+        let global = GlobalTransform::from(Transform {
+            translation,
+            rotation,
+            scale,
+        });
+        if !global_transform.set_if_neq(global) {
+            continue;
+        }
+
+        // The descendants were already propagated from the previous rotation.
+        stack.extend(children.into_iter().flatten().map(|&child| (global, child)));
+        while let Some((parent, entity)) = stack.pop() {
+            let Ok((transform, mut global_transform, children)) = descendants.get_mut(entity)
+            else {
+                continue;
+            };
+            let global = parent.mul_transform(*transform);
+            global_transform.set_if_neq(global);
+            stack.extend(children.into_iter().flatten().map(|&child| (global, child)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn billboard_rotations() {
+        let camera = GlobalTransform::from(
+            Transform::from_xyz(0.0, 10.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+        );
+        let position = Vec3::new(5.0, 0.0, 0.0);
+
+        // The +Z axis points toward the camera.
+        let full = Billboard::Full.rotation(position, &camera);
+        let to_camera = (camera.translation() - position).normalize();
+        assert!((full * Vec3::Z).abs_diff_eq(to_camera, 1e-5));
+
+        // Stays upright, facing the camera horizontally.
+        let axis_y = Billboard::AxisY.rotation(position, &camera);
+        assert!((axis_y * Vec3::Y).abs_diff_eq(Vec3::Y, 1e-5));
+        let horizontal = to_camera.with_y(0.0).normalize();
+        assert!((axis_y * Vec3::Z).abs_diff_eq(horizontal, 1e-5));
+
+        // Parallel to the screen.
+        let screen = Billboard::Screen.rotation(position, &camera);
+        assert!(screen.abs_diff_eq(camera.rotation(), 1e-5));
+    }
+}
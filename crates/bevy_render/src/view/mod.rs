@@ -1,8 +1,10 @@
+mod billboard;
 pub mod visibility;
 pub mod window;
 
 use bevy_asset::{load_internal_asset, weak_handle, Handle};
 use bevy_diagnostic::FrameCount;
+pub use billboard::*;
 pub use visibility::*;
 pub use window::*;
 
@@ -118,6 +120,7 @@ impl Plugin for ViewPlugin {
                 ExtractComponentPlugin::<OcclusionCulling>::default(),
                 VisibilityPlugin,
                 VisibilityRangePlugin,
+                BillboardPlugin,
             ));
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
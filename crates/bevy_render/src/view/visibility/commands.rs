@@ -0,0 +1,128 @@
+use bevy_ecs::{
+    change_detection::DetectChangesMut, hierarchy::ChildOf, system::EntityCommands,
+    world::EntityWorldMut,
+};
+
+use super::{InheritedVisibility, Visibility};
+
+/// Methods on [`EntityWorldMut`] and [`EntityCommands`] to show and hide an entity, taking the
+/// visibility of its [`ChildOf`] target into account.
+///
+/// The [`Visibility`] is only changed, triggering change detection, if the entity wasn't already
+/// in the requested state.
+pub trait VisibilityCommandsExt {
+    /// Shows or hides this entity.
+    ///
+    /// A hidden entity is set to [`Visibility::Hidden`]. An entity that is shown is set to
+    /// [`Visibility::Inherited`] if its parent is visible, so that it is hidden with its parent
+    /// again later, and to [`Visibility::Visible`] otherwise.
+    ///
+    /// The visibility of the parent is its [`InheritedVisibility`], computed in
+    /// [`PostUpdate`](bevy_app::PostUpdate).
+    fn set_visible(&mut self, visible: bool) -> &mut Self;
+
+    /// Shows this entity if it is hidden, and hides it otherwise, with
+    /// [`set_visible`](Self::set_visible).
+    fn toggle_visibility(&mut self) -> &mut Self;
+}
+
+impl VisibilityCommandsExt for EntityCommands<'_> {
+    fn set_visible(&mut self, visible: bool) -> &mut Self {
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.set_visible(visible);
+        })
+    }
+
+    fn toggle_visibility(&mut self) -> &mut Self {
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.toggle_visibility();
+        })
+    }
+}
+
+impl VisibilityCommandsExt for EntityWorldMut<'_> {
+    fn set_visible(&mut self, visible: bool) -> &mut Self {
+        let parent_visible = parent_visible(self);
+        let current = self.get::<Visibility>().copied().unwrap_or_default();
+        let visibility = match (visible, current) {
+            (false, _) => Visibility::Hidden,
+            (true, Visibility::Visible) => Visibility::Visible,
+            (true, _) if parent_visible => Visibility::Inherited,
+            (true, _) => Visibility::Visible,
+        };
+        match self.get_mut::<Visibility>() {
+            Some(mut current) => {
+                current.set_if_neq(visibility);
+            }
+            None => {
+                self.insert(visibility);
+            }
+        }
+        self
+    }
+
+    fn toggle_visibility(&mut self) -> &mut Self {
+        let visible = match self.get::<Visibility>() {
+            Some(Visibility::Hidden) => false,
+            Some(Visibility::Visible) => true,
+            Some(Visibility::Inherited) | None => parent_visible(self),
+        };
+        self.set_visible(!visible)
+    }
+}
+
+/// Returns `true` if the entity has no parent, or if its parent is visible.
+fn parent_visible(entity: &EntityWorldMut) -> bool {
+    entity.get::<ChildOf>().is_none_or(|child_of| {
+        entity
+            .world()
+            .get::<InheritedVisibility>(child_of.parent)
+            .is_none_or(|visibility| visibility.get())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{change_detection::DetectChanges, world::World};
+
+    #[test]
+    fn set_visible_with_hidden_parent() {
+        let mut world = World::new();
+        let parent = world.spawn(InheritedVisibility::HIDDEN).id();
+        let child = world.spawn((Visibility::Hidden, ChildOf { parent })).id();
+
+        // The parent is hidden, so inheriting its visibility wouldn't show the child.
+        world.entity_mut(child).set_visible(true);
+        assert_eq!(world.get::<Visibility>(child), Some(&Visibility::Visible));
+
+        world.entity_mut(child).toggle_visibility();
+        assert_eq!(world.get::<Visibility>(child), Some(&Visibility::Hidden));
+
+        world
+            .entity_mut(parent)
+            .insert(InheritedVisibility::VISIBLE);
+        world.entity_mut(child).toggle_visibility();
+        assert_eq!(world.get::<Visibility>(child), Some(&Visibility::Inherited));
+    }
+
+    #[test]
+    fn set_visible_only_changes_when_needed() {
+        let mut world = World::new();
+        let entity = world.spawn(Visibility::Visible).id();
+        world.clear_trackers();
+        let is_changed = |world: &World| {
+            world
+                .entity(entity)
+                .get_ref::<Visibility>()
+                .unwrap()
+                .is_changed()
+        };
+
+        world.entity_mut(entity).set_visible(true);
+        assert!(!is_changed(&world));
+
+        world.entity_mut(entity).set_visible(false);
+        assert!(is_changed(&world));
+    }
+}
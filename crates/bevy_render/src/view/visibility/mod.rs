@@ -1,3 +1,4 @@
+mod commands;
 mod range;
 mod render_layers;
 
@@ -6,6 +7,7 @@ use core::any::TypeId;
 use bevy_ecs::component::HookContext;
 use bevy_ecs::entity::hash_set::EntityHashSet;
 use bevy_ecs::world::DeferredWorld;
+pub use commands::*;
 use derive_more::derive::{Deref, DerefMut};
 pub use range::*;
 pub use render_layers::*;
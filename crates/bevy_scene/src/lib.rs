@@ -16,6 +16,8 @@ mod components;
 mod dynamic_scene;
 mod dynamic_scene_builder;
 mod reflect_utils;
+#[cfg(feature = "serialize")]
+mod save_game;
 mod scene;
 mod scene_filter;
 mod scene_loader;
@@ -30,6 +32,8 @@ pub use bevy_asset::ron;
 pub use components::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
+#[cfg(feature = "serialize")]
+pub use save_game::*;
 pub use scene::*;
 pub use scene_filter::*;
 pub use scene_loader::*;
@@ -44,6 +48,10 @@ pub mod prelude {
         DynamicScene, DynamicSceneBuilder, DynamicSceneRoot, Scene, SceneFilter, SceneRoot,
         SceneSpawner,
     };
+
+    #[cfg(feature = "serialize")]
+    #[doc(hidden)]
+    pub use crate::{ReflectSave, SaveGame, SaveGameCommandsExt, SaveGamePlugin};
 }
 
 use bevy_app::prelude::*;
@@ -0,0 +1,718 @@
+//! Saving and loading game state built on top of [`DynamicScene`].
+//!
+//! See [`SaveGame`] for more details.
+
+use crate::{
+    ron,
+    serde::{SceneDeserializer, SceneSerializer},
+    DynamicScene, DynamicSceneBuilder, SceneFilter, SceneSpawnError,
+};
+use alloc::sync::Arc;
+use bevy_app::{App, Plugin, PreUpdate, StandardPaths};
+use bevy_ecs::{
+    archetype::ArchetypeEntity,
+    component::ComponentId,
+    entity::{hash_map::EntityHashMap, Entity},
+    event::{Event, Events},
+    reflect::AppTypeRegistry,
+    resource::Resource,
+    system::Commands,
+    world::World,
+};
+use bevy_platform_support::collections::HashSet;
+use bevy_reflect::{FromType, TypeRegistry};
+use bevy_tasks::{futures::check_ready, IoTaskPool, Task};
+use core::fmt::Formatter;
+use serde::{
+    de::{DeserializeSeed, Error as _, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{io, path::PathBuf};
+use thiserror::Error;
+
+/// Name of the serialized save game struct type.
+pub const SAVE_GAME_STRUCT: &str = "SaveGame";
+/// Name of the serialized version field in a save game struct.
+pub const SAVE_GAME_VERSION: &str = "version";
+/// Name of the serialized scene field in a save game struct.
+pub const SAVE_GAME_SCENE: &str = "scene";
+
+/// Marks a reflected component or resource as part of the game state captured by a [`SaveGame`].
+///
+/// Add it with `#[reflect(Save)]`, next to `#[reflect(Component)]` or `#[reflect(Resource)]`:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::Reflect;
+/// # use bevy_scene::ReflectSave;
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component, Save)]
+/// struct Health(u32);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ReflectSave;
+
+impl<T> FromType<T> for ReflectSave {
+    fn from_type() -> Self {
+        ReflectSave
+    }
+}
+
+/// A versioned snapshot of the components and resources marked with [`ReflectSave`].
+///
+/// A save game is a [`DynamicScene`] restricted to the saved types, along with the version of
+/// the save format it was written with. When loading a save written by an older version of the game,
+/// the registered [`SaveGameMigration`]s are applied before it is written back to the world.
+///
+/// Most games will use [`SaveGamePlugin`] along with [`SaveGameCommandsExt`] to save and load from
+/// files without blocking the main thread, but snapshots can also be captured and restored directly:
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, entity::hash_map::EntityHashMap};
+/// # use bevy_reflect::Reflect;
+/// # use bevy_scene::{ReflectSave, SaveGame};
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component, Save)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<AppTypeRegistry>();
+/// world.resource::<AppTypeRegistry>().write().register::<Health>();
+/// world.spawn(Health(10));
+///
+/// let save = SaveGame::from_world(&world, 1);
+/// let serialized = save.serialize(&world.resource::<AppTypeRegistry>().read()).unwrap();
+///
+/// let loaded = SaveGame::deserialize(&serialized, &world.resource::<AppTypeRegistry>().read(), 1, &[])
+///     .unwrap();
+/// loaded.write_to_world(&mut world, &mut EntityHashMap::default()).unwrap();
+/// ```
+pub struct SaveGame {
+    /// The version of the save format this snapshot was written with.
+    pub version: u32,
+    /// The saved entities and resources.
+    pub scene: DynamicScene,
+}
+
+impl SaveGame {
+    /// Captures every entity with at least one saved component, along with all saved resources.
+    ///
+    /// Only the components and resources marked with [`ReflectSave`] are extracted.
+    /// Entity references pointing to entities that aren't part of the save will be mapped to
+    /// new, empty entities when the save is loaded.
+    pub fn from_world(world: &World, version: u32) -> Self {
+        let registry = world.resource::<AppTypeRegistry>().read();
+
+        let mut filter = SceneFilter::deny_all();
+        let mut saved_components = HashSet::<ComponentId>::default();
+        for registration in registry.iter_with_data::<ReflectSave>().map(|(r, _)| r) {
+            let type_id = registration.type_id();
+            filter = filter.allow_by_id(type_id);
+            if let Some(id) = world.components().get_id(type_id) {
+                saved_components.insert(id);
+            }
+        }
+        drop(registry);
+
+        let entities = world
+            .archetypes()
+            .iter()
+            .filter(|archetype| {
+                archetype
+                    .components()
+                    .any(|id| saved_components.contains(&id))
+            })
+            .flat_map(|archetype| archetype.entities().iter().map(ArchetypeEntity::id));
+
+        let scene = DynamicSceneBuilder::from_world(world)
+            .with_component_filter(filter.clone())
+            .with_resource_filter(filter)
+            .extract_entities(entities)
+            .extract_resources()
+            .build();
+
+        Self { version, scene }
+    }
+
+    /// Serializes this save game into RON, with the version stored next to the scene.
+    pub fn serialize(&self, registry: &TypeRegistry) -> Result<String, ron::Error> {
+        crate::serialize_ron(SaveGameSerializer {
+            save_game: self,
+            registry,
+        })
+    }
+
+    /// Deserializes a save game written by [`SaveGame::serialize`].
+    ///
+    /// Saves written with a version older than `current_version` are upgraded with the `migrations`
+    /// whose [version](SaveGameMigration::version) is newer than the save, in order of version.
+    /// Saves written with a version newer than `current_version` are rejected.
+    pub fn deserialize(
+        input: &str,
+        registry: &TypeRegistry,
+        current_version: u32,
+        migrations: &[SaveGameMigration],
+    ) -> Result<Self, SaveGameError> {
+        let SaveGameHeader { version } = ron::de::from_str(input)?;
+        if version > current_version {
+            return Err(SaveGameError::UnsupportedVersion {
+                version,
+                current_version,
+            });
+        }
+
+        let mut pending: Vec<_> = migrations
+            .iter()
+            .filter(|migration| migration.version > version && migration.version <= current_version)
+            .collect();
+        pending.sort_by_key(|migration| migration.version);
+
+        // Renames have to be applied to the text, since the old type paths no longer resolve in the registry.
+        let mut input = input.to_string();
+        for migration in &pending {
+            if let MigrationKind::RenameType { from, to } = &migration.kind {
+                input = input.replace(&format!("\"{from}\""), &format!("\"{to}\""));
+            }
+        }
+
+        let mut deserializer = ron::de::Deserializer::from_str(&input)?;
+        let (_, mut scene) = SaveGameDeserializer {
+            type_registry: registry,
+        }
+        .deserialize(&mut deserializer)
+        .map_err(|e| deserializer.span_error(e))?;
+
+        for migration in &pending {
+            if let MigrationKind::Scene(migrate) = &migration.kind {
+                migrate(&mut scene);
+            }
+        }
+
+        Ok(Self {
+            version: current_version,
+            scene,
+        })
+    }
+
+    /// Writes the saved entities and resources to the given world.
+    ///
+    /// Saved entities are spawned as new entities, and `entity_map` is filled with the mapping from
+    /// the saved entities to the spawned ones. Entity references within the saved components are
+    /// remapped accordingly. Saved resources replace the existing ones.
+    pub fn write_to_world(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityHashMap<Entity>,
+    ) -> Result<(), SceneSpawnError> {
+        self.scene.write_to_world(world, entity_map)
+    }
+}
+
+/// A step upgrading a [`SaveGame`] written by an older version of the game.
+///
+/// Each migration is tagged with the version that introduced the change it accounts for,
+/// and only runs for saves written before that version. Type renames run before the save is
+/// deserialized, and scene migrations run afterwards.
+///
+/// Fields added to a saved type since a save was written must be marked `#[reflect(default)]`
+/// for older saves to deserialize; scene migrations can then fill them in.
+pub struct SaveGameMigration {
+    version: u32,
+    kind: MigrationKind,
+}
+
+enum MigrationKind {
+    RenameType { from: String, to: String },
+    Scene(Arc<dyn Fn(&mut DynamicScene) + Send + Sync>),
+}
+
+impl SaveGameMigration {
+    /// Renames the saved type with the type path `from` to `to`, for saves older than `version`.
+    pub fn rename_type(version: u32, from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            version,
+            kind: MigrationKind::RenameType {
+                from: from.into(),
+                to: to.into(),
+            },
+        }
+    }
+
+    /// Runs `migrate` on the deserialized scene of saves older than `version`.
+    pub fn scene(
+        version: u32,
+        migrate: impl Fn(&mut DynamicScene) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            version,
+            kind: MigrationKind::Scene(Arc::new(migrate)),
+        }
+    }
+
+    /// Returns the version that introduced this migration.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+/// Errors that can occur while loading a [`SaveGame`].
+#[derive(Debug, Error)]
+pub enum SaveGameError {
+    /// The save file couldn't be read or written.
+    #[error("error while accessing the save file: {0}")]
+    Io(#[from] io::Error),
+    /// The save file couldn't be serialized.
+    #[error("could not serialize the save game: {0}")]
+    Serialize(#[from] ron::Error),
+    /// The save file couldn't be parsed.
+    #[error("could not parse the save game: {0}")]
+    Deserialize(#[from] ron::error::SpannedError),
+    /// The save was written by a newer version of the game.
+    #[error("save game version {version} is newer than the current version {current_version}")]
+    UnsupportedVersion {
+        /// The version of the save.
+        version: u32,
+        /// The current save version.
+        current_version: u32,
+    },
+    /// The save couldn't be written to the world.
+    #[error(transparent)]
+    Spawn(#[from] SceneSpawnError),
+}
+
+#[derive(Deserialize)]
+struct SaveGameHeader {
+    version: u32,
+}
+
+struct SaveGameSerializer<'a> {
+    save_game: &'a SaveGame,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for SaveGameSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct(SAVE_GAME_STRUCT, 2)?;
+        state.serialize_field(SAVE_GAME_VERSION, &self.save_game.version)?;
+        state.serialize_field(
+            SAVE_GAME_SCENE,
+            &SceneSerializer::new(&self.save_game.scene, self.registry),
+        )?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SaveGameField {
+    Version,
+    Scene,
+}
+
+struct SaveGameDeserializer<'a> {
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SaveGameDeserializer<'a> {
+    type Value = (u32, DynamicScene);
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            SAVE_GAME_STRUCT,
+            &[SAVE_GAME_VERSION, SAVE_GAME_SCENE],
+            SaveGameVisitor {
+                type_registry: self.type_registry,
+            },
+        )
+    }
+}
+
+struct SaveGameVisitor<'a> {
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SaveGameVisitor<'a> {
+    type Value = (u32, DynamicScene);
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("save game struct")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let version = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::missing_field(SAVE_GAME_VERSION))?;
+        let scene = seq
+            .next_element_seed(SceneDeserializer {
+                type_registry: self.type_registry,
+            })?
+            .ok_or_else(|| A::Error::missing_field(SAVE_GAME_SCENE))?;
+        Ok((version, scene))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut version = None;
+        let mut scene = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SaveGameField::Version => {
+                    if version.is_some() {
+                        return Err(A::Error::duplicate_field(SAVE_GAME_VERSION));
+                    }
+                    version = Some(map.next_value()?);
+                }
+                SaveGameField::Scene => {
+                    if scene.is_some() {
+                        return Err(A::Error::duplicate_field(SAVE_GAME_SCENE));
+                    }
+                    scene = Some(map.next_value_seed(SceneDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+            }
+        }
+
+        let version = version.ok_or_else(|| A::Error::missing_field(SAVE_GAME_VERSION))?;
+        let scene = scene.ok_or_else(|| A::Error::missing_field(SAVE_GAME_SCENE))?;
+        Ok((version, scene))
+    }
+}
+
+/// Adds support for saving and loading [`SaveGame`]s through [`SaveGameCommandsExt`].
+///
+/// Save files are written and read on the [`IoTaskPool`], and the outcome of each operation
+/// is reported with a [`SaveGameEvent`].
+pub struct SaveGamePlugin {
+    /// The current version of the save format, see [`SaveGameSettings::version`].
+    pub version: u32,
+}
+
+impl Default for SaveGamePlugin {
+    fn default() -> Self {
+        Self { version: 1 }
+    }
+}
+
+impl Plugin for SaveGamePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SaveGameSettings {
+            version: self.version,
+            migrations: Vec::new(),
+        })
+        .init_resource::<SaveGameTasks>()
+        .add_event::<SaveGameEvent>()
+        .add_systems(PreUpdate, handle_save_game_tasks);
+    }
+}
+
+/// Configuration of the saves handled by [`SaveGamePlugin`].
+#[derive(Resource)]
+pub struct SaveGameSettings {
+    /// The current version of the save format.
+    ///
+    /// Bump it whenever a change to the saved types requires a [`SaveGameMigration`].
+    pub version: u32,
+    /// The migrations applied to saves written with an older version.
+    pub migrations: Vec<SaveGameMigration>,
+}
+
+impl SaveGameSettings {
+    /// Adds a migration for saves written with an older version.
+    pub fn add_migration(&mut self, migration: SaveGameMigration) -> &mut Self {
+        self.migrations.push(migration);
+        self
+    }
+}
+
+/// Reports the outcome of an operation started with [`SaveGameCommandsExt`].
+#[derive(Event, Debug)]
+pub enum SaveGameEvent {
+    /// The game was saved to `path`.
+    Saved {
+        /// The path of the save file.
+        path: PathBuf,
+    },
+    /// The game was loaded from `path`.
+    Loaded {
+        /// The path of the save file.
+        path: PathBuf,
+        /// Maps the entities of the save to the entities spawned in the world.
+        entity_map: EntityHashMap<Entity>,
+    },
+    /// Saving to or loading from `path` failed.
+    Failed {
+        /// The path of the save file.
+        path: PathBuf,
+        /// The reason of the failure.
+        error: SaveGameError,
+    },
+}
+
+#[derive(Resource, Default)]
+struct SaveGameTasks {
+    saves: Vec<(PathBuf, Task<io::Result<()>>)>,
+    loads: Vec<(PathBuf, Task<io::Result<String>>)>,
+}
+
+/// Extension trait for [`Commands`] to save and load [`SaveGame`]s.
+///
+/// Requires the [`SaveGamePlugin`].
+pub trait SaveGameCommandsExt {
+    /// Captures a [`SaveGame`] at the end of the current command flush and writes it to `path`
+    /// in the background.
+    ///
+    /// Relative paths are resolved against the [data directory](StandardPaths::data_dir) of the
+    /// application. A [`SaveGameEvent`] is sent once the file has been written.
+    fn save_game(&mut self, path: impl Into<PathBuf>);
+
+    /// Reads the [`SaveGame`] at `path` in the background, then writes it to the world.
+    ///
+    /// Relative paths are resolved against the [data directory](StandardPaths::data_dir) of the
+    /// application.
+    ///
+    /// Saved entities are spawned in addition to the existing entities, so entities from a previous
+    /// session should be despawned beforehand. A [`SaveGameEvent`] is sent once the save has been loaded.
+    fn load_game(&mut self, path: impl Into<PathBuf>);
+}
+
+impl SaveGameCommandsExt for Commands<'_, '_> {
+    fn save_game(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        self.queue(move |world: &mut World| {
+            let path = resolve_save_path(world, path);
+            let version = world.resource::<SaveGameSettings>().version;
+            let serialized = SaveGame::from_world(world, version)
+                .serialize(&world.resource::<AppTypeRegistry>().read());
+            match serialized {
+                Ok(serialized) => {
+                    let task_path = path.clone();
+                    let task = IoTaskPool::get()
+                        .spawn(async move { StandardPaths::write(&task_path, &serialized) });
+                    world
+                        .resource_mut::<SaveGameTasks>()
+                        .saves
+                        .push((path, task));
+                }
+                Err(error) => {
+                    world.send_event(SaveGameEvent::Failed {
+                        path,
+                        error: error.into(),
+                    });
+                }
+            }
+        });
+    }
+
+    fn load_game(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        self.queue(move |world: &mut World| {
+            let path = resolve_save_path(world, path);
+            let task_path = path.clone();
+            let task =
+                IoTaskPool::get().spawn(async move { StandardPaths::read_to_string(&task_path) });
+            world
+                .resource_mut::<SaveGameTasks>()
+                .loads
+                .push((path, task));
+        });
+    }
+}
+
+fn resolve_save_path(world: &World, path: PathBuf) -> PathBuf {
+    match world
+        .get_resource::<StandardPaths>()
+        .and_then(StandardPaths::data_dir)
+    {
+        Some(data_dir) if path.is_relative() => data_dir.join(path),
+        _ => path,
+    }
+}
+
+/// Polls the pending save and load tasks, writing loaded saves to the world.
+fn handle_save_game_tasks(world: &mut World) {
+    let mut events = Vec::new();
+    let mut loaded = Vec::new();
+    {
+        let mut tasks = world.resource_mut::<SaveGameTasks>();
+        tasks.saves.retain_mut(|(path, task)| {
+            let Some(result) = check_ready(task) else {
+                return true;
+            };
+            events.push(match result {
+                Ok(()) => SaveGameEvent::Saved { path: path.clone() },
+                Err(error) => SaveGameEvent::Failed {
+                    path: path.clone(),
+                    error: error.into(),
+                },
+            });
+            false
+        });
+        tasks.loads.retain_mut(|(path, task)| {
+            let Some(result) = check_ready(task) else {
+                return true;
+            };
+            loaded.push((path.clone(), result));
+            false
+        });
+    }
+
+    for (path, result) in loaded {
+        let result = result.map_err(SaveGameError::from).and_then(|input| {
+            let settings = world.resource::<SaveGameSettings>();
+            let save = SaveGame::deserialize(
+                &input,
+                &world.resource::<AppTypeRegistry>().read(),
+                settings.version,
+                &settings.migrations,
+            )?;
+            let mut entity_map = EntityHashMap::default();
+            save.write_to_world(world, &mut entity_map)?;
+            Ok(entity_map)
+        });
+        events.push(match result {
+            Ok(entity_map) => SaveGameEvent::Loaded { path, entity_map },
+            Err(error) => SaveGameEvent::Failed { path, error },
+        });
+    }
+
+    if !events.is_empty() {
+        world
+            .resource_mut::<Events<SaveGameEvent>>()
+            .send_batch(events);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        component::Component,
+        entity::{EntityMapper, MapEntities},
+        reflect::{ReflectComponent, ReflectMapEntities, ReflectResource},
+    };
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, PartialEq, Debug)]
+    #[reflect(Component, Save)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Transient;
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component, MapEntities, Save)]
+    struct Target(Entity);
+
+    impl MapEntities for Target {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            self.0 = entity_mapper.get_mapped(self.0);
+        }
+    }
+
+    #[derive(Resource, Reflect, PartialEq, Debug)]
+    #[reflect(Resource, Save)]
+    struct Score(u32);
+
+    fn registered_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Health>();
+            registry.register::<Transient>();
+            registry.register::<Target>();
+            registry.register::<Score>();
+        }
+        world
+    }
+
+    #[test]
+    fn saves_only_marked_types() {
+        let mut world = registered_world();
+        let a = world.spawn((Health(3), Transient)).id();
+        world.spawn(Target(a));
+        world.spawn(Transient);
+        world.insert_resource(Score(7));
+
+        let save = SaveGame::from_world(&world, 1);
+        assert_eq!(save.scene.entities.len(), 2);
+        assert_eq!(save.scene.resources.len(), 1);
+        assert!(save
+            .scene
+            .entities
+            .iter()
+            .all(|entity| entity.components.len() == 1));
+
+        let serialized = save
+            .serialize(&world.resource::<AppTypeRegistry>().read())
+            .unwrap();
+
+        let mut loaded_world = registered_world();
+        let registry = loaded_world.resource::<AppTypeRegistry>().clone();
+        let loaded = SaveGame::deserialize(&serialized, &registry.read(), 1, &[]).unwrap();
+        let mut entity_map = EntityHashMap::default();
+        loaded
+            .write_to_world(&mut loaded_world, &mut entity_map)
+            .unwrap();
+
+        assert_eq!(loaded_world.resource::<Score>(), &Score(7));
+        let new_a = entity_map[&a];
+        assert_eq!(loaded_world.get::<Health>(new_a), Some(&Health(3)));
+        assert!(loaded_world.get::<Transient>(new_a).is_none());
+        let mut targets = loaded_world.query::<&Target>();
+        assert_eq!(targets.single(&loaded_world).unwrap().0, new_a);
+    }
+
+    #[test]
+    fn migrates_older_saves() {
+        let mut world = registered_world();
+        world.insert_resource(Score(7));
+        let registry = world.resource::<AppTypeRegistry>().clone();
+
+        let serialized = SaveGame::from_world(&world, 1)
+            .serialize(&registry.read())
+            .unwrap()
+            .replace("save_game::tests::Score", "save_game::tests::Points");
+
+        let migrations = [
+            SaveGameMigration::rename_type(
+                2,
+                "bevy_scene::save_game::tests::Points",
+                "bevy_scene::save_game::tests::Score",
+            ),
+            SaveGameMigration::scene(3, |scene| {
+                for resource in &mut scene.resources {
+                    if let Some(score) = resource.try_downcast_mut::<Score>() {
+                        score.0 *= 10;
+                    }
+                }
+            }),
+        ];
+
+        let loaded = SaveGame::deserialize(&serialized, &registry.read(), 3, &migrations).unwrap();
+        assert_eq!(loaded.version, 3);
+        loaded
+            .write_to_world(&mut world, &mut EntityHashMap::default())
+            .unwrap();
+        assert_eq!(world.resource::<Score>(), &Score(70));
+
+        assert!(matches!(
+            SaveGame::deserialize(&serialized, &registry.read(), 0, &[]),
+            Err(SaveGameError::UnsupportedVersion { version: 1, .. })
+        ));
+    }
+}
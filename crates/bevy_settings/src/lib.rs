@@ -0,0 +1,99 @@
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![doc(
+    html_logo_url = "https://bevyengine.org/assets/icon.png",
+    html_favicon_url = "https://bevyengine.org/assets/icon.png"
+)]
+
+//! Provides persistent user settings, such as graphics, audio or input binding preferences.
+//!
+//! Settings are reflected [`Resource`](bevy_ecs::resource::Resource)s registered with
+//! [`AppSettingsExt::register_settings`]. All registered settings are merged into a single RON file
+//! stored in the [configuration directory](StandardPaths::config_dir) of the application,
+//! with one section per settings type.
+//!
+//! Settings are loaded from the file as soon as they are registered, so a plugin registering its
+//! settings at the start of [`Plugin::build`](bevy_app::Plugin::build) can read the stored values
+//! while building. Whenever a settings resource changes, the file is saved again once the settings
+//! have stopped changing for [`SettingsPlugin::debounce`].
+//!
+//! ```no_run
+//! # use bevy_app::{prelude::*, StandardPathsPlugin};
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_reflect::prelude::*;
+//! use bevy_settings::prelude::*;
+//!
+//! #[derive(Resource, Reflect, Default)]
+//! #[reflect(Resource, Default)]
+//! struct AudioSettings {
+//!     master_volume: f32,
+//!     muted: bool,
+//! }
+//!
+//! App::new()
+//!     .add_plugins((StandardPathsPlugin::new("my_game"), SettingsPlugin::default()))
+//!     .register_settings::<AudioSettings>()
+//!     .run();
+//! ```
+
+extern crate alloc;
+
+mod serde;
+mod settings;
+
+pub use settings::*;
+
+/// The settings prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{AppSettingsExt, SettingsPlugin};
+}
+
+use bevy_app::{prelude::*, StandardPaths};
+use core::time::Duration;
+use std::path::PathBuf;
+
+/// Adds support for persistent settings registered with [`AppSettingsExt::register_settings`].
+///
+/// This plugin must be added before any plugin registering settings, and after the
+/// [`StandardPathsPlugin`] if the application isn't using the default paths.
+pub struct SettingsPlugin {
+    /// Overrides the path of the settings file.
+    ///
+    /// If `None`, settings are stored in `settings.ron` in the [configuration directory](StandardPaths::config_dir).
+    pub path: Option<PathBuf>,
+    /// How long settings must remain unchanged before they are saved.
+    ///
+    /// This avoids writing the file on every frame while a setting is being adjusted, for example
+    /// with a slider.
+    pub debounce: Duration,
+}
+
+impl Default for SettingsPlugin {
+    fn default() -> Self {
+        Self {
+            path: None,
+            debounce: Duration::from_secs(1),
+        }
+    }
+}
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        let path = self.path.clone().or_else(|| {
+            let paths = app
+                .world()
+                .get_resource::<StandardPaths>()
+                .cloned()
+                .unwrap_or_default();
+            paths.config_dir().map(|dir| dir.join("settings.ron"))
+        });
+        if path.is_none() {
+            log::warn!("No configuration directory available, settings will not be persisted");
+        }
+
+        let store = SettingsStore::load(path, self.debounce);
+        app.insert_resource(store).add_systems(Last, save_settings);
+    }
+}
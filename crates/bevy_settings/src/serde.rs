@@ -0,0 +1,286 @@
+//! `serde` implementation of the settings file format.
+//!
+//! A settings file is a map from the type path of each settings type to its serialized value.
+//! Sections that no registered type reads are kept as source text by [`raw_sections`], and
+//! written back unchanged.
+
+use alloc::vec::Vec;
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    PartialReflect, Reflect, TypeRegistration, TypeRegistry,
+};
+use core::fmt::Formatter;
+use serde::{
+    de::{DeserializeSeed, IgnoredAny, MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserializer, Serialize, Serializer,
+};
+
+/// Serializes the given settings sections into a single map.
+pub(crate) struct SettingsSerializer<'a> {
+    pub sections: Vec<(&'static str, &'a dyn Reflect)>,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for SettingsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.sections.len()))?;
+        for (key, value) in &self.sections {
+            map.serialize_entry(
+                key,
+                &TypedReflectSerializer::new(value.as_partial_reflect(), self.registry),
+            )?;
+        }
+        map.end()
+    }
+}
+
+/// Deserializes the section named `key` from a settings map, ignoring all other sections.
+///
+/// Returns `None` if the section is missing.
+pub(crate) struct SettingsSectionDeserializer<'a> {
+    pub key: &'a str,
+    pub registration: &'a TypeRegistration,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SettingsSectionDeserializer<'a> {
+    type Value = Option<Box<dyn PartialReflect>>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for SettingsSectionDeserializer<'a> {
+    type Value = Option<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("map of settings")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut section = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if section.is_none() && key == self.key {
+                section = Some(map.next_value_seed(TypedReflectDeserializer::new(
+                    self.registration,
+                    self.registry,
+                ))?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(section)
+    }
+}
+
+/// Splits the top-level map of a settings file into the source text of each section, as
+/// `(key, value)` pairs.
+///
+/// `ron` can't keep a value it doesn't know the type of without losing information, such as the
+/// names of enum variants, so the sections that aren't registered are copied as text instead.
+///
+/// Returns `None` if `contents` isn't a map.
+pub(crate) fn raw_sections(contents: &str) -> Option<Vec<(&str, &str)>> {
+    let mut scanner = Scanner {
+        source: contents.as_bytes(),
+        position: 0,
+    };
+    let mut sections = Vec::new();
+    scanner.skip_whitespace()?;
+    scanner.expect(b'{')?;
+    loop {
+        scanner.skip_whitespace()?;
+        if scanner.eat(b'}') {
+            return Some(sections);
+        }
+        let key_start = scanner.position;
+        let key_end = scanner.skip_value()?;
+        scanner.skip_whitespace()?;
+        scanner.expect(b':')?;
+        scanner.skip_whitespace()?;
+        let value_start = scanner.position;
+        let value_end = scanner.skip_value()?;
+        sections.push((
+            &contents[key_start..key_end],
+            &contents[value_start..value_end],
+        ));
+        scanner.skip_whitespace()?;
+        if !scanner.eat(b',') {
+            scanner.expect(b'}')?;
+            return Some(sections);
+        }
+    }
+}
+
+/// Finds the boundaries of RON values, without parsing them.
+struct Scanner<'a> {
+    source: &'a [u8],
+    position: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.source.get(self.position).copied()
+    }
+
+    fn peek_next(&self) -> Option<u8> {
+        self.source.get(self.position + 1).copied()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        let eaten = self.peek() == Some(byte);
+        if eaten {
+            self.position += 1;
+        }
+        eaten
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.eat(byte).then_some(())
+    }
+
+    /// Skips whitespace and comments.
+    fn skip_whitespace(&mut self) -> Option<()> {
+        loop {
+            match (self.peek(), self.peek_next()) {
+                (Some(byte), _) if byte.is_ascii_whitespace() => self.position += 1,
+                (Some(b'/'), Some(b'/')) => {
+                    while !matches!(self.peek(), None | Some(b'\n')) {
+                        self.position += 1;
+                    }
+                }
+                (Some(b'/'), Some(b'*')) => {
+                    // Block comments can be nested.
+                    self.position += 2;
+                    let mut depth = 1;
+                    while depth > 0 {
+                        match (self.peek()?, self.peek_next()) {
+                            (b'/', Some(b'*')) => {
+                                depth += 1;
+                                self.position += 2;
+                            }
+                            (b'*', Some(b'/')) => {
+                                depth -= 1;
+                                self.position += 2;
+                            }
+                            _ => self.position += 1,
+                        }
+                    }
+                }
+                _ => return Some(()),
+            }
+        }
+    }
+
+    /// Skips a single value, stopping before the `,`, `:` or closing bracket that follows it.
+    ///
+    /// Returns the position right after the last token of the value.
+    fn skip_value(&mut self) -> Option<usize> {
+        let start = self.position;
+        let mut end = start;
+        let mut depth = 0_usize;
+        loop {
+            self.skip_whitespace()?;
+            match self.peek()? {
+                b'(' | b'[' | b'{' => {
+                    depth += 1;
+                    self.position += 1;
+                }
+                b')' | b']' | b'}' | b',' | b':' if depth == 0 => break,
+                b')' | b']' | b'}' => {
+                    depth -= 1;
+                    self.position += 1;
+                }
+                quote @ (b'"' | b'\'') => self.skip_quoted(quote)?,
+                b'r' if matches!(self.peek_next(), Some(b'"' | b'#')) => self.skip_raw_string()?,
+                _ => self.position += 1,
+            }
+            end = self.position;
+        }
+        (end > start).then_some(end)
+    }
+
+    /// Skips a string or a character literal.
+    fn skip_quoted(&mut self, quote: u8) -> Option<()> {
+        self.position += 1;
+        loop {
+            match self.peek()? {
+                b'\\' => self.position += 2,
+                byte if byte == quote => {
+                    self.position += 1;
+                    return Some(());
+                }
+                _ => self.position += 1,
+            }
+        }
+    }
+
+    /// Skips a raw string such as `r#"text"#`, or only the `r` of a raw identifier such as `r#type`.
+    fn skip_raw_string(&mut self) -> Option<()> {
+        self.position += 1;
+        let hashes_start = self.position;
+        while self.eat(b'#') {}
+        let hashes = self.position - hashes_start;
+        if !self.eat(b'"') {
+            self.position = hashes_start;
+            return Some(());
+        }
+        loop {
+            if self.eat(b'"') {
+                let closing = self.source[self.position..]
+                    .iter()
+                    .take(hashes)
+                    .take_while(|&&byte| byte == b'#')
+                    .count();
+                if closing == hashes {
+                    self.position += hashes;
+                    return Some(());
+                }
+            } else {
+                self.peek()?;
+                self.position += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::raw_sections;
+    use alloc::vec;
+
+    #[test]
+    fn splits_raw_sections() {
+        let contents = r##"
+            // Settings
+            {
+                "a::Settings": (name: "a, }", quote: '\'', mode: Fullscreen), /* done */
+                "b::Settings": [r#"raw "}" string"#, Some((1, 2))]
+            }
+        "##;
+        assert_eq!(
+            raw_sections(contents),
+            Some(vec![
+                (
+                    "\"a::Settings\"",
+                    "(name: \"a, }\", quote: '\\'', mode: Fullscreen)"
+                ),
+                ("\"b::Settings\"", "[r#\"raw \"}\" string\"#, Some((1, 2))]"),
+            ])
+        );
+        assert_eq!(raw_sections("{}"), Some(vec![]));
+        assert_eq!(raw_sections("(a: 1)"), None);
+        assert_eq!(raw_sections("{\"a\": (b: 1"), None);
+    }
+}
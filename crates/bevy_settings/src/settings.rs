@@ -0,0 +1,376 @@
+use crate::serde::{raw_sections, SettingsSectionDeserializer, SettingsSerializer};
+use alloc::vec::Vec;
+use bevy_app::{App, AppExit, StandardPaths};
+use bevy_ecs::{
+    component::{ComponentId, Tick},
+    event::Events,
+    reflect::AppTypeRegistry,
+    resource::Resource,
+    world::{Mut, World},
+};
+use bevy_platform_support::time::Instant;
+use bevy_reflect::{FromReflect, GetTypeRegistration, Reflect, TypePath, TypeRegistry};
+use bevy_tasks::IoTaskPool;
+use core::time::Duration;
+use serde::de::DeserializeSeed;
+use std::path::{Path, PathBuf};
+
+/// Stores the settings registered with [`AppSettingsExt::register_settings`] and tracks when they
+/// need to be saved.
+///
+/// Inserted by the [`SettingsPlugin`](crate::SettingsPlugin).
+#[derive(Resource)]
+pub struct SettingsStore {
+    path: Option<PathBuf>,
+    contents: Option<String>,
+    entries: Vec<SettingsEntry>,
+    debounce: Duration,
+    last_check: Tick,
+    dirty_since: Option<Instant>,
+}
+
+struct SettingsEntry {
+    key: &'static str,
+    component_id: ComponentId,
+    reflect: fn(&World) -> Option<&dyn Reflect>,
+}
+
+impl SettingsStore {
+    pub(crate) fn load(path: Option<PathBuf>, debounce: Duration) -> Self {
+        let contents = path.as_deref().and_then(|path| {
+            StandardPaths::read_to_string(path)
+                .inspect_err(|error| {
+                    if error.kind() != std::io::ErrorKind::NotFound {
+                        log::warn!("Failed to read settings from {}: {error}", path.display());
+                    }
+                })
+                .ok()
+        });
+        Self {
+            path,
+            contents,
+            entries: Vec::new(),
+            debounce,
+            last_check: Tick::new(0),
+            dirty_since: None,
+        }
+    }
+
+    /// Returns the path of the settings file, if settings are persisted on this platform.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Returns `true` if a change to the settings hasn't been saved yet.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty_since.is_some()
+    }
+
+    /// Serializes all registered settings into the contents of a settings file.
+    ///
+    /// The other sections of the loaded settings file, such as the settings of a plugin that isn't
+    /// added to the app, are kept unchanged.
+    pub fn serialize(&self, world: &World, registry: &TypeRegistry) -> Result<String, ron::Error> {
+        let sections: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|entry| Some((entry.key, (entry.reflect)(world)?)))
+            .collect();
+        let kept: Vec<_> = self
+            .contents
+            .as_deref()
+            .and_then(raw_sections)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(key, _)| {
+                ron::from_str::<String>(key)
+                    .is_ok_and(|key| sections.iter().all(|(section, _)| *section != key))
+            })
+            .collect();
+
+        let pretty_config = ron::ser::PrettyConfig::default()
+            .indentor("  ".to_string())
+            .new_line("\n".to_string());
+        let mut contents =
+            ron::ser::to_string_pretty(&SettingsSerializer { sections, registry }, pretty_config)?;
+        if !kept.is_empty() {
+            // Insert the kept sections at the end of the map.
+            contents.truncate(contents.trim_end().len() - "}".len());
+            contents.truncate(contents.trim_end().len());
+            if !contents.ends_with(['{', ',']) {
+                contents.push(',');
+            }
+            contents.push('\n');
+            for (key, value) in kept {
+                contents.push_str(&format!("  {key}: {value},\n"));
+            }
+            contents.push('}');
+        }
+        Ok(contents)
+    }
+
+    /// Reads the settings of type `T` from the loaded settings file.
+    fn read<T: Reflect + FromReflect + TypePath>(&self, registry: &TypeRegistry) -> Option<T> {
+        let contents = self.contents.as_deref()?;
+        let registration = registry.get(core::any::TypeId::of::<T>())?;
+        let key = T::type_path();
+
+        let result = ron::de::Deserializer::from_str(contents).and_then(|mut deserializer| {
+            SettingsSectionDeserializer {
+                key,
+                registration,
+                registry,
+            }
+            .deserialize(&mut deserializer)
+            .map_err(|error| deserializer.span_error(error))
+        });
+        match result {
+            Ok(value) => {
+                let value = value?;
+                let settings = T::from_reflect(value.as_partial_reflect());
+                if settings.is_none() {
+                    log::warn!(
+                        "Stored settings `{key}` are incomplete, using the defaults instead"
+                    );
+                }
+                settings
+            }
+            Err(error) => {
+                log::warn!("Failed to parse stored settings `{key}`: {error}");
+                None
+            }
+        }
+    }
+}
+
+/// Adds [settings](crate) registration methods to [`App`].
+pub trait AppSettingsExt {
+    /// Registers `T` as a persistent settings resource.
+    ///
+    /// The resource is immediately inserted with the stored value, so it can be read while building
+    /// the plugin that registers it. If the settings file doesn't contain valid settings for `T`,
+    /// the resource is kept if it was already inserted, for example with the defaults configured on
+    /// a plugin, and inserted with its [`Default`] otherwise. Changes to the resource are saved to
+    /// the settings file.
+    ///
+    /// Fields added to `T` since the settings were saved should be marked with `#[reflect(default)]`,
+    /// so that the existing settings keep loading.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`SettingsPlugin`](crate::SettingsPlugin) hasn't been added.
+    fn register_settings<T>(&mut self) -> &mut Self
+    where
+        T: Resource + Reflect + FromReflect + TypePath + GetTypeRegistration + Default;
+}
+
+impl AppSettingsExt for App {
+    fn register_settings<T>(&mut self) -> &mut Self
+    where
+        T: Resource + Reflect + FromReflect + TypePath + GetTypeRegistration + Default,
+    {
+        self.register_type::<T>();
+
+        let world = self.world_mut();
+        let settings = {
+            let store = world
+                .get_resource::<SettingsStore>()
+                .expect("the `SettingsPlugin` must be added before registering settings");
+            store.read::<T>(&world.resource::<AppTypeRegistry>().read())
+        };
+        if let Some(settings) = settings {
+            world.insert_resource(settings);
+        } else {
+            world.init_resource::<T>();
+        }
+
+        let component_id = world.resource_id::<T>().unwrap();
+        let last_check = world.change_tick();
+        let mut store = world.resource_mut::<SettingsStore>();
+        if store
+            .entries
+            .iter()
+            .all(|entry| entry.component_id != component_id)
+        {
+            store.entries.push(SettingsEntry {
+                key: T::type_path(),
+                component_id,
+                reflect: reflect_settings::<T>,
+            });
+        }
+        store.last_check = last_check;
+        self
+    }
+}
+
+fn reflect_settings<T: Resource + Reflect>(world: &World) -> Option<&dyn Reflect> {
+    world
+        .get_resource::<T>()
+        .map(|settings| settings as &dyn Reflect)
+}
+
+/// Saves the settings once they have stopped changing for the debounce duration, or when the app exits.
+pub(crate) fn save_settings(world: &mut World) {
+    let this_run = world.change_tick();
+    world.resource_scope(|world, mut store: Mut<SettingsStore>| {
+        let last_check = store.last_check;
+        let changed = store.entries.iter().any(|entry| {
+            world
+                .get_resource_change_ticks_by_id(entry.component_id)
+                .is_some_and(|ticks| ticks.is_changed(last_check, this_run))
+        });
+        store.last_check = this_run;
+        if changed {
+            store.dirty_since = Some(Instant::now());
+        }
+
+        let Some(dirty_since) = store.dirty_since else {
+            return;
+        };
+        let exiting = world
+            .get_resource::<Events<AppExit>>()
+            .is_some_and(|events| !events.is_empty());
+        if dirty_since.elapsed() < store.debounce && !exiting {
+            return;
+        }
+        store.dirty_since = None;
+
+        let Some(path) = store.path.clone() else {
+            return;
+        };
+        let contents = match store.serialize(world, &world.resource::<AppTypeRegistry>().read()) {
+            Ok(contents) => contents,
+            Err(error) => {
+                log::error!("Failed to serialize settings: {error}");
+                return;
+            }
+        };
+        store.contents = Some(contents.clone());
+
+        // The app won't wait for background tasks once it exits.
+        if exiting {
+            write_settings(&path, &contents);
+        } else {
+            IoTaskPool::get()
+                .spawn(async move { write_settings(&path, &contents) })
+                .detach();
+        }
+    });
+}
+
+fn write_settings(path: &Path, contents: &str) {
+    if let Err(error) = StandardPaths::write(path, contents) {
+        log::error!("Failed to save settings to {}: {error}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AppSettingsExt, SettingsPlugin, SettingsStore};
+    use bevy_app::App;
+    use bevy_ecs::{
+        reflect::{AppTypeRegistry, ReflectResource},
+        resource::Resource,
+    };
+    use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+    #[derive(Resource, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Resource, Default)]
+    struct GraphicsSettings {
+        vsync: bool,
+        #[reflect(default)]
+        msaa: u32,
+    }
+
+    #[derive(Resource, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Resource, Default)]
+    struct AudioSettings {
+        volume: f32,
+    }
+
+    #[test]
+    fn loads_and_merges_sections() {
+        let path = std::env::temp_dir().join("bevy_settings_loads_and_merges_sections.ron");
+        std::fs::write(
+            &path,
+            r#"{
+                "bevy_settings::settings::tests::GraphicsSettings": (vsync: true),
+                "some_other::Settings": (value: 5, mode: Fullscreen),
+            }"#,
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        app.add_plugins(SettingsPlugin {
+            path: Some(path.clone()),
+            ..Default::default()
+        })
+        .register_settings::<GraphicsSettings>()
+        .register_settings::<AudioSettings>();
+
+        let world = app.world_mut();
+        assert_eq!(
+            world.resource::<GraphicsSettings>(),
+            &GraphicsSettings {
+                vsync: true,
+                msaa: 0
+            }
+        );
+        assert_eq!(world.resource::<AudioSettings>(), &AudioSettings::default());
+
+        world.resource_mut::<AudioSettings>().volume = 0.5;
+        let contents = world
+            .resource::<SettingsStore>()
+            .serialize(world, &world.resource::<AppTypeRegistry>().read())
+            .unwrap();
+        assert!(contents.contains("\"bevy_settings::settings::tests::GraphicsSettings\""));
+        assert!(contents.contains("volume: 0.5"));
+        assert!(contents.contains("\"some_other::Settings\": (value: 5, mode: Fullscreen)"));
+
+        // Sections of settings that aren't registered survive saving.
+        std::fs::write(&path, &contents).unwrap();
+        let mut app = App::new();
+        app.add_plugins(SettingsPlugin {
+            path: Some(path.clone()),
+            ..Default::default()
+        })
+        .register_settings::<GraphicsSettings>();
+        let world = app.world();
+        let contents = world
+            .resource::<SettingsStore>()
+            .serialize(world, &world.resource::<AppTypeRegistry>().read())
+            .unwrap();
+        std::fs::write(&path, &contents).unwrap();
+        assert!(contents.contains("\"some_other::Settings\": (value: 5, mode: Fullscreen)"));
+
+        let mut app = App::new();
+        app.add_plugins(SettingsPlugin {
+            path: Some(path.clone()),
+            ..Default::default()
+        })
+        .register_settings::<GraphicsSettings>()
+        .register_settings::<AudioSettings>();
+        std::fs::remove_file(&path).unwrap();
+        let world = app.world();
+        assert_eq!(
+            world.resource::<AudioSettings>(),
+            &AudioSettings { volume: 0.5 }
+        );
+        assert!(world.resource::<GraphicsSettings>().vsync);
+    }
+
+    #[test]
+    fn keeps_inserted_settings_without_stored_ones() {
+        let mut app = App::new();
+        app.add_plugins(SettingsPlugin {
+            path: None,
+            ..Default::default()
+        })
+        .insert_resource(AudioSettings { volume: 0.5 })
+        .register_settings::<AudioSettings>();
+        assert_eq!(
+            app.world().resource::<AudioSettings>(),
+            &AudioSettings { volume: 0.5 }
+        );
+    }
+}
@@ -13,9 +13,11 @@ extern crate alloc;
 mod mesh2d;
 #[cfg(feature = "bevy_sprite_picking_backend")]
 mod picking_backend;
+mod pixel_perfect;
 mod render;
 mod sprite;
 mod texture_slice;
+mod tilemap;
 
 /// The sprite prelude.
 ///
@@ -30,16 +32,20 @@ pub mod prelude {
     pub use crate::{
         sprite::{Sprite, SpriteImageMode},
         texture_slice::{BorderRect, SliceScaleMode, TextureSlice, TextureSlicer},
-        ColorMaterial, MeshMaterial2d, ScalingMode,
+        tilemap::{Tile, TileAnimation, TileStorage, Tilemap},
+        AmbientLight2d, ColorMaterial, LightOccluder2d, LitMaterial2d, MeshMaterial2d,
+        PixelArtCamera, PixelPerfectCamera, PointLight2d, ScalingMode, SpotLight2d,
     };
 }
 
 pub use mesh2d::*;
 #[cfg(feature = "bevy_sprite_picking_backend")]
 pub use picking_backend::*;
+pub use pixel_perfect::*;
 pub use render::*;
 pub use sprite::*;
 pub use texture_slice::*;
+pub use tilemap::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, weak_handle, AssetEvents, Assets, Handle};
@@ -96,7 +102,12 @@ impl Plugin for SpritePlugin {
             .register_type::<TextureSlicer>()
             .register_type::<Anchor>()
             .register_type::<Mesh2d>()
-            .add_plugins((Mesh2dRenderPlugin, ColorMaterialPlugin))
+            .add_plugins((
+                Mesh2dRenderPlugin,
+                ColorMaterialPlugin,
+                TilemapPlugin,
+                PixelPerfectPlugin,
+            ))
             .add_systems(
                 PostUpdate,
                 (
@@ -0,0 +1,455 @@
+use crate::{AlphaMode2d, Material2d, Material2dPlugin};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, weak_handle, Asset, AssetApp, AssetId, Assets, Handle};
+use bevy_color::{Color, ColorToComponents, LinearRgba};
+use bevy_ecs::prelude::*;
+use bevy_image::Image;
+use bevy_math::{ops, Vec2, Vec4};
+use bevy_reflect::prelude::*;
+use bevy_render::{
+    render_asset::RenderAssets,
+    render_resource::*,
+    texture::GpuImage,
+    view::{InheritedVisibility, Visibility},
+};
+use bevy_transform::{components::GlobalTransform, prelude::Transform, TransformSystem};
+
+pub const LIT_MATERIAL_2D_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("3f1a5a8e-5b1e-4c55-9d3b-2f7f1a0c4e61");
+
+/// The maximum number of [`PointLight2d`]s and [`SpotLight2d`]s lighting [`LitMaterial2d`]s.
+///
+/// Further lights are ignored.
+pub const MAX_LIGHTS_2D: usize = 16;
+
+/// The maximum number of [`LightOccluder2d`]s casting shadows on [`LitMaterial2d`]s.
+///
+/// Further occluders are ignored.
+pub const MAX_OCCLUDERS_2D: usize = 32;
+
+/// Adds 2D lighting: [`PointLight2d`]s and [`SpotLight2d`]s light the 2D meshes using a
+/// [`LitMaterial2d`], and [`LightOccluder2d`]s cast shadows on them.
+///
+/// This plugin isn't included in the default plugins.
+#[derive(Default)]
+pub struct Lighting2dPlugin;
+
+impl Plugin for Lighting2dPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            LIT_MATERIAL_2D_SHADER_HANDLE,
+            "lit_material2d.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.add_plugins(Material2dPlugin::<LitMaterial2d>::default())
+            .register_asset_reflect::<LitMaterial2d>()
+            .register_type::<PointLight2d>()
+            .register_type::<SpotLight2d>()
+            .register_type::<LightOccluder2d>()
+            .register_type::<AmbientLight2d>()
+            .init_resource::<AmbientLight2d>()
+            .add_systems(
+                PostUpdate,
+                update_lit_materials_2d.after(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// A 2D light emitting in all directions from its position, lighting [`LitMaterial2d`]s.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+#[require(Transform, Visibility)]
+pub struct PointLight2d {
+    /// The color of the light.
+    pub color: Color,
+    /// The brightness of the light at its position.
+    pub intensity: f32,
+    /// The distance at which the light fades out completely.
+    pub range: f32,
+    /// The height of the light above the lit plane, in world units.
+    ///
+    /// Lower lights graze the surfaces, emphasizing the details of normal maps.
+    pub height: f32,
+    /// The radius of the light source. Larger lights cast softer shadows.
+    pub radius: f32,
+    /// Whether [`LightOccluder2d`]s cast shadows from this light.
+    pub shadows_enabled: bool,
+}
+
+impl Default for PointLight2d {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            intensity: 1.0,
+            range: 200.0,
+            height: 20.0,
+            radius: 0.0,
+            shadows_enabled: true,
+        }
+    }
+}
+
+/// A 2D light emitting in a cone, along the local X axis of its [`Transform`], lighting
+/// [`LitMaterial2d`]s.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+#[require(Transform, Visibility)]
+pub struct SpotLight2d {
+    /// The color of the light.
+    pub color: Color,
+    /// The brightness of the light at its position.
+    pub intensity: f32,
+    /// The distance at which the light fades out completely.
+    pub range: f32,
+    /// The height of the light above the lit plane, in world units.
+    pub height: f32,
+    /// The radius of the light source. Larger lights cast softer shadows.
+    pub radius: f32,
+    /// The angle from the direction of the light, in radians, within which the light is at full
+    /// brightness.
+    pub inner_angle: f32,
+    /// The angle from the direction of the light, in radians, beyond which there is no light.
+    pub outer_angle: f32,
+    /// Whether [`LightOccluder2d`]s cast shadows from this light.
+    pub shadows_enabled: bool,
+}
+
+impl Default for SpotLight2d {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            intensity: 1.0,
+            range: 200.0,
+            height: 20.0,
+            radius: 0.0,
+            inner_angle: 0.0,
+            outer_angle: core::f32::consts::FRAC_PI_4,
+            shadows_enabled: true,
+        }
+    }
+}
+
+/// A rectangle blocking the light of [`PointLight2d`]s and [`SpotLight2d`]s, casting shadows on
+/// [`LitMaterial2d`]s.
+///
+/// The rectangle is centered on the entity, and follows its [`GlobalTransform`]. The inside of the
+/// rectangle itself isn't shadowed, so that the occluder can be lit.
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+#[require(Transform, Visibility)]
+pub struct LightOccluder2d {
+    /// Half the size of the rectangle, before scaling.
+    pub half_size: Vec2,
+}
+
+/// The light lighting all [`LitMaterial2d`]s uniformly, in addition to 2D lights.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource, Default, Debug, Clone)]
+pub struct AmbientLight2d {
+    /// The color of the light.
+    pub color: Color,
+    /// The brightness of the light.
+    pub brightness: f32,
+}
+
+impl Default for AmbientLight2d {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            brightness: 0.1,
+        }
+    }
+}
+
+/// A [2d material](Material2d) lit by [`PointLight2d`]s, [`SpotLight2d`]s and the
+/// [`AmbientLight2d`], with shadows cast by [`LightOccluder2d`]s.
+///
+/// To light a sprite, render it as a [`Mesh2d`](bevy_render::mesh::Mesh2d) rectangle with this
+/// material.
+///
+/// Requires the [`Lighting2dPlugin`].
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[reflect(Default, Debug, Clone)]
+#[uniform(0, LitMaterial2dUniform)]
+pub struct LitMaterial2d {
+    /// The color of the surface, multiplied with the texture.
+    pub color: Color,
+    pub alpha_mode: AlphaMode2d,
+    #[texture(1)]
+    #[sampler(2)]
+    pub texture: Option<Handle<Image>>,
+    /// A normal map, with the X axis of the normals pointing right and the Y axis pointing up in
+    /// world space, shading the surface according to the direction of the lights.
+    ///
+    /// Normal maps must be loaded with `is_srgb: false`.
+    #[texture(3)]
+    #[sampler(4)]
+    pub normal_map: Option<Handle<Image>>,
+    /// The lights of the scene, written by the [`Lighting2dPlugin`].
+    #[reflect(ignore, clone)]
+    lights: Lights2dUniform,
+}
+
+impl Default for LitMaterial2d {
+    fn default() -> Self {
+        LitMaterial2d {
+            color: Color::WHITE,
+            alpha_mode: AlphaMode2d::Blend,
+            texture: None,
+            normal_map: None,
+            lights: Lights2dUniform::default(),
+        }
+    }
+}
+
+impl From<Handle<Image>> for LitMaterial2d {
+    fn from(texture: Handle<Image>) -> Self {
+        LitMaterial2d {
+            texture: Some(texture),
+            ..Default::default()
+        }
+    }
+}
+
+// NOTE: These must match the bit flags in bevy_sprite/src/mesh2d/lit_material2d.wgsl!
+bitflags::bitflags! {
+    #[repr(transparent)]
+    pub struct LitMaterial2dFlags: u32 {
+        const TEXTURE                    = 1 << 0;
+        const NORMAL_MAP                 = 1 << 1;
+        const ALPHA_MODE_RESERVED_BITS   = Self::ALPHA_MODE_MASK_BITS << Self::ALPHA_MODE_SHIFT_BITS;
+        const ALPHA_MODE_OPAQUE          = 0 << Self::ALPHA_MODE_SHIFT_BITS;
+        const ALPHA_MODE_MASK            = 1 << Self::ALPHA_MODE_SHIFT_BITS;
+        const ALPHA_MODE_BLEND           = 2 << Self::ALPHA_MODE_SHIFT_BITS;
+        const NONE                       = 0;
+    }
+}
+
+impl LitMaterial2dFlags {
+    const ALPHA_MODE_MASK_BITS: u32 = 0b11;
+    const ALPHA_MODE_SHIFT_BITS: u32 = 32 - Self::ALPHA_MODE_MASK_BITS.count_ones();
+}
+
+/// The GPU representation of a [`PointLight2d`] or [`SpotLight2d`].
+#[derive(Clone, Copy, Default, Debug, PartialEq, ShaderType)]
+pub struct GpuLight2d {
+    /// The color of the light, multiplied by its intensity.
+    color: Vec4,
+    /// The position of the light, its height and its range.
+    position_height_range: Vec4,
+    /// The direction of a spot light, and the cosines of its inner and outer angles.
+    direction_cos_angles: Vec4,
+    /// The radius of the light source, or a negative value if the light doesn't cast shadows.
+    radius: f32,
+    /// Whether the light is a spot light.
+    spot: u32,
+}
+
+/// The GPU representation of a [`LightOccluder2d`], as an oriented rectangle.
+#[derive(Clone, Copy, Default, Debug, PartialEq, ShaderType)]
+pub struct GpuOccluder2d {
+    /// The center of the rectangle, and its X axis scaled by its half width.
+    center_x_axis: Vec4,
+    /// The Y axis of the rectangle, scaled by its half height.
+    y_axis: Vec4,
+}
+
+/// The lights and occluders of the scene, shared by all [`LitMaterial2d`]s.
+#[derive(Clone, Copy, Default, Debug, PartialEq, ShaderType)]
+pub struct Lights2dUniform {
+    ambient: Vec4,
+    lights: [GpuLight2d; MAX_LIGHTS_2D],
+    occluders: [GpuOccluder2d; MAX_OCCLUDERS_2D],
+    light_count: u32,
+    occluder_count: u32,
+}
+
+/// The GPU representation of the uniform data of a [`LitMaterial2d`].
+#[derive(Clone, Default, ShaderType)]
+pub struct LitMaterial2dUniform {
+    pub color: Vec4,
+    pub flags: u32,
+    pub alpha_cutoff: f32,
+    pub lights: Lights2dUniform,
+}
+
+impl AsBindGroupShaderType<LitMaterial2dUniform> for LitMaterial2d {
+    fn as_bind_group_shader_type(&self, _images: &RenderAssets<GpuImage>) -> LitMaterial2dUniform {
+        let mut flags = LitMaterial2dFlags::NONE;
+        if self.texture.is_some() {
+            flags |= LitMaterial2dFlags::TEXTURE;
+        }
+        if self.normal_map.is_some() {
+            flags |= LitMaterial2dFlags::NORMAL_MAP;
+        }
+
+        let mut alpha_cutoff = 0.5;
+        match self.alpha_mode {
+            AlphaMode2d::Opaque => flags |= LitMaterial2dFlags::ALPHA_MODE_OPAQUE,
+            AlphaMode2d::Mask(c) => {
+                alpha_cutoff = c;
+                flags |= LitMaterial2dFlags::ALPHA_MODE_MASK;
+            }
+            AlphaMode2d::Blend => flags |= LitMaterial2dFlags::ALPHA_MODE_BLEND,
+        };
+        LitMaterial2dUniform {
+            color: LinearRgba::from(self.color).to_vec4(),
+            flags: flags.bits(),
+            alpha_cutoff,
+            lights: self.lights,
+        }
+    }
+}
+
+impl Material2d for LitMaterial2d {
+    fn fragment_shader() -> ShaderRef {
+        LIT_MATERIAL_2D_SHADER_HANDLE.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        self.alpha_mode
+    }
+}
+
+fn light_color(color: Color, intensity: f32) -> Vec4 {
+    (LinearRgba::from(color).to_vec3() * intensity).extend(1.0)
+}
+
+/// Gathers the 2D lights and occluders, and writes them to the [`LitMaterial2d`]s when they change.
+pub fn update_lit_materials_2d(
+    ambient_light: Res<AmbientLight2d>,
+    point_lights: Query<(&PointLight2d, &GlobalTransform, &InheritedVisibility)>,
+    spot_lights: Query<(&SpotLight2d, &GlobalTransform, &InheritedVisibility)>,
+    occluders: Query<(&LightOccluder2d, &GlobalTransform, &InheritedVisibility)>,
+    mut materials: ResMut<Assets<LitMaterial2d>>,
+) {
+    let mut uniform = Lights2dUniform {
+        ambient: light_color(ambient_light.color, ambient_light.brightness),
+        ..Default::default()
+    };
+
+    let point_lights = point_lights
+        .iter()
+        .filter(|(.., visibility)| visibility.get())
+        .map(|(light, transform, _)| GpuLight2d {
+            color: light_color(light.color, light.intensity),
+            position_height_range: transform
+                .translation()
+                .truncate()
+                .extend(light.height)
+                .extend(light.range),
+            direction_cos_angles: Vec4::ZERO,
+            radius: if light.shadows_enabled {
+                light.radius
+            } else {
+                -1.0
+            },
+            spot: 0,
+        });
+    let spot_lights = spot_lights
+        .iter()
+        .filter(|(.., visibility)| visibility.get())
+        .map(|(light, transform, _)| GpuLight2d {
+            color: light_color(light.color, light.intensity),
+            position_height_range: transform
+                .translation()
+                .truncate()
+                .extend(light.height)
+                .extend(light.range),
+            direction_cos_angles: transform
+                .right()
+                .truncate()
+                .normalize_or_zero()
+                .extend(ops::cos(light.inner_angle))
+                .extend(ops::cos(light.outer_angle)),
+            radius: if light.shadows_enabled {
+                light.radius
+            } else {
+                -1.0
+            },
+            spot: 1,
+        });
+    for (gpu_light, light) in uniform
+        .lights
+        .iter_mut()
+        .zip(point_lights.chain(spot_lights))
+    {
+        *gpu_light = light;
+        uniform.light_count += 1;
+    }
+
+    let occluders = occluders
+        .iter()
+        .filter(|(.., visibility)| visibility.get())
+        .map(|(occluder, transform, _)| {
+            let affine = transform.affine();
+            let center = affine.translation.truncate();
+            let x_axis = affine.matrix3.x_axis.truncate() * occluder.half_size.x;
+            let y_axis = affine.matrix3.y_axis.truncate() * occluder.half_size.y;
+            GpuOccluder2d {
+                center_x_axis: Vec4::new(center.x, center.y, x_axis.x, x_axis.y),
+                y_axis: Vec4::new(y_axis.x, y_axis.y, 0.0, 0.0),
+            }
+        });
+    for (gpu_occluder, occluder) in uniform.occluders.iter_mut().zip(occluders) {
+        *gpu_occluder = occluder;
+        uniform.occluder_count += 1;
+    }
+
+    let outdated: Vec<AssetId<LitMaterial2d>> = materials
+        .iter()
+        .filter(|(_, material)| material.lights != uniform)
+        .map(|(id, _)| id)
+        .collect();
+    for id in outdated {
+        if let Some(material) = materials.get_mut(id) {
+            material.lights = uniform;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn lights_are_written_to_materials() {
+        let mut world = World::new();
+        world.init_resource::<AmbientLight2d>();
+        let mut materials = Assets::<LitMaterial2d>::default();
+        let material = materials.add(LitMaterial2d::default());
+        world.insert_resource(materials);
+
+        world.spawn((
+            PointLight2d::default(),
+            GlobalTransform::from_xyz(1.0, 2.0, 0.0),
+            InheritedVisibility::VISIBLE,
+        ));
+        world.spawn((
+            PointLight2d::default(),
+            GlobalTransform::default(),
+            InheritedVisibility::HIDDEN,
+        ));
+        world.spawn((
+            LightOccluder2d {
+                half_size: Vec2::ONE,
+            },
+            GlobalTransform::default(),
+            InheritedVisibility::VISIBLE,
+        ));
+        world.run_system_once(update_lit_materials_2d).unwrap();
+
+        let lights = world
+            .resource::<Assets<LitMaterial2d>>()
+            .get(&material)
+            .unwrap()
+            .lights;
+        assert_eq!(lights.light_count, 1);
+        assert_eq!(lights.occluder_count, 1);
+        assert_eq!(lights.lights[0].position_height_range.x, 1.0);
+        assert_eq!(lights.lights[0].position_height_range.y, 2.0);
+    }
+}
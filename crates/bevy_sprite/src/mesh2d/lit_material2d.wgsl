@@ -0,0 +1,205 @@
+#import bevy_sprite::{
+    mesh2d_vertex_output::VertexOutput,
+    mesh2d_view_bindings::view,
+}
+
+#ifdef TONEMAP_IN_SHADER
+#import bevy_core_pipeline::tonemapping
+#endif
+
+// Must be kept in sync with `lighting2d.rs`
+const MAX_LIGHTS_2D: u32 = 16u;
+const MAX_OCCLUDERS_2D: u32 = 32u;
+// The number of samples across the light source used for soft shadows.
+const SOFT_SHADOW_SAMPLES: u32 = 8u;
+
+struct Light2d {
+    color: vec4<f32>,
+    position_height_range: vec4<f32>,
+    direction_cos_angles: vec4<f32>,
+    // Negative if the light doesn't cast shadows.
+    radius: f32,
+    spot: u32,
+};
+
+struct Occluder2d {
+    // The center of the rectangle, and its X axis scaled by its half width.
+    center_x_axis: vec4<f32>,
+    // The Y axis of the rectangle, scaled by its half height.
+    y_axis: vec4<f32>,
+};
+
+struct Lights2d {
+    ambient: vec4<f32>,
+    lights: array<Light2d, MAX_LIGHTS_2D>,
+    occluders: array<Occluder2d, MAX_OCCLUDERS_2D>,
+    light_count: u32,
+    occluder_count: u32,
+};
+
+struct LitMaterial2d {
+    color: vec4<f32>,
+    // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
+    flags: u32,
+    alpha_cutoff: f32,
+    lights: Lights2d,
+};
+
+const LIT_MATERIAL_2D_FLAGS_TEXTURE_BIT: u32              = 1u;
+const LIT_MATERIAL_2D_FLAGS_NORMAL_MAP_BIT: u32           = 2u;
+const LIT_MATERIAL_2D_FLAGS_ALPHA_MODE_RESERVED_BITS: u32 = 3221225472u; // (0b11u32 << 30)
+const LIT_MATERIAL_2D_FLAGS_ALPHA_MODE_OPAQUE: u32        = 0u;          // (0u32 << 30)
+const LIT_MATERIAL_2D_FLAGS_ALPHA_MODE_MASK: u32          = 1073741824u; // (1u32 << 30)
+const LIT_MATERIAL_2D_FLAGS_ALPHA_MODE_BLEND: u32         = 2147483648u; // (2u32 << 30)
+
+@group(2) @binding(0) var<uniform> material: LitMaterial2d;
+@group(2) @binding(1) var texture: texture_2d<f32>;
+@group(2) @binding(2) var texture_sampler: sampler;
+@group(2) @binding(3) var normal_map: texture_2d<f32>;
+@group(2) @binding(4) var normal_map_sampler: sampler;
+
+@fragment
+fn fragment(
+    mesh: VertexOutput,
+) -> @location(0) vec4<f32> {
+    var output_color: vec4<f32> = material.color;
+
+#ifdef VERTEX_COLORS
+    output_color = output_color * mesh.color;
+#endif
+
+    if ((material.flags & LIT_MATERIAL_2D_FLAGS_TEXTURE_BIT) != 0u) {
+        output_color = output_color * textureSample(texture, texture_sampler, mesh.uv);
+    }
+
+    var normal = vec3<f32>(0.0, 0.0, 1.0);
+    if ((material.flags & LIT_MATERIAL_2D_FLAGS_NORMAL_MAP_BIT) != 0u) {
+        normal = normalize(textureSample(normal_map, normal_map_sampler, mesh.uv).rgb * 2.0 - 1.0);
+    }
+
+    let light = lighting(mesh.world_position.xy, normal);
+    output_color = vec4<f32>(output_color.rgb * light, output_color.a);
+
+    output_color = alpha_discard(material, output_color);
+
+#ifdef TONEMAP_IN_SHADER
+    output_color = tonemapping::tone_mapping(output_color, view.color_grading);
+#endif
+    return output_color;
+}
+
+// Returns the light received by a point of the lit plane with the given normal.
+fn lighting(position: vec2<f32>, normal: vec3<f32>) -> vec3<f32> {
+    var light = material.lights.ambient.rgb;
+    for (var i = 0u; i < material.lights.light_count; i += 1u) {
+        let light_2d = material.lights.lights[i];
+        let to_light = light_2d.position_height_range.xy - position;
+        let distance = length(to_light);
+        let range = light_2d.position_height_range.w;
+        if (distance >= range) {
+            continue;
+        }
+
+        // Smooth windowed falloff, reaching zero at the range of the light.
+        let falloff = saturate(1.0 - (distance * distance) / (range * range));
+        var attenuation = falloff * falloff;
+
+        if (light_2d.spot != 0u) {
+            let cos_angle = dot(-to_light / max(distance, 0.0001), light_2d.direction_cos_angles.xy);
+            attenuation *= smoothstep(
+                light_2d.direction_cos_angles.w,
+                light_2d.direction_cos_angles.z,
+                cos_angle,
+            );
+        }
+
+        let direction = normalize(vec3<f32>(to_light, light_2d.position_height_range.z));
+        let diffuse = max(dot(normal, direction), 0.0);
+        if (attenuation * diffuse <= 0.0) {
+            continue;
+        }
+
+        if (light_2d.radius >= 0.0) {
+            attenuation *= visibility(position, light_2d);
+        }
+        light += light_2d.color.rgb * attenuation * diffuse;
+    }
+    return light;
+}
+
+// Returns the fraction of the light source visible from the given point.
+fn visibility(position: vec2<f32>, light_2d: Light2d) -> f32 {
+    let light_position = light_2d.position_height_range.xy;
+    if (light_2d.radius <= 0.0) {
+        return select(1.0, 0.0, is_occluded(position, light_position));
+    }
+
+    // Sample points across the light source, perpendicular to the direction of the light.
+    let to_light = light_position - position;
+    let tangent = normalize(vec2<f32>(-to_light.y, to_light.x) + vec2<f32>(0.0001, 0.0));
+    var visible = 0.0;
+    for (var i = 0u; i < SOFT_SHADOW_SAMPLES; i += 1u) {
+        let offset = (f32(i) + 0.5) / f32(SOFT_SHADOW_SAMPLES) * 2.0 - 1.0;
+        let sample_position = light_position + tangent * offset * light_2d.radius;
+        visible += select(1.0, 0.0, is_occluded(position, sample_position));
+    }
+    return visible / f32(SOFT_SHADOW_SAMPLES);
+}
+
+// Returns whether the segment from `position` to `light_position` crosses an occluder.
+fn is_occluded(position: vec2<f32>, light_position: vec2<f32>) -> bool {
+    for (var i = 0u; i < material.lights.occluder_count; i += 1u) {
+        let occluder = material.lights.occluders[i];
+        // Transform the segment to the space of the occluder, where it's the [-1, 1] square.
+        let basis = mat2x2<f32>(occluder.center_x_axis.zw, occluder.y_axis.xy);
+        let basis_determinant = determinant(basis);
+        if (abs(basis_determinant) < 1e-8) {
+            continue;
+        }
+        let inverse_basis = mat2x2<f32>(
+            vec2<f32>(basis[1].y, -basis[0].y),
+            vec2<f32>(-basis[1].x, basis[0].x),
+        ) / basis_determinant;
+        let start = inverse_basis * (position - occluder.center_x_axis.xy);
+        let end = inverse_basis * (light_position - occluder.center_x_axis.xy);
+
+        // Points inside the occluder are lit, so that the occluder itself is visible.
+        if (all(abs(start) <= vec2<f32>(1.0))) {
+            continue;
+        }
+
+        // Slab test against the square.
+        let direction = end - start;
+        let inverse_direction = 1.0 / select(direction, vec2<f32>(1e-8), abs(direction) < vec2<f32>(1e-8));
+        let t0 = (vec2<f32>(-1.0) - start) * inverse_direction;
+        let t1 = (vec2<f32>(1.0) - start) * inverse_direction;
+        let near = max(max(min(t0.x, t1.x), min(t0.y, t1.y)), 0.0);
+        let far = min(min(max(t0.x, t1.x), max(t0.y, t1.y)), 1.0);
+        if (near <= far) {
+            return true;
+        }
+    }
+    return false;
+}
+
+fn alpha_discard(material: LitMaterial2d, output_color: vec4<f32>) -> vec4<f32> {
+    var color = output_color;
+    let alpha_mode = material.flags & LIT_MATERIAL_2D_FLAGS_ALPHA_MODE_RESERVED_BITS;
+    if alpha_mode == LIT_MATERIAL_2D_FLAGS_ALPHA_MODE_OPAQUE {
+        // NOTE: If rendering as opaque, alpha should be ignored so set to 1.0
+        color.a = 1.0;
+    }
+#ifdef MAY_DISCARD
+    else if alpha_mode == LIT_MATERIAL_2D_FLAGS_ALPHA_MODE_MASK {
+       if color.a >= material.alpha_cutoff {
+            // NOTE: If rendering as masked alpha and >= the cutoff, render as fully opaque
+            color.a = 1.0;
+        } else {
+            // NOTE: output_color.a < in.material.alpha_cutoff should not be rendered
+            discard;
+        }
+    }
+#endif // MAY_DISCARD
+
+    return color;
+}
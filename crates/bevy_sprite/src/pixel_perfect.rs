@@ -0,0 +1,427 @@
+use crate::Sprite;
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{Assets, Handle};
+use bevy_color::Color;
+use bevy_core_pipeline::core_2d::Camera2d;
+use bevy_ecs::prelude::*;
+use bevy_image::{Image, ImageSampler};
+use bevy_math::{Rect, UVec2, Vec2, Vec3};
+use bevy_reflect::prelude::*;
+use bevy_render::{
+    camera::{Camera, CameraUpdateSystem, ClearColorConfig, Projection, RenderTarget, ScalingMode},
+    render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+    view::{Msaa, RenderLayers, VisibilitySystems},
+};
+use bevy_transform::{
+    components::{GlobalTransform, Transform},
+    TransformSystem,
+};
+
+/// Adds support for [`PixelPerfectCamera`]s and [`PixelArtCamera`]s.
+#[derive(Default)]
+pub struct PixelPerfectPlugin;
+
+impl Plugin for PixelPerfectPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PixelPerfectCamera>()
+            .register_type::<PixelArtCamera>()
+            .add_systems(
+                PostUpdate,
+                (
+                    (setup_pixel_perfect_cameras, update_pixel_art_projections)
+                        .before(CameraUpdateSystem),
+                    update_pixel_perfect_cameras.after(CameraUpdateSystem),
+                    (snap_pixel_art_cameras, snap_sprites_to_pixels)
+                        .after(CameraUpdateSystem)
+                        .after(TransformSystem::TransformPropagate)
+                        .before(VisibilitySystems::UpdateFrusta),
+                ),
+            );
+    }
+}
+
+/// Renders a camera at a low resolution, and displays it upscaled by the largest integer factor
+/// fitting its window, with nearest filtering, for pixel-art games.
+///
+/// The camera renders to an [`Image`] of [`resolution`](Self::resolution), shown by a
+/// [`Sprite`] on [`output_layer`](Self::output_layer). That layer is rendered to the original
+/// target of the camera by an output camera, which fills the remaining space with the
+/// [`letterbox_color`](Self::letterbox_color). These are created by the [`PixelPerfectPlugin`],
+/// which then adds a [`PixelPerfectOutput`] component to the camera.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_core_pipeline::core_2d::Camera2d;
+/// # use bevy_math::UVec2;
+/// # use bevy_sprite::PixelPerfectCamera;
+/// # fn system(mut commands: Commands) {
+/// commands.spawn((
+///     Camera2d,
+///     PixelPerfectCamera {
+///         resolution: UVec2::new(320, 180),
+///         ..Default::default()
+///     },
+/// ));
+/// # }
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+#[require(Camera2d)]
+pub struct PixelPerfectCamera {
+    /// The size of the image the camera renders to, in pixels.
+    pub resolution: UVec2,
+    /// The [`RenderLayers`] layer the upscaled image is rendered on. Nothing else should be on this
+    /// layer.
+    pub output_layer: usize,
+    /// The color of the bars around the upscaled image.
+    pub letterbox_color: Color,
+}
+
+impl Default for PixelPerfectCamera {
+    fn default() -> Self {
+        Self {
+            resolution: UVec2::new(320, 180),
+            output_layer: 31,
+            letterbox_color: Color::BLACK,
+        }
+    }
+}
+
+/// The upscaled output of a [`PixelPerfectCamera`], added by the [`PixelPerfectPlugin`].
+#[derive(Component, Clone, Debug)]
+pub struct PixelPerfectOutput {
+    /// The low resolution image the camera renders to.
+    pub image: Handle<Image>,
+    /// The entity displaying the upscaled image.
+    pub canvas: Entity,
+    /// The camera rendering the upscaled image to the original target of the camera.
+    pub output_camera: Entity,
+    /// The integer factor the image is upscaled by, in physical pixels.
+    pub scale: u32,
+    resolution: UVec2,
+    viewport_size: Vec2,
+    scale_factor: f32,
+}
+
+impl PixelPerfectOutput {
+    /// Converts a position in the logical viewport of the output camera, such as the position of
+    /// the cursor in the window, to a position in the low resolution image.
+    ///
+    /// Returns `None` if the position is outside of the image, in the letterbox. The result can be
+    /// passed to methods like [`Camera::viewport_to_world_2d`] of the [`PixelPerfectCamera`].
+    pub fn viewport_to_canvas(&self, viewport_position: Vec2) -> Option<Vec2> {
+        if self.scale == 0 {
+            return None;
+        }
+        let pixel_size = self.scale as f32 / self.scale_factor;
+        let canvas_size = self.resolution.as_vec2() * pixel_size;
+        let offset = (self.viewport_size - canvas_size) / 2.0;
+        let position = (viewport_position - offset) / pixel_size;
+        (position.cmpge(Vec2::ZERO).all() && position.cmplt(self.resolution.as_vec2()).all())
+            .then_some(position)
+    }
+}
+
+fn pixel_perfect_image(resolution: UVec2) -> Image {
+    let mut image = Image::new_fill(
+        Extent3d {
+            width: resolution.x.max(1),
+            height: resolution.y.max(1),
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0; 4],
+        TextureFormat::Bgra8UnormSrgb,
+        Default::default(),
+    );
+    image.texture_descriptor.usage =
+        TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST | TextureUsages::RENDER_ATTACHMENT;
+    image.sampler = ImageSampler::nearest();
+    image
+}
+
+/// Renders new [`PixelPerfectCamera`]s to a low resolution image, and spawns their output.
+pub fn setup_pixel_perfect_cameras(
+    mut commands: Commands,
+    mut cameras: Query<(Entity, &PixelPerfectCamera, &mut Camera), Without<PixelPerfectOutput>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for (entity, pixel_perfect, mut camera) in &mut cameras {
+        let image = images.add(pixel_perfect_image(pixel_perfect.resolution));
+        let output_layers = RenderLayers::layer(pixel_perfect.output_layer);
+
+        let canvas = commands
+            .spawn((Sprite::from_image(image.clone()), output_layers.clone()))
+            .id();
+        let output_camera = commands
+            .spawn((
+                Camera2d,
+                Camera {
+                    order: camera.order + 1,
+                    target: camera.target.clone(),
+                    clear_color: ClearColorConfig::Custom(pixel_perfect.letterbox_color),
+                    ..Default::default()
+                },
+                Msaa::Off,
+                output_layers,
+            ))
+            .id();
+
+        camera.target = RenderTarget::Image(image.clone().into());
+        commands.entity(entity).insert((
+            Msaa::Off,
+            PixelPerfectOutput {
+                image,
+                canvas,
+                output_camera,
+                scale: 0,
+                resolution: pixel_perfect.resolution,
+                viewport_size: Vec2::ZERO,
+                scale_factor: 1.0,
+            },
+        ));
+    }
+}
+
+/// Resizes the images of [`PixelPerfectCamera`]s, and upscales them to fit their viewport.
+pub fn update_pixel_perfect_cameras(
+    mut cameras: Query<(&PixelPerfectCamera, &mut PixelPerfectOutput)>,
+    output_cameras: Query<&Camera>,
+    mut canvases: Query<&mut Transform>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for (pixel_perfect, mut output) in &mut cameras {
+        if output.resolution != pixel_perfect.resolution {
+            if let Some(image) = images.get_mut(&output.image) {
+                image.resize(Extent3d {
+                    width: pixel_perfect.resolution.x.max(1),
+                    height: pixel_perfect.resolution.y.max(1),
+                    depth_or_array_layers: 1,
+                });
+            }
+            output.resolution = pixel_perfect.resolution;
+        }
+
+        let Ok(output_camera) = output_cameras.get(output.output_camera) else {
+            continue;
+        };
+        let (Some(physical_size), Some(viewport_size), Some(scale_factor)) = (
+            output_camera.physical_viewport_size(),
+            output_camera.logical_viewport_size(),
+            output_camera.target_scaling_factor(),
+        ) else {
+            continue;
+        };
+        let fit = physical_size / pixel_perfect.resolution.max(UVec2::ONE);
+        let scale = fit.min_element().max(1);
+        if output.scale != scale
+            || output.viewport_size != viewport_size
+            || output.scale_factor != scale_factor
+        {
+            output.scale = scale;
+            output.viewport_size = viewport_size;
+            output.scale_factor = scale_factor;
+        }
+
+        if let Ok(mut transform) = canvases.get_mut(output.canvas) {
+            let canvas_scale = Vec3::splat(scale as f32 / scale_factor).with_z(1.0);
+            if transform.scale != canvas_scale {
+                transform.scale = canvas_scale;
+            }
+        }
+    }
+}
+
+/// A 2D camera for pixel-art games rendering directly to its target, with an integer
+/// [`zoom`](Self::zoom), so that each pixel of the art covers the same number of physical pixels.
+///
+/// One world unit is one pixel of the art. The [`PixelArtCamera`] sets the
+/// [`OrthographicProjection`](bevy_render::camera::OrthographicProjection) of the camera to match
+/// the zoom and the scale factor of the window, overriding its
+/// [`scale`](bevy_render::camera::OrthographicProjection::scale) and
+/// [`scaling_mode`](bevy_render::camera::OrthographicProjection::scaling_mode).
+///
+/// To avoid shimmering when the camera or the sprites move by fractions of a pixel, the position of
+/// the camera, and optionally of all [`Sprite`]s, is rounded to whole pixels after transform
+/// propagation. Only their [`GlobalTransform`] is rounded, so their [`Transform`] keeps moving
+/// smoothly. The camera is also kept within [`bounds`](Self::bounds) before being rounded.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::Rect;
+/// # use bevy_sprite::PixelArtCamera;
+/// # fn system(mut commands: Commands) {
+/// commands.spawn(PixelArtCamera {
+///     zoom: 3,
+///     bounds: Some(Rect::new(0.0, 0.0, 1024.0, 512.0)),
+///     ..Default::default()
+/// });
+/// # }
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+#[require(Camera2d, Msaa::Off)]
+pub struct PixelArtCamera {
+    /// The number of physical pixels covered by each pixel of the art, in each direction.
+    ///
+    /// Defaults to `1`. A zoom of `0` is treated as `1`.
+    pub zoom: u32,
+    /// Whether the positions of all [`Sprite`]s are rounded to whole pixels.
+    ///
+    /// Defaults to `true`.
+    pub snap_sprites: bool,
+    /// The area of the world the view of the camera is kept within, if any. When the view is larger
+    /// than the bounds, it is centered on them.
+    ///
+    /// Defaults to [`None`].
+    pub bounds: Option<Rect>,
+}
+
+impl Default for PixelArtCamera {
+    fn default() -> Self {
+        Self {
+            zoom: 1,
+            snap_sprites: true,
+            bounds: None,
+        }
+    }
+}
+
+impl PixelArtCamera {
+    /// Increases the [`zoom`](Self::zoom) by one level, up to `max_zoom`.
+    pub fn zoom_in(&mut self, max_zoom: u32) {
+        self.zoom = (self.zoom.max(1) + 1).min(max_zoom.max(1));
+    }
+
+    /// Decreases the [`zoom`](Self::zoom) by one level, down to `1`.
+    pub fn zoom_out(&mut self) {
+        self.zoom = self.zoom.saturating_sub(1).max(1);
+    }
+
+    /// Returns the translation the view of a camera at `translation` is clamped and rounded to,
+    /// given the `area` it sees relative to its position.
+    fn snapped_translation(&self, translation: Vec2, area: Rect) -> Vec2 {
+        let mut min = translation + area.min;
+        if let Some(bounds) = self.bounds {
+            let size = area.size();
+            let free = (bounds.size() - size).max(Vec2::ZERO);
+            let centered = bounds.min - (size - bounds.size()).max(Vec2::ZERO) / 2.0;
+            min = min.clamp(centered, centered + free);
+        }
+        // Align the edge of the view, rather than its center, with the pixels of the art, for
+        // viewports with an odd size.
+        min.round() - area.min
+    }
+}
+
+/// Sets the projections of [`PixelArtCamera`]s to match their zoom.
+pub fn update_pixel_art_projections(
+    mut cameras: Query<(&PixelArtCamera, &Camera, &mut Projection)>,
+) {
+    for (pixel_art, camera, mut projection) in &mut cameras {
+        let scale = camera.target_scaling_factor().unwrap_or(1.0) / pixel_art.zoom.max(1) as f32;
+        let Projection::Orthographic(orthographic) = projection.bypass_change_detection() else {
+            continue;
+        };
+        if orthographic.scale != scale
+            || !matches!(orthographic.scaling_mode, ScalingMode::WindowSize)
+        {
+            orthographic.scale = scale;
+            orthographic.scaling_mode = ScalingMode::WindowSize;
+            projection.set_changed();
+        }
+    }
+}
+
+/// Clamps the [`GlobalTransform`]s of [`PixelArtCamera`]s to their bounds, and rounds them to
+/// whole pixels.
+pub fn snap_pixel_art_cameras(
+    mut cameras: Query<(&PixelArtCamera, &Projection, &mut GlobalTransform)>,
+) {
+    for (pixel_art, projection, mut global_transform) in &mut cameras {
+        let Projection::Orthographic(orthographic) = projection else {
+            continue;
+        };
+        let mut transform = global_transform.compute_transform();
+        let snapped =
+            pixel_art.snapped_translation(transform.translation.truncate(), orthographic.area);
+        if snapped != transform.translation.truncate() {
+            transform.translation = snapped.extend(transform.translation.z);
+            *global_transform = transform.into();
+        }
+    }
+}
+
+/// Rounds the [`GlobalTransform`]s of [`Sprite`]s to whole pixels, if a [`PixelArtCamera`] has
+/// [`snap_sprites`](PixelArtCamera::snap_sprites) enabled.
+pub fn snap_sprites_to_pixels(
+    cameras: Query<&PixelArtCamera>,
+    mut sprites: Query<&mut GlobalTransform, (With<Sprite>, Changed<GlobalTransform>)>,
+) {
+    if !cameras.iter().any(|pixel_art| pixel_art.snap_sprites) {
+        return;
+    }
+    for mut global_transform in &mut sprites {
+        let translation = global_transform.translation();
+        let snapped = translation.truncate().round().extend(translation.z);
+        if snapped != translation {
+            let mut transform = global_transform.compute_transform();
+            transform.translation = snapped;
+            *global_transform = transform.into();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapped_translation() {
+        let camera = PixelArtCamera {
+            bounds: Some(Rect::new(0.0, 0.0, 100.0, 50.0)),
+            ..Default::default()
+        };
+        // A view of 21x10 pixels, centered on the camera.
+        let area = Rect::new(-10.5, -5.0, 10.5, 5.0);
+        assert_eq!(
+            camera.snapped_translation(Vec2::new(50.2, 20.7), area),
+            Vec2::new(50.5, 21.0)
+        );
+        // Clamped to the bounds.
+        assert_eq!(
+            camera.snapped_translation(Vec2::new(-20.0, 60.0), area),
+            Vec2::new(10.5, 45.0)
+        );
+        // A view taller than the bounds is centered on them.
+        let tall_area = Rect::new(-10.5, -30.0, 10.5, 30.0);
+        assert_eq!(
+            camera.snapped_translation(Vec2::new(50.0, 0.0), tall_area),
+            Vec2::new(50.5, 25.0)
+        );
+    }
+
+    #[test]
+    fn viewport_to_canvas() {
+        let output = PixelPerfectOutput {
+            image: Handle::default(),
+            canvas: Entity::PLACEHOLDER,
+            output_camera: Entity::PLACEHOLDER,
+            scale: 3,
+            resolution: UVec2::new(100, 50),
+            viewport_size: Vec2::new(400.0, 200.0),
+            scale_factor: 1.0,
+        };
+        // The 300x150 canvas is centered, with 50 and 25 pixel bars.
+        assert_eq!(
+            output.viewport_to_canvas(Vec2::new(50.0, 25.0)),
+            Some(Vec2::ZERO)
+        );
+        assert_eq!(
+            output.viewport_to_canvas(Vec2::new(200.0, 100.0)),
+            Some(Vec2::new(50.0, 25.0))
+        );
+        assert_eq!(output.viewport_to_canvas(Vec2::new(10.0, 100.0)), None);
+        assert_eq!(output.viewport_to_canvas(Vec2::new(200.0, 175.0)), None);
+    }
+}
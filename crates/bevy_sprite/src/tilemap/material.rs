@@ -0,0 +1,63 @@
+use crate::{AlphaMode2d, Material2d, Material2dKey};
+use bevy_asset::{weak_handle, Asset, Handle};
+use bevy_image::Image;
+use bevy_math::UVec2;
+use bevy_reflect::prelude::*;
+use bevy_render::{
+    mesh::{Mesh, MeshVertexAttribute, MeshVertexBufferLayoutRef},
+    render_resource::{
+        AsBindGroup, RenderPipelineDescriptor, Shader, ShaderRef, SpecializedMeshPipelineError,
+        VertexFormat,
+    },
+};
+
+pub const TILEMAP_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("5d8f3b53-2b8a-4a4e-8d83-3f6ff0c9a6f1");
+
+/// Per-tile data of a tilemap chunk mesh: the tileset index, the flip flags, the number of
+/// animation frames and the animation frames per second, stored as `f32` bits.
+pub const ATTRIBUTE_TILE: MeshVertexAttribute =
+    MeshVertexAttribute::new("Tile", 2_715_470_301, VertexFormat::Uint32x4);
+
+/// The [2d material](Material2d) used to render the chunks of a [`Tilemap`](super::Tilemap).
+///
+/// A material is created for each tilemap, and kept in sync with its tileset.
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[reflect(Debug, Clone)]
+pub struct TilemapMaterial {
+    /// The number of columns and rows of tiles in the tileset.
+    #[uniform(0)]
+    pub tileset_size: UVec2,
+    /// The tileset image.
+    #[texture(1)]
+    #[sampler(2)]
+    pub tileset: Handle<Image>,
+}
+
+impl Material2d for TilemapMaterial {
+    fn vertex_shader() -> ShaderRef {
+        TILEMAP_SHADER_HANDLE.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        TILEMAP_SHADER_HANDLE.into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+
+    fn specialize(
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: Material2dKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        let vertex_layout = layout.0.get_layout(&[
+            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+            Mesh::ATTRIBUTE_UV_0.at_shader_location(1),
+            ATTRIBUTE_TILE.at_shader_location(2),
+        ])?;
+        descriptor.vertex.buffers = vec![vertex_layout];
+        Ok(())
+    }
+}
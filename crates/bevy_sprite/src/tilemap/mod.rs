@@ -0,0 +1,477 @@
+//! Chunked 2D tilemaps.
+//!
+//! See [`Tilemap`] for more details.
+
+mod material;
+
+pub use material::*;
+
+use crate::{Material2dPlugin, MeshMaterial2d};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, Assets, Handle, RenderAssetUsages};
+use bevy_ecs::prelude::*;
+use bevy_image::Image;
+use bevy_math::{IVec2, UVec2, Vec2, Vec3};
+use bevy_platform_support::collections::{HashMap, HashSet};
+use bevy_reflect::prelude::*;
+use bevy_render::{
+    mesh::{Indices, Mesh, Mesh2d},
+    primitives::Aabb,
+    render_resource::{PrimitiveTopology, Shader},
+    view::{Visibility, VisibilitySystems},
+};
+use bevy_transform::components::Transform;
+
+/// Adds support for rendering [`Tilemap`]s.
+///
+/// This plugin is added by the [`SpritePlugin`](crate::SpritePlugin).
+#[derive(Default)]
+pub struct TilemapPlugin;
+
+impl Plugin for TilemapPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            TILEMAP_SHADER_HANDLE,
+            "tilemap.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.add_plugins(Material2dPlugin::<TilemapMaterial>::default())
+            .register_type::<Tilemap>()
+            .register_type::<Tile>()
+            .register_type::<TileAnimation>()
+            .register_type::<TileStorage>()
+            .register_type::<TilemapChunk>()
+            .register_type::<TilemapChunks>()
+            .add_systems(
+                PostUpdate,
+                sync_tilemap_chunks.before(VisibilitySystems::CalculateBounds),
+            );
+    }
+}
+
+/// A grid of tiles drawn from a single tileset image.
+///
+/// The tiles are stored in the [`TileStorage`] of the tilemap entity, and addressed by their
+/// position on the grid, with `(0, 0)` at the origin of the tilemap and `y` pointing up.
+///
+/// Tiles are grouped in chunks of [`TileStorage::chunk_size`] tiles. Each chunk is rendered as a
+/// single mesh by a child entity with a [`TilemapChunk`] component, so that the whole chunk is
+/// drawn with one draw call, and chunks outside of the view are culled. Editing a tile only
+/// rebuilds the chunk containing it, at the end of the frame.
+///
+/// ```
+/// # use bevy_asset::Handle;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_image::Image;
+/// # use bevy_math::{IVec2, UVec2, Vec2};
+/// # use bevy_sprite::{Tile, TileStorage, Tilemap};
+/// fn spawn_tilemap(mut commands: Commands, tileset: Handle<Image>) {
+///     let mut storage = TileStorage::default();
+///     for x in 0..64 {
+///         storage.set(IVec2::new(x, 0), Tile::new(3));
+///     }
+///
+///     commands.spawn((
+///         Tilemap {
+///             tileset,
+///             tileset_size: UVec2::new(8, 8),
+///             tile_size: Vec2::splat(16.0),
+///         },
+///         storage,
+///     ));
+/// }
+///
+/// fn dig(mut tilemaps: Query<&mut TileStorage>) {
+///     for mut storage in &mut tilemaps {
+///         storage.remove(IVec2::new(10, 0));
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(dig);
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Debug, Clone)]
+#[require(TileStorage, TilemapChunks, Transform, Visibility)]
+pub struct Tilemap {
+    /// The tileset image, made of a grid of equally sized tiles.
+    pub tileset: Handle<Image>,
+    /// The number of columns and rows of tiles in the tileset.
+    ///
+    /// Tiles are indexed from left to right, then top to bottom.
+    pub tileset_size: UVec2,
+    /// The size of a tile in world units.
+    pub tile_size: Vec2,
+}
+
+/// A tile of a [`Tilemap`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub struct Tile {
+    /// The index of the tile in the tileset.
+    pub index: u32,
+    /// Flips the tile horizontally.
+    pub flip_x: bool,
+    /// Flips the tile vertically.
+    pub flip_y: bool,
+    /// Flips the tile along its top-left to bottom-right diagonal, before any other flip.
+    ///
+    /// Combined with the other flips, this allows rotating tiles by multiples of 90 degrees.
+    pub flip_diagonal: bool,
+    /// Animates the tile through consecutive tiles of the tileset.
+    pub animation: Option<TileAnimation>,
+}
+
+impl Tile {
+    /// Creates a tile showing the tile at `index` in the tileset.
+    pub const fn new(index: u32) -> Self {
+        Self {
+            index,
+            flip_x: false,
+            flip_y: false,
+            flip_diagonal: false,
+            animation: None,
+        }
+    }
+
+    /// Returns this tile animated with `animation`.
+    pub const fn with_animation(mut self, animation: TileAnimation) -> Self {
+        self.animation = Some(animation);
+        self
+    }
+
+    fn flags(&self) -> u32 {
+        self.flip_x as u32 | (self.flip_y as u32) << 1 | (self.flip_diagonal as u32) << 2
+    }
+}
+
+/// An animation cycling a [`Tile`] through consecutive tiles of the tileset, starting with
+/// [`Tile::index`].
+///
+/// Animations are evaluated on the GPU, so animated tiles don't cause their chunk to be rebuilt.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq, Clone)]
+pub struct TileAnimation {
+    /// The number of frames of the animation.
+    pub frames: u32,
+    /// The number of frames shown per second.
+    pub fps: f32,
+}
+
+/// The tiles of a [`Tilemap`], stored in chunks.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+pub struct TileStorage {
+    chunk_size: UVec2,
+    chunks: HashMap<IVec2, TileChunk>,
+    // Chunks are all rebuilt when the tilemap is added, so this doesn't need to be cloned with reflection.
+    #[reflect(ignore)]
+    changed_chunks: HashSet<IVec2>,
+}
+
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Debug, Clone)]
+struct TileChunk {
+    tiles: Vec<Option<Tile>>,
+    len: usize,
+}
+
+impl Default for TileStorage {
+    fn default() -> Self {
+        Self::new(UVec2::splat(32))
+    }
+}
+
+impl TileStorage {
+    /// Creates an empty storage grouping tiles in chunks of `chunk_size` tiles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either dimension of `chunk_size` is zero.
+    pub fn new(chunk_size: UVec2) -> Self {
+        assert!(
+            chunk_size.x > 0 && chunk_size.y > 0,
+            "tilemap chunks must not be empty"
+        );
+        Self {
+            chunk_size,
+            chunks: HashMap::default(),
+            changed_chunks: HashSet::default(),
+        }
+    }
+
+    /// Returns the number of columns and rows of tiles in a chunk.
+    pub fn chunk_size(&self) -> UVec2 {
+        self.chunk_size
+    }
+
+    /// Returns the coordinates of the chunk containing the tile at `position`.
+    pub fn chunk_coord(&self, position: IVec2) -> IVec2 {
+        position.div_euclid(self.chunk_size.as_ivec2())
+    }
+
+    fn locate(&self, position: IVec2) -> (IVec2, usize) {
+        let chunk_size = self.chunk_size.as_ivec2();
+        let local = position.rem_euclid(chunk_size);
+        (
+            position.div_euclid(chunk_size),
+            (local.y * chunk_size.x + local.x) as usize,
+        )
+    }
+
+    /// Returns the tile at `position`, if any.
+    pub fn get(&self, position: IVec2) -> Option<&Tile> {
+        let (coord, index) = self.locate(position);
+        self.chunks.get(&coord)?.tiles[index].as_ref()
+    }
+
+    /// Sets the tile at `position`, returning the previous tile.
+    pub fn set(&mut self, position: IVec2, tile: Tile) -> Option<Tile> {
+        let (coord, index) = self.locate(position);
+        let tiles_per_chunk = self.chunk_size.element_product() as usize;
+        let chunk = self.chunks.entry(coord).or_insert_with(|| TileChunk {
+            tiles: vec![None; tiles_per_chunk],
+            len: 0,
+        });
+        let previous = chunk.tiles[index].replace(tile);
+        if previous != Some(tile) {
+            if previous.is_none() {
+                chunk.len += 1;
+            }
+            self.changed_chunks.insert(coord);
+        }
+        previous
+    }
+
+    /// Removes the tile at `position`, returning it.
+    pub fn remove(&mut self, position: IVec2) -> Option<Tile> {
+        let (coord, index) = self.locate(position);
+        let chunk = self.chunks.get_mut(&coord)?;
+        let previous = chunk.tiles[index].take()?;
+        chunk.len -= 1;
+        if chunk.len == 0 {
+            self.chunks.remove(&coord);
+        }
+        self.changed_chunks.insert(coord);
+        Some(previous)
+    }
+
+    /// Removes all tiles.
+    pub fn clear(&mut self) {
+        self.changed_chunks
+            .extend(self.chunks.drain().map(|(coord, _)| coord));
+    }
+
+    /// Returns the number of tiles.
+    pub fn len(&self) -> usize {
+        self.chunks.values().map(|chunk| chunk.len).sum()
+    }
+
+    /// Returns `true` if there are no tiles.
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Iterates over all tiles and their positions, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &Tile)> + '_ {
+        let chunk_size = self.chunk_size.as_ivec2();
+        self.chunks.iter().flat_map(move |(coord, chunk)| {
+            chunk
+                .tiles
+                .iter()
+                .enumerate()
+                .filter_map(move |(index, tile)| {
+                    let local =
+                        IVec2::new(index as i32 % chunk_size.x, index as i32 / chunk_size.x);
+                    Some((coord * chunk_size + local, tile.as_ref()?))
+                })
+        })
+    }
+
+    fn build_chunk_mesh(&self, coord: IVec2, tile_size: Vec2) -> Option<Mesh> {
+        let chunk = self.chunks.get(&coord)?;
+        let mut positions = Vec::with_capacity(chunk.len * 4);
+        let mut uvs = Vec::with_capacity(chunk.len * 4);
+        let mut tiles = Vec::with_capacity(chunk.len * 4);
+        let mut indices = Vec::with_capacity(chunk.len * 6);
+
+        for (index, tile) in chunk.tiles.iter().enumerate() {
+            let Some(tile) = tile else {
+                continue;
+            };
+            let cell = UVec2::new(
+                index as u32 % self.chunk_size.x,
+                index as u32 / self.chunk_size.x,
+            );
+            let min = cell.as_vec2() * tile_size;
+            let max = min + tile_size;
+
+            let base = positions.len() as u32;
+            positions.extend([
+                [min.x, min.y, 0.0],
+                [max.x, min.y, 0.0],
+                [max.x, max.y, 0.0],
+                [min.x, max.y, 0.0],
+            ]);
+            uvs.extend([[0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]);
+            let (frames, fps) = tile
+                .animation
+                .map_or((1, 0.0), |animation| (animation.frames, animation.fps));
+            tiles.extend([[tile.index, tile.flags(), frames, f32::to_bits(fps)]; 4]);
+            indices.extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+        }
+
+        Some(
+            Mesh::new(
+                PrimitiveTopology::TriangleList,
+                RenderAssetUsages::RENDER_WORLD,
+            )
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+            .with_inserted_attribute(ATTRIBUTE_TILE, tiles)
+            .with_inserted_indices(Indices::U32(indices)),
+        )
+    }
+}
+
+/// A chunk of a [`Tilemap`], spawned as a child of the tilemap to render the tiles of the chunk.
+///
+/// Chunk entities are managed by the [`TilemapPlugin`], and shouldn't be spawned manually.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Debug, Clone)]
+pub struct TilemapChunk {
+    /// The coordinates of the chunk, in chunks.
+    pub coord: IVec2,
+}
+
+/// The chunk entities and material of a [`Tilemap`].
+#[derive(Component, Default, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct TilemapChunks {
+    #[reflect(ignore)]
+    entities: HashMap<IVec2, Entity>,
+    #[reflect(ignore)]
+    material: Option<Handle<TilemapMaterial>>,
+}
+
+impl TilemapChunks {
+    /// Returns the entity rendering the chunk at `coord`, if it contains any tile.
+    pub fn get(&self, coord: IVec2) -> Option<Entity> {
+        self.entities.get(&coord).copied()
+    }
+}
+
+/// Spawns, rebuilds and despawns the chunk entities of the [`Tilemap`]s whose tiles changed.
+pub fn sync_tilemap_chunks(
+    mut commands: Commands,
+    mut tilemaps: Query<(Entity, Ref<Tilemap>, &mut TileStorage, &mut TilemapChunks)>,
+    mut chunk_query: Query<(&Mesh2d, &mut Transform, &mut Aabb), With<TilemapChunk>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<TilemapMaterial>>,
+) {
+    for (entity, tilemap, mut storage, mut chunks) in &mut tilemaps {
+        if !tilemap.is_changed() && storage.changed_chunks.is_empty() {
+            continue;
+        }
+
+        let material = TilemapMaterial {
+            tileset_size: tilemap.tileset_size,
+            tileset: tilemap.tileset.clone(),
+        };
+        let material = match chunks.material.clone() {
+            Some(handle) => {
+                if tilemap.is_changed() {
+                    materials.insert(&handle, material);
+                }
+                handle
+            }
+            None => {
+                let handle = materials.add(material);
+                chunks.material = Some(handle.clone());
+                handle
+            }
+        };
+
+        let storage = storage.bypass_change_detection();
+        let mut changed = core::mem::take(&mut storage.changed_chunks);
+        // The tile size affects every chunk.
+        if tilemap.is_changed() {
+            changed.extend(storage.chunks.keys().copied());
+        }
+
+        let chunk_extent = storage.chunk_size.as_vec2() * tilemap.tile_size;
+        for coord in changed {
+            let Some(mesh) = storage.build_chunk_mesh(coord, tilemap.tile_size) else {
+                if let Some(chunk_entity) = chunks.entities.remove(&coord) {
+                    commands.entity(chunk_entity).despawn();
+                }
+                continue;
+            };
+
+            let transform =
+                Transform::from_translation((coord.as_vec2() * chunk_extent).extend(0.0));
+            let aabb = Aabb::from_min_max(Vec3::ZERO, chunk_extent.extend(0.0));
+            match chunks.entities.get(&coord) {
+                Some(&chunk_entity) => {
+                    if let Ok((Mesh2d(handle), mut chunk_transform, mut chunk_aabb)) =
+                        chunk_query.get_mut(chunk_entity)
+                    {
+                        meshes.insert(handle, mesh);
+                        *chunk_transform = transform;
+                        *chunk_aabb = aabb;
+                    }
+                }
+                None => {
+                    let chunk_entity = commands
+                        .spawn((
+                            TilemapChunk { coord },
+                            Mesh2d(meshes.add(mesh)),
+                            MeshMaterial2d(material.clone()),
+                            transform,
+                            aabb,
+                            ChildOf { parent: entity },
+                        ))
+                        .id();
+                    chunks.entities.insert(coord, chunk_entity);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn storage_tracks_changed_chunks() {
+        let mut storage = TileStorage::new(UVec2::new(4, 2));
+        assert_eq!(storage.set(IVec2::new(-1, 0), Tile::new(1)), None);
+        assert_eq!(storage.set(IVec2::new(5, 3), Tile::new(2)), None);
+        assert_eq!(storage.chunk_coord(IVec2::new(-1, 0)), IVec2::new(-1, 0));
+        assert_eq!(storage.chunk_coord(IVec2::new(5, 3)), IVec2::new(1, 1));
+        assert_eq!(storage.get(IVec2::new(5, 3)), Some(&Tile::new(2)));
+        assert_eq!(storage.get(IVec2::new(4, 3)), None);
+        assert_eq!(storage.len(), 2);
+        assert_eq!(storage.changed_chunks.len(), 2);
+
+        storage.changed_chunks.clear();
+        storage.set(IVec2::new(5, 3), Tile::new(2));
+        assert!(storage.changed_chunks.is_empty());
+
+        assert_eq!(storage.remove(IVec2::new(-1, 0)), Some(Tile::new(1)));
+        assert!(storage.changed_chunks.contains(&IVec2::new(-1, 0)));
+        assert!(storage
+            .build_chunk_mesh(IVec2::new(-1, 0), Vec2::ONE)
+            .is_none());
+
+        let mesh = storage
+            .build_chunk_mesh(IVec2::new(1, 1), Vec2::ONE)
+            .unwrap();
+        assert_eq!(mesh.count_vertices(), 4);
+
+        let mut tiles: Vec<_> = storage.iter().collect();
+        tiles.sort_by_key(|(position, _)| (position.x, position.y));
+        assert_eq!(tiles, [(IVec2::new(5, 3), &Tile::new(2))]);
+    }
+}
@@ -0,0 +1,72 @@
+#import bevy_sprite::{
+    mesh2d_functions as mesh_functions,
+    mesh2d_view_bindings::{globals, view},
+}
+
+#ifdef TONEMAP_IN_SHADER
+#import bevy_core_pipeline::tonemapping
+#endif
+
+const TILE_FLIP_X: u32 = 1u;
+const TILE_FLIP_Y: u32 = 2u;
+const TILE_FLIP_DIAGONAL: u32 = 4u;
+
+@group(2) @binding(0) var<uniform> tileset_size: vec2<u32>;
+@group(2) @binding(1) var tileset_texture: texture_2d<f32>;
+@group(2) @binding(2) var tileset_sampler: sampler;
+
+struct Vertex {
+    @builtin(instance_index) instance_index: u32,
+    @location(0) position: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+    // x: tileset index, y: flip flags, z: animation frame count, w: animation frames per second (f32 bits)
+    @location(2) tile: vec4<u32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+    let world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+    let world_position = mesh_functions::mesh2d_position_local_to_world(
+        world_from_local,
+        vec4<f32>(vertex.position, 1.0)
+    );
+    out.position = mesh_functions::mesh2d_position_world_to_clip(world_position);
+
+    var index = vertex.tile.x;
+    let frames = vertex.tile.z;
+    if frames > 1u {
+        let fps = bitcast<f32>(vertex.tile.w);
+        index += u32(floor(globals.time * fps)) % frames;
+    }
+
+    var uv = vertex.uv;
+    let flags = vertex.tile.y;
+    if (flags & TILE_FLIP_DIAGONAL) != 0u {
+        uv = uv.yx;
+    }
+    if (flags & TILE_FLIP_X) != 0u {
+        uv.x = 1.0 - uv.x;
+    }
+    if (flags & TILE_FLIP_Y) != 0u {
+        uv.y = 1.0 - uv.y;
+    }
+
+    let cell = vec2<f32>(f32(index % tileset_size.x), f32(index / tileset_size.x));
+    out.uv = (cell + uv) / vec2<f32>(tileset_size);
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    var color = textureSample(tileset_texture, tileset_sampler, in.uv);
+#ifdef TONEMAP_IN_SHADER
+    color = tonemapping::tone_mapping(color, view.color_grading);
+#endif
+    return color;
+}
@@ -28,6 +28,9 @@
 //! - A [`StateTransitionEvent<S>`](crate::state::StateTransitionEvent) that gets fired when a given state changes.
 //! - The [`in_state<S>`](crate::condition::in_state) and [`state_changed<S>`](crate::condition::state_changed) run conditions - which are used
 //!   to determine whether a system should run based on the current state.
+//!
+//! For finer-grained behavior, such as the AI of each enemy, the [`StateMachine`](crate::state_machine::StateMachine)
+//! component drives a state machine per entity, whose states are components.
 
 #![cfg_attr(
     any(docsrs, docsrs_dep),
@@ -56,6 +59,10 @@ pub mod condition;
 /// Provides definitions for the basic traits required by the state system
 pub mod state;
 
+/// Provides [`StateMachine`](crate::state_machine::StateMachine), a state machine driving the
+/// behavior of a single entity with states stored as components.
+pub mod state_machine;
+
 /// Provides [`StateScoped`](crate::state_scoped::StateScoped) and
 /// [`clear_state_scoped_entities`](crate::state_scoped::clear_state_scoped_entities) for managing lifetime of entities.
 pub mod state_scoped;
@@ -89,6 +96,7 @@ pub mod prelude {
             OnExit, OnTransition, State, StateSet, StateTransition, StateTransitionEvent, States,
             SubStates, TransitionSchedules,
         },
+        state_machine::{EntityStateChanged, StateMachine},
         state_scoped::StateScoped,
     };
 }
@@ -8,6 +8,7 @@ use bevy_ecs::{
 };
 
 use super::{resources::State, states::States};
+use crate::state_machine::run_state_machines;
 
 /// The label of a [`Schedule`] that **only** runs whenever [`State<S>`] enters the provided state.
 ///
@@ -198,6 +199,8 @@ pub fn setup_state_transitions_in_world(world: &mut World) {
         )
             .chain(),
     );
+    // Entity state machines run once the app-wide states are up to date.
+    schedule.add_systems(run_state_machines.after(StateTransitionSteps::EnterSchedules));
     schedules.insert(schedule);
 }
 
@@ -0,0 +1,536 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    any::{type_name, TypeId},
+    fmt,
+};
+
+use bevy_ecs::{
+    component::{Component, HookContext},
+    entity::Entity,
+    event::Event,
+    observer::{Observer, Trigger},
+    query::{QueryState, With},
+    schedule::{BoxedCondition, Condition},
+    system::{Commands, EntityCommand, In, IntoSystem, Query},
+    world::{DeferredWorld, EntityWorldMut, World},
+};
+use log::info;
+
+/// A state machine driving the behavior of a single entity, such as the AI of an enemy.
+///
+/// Each state is a component: the entity has exactly one of them at a time, which is removed when
+/// the state is exited, and inserted when a state is entered. This means that:
+/// - systems can run only for entities in a state by filtering on its component, such as
+///   `Query<&mut Transform, With<Chasing>>`;
+/// - states can store their own data, reset every time they are entered;
+/// - enter and exit logic can be written as [`OnAdd`](bevy_ecs::world::OnAdd) and
+///   [`OnRemove`](bevy_ecs::world::OnRemove) observers or component hooks of the state.
+///
+/// Transitions are declared when building the state machine, and are either:
+/// - checked each frame with a [`Condition`] taking the entity as input, with
+///   [`transition`](Self::transition) and [`transition_from_any`](Self::transition_from_any),
+/// - or applied when an [`Event`] is triggered for the entity, with
+///   [`transition_on`](Self::transition_on).
+///
+/// The state can also be changed manually with [`set`](Self::set), or with the [`set_state`]
+/// command. An [`EntityStateChanged`] event is triggered for the entity after each transition.
+///
+/// State machines are run by [`run_state_machines`], in the
+/// [`StateTransition`](crate::state::StateTransition) schedule, after the app-wide states changed.
+///
+/// ```
+/// use bevy_ecs::prelude::*;
+/// use bevy_state::prelude::*;
+///
+/// #[derive(Component, Clone)]
+/// struct Idle;
+///
+/// #[derive(Component, Clone)]
+/// struct Chasing;
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// #[derive(Event)]
+/// struct Stunned;
+///
+/// fn player_nearby(In(_enemy): In<Entity>, players: Query<(), With<Player>>) -> bool {
+///     // Check the distance between the enemy and the player here.
+///     !players.is_empty()
+/// }
+///
+/// fn spawn_enemy(mut commands: Commands) {
+///     commands.spawn(
+///         StateMachine::new(Idle)
+///             .transition::<Idle, _, _>(player_nearby, Chasing)
+///             .transition_on::<Stunned, Chasing, _>(Idle),
+///     );
+/// }
+///
+/// fn on_chase(trigger: Trigger<OnAdd, Chasing>) {
+///     println!("{} started chasing the player", trigger.target());
+/// }
+/// # bevy_ecs::system::assert_is_system(spawn_enemy);
+/// ```
+#[derive(Component)]
+#[component(on_insert = add_event_transitions, on_replace = remove_event_transitions)]
+pub struct StateMachine {
+    current: Option<StateInfo>,
+    next: Option<(StateInfo, InsertState)>,
+    transitions: Vec<ConditionTransition>,
+    event_transitions: Vec<AddEventTransition>,
+    /// The observers applying the event transitions, while the state machine is on an entity.
+    event_observers: Vec<Entity>,
+    log_transitions: bool,
+}
+
+type InsertState = Box<dyn FnOnce(&mut EntityWorldMut) + Send + Sync>;
+
+/// Spawns the observer of an event transition for the given entity, returning the observer.
+type AddEventTransition = Box<dyn Fn(&mut Commands, Entity) -> Entity + Send + Sync>;
+
+/// The type of a state component.
+#[derive(Clone, Copy)]
+struct StateInfo {
+    type_id: TypeId,
+    name: &'static str,
+    remove: fn(&mut EntityWorldMut),
+}
+
+impl StateInfo {
+    fn of<S: Component>() -> Self {
+        Self {
+            type_id: TypeId::of::<S>(),
+            name: type_name::<S>(),
+            remove: |entity| {
+                entity.remove::<S>();
+            },
+        }
+    }
+}
+
+struct ConditionTransition {
+    from: Option<TypeId>,
+    to: StateInfo,
+    insert: Box<dyn Fn(&mut EntityWorldMut) + Send + Sync>,
+    condition: BoxedCondition<In<Entity>>,
+    initialized: bool,
+}
+
+impl StateMachine {
+    /// Creates a state machine entering the `initial` state the next time state machines run.
+    pub fn new<S: Component>(initial: S) -> Self {
+        let mut machine = Self {
+            current: None,
+            next: None,
+            transitions: Vec::new(),
+            event_transitions: Vec::new(),
+            event_observers: Vec::new(),
+            log_transitions: false,
+        };
+        machine.set(initial);
+        machine
+    }
+
+    /// Adds a transition from the `From` state to `to`, applied when `condition` returns `true`.
+    ///
+    /// The condition takes the entity of the state machine as input. Transitions are checked in
+    /// the order they were added, and at most one transition is applied each time state machines
+    /// run.
+    pub fn transition<From: Component, To: Component + Clone, M>(
+        mut self,
+        condition: impl Condition<M, In<Entity>>,
+        to: To,
+    ) -> Self {
+        self.add_transition(Some(TypeId::of::<From>()), condition, to);
+        self
+    }
+
+    /// Adds a transition from any state to `to`, applied when `condition` returns `true`.
+    ///
+    /// See [`transition`](Self::transition) for more details.
+    pub fn transition_from_any<To: Component + Clone, M>(
+        mut self,
+        condition: impl Condition<M, In<Entity>>,
+        to: To,
+    ) -> Self {
+        self.add_transition(None, condition, to);
+        self
+    }
+
+    fn add_transition<To: Component + Clone, M>(
+        &mut self,
+        from: Option<TypeId>,
+        condition: impl Condition<M, In<Entity>>,
+        to: To,
+    ) {
+        self.transitions.push(ConditionTransition {
+            from,
+            to: StateInfo::of::<To>(),
+            insert: Box::new(insert_cloned(to)),
+            condition: Box::new(IntoSystem::into_system(condition)),
+            initialized: false,
+        });
+    }
+
+    /// Adds a transition from the `From` state to `to`, applied when an `E` event is triggered
+    /// for the entity of the state machine.
+    ///
+    /// The transition is applied as soon as the commands of the observer are applied.
+    pub fn transition_on<E: Event, From: Component, To: Component + Clone>(
+        mut self,
+        to: To,
+    ) -> Self {
+        self.event_transitions
+            .push(Box::new(observe_transition::<E, From, To>(to)));
+        self
+    }
+
+    /// Logs every transition of this state machine, for debugging.
+    pub fn with_logging(mut self) -> Self {
+        self.log_transitions = true;
+        self
+    }
+
+    /// Sets the next state, entered the next time state machines run.
+    ///
+    /// Transitions aren't checked on the frame the state is set.
+    pub fn set<S: Component>(&mut self, state: S) {
+        self.next = Some((StateInfo::of::<S>(), Box::new(insert(state))));
+    }
+
+    /// Returns `true` if the current state is `S`.
+    pub fn is_in<S: Component>(&self) -> bool {
+        self.current
+            .is_some_and(|current| current.type_id == TypeId::of::<S>())
+    }
+
+    /// Returns the type name of the current state, if the initial state has been entered.
+    pub fn state_name(&self) -> Option<&'static str> {
+        self.current.map(|current| current.name)
+    }
+
+    /// Returns the type name of the state that will be entered the next time state machines run,
+    /// if any.
+    pub fn next_state_name(&self) -> Option<&'static str> {
+        self.next.as_ref().map(|(next, _)| next.name)
+    }
+}
+
+impl fmt::Debug for StateMachine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateMachine")
+            .field("state", &self.state_name())
+            .field("next_state", &self.next_state_name())
+            .field(
+                "transitions",
+                &self
+                    .transitions
+                    .iter()
+                    .map(|transition| transition.to.name)
+                    .collect::<Vec<_>>(),
+            )
+            .field("log_transitions", &self.log_transitions)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Triggered for an entity after its [`StateMachine`] changed state.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct EntityStateChanged {
+    /// The type name of the exited state, or `None` when entering the initial state.
+    pub exited: Option<&'static str>,
+    /// The type name of the entered state.
+    pub entered: &'static str,
+}
+
+/// Returns an [`EntityCommand`] changing the state of the [`StateMachine`] of an entity
+/// immediately.
+///
+/// Does nothing if the entity has no state machine.
+pub fn set_state<S: Component>(state: S) -> impl EntityCommand {
+    move |mut entity: EntityWorldMut| {
+        enter_state(&mut entity, StateInfo::of::<S>(), insert(state));
+    }
+}
+
+fn insert<S: Component>(state: S) -> impl FnOnce(&mut EntityWorldMut) + Send + Sync {
+    move |entity| {
+        entity.insert(state);
+    }
+}
+
+fn insert_cloned<S: Component + Clone>(state: S) -> impl Fn(&mut EntityWorldMut) + Send + Sync {
+    move |entity| {
+        entity.insert(state.clone());
+    }
+}
+
+fn observe_transition<E: Event, From: Component, To: Component + Clone>(
+    to: To,
+) -> impl Fn(&mut Commands, Entity) -> Entity + Send + Sync {
+    move |commands, entity| {
+        let to = to.clone();
+        let observer = Observer::new(
+            move |trigger: Trigger<E>, machines: Query<&StateMachine>, mut commands: Commands| {
+                let entity = trigger.target();
+                if machines.get(entity).is_ok_and(StateMachine::is_in::<From>) {
+                    commands.entity(entity).queue(set_state(to.clone()));
+                }
+            },
+        );
+        commands.spawn(observer.with_entity(entity)).id()
+    }
+}
+
+fn enter_state(
+    entity: &mut EntityWorldMut,
+    next: StateInfo,
+    insert: impl FnOnce(&mut EntityWorldMut),
+) {
+    let Some(mut machine) = entity.get_mut::<StateMachine>() else {
+        return;
+    };
+    let exited = machine.current.replace(next);
+    let log_transitions = machine.log_transitions;
+
+    // Removing the previous state first lets it run its exit logic, even when entering the same
+    // state again.
+    if let Some(exited) = exited {
+        (exited.remove)(entity);
+    }
+    insert(entity);
+
+    let id = entity.id();
+    let event = EntityStateChanged {
+        exited: exited.map(|exited| exited.name),
+        entered: next.name,
+    };
+    if log_transitions {
+        info!(
+            "{id} changed state from {} to {}",
+            event.exited.unwrap_or("nothing"),
+            event.entered
+        );
+    }
+    entity.world_scope(|world| world.trigger_targets(event, id));
+}
+
+fn add_event_transitions(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let Some(mut machine) = world.get_mut::<StateMachine>(entity) else {
+        return;
+    };
+    let event_transitions = core::mem::take(&mut machine.event_transitions);
+    let mut commands = world.commands();
+    let event_observers = event_transitions
+        .iter()
+        .map(|add_observer| add_observer(&mut commands, entity))
+        .collect();
+    if let Some(mut machine) = world.get_mut::<StateMachine>(entity) {
+        machine.event_transitions = event_transitions;
+        machine.event_observers = event_observers;
+    }
+}
+
+fn remove_event_transitions(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    let Some(mut machine) = world.get_mut::<StateMachine>(entity) else {
+        return;
+    };
+    let event_observers = core::mem::take(&mut machine.event_observers);
+    let mut commands = world.commands();
+    for observer in event_observers {
+        // The observers are already despawned if the entity itself was despawned.
+        commands.entity(observer).try_despawn();
+    }
+}
+
+/// Applies the states set with [`StateMachine::set`], then the first transition of each
+/// [`StateMachine`] whose condition is met.
+pub fn run_state_machines(
+    world: &mut World,
+    machines: &mut QueryState<Entity, With<StateMachine>>,
+) {
+    let entities = machines.iter(world).collect::<Vec<_>>();
+    for entity in entities {
+        let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+            continue;
+        };
+        let Some(next) = entity_mut
+            .get_mut::<StateMachine>()
+            .map(|mut machine| machine.next.take())
+        else {
+            continue;
+        };
+        if let Some((next, insert)) = next {
+            enter_state(&mut entity_mut, next, insert);
+            continue;
+        }
+
+        let Some(mut machine) = entity_mut.get_mut::<StateMachine>() else {
+            continue;
+        };
+        let Some(current) = machine.current.map(|current| current.type_id) else {
+            continue;
+        };
+        let mut transitions = core::mem::take(&mut machine.transitions);
+
+        let applied = transitions.iter_mut().position(|transition| {
+            if transition.from.is_some_and(|from| from != current) {
+                return false;
+            }
+            if !transition.initialized {
+                transition.condition.initialize(world);
+                transition.initialized = true;
+            }
+            transition.condition.validate_param(world).is_ok()
+                && transition.condition.run_readonly(entity, world)
+        });
+
+        let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+            continue;
+        };
+        if let Some(index) = applied {
+            let transition = &transitions[index];
+            enter_state(&mut entity_mut, transition.to, |entity| {
+                (transition.insert)(entity);
+            });
+        }
+        if let Some(mut machine) = entity_mut.get_mut::<StateMachine>() {
+            machine.transitions = transitions;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        resource::Resource,
+        system::{Res, ResMut},
+        world::OnAdd,
+    };
+
+    #[derive(Component, Clone)]
+    struct Idle;
+
+    #[derive(Component, Clone)]
+    struct Chasing(u32);
+
+    #[derive(Resource, Default)]
+    struct PlayerNearby(bool);
+
+    #[derive(Resource, Default)]
+    struct Entered(Vec<&'static str>);
+
+    #[derive(Event)]
+    struct Stunned;
+
+    fn player_nearby(_: In<Entity>, nearby: Res<PlayerNearby>) -> bool {
+        nearby.0
+    }
+
+    fn setup() -> (World, QueryState<Entity, With<StateMachine>>, Entity) {
+        let mut world = World::new();
+        world.init_resource::<PlayerNearby>();
+        world.init_resource::<Entered>();
+        world.add_observer(
+            |trigger: Trigger<EntityStateChanged>, mut entered: ResMut<Entered>| {
+                entered.0.push(trigger.event().entered);
+            },
+        );
+        let entity = world
+            .spawn(
+                StateMachine::new(Idle)
+                    .transition::<Idle, _, _>(player_nearby, Chasing(3))
+                    .transition_on::<Stunned, Chasing, _>(Idle),
+            )
+            .id();
+        let machines = QueryState::new(&mut world);
+        (world, machines, entity)
+    }
+
+    #[test]
+    fn condition_transitions() {
+        let (mut world, mut machines, entity) = setup();
+        world.add_observer(|_: Trigger<OnAdd, Chasing>, mut entered: ResMut<Entered>| {
+            entered.0.push("on_add");
+        });
+
+        run_state_machines(&mut world, &mut machines);
+        assert!(world.entity(entity).contains::<Idle>());
+        let machine = world.get::<StateMachine>(entity).unwrap();
+        assert!(machine.is_in::<Idle>());
+        assert_eq!(machine.state_name(), Some(type_name::<Idle>()));
+
+        run_state_machines(&mut world, &mut machines);
+        assert!(world.entity(entity).contains::<Idle>());
+
+        world.resource_mut::<PlayerNearby>().0 = true;
+        run_state_machines(&mut world, &mut machines);
+        assert!(!world.entity(entity).contains::<Idle>());
+        assert_eq!(world.get::<Chasing>(entity).unwrap().0, 3);
+        assert_eq!(
+            world.resource::<Entered>().0,
+            [type_name::<Idle>(), "on_add", type_name::<Chasing>()]
+        );
+    }
+
+    #[test]
+    fn event_transitions() {
+        let (mut world, mut machines, entity) = setup();
+        run_state_machines(&mut world, &mut machines);
+
+        // The machine isn't chasing, so the event is ignored.
+        world.trigger_targets(Stunned, entity);
+        world.flush();
+        world
+            .entity_mut(entity)
+            .get_mut::<StateMachine>()
+            .unwrap()
+            .set(Chasing(1));
+        run_state_machines(&mut world, &mut machines);
+        assert!(world
+            .get::<StateMachine>(entity)
+            .unwrap()
+            .is_in::<Chasing>());
+
+        world.trigger_targets(Stunned, entity);
+        world.flush();
+        assert!(world.get::<StateMachine>(entity).unwrap().is_in::<Idle>());
+        assert!(!world.entity(entity).contains::<Chasing>());
+        assert_eq!(
+            world.resource::<Entered>().0,
+            [
+                type_name::<Idle>(),
+                type_name::<Chasing>(),
+                type_name::<Idle>()
+            ]
+        );
+    }
+
+    #[test]
+    fn event_transitions_follow_the_inserted_machine() {
+        let (mut world, mut machines, entity) = setup();
+        let stunned = |world: &mut World| {
+            world.trigger_targets(Stunned, entity);
+            world.flush();
+            world.get::<StateMachine>(entity).unwrap().is_in::<Idle>()
+        };
+
+        // Replacing the machine removes the transitions of the previous one.
+        world
+            .entity_mut(entity)
+            .insert(StateMachine::new(Chasing(0)));
+        run_state_machines(&mut world, &mut machines);
+        assert!(!stunned(&mut world));
+
+        // The transitions of a machine are kept when it's removed and inserted again.
+        world
+            .entity_mut(entity)
+            .insert(StateMachine::new(Chasing(0)).transition_on::<Stunned, Chasing, _>(Idle));
+        let machine = world.entity_mut(entity).take::<StateMachine>().unwrap();
+        world.entity_mut(entity).insert(machine);
+        run_state_machines(&mut world, &mut machines);
+        assert!(stunned(&mut world));
+    }
+}
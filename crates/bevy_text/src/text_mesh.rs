@@ -0,0 +1,497 @@
+use alloc::vec::Vec;
+
+use bevy_asset::RenderAssetUsages;
+use bevy_math::{Vec2, Vec3};
+use bevy_render::mesh::{Indices, Mesh, PrimitiveTopology};
+use cosmic_text::ttf_parser::{self, Face, GlyphId, OutlineBuilder};
+use lyon_tessellation::{
+    math::point,
+    path::{iterator::PathIterator, path::Builder, Path, PathEvent},
+    BuffersBuilder, FillOptions, FillRule, FillTessellator, FillVertex, VertexBuffers,
+};
+use tracing::warn;
+
+use crate::{Font, JustifyText};
+
+/// Walls meeting at a sharper angle than this, in radians, have a hard edge between them.
+const SMOOTH_ANGLE: f32 = 0.5;
+
+/// Builds extruded 3D text [`Mesh`]es from the glyph outlines of a [`Font`], for titles and
+/// in-world signage, to be used with any material.
+///
+/// The text is laid out on lines separated by `\n`, the baseline of the first line on the X axis,
+/// facing +Z, and extruded along Z, centered on the origin. The UVs are the X and Y positions
+/// divided by the [`font_size`](Self::font_size).
+///
+/// Glyphs are placed using their advance only: there is no shaping, so ligatures, kerning and
+/// complex scripts aren't supported. Missing glyphs are skipped.
+///
+/// ```
+/// # use bevy_asset::Assets;
+/// # use bevy_render::mesh::Mesh;
+/// # use bevy_text::{Font, TextBevel, TextMeshBuilder};
+/// # fn build(font: &Font, meshes: &mut Assets<Mesh>) {
+/// let mesh = TextMeshBuilder {
+///     depth: 0.3,
+///     bevel: Some(TextBevel {
+///         width: 0.02,
+///         depth: 0.02,
+///     }),
+///     ..Default::default()
+/// }
+/// .build(font, "Game Over")
+/// .unwrap();
+/// let handle = meshes.add(mesh);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextMeshBuilder {
+    /// The size of the font, the height of its em square, in world units.
+    ///
+    /// Defaults to `1.0`.
+    pub font_size: f32,
+    /// The thickness of the extruded text, bevels included.
+    ///
+    /// Defaults to `0.2`.
+    pub depth: f32,
+    /// The bevel around the front and back faces, or `None` for sharp edges.
+    ///
+    /// Defaults to `None`.
+    pub bevel: Option<TextBevel>,
+    /// The horizontal alignment of the lines, relative to the origin.
+    /// [`JustifyText::Justified`] is the same as [`JustifyText::Left`].
+    ///
+    /// Defaults to [`JustifyText::Left`].
+    pub justify: JustifyText,
+    /// The maximum distance between the glyph curves and their flattened segments, in world units.
+    ///
+    /// Defaults to `0.005`.
+    pub tolerance: f32,
+}
+
+impl Default for TextMeshBuilder {
+    fn default() -> Self {
+        Self {
+            font_size: 1.0,
+            depth: 0.2,
+            bevel: None,
+            justify: JustifyText::Left,
+            tolerance: 0.005,
+        }
+    }
+}
+
+/// A bevel around the faces of a [`TextMeshBuilder`] mesh.
+///
+/// The faces are inset by the [`width`](Self::width), which should stay smaller than half the
+/// thinnest stroke of the font.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextBevel {
+    /// How far the faces are inset from the outline, in world units.
+    pub width: f32,
+    /// How far the bevel goes along the extrusion, on each side, in world units.
+    pub depth: f32,
+}
+
+impl TextMeshBuilder {
+    /// Builds the mesh of `text`.
+    ///
+    /// Fails if the [`Font`] data can't be parsed.
+    pub fn build(&self, font: &Font, text: &str) -> Result<Mesh, ttf_parser::FaceParsingError> {
+        let face = Face::parse(&font.data, 0)?;
+        let scale = self.font_size / face.units_per_em() as f32;
+        let line_height =
+            (face.ascender() as f32 - face.descender() as f32 + face.line_gap() as f32) * scale;
+
+        let mut mesh = TextMeshData::default();
+        for (line_index, line) in text.lines().enumerate() {
+            let mut glyphs = Vec::new();
+            let mut advance = 0.0;
+            for glyph_id in line.chars().filter_map(|c| face.glyph_index(c)) {
+                glyphs.push((glyph_id, advance));
+                advance += face.glyph_hor_advance(glyph_id).unwrap_or(0) as f32 * scale;
+            }
+            let start = match self.justify {
+                JustifyText::Left | JustifyText::Justified => 0.0,
+                JustifyText::Center => -advance / 2.0,
+                JustifyText::Right => -advance,
+            };
+            let y = -(line_index as f32) * line_height;
+            for (glyph_id, x) in glyphs {
+                let offset = Vec2::new(start + x, y);
+                let contours = self.glyph_contours(&face, glyph_id, scale, offset);
+                self.add_glyph(&mut mesh, &contours);
+            }
+        }
+
+        let TextMeshData {
+            positions,
+            normals,
+            indices,
+        } = mesh;
+        let uvs: Vec<[f32; 2]> = positions
+            .iter()
+            .map(|position| [position[0] / self.font_size, position[1] / self.font_size])
+            .collect();
+        Ok(Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices)))
+    }
+
+    /// Returns the flattened contours of a glyph, without repeating their first point.
+    fn glyph_contours(
+        &self,
+        face: &Face,
+        glyph_id: GlyphId,
+        scale: f32,
+        offset: Vec2,
+    ) -> Vec<Vec<Vec2>> {
+        let mut builder = OutlinePathBuilder {
+            builder: Path::builder(),
+            scale,
+            offset,
+            is_open: false,
+        };
+        face.outline_glyph(glyph_id, &mut builder);
+        if builder.is_open {
+            builder.builder.end(true);
+        }
+        let path = builder.builder.build();
+
+        let mut contours = Vec::new();
+        let mut contour = Vec::new();
+        for event in path.iter().flattened(self.tolerance) {
+            match event {
+                PathEvent::Begin { at } => contour.push(Vec2::new(at.x, at.y)),
+                PathEvent::Line { to, .. } => contour.push(Vec2::new(to.x, to.y)),
+                PathEvent::End { .. } => {
+                    if contour.len() > 1 && contour.first() == contour.last() {
+                        contour.pop();
+                    }
+                    contour.dedup();
+                    if contour.len() >= 3 {
+                        contours.push(core::mem::take(&mut contour));
+                    }
+                    contour.clear();
+                }
+                // Curves are flattened.
+                PathEvent::Quadratic { .. } | PathEvent::Cubic { .. } => {}
+            }
+        }
+        contours
+    }
+
+    /// Adds the faces, bevels and walls of a glyph to the mesh.
+    fn add_glyph(&self, mesh: &mut TextMeshData, contours: &[Vec<Vec2>]) {
+        // Fonts fill either the left or the right side of their contours. The largest contour is
+        // an outer one, and tells which.
+        let Some(outer) = contours
+            .iter()
+            .map(|contour| signed_area(contour))
+            .max_by(|a, b| a.abs().total_cmp(&b.abs()))
+        else {
+            return;
+        };
+        let filled_left = outer > 0.0;
+
+        let half_depth = self.depth / 2.0;
+        let (face_contours, wall_depth) = match self.bevel {
+            Some(bevel) => (
+                contours
+                    .iter()
+                    .map(|contour| inset(contour, bevel.width, filled_left))
+                    .collect(),
+                half_depth - bevel.depth,
+            ),
+            None => (contours.to_vec(), half_depth),
+        };
+
+        mesh.add_faces(&face_contours, half_depth, self.tolerance);
+        for (contour, face_contour) in contours.iter().zip(&face_contours) {
+            if self.bevel.is_some() {
+                mesh.add_strip(face_contour, half_depth, contour, wall_depth, filled_left);
+                mesh.add_strip(contour, -wall_depth, face_contour, -half_depth, filled_left);
+            }
+            mesh.add_strip(contour, wall_depth, contour, -wall_depth, filled_left);
+        }
+    }
+}
+
+/// The attributes of a [`TextMeshBuilder`] mesh.
+#[derive(Default)]
+struct TextMeshData {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    indices: Vec<u32>,
+}
+
+impl TextMeshData {
+    /// Adds a triangle, with its vertices ordered so that it faces along `normal`.
+    fn add_triangle(&mut self, vertices: [(Vec3, Vec3); 3], normal: Vec3) {
+        let [a, mut b, mut c] = vertices;
+        if (b.0 - a.0).cross(c.0 - a.0).dot(normal) < 0.0 {
+            core::mem::swap(&mut b, &mut c);
+        }
+        for (position, normal) in [a, b, c] {
+            self.indices.push(self.positions.len() as u32);
+            self.positions.push(position.to_array());
+            self.normals.push(normal.to_array());
+        }
+    }
+
+    /// Adds the front face at `depth` and the back face at `-depth`.
+    fn add_faces(&mut self, contours: &[Vec<Vec2>], depth: f32, tolerance: f32) {
+        let mut builder = Path::builder();
+        for contour in contours {
+            builder.begin(point(contour[0].x, contour[0].y));
+            for vertex in &contour[1..] {
+                builder.line_to(point(vertex.x, vertex.y));
+            }
+            builder.end(true);
+        }
+        let path = builder.build();
+
+        let mut buffers: VertexBuffers<Vec2, u32> = VertexBuffers::new();
+        // Fonts use the non-zero rule, so that overlapping contours are filled once.
+        let options = FillOptions::tolerance(tolerance).with_fill_rule(FillRule::NonZero);
+        if let Err(err) = FillTessellator::new().tessellate_path(
+            &path,
+            &options,
+            &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| {
+                Vec2::new(vertex.position().x, vertex.position().y)
+            }),
+        ) {
+            warn!("Failed to tessellate the face of a glyph: {err:?}");
+            return;
+        }
+
+        for triangle in buffers.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| buffers.vertices[triangle[i] as usize]);
+            for (z, normal) in [(depth, Vec3::Z), (-depth, Vec3::NEG_Z)] {
+                let vertex = |v: Vec2| (v.extend(z), normal);
+                self.add_triangle([vertex(a), vertex(b), vertex(c)], normal);
+            }
+        }
+    }
+
+    /// Adds the band between the contour `a` at the depth `a_z` and the contour `b`, with the
+    /// same number of points, at the depth `b_z`, facing outward.
+    fn add_strip(&mut self, a: &[Vec2], a_z: f32, b: &[Vec2], b_z: f32, filled_left: bool) {
+        let len = a.len();
+        let segment = |i: usize| {
+            let j = (i + 1) % len;
+            [
+                a[i].extend(a_z),
+                a[j].extend(a_z),
+                b[j].extend(b_z),
+                b[i].extend(b_z),
+            ]
+        };
+        // Along the walls, the outward side is the one that isn't filled. Along the bevels, it
+        // also points toward the face.
+        let z_hint = (a_z + b_z).signum() * 0.5;
+        let normals: Vec<Vec3> = (0..len)
+            .map(|i| {
+                let [a0, a1, b1, b0] = segment(i);
+                let direction = (a1 - a0).truncate();
+                let outward = if filled_left {
+                    Vec2::new(direction.y, -direction.x)
+                } else {
+                    Vec2::new(-direction.y, direction.x)
+                };
+                let normal = (a1 - a0).cross(b0 - a0).normalize_or_zero();
+                let normal = if normal.dot(outward.normalize_or_zero().extend(z_hint)) < 0.0 {
+                    -normal
+                } else {
+                    normal
+                };
+                // Degenerate segments use the normal of the quad's other diagonal.
+                if normal == Vec3::ZERO {
+                    (b1 - b0).cross(a0 - b0).normalize_or_zero()
+                } else {
+                    normal
+                }
+            })
+            .collect();
+
+        // Walls are smooth across small angles, so that curves don't look faceted.
+        let smooth = |normal: Vec3, neighbor: Vec3| {
+            if normal.angle_between(neighbor) < SMOOTH_ANGLE {
+                (normal + neighbor).normalize_or_zero()
+            } else {
+                normal
+            }
+        };
+        for i in 0..len {
+            let normal = normals[i];
+            let start = smooth(normal, normals[(i + len - 1) % len]);
+            let end = smooth(normal, normals[(i + 1) % len]);
+            let [a0, a1, b1, b0] = segment(i);
+            self.add_triangle([(a0, start), (a1, end), (b1, end)], normal);
+            self.add_triangle([(a0, start), (b1, end), (b0, start)], normal);
+        }
+    }
+}
+
+/// Returns the area of a contour, positive if it turns counterclockwise.
+fn signed_area(contour: &[Vec2]) -> f32 {
+    let len = contour.len();
+    (0..len)
+        .map(|i| contour[i].perp_dot(contour[(i + 1) % len]))
+        .sum::<f32>()
+        / 2.0
+}
+
+/// Moves each point of a contour by `width` toward its filled side.
+fn inset(contour: &[Vec2], width: f32, filled_left: bool) -> Vec<Vec2> {
+    let len = contour.len();
+    let inward = |from: Vec2, to: Vec2| {
+        let direction = (to - from).normalize_or_zero();
+        if filled_left {
+            direction.perp()
+        } else {
+            -direction.perp()
+        }
+    };
+    (0..len)
+        .map(|i| {
+            let previous = contour[(i + len - 1) % len];
+            let point = contour[i];
+            let next = contour[(i + 1) % len];
+            let (before, after) = (inward(previous, point), inward(point, next));
+            let miter = (before + after).normalize_or_zero();
+            // Sharp corners are limited to twice the width.
+            point + miter * width / miter.dot(after).max(0.5)
+        })
+        .collect()
+}
+
+/// Builds a [`Path`] from the outline of a glyph, scaled and moved to its place in the text.
+struct OutlinePathBuilder {
+    builder: Builder,
+    scale: f32,
+    offset: Vec2,
+    is_open: bool,
+}
+
+impl OutlinePathBuilder {
+    fn point(&self, x: f32, y: f32) -> lyon_tessellation::math::Point {
+        let position = Vec2::new(x, y) * self.scale + self.offset;
+        point(position.x, position.y)
+    }
+}
+
+impl OutlineBuilder for OutlinePathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        if self.is_open {
+            self.builder.end(true);
+        }
+        let at = self.point(x, y);
+        self.builder.begin(at);
+        self.is_open = true;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let to = self.point(x, y);
+        self.builder.line_to(to);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (ctrl, to) = (self.point(x1, y1), self.point(x, y));
+        self.builder.quadratic_bezier_to(ctrl, to);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (ctrl1, ctrl2, to) = (self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+        self.builder.cubic_bezier_to(ctrl1, ctrl2, to);
+    }
+
+    fn close(&mut self) {
+        if self.is_open {
+            self.builder.end(true);
+            self.is_open = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::sync::Arc;
+    use bevy_render::mesh::VertexAttributeValues;
+
+    fn font() -> Font {
+        Font {
+            data: Arc::new(include_bytes!("FiraMono-subset.ttf").to_vec()),
+        }
+    }
+
+    fn positions_and_normals(mesh: &Mesh) -> (&[[f32; 3]], &[[f32; 3]]) {
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("missing positions");
+        };
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("missing normals");
+        };
+        (positions, normals)
+    }
+
+    #[test]
+    fn extruded_glyphs() {
+        let builder = TextMeshBuilder {
+            bevel: Some(TextBevel {
+                width: 0.01,
+                depth: 0.02,
+            }),
+            ..Default::default()
+        };
+        let mesh = builder.build(&font(), "Bo").unwrap();
+        let (positions, normals) = positions_and_normals(&mesh);
+        assert!(!positions.is_empty());
+
+        for triangle in positions.chunks_exact(3).zip(normals.chunks_exact(3)) {
+            let ([a, b, c], [na, nb, nc]) = triangle else {
+                unreachable!();
+            };
+            let [a, b, c] = [*a, *b, *c].map(Vec3::from_array);
+            for position in [a, b, c] {
+                assert!(position.z.abs() <= builder.depth / 2.0 + 1e-5);
+            }
+            // Triangles face the same way as their normals.
+            let face = (b - a).cross(c - a);
+            let normal = Vec3::from_array(*na) + Vec3::from_array(*nb) + Vec3::from_array(*nc);
+            assert!(face.dot(normal) >= 0.0);
+        }
+
+        // The glyphs are placed next to each other, above the baseline.
+        let max_x = positions.iter().map(|p| p[0]).fold(f32::MIN, f32::max);
+        let max_y = positions.iter().map(|p| p[1]).fold(f32::MIN, f32::max);
+        assert!(max_x > 0.6 && max_x < 1.3);
+        assert!(max_y > 0.5 && max_y < 1.0);
+    }
+
+    #[test]
+    fn justified_lines() {
+        let builder = TextMeshBuilder {
+            justify: JustifyText::Right,
+            ..Default::default()
+        };
+        let mesh = builder.build(&font(), "a\nb").unwrap();
+        let (positions, _) = positions_and_normals(&mesh);
+        assert!(positions.iter().all(|p| p[0] <= 1e-5));
+        // The second line is below the baseline.
+        assert!(positions.iter().any(|p| p[1] < -0.5));
+
+        let empty = builder.build(&font(), "").unwrap();
+        assert_eq!(positions_and_normals(&empty).0.len(), 0);
+    }
+}
@@ -0,0 +1,303 @@
+//! A virtual cursor driven by a gamepad, for navigating UI without a mouse.
+//!
+//! The [`GamepadCursor`] component turns its entity into a custom picking pointer. Each frame, its
+//! position is moved by a gamepad stick and [`PointerInput`] events are sent for it, so it hovers,
+//! presses and clicks UI nodes (or anything else handled by the picking backends) exactly like the
+//! mouse. When the stick is released, the cursor snaps to the center of the closest nearby
+//! [`Button`].
+//!
+//! If the cursor entity has a [`Node`], it is positioned at the cursor, so it can be used to draw
+//! the cursor, for example with an [`ImageNode`](crate::widget::ImageNode).
+
+use crate::{widget::Button, ComputedNode, ComputedNodeTarget, Node, PositionType, UiScale, Val};
+use bevy_app::prelude::*;
+use bevy_ecs::{component::HookContext, prelude::*, world::DeferredWorld};
+use bevy_input::gamepad::{Gamepad, GamepadButton};
+use bevy_math::Vec2;
+use bevy_picking::{
+    pointer::{Location, PointerAction, PointerButton, PointerId, PointerInput},
+    PickSet, Pickable,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::camera::{Camera, NormalizedRenderTarget, RenderTarget};
+use bevy_time::Time;
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{PrimaryWindow, Window, WindowRef};
+use uuid::Uuid;
+
+/// Adds support for [`GamepadCursor`]s.
+///
+/// This is included by default in [`UiPlugin`](crate::UiPlugin).
+#[derive(Default)]
+pub struct GamepadCursorPlugin;
+
+impl Plugin for GamepadCursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<(GamepadCursor, GamepadCursorStick)>()
+            .add_systems(First, gamepad_cursor_events.in_set(PickSet::Input));
+    }
+}
+
+/// The stick moving a [`GamepadCursor`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, Debug, PartialEq, Clone)]
+pub enum GamepadCursorStick {
+    /// The left stick.
+    #[default]
+    Left,
+    /// The right stick.
+    Right,
+}
+
+/// A virtual pointer moved by a gamepad stick, which sends [`PointerInput`] events like the mouse.
+///
+/// A [`PointerId::Custom`] is added to the entity when this component is inserted.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::{gamepad_cursor::GamepadCursor, prelude::*};
+/// # fn system(mut commands: Commands) {
+/// commands.spawn((
+///     GamepadCursor::default(),
+///     // Draw the cursor as a small square, whose top left corner is the hotspot.
+///     Node {
+///         width: Val::Px(8.0),
+///         height: Val::Px(8.0),
+///         ..Default::default()
+///     },
+///     BackgroundColor(Color::WHITE),
+///     GlobalZIndex(i32::MAX),
+/// ));
+/// # }
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug, Clone)]
+#[component(on_add = add_gamepad_cursor_pointer)]
+#[require(Pickable = Pickable::IGNORE)]
+pub struct GamepadCursor {
+    /// The gamepad entity controlling the cursor, or `None` to use any connected gamepad.
+    pub gamepad: Option<Entity>,
+    /// The stick moving the cursor.
+    pub stick: GamepadCursorStick,
+    /// The window the cursor is in.
+    pub window: WindowRef,
+    /// The position of the cursor, in logical pixels from the top left corner of the window.
+    pub position: Vec2,
+    /// The speed of the cursor when the stick is fully tilted, in logical pixels per second.
+    pub max_speed: f32,
+    /// The exponent of the response curve of the stick.
+    ///
+    /// The speed of the cursor is the tilt of the stick raised to this power. Values above `1.0`
+    /// give finer control at small tilts.
+    pub response_exponent: f32,
+    /// The number of seconds the stick has to be held before the cursor reaches its full speed.
+    ///
+    /// The cursor starts at a quarter of its speed, for precise adjustments with short flicks.
+    pub acceleration_time: f32,
+    /// Stick tilts below this value are ignored.
+    pub dead_zone: f32,
+    /// When the stick is released, the cursor snaps to the center of the closest [`Button`] within
+    /// this distance, in logical pixels. `0.0` disables snapping.
+    pub snap_distance: f32,
+    /// The button acting as the primary pointer button.
+    pub primary_button: GamepadButton,
+    /// The button acting as the secondary pointer button.
+    pub secondary_button: GamepadButton,
+    /// The number of seconds the stick has been held for.
+    held_time: f32,
+}
+
+impl Default for GamepadCursor {
+    fn default() -> Self {
+        Self {
+            gamepad: None,
+            stick: GamepadCursorStick::Left,
+            window: WindowRef::Primary,
+            position: Vec2::ZERO,
+            max_speed: 1200.0,
+            response_exponent: 2.0,
+            acceleration_time: 0.3,
+            dead_zone: 0.15,
+            snap_distance: 48.0,
+            primary_button: GamepadButton::South,
+            secondary_button: GamepadButton::East,
+            held_time: 0.0,
+        }
+    }
+}
+
+impl GamepadCursor {
+    /// Returns the velocity of the cursor for the given stick tilt, in logical pixels per second,
+    /// with the y axis pointing down.
+    pub fn velocity(&self, stick: Vec2) -> Vec2 {
+        let tilt = stick.length();
+        if tilt <= self.dead_zone {
+            return Vec2::ZERO;
+        }
+        let tilt = ((tilt - self.dead_zone) / (1.0 - self.dead_zone).max(f32::EPSILON)).min(1.0);
+        let acceleration = if self.acceleration_time > 0.0 {
+            0.25 + 0.75 * (self.held_time / self.acceleration_time).min(1.0)
+        } else {
+            1.0
+        };
+        let direction = Vec2::new(stick.x, -stick.y).normalize_or_zero();
+        direction * tilt.powf(self.response_exponent) * self.max_speed * acceleration
+    }
+}
+
+fn add_gamepad_cursor_pointer(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+    // Derived from the entity, so that every cursor has a distinct, stable id.
+    let id = Uuid::from_u64_pair(u64::from_le_bytes(*b"gamepadc"), entity.to_bits());
+    world
+        .commands()
+        .entity(entity)
+        .insert(PointerId::Custom(id));
+}
+
+/// Moves [`GamepadCursor`]s and sends their [`PointerInput`] events.
+pub fn gamepad_cursor_events(
+    mut cursors: Query<(&mut GamepadCursor, &PointerId, Option<&mut Node>)>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    windows: Query<&Window>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    cameras: Query<&Camera>,
+    buttons: Query<(&ComputedNode, &ComputedNodeTarget, &GlobalTransform), With<Button>>,
+    time: Res<Time>,
+    ui_scale: Res<UiScale>,
+    mut pointer_events: EventWriter<PointerInput>,
+) {
+    for (mut cursor, pointer_id, node) in &mut cursors {
+        let Some(target) =
+            RenderTarget::Window(cursor.window).normalize(primary_window.single().ok())
+        else {
+            continue;
+        };
+        let NormalizedRenderTarget::Window(window_ref) = &target else {
+            continue;
+        };
+        let Ok(window) = windows.get(window_ref.entity()) else {
+            continue;
+        };
+        let Some((_, gamepad)) = gamepads
+            .iter()
+            .find(|(entity, _)| cursor.gamepad.is_none_or(|gamepad| gamepad == *entity))
+        else {
+            continue;
+        };
+
+        let stick = match cursor.stick {
+            GamepadCursorStick::Left => gamepad.left_stick(),
+            GamepadCursorStick::Right => gamepad.right_stick(),
+        };
+        let velocity = cursor.velocity(stick);
+        let mut position = cursor.position;
+        if velocity != Vec2::ZERO {
+            cursor.held_time += time.delta_secs();
+            position += velocity * time.delta_secs();
+        } else {
+            cursor.held_time = 0.0;
+            if cursor.snap_distance > 0.0 {
+                let snap = buttons
+                    .iter()
+                    .filter_map(|(node, node_target, transform)| {
+                        let camera = cameras.get(node_target.camera()?).ok()?;
+                        if camera.target.normalize(primary_window.single().ok())? != target
+                            || node.is_empty()
+                        {
+                            return None;
+                        }
+                        let viewport_min = camera
+                            .physical_viewport_rect()
+                            .map(|viewport| viewport.min.as_vec2())
+                            .unwrap_or_default();
+                        let center = (transform.translation().truncate() + viewport_min)
+                            / camera.target_scaling_factor().unwrap_or(1.0);
+                        Some(center)
+                    })
+                    .map(|center| (center, center.distance_squared(position)))
+                    .filter(|(_, distance_squared)| {
+                        *distance_squared <= cursor.snap_distance * cursor.snap_distance
+                    })
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b));
+                if let Some((center, _)) = snap {
+                    position = center;
+                }
+            }
+        }
+        position = position.clamp(Vec2::ZERO, window.size());
+
+        let location = Location {
+            target: target.clone(),
+            position,
+        };
+        if position != cursor.position {
+            pointer_events.write(PointerInput::new(
+                *pointer_id,
+                location.clone(),
+                PointerAction::Move {
+                    delta: position - cursor.position,
+                },
+            ));
+            cursor.position = position;
+        }
+
+        for (gamepad_button, pointer_button) in [
+            (cursor.primary_button, PointerButton::Primary),
+            (cursor.secondary_button, PointerButton::Secondary),
+        ] {
+            if gamepad.just_pressed(gamepad_button) {
+                pointer_events.write(PointerInput::new(
+                    *pointer_id,
+                    location.clone(),
+                    PointerAction::Press(pointer_button),
+                ));
+            }
+            if gamepad.just_released(gamepad_button) {
+                pointer_events.write(PointerInput::new(
+                    *pointer_id,
+                    location.clone(),
+                    PointerAction::Release(pointer_button),
+                ));
+            }
+        }
+
+        if let Some(mut node) = node {
+            let left = Val::Px(position.x / ui_scale.0);
+            let top = Val::Px(position.y / ui_scale.0);
+            if node.position_type != PositionType::Absolute || node.left != left || node.top != top
+            {
+                node.position_type = PositionType::Absolute;
+                node.left = left;
+                node.top = top;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn velocity_curve() {
+        let mut cursor = GamepadCursor {
+            max_speed: 100.0,
+            response_exponent: 2.0,
+            acceleration_time: 1.0,
+            dead_zone: 0.0,
+            ..Default::default()
+        };
+
+        assert_eq!(cursor.velocity(Vec2::ZERO), Vec2::ZERO);
+        // Half tilt, squared, at a quarter of the speed before accelerating.
+        assert_eq!(cursor.velocity(Vec2::new(0.5, 0.0)), Vec2::new(6.25, 0.0));
+
+        cursor.held_time = 1.0;
+        assert_eq!(cursor.velocity(Vec2::new(0.5, 0.0)), Vec2::new(25.0, 0.0));
+        // Up on the stick is up on the screen.
+        assert_eq!(cursor.velocity(Vec2::new(0.0, 1.0)), Vec2::new(0.0, -100.0));
+
+        cursor.dead_zone = 0.2;
+        assert_eq!(cursor.velocity(Vec2::new(0.1, 0.0)), Vec2::ZERO);
+    }
+}
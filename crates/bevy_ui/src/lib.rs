@@ -15,6 +15,8 @@ pub mod ui_material;
 pub mod update;
 pub mod widget;
 
+#[cfg(feature = "bevy_ui_picking_backend")]
+pub mod gamepad_cursor;
 #[cfg(feature = "bevy_ui_picking_backend")]
 pub mod picking_backend;
 
@@ -186,7 +188,10 @@ impl Plugin for UiPlugin {
             );
 
         #[cfg(feature = "bevy_ui_picking_backend")]
-        app.add_plugins(picking_backend::UiPickingPlugin);
+        app.add_plugins((
+            picking_backend::UiPickingPlugin,
+            gamepad_cursor::GamepadCursorPlugin,
+        ));
 
         let ui_layout_system_config = ui_layout_system
             .in_set(UiSystem::Layout)
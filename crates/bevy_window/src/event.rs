@@ -329,6 +329,28 @@ pub struct WindowOccluded {
     pub occluded: bool,
 }
 
+/// An event that indicates a window has been minimized or restored.
+///
+/// It is sent by the window backend when it notices the change, usually when the window is resized
+/// or occluded, on the platforms able to tell whether a window is minimized.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct WindowMinimized {
+    /// Window that was minimized or restored.
+    pub window: Entity,
+    /// Whether it was minimized (true) or restored (false).
+    pub minimized: bool,
+}
+
 /// An event that indicates a window's scale factor has changed.
 #[derive(Event, Debug, Clone, PartialEq)]
 #[cfg_attr(
@@ -482,6 +504,23 @@ impl AppLifecycle {
     }
 }
 
+/// An event sent when the operating system is running low on memory.
+///
+/// Applications should free caches and other memory they can rebuild, or they may be terminated.
+/// This is only sent on Android and iOS.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct MemoryWarning;
+
 /// Wraps all `bevy_window` and `bevy_input` events in a common enum.
 ///
 /// Read these events with `EventReader<WindowEvent>` if you need to
@@ -507,12 +546,14 @@ pub enum WindowEvent {
     CursorMoved(CursorMoved),
     FileDragAndDrop(FileDragAndDrop),
     Ime(Ime),
+    MemoryWarning(MemoryWarning),
     RequestRedraw(RequestRedraw),
     WindowBackendScaleFactorChanged(WindowBackendScaleFactorChanged),
     WindowCloseRequested(WindowCloseRequested),
     WindowCreated(WindowCreated),
     WindowDestroyed(WindowDestroyed),
     WindowFocused(WindowFocused),
+    WindowMinimized(WindowMinimized),
     WindowMoved(WindowMoved),
     WindowOccluded(WindowOccluded),
     WindowResized(WindowResized),
@@ -539,6 +580,11 @@ impl From<AppLifecycle> for WindowEvent {
         Self::AppLifecycle(e)
     }
 }
+impl From<MemoryWarning> for WindowEvent {
+    fn from(e: MemoryWarning) -> Self {
+        Self::MemoryWarning(e)
+    }
+}
 impl From<CursorEntered> for WindowEvent {
     fn from(e: CursorEntered) -> Self {
         Self::CursorEntered(e)
@@ -599,6 +645,11 @@ impl From<WindowMoved> for WindowEvent {
         Self::WindowMoved(e)
     }
 }
+impl From<WindowMinimized> for WindowEvent {
+    fn from(e: WindowMinimized) -> Self {
+        Self::WindowMinimized(e)
+    }
+}
 impl From<WindowOccluded> for WindowEvent {
     fn from(e: WindowOccluded) -> Self {
         Self::WindowOccluded(e)
@@ -23,6 +23,7 @@ use bevy_platform_support::sync::Mutex;
 
 mod event;
 mod monitor;
+mod power;
 mod raw_handle;
 mod system;
 mod system_cursor;
@@ -35,6 +36,7 @@ pub use android_activity;
 
 pub use event::*;
 pub use monitor::*;
+pub use power::*;
 pub use system::*;
 pub use system_cursor::*;
 pub use window::*;
@@ -114,12 +116,16 @@ impl Plugin for WindowPlugin {
             .add_event::<Ime>()
             .add_event::<WindowFocused>()
             .add_event::<WindowOccluded>()
+            .add_event::<WindowMinimized>()
             .add_event::<WindowScaleFactorChanged>()
             .add_event::<WindowBackendScaleFactorChanged>()
             .add_event::<FileDragAndDrop>()
             .add_event::<WindowMoved>()
             .add_event::<WindowThemeChanged>()
-            .add_event::<AppLifecycle>();
+            .add_event::<AppLifecycle>()
+            .add_event::<MemoryWarning>()
+            .add_event::<PowerStateChanged>()
+            .init_resource::<PowerState>();
 
         if let Some(primary_window) = &self.primary_window {
             app.world_mut().spawn(primary_window.clone()).insert((
@@ -157,18 +163,22 @@ impl Plugin for WindowPlugin {
             .register_type::<CursorLeft>()
             .register_type::<WindowFocused>()
             .register_type::<WindowOccluded>()
+            .register_type::<WindowMinimized>()
             .register_type::<WindowScaleFactorChanged>()
             .register_type::<WindowBackendScaleFactorChanged>()
             .register_type::<FileDragAndDrop>()
             .register_type::<WindowMoved>()
             .register_type::<WindowThemeChanged>()
             .register_type::<AppLifecycle>()
+            .register_type::<MemoryWarning>()
+            .register_type::<PowerStateChanged>()
             .register_type::<Monitor>();
 
         // Register window descriptor and related types
         #[cfg(feature = "bevy_reflect")]
         app.register_type::<Window>()
-            .register_type::<PrimaryWindow>();
+            .register_type::<PrimaryWindow>()
+            .register_type::<PowerState>();
     }
 }
 
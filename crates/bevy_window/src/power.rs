@@ -0,0 +1,137 @@
+use bevy_ecs::{event::Event, resource::Resource};
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    bevy_ecs::prelude::ReflectResource,
+    bevy_reflect::{prelude::ReflectDefault, Reflect},
+};
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// The power state of the device the app is running on, such as its battery level and whether it
+/// is overheating.
+///
+/// This resource is updated by the windowing backend, where the platform reports it. Games can
+/// watch it to lower their resolution or disable expensive effects, either by checking
+/// [`should_reduce_quality`](Self::should_reduce_quality) or by reading [`PowerStateChanged`]
+/// events.
+///
+/// # Platform-specific
+///
+/// - **Linux** and **Android**: read from `/sys/class/power_supply` and `/sys/class/thermal`,
+///   when they are readable.
+/// - Other platforms: not reported, everything is left unknown.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Resource, Debug, PartialEq, Default, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PowerState {
+    /// The charge of the battery, between `0.0` and `1.0`, or `None` if the device has no battery
+    /// or it is unknown.
+    pub battery_level: Option<f32>,
+    /// Whether the battery is charging, or `None` if it is unknown.
+    pub charging: Option<bool>,
+    /// How hot the device is.
+    pub thermal_state: ThermalState,
+}
+
+impl PowerState {
+    /// The battery level below which [`should_reduce_quality`](Self::should_reduce_quality)
+    /// returns `true` when the battery isn't charging.
+    pub const LOW_BATTERY_LEVEL: f32 = 0.2;
+
+    /// Returns `true` if the app should lower its quality settings to save power or cool down the
+    /// device: when the device is [`ThermalState::Serious`] or worse, or when its battery is low
+    /// and not charging.
+    pub fn should_reduce_quality(&self) -> bool {
+        let low_battery = self
+            .battery_level
+            .is_some_and(|level| level < Self::LOW_BATTERY_LEVEL)
+            && self.charging != Some(true);
+        self.thermal_state >= ThermalState::Serious || low_battery
+    }
+}
+
+/// How hot a device is, and how much the platform is throttling it.
+///
+/// This follows the thermal states reported by mobile platforms, in increasing order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Hash, Default, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum ThermalState {
+    /// The thermal state isn't reported by the platform.
+    #[default]
+    Unknown,
+    /// The device is within its normal operating temperature.
+    Nominal,
+    /// The device is slightly hot.
+    Fair,
+    /// The device is hot, and is likely being throttled. The app should reduce its workload.
+    Serious,
+    /// The device is too hot, and is being heavily throttled. The app should reduce its workload
+    /// as much as it can.
+    Critical,
+}
+
+/// An event sent when the [`PowerState`] changes.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub struct PowerStateChanged {
+    /// The previous power state.
+    pub previous: PowerState,
+    /// The new power state.
+    pub current: PowerState,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_reduce_quality() {
+        assert!(!PowerState::default().should_reduce_quality());
+
+        let low_battery = PowerState {
+            battery_level: Some(0.1),
+            charging: Some(false),
+            thermal_state: ThermalState::Nominal,
+        };
+        assert!(low_battery.should_reduce_quality());
+        assert!(!PowerState {
+            charging: Some(true),
+            ..low_battery
+        }
+        .should_reduce_quality());
+
+        assert!(PowerState {
+            thermal_state: ThermalState::Serious,
+            ..Default::default()
+        }
+        .should_reduce_quality());
+    }
+}
@@ -1,10 +1,11 @@
-use alloc::{borrow::ToOwned, format, string::String};
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
 use core::num::NonZero;
 
 use bevy_ecs::{
     entity::{Entity, EntityBorrow},
     prelude::Component,
 };
+use bevy_input::keyboard::KeyCode;
 use bevy_math::{CompassOctant, DVec2, IVec2, UVec2, Vec2};
 use bevy_platform_support::sync::LazyLock;
 use log::warn;
@@ -255,6 +256,15 @@ pub struct Window {
     ///
     /// This value has no effect on non-web platforms.
     pub prevent_default_event_handling: bool,
+    /// Keys whose default browser behavior is prevented while the canvas has focus, even when
+    /// [`prevent_default_event_handling`](Self::prevent_default_event_handling) is `false`.
+    ///
+    /// This lets the app use some keys, like the arrow keys or space without scrolling the page,
+    /// while keeping the other browser hotkeys working.
+    ///
+    /// This value has no effect on non-web platforms, and can't be changed after the window is
+    /// created.
+    pub prevent_default_keys: Vec<KeyCode>,
     /// Stores internal state that isn't directly accessible.
     pub internal: InternalWindowState,
     /// Should the window use Input Method Editor?
@@ -468,6 +478,7 @@ impl Default for Window {
             window_level: Default::default(),
             fit_canvas_to_parent: false,
             prevent_default_event_handling: true,
+            prevent_default_keys: Vec::new(),
             canvas: None,
             window_theme: None,
             visible: true,
@@ -0,0 +1,145 @@
+//! Pauses the app while its windows are unfocused or minimized.
+
+use bevy_ecs::prelude::*;
+use bevy_platform_support::collections::HashSet;
+use bevy_time::{Time, Virtual};
+use bevy_window::{Window, WindowMinimized};
+use core::time::Duration;
+
+use crate::UpdateMode;
+
+/// Pauses the app while none of its windows are focused, or all of them are minimized, when
+/// inserted as a resource.
+///
+/// While paused, [`Time<Virtual>`] is paused, which stops [`FixedUpdate`](bevy_app::FixedUpdate)
+/// and everything else driven by the virtual clock, and the app updates with the
+/// [`paused_mode`](Self::paused_mode) instead of the [`WinitSettings`](crate::WinitSettings),
+/// throttling rendering. If the virtual clock was already paused, it is left paused on resume.
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// # use bevy_winit::AutoPause;
+/// App::new().insert_resource(AutoPause {
+///     when_unfocused: false,
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct AutoPause {
+    /// Whether the app is paused while none of its windows are focused.
+    ///
+    /// Defaults to `true`.
+    pub when_unfocused: bool,
+    /// Whether the app is paused while all of its windows are minimized.
+    ///
+    /// Defaults to `true`.
+    pub when_minimized: bool,
+    /// How frequently the app updates while paused.
+    ///
+    /// Defaults to [`UpdateMode::reactive_low_power`] with a wait of one second.
+    pub paused_mode: UpdateMode,
+}
+
+impl Default for AutoPause {
+    fn default() -> Self {
+        Self {
+            when_unfocused: true,
+            when_minimized: true,
+            paused_mode: UpdateMode::reactive_low_power(Duration::from_secs(1)),
+        }
+    }
+}
+
+/// State of the [`AutoPause`], read by the winit runner.
+#[derive(Resource, Default)]
+pub(crate) struct AutoPauseState {
+    /// The update mode while paused, or `None` if the app isn't paused.
+    pub(crate) paused_mode: Option<UpdateMode>,
+    /// Whether the virtual clock was paused by the [`AutoPause`], and has to be unpaused.
+    paused_time: bool,
+    minimized_windows: HashSet<Entity>,
+}
+
+/// Pauses or resumes the app according to the [`AutoPause`] resource.
+pub(crate) fn auto_pause(
+    auto_pause: Option<Res<AutoPause>>,
+    mut state: ResMut<AutoPauseState>,
+    mut minimized_events: EventReader<WindowMinimized>,
+    windows: Query<(Entity, &Window)>,
+    time: Option<ResMut<Time<Virtual>>>,
+) {
+    for event in minimized_events.read() {
+        if event.minimized {
+            state.minimized_windows.insert(event.window);
+        } else {
+            state.minimized_windows.remove(&event.window);
+        }
+    }
+
+    let pausing = auto_pause.filter(|auto_pause| {
+        !windows.is_empty()
+            && ((auto_pause.when_unfocused && windows.iter().all(|(_, window)| !window.focused))
+                || (auto_pause.when_minimized
+                    && windows
+                        .iter()
+                        .all(|(entity, _)| state.minimized_windows.contains(&entity))))
+    });
+    if pausing.is_some() == state.paused_mode.is_some() {
+        return;
+    }
+
+    state.paused_mode = pausing.map(|auto_pause| auto_pause.paused_mode);
+    let Some(mut time) = time else {
+        return;
+    };
+    if state.paused_mode.is_some() {
+        state.paused_time = !time.is_paused();
+        time.pause();
+    } else if core::mem::take(&mut state.paused_time) {
+        time.unpause();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::{App, Update};
+
+    #[test]
+    fn pauses_while_minimized() {
+        let mut app = App::new();
+        app.add_event::<WindowMinimized>()
+            .init_resource::<AutoPauseState>()
+            .init_resource::<Time<Virtual>>()
+            .insert_resource(AutoPause::default())
+            .add_systems(Update, auto_pause);
+        let window = app.world_mut().spawn(Window::default()).id();
+
+        app.update();
+        assert!(!app.world().resource::<Time<Virtual>>().is_paused());
+
+        app.world_mut().send_event(WindowMinimized {
+            window,
+            minimized: true,
+        });
+        app.update();
+        assert!(app.world().resource::<Time<Virtual>>().is_paused());
+        assert!(app
+            .world()
+            .resource::<AutoPauseState>()
+            .paused_mode
+            .is_some());
+
+        app.world_mut().send_event(WindowMinimized {
+            window,
+            minimized: false,
+        });
+        app.update();
+        assert!(!app.world().resource::<Time<Virtual>>().is_paused());
+        assert!(app
+            .world()
+            .resource::<AutoPauseState>()
+            .paused_mode
+            .is_none());
+    }
+}
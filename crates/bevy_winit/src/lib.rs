@@ -21,9 +21,12 @@ use bevy_window::{RawHandleWrapperHolder, WindowEvent};
 use core::marker::PhantomData;
 use winit::{event_loop::EventLoop, window::WindowId};
 
+pub use auto_pause::AutoPause;
+use auto_pause::{auto_pause, AutoPauseState};
 use bevy_a11y::AccessibilityRequested;
-use bevy_app::{App, Last, Plugin};
+use bevy_app::{App, First, Last, Plugin};
 use bevy_ecs::prelude::*;
+use bevy_time::TimeSystem;
 use bevy_window::{exit_on_all_closed, Window, WindowCreated};
 use system::{changed_windows, check_keyboard_focus_lost, despawn_windows};
 pub use system::{create_monitors, create_windows};
@@ -43,10 +46,13 @@ use crate::{
 };
 
 pub mod accessibility;
+mod auto_pause;
 mod converters;
 pub mod cursor;
 #[cfg(feature = "custom_cursor")]
 mod custom_cursor;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod power;
 mod state;
 mod system;
 mod winit_config;
@@ -127,8 +133,10 @@ impl<T: Event> Plugin for WinitPlugin<T> {
         app.init_non_send_resource::<WinitWindows>()
             .init_resource::<WinitMonitors>()
             .init_resource::<WinitSettings>()
+            .init_resource::<AutoPauseState>()
             .add_event::<RawWinitWindowEvent>()
             .set_runner(|app| winit_runner(app, event_loop))
+            .add_systems(First, auto_pause.before(TimeSystem))
             .add_systems(
                 Last,
                 (
@@ -141,6 +149,9 @@ impl<T: Event> Plugin for WinitPlugin<T> {
                     .chain(),
             );
 
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        app.add_systems(Last, power::update_power_state);
+
         app.add_plugins(AccessKitPlugin);
         app.add_plugins(cursor::CursorPlugin);
     }
@@ -0,0 +1,121 @@
+//! Reports the [`PowerState`] of the device from the Linux `sysfs`, which is also used by Android.
+
+use std::{fs, path::Path};
+
+use bevy_ecs::prelude::*;
+use bevy_platform_support::time::Instant;
+use bevy_window::{PowerState, PowerStateChanged, ThermalState};
+use core::time::Duration;
+
+/// How often the power state is read.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Temperatures this far below the first passive trip point, where the platform starts throttling,
+/// are reported as [`ThermalState::Fair`], in millidegrees Celsius.
+const FAIR_MARGIN: i64 = 10_000;
+
+/// Updates the [`PowerState`] resource, and sends [`PowerStateChanged`] events when it changes.
+pub(crate) fn update_power_state(
+    mut power_state: ResMut<PowerState>,
+    mut power_state_changed: EventWriter<PowerStateChanged>,
+    mut last_poll: Local<Option<Instant>>,
+) {
+    if last_poll.is_some_and(|last_poll| last_poll.elapsed() < POLL_INTERVAL) {
+        return;
+    }
+    *last_poll = Some(Instant::now());
+
+    let (battery_level, charging) = read_battery().unzip();
+    let current = PowerState {
+        battery_level,
+        charging,
+        thermal_state: read_thermal_state(),
+    };
+    if *power_state != current {
+        power_state_changed.write(PowerStateChanged {
+            previous: *power_state,
+            current,
+        });
+        *power_state = current;
+    }
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_owned())
+}
+
+/// Returns the level of the first battery and whether it is charging.
+fn read_battery() -> Option<(f32, bool)> {
+    fs::read_dir("/sys/class/power_supply")
+        .ok()?
+        .flatten()
+        .map(|supply| supply.path())
+        .filter(|supply| read_trimmed(&supply.join("type")).as_deref() == Some("Battery"))
+        .find_map(|battery| {
+            let capacity: f32 = read_trimmed(&battery.join("capacity"))?.parse().ok()?;
+            let status = read_trimmed(&battery.join("status")).unwrap_or_default();
+            Some((
+                (capacity / 100.0).clamp(0.0, 1.0),
+                status == "Charging" || status == "Full",
+            ))
+        })
+}
+
+/// Returns the hottest thermal state of the thermal zones, based on their trip points.
+fn read_thermal_state() -> ThermalState {
+    let Ok(zones) = fs::read_dir("/sys/class/thermal") else {
+        return ThermalState::Unknown;
+    };
+    zones
+        .flatten()
+        .map(|zone| zone.path())
+        .filter(|zone| {
+            zone.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("thermal_zone"))
+        })
+        .filter_map(|zone| zone_thermal_state(&zone))
+        .max()
+        .unwrap_or(ThermalState::Unknown)
+}
+
+fn zone_thermal_state(zone: &Path) -> Option<ThermalState> {
+    let temperature: i64 = read_trimmed(&zone.join("temp"))?.parse().ok()?;
+
+    let mut passive = None::<i64>;
+    let mut critical = None::<i64>;
+    for trip in 0.. {
+        let Some(kind) = read_trimmed(&zone.join(format!("trip_point_{trip}_type"))) else {
+            break;
+        };
+        let Some(trip_temperature) = read_trimmed(&zone.join(format!("trip_point_{trip}_temp")))
+            .and_then(|temperature| temperature.parse::<i64>().ok())
+        else {
+            continue;
+        };
+        let threshold = match kind.as_str() {
+            "passive" => &mut passive,
+            "hot" | "critical" => &mut critical,
+            _ => continue,
+        };
+        *threshold = Some(threshold.map_or(trip_temperature, |t| t.min(trip_temperature)));
+    }
+
+    // Zones without trip points don't say what temperatures are too hot.
+    if passive.is_none() && critical.is_none() {
+        return None;
+    }
+    Some(
+        if critical.is_some_and(|critical| temperature >= critical) {
+            ThermalState::Critical
+        } else if passive.is_some_and(|passive| temperature >= passive) {
+            ThermalState::Serious
+        } else if passive.is_some_and(|passive| temperature >= passive - FAIR_MARGIN) {
+            ThermalState::Fair
+        } else {
+            ThermalState::Nominal
+        },
+    )
+}
@@ -24,7 +24,7 @@ use bevy_math::URect;
 use bevy_math::{ivec2, DVec2, Vec2};
 #[cfg(feature = "custom_cursor")]
 use bevy_platform_support::collections::HashMap;
-use bevy_platform_support::time::Instant;
+use bevy_platform_support::{collections::HashSet, time::Instant};
 #[cfg(not(target_arch = "wasm32"))]
 use bevy_tasks::tick_global_task_pools_on_main_thread;
 use core::marker::PhantomData;
@@ -40,16 +40,17 @@ use winit::{
 };
 
 use bevy_window::{
-    AppLifecycle, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, Ime, RequestRedraw,
-    Window, WindowBackendScaleFactorChanged, WindowCloseRequested, WindowDestroyed,
-    WindowEvent as BevyWindowEvent, WindowFocused, WindowMoved, WindowOccluded, WindowResized,
-    WindowScaleFactorChanged, WindowThemeChanged,
+    AppLifecycle, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, Ime, MemoryWarning,
+    RequestRedraw, Window, WindowBackendScaleFactorChanged, WindowCloseRequested, WindowDestroyed,
+    WindowEvent as BevyWindowEvent, WindowFocused, WindowMinimized, WindowMoved, WindowOccluded,
+    WindowResized, WindowScaleFactorChanged, WindowThemeChanged,
 };
 #[cfg(target_os = "android")]
 use bevy_window::{PrimaryWindow, RawHandleWrapper};
 
 use crate::{
     accessibility::AccessKitAdapters,
+    auto_pause::AutoPauseState,
     converters, create_windows,
     system::{create_monitors, CachedWindow, WinitWindowPressedKeys},
     AppSendEvent, CreateMonitorParams, CreateWindowParams, EventLoopProxyWrapper,
@@ -88,6 +89,8 @@ struct WinitAppRunnerState<T: Event> {
     bevy_window_events: Vec<bevy_window::WindowEvent>,
     /// Raw Winit window events to send
     raw_winit_events: Vec<RawWinitWindowEvent>,
+    /// The windows known to be minimized, to send [`WindowMinimized`] events when it changes.
+    minimized_windows: HashSet<Entity>,
     _marker: PhantomData<T>,
 
     event_writer_system_state: SystemState<(
@@ -138,6 +141,7 @@ impl<T: Event> WinitAppRunnerState<T> {
             startup_forced_updates: 5,
             bevy_window_events: Vec::new(),
             raw_winit_events: Vec::new(),
+            minimized_windows: HashSet::default(),
             _marker: PhantomData,
             event_writer_system_state,
         }
@@ -288,6 +292,11 @@ impl<T: Event> ApplicationHandler<T> for WinitAppRunnerState<T> {
             }
         }
 
+        // Winit has no event for minimizing, which usually resizes or occludes the window.
+        let minimized = matches!(event, WindowEvent::Resized(_) | WindowEvent::Occluded(_))
+            .then(|| winit_windows.get_window(window)?.is_minimized())
+            .flatten();
+
         match event {
             WindowEvent::Resized(size) => {
                 react_to_resize(window, &mut win, size, &mut window_resized);
@@ -463,6 +472,18 @@ impl<T: Event> ApplicationHandler<T> for WinitAppRunnerState<T> {
             _ => {}
         }
 
+        if let Some(minimized) = minimized {
+            let changed = if minimized {
+                self.minimized_windows.insert(window)
+            } else {
+                self.minimized_windows.remove(&window)
+            };
+            if changed {
+                self.bevy_window_events
+                    .send(WindowMinimized { window, minimized });
+            }
+        }
+
         let mut windows = self.world_mut().query::<(&mut Window, &mut CachedWindow)>();
         if let Ok((window_component, mut cache)) = windows.get_mut(self.world_mut(), window) {
             if window_component.is_changed() {
@@ -528,6 +549,11 @@ impl<T: Event> ApplicationHandler<T> for WinitAppRunnerState<T> {
         self.lifecycle = AppLifecycle::WillSuspend;
     }
 
+    fn memory_warning(&mut self, _event_loop: &ActiveEventLoop) {
+        self.bevy_window_events.send(MemoryWarning);
+        self.redraw_requested = true;
+    }
+
     fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
         let world = self.world_mut();
         world.clear_all();
@@ -538,8 +564,11 @@ impl<T: Event> WinitAppRunnerState<T> {
     fn redraw_requested(&mut self, event_loop: &ActiveEventLoop) {
         let mut redraw_event_reader = EventCursor::<RequestRedraw>::default();
 
-        let mut focused_windows_state: SystemState<(Res<WinitSettings>, Query<(Entity, &Window)>)> =
-            SystemState::new(self.world_mut());
+        let mut focused_windows_state: SystemState<(
+            Res<WinitSettings>,
+            Res<AutoPauseState>,
+            Query<(Entity, &Window)>,
+        )> = SystemState::new(self.world_mut());
 
         if let Some(app_redraw_events) = self.world().get_resource::<Events<RequestRedraw>>() {
             if redraw_event_reader.read(app_redraw_events).last().is_some() {
@@ -547,10 +576,12 @@ impl<T: Event> WinitAppRunnerState<T> {
             }
         }
 
-        let (config, windows) = focused_windows_state.get(self.world());
+        let (config, auto_pause, windows) = focused_windows_state.get(self.world());
         let focused = windows.iter().any(|(_, window)| window.focused);
 
-        let mut update_mode = config.update_mode(focused);
+        let mut update_mode = auto_pause
+            .paused_mode
+            .unwrap_or_else(|| config.update_mode(focused));
         let mut should_update = self.should_update(update_mode);
 
         if self.startup_forced_updates > 0 {
@@ -635,7 +666,7 @@ impl<T: Event> WinitAppRunnerState<T> {
         let begin_frame_time = Instant::now();
 
         if should_update {
-            let (_, windows) = focused_windows_state.get(self.world());
+            let (_, _, windows) = focused_windows_state.get(self.world());
             // If no windows exist, this will evaluate to `true`.
             let all_invisible = windows.iter().all(|w| !w.1.visible);
 
@@ -656,9 +687,11 @@ impl<T: Event> WinitAppRunnerState<T> {
             }
 
             // Running the app may have changed the WinitSettings resource, so we have to re-extract it.
-            let (config, windows) = focused_windows_state.get(self.world());
+            let (config, auto_pause, windows) = focused_windows_state.get(self.world());
             let focused = windows.iter().any(|(_, window)| window.focused);
-            update_mode = config.update_mode(focused);
+            update_mode = auto_pause
+                .paused_mode
+                .unwrap_or_else(|| config.update_mode(focused));
         }
 
         // The update mode could have been changed, so we need to redraw and force an update
@@ -795,6 +828,9 @@ impl<T: Event> WinitAppRunnerState<T> {
                 BevyWindowEvent::Ime(e) => {
                     world.send_event(e);
                 }
+                BevyWindowEvent::MemoryWarning(e) => {
+                    world.send_event(e);
+                }
                 BevyWindowEvent::RequestRedraw(e) => {
                     world.send_event(e);
                 }
@@ -813,6 +849,9 @@ impl<T: Event> WinitAppRunnerState<T> {
                 BevyWindowEvent::WindowFocused(e) => {
                     world.send_event(e);
                 }
+                BevyWindowEvent::WindowMinimized(e) => {
+                    world.send_event(e);
+                }
                 BevyWindowEvent::WindowMoved(e) => {
                     world.send_event(e);
                 }
@@ -287,7 +287,8 @@ pub struct CachedWindow {
 ///
 /// - [`Window::present_mode`] and [`Window::composite_alpha_mode`] changes are handled by the `bevy_render` crate.
 /// - [`Window::transparent`] cannot be changed after the window is created.
-/// - [`Window::canvas`] cannot be changed after the window is created.
+/// - [`Window::canvas`] and [`Window::prevent_default_keys`] cannot be changed after the window is
+///   created.
 /// - [`Window::focused`] cannot be manually changed to `false` after the window is created.
 pub(crate) fn changed_windows(
     mut changed_windows: Query<(Entity, &mut Window, &mut CachedWindow), Changed<Window>>,
@@ -518,6 +519,16 @@ pub(crate) fn changed_windows(
             );
         }
 
+        #[cfg(target_arch = "wasm32")]
+        if window.prevent_default_keys != cache.window.prevent_default_keys {
+            window
+                .prevent_default_keys
+                .clone_from(&cache.window.prevent_default_keys);
+            warn!(
+                "Bevy currently doesn't support modifying the prevented default keys after initialization."
+            );
+        }
+
         if window.ime_enabled != cache.window.ime_enabled {
             winit_window.set_ime_allowed(window.ime_enabled);
         }
@@ -276,6 +276,36 @@ impl WinitWindows {
         }
 
         let winit_window = event_loop.create_window(winit_window_attributes).unwrap();
+
+        #[cfg(target_arch = "wasm32")]
+        if !window.prevent_default_event_handling && !window.prevent_default_keys.is_empty() {
+            use wasm_bindgen::{closure::Closure, JsCast};
+            use winit::platform::web::WindowExtWebSys;
+
+            // Bevy's key codes are named after the `KeyboardEvent.code` values of the web.
+            let codes: Vec<String> = window
+                .prevent_default_keys
+                .iter()
+                .map(|key_code| format!("{key_code:?}"))
+                .collect();
+            let prevent_default = Closure::<dyn FnMut(web_sys::KeyboardEvent)>::new(
+                move |event: web_sys::KeyboardEvent| {
+                    if codes.contains(&event.code()) {
+                        event.prevent_default();
+                    }
+                },
+            );
+            if let Some(canvas) = winit_window.canvas() {
+                for event in ["keydown", "keyup"] {
+                    let _ = canvas.add_event_listener_with_callback(
+                        event,
+                        prevent_default.as_ref().unchecked_ref(),
+                    );
+                }
+            }
+            // The listener lives as long as the canvas.
+            prevent_default.forget();
+        }
         let name = window.title.clone();
         prepare_accessibility_for_window(
             event_loop,
@@ -376,7 +376,8 @@ impl GetBatchData for StencilPipeline {
                 current_skin_index: u32::MAX,
                 material_and_lightmap_bind_group_slot: 0,
                 tag: 0,
-                pad: 0,
+                tint: u32::MAX,
+                emissive_intensity: 1.0,
             }
         };
         Some((mesh_uniform, None))